@@ -17,8 +17,12 @@
 //! Helpers for implementing various message-related runtime API mthods.
 
 use bp_messages::{
-	InboundMessageDetails, LaneId, MessageNonce, MessagePayload, OutboundMessageDetails,
+	target_chain::InboundLanesQos, InboundMessageDetails, LaneId, MessageDeliveryProofLimits,
+	MessageNonce, MessagePayload, OutboundMessageDetails,
 };
+use bp_runtime::{Chain, PreComputedSize, RangeInclusiveExt};
+use frame_support::{traits::Get, weights::Weight};
+use pallet_bridge_messages::{WeightInfoExt, SIGNED_EXTENSIONS_SIZE};
 use sp_std::vec::Vec;
 
 /// Implementation of the `To*OutboundLaneApi::message_details`.
@@ -64,3 +68,45 @@ where
 		})
 		.collect()
 }
+
+/// Implementation of the `From*MessageDeliveryProofSizeApi::delivery_proof_limits`.
+///
+/// The `ThisChain` is the chain, where the `Runtime` is deployed.
+pub fn message_delivery_proof_limits<Runtime, MessagesPalletInstance, ThisChain>(
+	lane: LaneId,
+	begin: MessageNonce,
+	end: MessageNonce,
+) -> Option<MessageDeliveryProofLimits>
+where
+	Runtime: pallet_bridge_messages::Config<MessagesPalletInstance>,
+	MessagesPalletInstance: 'static,
+	ThisChain: Chain,
+{
+	// the same limits are checked by the `receive_messages_proof` call and by the inbound lane
+	let lane_qos_limits =
+		Runtime::InboundLanesQos::class_limits(Runtime::InboundLanesQos::lane_class(&lane));
+	let mut max_messages_count = Runtime::MaxUnconfirmedMessagesAtInboundLane::get();
+	if let Some(ref lane_qos_limits) = lane_qos_limits {
+		max_messages_count = max_messages_count.min(lane_qos_limits.max_unconfirmed_messages);
+	}
+	let messages_count = (begin..=end).checked_len().unwrap_or(0);
+	if messages_count > max_messages_count {
+		return None
+	}
+
+	// the rest of the transaction (signed extensions and other call arguments) needs to fit
+	// into the `SIGNED_EXTENSIONS_SIZE`
+	let max_proof_size = ThisChain::max_extrinsic_size().saturating_sub(SIGNED_EXTENSIONS_SIZE);
+	let max_delivery_weight = Runtime::WeightInfo::receive_messages_proof_weight(
+		&PreComputedSize(max_proof_size as usize),
+		u32::try_from(messages_count).ok()?,
+		Weight::zero(),
+	);
+	let mut max_dispatch_weight =
+		ThisChain::max_extrinsic_weight().checked_sub(&max_delivery_weight)?;
+	if let Some(ref lane_qos_limits) = lane_qos_limits {
+		max_dispatch_weight = max_dispatch_weight.min(lane_qos_limits.max_dispatch_weight);
+	}
+
+	Some(MessageDeliveryProofLimits { max_proof_size, max_dispatch_weight })
+}
@@ -156,6 +156,14 @@ pub trait XcmBlobHauler {
 	/// congested.
 	type UncongestedMessage: Get<Option<Xcm<()>>>;
 
+	/// Maximal number of messages in the outbound bridge queue. Once we reach this limit, we
+	/// send a "congestion" XCM message to the sending chain.
+	const CONGESTED_THRESHOLD: MessageNonce = OUTBOUND_LANE_CONGESTED_THRESHOLD;
+	/// After we have sent "congestion" XCM message to the sending chain, we wait until number
+	/// of messages in the outbound bridge queue drops to this count, before sending
+	/// `uncongestion` XCM message.
+	const UNCONGESTED_THRESHOLD: MessageNonce = OUTBOUND_LANE_UNCONGESTED_THRESHOLD;
+
 	/// Returns `true` if we want to handle congestion.
 	fn supports_congestion_detection() -> bool {
 		Self::CongestedMessage::get().is_some() || Self::UncongestedMessage::get().is_some()
@@ -223,14 +231,13 @@ impl<H: XcmBlobHauler> OnMessagesDelivered for XcmBlobHaulerAdapter<H> {
 /// It needs to be used at the source bridge hub.
 pub struct LocalXcmQueueManager<H>(PhantomData<H>);
 
-/// Maximal number of messages in the outbound bridge queue. Once we reach this limit, we
-/// send a "congestion" XCM message to the sending chain.
-const OUTBOUND_LANE_CONGESTED_THRESHOLD: MessageNonce = 8_192;
+/// Default maximal number of messages in the outbound bridge queue. Once we reach this limit,
+/// we send a "congestion" XCM message to the sending chain.
+pub const OUTBOUND_LANE_CONGESTED_THRESHOLD: MessageNonce = 8_192;
 
-/// After we have sent "congestion" XCM message to the sending chain, we wait until number
-/// of messages in the outbound bridge queue drops to this count, before sending `uncongestion`
-/// XCM message.
-const OUTBOUND_LANE_UNCONGESTED_THRESHOLD: MessageNonce = 1_024;
+/// Default number of messages in the outbound bridge queue, that we wait for after we have sent
+/// "congestion" XCM message to the sending chain, before sending `uncongestion` XCM message.
+pub const OUTBOUND_LANE_UNCONGESTED_THRESHOLD: MessageNonce = 1_024;
 
 impl<H: XcmBlobHauler> LocalXcmQueueManager<H> {
 	/// Must be called whenever we push a message to the bridge lane.
@@ -249,7 +256,7 @@ impl<H: XcmBlobHauler> LocalXcmQueueManager<H> {
 		}
 
 		// if the bridge queue is not congested, we don't want to do anything
-		let is_congested = enqueued_messages > OUTBOUND_LANE_CONGESTED_THRESHOLD;
+		let is_congested = enqueued_messages > H::CONGESTED_THRESHOLD;
 		if !is_congested {
 			return
 		}
@@ -289,7 +296,7 @@ impl<H: XcmBlobHauler> LocalXcmQueueManager<H> {
 		}
 
 		// if the bridge queue is still congested, we don't want to do anything
-		let is_congested = enqueued_messages > OUTBOUND_LANE_UNCONGESTED_THRESHOLD;
+		let is_congested = enqueued_messages > H::UNCONGESTED_THRESHOLD;
 		if is_congested {
 			return
 		}
@@ -51,7 +51,7 @@ use pallet_transaction_payment::Multiplier;
 use sp_runtime::{
 	testing::H256,
 	traits::{BlakeTwo256, ConstU32, ConstU64, ConstU8, IdentityLookup},
-	FixedPointNumber, Perquintill,
+	FixedPointNumber, Perbill, Perquintill,
 };
 
 /// Account identifier at `ThisChain`.
@@ -118,6 +118,7 @@ frame_support::construct_runtime! {
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		TransactionPayment: pallet_transaction_payment::{Pallet, Storage, Event<T>},
 		BridgeRelayers: pallet_bridge_relayers::{Pallet, Call, Storage, Event<T>},
+		BridgeFeeOracle: pallet_bridge_fee_oracle::{Pallet, Call, Storage, Event<T>},
 		BridgeGrandpa: pallet_bridge_grandpa::{Pallet, Call, Storage, Event<T>},
 		BridgeParachains: pallet_bridge_parachains::{Pallet, Call, Storage, Event<T>},
 		BridgeMessages: pallet_bridge_messages::{Pallet, Call, Storage, Event<T>, Config<T>},
@@ -144,6 +145,14 @@ parameter_types! {
 	pub const MaxUnrewardedRelayerEntriesAtInboundLane: MessageNonce = 16;
 	pub const MaxUnconfirmedMessagesAtInboundLane: MessageNonce = 1_000;
 	pub const ReserveId: [u8; 8] = *b"brdgrlrs";
+	pub const RelayerReputationPeriod: ThisChainBlockNumber = 10;
+	pub const RelayerMaxInactivePeriods: u32 = 3;
+	pub const StaleRelayerSlash: Perbill = Perbill::from_percent(20);
+	pub const RelayerSlashDestination: ThisChainAccountId = 1000;
+	pub const InitialByteFee: ThisChainBalance = 1_000;
+	pub const MinByteFee: ThisChainBalance = 1;
+	pub const MaxByteFee: ThisChainBalance = 1_000_000_000_000;
+	pub const ByteFeeSmoothingFactor: Perbill = Perbill::from_percent(50);
 }
 
 impl frame_system::Config for TestRuntime {
@@ -214,6 +223,7 @@ impl pallet_bridge_grandpa::Config for TestRuntime {
 	type RuntimeEvent = RuntimeEvent;
 	type BridgedChain = BridgedUnderlyingChain;
 	type MaxFreeMandatoryHeadersPerBlock = ConstU32<4>;
+	type FreeHeadersInterval = ();
 	type HeadersToKeep = ConstU32<8>;
 	type WeightInfo = pallet_bridge_grandpa::weights::BridgeWeight<TestRuntime>;
 }
@@ -251,9 +261,11 @@ impl pallet_bridge_messages::Config for TestRuntime {
 		ConstU64<100_000>,
 	>;
 	type OnMessagesDelivered = ();
+	type MessageRetentionBlocks = ConstU32<0>;
 
 	type SourceHeaderChain = SourceHeaderChainAdapter<OnThisChainBridge>;
 	type MessageDispatch = DummyMessageDispatch;
+	type InboundLanesQos = ();
 	type BridgedChainId = BridgedChainId;
 }
 
@@ -262,9 +274,24 @@ impl pallet_bridge_relayers::Config for TestRuntime {
 	type Reward = ThisChainBalance;
 	type PaymentProcedure = TestPaymentProcedure;
 	type StakeAndSlash = TestStakeAndSlash;
+	type ReputationPeriod = RelayerReputationPeriod;
+	type MaxInactivePeriods = RelayerMaxInactivePeriods;
+	type StaleRelayerSlash = StaleRelayerSlash;
+	type SlashDestination = RelayerSlashDestination;
 	type WeightInfo = ();
 }
 
+impl pallet_bridge_fee_oracle::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type Balance = ThisChainBalance;
+	type ReportOrigin = frame_system::EnsureRoot<ThisChainAccountId>;
+	type InitialByteFee = InitialByteFee;
+	type MinByteFee = MinByteFee;
+	type MaxByteFee = MaxByteFee;
+	type SmoothingFactor = ByteFeeSmoothingFactor;
+}
+
 /// Dummy message dispatcher.
 pub struct DummyMessageDispatch;
 
@@ -32,6 +32,7 @@ use frame_support::{
 	weights::Weight,
 	CloneNoBound, DefaultNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
 };
+use pallet_bridge_fee_oracle::{Config as FeeOracleConfig, Pallet as FeeOraclePallet};
 use pallet_bridge_grandpa::{
 	CallSubType as GrandpaCallSubType, SubmitFinalityProofHelper, SubmitFinalityProofInfo,
 };
@@ -47,7 +48,7 @@ use pallet_transaction_payment::{Config as TransactionPaymentConfig, OnChargeTra
 use pallet_utility::{Call as UtilityCall, Config as UtilityConfig, Pallet as UtilityPallet};
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{DispatchInfoOf, Get, PostDispatchInfoOf, SignedExtension, Zero},
+	traits::{DispatchInfoOf, Get, PostDispatchInfoOf, SaturatedConversion, SignedExtension, Zero},
 	transaction_validity::{
 		TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransactionBuilder,
 	},
@@ -147,6 +148,35 @@ where
 	}
 }
 
+/// `RefundCalculator` implementation which computes refund using the wrapped calculator and
+/// reports it to the bridge fee oracle pallet as the cost of the bridge transaction.
+///
+/// The refund is only computed for transactions that have delivered new messages (or
+/// confirmations of their delivery), so the oracle only tracks costs of useful transactions.
+/// The weight of the report must be included in the messages pallet weights (see
+/// `receive_messages_proof_overhead_from_runtime`).
+pub struct FeeOracleReportingRefund<Refund, R, I = ()>(PhantomData<(Refund, R, I)>);
+
+impl<Refund, R, I> RefundCalculator for FeeOracleReportingRefund<Refund, R, I>
+where
+	Refund: RefundCalculator,
+	R: FeeOracleConfig<I, Balance = Refund::Balance>,
+	I: 'static,
+{
+	type Balance = Refund::Balance;
+
+	fn compute_refund(
+		info: &DispatchInfo,
+		post_info: &PostDispatchInfo,
+		len: usize,
+		tip: Self::Balance,
+	) -> Self::Balance {
+		let refund = Refund::compute_refund(info, post_info, len, tip);
+		FeeOraclePallet::<R, I>::note_delivery_cost(len.saturated_into(), refund);
+		refund
+	}
+}
+
 /// Data that is crafted in `pre_dispatch` method and used at `post_dispatch`.
 #[cfg_attr(test, derive(Debug, PartialEq))]
 pub struct PreDispatchData<AccountId> {
@@ -1593,6 +1623,61 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn fee_oracle_reporting_refund_reports_cost_of_successful_transactions() {
+		type ReportingTestExtension = RefundBridgedParachainMessages<
+			TestRuntime,
+			DefaultRefundableParachainId<(), TestParachain>,
+			RefundableMessagesLane<(), TestLaneId>,
+			FeeOracleReportingRefund<ActualFeeRefund<TestRuntime>, TestRuntime>,
+			ConstU64<1>,
+			StrTestExtension,
+		>;
+
+		run_test(|| {
+			initialize_environment(200, 200, 200);
+
+			// cost of failed transaction is not reported
+			assert_eq!(
+				ReportingTestExtension::post_dispatch(
+					Some(Some(delivery_pre_dispatch_data())),
+					&dispatch_info(),
+					&post_dispatch_info(),
+					1024,
+					&Err(DispatchError::BadOrigin),
+				),
+				Ok(()),
+			);
+			assert_eq!(FeeOraclePallet::<TestRuntime>::byte_fee(), InitialByteFee::get());
+
+			// cost of successful delivery transaction is reported
+			assert_eq!(
+				ReportingTestExtension::post_dispatch(
+					Some(Some(delivery_pre_dispatch_data())),
+					&dispatch_info(),
+					&post_dispatch_info(),
+					1024,
+					&Ok(()),
+				),
+				Ok(()),
+			);
+			let reported_byte_fee = expected_delivery_reward() / 1024;
+			assert_eq!(
+				FeeOraclePallet::<TestRuntime>::byte_fee(),
+				InitialByteFee::get() / 2 + reported_byte_fee / 2,
+			);
+
+			// and the relayer is still refunded
+			assert_eq!(
+				RelayersPallet::<TestRuntime>::relayer_reward(
+					relayer_account_at_this_chain(),
+					MsgProofsRewardsAccount::get()
+				),
+				Some(expected_delivery_reward()),
+			);
+		});
+	}
+
 	#[test]
 	fn post_dispatch_slashing_relayer_stake() {
 		run_test(|| {
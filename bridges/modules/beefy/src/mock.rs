@@ -0,0 +1,273 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+// From construct_runtime macro
+#![allow(clippy::from_over_into)]
+
+use crate as beefy;
+use crate::{
+	utils::get_authorities_mmr_root, BridgedBeefyAuthoritySet, BridgedBeefyAuthoritySetInfo,
+	BridgedBeefyMmrLeaf, BridgedBeefySignedCommitment, BridgedMmrHash, BridgedMmrHashing,
+	BridgedMmrProof,
+};
+
+use bp_beefy::{BeefyPayload, ChainWithBeefy, Commitment, MmrLeafVersion};
+use bp_runtime::{BasicOperatingMode, Chain};
+use codec::Encode;
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{ConstU32, ConstU64, Hooks},
+	weights::Weight,
+};
+use sp_core::{ecdsa, sr25519::Signature, Pair};
+use sp_runtime::{
+	testing::H256,
+	traits::{BlakeTwo256, Hash, IdentityLookup, Keccak256},
+	Perbill,
+};
+
+pub use sp_consensus_beefy::ecdsa_crypto::AuthorityId as BeefyId;
+
+pub type AccountId = u64;
+pub type TestHeader = sp_runtime::testing::Header;
+pub type TestNumber = u64;
+
+pub type TestBridgedAuthoritySetInfo = BridgedBeefyAuthoritySetInfo<TestRuntime, ()>;
+pub type TestBridgedValidatorSet = BridgedBeefyAuthoritySet<TestRuntime, ()>;
+pub type TestBridgedSignedCommitment = BridgedBeefySignedCommitment<TestRuntime, ()>;
+pub type TestBridgedValidatorSignature = bp_beefy::BeefyValidatorSignatureOf<TestBridgedChain>;
+pub type TestBridgedMmrHashing = BridgedMmrHashing<TestRuntime, ()>;
+pub type TestBridgedMmrHash = BridgedMmrHash<TestRuntime, ()>;
+pub type TestBridgedRawMmrLeaf = BridgedBeefyMmrLeaf<TestRuntime, ()>;
+pub type TestBridgedMmrProof = BridgedMmrProof<TestRuntime, ()>;
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+construct_runtime! {
+	pub enum TestRuntime
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Beefy: beefy::{Pallet, Call, Event<T>},
+	}
+}
+
+parameter_types! {
+	pub const MaximumBlockWeight: Weight = Weight::from_parts(1024, 0);
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Config for TestRuntime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Nonce = u64;
+	type RuntimeCall = RuntimeCall;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type SystemWeightInfo = ();
+	type DbWeight = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl beefy::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type BridgedChain = TestBridgedChain;
+	type MaxRequests = ConstU32<16>;
+	type CommitmentsToKeep = ConstU32<16>;
+	type WeightInfo = ();
+}
+
+#[derive(Debug)]
+pub struct TestBridgedChain;
+
+impl Chain for TestBridgedChain {
+	type BlockNumber = TestNumber;
+	type Hash = H256;
+	type Hasher = BlakeTwo256;
+	type Header = TestHeader;
+
+	type AccountId = AccountId;
+	type Balance = u64;
+	type Nonce = u64;
+	type Signature = Signature;
+
+	fn max_extrinsic_size() -> u32 {
+		unreachable!()
+	}
+	fn max_extrinsic_weight() -> Weight {
+		unreachable!()
+	}
+}
+
+impl ChainWithBeefy for TestBridgedChain {
+	type CommitmentHasher = Keccak256;
+	type MmrHashing = Keccak256;
+	type MmrHash = <Keccak256 as Hash>::Output;
+	type BeefyMmrLeafExtra = ();
+	type AuthorityId = BeefyId;
+	type AuthorityIdToMerkleLeaf = bp_beefy::BeefyEcdsaToEthereum;
+}
+
+/// Commitment, that is ready to be submitted to the pallet, together with the data that is
+/// required to verify it.
+pub struct TestBridgedCommitment {
+	/// Signed commitment.
+	pub commitment: TestBridgedSignedCommitment,
+	/// Validator set, that has signed the commitment.
+	pub validator_set: TestBridgedValidatorSet,
+	/// MMR leaf, which is the only leaf of the MMR.
+	pub leaf: TestBridgedRawMmrLeaf,
+	/// Proof of the `leaf` membership in the MMR.
+	pub leaf_proof: TestBridgedMmrProof,
+	/// MMR root, that is included in the commitment.
+	pub mmr_root: TestBridgedMmrHash,
+}
+
+impl TestBridgedCommitment {
+	/// Create commitment for given block number, signed by the first `validators_count`
+	/// validators of the set `0`. The MMR leaf announces `next_validator_set_id` set with the same
+	/// validators.
+	pub fn new(
+		block_number: TestNumber,
+		next_validator_set_id: u64,
+		validators_count: u32,
+	) -> Self {
+		let leaf = TestBridgedRawMmrLeaf {
+			version: MmrLeafVersion::new(1, 0),
+			parent_number_and_hash: (block_number - 1, H256::repeat_byte(block_number as u8)),
+			beefy_next_authority_set: authority_set_info(
+				next_validator_set_id,
+				&validator_ids(0, validators_count),
+			),
+			leaf_extra: (),
+		};
+		// MMR with a single leaf has the leaf hash as a root
+		let mmr_root = TestBridgedMmrHashing::hash(&leaf.encode());
+		let leaf_proof =
+			TestBridgedMmrProof { leaf_indices: vec![0], leaf_count: 1, items: vec![] };
+
+		let commitment = Commitment {
+			payload: BeefyPayload::from_single_entry(
+				bp_beefy::MMR_ROOT_PAYLOAD_ID,
+				mmr_root.encode(),
+			),
+			block_number,
+			validator_set_id: 0,
+		};
+		let signatures =
+			sign_commitment(&commitment, &validator_pairs(0, validators_count), validators_count);
+
+		TestBridgedCommitment {
+			commitment: TestBridgedSignedCommitment { commitment, signatures },
+			validator_set: TestBridgedValidatorSet::new(validator_ids(0, validators_count), 0)
+				.unwrap(),
+			leaf,
+			leaf_proof,
+			mmr_root,
+		}
+	}
+}
+
+/// Return test externalities to use in tests.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	sp_io::TestExternalities::new(Default::default())
+}
+
+/// Run test within test externalities.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	new_test_ext().execute_with(|| {
+		let _ = Beefy::on_initialize(0);
+		test()
+	})
+}
+
+/// Initialize pallet with the set `0` of `initial_validators_count` validators and run test.
+pub fn run_test_with_initialize<T>(initial_validators_count: u32, test: impl FnOnce() -> T) -> T {
+	run_test(|| {
+		let validators = validator_ids(0, initial_validators_count);
+		let authority_set = authority_set_info(0, &validators);
+
+		crate::Pallet::<TestRuntime>::initialize(
+			RuntimeOrigin::root(),
+			bp_beefy::InitializationData {
+				operating_mode: BasicOperatingMode::Normal,
+				best_block_number: 0,
+				authority_set,
+			},
+		)
+		.expect("initialization data is correct");
+
+		test()
+	})
+}
+
+/// Return key pairs of `count` validators, starting from the validator with given `index`.
+pub fn validator_pairs(index: u32, count: u32) -> Vec<ecdsa::Pair> {
+	(index..index + count)
+		.map(|index| {
+			let mut seed = [1u8; 32];
+			seed[0..4].copy_from_slice(&index.to_le_bytes());
+			ecdsa::Pair::from_seed(&seed)
+		})
+		.collect()
+}
+
+/// Return ids of `count` validators, starting from the validator with given `index`.
+pub fn validator_ids(index: u32, count: u32) -> Vec<BeefyId> {
+	validator_pairs(index, count)
+		.into_iter()
+		.map(|pair| pair.public().into())
+		.collect()
+}
+
+/// Return authority set info for given validators.
+pub fn authority_set_info(id: u64, validators: &[BeefyId]) -> TestBridgedAuthoritySetInfo {
+	let merkle_root = get_authorities_mmr_root::<TestRuntime, (), _>(validators.iter());
+
+	TestBridgedAuthoritySetInfo { id, len: validators.len() as u32, keyset_commitment: merkle_root }
+}
+
+/// Sign BEEFY commitment by the first `signature_count` validators.
+pub fn sign_commitment(
+	commitment: &Commitment<TestNumber>,
+	validator_pairs: &[ecdsa::Pair],
+	signature_count: u32,
+) -> Vec<Option<TestBridgedValidatorSignature>> {
+	let total_validators = validator_pairs.len();
+	let commitment_hash = Keccak256::hash(&commitment.encode());
+
+	validator_pairs
+		.iter()
+		.take(signature_count as usize)
+		.map(|pair| Some(pair.sign_prehashed(commitment_hash.as_fixed_bytes()).into()))
+		.chain(sp_std::iter::repeat(None))
+		.take(total_validators)
+		.collect()
+}
@@ -0,0 +1,189 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Helpers for verifying BEEFY commitments and MMR leafs.
+
+use crate::{
+	BridgedBeefyAuthorityId, BridgedBeefyAuthoritySet, BridgedBeefyAuthoritySetInfo,
+	BridgedBeefyMmrLeaf, BridgedBeefySignedCommitment, BridgedChain, BridgedMmrHash,
+	BridgedMmrHashing, BridgedMmrProof, Config, Error, LOG_TARGET,
+};
+use bp_beefy::{merkle_root, verify_mmr_leaves_proof, BeefyAuthorityId, MmrDataOrHash};
+use codec::Encode;
+use frame_support::ensure;
+use sp_runtime::traits::{Convert, Hash};
+use sp_std::{vec, vec::Vec};
+
+type BridgedMmrDataOrHash<T, I> = MmrDataOrHash<BridgedMmrHashing<T, I>, BridgedBeefyMmrLeaf<T, I>>;
+/// A way to encode validator id to the BEEFY merkle tree leaf.
+type BridgedBeefyAuthorityIdToMerkleLeaf<T, I> =
+	bp_beefy::BeefyAuthorityIdToMerkleLeafOf<BridgedChain<T, I>>;
+
+/// Get the MMR root for a collection of validators.
+pub(crate) fn get_authorities_mmr_root<
+	'a,
+	T: Config<I>,
+	I: 'static,
+	V: Iterator<Item = &'a BridgedBeefyAuthorityId<T, I>>,
+>(
+	authorities: V,
+) -> BridgedMmrHash<T, I> {
+	let merkle_leafs = authorities
+		.cloned()
+		.map(BridgedBeefyAuthorityIdToMerkleLeaf::<T, I>::convert)
+		.collect::<Vec<_>>();
+	merkle_root::<BridgedMmrHashing<T, I>, _>(merkle_leafs)
+}
+
+/// Ensure that the provided validator set matches the authority set info known to the pallet.
+fn verify_authority_set<T: Config<I>, I: 'static>(
+	authority_set_info: &BridgedBeefyAuthoritySetInfo<T, I>,
+	authority_set: &BridgedBeefyAuthoritySet<T, I>,
+) -> Result<(), Error<T, I>> {
+	ensure!(authority_set.id() == authority_set_info.id, Error::<T, I>::InvalidValidatorSetId);
+	ensure!(
+		authority_set.len() == authority_set_info.len as usize,
+		Error::<T, I>::InvalidValidatorSetLen
+	);
+
+	// Ensure that the authority set that signed the commitment is the expected one.
+	let root = get_authorities_mmr_root::<T, I, _>(authority_set.validators().iter());
+	ensure!(root == authority_set_info.keyset_commitment, Error::<T, I>::InvalidValidatorSetRoot);
+
+	Ok(())
+}
+
+/// Number of correct signatures, required from given validators set to accept signed
+/// commitment.
+///
+/// We're using 'conservative' approach here, where signatures of `2/3+1` validators are
+/// required.
+pub(crate) fn signatures_required(validators_len: usize) -> usize {
+	validators_len - validators_len.saturating_sub(1) / 3
+}
+
+fn verify_signatures<T: Config<I>, I: 'static>(
+	commitment: &BridgedBeefySignedCommitment<T, I>,
+	authority_set: &BridgedBeefyAuthoritySet<T, I>,
+) -> Result<(), Error<T, I>> {
+	ensure!(
+		commitment.signatures.len() == authority_set.len(),
+		Error::<T, I>::InvalidCommitmentSignaturesLen
+	);
+
+	// Ensure that the commitment was signed by enough authorities.
+	let msg = commitment.commitment.encode();
+	let mut missing_signatures = authority_set.len() - signatures_required(authority_set.len());
+	for (idx, (authority, maybe_sig)) in
+		authority_set.validators().iter().zip(commitment.signatures.iter()).enumerate()
+	{
+		match maybe_sig {
+			Some(sig) if authority.verify(sig, &msg) => {},
+			_ => {
+				missing_signatures = missing_signatures
+					.checked_sub(1)
+					.ok_or(Error::<T, I>::NotEnoughCorrectSignatures)?;
+				log::debug!(
+					target: LOG_TARGET,
+					"Signed commitment contains incorrect signature of validator {} ({:?}): {:?}",
+					idx,
+					authority,
+					maybe_sig,
+				);
+			},
+		};
+	}
+
+	Ok(())
+}
+
+/// Extract MMR root from commitment payload.
+fn extract_mmr_root<T: Config<I>, I: 'static>(
+	commitment: &BridgedBeefySignedCommitment<T, I>,
+) -> Result<BridgedMmrHash<T, I>, Error<T, I>> {
+	commitment
+		.commitment
+		.payload
+		.get_decoded(&bp_beefy::MMR_ROOT_PAYLOAD_ID)
+		.ok_or(Error::MmrRootMissingFromCommitment)
+}
+
+/// Verify the commitment and return the MMR root that it finalizes.
+pub(crate) fn verify_commitment<T: Config<I>, I: 'static>(
+	commitment: &BridgedBeefySignedCommitment<T, I>,
+	authority_set_info: &BridgedBeefyAuthoritySetInfo<T, I>,
+	authority_set: &BridgedBeefyAuthoritySet<T, I>,
+) -> Result<BridgedMmrHash<T, I>, Error<T, I>> {
+	// Ensure that the commitment is signed by the best known BEEFY validator set.
+	ensure!(
+		commitment.commitment.validator_set_id == authority_set_info.id,
+		Error::<T, I>::InvalidCommitmentValidatorSetId
+	);
+	ensure!(
+		commitment.signatures.len() == authority_set_info.len as usize,
+		Error::<T, I>::InvalidCommitmentSignaturesLen
+	);
+
+	verify_authority_set(authority_set_info, authority_set)?;
+	verify_signatures(commitment, authority_set)?;
+
+	extract_mmr_root(commitment)
+}
+
+/// Verify MMR proof of given leaf.
+pub(crate) fn verify_beefy_mmr_leaf<T: Config<I>, I: 'static>(
+	mmr_leaf: &BridgedBeefyMmrLeaf<T, I>,
+	mmr_proof: BridgedMmrProof<T, I>,
+	mmr_root: BridgedMmrHash<T, I>,
+) -> Result<(), Error<T, I>> {
+	let mmr_proof_leaf_count = mmr_proof.leaf_count;
+	let mmr_proof_length = mmr_proof.items.len();
+
+	// Verify the mmr proof for the provided leaf.
+	let mmr_leaf_hash = BridgedMmrHashing::<T, I>::hash(&mmr_leaf.encode());
+	verify_mmr_leaves_proof(
+		mmr_root,
+		vec![BridgedMmrDataOrHash::<T, I>::Hash(mmr_leaf_hash)],
+		mmr_proof,
+	)
+	.map_err(|e| {
+		log::error!(
+			target: LOG_TARGET,
+			"MMR proof of leaf {:?} (root: {:?}, leaf count: {}, len: {}) \
+				verification has failed with error: {:?}",
+			mmr_leaf_hash,
+			mmr_root,
+			mmr_proof_leaf_count,
+			mmr_proof_length,
+			e,
+		);
+
+		Error::<T, I>::MmrProofVerificationFailed
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn signatures_required_is_correct() {
+		assert_eq!(signatures_required(1), 1);
+		assert_eq!(signatures_required(4), 3);
+		assert_eq!(signatures_required(8), 6);
+		assert_eq!(signatures_required(100), 67);
+	}
+}
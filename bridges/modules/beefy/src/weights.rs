@@ -0,0 +1,92 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for pallet_bridge_beefy.
+//!
+//! The weights are not benchmarked yet and are rough estimations of the worst case. The
+//! dominating part of the `submit_commitment` call cost is the secp256k1 signature verification,
+//! performed once for every validator of the current BEEFY authority set.
+
+#![allow(clippy::all)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_bridge_beefy.
+pub trait WeightInfo {
+	fn submit_commitment(v: u32, p: u32) -> Weight;
+}
+
+/// Weights for `pallet_bridge_beefy` that are estimated using one of the Bridge testnets.
+///
+/// Those weights are test only and must never be used in production.
+pub struct BridgeWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
+	/// Storage: BridgeBeefy PalletOperatingMode (r:1 w:0)
+	///
+	/// Storage: BridgeBeefy RequestCount (r:1 w:1)
+	///
+	/// Storage: BridgeBeefy ImportedCommitmentsInfo (r:1 w:1)
+	///
+	/// Storage: BridgeBeefy CurrentAuthoritySetInfo (r:1 w:1)
+	///
+	/// Storage: BridgeBeefy ImportedBlockNumbers (r:1 w:1)
+	///
+	/// Storage: BridgeBeefy ImportedCommitments (r:0 w:2)
+	///
+	/// The range of component `v` is `[1, 1024]`.
+	///
+	/// The range of component `p` is `[0, 32]`.
+	fn submit_commitment(v: u32, p: u32) -> Weight {
+		Weight::from_parts(49_713_000, 4860)
+			.saturating_add(Weight::from_parts(28_812_000, 0).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(1_044_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: BridgeBeefy PalletOperatingMode (r:1 w:0)
+	///
+	/// Storage: BridgeBeefy RequestCount (r:1 w:1)
+	///
+	/// Storage: BridgeBeefy ImportedCommitmentsInfo (r:1 w:1)
+	///
+	/// Storage: BridgeBeefy CurrentAuthoritySetInfo (r:1 w:1)
+	///
+	/// Storage: BridgeBeefy ImportedBlockNumbers (r:1 w:1)
+	///
+	/// Storage: BridgeBeefy ImportedCommitments (r:0 w:2)
+	///
+	/// The range of component `v` is `[1, 1024]`.
+	///
+	/// The range of component `p` is `[0, 32]`.
+	fn submit_commitment(v: u32, p: u32) -> Weight {
+		Weight::from_parts(49_713_000, 4860)
+			.saturating_add(Weight::from_parts(28_812_000, 0).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(1_044_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+}
@@ -0,0 +1,46 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the fee oracle Pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::*;
+
+use frame_benchmarking::benchmarks_instance_pallet;
+use frame_support::{dispatch::UnfilteredDispatchable, traits::EnsureOrigin};
+use sp_runtime::Saturating;
+
+/// Pallet we're benchmarking here.
+pub struct Pallet<T: Config<I>, I: 'static = ()>(crate::Pallet<T, I>);
+
+benchmarks_instance_pallet! {
+	// Benchmark `report_delivery_cost` call.
+	report_delivery_cost {
+		let origin: T::RuntimeOrigin = T::ReportOrigin::try_successful_origin()
+			.expect("expected valid ReportOrigin");
+		let call = Call::<T, I>::report_delivery_cost {
+			transaction_size: 1_024,
+			cost: T::MaxByteFee::get().saturating_mul(1_024u32.into()),
+		};
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		// reported byte fee is maximal, so it may only increase
+		assert!(crate::Pallet::<T, I>::byte_fee() >= T::InitialByteFee::get());
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::TestRuntime)
+}
@@ -0,0 +1,274 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet that tracks moving average of the bridge delivery transaction costs.
+//!
+//! Every report contains the size of the delivery transaction and its total cost. The pallet
+//! computes per-byte cost of the reported transaction and updates the exponential moving
+//! average of the byte fee. Reports may be submitted by the `ReportOrigin` (e.g. relayers or
+//! governance) or computed by other runtime components from confirmed deliveries, using the
+//! [`Pallet::note_delivery_cost`] function. The `FeeOracleReportingRefund` adapter from the
+//! `bridge-runtime-common` crate reports costs of all transactions, refunded to relayers.
+//!
+//! The current byte fee may be used by other runtime components via the [`CurrentByteFee`]
+//! adapter, which implements `Get<Balance>`.
+//! When the byte fee is used as the `LengthToFee` of the chain, the `ReportOrigin` should report
+//! costs that are representative for all transactions of the chain.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+
+use frame_support::traits::Get;
+use sp_arithmetic::traits::{AtLeast32BitUnsigned, Zero};
+use sp_runtime::{Perbill, Saturating};
+use sp_std::marker::PhantomData;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+pub mod benchmarking;
+pub mod weights;
+
+mod mock;
+
+/// The target that will be used when publishing logs related to this pallet.
+pub const LOG_TARGET: &str = "runtime::bridge-fee-oracle";
+
+/// Adapter that returns current byte fee, tracked by the pallet.
+pub struct CurrentByteFee<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> Get<T::Balance> for CurrentByteFee<T, I> {
+	fn get() -> T::Balance {
+		Pallet::<T, I>::byte_fee()
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Benchmarks results from runtime we're plugged into.
+		type WeightInfo: WeightInfo;
+
+		/// Type of the delivery transaction cost.
+		type Balance: AtLeast32BitUnsigned + Copy + Member + Parameter + MaxEncodedLen;
+		/// Origin that is allowed to report delivery transaction costs.
+		type ReportOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Byte fee that is used until first delivery cost is reported.
+		#[pallet::constant]
+		type InitialByteFee: Get<Self::Balance>;
+		/// Minimal byte fee. Byte fee never goes below this value, no matter what is reported.
+		#[pallet::constant]
+		type MinByteFee: Get<Self::Balance>;
+		/// Maximal byte fee. Byte fee never goes above this value, no matter what is reported.
+		#[pallet::constant]
+		type MaxByteFee: Get<Self::Balance>;
+		/// Weight of the every new report in the moving average.
+		///
+		/// The larger it is, the faster byte fee follows reported costs.
+		#[pallet::constant]
+		type SmoothingFactor: Get<Perbill>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Report cost of the delivery transaction.
+		///
+		/// The `transaction_size` is the size of the encoded transaction and the `cost` is its
+		/// total cost, paid by the submitter.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::report_delivery_cost())]
+		pub fn report_delivery_cost(
+			origin: OriginFor<T>,
+			transaction_size: u32,
+			cost: T::Balance,
+		) -> DispatchResult {
+			T::ReportOrigin::ensure_origin(origin)?;
+			ensure!(transaction_size != 0, Error::<T, I>::ZeroTransactionSize);
+
+			Self::note_delivery_cost(transaction_size, cost);
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Update moving average of the byte fee using cost of the delivery transaction.
+		///
+		/// Reports with zero `transaction_size` are ignored.
+		pub fn note_delivery_cost(transaction_size: u32, cost: T::Balance) {
+			if transaction_size == 0 {
+				return
+			}
+
+			let reported_byte_fee = cost / T::Balance::from(transaction_size);
+			let smoothing_factor = T::SmoothingFactor::get();
+			let byte_fee = AverageByteFee::<T, I>::mutate(|byte_fee| {
+				*byte_fee = smoothing_factor
+					.left_from_one()
+					.mul_floor(*byte_fee)
+					.saturating_add(smoothing_factor.mul_floor(reported_byte_fee))
+					.max(T::MinByteFee::get())
+					.min(T::MaxByteFee::get());
+				*byte_fee
+			});
+
+			log::trace!(
+				target: LOG_TARGET,
+				"Delivery transaction of {} bytes costs {:?}. New byte fee: {:?}",
+				transaction_size,
+				cost,
+				byte_fee,
+			);
+
+			Self::deposit_event(Event::<T, I>::ByteFeeUpdated { byte_fee });
+		}
+
+		/// Return estimated fee of the delivery transaction of given size.
+		pub fn delivery_fee(transaction_size: u32) -> T::Balance {
+			Self::byte_fee().saturating_mul(T::Balance::from(transaction_size))
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// Byte fee has been updated.
+		ByteFeeUpdated {
+			/// New byte fee.
+			byte_fee: T::Balance,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// Transaction size in the report is zero.
+		ZeroTransactionSize,
+	}
+
+	/// Moving average of the delivery transaction byte fee.
+	#[pallet::storage]
+	#[pallet::getter(fn byte_fee)]
+	pub type AverageByteFee<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, T::Balance, ValueQuery, T::InitialByteFee>;
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn integrity_test() {
+			assert!(
+				T::MinByteFee::get() <= T::MaxByteFee::get(),
+				"MinByteFee must not be larger than MaxByteFee",
+			);
+			assert!(!T::MaxByteFee::get().is_zero(), "MaxByteFee must be non-zero",);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use mock::{RuntimeEvent as TestEvent, *};
+
+	use frame_support::{assert_noop, assert_ok};
+	use frame_system::{EventRecord, Pallet as System, Phase};
+	use sp_runtime::DispatchError;
+
+	fn get_ready_for_events() {
+		System::<TestRuntime>::set_block_number(1);
+		System::<TestRuntime>::reset_events();
+	}
+
+	#[test]
+	fn initial_byte_fee_is_used_when_nothing_is_reported() {
+		run_test(|| {
+			assert_eq!(Pallet::<TestRuntime>::byte_fee(), InitialByteFee::get());
+			assert_eq!(CurrentByteFee::<TestRuntime>::get(), InitialByteFee::get());
+			assert_eq!(Pallet::<TestRuntime>::delivery_fee(10), InitialByteFee::get() * 10);
+		});
+	}
+
+	#[test]
+	fn report_delivery_cost_rejects_unknown_origin() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::report_delivery_cost(RuntimeOrigin::signed(1), 100, 100),
+				DispatchError::BadOrigin,
+			);
+		});
+	}
+
+	#[test]
+	fn report_delivery_cost_rejects_zero_transaction_size() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::report_delivery_cost(RuntimeOrigin::root(), 0, 100),
+				Error::<TestRuntime>::ZeroTransactionSize,
+			);
+		});
+	}
+
+	#[test]
+	fn report_delivery_cost_updates_moving_average() {
+		run_test(|| {
+			get_ready_for_events();
+
+			// reported byte fee is 200, initial is 100 and smoothing factor is 25%
+			assert_ok!(Pallet::<TestRuntime>::report_delivery_cost(
+				RuntimeOrigin::root(),
+				10,
+				2_000
+			));
+			assert_eq!(Pallet::<TestRuntime>::byte_fee(), 125);
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::FeeOracle(Event::ByteFeeUpdated { byte_fee: 125 }),
+					topics: vec![],
+				}],
+			);
+
+			// reported byte fee is 29
+			assert_ok!(Pallet::<TestRuntime>::report_delivery_cost(RuntimeOrigin::root(), 10, 290));
+			assert_eq!(Pallet::<TestRuntime>::byte_fee(), 100);
+		});
+	}
+
+	#[test]
+	fn byte_fee_is_bounded() {
+		run_test(|| {
+			for _ in 0..100 {
+				Pallet::<TestRuntime>::note_delivery_cost(1, MaxByteFee::get() * 10);
+			}
+			assert_eq!(Pallet::<TestRuntime>::byte_fee(), MaxByteFee::get());
+
+			for _ in 0..100 {
+				Pallet::<TestRuntime>::note_delivery_cost(1, 0);
+			}
+			assert_eq!(Pallet::<TestRuntime>::byte_fee(), MinByteFee::get());
+		});
+	}
+}
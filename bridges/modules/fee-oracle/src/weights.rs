@@ -0,0 +1,65 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for pallet_bridge_fee_oracle.
+//!
+//! The weights are not benchmarked yet and are rough estimations of the worst case. The
+//! `report_delivery_cost` call only reads and writes the `AverageByteFee` value.
+
+#![allow(clippy::all)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_bridge_fee_oracle.
+pub trait WeightInfo {
+	fn report_delivery_cost() -> Weight;
+}
+
+/// Weights for `pallet_bridge_fee_oracle` that are estimated using one of the Bridge testnets.
+///
+/// Those weights are test only and must never be used in production.
+pub struct BridgeWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
+	/// Storage: BridgeFeeOracle AverageByteFee (r:1 w:1)
+	///
+	/// Proof: BridgeFeeOracle AverageByteFee (max_values: Some(1), max_size: Some(16), added:
+	/// 511, mode: MaxEncodedLen)
+	fn report_delivery_cost() -> Weight {
+		Weight::from_parts(9_617_000, 1501)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: BridgeFeeOracle AverageByteFee (r:1 w:1)
+	///
+	/// Proof: BridgeFeeOracle AverageByteFee (max_values: Some(1), max_size: Some(16), added:
+	/// 511, mode: MaxEncodedLen)
+	fn report_delivery_cost() -> Weight {
+		Weight::from_parts(9_617_000, 1501)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
@@ -41,11 +41,11 @@
 
 use crate::*;
 
-use bp_header_chain::justification::required_justification_precommits;
+use bp_header_chain::justification::{aggregate_justification, required_justification_precommits};
 use bp_runtime::BasicOperatingMode;
 use bp_test_utils::{
-	accounts, make_justification_for_header, JustificationGeneratorParams, TEST_GRANDPA_ROUND,
-	TEST_GRANDPA_SET_ID,
+	accounts, header_id, make_justification_for_header, precommit_equivocation_proof,
+	JustificationGeneratorParams, TEST_GRANDPA_ROUND, TEST_GRANDPA_SET_ID,
 };
 use frame_benchmarking::{benchmarks_instance_pallet, whitelisted_caller};
 use frame_system::RawOrigin;
@@ -102,6 +102,10 @@ fn prepare_benchmark_data<T: Config<I>, I: 'static>(
 	bootstrap_bridge::<T, I>(init_data);
 	assert!(<ImportedHeaders<T, I>>::contains_key(genesis_hash));
 
+	// hooks are not called during benchmarks, but we want to benchmark the worst case, where
+	// the header is imported for free and the free headers counter is updated
+	FreeMandatoryHeadersRemaining::<T, I>::put(T::MaxFreeMandatoryHeadersPerBlock::get());
+
 	let header: BridgedHeader<T, I> = bp_test_utils::test_header(One::one());
 	let params = JustificationGeneratorParams {
 		header: header.clone(),
@@ -137,5 +141,45 @@ benchmarks_instance_pallet! {
 		assert!(!<ImportedHeaders<T, I>>::contains_key(genesis_header.hash()));
 	}
 
+	submit_aggregated_finality_proof {
+		let p in 1 .. precommits_range_end::<T, I>();
+		let v in MAX_VOTE_ANCESTRIES_RANGE_BEGIN..MAX_VOTE_ANCESTRIES_RANGE_END;
+		let caller: T::AccountId = whitelisted_caller();
+		let (header, justification) = prepare_benchmark_data::<T, I>(p, v);
+		let justification = aggregate_justification(&justification, TEST_GRANDPA_SET_ID)
+			.expect("benchmark justification is valid; qed");
+	}: submit_aggregated_finality_proof(RawOrigin::Signed(caller), Box::new(header), justification)
+	verify {
+		let header: BridgedHeader<T, I> = bp_test_utils::test_header(One::one());
+		assert_eq!(<BestFinalized<T, I>>::get().unwrap().1, header.hash());
+	}
+
+	report_equivocation {
+		let caller: T::AccountId = whitelisted_caller();
+		let authorities = accounts(T::BridgedChain::MAX_AUTHORITIES_COUNT as u16);
+		let offender = authorities[0];
+		let init_data = InitializationData {
+			header: Box::new(bp_test_utils::test_header(Zero::zero())),
+			authority_list: authorities.iter().map(|id| (AuthorityId::from(*id), 1)).collect(),
+			set_id: TEST_GRANDPA_SET_ID,
+			operating_mode: BasicOperatingMode::Normal,
+		};
+		bootstrap_bridge::<T, I>(init_data);
+
+		let equivocation_proof = precommit_equivocation_proof::<BridgedHeader<T, I>>(
+			&offender,
+			header_id::<BridgedHeader<T, I>>(1),
+			header_id::<BridgedHeader<T, I>>(2),
+			TEST_GRANDPA_ROUND,
+			TEST_GRANDPA_SET_ID,
+		);
+	}: report_equivocation(RawOrigin::Signed(caller), Box::new(equivocation_proof))
+	verify {
+		assert!(<ReportedEquivocations<T, I>>::contains_key(
+			TEST_GRANDPA_SET_ID,
+			AuthorityId::from(offender),
+		));
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::TestRuntime)
 }
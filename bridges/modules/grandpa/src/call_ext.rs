@@ -18,7 +18,11 @@ use crate::{weights::WeightInfo, BridgedBlockNumber, BridgedHeader, Config, Erro
 use bp_header_chain::{justification::GrandpaJustification, ChainWithGrandpa};
 use bp_runtime::BlockNumberOf;
 use codec::Encode;
-use frame_support::{dispatch::CallableCallFor, traits::IsSubType, weights::Weight};
+use frame_support::{
+	dispatch::CallableCallFor,
+	traits::{Get, IsSubType},
+	weights::Weight,
+};
 use sp_runtime::{
 	traits::{Header, Zero},
 	transaction_validity::{InvalidTransaction, TransactionValidity, ValidTransaction},
@@ -58,10 +62,10 @@ pub struct SubmitFinalityProofHelper<T: Config<I>, I: 'static> {
 
 impl<T: Config<I>, I: 'static> SubmitFinalityProofHelper<T, I> {
 	/// Check that the GRANDPA head provided by the `SubmitFinalityProof` is better than the best
-	/// one we know.
+	/// one we know. Returns number of the best known header on success.
 	pub fn check_obsolete(
 		finality_target: BlockNumberOf<T::BridgedChain>,
-	) -> Result<(), Error<T, I>> {
+	) -> Result<BlockNumberOf<T::BridgedChain>, Error<T, I>> {
 		let best_finalized = crate::BestFinalized::<T, I>::get().ok_or_else(|| {
 			log::trace!(
 				target: crate::LOG_TARGET,
@@ -82,7 +86,21 @@ impl<T: Config<I>, I: 'static> SubmitFinalityProofHelper<T, I> {
 			return Err(Error::<T, I>::OldHeader)
 		}
 
-		Ok(())
+		Ok(best_finalized.number())
+	}
+
+	/// Returns `true` if the `finality_target` improves the best known header by at least
+	/// `FreeHeadersInterval` blocks, so it may be imported for free.
+	pub fn is_free_interval_header(
+		best_finalized_number: BlockNumberOf<T::BridgedChain>,
+		finality_target: BlockNumberOf<T::BridgedChain>,
+	) -> bool {
+		match T::FreeHeadersInterval::get() {
+			Some(free_headers_interval) if free_headers_interval != 0 =>
+				finality_target.saturating_sub(best_finalized_number) >=
+					free_headers_interval.into(),
+			_ => false,
+		}
 	}
 
 	/// Check if the `SubmitFinalityProof` was successfully executed.
@@ -122,11 +140,17 @@ pub trait CallSubType<T: Config<I, RuntimeCall = Self>, I: 'static>:
 		Self: Sized,
 	{
 		let finality_target = match self.submit_finality_proof_info() {
-			Some(finality_proof) => finality_proof,
-			_ => return Ok(ValidTransaction::default()),
+			Some(finality_proof) => finality_proof.block_number,
+			_ => match self.is_sub_type() {
+				Some(crate::Call::<T, I>::submit_aggregated_finality_proof {
+					finality_target,
+					..
+				}) => *finality_target.number(),
+				_ => return Ok(ValidTransaction::default()),
+			},
 		};
 
-		match SubmitFinalityProofHelper::<T, I>::check_obsolete(finality_target.block_number) {
+		match SubmitFinalityProofHelper::<T, I>::check_obsolete(finality_target) {
 			Ok(_) => Ok(ValidTransaction::default()),
 			Err(Error::<T, I>::OldHeader) => InvalidTransaction::Stale.into(),
 			Err(_) => InvalidTransaction::Call.into(),
@@ -192,14 +216,14 @@ mod tests {
 	use crate::{
 		call_ext::CallSubType,
 		mock::{run_test, test_header, RuntimeCall, TestBridgedChain, TestNumber, TestRuntime},
-		BestFinalized, Config, WeightInfo,
+		BestFinalized, Config, SubmitFinalityProofHelper, WeightInfo,
 	};
 	use bp_header_chain::ChainWithGrandpa;
 	use bp_runtime::HeaderId;
 	use bp_test_utils::{
 		make_default_justification, make_justification_for_header, JustificationGeneratorParams,
 	};
-	use frame_support::weights::Weight;
+	use frame_support::{traits::Get, weights::Weight};
 	use sp_runtime::{testing::DigestItem, traits::Header as _, SaturatedConversion};
 
 	fn validate_block_submit(num: TestNumber) -> bool {
@@ -228,6 +252,32 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn extension_rejects_obsolete_header_with_aggregated_finality() {
+		run_test(|| {
+			sync_to_header_10();
+
+			let validate_aggregated_block_submit = |num| {
+				let justification = bp_header_chain::justification::aggregate_justification(
+					&make_default_justification(&test_header(num)),
+					bp_test_utils::TEST_GRANDPA_SET_ID,
+				)
+				.unwrap();
+				RuntimeCall::check_obsolete_submit_finality_proof(&RuntimeCall::Grandpa(
+					crate::Call::<TestRuntime, ()>::submit_aggregated_finality_proof {
+						finality_target: Box::new(test_header(num)),
+						justification,
+					},
+				))
+				.is_ok()
+			};
+
+			assert!(!validate_aggregated_block_submit(5));
+			assert!(!validate_aggregated_block_submit(10));
+			assert!(validate_aggregated_block_submit(15));
+		})
+	}
+
 	#[test]
 	fn extension_rejects_same_header() {
 		run_test(|| {
@@ -310,4 +360,25 @@ mod tests {
 		});
 		assert_eq!(call.submit_finality_proof_info().unwrap().extra_weight, call_weight);
 	}
+
+	#[test]
+	fn is_free_interval_header_works() {
+		run_test(|| {
+			let interval: TestNumber =
+				<TestRuntime as Config>::FreeHeadersInterval::get().unwrap().into();
+
+			assert!(!SubmitFinalityProofHelper::<TestRuntime, ()>::is_free_interval_header(
+				10,
+				10 + interval - 1
+			));
+			assert!(SubmitFinalityProofHelper::<TestRuntime, ()>::is_free_interval_header(
+				10,
+				10 + interval
+			));
+			assert!(SubmitFinalityProofHelper::<TestRuntime, ()>::is_free_interval_header(
+				10,
+				10 + interval + 1
+			));
+		});
+	}
 }
@@ -36,15 +36,16 @@
 // Runtime-generated enums
 #![allow(clippy::large_enum_variant)]
 
-pub use storage_types::StoredAuthoritySet;
+pub use storage_types::{StoredAuthoritySet, StoredPendingForcedChange};
 
 use bp_header_chain::{
-	justification::GrandpaJustification, AuthoritySet, ChainWithGrandpa, GrandpaConsensusLogReader,
-	HeaderChain, InitializationData, StoredHeaderData, StoredHeaderDataBuilder,
-	StoredHeaderGrandpaInfo,
+	justification::{AggregatedGrandpaJustification, GrandpaJustification},
+	AuthoritySet, ChainWithGrandpa, ConsensusLogReader, GrandpaConsensusLogReader, HeaderChain,
+	InitializationData, StoredHeaderData, StoredHeaderDataBuilder, StoredHeaderGrandpaInfo,
 };
 use bp_runtime::{BlockNumberOf, HashOf, HasherOf, HeaderId, HeaderOf, OwnedBridgeModule};
 use frame_support::{dispatch::PostDispatchInfo, ensure, DefaultNoBound};
+use sp_consensus_grandpa::{AuthorityId, EquivocationProof, SetId};
 use sp_runtime::{
 	traits::{Header as HeaderT, Zero},
 	SaturatedConversion,
@@ -85,6 +86,9 @@ pub type BridgedHeader<T, I> = HeaderOf<<T as Config<I>>::BridgedChain>;
 /// Header data of the bridged chain that is stored at this chain by this pallet.
 pub type BridgedStoredHeaderData<T, I> =
 	StoredHeaderData<BridgedBlockNumber<T, I>, BridgedBlockHash<T, I>>;
+/// GRANDPA equivocation proof of the bridged chain.
+pub type BridgedEquivocationProof<T, I> =
+	EquivocationProof<BridgedBlockHash<T, I>, BridgedBlockNumber<T, I>>;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -111,9 +115,23 @@ pub mod pallet {
 		/// as they want. And they may fill the whole block (at this chain) for free. This constants
 		/// limits number of calls that we may refund in a single block. All calls above this
 		/// limit are accepted, but are not refunded.
+		///
+		/// The same limit also applies to headers that are accepted for free because of the
+		/// `FreeHeadersInterval` setting.
 		#[pallet::constant]
 		type MaxFreeMandatoryHeadersPerBlock: Get<u32>;
 
+		/// Interval (in blocks) for free non-mandatory headers.
+		///
+		/// Permissionless relayers are not paid for keeping the bridge alive, so they may be
+		/// unwilling to submit headers between two mandatory headers. If this setting is
+		/// `Some(N)`, then any header that improves the best finalized header by at least `N`
+		/// blocks is accepted for free, just like mandatory headers. Such headers are counted
+		/// towards the `MaxFreeMandatoryHeadersPerBlock` limit. If it is `None`, only mandatory
+		/// headers may be accepted for free.
+		#[pallet::constant]
+		type FreeHeadersInterval: Get<Option<u32>>;
+
 		/// Maximal number of finalized headers to keep in the storage.
 		///
 		/// The setting is there to prevent growing the on-chain state indefinitely. Note
@@ -168,7 +186,7 @@ pub mod pallet {
 		///
 		/// - verification is not optimized or invalid;
 		///
-		/// - header contains forced authorities set change or change with non-zero delay.
+		/// - header contains scheduled authorities set change with non-zero delay.
 		#[pallet::call_index(0)]
 		#[pallet::weight(<T::WeightInfo as WeightInfo>::submit_finality_proof(
 			justification.commit.precommits.len().saturated_into(),
@@ -182,46 +200,35 @@ pub mod pallet {
 			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
 			ensure_signed(origin)?;
 
-			let (hash, number) = (finality_target.hash(), *finality_target.number());
-			log::trace!(
-				target: LOG_TARGET,
-				"Going to try and finalize header {:?}",
-				finality_target
-			);
-
-			SubmitFinalityProofHelper::<T, I>::check_obsolete(number)?;
-
-			let authority_set = <CurrentAuthoritySet<T, I>>::get();
-			let unused_proof_size = authority_set.unused_proof_size();
-			let set_id = authority_set.set_id;
-			let authority_set: AuthoritySet = authority_set.into();
-			verify_justification::<T, I>(&justification, hash, number, authority_set)?;
-
-			let maybe_new_authority_set =
-				try_enact_authority_change::<T, I>(&finality_target, set_id)?;
-			let may_refund_call_fee = maybe_new_authority_set.is_some() &&
-				// if we have seen too many mandatory headers in this block, we don't want to refund
+			let finality_proof_info =
+				submit_finality_proof_info_from_args::<T, I>(&finality_target, &justification);
+			let imported =
+				import_finality_target::<T, I>(finality_target, |hash, number, authority_set| {
+					verify_justification::<T, I>(&justification, hash, number, authority_set)
+				})?;
+
+			let is_free_header = imported.is_mandatory_header ||
+				SubmitFinalityProofHelper::<T, I>::is_free_interval_header(
+					imported.best_finalized_number,
+					imported.number,
+				);
+			let may_refund_call_fee = is_free_header &&
+				// if we have seen too many free headers in this block, we don't want to refund
 				Self::free_mandatory_headers_remaining() > 0 &&
 				// if arguments out of expected bounds, we don't want to refund
-				submit_finality_proof_info_from_args::<T, I>(&finality_target, &justification)
-					.fits_limits();
+				finality_proof_info.fits_limits();
 			if may_refund_call_fee {
 				FreeMandatoryHeadersRemaining::<T, I>::mutate(|count| {
 					*count = count.saturating_sub(1)
 				});
 			}
-			insert_header::<T, I>(*finality_target, hash);
-			log::info!(
-				target: LOG_TARGET,
-				"Successfully imported finalized header with hash {:?}!",
-				hash
-			);
 
 			// mandatory header is a header that changes authorities set. The pallet can't go
 			// further without importing this header. So every bridge MUST import mandatory headers.
 			//
 			// We don't want to charge extra costs for mandatory operations. So relayer is not
-			// paying fee for mandatory headers import transactions.
+			// paying fee for mandatory headers import transactions. The same applies to headers
+			// that are submitted at the `FreeHeadersInterval`, to keep the bridge alive.
 			//
 			// If size/weight of the call is exceeds our estimated limits, the relayer still needs
 			// to pay for the transaction.
@@ -236,14 +243,17 @@ pub mod pallet {
 				justification.votes_ancestries.len().saturated_into(),
 			);
 			let actual_weight = pre_dispatch_weight
-				.set_proof_size(pre_dispatch_weight.proof_size().saturating_sub(unused_proof_size));
+				.set_proof_size(
+					pre_dispatch_weight.proof_size().saturating_sub(imported.unused_proof_size),
+				)
+				.saturating_add(imported.pruning_weight);
 
 			Self::deposit_event(Event::UpdatedBestFinalizedHeader {
-				number,
-				hash,
+				number: imported.number,
+				hash: imported.hash,
 				grandpa_info: StoredHeaderGrandpaInfo {
 					finality_proof: justification,
-					new_verification_context: maybe_new_authority_set,
+					new_verification_context: imported.new_verification_context,
 				},
 			});
 
@@ -300,12 +310,122 @@ pub mod pallet {
 		) -> DispatchResult {
 			<Self as OwnedBridgeModule<_>>::set_operating_mode(origin, operating_mode)
 		}
+
+		/// Report equivocation of the bridged chain GRANDPA authority.
+		///
+		/// The equivocation proof is verified against the current authority set, known to the
+		/// pallet. Proofs are normally found by relayers, using `EquivocationsCollector` over
+		/// justifications from the bridged chain. The pallet itself can't slash bridged chain
+		/// authorities, so accepted reports are only recorded and announced with an event, to
+		/// be forwarded to the bridged chain.
+		///
+		/// The call fails if:
+		///
+		/// - the pallet is halted;
+		///
+		/// - the proof is not generated by current authority set or is invalid;
+		///
+		/// - equivocation of the same authority has already been reported in this set.
+		///
+		/// Valid reports are free for the submitter.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T::WeightInfo as WeightInfo>::report_equivocation())]
+		pub fn report_equivocation(
+			origin: OriginFor<T>,
+			equivocation_proof: Box<BridgedEquivocationProof<T, I>>,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			ensure_signed(origin)?;
+
+			let set_id = equivocation_proof.set_id();
+			let offender = equivocation_proof.offender().clone();
+
+			let authority_set = <CurrentAuthoritySet<T, I>>::get();
+			ensure!(set_id == authority_set.set_id, <Error<T, I>>::InvalidEquivocationProof);
+			ensure!(
+				authority_set.authorities.iter().any(|(id, _)| *id == offender),
+				<Error<T, I>>::InvalidEquivocationProof
+			);
+			ensure!(
+				!<ReportedEquivocations<T, I>>::contains_key(set_id, &offender),
+				<Error<T, I>>::DuplicateEquivocationReport
+			);
+			ensure!(
+				sp_consensus_grandpa::check_equivocation_proof(*equivocation_proof),
+				<Error<T, I>>::InvalidEquivocationProof
+			);
+
+			<ReportedEquivocations<T, I>>::insert(set_id, &offender, ());
+			log::info!(
+				target: LOG_TARGET,
+				"Accepted equivocation report of authority {:?} from set {}",
+				offender,
+				set_id,
+			);
+
+			Self::deposit_event(Event::EquivocationReported { set_id, offender });
+
+			Ok(Pays::No.into())
+		}
+
+		/// Verify a target header is finalized according to the given finality proof with
+		/// half-aggregated precommit signatures.
+		///
+		/// The call works exactly like the `submit_finality_proof`, but accepts the
+		/// [`AggregatedGrandpaJustification`], which is prepared off-chain by relayers from the
+		/// regular justification. It is much smaller than the regular justification, but its
+		/// verification is more expensive, because no host functions are used. So this call is
+		/// never refunded, even if the header is mandatory.
+		///
+		/// The call fails with `AggregatedJustificationsNotSupported` if the pallet is compiled
+		/// without the `aggregated-signatures` feature.
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T::WeightInfo as WeightInfo>::submit_aggregated_finality_proof(
+			justification.precommits.len().saturated_into(),
+			justification.votes_ancestries.len().saturated_into(),
+		))]
+		pub fn submit_aggregated_finality_proof(
+			origin: OriginFor<T>,
+			finality_target: Box<BridgedHeader<T, I>>,
+			justification: AggregatedGrandpaJustification<BridgedHeader<T, I>>,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			ensure_signed(origin)?;
+
+			let imported =
+				import_finality_target::<T, I>(finality_target, |hash, number, authority_set| {
+					verify_aggregated_justification::<T, I>(
+						&justification,
+						hash,
+						number,
+						authority_set,
+					)
+				})?;
+
+			let pre_dispatch_weight = T::WeightInfo::submit_aggregated_finality_proof(
+				justification.precommits.len().saturated_into(),
+				justification.votes_ancestries.len().saturated_into(),
+			);
+			let actual_weight = pre_dispatch_weight
+				.set_proof_size(
+					pre_dispatch_weight.proof_size().saturating_sub(imported.unused_proof_size),
+				)
+				.saturating_add(imported.pruning_weight);
+
+			Self::deposit_event(Event::UpdatedBestFinalizedHeaderWithAggregatedProof {
+				number: imported.number,
+				hash: imported.hash,
+				new_verification_context: imported.new_verification_context,
+			});
+
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
+		}
 	}
 
-	/// Number mandatory headers that we may accept in the current block for free (returning
-	/// `Pays::No`).
+	/// Number of mandatory headers (or headers at `FreeHeadersInterval`) that we may accept in
+	/// the current block for free (returning `Pays::No`).
 	///
-	/// If the `FreeMandatoryHeadersRemaining` hits zero, all following mandatory headers in the
+	/// If the `FreeMandatoryHeadersRemaining` hits zero, all following free headers in the
 	/// current block are accepted with fee (`Pays::Yes` is returned).
 	///
 	/// The `FreeMandatoryHeadersRemaining` is an ephemeral value that is set to
@@ -360,6 +480,24 @@ pub mod pallet {
 	pub type CurrentAuthoritySet<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, StoredAuthoritySet<T, I>, ValueQuery>;
 
+	/// Authorities set change, forced by the bridged chain and not yet enacted.
+	///
+	/// Forced changes are enacted without waiting for finality, once the bridged chain reaches
+	/// the `enact_at` block. So justifications of all headers starting from this block are
+	/// generated by the new authorities set.
+	#[pallet::storage]
+	pub type PendingForcedChange<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, StoredPendingForcedChange<T, I>, OptionQuery>;
+
+	/// Bridged chain GRANDPA authorities, which equivocations have been reported to the pallet.
+	///
+	/// Only equivocations of authorities from the current set may be reported, so the
+	/// map will never have more than `MAX_AUTHORITIES_COUNT` entries for every set. The entries
+	/// of a set are removed once it is replaced.
+	#[pallet::storage]
+	pub type ReportedEquivocations<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Identity, SetId, Blake2_128Concat, AuthorityId, (), OptionQuery>;
+
 	/// Optional pallet owner.
 	///
 	/// Pallet owner has a right to halt all pallet operations and then resume it. If it is
@@ -413,6 +551,22 @@ pub mod pallet {
 			/// The Grandpa info associated to the new best finalized header.
 			grandpa_info: StoredHeaderGrandpaInfo<BridgedHeader<T, I>>,
 		},
+		/// Best finalized chain header has been updated to the header with given number and hash,
+		/// using the finality proof with aggregated signatures.
+		UpdatedBestFinalizedHeaderWithAggregatedProof {
+			number: BridgedBlockNumber<T, I>,
+			hash: BridgedBlockHash<T, I>,
+			/// Authority set that will be used to verify the next header, if it has been changed
+			/// by the new best finalized header.
+			new_verification_context: Option<AuthoritySet>,
+		},
+		/// Equivocation of the bridged chain GRANDPA authority has been reported.
+		EquivocationReported {
+			/// Identifier of the authority set that the offender belongs to.
+			set_id: SetId,
+			/// The equivocating authority.
+			offender: AuthorityId,
+		},
 	}
 
 	#[pallet::error]
@@ -425,7 +579,7 @@ pub mod pallet {
 		OldHeader,
 		/// The scheduled authority set change found in the header is unsupported by the pallet.
 		///
-		/// This is the case for non-standard (e.g forced) authority set changes.
+		/// This is the case for scheduled authority set changes with non-zero delay.
 		UnsupportedScheduledChange,
 		/// The pallet is not yet initialized.
 		NotInitialized,
@@ -435,28 +589,28 @@ pub mod pallet {
 		TooManyAuthoritiesInSet,
 		/// Error generated by the `OwnedBridgeModule` trait.
 		BridgeModule(bp_runtime::OwnedBridgeModuleError),
+		/// The equivocation proof is invalid or is not generated by the current authority set.
+		InvalidEquivocationProof,
+		/// Equivocation of the same authority has already been reported.
+		DuplicateEquivocationReport,
+		/// The header signals both forced and scheduled authority set changes.
+		ConflictingAuthoritySetChanges,
+		/// The pallet is compiled without support of justifications with aggregated signatures.
+		AggregatedJustificationsNotSupported,
 	}
 
 	/// Check the given header for a GRANDPA scheduled authority set change. If a change
 	/// is found it will be enacted immediately.
 	///
-	/// This function does not support forced changes, or scheduled changes with delays
-	/// since these types of changes are indicative of abnormal behavior from GRANDPA.
+	/// This function does not support scheduled changes with delays since these types of changes
+	/// are indicative of abnormal behavior from GRANDPA. Forced changes are handled separately,
+	/// by the `find_forced_change` and `active_forced_change`.
 	///
 	/// Returned value will indicate if a change was enacted or not.
 	pub(crate) fn try_enact_authority_change<T: Config<I>, I: 'static>(
 		header: &BridgedHeader<T, I>,
 		current_set_id: sp_consensus_grandpa::SetId,
 	) -> Result<Option<AuthoritySet>, DispatchError> {
-		// We don't support forced changes - at that point governance intervention is required.
-		ensure!(
-			GrandpaConsensusLogReader::<BridgedBlockNumber<T, I>>::find_forced_change(
-				header.digest()
-			)
-			.is_none(),
-			<Error<T, I>>::UnsupportedScheduledChange
-		);
-
 		if let Some(change) =
 			GrandpaConsensusLogReader::<BridgedBlockNumber<T, I>>::find_scheduled_change(
 				header.digest(),
@@ -491,6 +645,205 @@ pub mod pallet {
 		Ok(None)
 	}
 
+	/// Returns GRANDPA forced authority set change, signalled by the given header.
+	///
+	/// The change is active starting from the `enact_at` block, which is the header itself if
+	/// the change has zero delay. Headers that signal both forced and scheduled changes are
+	/// rejected, because the bridged chain is not expected to produce them and it is unclear
+	/// which set would finalize the following headers.
+	pub(crate) fn find_forced_change<T: Config<I>, I: 'static>(
+		header: &BridgedHeader<T, I>,
+	) -> Result<Option<StoredPendingForcedChange<T, I>>, DispatchError> {
+		let Some((_, change)) =
+			GrandpaConsensusLogReader::<BridgedBlockNumber<T, I>>::find_forced_change(
+				header.digest(),
+			)
+		else {
+			return Ok(None)
+		};
+
+		ensure!(
+			GrandpaConsensusLogReader::<BridgedBlockNumber<T, I>>::find_scheduled_change(
+				header.digest()
+			)
+			.is_none(),
+			<Error<T, I>>::ConflictingAuthoritySetChanges
+		);
+
+		let authorities = change
+			.next_authorities
+			.try_into()
+			.map_err(|_| Error::<T, I>::TooManyAuthoritiesInSet)?;
+		Ok(Some(StoredPendingForcedChange::<T, I> {
+			enact_at: header.number().saturating_add(change.delay),
+			authorities,
+		}))
+	}
+
+	/// Save forced change with non-zero delay to the `PendingForcedChange`.
+	pub(crate) fn schedule_forced_change<T: Config<I>, I: 'static>(
+		header: &BridgedHeader<T, I>,
+		pending_change: StoredPendingForcedChange<T, I>,
+	) {
+		log::info!(
+			target: LOG_TARGET,
+			"Header {:?} forces authority set change at block {:?}. New authorities are: {:?}",
+			header.hash(),
+			pending_change.enact_at,
+			pending_change.authorities,
+		);
+
+		<PendingForcedChange<T, I>>::put(pending_change);
+	}
+
+	/// Returns authorities set of the forced change, if it is active at the given bridged chain
+	/// block.
+	///
+	/// The change, signalled by the block itself, takes precedence over the
+	/// `PendingForcedChange`, signalled by one of previous blocks.
+	pub(crate) fn active_forced_change<T: Config<I>, I: 'static>(
+		signalled_change: Option<&StoredPendingForcedChange<T, I>>,
+		number: BridgedBlockNumber<T, I>,
+		current_set_id: sp_consensus_grandpa::SetId,
+	) -> Option<StoredAuthoritySet<T, I>> {
+		let active_change = |change: &StoredPendingForcedChange<T, I>| {
+			(change.enact_at <= number).then(|| change.authorities.clone())
+		};
+		let authorities = signalled_change
+			.and_then(active_change)
+			.or_else(|| <PendingForcedChange<T, I>>::get().as_ref().and_then(active_change))?;
+
+		// TODO [#788]: Stop manually increasing the `set_id` here.
+		Some(StoredAuthoritySet::<T, I> { authorities, set_id: current_set_id + 1 })
+	}
+
+	/// Enact authorities set of the active forced change.
+	pub(crate) fn enact_forced_change<T: Config<I>, I: 'static>(
+		next_authorities: StoredAuthoritySet<T, I>,
+	) -> AuthoritySet {
+		<CurrentAuthoritySet<T, I>>::put(&next_authorities);
+		<PendingForcedChange<T, I>>::kill();
+
+		log::info!(
+			target: LOG_TARGET,
+			"Transitioned to forced authority set {}! New authorities are: {:?}",
+			next_authorities.set_id,
+			next_authorities,
+		);
+
+		next_authorities.into()
+	}
+
+	/// Header, that has been imported by the [`import_finality_target`].
+	pub(crate) struct ImportedFinalityTarget<T: Config<I>, I: 'static> {
+		/// Number of the imported header.
+		pub number: BridgedBlockNumber<T, I>,
+		/// Hash of the imported header.
+		pub hash: BridgedBlockHash<T, I>,
+		/// Number of the best finalized header before import.
+		pub best_finalized_number: BridgedBlockNumber<T, I>,
+		/// True if the header has enacted authority set change.
+		pub is_mandatory_header: bool,
+		/// Authority set, enacted by the header.
+		pub new_verification_context: Option<AuthoritySet>,
+		/// Unused proof size of the `CurrentAuthoritySet` read.
+		pub unused_proof_size: u64,
+		/// Weight of the removal of the equivocation reports of the replaced authority sets.
+		pub pruning_weight: Weight,
+	}
+
+	/// Verify finality proof of the given header using the `verify` function and import it.
+	///
+	/// The `verify` function is called with the hash and number of the `finality_target` and
+	/// the authority set that must have generated the finality proof. All authority set
+	/// changes, scheduled or forced by the header, are enacted after successful verification.
+	pub(crate) fn import_finality_target<T: Config<I>, I: 'static>(
+		finality_target: Box<BridgedHeader<T, I>>,
+		verify: impl FnOnce(
+			BridgedBlockHash<T, I>,
+			BridgedBlockNumber<T, I>,
+			AuthoritySet,
+		) -> Result<(), DispatchError>,
+	) -> Result<ImportedFinalityTarget<T, I>, DispatchError> {
+		let (hash, number) = (finality_target.hash(), *finality_target.number());
+		log::trace!(target: LOG_TARGET, "Going to try and finalize header {:?}", finality_target);
+
+		let best_finalized_number = SubmitFinalityProofHelper::<T, I>::check_obsolete(number)?;
+
+		// forced change, that has been signalled by one of previous headers or by the
+		// finality target itself (with zero delay), may already be active at the finality
+		// target and then the justification is generated by the new authorities set
+		let current_authority_set = <CurrentAuthoritySet<T, I>>::get();
+		let previous_set_id = current_authority_set.set_id;
+		let signalled_forced_change = find_forced_change::<T, I>(&finality_target)?;
+		let forced_authority_set = active_forced_change::<T, I>(
+			signalled_forced_change.as_ref(),
+			number,
+			current_authority_set.set_id,
+		);
+		let authority_set = forced_authority_set.clone().unwrap_or(current_authority_set);
+		let unused_proof_size = authority_set.unused_proof_size();
+		let set_id = authority_set.set_id;
+		verify(hash, number, authority_set.into())?;
+
+		let enacted_forced_change = forced_authority_set.map(enact_forced_change::<T, I>);
+		if let Some(pending_change) =
+			signalled_forced_change.filter(|change| change.enact_at > number)
+		{
+			schedule_forced_change::<T, I>(&finality_target, pending_change);
+		}
+
+		let is_mandatory_header = enacted_forced_change.is_some() ||
+			GrandpaConsensusLogReader::<BridgedBlockNumber<T, I>>::schedules_authorities_change(
+				finality_target.digest(),
+			);
+		let new_verification_context =
+			try_enact_authority_change::<T, I>(&finality_target, set_id)?.or(enacted_forced_change);
+		let pruning_weight = clear_reported_equivocations::<T, I>(
+			previous_set_id,
+			<CurrentAuthoritySet<T, I>>::get().set_id,
+		);
+		insert_header::<T, I>(*finality_target, hash);
+		log::info!(
+			target: LOG_TARGET,
+			"Successfully imported finalized header with hash {:?}!",
+			hash
+		);
+
+		Ok(ImportedFinalityTarget {
+			number,
+			hash,
+			best_finalized_number,
+			is_mandatory_header,
+			new_verification_context,
+			unused_proof_size,
+			pruning_weight,
+		})
+	}
+
+	/// Removes the equivocation reports of the authority sets from `previous_set_id` up to, but
+	/// excluding, `current_set_id`. Those sets have been replaced and their authorities can't be
+	/// reported anymore.
+	///
+	/// Returns the weight consumed.
+	pub(crate) fn clear_reported_equivocations<T: Config<I>, I: 'static>(
+		previous_set_id: SetId,
+		current_set_id: SetId,
+	) -> Weight {
+		let mut weight = Weight::zero();
+		for set_id in previous_set_id..current_set_id {
+			let removed = <ReportedEquivocations<T, I>>::clear_prefix(
+				set_id,
+				T::BridgedChain::MAX_AUTHORITIES_COUNT,
+				None,
+			);
+			weight.saturating_accrue(
+				T::DbWeight::get().reads_writes(removed.loops.into(), removed.backend.into()),
+			);
+		}
+		weight
+	}
+
 	/// Verify a GRANDPA justification (finality proof) for a given header.
 	///
 	/// Will use the GRANDPA current authorities known to the pallet.
@@ -521,6 +874,45 @@ pub mod pallet {
 		})?)
 	}
 
+	/// Verify a GRANDPA justification with aggregated signatures for a given header.
+	#[cfg(any(test, feature = "aggregated-signatures"))]
+	pub(crate) fn verify_aggregated_justification<T: Config<I>, I: 'static>(
+		justification: &AggregatedGrandpaJustification<BridgedHeader<T, I>>,
+		hash: BridgedBlockHash<T, I>,
+		number: BridgedBlockNumber<T, I>,
+		authority_set: bp_header_chain::AuthoritySet,
+	) -> Result<(), sp_runtime::DispatchError> {
+		use bp_header_chain::justification::verify_aggregated_justification;
+
+		Ok(verify_aggregated_justification::<BridgedHeader<T, I>>(
+			(hash, number),
+			&authority_set.try_into().map_err(|_| <Error<T, I>>::InvalidAuthoritySet)?,
+			justification,
+		)
+		.map_err(|e| {
+			log::error!(
+				target: LOG_TARGET,
+				"Received invalid aggregated justification for {:?}: {:?}",
+				hash,
+				e,
+			);
+			<Error<T, I>>::InvalidJustification
+		})?)
+	}
+
+	/// Verify a GRANDPA justification with aggregated signatures for a given header.
+	///
+	/// Always fails, because the pallet is compiled without the `aggregated-signatures` feature.
+	#[cfg(not(any(test, feature = "aggregated-signatures")))]
+	pub(crate) fn verify_aggregated_justification<T: Config<I>, I: 'static>(
+		_justification: &AggregatedGrandpaJustification<BridgedHeader<T, I>>,
+		_hash: BridgedBlockHash<T, I>,
+		_number: BridgedBlockNumber<T, I>,
+		_authority_set: bp_header_chain::AuthoritySet,
+	) -> Result<(), sp_runtime::DispatchError> {
+		Err(<Error<T, I>>::AggregatedJustificationsNotSupported.into())
+	}
+
 	/// Import a previously verified header to the storage.
 	///
 	/// Note this function solely takes care of updating the storage and pruning old entries,
@@ -657,11 +1049,12 @@ mod tests {
 		run_test, test_header, RuntimeEvent as TestEvent, RuntimeOrigin, System, TestBridgedChain,
 		TestHeader, TestNumber, TestRuntime, MAX_BRIDGED_AUTHORITIES,
 	};
-	use bp_header_chain::BridgeGrandpaCall;
+	use bp_header_chain::{justification::aggregate_justification, BridgeGrandpaCall};
 	use bp_runtime::BasicOperatingMode;
 	use bp_test_utils::{
-		authority_list, generate_owned_bridge_module_tests, make_default_justification,
-		make_justification_for_header, JustificationGeneratorParams, ALICE, BOB,
+		authority_list, generate_owned_bridge_module_tests, header_id, make_default_justification,
+		make_justification_for_header, precommit_equivocation_proof, JustificationGeneratorParams,
+		ALICE, BOB, EVE, TEST_GRANDPA_ROUND,
 	};
 	use codec::Encode;
 	use frame_support::{
@@ -947,6 +1340,125 @@ mod tests {
 		})
 	}
 
+	fn submit_aggregated_finality_proof(
+		header: TestHeader,
+		justification: GrandpaJustification<TestHeader>,
+		set_id: u64,
+	) -> frame_support::dispatch::DispatchResultWithPostInfo {
+		Pallet::<TestRuntime>::submit_aggregated_finality_proof(
+			RuntimeOrigin::signed(1),
+			Box::new(header),
+			aggregate_justification(&justification, set_id).unwrap(),
+		)
+	}
+
+	#[test]
+	fn succesfully_imports_header_with_valid_aggregated_finality() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let header = test_header(1);
+			let justification = make_default_justification(&header);
+			let result = submit_aggregated_finality_proof(header.clone(), justification, 1);
+			assert_ok!(result);
+			// aggregated proofs are never refunded
+			assert_eq!(result.unwrap().pays_fee, frame_support::dispatch::Pays::Yes);
+
+			assert_eq!(<BestFinalized<TestRuntime>>::get().unwrap().1, header.hash());
+			assert!(<ImportedHeaders<TestRuntime>>::contains_key(header.hash()));
+			assert_eq!(
+				System::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Grandpa(
+						Event::UpdatedBestFinalizedHeaderWithAggregatedProof {
+							number: *header.number(),
+							hash: header.hash(),
+							new_verification_context: None,
+						}
+					),
+					topics: vec![],
+				}],
+			);
+		})
+	}
+
+	#[test]
+	fn does_not_import_header_with_invalid_aggregated_finality_proof() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let header = test_header(1);
+			let mut justification =
+				aggregate_justification(&make_default_justification(&header), 1).unwrap();
+			justification.aggregated_signature = [0u8; 32];
+
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_aggregated_finality_proof(
+					RuntimeOrigin::signed(1),
+					Box::new(header.clone()),
+					justification,
+				),
+				<Error<TestRuntime>>::InvalidJustification
+			);
+
+			// justification, signed by the wrong set is also rejected
+			assert_noop!(
+				submit_aggregated_finality_proof(
+					header.clone(),
+					make_justification_for_header(JustificationGeneratorParams {
+						header,
+						set_id: 2,
+						..Default::default()
+					}),
+					2,
+				),
+				<Error<TestRuntime>>::InvalidJustification
+			);
+		})
+	}
+
+	#[test]
+	fn importing_header_with_aggregated_finality_enacts_new_authority_set() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let next_set_id = 2;
+			let next_authorities = vec![(ALICE.into(), 1), (BOB.into(), 1)];
+
+			// Need to update the header digest to indicate that our header signals an authority
+			// set change. The change will be enacted when we import our header.
+			let mut header = test_header(2);
+			header.digest = change_log(0);
+			let justification = make_default_justification(&header);
+
+			assert_ok!(submit_aggregated_finality_proof(header.clone(), justification, 1));
+
+			// the header, signed by the current set, enacts the new set
+			assert_eq!(<BestFinalized<TestRuntime>>::get().unwrap().1, header.hash());
+			assert_eq!(
+				<CurrentAuthoritySet<TestRuntime>>::get(),
+				StoredAuthoritySet::<TestRuntime, ()>::try_new(next_authorities, next_set_id)
+					.unwrap(),
+			);
+		})
+	}
+
+	#[test]
+	fn rejects_obsolete_header_with_aggregated_finality() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			assert_ok!(submit_finality_proof(2));
+
+			let header = test_header(1);
+			let justification = make_default_justification(&header);
+			assert_noop!(
+				submit_aggregated_finality_proof(header, justification, 1),
+				<Error<TestRuntime>>::OldHeader
+			);
+		})
+	}
+
 	#[test]
 	fn rejects_justification_that_skips_authority_set_transition() {
 		run_test(|| {
@@ -1179,7 +1691,7 @@ mod tests {
 	}
 
 	#[test]
-	fn importing_header_rejects_header_with_forced_changes() {
+	fn importing_header_enacts_forced_change_with_zero_delay() {
 		run_test(|| {
 			initialize_substrate_bridge();
 
@@ -1188,21 +1700,149 @@ mod tests {
 			let mut header = test_header(2);
 			header.digest = forced_change_log(0);
 
-			// Create a valid justification for the header
+			// The change is active at the header itself, so it is finalized by the new set
+			let justification = make_justification_for_header(JustificationGeneratorParams {
+				header: header.clone(),
+				set_id: 2,
+				authorities: vec![(ALICE, 1), (BOB, 1)],
+				..Default::default()
+			});
+
+			// Let's import our test header
+			let result = Pallet::<TestRuntime>::submit_finality_proof(
+				RuntimeOrigin::signed(1),
+				Box::new(header.clone()),
+				justification,
+			);
+			assert_ok!(result);
+			assert_eq!(result.unwrap().pays_fee, frame_support::dispatch::Pays::No);
+
+			// The forced change is enacted immediately
+			assert_eq!(
+				<CurrentAuthoritySet<TestRuntime>>::get(),
+				StoredAuthoritySet::<TestRuntime, ()>::try_new(
+					vec![(ALICE.into(), 1), (BOB.into(), 1)],
+					2
+				)
+				.unwrap(),
+			);
+			assert_eq!(<PendingForcedChange<TestRuntime>>::get(), None);
+			assert_eq!(<BestFinalized<TestRuntime>>::get().unwrap().1, header.hash());
+		})
+	}
+
+	#[test]
+	fn importing_header_rejects_old_set_justification_of_forced_change_with_zero_delay() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut header = test_header(2);
+			header.digest = forced_change_log(0);
 			let justification = make_default_justification(&header);
 
-			// Should not be allowed to import this header
-			assert_err!(
+			assert_noop!(
 				Pallet::<TestRuntime>::submit_finality_proof(
 					RuntimeOrigin::signed(1),
 					Box::new(header),
-					justification
+					justification,
 				),
-				<Error<TestRuntime>>::UnsupportedScheduledChange
+				<Error<TestRuntime>>::InvalidJustification
 			);
 		})
 	}
 
+	#[test]
+	fn importing_header_rejects_header_with_forced_and_scheduled_changes() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut header = test_header(2);
+			header.digest = forced_change_log(2);
+			header.digest.logs.extend(change_log(0).logs);
+			let justification = make_default_justification(&header);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_finality_proof(
+					RuntimeOrigin::signed(1),
+					Box::new(header),
+					justification,
+				),
+				<Error<TestRuntime>>::ConflictingAuthoritySetChanges
+			);
+		})
+	}
+
+	#[test]
+	fn importing_header_enacts_forced_change_after_delay() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// header#2 forces authority set change at header#4
+			let mut header = test_header(2);
+			header.digest = forced_change_log(2);
+			let justification = make_default_justification(&header);
+			assert_ok!(Pallet::<TestRuntime>::submit_finality_proof(
+				RuntimeOrigin::signed(1),
+				Box::new(header),
+				justification,
+			));
+			assert_eq!(<CurrentAuthoritySet<TestRuntime>>::get().set_id, 1);
+			assert_eq!(<PendingForcedChange<TestRuntime>>::get().unwrap().enact_at, 4);
+
+			// header#3 is still finalized by the old set
+			assert_ok!(submit_finality_proof(3));
+			assert_eq!(<CurrentAuthoritySet<TestRuntime>>::get().set_id, 1);
+
+			// header#4 is finalized by the new set
+			let header = test_header(4);
+			let justification = make_justification_for_header(JustificationGeneratorParams {
+				header: header.clone(),
+				set_id: 2,
+				authorities: vec![(ALICE, 1), (BOB, 1)],
+				..Default::default()
+			});
+			let result = Pallet::<TestRuntime>::submit_finality_proof(
+				RuntimeOrigin::signed(1),
+				Box::new(header.clone()),
+				justification,
+			);
+			assert_ok!(result);
+			assert_eq!(result.unwrap().pays_fee, frame_support::dispatch::Pays::No);
+			assert_eq!(
+				<CurrentAuthoritySet<TestRuntime>>::get(),
+				StoredAuthoritySet::<TestRuntime, ()>::try_new(
+					vec![(ALICE.into(), 1), (BOB.into(), 1)],
+					2
+				)
+				.unwrap(),
+			);
+			assert_eq!(<PendingForcedChange<TestRuntime>>::get(), None);
+			assert_eq!(<BestFinalized<TestRuntime>>::get().unwrap().1, header.hash());
+		})
+	}
+
+	#[test]
+	fn importing_header_rejects_old_set_justification_once_forced_change_is_active() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// header#2 forces authority set change at header#4
+			let mut header = test_header(2);
+			header.digest = forced_change_log(2);
+			let justification = make_default_justification(&header);
+			assert_ok!(Pallet::<TestRuntime>::submit_finality_proof(
+				RuntimeOrigin::signed(1),
+				Box::new(header),
+				justification,
+			));
+
+			// header#5 can't be finalized by the old set
+			assert_noop!(submit_finality_proof(5), <Error<TestRuntime>>::InvalidJustification);
+			assert_eq!(<CurrentAuthoritySet<TestRuntime>>::get().set_id, 1);
+			assert!(<PendingForcedChange<TestRuntime>>::get().is_some());
+		})
+	}
+
 	#[test]
 	fn importing_header_rejects_header_with_too_many_authorities() {
 		run_test(|| {
@@ -1359,6 +1999,46 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn headers_at_free_headers_interval_are_accepted_for_free() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// header#31 improves best finalized header by less than `FreeHeadersInterval`
+			let result = submit_finality_proof(31);
+			assert_eq!(result.expect("call failed").pays_fee, Pays::Yes);
+
+			// header#63 improves best finalized header by `FreeHeadersInterval`
+			let result = submit_finality_proof(63);
+			assert_eq!(result.expect("call failed").pays_fee, Pays::No);
+
+			// header#100 improves best finalized header by more than `FreeHeadersInterval`
+			let result = submit_finality_proof(100);
+			assert_eq!(result.expect("call failed").pays_fee, Pays::No);
+		})
+	}
+
+	#[test]
+	fn headers_at_free_headers_interval_are_counted_towards_free_headers_limit() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let result = submit_finality_proof(32);
+			assert_eq!(result.expect("call failed").pays_fee, Pays::No);
+
+			let result = submit_mandatory_finality_proof(33, 1);
+			assert_eq!(result.expect("call failed").pays_fee, Pays::No);
+
+			let result = submit_finality_proof_with_set_id(65, 2);
+			assert_eq!(result.expect("call failed").pays_fee, Pays::Yes);
+
+			next_block();
+
+			let result = submit_finality_proof_with_set_id(97, 2);
+			assert_eq!(result.expect("call failed").pays_fee, Pays::No);
+		})
+	}
+
 	#[test]
 	fn should_prune_headers_over_headers_to_keep_parameter() {
 		run_test(|| {
@@ -1385,6 +2065,151 @@ mod tests {
 		})
 	}
 
+	fn equivocation_proof_by(
+		signer: &bp_test_utils::Account,
+		set_id: SetId,
+	) -> Box<BridgedEquivocationProof<TestRuntime, ()>> {
+		Box::new(precommit_equivocation_proof::<TestHeader>(
+			signer,
+			header_id::<TestHeader>(1),
+			header_id::<TestHeader>(2),
+			TEST_GRANDPA_ROUND,
+			set_id,
+		))
+	}
+
+	#[test]
+	fn reports_valid_equivocation() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let result = Pallet::<TestRuntime>::report_equivocation(
+				RuntimeOrigin::signed(1),
+				equivocation_proof_by(&ALICE, 1),
+			);
+			assert_ok!(result);
+			assert_eq!(result.unwrap().pays_fee, Pays::No);
+			assert!(ReportedEquivocations::<TestRuntime>::contains_key(
+				1,
+				AuthorityId::from(ALICE)
+			));
+
+			assert_eq!(
+				System::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Grandpa(Event::EquivocationReported {
+						set_id: 1,
+						offender: ALICE.into(),
+					}),
+					topics: vec![],
+				}],
+			);
+		})
+	}
+
+	#[test]
+	fn rejects_duplicate_equivocation_report() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_ok!(Pallet::<TestRuntime>::report_equivocation(
+				RuntimeOrigin::signed(1),
+				equivocation_proof_by(&ALICE, 1),
+			));
+			assert_noop!(
+				Pallet::<TestRuntime>::report_equivocation(
+					RuntimeOrigin::signed(1),
+					equivocation_proof_by(&ALICE, 1),
+				),
+				Error::<TestRuntime>::DuplicateEquivocationReport,
+			);
+		})
+	}
+
+	#[test]
+	fn rejects_equivocation_report_from_other_authority_set() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_noop!(
+				Pallet::<TestRuntime>::report_equivocation(
+					RuntimeOrigin::signed(1),
+					equivocation_proof_by(&ALICE, 2),
+				),
+				Error::<TestRuntime>::InvalidEquivocationProof,
+			);
+		})
+	}
+
+	#[test]
+	fn rejects_equivocation_report_of_unknown_authority() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_noop!(
+				Pallet::<TestRuntime>::report_equivocation(
+					RuntimeOrigin::signed(1),
+					equivocation_proof_by(&EVE, 1),
+				),
+				Error::<TestRuntime>::InvalidEquivocationProof,
+			);
+		})
+	}
+
+	#[test]
+	fn equivocation_reports_are_removed_when_authority_set_changes() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_ok!(Pallet::<TestRuntime>::report_equivocation(
+				RuntimeOrigin::signed(1),
+				equivocation_proof_by(&ALICE, 1),
+			));
+			assert_ok!(submit_mandatory_finality_proof(1, 1));
+			assert_eq!(<CurrentAuthoritySet<TestRuntime>>::get().set_id, 2);
+			assert!(!ReportedEquivocations::<TestRuntime>::contains_key(
+				1,
+				AuthorityId::from(ALICE)
+			));
+
+			// reports of the new set are kept until it is replaced too
+			assert_ok!(Pallet::<TestRuntime>::report_equivocation(
+				RuntimeOrigin::signed(1),
+				equivocation_proof_by(&ALICE, 2),
+			));
+			assert!(ReportedEquivocations::<TestRuntime>::contains_key(
+				2,
+				AuthorityId::from(ALICE)
+			));
+			next_block();
+			assert_ok!(submit_mandatory_finality_proof(2, 2));
+			assert_eq!(ReportedEquivocations::<TestRuntime>::iter().count(), 0);
+		})
+	}
+
+	#[test]
+	fn rejects_equivocation_report_with_identical_votes() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let equivocation_proof = precommit_equivocation_proof::<TestHeader>(
+				&ALICE,
+				header_id::<TestHeader>(1),
+				header_id::<TestHeader>(1),
+				TEST_GRANDPA_ROUND,
+				1,
+			);
+			assert_noop!(
+				Pallet::<TestRuntime>::report_equivocation(
+					RuntimeOrigin::signed(1),
+					Box::new(equivocation_proof),
+				),
+				Error::<TestRuntime>::InvalidEquivocationProof,
+			);
+		})
+	}
+
 	#[test]
 	fn storage_keys_computed_properly() {
 		assert_eq!(
@@ -1432,6 +2257,40 @@ mod tests {
 			direct_submit_finality_proof_call.encode(),
 			indirect_submit_finality_proof_call.encode()
 		);
+
+		let equivocation_proof = Box::new(precommit_equivocation_proof::<TestHeader>(
+			&ALICE,
+			header_id::<TestHeader>(1),
+			header_id::<TestHeader>(2),
+			TEST_GRANDPA_ROUND,
+			1,
+		));
+		let direct_report_equivocation_call = Call::<TestRuntime>::report_equivocation {
+			equivocation_proof: equivocation_proof.clone(),
+		};
+		let indirect_report_equivocation_call =
+			BridgeGrandpaCall::<TestHeader>::report_equivocation { equivocation_proof };
+		assert_eq!(
+			direct_report_equivocation_call.encode(),
+			indirect_report_equivocation_call.encode()
+		);
+
+		let aggregated_justification =
+			aggregate_justification(&make_default_justification(&test_header(1)), 1).unwrap();
+		let direct_submit_aggregated_finality_proof_call =
+			Call::<TestRuntime>::submit_aggregated_finality_proof {
+				finality_target: Box::new(test_header(1)),
+				justification: aggregated_justification.clone(),
+			};
+		let indirect_submit_aggregated_finality_proof_call =
+			BridgeGrandpaCall::<TestHeader>::submit_aggregated_finality_proof {
+				finality_target: Box::new(test_header(1)),
+				justification: aggregated_justification,
+			};
+		assert_eq!(
+			direct_submit_aggregated_finality_proof_call.encode(),
+			indirect_submit_aggregated_finality_proof_call.encode()
+		);
 	}
 
 	generate_owned_bridge_module_tests!(BasicOperatingMode::Normal, BasicOperatingMode::Halted);
@@ -1441,6 +2300,22 @@ mod tests {
 		assert_eq!(MaybeHeadersToKeep::<TestRuntime, ()>::get(), Some(mock::HeadersToKeep::get()));
 	}
 
+	#[test]
+	fn justifications_batch_weight_charges_shared_votes_ancestries_once() {
+		// 4 justifications of consecutive headers, sharing the same 8 votes ancestries headers
+		let one_by_one =
+			<TestRuntime as Config>::WeightInfo::submit_finality_proof(3, 8).saturating_mul(4);
+		let batch = <TestRuntime as Config>::WeightInfo::verify_justifications_batch(4, 3, 8);
+		assert!(batch.all_lte(one_by_one));
+		assert!(batch.ref_time() < one_by_one.ref_time());
+
+		// a batch of a single justification costs the same as a separate call
+		assert_eq!(
+			<TestRuntime as Config>::WeightInfo::verify_justifications_batch(1, 3, 8),
+			<TestRuntime as Config>::WeightInfo::submit_finality_proof(3, 8),
+		);
+	}
+
 	#[test]
 	fn submit_finality_proof_requires_signed_origin() {
 		run_test(|| {
@@ -83,6 +83,7 @@ impl frame_system::Config for TestRuntime {
 
 parameter_types! {
 	pub const MaxFreeMandatoryHeadersPerBlock: u32 = 2;
+	pub const FreeHeadersInterval: Option<u32> = Some(32);
 	pub const HeadersToKeep: u32 = 5;
 	pub const SessionLength: u64 = 5;
 	pub const NumValidators: u32 = 5;
@@ -92,6 +93,7 @@ impl grandpa::Config for TestRuntime {
 	type RuntimeEvent = RuntimeEvent;
 	type BridgedChain = TestBridgedChain;
 	type MaxFreeMandatoryHeadersPerBlock = MaxFreeMandatoryHeadersPerBlock;
+	type FreeHeadersInterval = FreeHeadersInterval;
 	type HeadersToKeep = HeadersToKeep;
 	type WeightInfo = ();
 }
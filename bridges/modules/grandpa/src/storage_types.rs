@@ -16,11 +16,13 @@
 
 //! Wrappers for public types that are implementing `MaxEncodedLen`
 
-use crate::{Config, Error};
+use crate::{BridgedBlockNumber, Config, Error};
 
 use bp_header_chain::{AuthoritySet, ChainWithGrandpa};
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::{traits::Get, BoundedVec, CloneNoBound, RuntimeDebugNoBound};
+use frame_support::{
+	traits::Get, BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
 use scale_info::TypeInfo;
 use sp_consensus_grandpa::{AuthorityId, AuthorityList, AuthorityWeight, SetId};
 use sp_std::marker::PhantomData;
@@ -97,6 +99,25 @@ impl<T: Config<I>, I: 'static> From<StoredAuthoritySet<T, I>> for AuthoritySet {
 	}
 }
 
+/// A bounded GRANDPA authorities set, forced by the bridged chain, that is waiting for enactment.
+#[derive(
+	CloneNoBound,
+	Decode,
+	Encode,
+	EqNoBound,
+	PartialEqNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+	RuntimeDebugNoBound,
+)]
+#[scale_info(skip_type_params(T, I))]
+pub struct StoredPendingForcedChange<T: Config<I>, I: 'static> {
+	/// Number of the bridged chain block, starting from which the new authorities are active.
+	pub enact_at: BridgedBlockNumber<T, I>,
+	/// List of GRANDPA authorities that will be active after enactment.
+	pub authorities: StoredAuthorityList<StoredAuthorityListLimit<T, I>>,
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::mock::{TestRuntime, MAX_BRIDGED_AUTHORITIES};
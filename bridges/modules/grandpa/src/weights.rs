@@ -21,6 +21,8 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `covid`, CPU: `11th Gen Intel(R) Core(TM) i7-11800H @ 2.30GHz`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! The weight of `report_equivocation` is an estimation, it has not been benchmarked yet.
 
 // Executed Command:
 // target/release/millau-bridge-node
@@ -51,6 +53,26 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for pallet_bridge_grandpa.
 pub trait WeightInfo {
 	fn submit_finality_proof(p: u32, v: u32) -> Weight;
+	fn report_equivocation() -> Weight;
+	/// Weight of the `submit_aggregated_finality_proof` call.
+	///
+	/// Until the `submit_aggregated_finality_proof` benchmark results are available for the
+	/// runtime, it is the same as the weight of `submit_finality_proof` call.
+	fn submit_aggregated_finality_proof(p: u32, v: u32) -> Weight {
+		Self::submit_finality_proof(p, v)
+	}
+	/// Weight of verifying and importing `n` justifications with `p` precommits each, using
+	/// `bp_header_chain::justification::verify_justifications_batch`.
+	///
+	/// `v` is the number of distinct votes ancestries headers of the whole batch (see
+	/// `bp_header_chain::justification::AncestryHashes::len`). Every header is charged the weight
+	/// of `submit_finality_proof` without votes ancestries and the cost of votes ancestries is only
+	/// charged once for every distinct header, instead of once per justification.
+	fn verify_justifications_batch(n: u32, p: u32, v: u32) -> Weight {
+		let per_header = Self::submit_finality_proof(p, 0);
+		let votes_ancestries = Self::submit_finality_proof(p, v).saturating_sub(per_header);
+		per_header.saturating_mul(n.into()).saturating_add(votes_ancestries)
+	}
 }
 
 /// Weights for `pallet_bridge_grandpa` that are generated using one of the Bridge testnets.
@@ -109,6 +131,26 @@ impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(6_u64))
 			.saturating_add(T::DbWeight::get().writes(6_u64))
 	}
+
+	/// Storage: BridgeRialtoGrandpa PalletOperatingMode (r:1 w:0)
+	///
+	/// Proof: BridgeRialtoGrandpa PalletOperatingMode (max_values: Some(1), max_size: Some(1),
+	/// added: 496, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRialtoGrandpa CurrentAuthoritySet (r:1 w:0)
+	///
+	/// Proof: BridgeRialtoGrandpa CurrentAuthoritySet (max_values: Some(1), max_size: Some(209),
+	/// added: 704, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRialtoGrandpa ReportedEquivocations (r:1 w:1)
+	///
+	/// Proof: BridgeRialtoGrandpa ReportedEquivocations (max_values: None, max_size: Some(56),
+	/// added: 2531, mode: MaxEncodedLen)
+	fn report_equivocation() -> Weight {
+		Weight::from_parts(103_021_000, 3521)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -164,4 +206,24 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(6_u64))
 			.saturating_add(RocksDbWeight::get().writes(6_u64))
 	}
+
+	/// Storage: BridgeRialtoGrandpa PalletOperatingMode (r:1 w:0)
+	///
+	/// Proof: BridgeRialtoGrandpa PalletOperatingMode (max_values: Some(1), max_size: Some(1),
+	/// added: 496, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRialtoGrandpa CurrentAuthoritySet (r:1 w:0)
+	///
+	/// Proof: BridgeRialtoGrandpa CurrentAuthoritySet (max_values: Some(1), max_size: Some(209),
+	/// added: 704, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRialtoGrandpa ReportedEquivocations (r:1 w:1)
+	///
+	/// Proof: BridgeRialtoGrandpa ReportedEquivocations (max_values: None, max_size: Some(56),
+	/// added: 2531, mode: MaxEncodedLen)
+	fn report_equivocation() -> Weight {
+		Weight::from_parts(103_021_000, 3521)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
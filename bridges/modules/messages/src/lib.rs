@@ -43,7 +43,7 @@ pub use weights::WeightInfo;
 pub use weights_ext::{
 	ensure_able_to_receive_confirmation, ensure_able_to_receive_message,
 	ensure_weights_are_correct, WeightInfoExt, EXPECTED_DEFAULT_MESSAGE_LENGTH,
-	EXTRA_STORAGE_PROOF_SIZE,
+	EXTRA_STORAGE_PROOF_SIZE, SIGNED_EXTENSIONS_SIZE,
 };
 
 use crate::{
@@ -57,20 +57,20 @@ use bp_messages::{
 		SendMessageArtifacts, TargetHeaderChain,
 	},
 	target_chain::{
-		DeliveryPayments, DispatchMessage, MessageDispatch, ProvedLaneMessages, ProvedMessages,
-		SourceHeaderChain,
+		DeliveryPayments, DispatchMessage, InboundLanesQos, MessageDispatch, ProvedLaneMessages,
+		ProvedMessages, SourceHeaderChain,
 	},
-	DeliveredMessages, InboundLaneData, InboundMessageDetails, LaneId, MessageKey, MessageNonce,
-	MessagePayload, MessagesOperatingMode, OutboundLaneData, OutboundMessageDetails,
-	UnrewardedRelayersState, VerificationError,
+	DeliveredMessages, InboundLaneData, InboundMessageDetails, LaneId, LaneQosClass, LaneQosLimits,
+	LaneState, MessageKey, MessageNonce, MessagePayload, MessagesOperatingMode, OutboundLaneData,
+	OutboundMessageDetails, UnrewardedRelayersState, VerificationError,
 };
 use bp_runtime::{
 	BasicOperatingMode, ChainId, OwnedBridgeModule, PreComputedSize, RangeInclusiveExt, Size,
 };
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{dispatch::PostDispatchInfo, ensure, fail, traits::Get, DefaultNoBound};
-use sp_runtime::traits::UniqueSaturatedFrom;
-use sp_std::{marker::PhantomData, prelude::*};
+use sp_runtime::traits::{Saturating, UniqueSaturatedFrom, Zero};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, prelude::*};
 
 mod inbound_lane;
 mod outbound_lane;
@@ -89,6 +89,12 @@ pub use pallet::*;
 /// The target that will be used when publishing logs related to this pallet.
 pub const LOG_TARGET: &str = "runtime::bridge-messages";
 
+/// Maximal number of delivery confirmations, retained for every outbound lane.
+///
+/// If there are more confirmations, the two oldest confirmations are merged. So messages from
+/// the oldest confirmation are retained a bit longer than the `MessageRetentionBlocks`.
+pub const MAX_RETAINED_DELIVERY_CONFIRMATIONS: u32 = 64;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -161,6 +167,14 @@ pub mod pallet {
 		type DeliveryConfirmationPayments: DeliveryConfirmationPayments<Self::AccountId>;
 		/// Delivery confirmation callback.
 		type OnMessagesDelivered: OnMessagesDelivered;
+		/// Number of blocks, during which delivered outbound messages are kept in the storage.
+		///
+		/// Messages are pruned by the `on_idle` hook, once `MessageRetentionBlocks` blocks have
+		/// passed since their delivery confirmation. This allows to keep proofs of recent
+		/// deliveries, e.g. for disputes or audit. Zero means that messages are pruned right
+		/// after their delivery is confirmed.
+		#[pallet::constant]
+		type MessageRetentionBlocks: Get<BlockNumberFor<Self>>;
 
 		// Types that are used by inbound_lane (on target chain).
 
@@ -168,8 +182,16 @@ pub mod pallet {
 		type SourceHeaderChain: SourceHeaderChain;
 		/// Message dispatch.
 		type MessageDispatch: MessageDispatch<DispatchPayload = Self::InboundPayload>;
+		/// Quality of service classes of inbound lanes and their limits.
+		type InboundLanesQos: InboundLanesQos;
 	}
 
+	/// Delivery confirmations of the outbound lane, with the blocks where they have happened.
+	pub type RetainedDeliveryConfirmationsOf<T> = BoundedVec<
+		(BlockNumberFor<T>, DeliveredMessages),
+		ConstU32<MAX_RETAINED_DELIVERY_CONFIRMATIONS>,
+	>;
+
 	/// Shortcut to messages proof type for Config.
 	pub type MessagesProofOf<T, I> =
 		<<T as Config<I>>::SourceHeaderChain as SourceHeaderChain>::MessagesProof;
@@ -214,10 +236,35 @@ pub mod pallet {
 			let active_lane_id = active_lanes[active_lane_index as usize];
 
 			// first db read - outbound lane state
-			let mut active_lane = outbound_lane::<T, I>(active_lane_id);
 			let mut used_weight = db_weight.reads(1);
 			// and here we'll have writes
-			used_weight += active_lane.prune_messages(db_weight, remaining_weight - used_weight);
+			used_weight += Self::prune_outbound_lane_messages(
+				active_lane_id,
+				db_weight,
+				remaining_weight - used_weight,
+			);
+
+			// lanes that have been opened or closed at runtime are pruned after the configured
+			// lane, while we have enough `remaining_weight` to read the lane and prune at least
+			// one message. Closed lanes are pruned too, because they're still draining queued
+			// messages
+			let mut runtime_lanes = LaneStates::<T, I>::iter_keys();
+			while remaining_weight.all_gte(used_weight + db_weight.reads_writes(2, 2)) {
+				// every `next()` reads the next key of the `LaneStates` map, even if there's none
+				used_weight += db_weight.reads(1);
+				let Some(lane_id) = runtime_lanes.next() else { break };
+				if active_lanes.contains(&lane_id) {
+					continue
+				}
+
+				// `prune_outbound_lane_messages` reads the outbound lane state
+				used_weight += db_weight.reads(1);
+				used_weight += Self::prune_outbound_lane_messages(
+					lane_id,
+					db_weight,
+					remaining_weight - used_weight,
+				);
+			}
 
 			// we already checked we have enough `remaining_weight` to cover this `used_weight`
 			used_weight
@@ -269,7 +316,10 @@ pub mod pallet {
 		/// The call may succeed, but some messages may not be delivered e.g. if they are not fit
 		/// into the unrewarded relayers vector.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::receive_messages_proof_weight(proof, *messages_count, *dispatch_weight))]
+		#[pallet::weight(
+			T::WeightInfo::receive_messages_proof_weight(proof, *messages_count, *dispatch_weight)
+				.saturating_add(Pallet::<T, I>::qos_dispatch_weight_bookkeeping())
+		)]
 		pub fn receive_messages_proof(
 			origin: OriginFor<T>,
 			relayer_id_at_bridged_chain: T::InboundRelayer,
@@ -303,7 +353,8 @@ pub mod pallet {
 				&proof,
 				messages_count,
 				dispatch_weight,
-			);
+			)
+			.saturating_add(Self::qos_dispatch_weight_bookkeeping());
 			let mut actual_weight = declared_weight;
 
 			// verify messages proof && convert proof into messages
@@ -322,8 +373,13 @@ pub mod pallet {
 			let mut valid_messages = 0;
 			let mut messages_received_status = Vec::with_capacity(messages.len());
 			let mut dispatch_weight_left = dispatch_weight;
+			let block_number = frame_system::Pallet::<T>::block_number();
+			let mut qos_dispatch_weight_left = BTreeMap::new();
 			for (lane_id, lane_data) in messages {
 				let mut lane = inbound_lane::<T, I>(lane_id);
+				let lane_qos_class = T::InboundLanesQos::lane_class(&lane_id);
+				let lane_qos_limits = lane.storage_mut().qos_limits();
+				let mut is_qos_dispatch_weight_limit_reached = false;
 
 				// subtract extra storage proof bytes from the actual PoV size - there may be
 				// less unrewarded relayers than the maximal configured value
@@ -367,11 +423,46 @@ pub mod pallet {
 						fail!(Error::<T, I>::InsufficientDispatchWeight);
 					}
 
-					let receival_result = lane.receive_message::<T::MessageDispatch>(
-						&relayer_id_at_bridged_chain,
-						message.key.nonce,
-						message.data,
-					);
+					// ensure that the lane QoS class has enough dispatch weight left in this block.
+					// We can't dispatch lane messages out-of-order, so if it has no weight left,
+					// all remaining lane messages are not dispatched
+					if let Some(ref lane_qos_limits) = lane_qos_limits {
+						let class_dispatch_weight_left =
+							qos_dispatch_weight_left.entry(lane_qos_class).or_insert_with(|| {
+								let spent_weight =
+									InboundLanesQosDispatchWeight::<T, I>::get(lane_qos_class)
+										.filter(|(spent_at, _)| *spent_at == block_number)
+										.map(|(_, spent_weight)| spent_weight)
+										.unwrap_or_default();
+								lane_qos_limits.max_dispatch_weight.saturating_sub(spent_weight)
+							});
+						if is_qos_dispatch_weight_limit_reached ||
+							message_dispatch_weight.any_gt(*class_dispatch_weight_left)
+						{
+							log::trace!(
+								target: LOG_TARGET,
+								"Cannot dispatch any more messages on lane {:?} of class {:?}. Weight: declared={}, left={}",
+								lane_id,
+								lane_qos_class,
+								message_dispatch_weight,
+								class_dispatch_weight_left,
+							);
+
+							is_qos_dispatch_weight_limit_reached = true;
+						} else {
+							*class_dispatch_weight_left -= message_dispatch_weight;
+						}
+					}
+
+					let receival_result = if is_qos_dispatch_weight_limit_reached {
+						ReceivalResult::QosDispatchWeightLimitReached
+					} else {
+						lane.receive_message::<T::MessageDispatch>(
+							&relayer_id_at_bridged_chain,
+							message.key.nonce,
+							message.data,
+						)
+					};
 
 					// note that we're returning unspent weight to relayer even if message has been
 					// rejected by the lane. This allows relayers to submit spam transactions with
@@ -386,18 +477,40 @@ pub mod pallet {
 						},
 						ReceivalResult::InvalidNonce |
 						ReceivalResult::TooManyUnrewardedRelayers |
-						ReceivalResult::TooManyUnconfirmedMessages => message_dispatch_weight,
+						ReceivalResult::TooManyUnconfirmedMessages |
+						ReceivalResult::QosDispatchWeightLimitReached => message_dispatch_weight,
 					};
 					lane_messages_received_status.push(message.key.nonce, receival_result);
 
 					let unspent_weight = unspent_weight.min(message_dispatch_weight);
 					dispatch_weight_left -= message_dispatch_weight - unspent_weight;
 					actual_weight = actual_weight.saturating_sub(unspent_weight);
+					if !is_qos_dispatch_weight_limit_reached && lane_qos_limits.is_some() {
+						// unspent weight is returned back to the class budget
+						if let Some(class_dispatch_weight_left) =
+							qos_dispatch_weight_left.get_mut(&lane_qos_class)
+						{
+							*class_dispatch_weight_left =
+								class_dispatch_weight_left.saturating_add(unspent_weight);
+						}
+					}
 				}
 
 				messages_received_status.push(lane_messages_received_status);
 			}
 
+			// remember how much dispatch weight has been spent by every QoS class in this block,
+			// so that the following delivery transactions can't exceed the class budget
+			for (qos_class, class_dispatch_weight_left) in qos_dispatch_weight_left {
+				let max_dispatch_weight = T::InboundLanesQos::class_limits(qos_class)
+					.map(|limits| limits.max_dispatch_weight)
+					.unwrap_or_default();
+				InboundLanesQosDispatchWeight::<T, I>::insert(
+					qos_class,
+					(block_number, max_dispatch_weight.saturating_sub(class_dispatch_weight_left)),
+				);
+			}
+
 			// let's now deal with relayer payments
 			T::DeliveryPayments::pay_reward(
 				relayer_id_at_this_chain,
@@ -462,6 +575,9 @@ pub mod pallet {
 				.map_err(Error::<T, I>::ReceivalConfirmation)?;
 
 			if let Some(confirmed_messages) = confirmed_messages {
+				// remember when messages have been delivered, so that we can retain them
+				Self::retain_delivered_messages(lane_id, &confirmed_messages);
+
 				// emit 'delivered' event
 				let received_range = confirmed_messages.begin..=confirmed_messages.end;
 				Self::deposit_event(Event::MessagesDelivered {
@@ -511,6 +627,55 @@ pub mod pallet {
 
 			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
 		}
+
+		/// Open outbound lane, so that it starts accepting outbound messages.
+		///
+		/// May only be called either by root, or by `PalletOwner`. The lane must not be opened
+		/// already - i.e. it must either be closed, or be unknown to the pallet. Messages, that
+		/// have been queued before the lane has been closed, are delivered before new messages.
+		#[pallet::call_index(4)]
+		#[pallet::weight((T::DbWeight::get().reads_writes(1, 1), DispatchClass::Operational))]
+		pub fn open_lane(origin: OriginFor<T>, lane_id: LaneId) -> DispatchResult {
+			Self::ensure_owner_or_root(origin)?;
+			ensure!(!Self::is_outbound_lane_opened(lane_id), Error::<T, I>::LaneAlreadyOpened);
+
+			LaneStates::<T, I>::insert(lane_id, LaneState::Opened);
+
+			log::info!(target: LOG_TARGET, "Opened outbound lane {:?}", lane_id);
+
+			Self::deposit_event(Event::LaneOpened { lane_id });
+
+			Ok(())
+		}
+
+		/// Close outbound lane, so that it stops accepting outbound messages.
+		///
+		/// Messages, that are already queued at the lane, are drained: they are still delivered
+		/// to the bridged chain and pruned once their delivery is confirmed. So the lane may be
+		/// reopened at any time.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::call_index(5)]
+		#[pallet::weight((T::DbWeight::get().reads_writes(2, 1), DispatchClass::Operational))]
+		pub fn close_lane(origin: OriginFor<T>, lane_id: LaneId) -> DispatchResult {
+			Self::ensure_owner_or_root(origin)?;
+			ensure!(Self::is_outbound_lane_opened(lane_id), Error::<T, I>::LaneAlreadyClosed);
+
+			let queued_messages =
+				outbound_lane::<T, I>(lane_id).data().queued_messages().saturating_len();
+			LaneStates::<T, I>::insert(lane_id, LaneState::Closed);
+
+			log::info!(
+				target: LOG_TARGET,
+				"Closed outbound lane {:?}. Messages to drain: {}",
+				lane_id,
+				queued_messages,
+			);
+
+			Self::deposit_event(Event::LaneClosed { lane_id, queued_messages });
+
+			Ok(())
+		}
 	}
 
 	#[pallet::event]
@@ -524,6 +689,11 @@ pub mod pallet {
 		),
 		/// Messages in the inclusive range have been delivered to the bridged chain.
 		MessagesDelivered { lane_id: LaneId, messages: DeliveredMessages },
+		/// Outbound lane has been opened.
+		LaneOpened { lane_id: LaneId },
+		/// Outbound lane has been closed. Given number of queued messages is still going to be
+		/// delivered to the bridged chain.
+		LaneClosed { lane_id: LaneId, queued_messages: MessageNonce },
 	}
 
 	#[pallet::error]
@@ -560,6 +730,10 @@ pub mod pallet {
 		ReceivalConfirmation(ReceivalConfirmationError),
 		/// Error generated by the `OwnedBridgeModule` trait.
 		BridgeModule(bp_runtime::OwnedBridgeModuleError),
+		/// The outbound lane is already opened.
+		LaneAlreadyOpened,
+		/// The outbound lane is already closed.
+		LaneAlreadyClosed,
 	}
 
 	/// Optional pallet owner.
@@ -580,6 +754,14 @@ pub mod pallet {
 	pub type PalletOperatingMode<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, MessagesOperatingMode, ValueQuery>;
 
+	/// Map of inbound lanes QoS class => dispatch weight, spent by messages of lanes of this class
+	/// at given block.
+	///
+	/// Values that are stored for previous blocks are treated as zero weight.
+	#[pallet::storage]
+	pub type InboundLanesQosDispatchWeight<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneQosClass, (BlockNumberFor<T>, Weight), OptionQuery>;
+
 	/// Map of lane id => inbound lane data.
 	#[pallet::storage]
 	pub type InboundLanes<T: Config<I>, I: 'static = ()> =
@@ -615,11 +797,28 @@ pub mod pallet {
 		MaxValues = MaybeOutboundLanesCount<T, I>,
 	>;
 
+	/// Map of lane id => state of the lane, that has been opened or closed at runtime.
+	///
+	/// If there's no entry for the lane, it is considered opened if it is listed in the
+	/// `ActiveOutboundLanes` configuration value.
+	#[pallet::storage]
+	pub type LaneStates<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, LaneState>;
+
 	/// All queued outbound messages.
 	#[pallet::storage]
 	pub type OutboundMessages<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Blake2_128Concat, MessageKey, StoredMessagePayload<T, I>>;
 
+	/// Map of lane id => delivery confirmations of messages, that are still retained in the
+	/// `OutboundMessages` map.
+	///
+	/// It is only filled if `MessageRetentionBlocks` is non-zero. Confirmations are ordered by
+	/// the block number.
+	#[pallet::storage]
+	pub type OutboundLanesRetainedConfirmations<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, RetainedDeliveryConfirmationsOf<T>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(DefaultNoBound)]
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
@@ -642,6 +841,24 @@ pub mod pallet {
 	}
 
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Returns weight of reading and updating dispatch weight, spent by every limited QoS
+		/// class in the current block.
+		pub(crate) fn qos_dispatch_weight_bookkeeping() -> Weight {
+			let limited_classes = [LaneQosClass::Normal, LaneQosClass::Priority]
+				.into_iter()
+				.filter(|class| T::InboundLanesQos::class_limits(*class).is_some())
+				.count() as u64;
+			T::DbWeight::get().reads_writes(limited_classes, limited_classes)
+		}
+
+		/// Returns true if the outbound lane is accepting outbound messages.
+		pub fn is_outbound_lane_opened(lane: LaneId) -> bool {
+			match LaneStates::<T, I>::get(lane) {
+				Some(state) => state.is_opened(),
+				None => T::ActiveOutboundLanes::get().contains(&lane),
+			}
+		}
+
 		/// Get stored data of the outbound message with given nonce.
 		pub fn outbound_message_data(lane: LaneId, nonce: MessageNonce) -> Option<MessagePayload> {
 			OutboundMessages::<T, I>::get(MessageKey { lane_id: lane, nonce }).map(Into::into)
@@ -671,6 +888,101 @@ pub mod pallet {
 		pub fn inbound_lane_data(lane: LaneId) -> InboundLaneData<T::InboundRelayer> {
 			InboundLanes::<T, I>::get(lane).0
 		}
+
+		/// Remember the block, where delivery of given messages has been confirmed.
+		fn retain_delivered_messages(lane_id: LaneId, messages: &DeliveredMessages) {
+			if T::MessageRetentionBlocks::get().is_zero() {
+				return
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			OutboundLanesRetainedConfirmations::<T, I>::mutate(lane_id, |confirmations| {
+				let mut updated_confirmations = sp_std::mem::take(confirmations).into_inner();
+				match updated_confirmations.last_mut() {
+					Some((confirmed_at, last_messages)) if *confirmed_at == now =>
+						last_messages.end = messages.end,
+					_ => {
+						if updated_confirmations.len() >=
+							MAX_RETAINED_DELIVERY_CONFIRMATIONS as usize
+						{
+							// merge two oldest confirmations, it only means that the oldest
+							// messages are retained longer
+							let (_, oldest_messages) = updated_confirmations.remove(0);
+							if let Some((_, next_messages)) = updated_confirmations.first_mut() {
+								next_messages.begin = oldest_messages.begin;
+							}
+						}
+						updated_confirmations.push((now, messages.clone()));
+					},
+				}
+				*confirmations = BoundedVec::truncate_from(updated_confirmations);
+			});
+		}
+
+		/// Prune delivered messages of the outbound lane, which retention period is over.
+		///
+		/// Returns weight, consumed by messages pruning and lane state update. Weight of the
+		/// outbound lane state read must be accounted by the caller.
+		fn prune_outbound_lane_messages(
+			lane_id: LaneId,
+			db_weight: frame_support::weights::RuntimeDbWeight,
+			remaining_weight: Weight,
+		) -> Weight {
+			let mut lane = outbound_lane::<T, I>(lane_id);
+			let retention_blocks = T::MessageRetentionBlocks::get();
+			if retention_blocks.is_zero() {
+				return lane.prune_messages(db_weight, remaining_weight)
+			}
+
+			// we'll need to read retained confirmations, prune at least one message, update
+			// the lane state, read it again and update retained confirmations
+			let confirmations_weight = db_weight.reads_writes(2, 1);
+			if !remaining_weight.all_gte(confirmations_weight + db_weight.writes(2)) {
+				return Weight::zero()
+			}
+
+			// messages that have been confirmed before the first retained confirmation (e.g.
+			// before retention has been enabled) may be pruned immediately
+			let now = frame_system::Pallet::<T>::block_number();
+			let confirmations = OutboundLanesRetainedConfirmations::<T, I>::get(lane_id);
+			let max_prunable_nonce = confirmations
+				.iter()
+				.take_while(|(confirmed_at, _)| {
+					confirmed_at.saturating_add(retention_blocks) <= now
+				})
+				.last()
+				.map(|(_, messages)| messages.end)
+				.or_else(|| {
+					confirmations.first().map(|(_, messages)| messages.begin.saturating_sub(1))
+				})
+				.unwrap_or(MessageNonce::MAX);
+
+			let mut used_weight = db_weight.reads(2);
+			used_weight += lane.prune_messages_up_to(
+				db_weight,
+				remaining_weight - confirmations_weight,
+				max_prunable_nonce,
+			);
+
+			// forget confirmations of already pruned messages
+			let oldest_unpruned_nonce = lane.data().oldest_unpruned_nonce;
+			let confirmations_len = confirmations.len();
+			let mut confirmations = confirmations.into_inner();
+			confirmations.retain(|(_, messages)| messages.end >= oldest_unpruned_nonce);
+			if confirmations.len() != confirmations_len {
+				used_weight += db_weight.writes(1);
+				if confirmations.is_empty() {
+					OutboundLanesRetainedConfirmations::<T, I>::remove(lane_id);
+				} else {
+					OutboundLanesRetainedConfirmations::<T, I>::insert(
+						lane_id,
+						BoundedVec::truncate_from(confirmations),
+					);
+				}
+			}
+
+			used_weight
+		}
 	}
 
 	/// Get-parameter that returns number of active outbound lanes that the pallet maintains.
@@ -709,7 +1021,7 @@ fn send_message<T: Config<I>, I: 'static>(
 	ensure_normal_operating_mode::<T, I>()?;
 
 	// let's check if outbound lane is active
-	ensure!(T::ActiveOutboundLanes::get().contains(&lane_id), Error::<T, I>::InactiveOutboundLane,);
+	ensure!(Pallet::<T, I>::is_outbound_lane_opened(lane_id), Error::<T, I>::InactiveOutboundLane);
 
 	// let's first check if message can be delivered to target chain
 	T::TargetHeaderChain::verify_message(&payload).map_err(|err| {
@@ -787,6 +1099,7 @@ fn outbound_lane<T: Config<I>, I: 'static>(
 /// Runtime inbound lane storage.
 struct RuntimeInboundLaneStorage<T: Config<I>, I: 'static = ()> {
 	lane_id: LaneId,
+	qos_limits: Option<LaneQosLimits>,
 	cached_data: Option<InboundLaneData<T::InboundRelayer>>,
 	_phantom: PhantomData<I>,
 }
@@ -794,11 +1107,21 @@ struct RuntimeInboundLaneStorage<T: Config<I>, I: 'static = ()> {
 impl<T: Config<I>, I: 'static> RuntimeInboundLaneStorage<T, I> {
 	/// Creates new runtime inbound lane storage.
 	fn from_lane_id(lane_id: LaneId) -> RuntimeInboundLaneStorage<T, I> {
-		RuntimeInboundLaneStorage { lane_id, cached_data: None, _phantom: Default::default() }
+		RuntimeInboundLaneStorage {
+			lane_id,
+			qos_limits: T::InboundLanesQos::class_limits(T::InboundLanesQos::lane_class(&lane_id)),
+			cached_data: None,
+			_phantom: Default::default(),
+		}
 	}
 }
 
 impl<T: Config<I>, I: 'static> RuntimeInboundLaneStorage<T, I> {
+	/// Returns quality of service limits of the lane.
+	pub fn qos_limits(&self) -> Option<LaneQosLimits> {
+		self.qos_limits
+	}
+
 	/// Returns number of bytes that may be subtracted from the PoV component of
 	/// `receive_messages_proof` call, because the actual inbound lane state is smaller than the
 	/// maximal configured.
@@ -825,11 +1148,20 @@ impl<T: Config<I>, I: 'static> InboundLaneStorage for RuntimeInboundLaneStorage<
 	}
 
 	fn max_unrewarded_relayer_entries(&self) -> MessageNonce {
-		T::MaxUnrewardedRelayerEntriesAtInboundLane::get()
+		let max_entries = T::MaxUnrewardedRelayerEntriesAtInboundLane::get();
+		match self.qos_limits {
+			Some(ref limits) =>
+				sp_std::cmp::min(limits.max_unrewarded_relayer_entries, max_entries),
+			None => max_entries,
+		}
 	}
 
 	fn max_unconfirmed_messages(&self) -> MessageNonce {
-		T::MaxUnconfirmedMessagesAtInboundLane::get()
+		let max_messages = T::MaxUnconfirmedMessagesAtInboundLane::get();
+		match self.qos_limits {
+			Some(ref limits) => sp_std::cmp::min(limits.max_unconfirmed_messages, max_messages),
+			None => max_messages,
+		}
 	}
 
 	fn get_or_init_data(&mut self) -> InboundLaneData<T::InboundRelayer> {
@@ -923,16 +1255,19 @@ mod tests {
 	use crate::{
 		mock::{
 			inbound_unrewarded_relayers_state, message, message_payload, run_test,
-			unrewarded_relayer, AccountId, DbWeight, RuntimeEvent as TestEvent, RuntimeOrigin,
+			unrewarded_relayer, AccountId, DbWeight, MessageRetentionBlocks,
+			NormalLanesQosDispatchWeight, RuntimeEvent as TestEvent, RuntimeOrigin,
 			TestDeliveryConfirmationPayments, TestDeliveryPayments, TestMessageDispatch,
 			TestMessagesDeliveryProof, TestMessagesProof, TestOnMessagesDelivered, TestRelayer,
 			TestRuntime, TestWeightInfo, MAX_OUTBOUND_PAYLOAD_SIZE,
-			PAYLOAD_REJECTED_BY_TARGET_CHAIN, REGULAR_PAYLOAD, TEST_LANE_ID, TEST_LANE_ID_2,
-			TEST_LANE_ID_3, TEST_RELAYER_A, TEST_RELAYER_B,
+			PAYLOAD_REJECTED_BY_TARGET_CHAIN, PRIORITY_LANE_QOS_LIMITS, REGULAR_PAYLOAD,
+			TEST_LANE_ID, TEST_LANE_ID_2, TEST_LANE_ID_3, TEST_RELAYER_A, TEST_RELAYER_B,
 		},
 		outbound_lane::ReceivalConfirmationError,
 	};
-	use bp_messages::{BridgeMessagesCall, UnrewardedRelayer, UnrewardedRelayersState};
+	use bp_messages::{
+		BridgeMessagesCall, Message, ReceivalResult, UnrewardedRelayer, UnrewardedRelayersState,
+	};
 	use bp_test_utils::generate_owned_bridge_module_tests;
 	use frame_support::{
 		assert_noop, assert_ok,
@@ -1299,6 +1634,127 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn inbound_lane_storage_respects_qos_limits() {
+		// normal lanes are using limits from the pallet configuration
+		let storage = RuntimeInboundLaneStorage::<TestRuntime, ()>::from_lane_id(TEST_LANE_ID);
+		assert_eq!(
+			storage.max_unrewarded_relayer_entries(),
+			crate::mock::MaxUnrewardedRelayerEntriesAtInboundLane::get(),
+		);
+		assert_eq!(
+			storage.max_unconfirmed_messages(),
+			crate::mock::MaxUnconfirmedMessagesAtInboundLane::get(),
+		);
+
+		// priority lanes are using their class limits
+		let storage = RuntimeInboundLaneStorage::<TestRuntime, ()>::from_lane_id(TEST_LANE_ID_3);
+		assert_eq!(
+			storage.max_unrewarded_relayer_entries(),
+			PRIORITY_LANE_QOS_LIMITS.max_unrewarded_relayer_entries,
+		);
+		assert_eq!(
+			storage.max_unconfirmed_messages(),
+			PRIORITY_LANE_QOS_LIMITS.max_unconfirmed_messages,
+		);
+	}
+
+	#[test]
+	fn receive_messages_proof_respects_qos_dispatch_weight_reservation() {
+		run_test(|| {
+			// normal lanes may only dispatch two messages per block, while priority lanes have
+			// their own, larger budget
+			NormalLanesQosDispatchWeight::set(REGULAR_PAYLOAD.declared_weight.saturating_mul(2));
+
+			let priority_message = |nonce| Message {
+				key: MessageKey { lane_id: TEST_LANE_ID_3, nonce },
+				payload: REGULAR_PAYLOAD.encode(),
+			};
+			let deliver = |messages: Vec<Message>| {
+				let messages_count = messages.len() as u32;
+				let proof: TestMessagesProof = Ok(messages).into();
+				let dispatch_weight =
+					REGULAR_PAYLOAD.declared_weight.saturating_mul(messages_count as u64);
+				let declared_weight = TestWeightInfo::receive_messages_proof_weight(
+					&proof,
+					messages_count,
+					dispatch_weight,
+				) + Pallet::<TestRuntime>::qos_dispatch_weight_bookkeeping();
+				let result = Pallet::<TestRuntime>::receive_messages_proof(
+					RuntimeOrigin::signed(1),
+					TEST_RELAYER_A,
+					proof,
+					messages_count,
+					dispatch_weight,
+				)
+				.expect("delivery has failed");
+				let received = match System::<TestRuntime>::events().pop().map(|e| e.event) {
+					Some(TestEvent::Messages(Event::MessagesReceived(received))) => received,
+					_ => panic!("MessagesReceived event is expected"),
+				};
+				(declared_weight, result.actual_weight.unwrap(), received)
+			};
+			let last_delivered_nonce =
+				|lane_id| InboundLanes::<TestRuntime>::get(lane_id).0.last_delivered_nonce();
+
+			// normal traffic exhausts its budget, but priority messages are still dispatched
+			get_ready_for_events();
+			let (declared_weight, actual_weight, received) = deliver(vec![
+				message(1, REGULAR_PAYLOAD),
+				message(2, REGULAR_PAYLOAD),
+				message(3, REGULAR_PAYLOAD),
+				priority_message(1),
+				priority_message(2),
+				priority_message(3),
+			]);
+			assert_eq!(last_delivered_nonce(TEST_LANE_ID), 2);
+			assert_eq!(last_delivered_nonce(TEST_LANE_ID_3), 3);
+			assert_eq!(
+				received[0]
+					.receive_results
+					.last()
+					.map(|(nonce, result)| (*nonce, result.clone())),
+				Some((3, ReceivalResult::QosDispatchWeightLimitReached)),
+			);
+			// the weight of not dispatched message is refunded
+			assert_eq!(
+				actual_weight.ref_time(),
+				(declared_weight - REGULAR_PAYLOAD.declared_weight).ref_time(),
+			);
+			assert_eq!(
+				InboundLanesQosDispatchWeight::<TestRuntime>::get(LaneQosClass::Normal),
+				Some((1, REGULAR_PAYLOAD.declared_weight.saturating_mul(2))),
+			);
+			assert_eq!(
+				InboundLanesQosDispatchWeight::<TestRuntime>::get(LaneQosClass::Priority),
+				Some((1, REGULAR_PAYLOAD.declared_weight.saturating_mul(3))),
+			);
+
+			// the budget is shared by all delivery transactions of the same block
+			get_ready_for_events();
+			let (_, _, received) = deliver(vec![message(3, REGULAR_PAYLOAD), priority_message(4)]);
+			assert_eq!(last_delivered_nonce(TEST_LANE_ID), 2);
+			assert_eq!(last_delivered_nonce(TEST_LANE_ID_3), 4);
+			assert_eq!(
+				received[0]
+					.receive_results
+					.last()
+					.map(|(nonce, result)| (*nonce, result.clone())),
+				Some((3, ReceivalResult::QosDispatchWeightLimitReached)),
+			);
+
+			// and it is restored at the next block
+			System::<TestRuntime>::set_block_number(2);
+			System::<TestRuntime>::reset_events();
+			deliver(vec![message(3, REGULAR_PAYLOAD)]);
+			assert_eq!(last_delivered_nonce(TEST_LANE_ID), 3);
+			assert_eq!(
+				InboundLanesQosDispatchWeight::<TestRuntime>::get(LaneQosClass::Normal),
+				Some((2, REGULAR_PAYLOAD.declared_weight)),
+			);
+		});
+	}
+
 	#[test]
 	fn receive_messages_proof_rejects_proof_with_too_many_messages() {
 		run_test(|| {
@@ -1554,6 +2010,8 @@ mod tests {
 	#[test]
 	fn actual_dispatch_weight_does_not_overlow() {
 		run_test(|| {
+			// QoS dispatch weight limit would reject messages before the overflow
+			NormalLanesQosDispatchWeight::set(Weight::MAX);
 			let message1 = message(1, message_payload(0, u64::MAX / 2));
 			let message2 = message(2, message_payload(0, u64::MAX / 2));
 			let message3 = message(3, message_payload(0, u64::MAX / 2));
@@ -1589,7 +2047,7 @@ mod tests {
 						&proof,
 						messages_count,
 						REGULAR_PAYLOAD.declared_weight,
-					);
+					) + Pallet::<TestRuntime>::qos_dispatch_weight_bookkeeping();
 				let result = Pallet::<TestRuntime>::receive_messages_proof(
 					RuntimeOrigin::signed(1),
 					TEST_RELAYER_A,
@@ -1915,12 +2373,99 @@ mod tests {
 			// if passed wight is enough to prune many messages
 			assert_eq!(
 				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100)),
-				dbw.reads_writes(1, 2),
+				// + 1 read for lanes, opened at runtime
+				dbw.reads_writes(2, 2),
+			);
+			assert_eq!(
+				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
+				5
+			);
+		});
+	}
+
+	#[test]
+	fn on_idle_callback_retains_delivered_messages() {
+		run_test(|| {
+			MessageRetentionBlocks::set(10);
+
+			let confirm_delivery = |latest_delivered_nonce| {
+				assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+					RuntimeOrigin::signed(1),
+					TestMessagesDeliveryProof(Ok((
+						TEST_LANE_ID,
+						InboundLaneData {
+							last_confirmed_nonce: 0,
+							relayers: vec![unrewarded_relayer(
+								1,
+								latest_delivered_nonce,
+								TEST_RELAYER_A
+							)]
+							.into_iter()
+							.collect(),
+						},
+					))),
+					UnrewardedRelayersState {
+						unrewarded_relayer_entries: 1,
+						messages_in_oldest_entry: latest_delivered_nonce,
+						total_messages: latest_delivered_nonce,
+						last_delivered_nonce: latest_delivered_nonce,
+					},
+				));
+			};
+
+			send_regular_message();
+			send_regular_message();
+			send_regular_message();
+			send_regular_message();
+
+			// messages 1+2 are confirmed at block 2 and messages 3+4 at block 6
+			System::<TestRuntime>::set_block_number(2);
+			confirm_delivery(2);
+			System::<TestRuntime>::set_block_number(6);
+			confirm_delivery(4);
+			assert_eq!(
+				OutboundLanesRetainedConfirmations::<TestRuntime>::get(TEST_LANE_ID).into_inner(),
+				vec![
+					(2, DeliveredMessages { begin: 1, end: 2 }),
+					(6, DeliveredMessages { begin: 3, end: 4 })
+				],
 			);
+
+			// nothing is pruned while retention period of messages 1+2 is not over
+			let dbw = DbWeight::get();
+			System::<TestRuntime>::set_block_number(10);
+			Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100));
+			assert_eq!(
+				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
+				1
+			);
+
+			// messages 1+2 are pruned at block 12
+			System::<TestRuntime>::set_block_number(12);
+			assert_eq!(
+				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100)),
+				// + 1 read for retained confirmations, 1 read of updated lane state and 1 read
+				// for lanes, opened at runtime
+				dbw.reads_writes(4, 4),
+			);
+			assert_eq!(
+				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
+				3
+			);
+			assert!(Pallet::<TestRuntime>::outbound_message_data(TEST_LANE_ID, 3).is_some());
+			assert_eq!(
+				OutboundLanesRetainedConfirmations::<TestRuntime>::get(TEST_LANE_ID).into_inner(),
+				vec![(6, DeliveredMessages { begin: 3, end: 4 })],
+			);
+
+			// messages 3+4 are pruned at block 16
+			System::<TestRuntime>::set_block_number(16);
+			Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100));
 			assert_eq!(
 				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
 				5
 			);
+			assert!(!OutboundLanesRetainedConfirmations::<TestRuntime>::contains_key(TEST_LANE_ID));
 		});
 	}
 
@@ -1974,7 +2519,8 @@ mod tests {
 			System::<TestRuntime>::set_block_number(2);
 			assert_eq!(
 				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100)),
-				dbw.reads_writes(1, 2),
+				// + 1 read for lanes, opened at runtime
+				dbw.reads_writes(2, 2),
 			);
 			assert_eq!(
 				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
@@ -1990,7 +2536,8 @@ mod tests {
 
 			assert_eq!(
 				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100)),
-				dbw.reads_writes(1, 2),
+				// + 1 read for lanes, opened at runtime
+				dbw.reads_writes(2, 2),
 			);
 			assert_eq!(
 				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
@@ -2013,6 +2560,246 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn open_lane_works() {
+		run_test(|| {
+			get_ready_for_events();
+
+			// only root or owner may open lanes
+			assert_noop!(
+				Pallet::<TestRuntime>::open_lane(RuntimeOrigin::signed(1), TEST_LANE_ID_3),
+				DispatchError::BadOrigin,
+			);
+			// configured lanes are already opened
+			assert_noop!(
+				Pallet::<TestRuntime>::open_lane(RuntimeOrigin::root(), TEST_LANE_ID),
+				Error::<TestRuntime, ()>::LaneAlreadyOpened,
+			);
+
+			assert_ok!(Pallet::<TestRuntime>::open_lane(RuntimeOrigin::root(), TEST_LANE_ID_3));
+			assert_eq!(LaneStates::<TestRuntime>::get(TEST_LANE_ID_3), Some(LaneState::Opened));
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Messages(Event::LaneOpened { lane_id: TEST_LANE_ID_3 }),
+					topics: vec![],
+				}],
+			);
+			assert_noop!(
+				Pallet::<TestRuntime>::open_lane(RuntimeOrigin::root(), TEST_LANE_ID_3),
+				Error::<TestRuntime, ()>::LaneAlreadyOpened,
+			);
+
+			// and now lane accepts outbound messages
+			assert_ok!(send_message::<TestRuntime, ()>(TEST_LANE_ID_3, REGULAR_PAYLOAD));
+		});
+	}
+
+	#[test]
+	fn close_lane_works() {
+		run_test(|| {
+			send_regular_message();
+			send_regular_message();
+			send_regular_message();
+			// message#1 is delivered
+			receive_messages_delivery_proof();
+			get_ready_for_events();
+
+			// only root or owner may close lanes
+			assert_noop!(
+				Pallet::<TestRuntime>::close_lane(RuntimeOrigin::signed(1), TEST_LANE_ID),
+				DispatchError::BadOrigin,
+			);
+			// unknown lanes are already closed
+			assert_noop!(
+				Pallet::<TestRuntime>::close_lane(RuntimeOrigin::root(), TEST_LANE_ID_3),
+				Error::<TestRuntime, ()>::LaneAlreadyClosed,
+			);
+
+			assert_ok!(Pallet::<TestRuntime>::close_lane(RuntimeOrigin::root(), TEST_LANE_ID));
+			assert_eq!(LaneStates::<TestRuntime>::get(TEST_LANE_ID), Some(LaneState::Closed));
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Messages(Event::LaneClosed {
+						lane_id: TEST_LANE_ID,
+						queued_messages: 2,
+					}),
+					topics: vec![],
+				}],
+			);
+
+			// lane state is untouched and queued messages are still waiting for delivery
+			let lane_data = outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data();
+			assert_eq!(lane_data.oldest_unpruned_nonce, 1);
+			assert_eq!(lane_data.latest_received_nonce, 1);
+			assert_eq!(lane_data.latest_generated_nonce, 3);
+			for nonce in 1..=3 {
+				assert!(Pallet::<TestRuntime>::outbound_message_data(TEST_LANE_ID, nonce).is_some());
+			}
+
+			// closed lane rejects outbound messages
+			assert_noop!(
+				send_message::<TestRuntime, ()>(TEST_LANE_ID, REGULAR_PAYLOAD),
+				Error::<TestRuntime, ()>::InactiveOutboundLane,
+			);
+			assert_noop!(
+				Pallet::<TestRuntime>::close_lane(RuntimeOrigin::root(), TEST_LANE_ID),
+				Error::<TestRuntime, ()>::LaneAlreadyClosed,
+			);
+		});
+	}
+
+	#[test]
+	fn closed_lane_messages_are_delivered() {
+		run_test(|| {
+			send_regular_message();
+			send_regular_message();
+			assert_ok!(Pallet::<TestRuntime>::close_lane(RuntimeOrigin::root(), TEST_LANE_ID));
+
+			// delivery confirmations are still accepted at closed lanes
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				RuntimeOrigin::signed(1),
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID,
+					InboundLaneData {
+						last_confirmed_nonce: 0,
+						relayers: vec![unrewarded_relayer(1, 2, TEST_RELAYER_A)]
+							.into_iter()
+							.collect(),
+					},
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					messages_in_oldest_entry: 2,
+					total_messages: 2,
+					last_delivered_nonce: 2,
+				},
+			));
+			assert_eq!(
+				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().latest_received_nonce,
+				2
+			);
+		});
+	}
+
+	#[test]
+	fn closed_lane_is_reopened_without_delivery_proof() {
+		run_test(|| {
+			send_regular_message();
+			send_regular_message();
+			assert_ok!(Pallet::<TestRuntime>::close_lane(RuntimeOrigin::root(), TEST_LANE_ID));
+
+			// queued messages are not delivered yet, but lane may be reopened immediately
+			assert_ok!(Pallet::<TestRuntime>::open_lane(RuntimeOrigin::root(), TEST_LANE_ID));
+			assert!(Pallet::<TestRuntime>::is_outbound_lane_opened(TEST_LANE_ID));
+
+			// and the next message gets the next nonce, so the bridged chain sees no gap
+			assert_eq!(
+				send_message::<TestRuntime, ()>(TEST_LANE_ID, REGULAR_PAYLOAD)
+					.map(|artifacts| artifacts.nonce),
+				Ok(3),
+			);
+			let lane_data = outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data();
+			assert_eq!(lane_data.queued_messages(), 1..=3);
+			for nonce in 1..=3 {
+				assert!(Pallet::<TestRuntime>::outbound_message_data(TEST_LANE_ID, nonce).is_some());
+			}
+		});
+	}
+
+	#[test]
+	fn on_idle_callback_prunes_lanes_opened_at_runtime() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::open_lane(RuntimeOrigin::root(), TEST_LANE_ID_3));
+			assert_ok!(send_message::<TestRuntime, ()>(TEST_LANE_ID_3, REGULAR_PAYLOAD));
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				RuntimeOrigin::signed(1),
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID_3,
+					InboundLaneData {
+						last_confirmed_nonce: 1,
+						relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)]
+							.into_iter()
+							.collect(),
+					},
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					messages_in_oldest_entry: 1,
+					total_messages: 1,
+					last_delivered_nonce: 1,
+				},
+			));
+
+			// configured lane has nothing to prune, so we only read its state (1 read), read
+			// runtime lane entry + its state (2 reads) and prune message (2 writes). Then we try
+			// to read next runtime lane entry (1 read)
+			let dbw = DbWeight::get();
+			assert_eq!(
+				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100)),
+				dbw.reads_writes(4, 2),
+			);
+			assert_eq!(
+				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID_3).data().oldest_unpruned_nonce,
+				2
+			);
+		});
+	}
+
+	#[test]
+	fn on_idle_callback_prunes_delivered_messages_of_closed_lanes() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::open_lane(RuntimeOrigin::root(), TEST_LANE_ID_3));
+			assert_ok!(send_message::<TestRuntime, ()>(TEST_LANE_ID_3, REGULAR_PAYLOAD));
+			assert_ok!(send_message::<TestRuntime, ()>(TEST_LANE_ID_3, REGULAR_PAYLOAD));
+			assert_ok!(Pallet::<TestRuntime>::close_lane(RuntimeOrigin::root(), TEST_LANE_ID_3));
+
+			// nothing is delivered yet, so nothing is pruned. We read configured lane state
+			// (1 read), read runtime lane entry + its state (2 reads) and then try to read
+			// next runtime lane entry (1 read)
+			let dbw = DbWeight::get();
+			assert_eq!(
+				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100)),
+				dbw.reads(4),
+			);
+			assert!(Pallet::<TestRuntime>::outbound_message_data(TEST_LANE_ID_3, 1).is_some());
+
+			// first message is delivered to the bridged chain
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				RuntimeOrigin::signed(1),
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID_3,
+					InboundLaneData {
+						last_confirmed_nonce: 0,
+						relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)]
+							.into_iter()
+							.collect(),
+					},
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					messages_in_oldest_entry: 1,
+					total_messages: 1,
+					last_delivered_nonce: 1,
+				},
+			));
+
+			// now the delivered message is pruned (1 write for message + 1 write for lane state)
+			assert_eq!(
+				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100)),
+				dbw.reads_writes(4, 2),
+			);
+			let lane_data = outbound_lane::<TestRuntime, ()>(TEST_LANE_ID_3).data();
+			assert_eq!(lane_data.oldest_unpruned_nonce, 2);
+			assert!(Pallet::<TestRuntime>::outbound_message_data(TEST_LANE_ID_3, 1).is_none());
+			// and the undelivered message is still there
+			assert!(Pallet::<TestRuntime>::outbound_message_data(TEST_LANE_ID_3, 2).is_some());
+		});
+	}
+
 	#[test]
 	fn test_bridge_messages_call_is_correctly_defined() {
 		let account_id = 1;
@@ -2090,6 +2877,7 @@ mod tests {
 		fn storage(relayer_entries: usize) -> RuntimeInboundLaneStorage<TestRuntime, ()> {
 			RuntimeInboundLaneStorage {
 				lane_id: Default::default(),
+				qos_limits: None,
 				cached_data: Some(InboundLaneData {
 					relayers: vec![relayer_entry(); relayer_entries].into_iter().collect(),
 					last_confirmed_nonce: 0,
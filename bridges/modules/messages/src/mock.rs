@@ -25,11 +25,12 @@ use bp_messages::{
 		DeliveryConfirmationPayments, LaneMessageVerifier, OnMessagesDelivered, TargetHeaderChain,
 	},
 	target_chain::{
-		DeliveryPayments, DispatchMessage, DispatchMessageData, MessageDispatch,
+		DeliveryPayments, DispatchMessage, DispatchMessageData, InboundLanesQos, MessageDispatch,
 		ProvedLaneMessages, ProvedMessages, SourceHeaderChain,
 	},
-	DeliveredMessages, InboundLaneData, LaneId, Message, MessageKey, MessageNonce, MessagePayload,
-	OutboundLaneData, UnrewardedRelayer, UnrewardedRelayersState, VerificationError,
+	DeliveredMessages, InboundLaneData, LaneId, LaneQosClass, LaneQosLimits, Message, MessageKey,
+	MessageNonce, MessagePayload, OutboundLaneData, UnrewardedRelayer, UnrewardedRelayersState,
+	VerificationError,
 };
 use bp_runtime::{messages::MessageDispatchResult, Size};
 use codec::{Decode, Encode};
@@ -141,6 +142,9 @@ parameter_types! {
 	pub const MaxUnconfirmedMessagesAtInboundLane: u64 = 128;
 	pub const TestBridgedChainId: bp_runtime::ChainId = *b"test";
 	pub const ActiveOutboundLanes: &'static [LaneId] = &[TEST_LANE_ID, TEST_LANE_ID_2];
+	pub static MessageRetentionBlocks: u64 = 0;
+	// enough to dispatch twenty `REGULAR_PAYLOAD` messages per block
+	pub static NormalLanesQosDispatchWeight: Weight = Weight::from_parts(1_000, 0);
 }
 
 /// weights of messages pallet calls we use in tests.
@@ -164,9 +168,11 @@ impl Config for TestRuntime {
 	type LaneMessageVerifier = TestLaneMessageVerifier;
 	type DeliveryConfirmationPayments = TestDeliveryConfirmationPayments;
 	type OnMessagesDelivered = TestOnMessagesDelivered;
+	type MessageRetentionBlocks = MessageRetentionBlocks;
 
 	type SourceHeaderChain = TestSourceHeaderChain;
 	type MessageDispatch = TestMessageDispatch;
+	type InboundLanesQos = TestInboundLanesQos;
 	type BridgedChainId = TestBridgedChainId;
 }
 
@@ -238,9 +244,26 @@ pub const TEST_LANE_ID: LaneId = LaneId([0, 0, 0, 1]);
 /// Secondary lane that we're using in tests.
 pub const TEST_LANE_ID_2: LaneId = LaneId([0, 0, 0, 2]);
 
-/// Inactive outbound lane.
+/// Inactive outbound lane. It is also the only inbound lane of `LaneQosClass::Priority` class.
 pub const TEST_LANE_ID_3: LaneId = LaneId([0, 0, 0, 3]);
 
+/// Limits of the `LaneQosClass::Priority` class.
+pub const PRIORITY_LANE_QOS_LIMITS: LaneQosLimits = LaneQosLimits {
+	max_unrewarded_relayer_entries: MaxUnrewardedRelayerEntriesAtInboundLane::get(),
+	max_unconfirmed_messages: MaxUnconfirmedMessagesAtInboundLane::get(),
+	// enough to dispatch forty `REGULAR_PAYLOAD` messages per block
+	max_dispatch_weight: Weight::from_parts(2_000, 0),
+};
+
+/// Limits of the `LaneQosClass::Normal` class.
+pub fn normal_lane_qos_limits() -> LaneQosLimits {
+	LaneQosLimits {
+		max_unrewarded_relayer_entries: MaxUnrewardedRelayerEntriesAtInboundLane::get(),
+		max_unconfirmed_messages: MaxUnconfirmedMessagesAtInboundLane::get(),
+		max_dispatch_weight: NormalLanesQosDispatchWeight::get(),
+	}
+}
+
 /// Regular message payload.
 pub const REGULAR_PAYLOAD: TestPayload = message_payload(0, 50);
 
@@ -442,6 +465,26 @@ impl MessageDispatch for TestMessageDispatch {
 	}
 }
 
+/// Test quality of service classes of inbound lanes.
+pub struct TestInboundLanesQos;
+
+impl InboundLanesQos for TestInboundLanesQos {
+	fn lane_class(lane: &LaneId) -> LaneQosClass {
+		if *lane == TEST_LANE_ID_3 {
+			LaneQosClass::Priority
+		} else {
+			LaneQosClass::Normal
+		}
+	}
+
+	fn class_limits(class: LaneQosClass) -> Option<LaneQosLimits> {
+		match class {
+			LaneQosClass::Normal => Some(normal_lane_qos_limits()),
+			LaneQosClass::Priority => Some(PRIORITY_LANE_QOS_LIMITS),
+		}
+	}
+}
+
 /// Test callback, called during message delivery confirmation transaction.
 pub struct TestOnMessagesDelivered;
 
@@ -150,16 +150,30 @@ impl<S: OutboundLaneStorage> OutboundLane<S> {
 	///
 	/// Returns weight, consumed by messages pruning and lane state update.
 	pub fn prune_messages(
+		&mut self,
+		db_weight: RuntimeDbWeight,
+		remaining_weight: Weight,
+	) -> Weight {
+		self.prune_messages_up_to(db_weight, remaining_weight, MessageNonce::MAX)
+	}
+
+	/// Prune already received messages with nonces that are less than or equal to
+	/// `max_prunable_nonce`.
+	///
+	/// Returns weight, consumed by messages pruning and lane state update.
+	pub fn prune_messages_up_to(
 		&mut self,
 		db_weight: RuntimeDbWeight,
 		mut remaining_weight: Weight,
+		max_prunable_nonce: MessageNonce,
 	) -> Weight {
 		let write_weight = db_weight.writes(1);
 		let two_writes_weight = write_weight + write_weight;
 		let mut spent_weight = Weight::zero();
 		let mut data = self.storage.data();
+		let max_prunable_nonce = sp_std::cmp::min(max_prunable_nonce, data.latest_received_nonce);
 		while remaining_weight.all_gte(two_writes_weight) &&
-			data.oldest_unpruned_nonce <= data.latest_received_nonce
+			data.oldest_unpruned_nonce <= max_prunable_nonce
 		{
 			self.storage.remove_message(&data.oldest_unpruned_nonce);
 
@@ -408,6 +422,44 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn prune_messages_up_to_respects_max_prunable_nonce() {
+		run_test(|| {
+			let mut lane = outbound_lane::<TestRuntime, _>(TEST_LANE_ID);
+			assert_ok!(lane.send_message(outbound_message_data(REGULAR_PAYLOAD)));
+			assert_ok!(lane.send_message(outbound_message_data(REGULAR_PAYLOAD)));
+			assert_ok!(lane.send_message(outbound_message_data(REGULAR_PAYLOAD)));
+			assert_eq!(
+				lane.confirm_delivery(2, 2, &unrewarded_relayers(1..=2)),
+				Ok(Some(delivered_messages(1..=2))),
+			);
+			// only first message may be pruned
+			assert_eq!(
+				lane.prune_messages_up_to(
+					RocksDbWeight::get(),
+					RocksDbWeight::get().writes(101),
+					1
+				),
+				RocksDbWeight::get().writes(2),
+			);
+			assert!(lane.storage.message(&1).is_none());
+			assert!(lane.storage.message(&2).is_some());
+			assert_eq!(lane.storage.data().oldest_unpruned_nonce, 2);
+			// undelivered messages are never pruned
+			assert_eq!(
+				lane.prune_messages_up_to(
+					RocksDbWeight::get(),
+					RocksDbWeight::get().writes(101),
+					3
+				),
+				RocksDbWeight::get().writes(2),
+			);
+			assert!(lane.storage.message(&2).is_none());
+			assert!(lane.storage.message(&3).is_some());
+			assert_eq!(lane.storage.data().oldest_unpruned_nonce, 3);
+		});
+	}
+
 	#[test]
 	fn confirm_delivery_detects_when_more_than_expected_messages_are_confirmed() {
 		run_test(|| {
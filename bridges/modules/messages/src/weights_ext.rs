@@ -27,7 +27,7 @@ pub const EXPECTED_DEFAULT_MESSAGE_LENGTH: u32 = 128;
 
 /// We assume that size of signed extensions on all our chains and size of all 'small' arguments of
 /// calls we're checking here would fit 1KB.
-const SIGNED_EXTENSIONS_SIZE: u32 = 1024;
+pub const SIGNED_EXTENSIONS_SIZE: u32 = 1024;
 
 /// Number of extra bytes (excluding size of storage value itself) of storage proof, built at
 /// Rialto chain. This mostly depends on number of entries (and their density) in the storage trie.
@@ -15,13 +15,14 @@
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{Config, Pallet, RelayBlockNumber};
-use bp_parachains::BestParaHeadHash;
+use bp_parachains::{BestParaHeadHash, ParaStoredHeaderDataBuilder};
 use bp_polkadot_core::parachains::{ParaHash, ParaId};
 use frame_support::{dispatch::CallableCallFor, traits::IsSubType};
 use sp_runtime::{
 	transaction_validity::{InvalidTransaction, TransactionValidity, ValidTransaction},
 	RuntimeDebug,
 };
+use sp_std::vec::Vec;
 
 /// Info about a `SubmitParachainHeads` call which tries to update a single parachain.
 #[derive(PartialEq, RuntimeDebug)]
@@ -115,6 +116,30 @@ pub trait CallSubType<T: Config<I, RuntimeCall = Self>, I: 'static>:
 		None
 	}
 
+	/// Create a new instance of `SubmitParachainHeadsInfo` for every parachain entry of the
+	/// `SubmitParachainHeads` call.
+	fn submit_parachain_heads_batch_info(&self) -> Option<Vec<SubmitParachainHeadsInfo>> {
+		if let Some(crate::Call::<T, I>::submit_parachain_heads {
+			ref at_relay_block,
+			ref parachains,
+			..
+		}) = self.is_sub_type()
+		{
+			return Some(
+				parachains
+					.iter()
+					.map(|(para_id, para_head_hash)| SubmitParachainHeadsInfo {
+						at_relay_block_number: at_relay_block.0,
+						para_id: *para_id,
+						para_head_hash: *para_head_hash,
+					})
+					.collect(),
+			)
+		}
+
+		None
+	}
+
 	/// Create a new instance of `SubmitParachainHeadsInfo` from a `SubmitParachainHeads` call with
 	/// one single parachain entry, if the entry is for the provided parachain id.
 	fn submit_parachain_heads_info_for(&self, para_id: u32) -> Option<SubmitParachainHeadsInfo> {
@@ -127,21 +152,27 @@ pub trait CallSubType<T: Config<I, RuntimeCall = Self>, I: 'static>:
 	/// may lose their funds if there are multiple relays running and submitting the
 	/// same information.
 	///
-	/// This validation only works with transactions that are updating single parachain
-	/// head. We can't use unbounded validation - it may take too long and either break
-	/// block production, or "eat" significant portion of block production time literally
-	/// for nothing. In addition, the single-parachain-head-per-transaction is how the
-	/// pallet will be used in our environment.
+	/// The transaction that is updating multiple parachain heads is only rejected if all
+	/// heads are obsolete - the pallet will accept valid heads and skip stale ones. We
+	/// can't use unbounded validation - it may take too long and either break block
+	/// production, or "eat" significant portion of block production time literally for
+	/// nothing. So batches with more heads than the number of parachains, supported by
+	/// the pallet, are not validated here.
 	fn check_obsolete_submit_parachain_heads(&self) -> TransactionValidity
 	where
 		Self: Sized,
 	{
-		let update = match self.one_entry_submit_parachain_heads_info() {
-			Some(update) => update,
-			None => return Ok(ValidTransaction::default()),
+		let updates = match self.submit_parachain_heads_batch_info() {
+			Some(updates) if !updates.is_empty() => updates,
+			_ => return Ok(ValidTransaction::default()),
 		};
 
-		if SubmitParachainHeadsHelper::<T, I>::is_obsolete(&update) {
+		let max_validated_updates = T::ParaStoredHeaderDataBuilder::supported_parachains();
+		if updates.len() > max_validated_updates as usize {
+			return Ok(ValidTransaction::default())
+		}
+
+		if updates.iter().all(SubmitParachainHeadsHelper::<T, I>::is_obsolete) {
 			return InvalidTransaction::Stale.into()
 		}
 
@@ -191,6 +222,19 @@ mod tests {
 		);
 	}
 
+	fn sync_parachain_2_to_relay_header_10() {
+		ParasInfo::<TestRuntime, ()>::insert(
+			ParaId(2),
+			ParaInfo {
+				best_head_hash: BestParaHeadHash {
+					at_relay_block_number: 10,
+					head_hash: [1u8; 32].into(),
+				},
+				next_imported_hash_position: 0,
+			},
+		);
+	}
+
 	#[test]
 	fn extension_rejects_header_from_the_obsolete_relay_block() {
 		run_test(|| {
@@ -243,4 +287,32 @@ mod tests {
 			));
 		});
 	}
+
+	#[test]
+	fn extension_rejects_multiple_parachains_if_all_heads_are_obsolete() {
+		run_test(|| {
+			// when current best finalized is #10 for both parachains and we're trying to import
+			// heads at #5 => tx is rejected
+			sync_to_relay_header_10();
+			sync_parachain_2_to_relay_header_10();
+			assert!(!validate_submit_parachain_heads(
+				5,
+				vec![(ParaId(1), [2u8; 32].into()), (ParaId(2), [2u8; 32].into())]
+			));
+		});
+	}
+
+	#[test]
+	fn extension_accepts_multiple_parachains_if_some_heads_are_not_obsolete() {
+		run_test(|| {
+			// when current best finalized is #10 for both parachains and we're trying to import
+			// heads at #15, but head of parachain#1 is the same => tx is accepted
+			sync_to_relay_header_10();
+			sync_parachain_2_to_relay_header_10();
+			assert!(validate_submit_parachain_heads(
+				15,
+				vec![(ParaId(1), [1u8; 32].into()), (ParaId(2), [2u8; 32].into())]
+			));
+		});
+	}
 }
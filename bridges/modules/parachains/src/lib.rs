@@ -299,7 +299,9 @@ pub mod pallet {
 		///   GRANDPA pallet.
 		///
 		/// The call may succeed, but some heads may not be updated e.g. because pallet knows
-		/// better head or it isn't tracked by the pallet.
+		/// better head or it isn't tracked by the pallet. So heads of multiple parachains may be
+		/// submitted in a single call, using single storage proof. Weight of every skipped head
+		/// is refunded to the submitter.
 		#[pallet::call_index(0)]
 		#[pallet::weight(WeightInfoOf::<T, I>::submit_parachain_heads_weight(
 			T::DbWeight::get(),
@@ -355,6 +357,9 @@ pub mod pallet {
 							},
 						);
 						Self::deposit_event(Event::MissingParachainHead { parachain });
+						actual_weight = actual_weight.saturating_sub(
+							WeightInfoOf::<T, I>::skipped_parachain_head_weight(T::DbWeight::get()),
+						);
 						continue
 					},
 					Err(e) => {
@@ -365,6 +370,9 @@ pub mod pallet {
 							e,
 						);
 						Self::deposit_event(Event::MissingParachainHead { parachain });
+						actual_weight = actual_weight.saturating_sub(
+							WeightInfoOf::<T, I>::skipped_parachain_head_weight(T::DbWeight::get()),
+						);
 						continue
 					},
 				};
@@ -386,6 +394,9 @@ pub mod pallet {
 						parachain_head_hash,
 						actual_parachain_head_hash,
 					});
+					actual_weight = actual_weight.saturating_sub(
+						WeightInfoOf::<T, I>::skipped_parachain_head_weight(T::DbWeight::get()),
+					);
 					continue
 				}
 
@@ -400,6 +411,11 @@ pub mod pallet {
 								parachain,
 							);
 							Self::deposit_event(Event::UntrackedParachainRejected { parachain });
+							actual_weight = actual_weight.saturating_sub(
+								WeightInfoOf::<T, I>::skipped_parachain_head_weight(
+									T::DbWeight::get(),
+								),
+							);
 							continue
 						},
 					};
@@ -1070,9 +1086,11 @@ pub(crate) mod tests {
 			// parachain
 			let expected_weight =
 				WeightInfo::submit_parachain_heads_weight(DbWeight::get(), &proof, 3)
-					.saturating_sub(WeightInfo::parachain_head_storage_write_weight(
-						DbWeight::get(),
-					));
+					.saturating_sub(
+						WeightInfo::parachain_head_storage_write_weight(DbWeight::get()),
+					)
+					// untracked parachain head is neither stored, nor pruned
+					.saturating_sub(WeightInfo::skipped_parachain_head_weight(DbWeight::get()));
 			initialize(state_root);
 			let result = Pallet::<TestRuntime>::submit_parachain_heads(
 				RuntimeOrigin::signed(1),
@@ -1516,12 +1534,17 @@ pub(crate) mod tests {
 		let parachains = vec![(ParaId(2), Default::default())];
 		run_test(|| {
 			initialize(state_root);
-			assert_ok!(Pallet::<TestRuntime>::submit_parachain_heads(
+			let expected_weight =
+				WeightInfo::submit_parachain_heads_weight(DbWeight::get(), &proof, 1)
+					.saturating_sub(WeightInfo::skipped_parachain_head_weight(DbWeight::get()));
+			let result = Pallet::<TestRuntime>::submit_parachain_heads(
 				RuntimeOrigin::signed(1),
 				(0, test_relay_header(0, state_root).hash()),
 				parachains,
 				proof,
-			));
+			);
+			assert_ok!(result);
+			assert_eq!(result.expect("checked above").actual_weight, Some(expected_weight));
 			assert_eq!(
 				System::<TestRuntime>::events(),
 				vec![EventRecord {
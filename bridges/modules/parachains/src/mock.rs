@@ -195,6 +195,7 @@ impl pallet_bridge_grandpa::Config<pallet_bridge_grandpa::Instance1> for TestRun
 	type RuntimeEvent = RuntimeEvent;
 	type BridgedChain = TestBridgedChain;
 	type MaxFreeMandatoryHeadersPerBlock = ConstU32<2>;
+	type FreeHeadersInterval = ();
 	type HeadersToKeep = HeadersToKeep;
 	type WeightInfo = ();
 }
@@ -203,6 +204,7 @@ impl pallet_bridge_grandpa::Config<pallet_bridge_grandpa::Instance2> for TestRun
 	type RuntimeEvent = RuntimeEvent;
 	type BridgedChain = TestBridgedChain;
 	type MaxFreeMandatoryHeadersPerBlock = ConstU32<2>;
+	type FreeHeadersInterval = ();
 	type HeadersToKeep = HeadersToKeep;
 	type WeightInfo = ();
 }
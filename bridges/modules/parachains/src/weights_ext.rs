@@ -85,6 +85,16 @@ pub trait WeightInfoExt: WeightInfo {
 		db_weight.writes(1)
 	}
 
+	/// Returns weight of single parachain head that has been skipped by the call.
+	///
+	/// If the head has been skipped (e.g. because it is missing from the proof or it is not
+	/// tracked by the pallet), it is neither written to the storage, nor causes any pruning.
+	/// So the weight of both operations may be refunded.
+	fn skipped_parachain_head_weight(db_weight: RuntimeDbWeight) -> Weight {
+		Self::parachain_head_storage_write_weight(db_weight)
+			.saturating_add(Self::parachain_head_pruning_weight(db_weight))
+	}
+
 	/// Returns weight that needs to be accounted when storage proof of given size is received.
 	fn storage_proof_size_overhead(extra_proof_bytes: u32) -> Weight {
 		let extra_byte_weight = (Self::submit_parachain_heads_with_16kb_proof() -
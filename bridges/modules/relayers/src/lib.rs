@@ -21,12 +21,16 @@
 #![warn(missing_docs)]
 
 use bp_relayers::{
-	PaymentProcedure, Registration, RelayerRewardsKeyProvider, RewardsAccountParams, StakeAndSlash,
+	PaymentProcedure, Registration, RelayerReputation, RelayerReputationsKeyProvider,
+	RelayerRewardsKeyProvider, RelayerStanding, RewardsAccountParams, StakeAndSlash,
 };
-use bp_runtime::StorageDoubleMapKeyProvider;
+use bp_runtime::{StorageDoubleMapKeyProvider, StorageMapKeyProvider};
 use frame_support::fail;
 use sp_arithmetic::traits::{AtLeast32BitUnsigned, Zero};
-use sp_runtime::{traits::CheckedSub, Saturating};
+use sp_runtime::{
+	traits::{CheckedDiv, CheckedSub},
+	Perbill, Saturating,
+};
 use sp_std::marker::PhantomData;
 
 pub use pallet::*;
@@ -36,6 +40,7 @@ pub use weights::WeightInfo;
 pub use weights_ext::WeightInfoExt;
 
 pub mod benchmarking;
+pub mod migration;
 
 mod mock;
 mod payment_adapter;
@@ -56,6 +61,9 @@ pub mod pallet {
 	/// `RelayerRewardsKeyProvider` for given configuration.
 	type RelayerRewardsKeyProviderOf<T> =
 		RelayerRewardsKeyProvider<<T as frame_system::Config>::AccountId, <T as Config>::Reward>;
+	/// `RelayerReputationsKeyProvider` for given configuration.
+	type RelayerReputationsKeyProviderOf<T> =
+		RelayerReputationsKeyProvider<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -67,11 +75,32 @@ pub mod pallet {
 		type PaymentProcedure: PaymentProcedure<Self::AccountId, Self::Reward>;
 		/// Stake and slash scheme.
 		type StakeAndSlash: StakeAndSlash<Self::AccountId, BlockNumberFor<Self>, Self::Reward>;
+		/// Length of the relayer reputation period (in blocks).
+		///
+		/// Reputation score of the registered relayer is increased for every period with
+		/// deliveries and it decays for every period without deliveries.
+		#[pallet::constant]
+		type ReputationPeriod: Get<BlockNumberFor<Self>>;
+		/// Maximal number of consecutive reputation periods without deliveries. If registered
+		/// relayer has been inactive for more periods, anyone may `deregister` it.
+		#[pallet::constant]
+		type MaxInactivePeriods: Get<u32>;
+		/// Part of the stake that is slashed when inactive relayer is `deregistered`.
+		#[pallet::constant]
+		type StaleRelayerSlash: Get<Perbill>;
+		/// Account that receives stake, slashed from inactive relayers.
+		///
+		/// The account must exist, otherwise the stake won't be slashed.
+		type SlashDestination: Get<Self::AccountId>;
 		/// Pallet call weights.
 		type WeightInfo: WeightInfoExt;
 	}
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::call]
@@ -116,7 +145,9 @@ pub mod pallet {
 		///
 		/// Registration allows relayer to get priority boost for its message delivery transactions.
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::register())]
+		#[pallet::weight(
+			T::WeightInfo::register().saturating_add(Pallet::<T>::relayer_reputation_weight())
+		)]
 		pub fn register(origin: OriginFor<T>, valid_till: BlockNumberFor<T>) -> DispatchResult {
 			let relayer = ensure_signed(origin)?;
 
@@ -161,6 +192,13 @@ pub mod pallet {
 				}
 				registration.stake = required_stake;
 
+				if maybe_registration.is_none() {
+					RelayerReputations::<T>::insert(
+						&relayer,
+						RelayerReputation::new(Self::current_reputation_period()),
+					);
+				}
+
 				log::trace!(target: LOG_TARGET, "Successfully registered relayer: {:?}", relayer);
 				Self::deposit_event(Event::<T>::RegistrationUpdated {
 					relayer: relayer.clone(),
@@ -178,7 +216,9 @@ pub mod pallet {
 		/// After this call, message delivery transactions of the relayer won't get any priority
 		/// boost.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::deregister())]
+		#[pallet::weight(
+			T::WeightInfo::deregister().saturating_add(Pallet::<T>::relayer_reputation_weight())
+		)]
 		pub fn deregister(origin: OriginFor<T>) -> DispatchResult {
 			let relayer = ensure_signed(origin)?;
 
@@ -199,6 +239,8 @@ pub mod pallet {
 					Self::do_unreserve(&relayer, registration.stake)?;
 				}
 
+				RelayerReputations::<T>::remove(&relayer);
+
 				log::trace!(target: LOG_TARGET, "Successfully deregistered relayer: {:?}", relayer);
 				Self::deposit_event(Event::<T>::Deregistered { relayer: relayer.clone() });
 
@@ -207,6 +249,62 @@ pub mod pallet {
 				Ok(())
 			})
 		}
+
+		/// `Deregister` relayer that has not been delivering messages for more than
+		/// `MaxInactivePeriods` reputation periods.
+		///
+		/// May be called by anyone. The `StaleRelayerSlash` part of relayer stake is slashed and
+		/// sent to the `SlashDestination` account. The rest of the stake is released.
+		#[pallet::call_index(3)]
+		#[pallet::weight(Pallet::<T>::deregister_inactive_weight())]
+		pub fn deregister_inactive(origin: OriginFor<T>, relayer: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			RegisteredRelayers::<T>::try_mutate(&relayer, |maybe_registration| -> DispatchResult {
+				let registration = match maybe_registration.take() {
+					Some(registration) => registration,
+					None => fail!(Error::<T>::NotRegistered),
+				};
+
+				let inactive_periods = Self::relayer_inactive_periods(&relayer);
+				ensure!(
+					inactive_periods > T::MaxInactivePeriods::get(),
+					Error::<T>::RelayerIsNotInactive,
+				);
+
+				// relayer has been occupying the registration without delivering messages, so
+				// we slash part of its stake
+				let slashed = Self::slash_stake_to_destination(
+					&relayer,
+					T::StaleRelayerSlash::get().mul_floor(registration.stake),
+				);
+
+				// if remaining stake is non-zero, we should do unreserve
+				let remaining_stake = registration.stake.saturating_sub(slashed);
+				if !remaining_stake.is_zero() {
+					Self::do_unreserve(&relayer, remaining_stake)?;
+				}
+
+				RelayerReputations::<T>::remove(&relayer);
+
+				log::trace!(
+					target: LOG_TARGET,
+					"Deregistered relayer {:?}, inactive for {} periods. Slashed: {:?}",
+					relayer,
+					inactive_periods,
+					slashed,
+				);
+				Self::deposit_event(Event::<T>::InactiveRelayerDeregistered {
+					relayer: relayer.clone(),
+					inactive_periods,
+					slashed,
+				});
+
+				*maybe_registration = None;
+
+				Ok(())
+			})
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -237,6 +335,30 @@ pub mod pallet {
 			true
 		}
 
+		/// Returns standing of the registered relayer at current block.
+		///
+		/// Returns `None` if relayer is not registered.
+		pub fn relayer_standing(
+			relayer: &T::AccountId,
+		) -> Option<RelayerStanding<BlockNumberFor<T>, T::Reward>> {
+			let registration = Self::registered_relayer(relayer)?;
+			let current_period = Self::current_reputation_period();
+			let (score, inactive_periods) = match Self::relayer_reputation(relayer) {
+				Some(reputation) => (
+					reputation.score_at(current_period),
+					reputation.inactive_periods(current_period),
+				),
+				None => (0, 0),
+			};
+
+			Some(RelayerStanding {
+				registration,
+				score,
+				inactive_periods,
+				may_be_deregistered: inactive_periods > T::MaxInactivePeriods::get(),
+			})
+		}
+
 		/// Slash and `deregister` relayer. This function slashes all staked balance.
 		///
 		/// It may fail inside, but error is swallowed and we only log it.
@@ -244,6 +366,7 @@ pub mod pallet {
 			relayer: &T::AccountId,
 			slash_destination: RewardsAccountParams,
 		) {
+			RelayerReputations::<T>::remove(relayer);
 			let registration = match RegisteredRelayers::<T>::take(relayer) {
 				Some(registration) => registration,
 				None => {
@@ -311,6 +434,11 @@ pub mod pallet {
 				return
 			}
 
+			let current_period = Self::current_reputation_period();
+			RelayerReputations::<T>::mutate_extant(relayer, |reputation| {
+				reputation.note_activity(current_period)
+			});
+
 			RelayerRewards::<T>::mutate(
 				relayer,
 				rewards_account_params,
@@ -329,6 +457,46 @@ pub mod pallet {
 			);
 		}
 
+		/// Returns weight of reading and updating `RelayerReputations` entry of a single relayer.
+		///
+		/// Pallet weights have been generated before the `RelayerReputations` map has been
+		/// added, so this weight is added on top of generated weights of all calls and methods
+		/// that are touching the map.
+		pub fn relayer_reputation_weight() -> Weight {
+			// `Blake2_128Concat` hash + key + value
+			let max_entry_size = 16_u64
+				.saturating_add(T::AccountId::max_encoded_len() as u64)
+				.saturating_add(RelayerReputation::<BlockNumberFor<T>>::max_encoded_len() as u64);
+			T::DbWeight::get()
+				.reads_writes(1, 1)
+				.saturating_add(Weight::from_parts(0, max_entry_size))
+		}
+
+		/// Returns weight of the `deregister_inactive` call.
+		///
+		/// The call slashes part of the relayer stake and unreserves the rest, so it is covered
+		/// by the weights of `slash_and_deregister` and `deregister`.
+		pub(crate) fn deregister_inactive_weight() -> Weight {
+			T::WeightInfo::slash_and_deregister()
+				.saturating_add(T::WeightInfo::deregister())
+				.saturating_add(Self::relayer_reputation_weight())
+		}
+
+		/// Return index of the current reputation period.
+		pub(crate) fn current_reputation_period() -> BlockNumberFor<T> {
+			frame_system::Pallet::<T>::block_number()
+				.checked_div(&T::ReputationPeriod::get())
+				.unwrap_or_else(Zero::zero)
+		}
+
+		/// Return number of full reputation periods, when registered relayer has not been
+		/// delivering messages.
+		fn relayer_inactive_periods(relayer: &T::AccountId) -> u32 {
+			Self::relayer_reputation(relayer)
+				.map(|reputation| reputation.inactive_periods(Self::current_reputation_period()))
+				.unwrap_or(0)
+		}
+
 		/// Return required registration lease.
 		pub(crate) fn required_registration_lease() -> BlockNumberFor<T> {
 			<T::StakeAndSlash as StakeAndSlash<
@@ -347,6 +515,48 @@ pub mod pallet {
 			>>::RequiredStake::get()
 		}
 
+		/// Slash up to `amount` from the relayer stake and send it to the `SlashDestination`.
+		///
+		/// Returns amount that has been actually slashed. Errors are swallowed and we only
+		/// log them.
+		fn slash_stake_to_destination(relayer: &T::AccountId, amount: T::Reward) -> T::Reward {
+			if amount.is_zero() {
+				return Zero::zero()
+			}
+
+			let slash_destination = T::SlashDestination::get();
+			match T::StakeAndSlash::repatriate_reserved_to_account(
+				relayer,
+				&slash_destination,
+				amount,
+			) {
+				Ok(failed_to_slash) => {
+					let slashed = amount.saturating_sub(failed_to_slash);
+					log::trace!(
+						target: LOG_TARGET,
+						"Relayer account {:?} has been slashed for {:?}/{:?}. Funds were deposited to {:?}",
+						relayer,
+						slashed,
+						amount,
+						slash_destination,
+					);
+					slashed
+				},
+				Err(e) => {
+					log::debug!(
+						target: LOG_TARGET,
+						"Failed to slash relayer account {:?}: {:?}. Maybe slash destination account \
+						doesn't exist? Destination: {:?}, amount: {:?}",
+						relayer,
+						e,
+						slash_destination,
+						amount,
+					);
+					Zero::zero()
+				},
+			}
+		}
+
 		/// `Unreserve` given amount on relayer account.
 		fn do_unreserve(relayer: &T::AccountId, amount: T::Reward) -> DispatchResult {
 			let failed_to_unreserve = T::StakeAndSlash::unreserve(relayer, amount);
@@ -390,6 +600,15 @@ pub mod pallet {
 			/// Relayer account that has been `deregistered`.
 			relayer: T::AccountId,
 		},
+		/// Relayer has been `deregistered` because of inactivity.
+		InactiveRelayerDeregistered {
+			/// Relayer account that has been `deregistered`.
+			relayer: T::AccountId,
+			/// Number of reputation periods without deliveries.
+			inactive_periods: u32,
+			/// Part of the relayer stake that has been slashed.
+			slashed: T::Reward,
+		},
 		/// Relayer has been slashed and `deregistered`.
 		SlashedAndDeregistered {
 			/// Relayer account that has been `deregistered`.
@@ -418,6 +637,8 @@ pub mod pallet {
 		NotRegistered,
 		/// Failed to `deregister` relayer, because lease is still active.
 		RegistrationIsStillActive,
+		/// Failed to `deregister` relayer, because it has been delivering messages recently.
+		RelayerIsNotInactive,
 	}
 
 	/// Map of the relayer => accumulated reward.
@@ -448,6 +669,19 @@ pub mod pallet {
 		Registration<BlockNumberFor<T>, T::Reward>,
 		OptionQuery,
 	>;
+
+	/// Reputation of registered relayers.
+	///
+	/// Use `Pallet::relayer_standing` to get the reputation score with decay applied.
+	#[pallet::storage]
+	#[pallet::getter(fn relayer_reputation)]
+	pub type RelayerReputations<T: Config> = StorageMap<
+		_,
+		<RelayerReputationsKeyProviderOf<T> as StorageMapKeyProvider>::Hasher,
+		<RelayerReputationsKeyProviderOf<T> as StorageMapKeyProvider>::Key,
+		<RelayerReputationsKeyProviderOf<T> as StorageMapKeyProvider>::Value,
+		OptionQuery,
+	>;
 }
 
 #[cfg(test)]
@@ -877,4 +1111,200 @@ mod tests {
 			assert!(Pallet::<TestRuntime>::is_registration_active(&REGISTER_RELAYER));
 		});
 	}
+
+	#[test]
+	fn relayer_reputation_is_tracked_for_registered_relayers() {
+		run_test(|| {
+			get_ready_for_events();
+
+			assert_ok!(Pallet::<TestRuntime>::register(
+				RuntimeOrigin::signed(REGISTER_RELAYER),
+				150
+			));
+			assert_eq!(
+				Pallet::<TestRuntime>::relayer_reputation(REGISTER_RELAYER),
+				Some(RelayerReputation { score: 0, last_active_period: 0 }),
+			);
+
+			System::<TestRuntime>::set_block_number(ReputationPeriod::get() + 5);
+			Pallet::<TestRuntime>::register_relayer_reward(
+				TEST_REWARDS_ACCOUNT_PARAMS,
+				&REGISTER_RELAYER,
+				100,
+			);
+			Pallet::<TestRuntime>::register_relayer_reward(
+				TEST_REWARDS_ACCOUNT_PARAMS,
+				&REGISTER_RELAYER,
+				100,
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::relayer_reputation(REGISTER_RELAYER),
+				Some(RelayerReputation { score: 1, last_active_period: 1 }),
+			);
+
+			// reputation of unregistered relayers is not tracked
+			Pallet::<TestRuntime>::register_relayer_reward(
+				TEST_REWARDS_ACCOUNT_PARAMS,
+				&REGULAR_RELAYER,
+				100,
+			);
+			assert_eq!(Pallet::<TestRuntime>::relayer_reputation(REGULAR_RELAYER), None);
+		});
+	}
+
+	#[test]
+	fn relayer_standing_reports_decayed_reputation() {
+		run_test(|| {
+			get_ready_for_events();
+
+			assert_eq!(Pallet::<TestRuntime>::relayer_standing(&REGISTER_RELAYER), None);
+
+			assert_ok!(Pallet::<TestRuntime>::register(
+				RuntimeOrigin::signed(REGISTER_RELAYER),
+				150
+			));
+			RelayerReputations::<TestRuntime>::insert(
+				REGISTER_RELAYER,
+				RelayerReputation { score: 10, last_active_period: 0 },
+			);
+
+			System::<TestRuntime>::set_block_number(
+				ReputationPeriod::get() * (MaxInactivePeriods::get() as u64 + 1),
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::relayer_standing(&REGISTER_RELAYER),
+				Some(RelayerStanding {
+					registration: Registration { valid_till: 150, stake: Stake::get() },
+					score: 10 - MaxInactivePeriods::get(),
+					inactive_periods: MaxInactivePeriods::get(),
+					may_be_deregistered: false,
+				}),
+			);
+
+			System::<TestRuntime>::set_block_number(
+				ReputationPeriod::get() * (MaxInactivePeriods::get() as u64 + 2),
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::relayer_standing(&REGISTER_RELAYER),
+				Some(RelayerStanding {
+					registration: Registration { valid_till: 150, stake: Stake::get() },
+					score: 10 - MaxInactivePeriods::get() - 1,
+					inactive_periods: MaxInactivePeriods::get() + 1,
+					may_be_deregistered: true,
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn deregister_inactive_fails_for_unregistered_relayer() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::deregister_inactive(
+					RuntimeOrigin::signed(REGULAR_RELAYER),
+					REGISTER_RELAYER
+				),
+				Error::<TestRuntime>::NotRegistered,
+			);
+		});
+	}
+
+	#[test]
+	fn deregister_inactive_fails_if_relayer_is_active() {
+		run_test(|| {
+			get_ready_for_events();
+
+			assert_ok!(Pallet::<TestRuntime>::register(
+				RuntimeOrigin::signed(REGISTER_RELAYER),
+				150
+			));
+
+			System::<TestRuntime>::set_block_number(
+				ReputationPeriod::get() * (MaxInactivePeriods::get() as u64 + 1),
+			);
+			assert_noop!(
+				Pallet::<TestRuntime>::deregister_inactive(
+					RuntimeOrigin::signed(REGULAR_RELAYER),
+					REGISTER_RELAYER
+				),
+				Error::<TestRuntime>::RelayerIsNotInactive,
+			);
+		});
+	}
+
+	#[test]
+	fn deregister_inactive_works() {
+		run_test(|| {
+			get_ready_for_events();
+
+			assert_ok!(Pallet::<TestRuntime>::register(
+				RuntimeOrigin::signed(REGISTER_RELAYER),
+				150
+			));
+
+			System::<TestRuntime>::set_block_number(
+				ReputationPeriod::get() * (MaxInactivePeriods::get() as u64 + 2),
+			);
+
+			let slashed = StaleRelayerSlash::get() * Stake::get();
+			let reserved_balance = Balances::reserved_balance(REGISTER_RELAYER);
+			let free_balance = Balances::free_balance(REGISTER_RELAYER);
+			let destination_balance = Balances::free_balance(SLASH_DESTINATION_ACCOUNT);
+			assert_ok!(Pallet::<TestRuntime>::deregister_inactive(
+				RuntimeOrigin::signed(REGULAR_RELAYER),
+				REGISTER_RELAYER
+			));
+			assert_eq!(
+				Balances::reserved_balance(REGISTER_RELAYER),
+				reserved_balance - Stake::get()
+			);
+			assert_eq!(
+				Balances::free_balance(REGISTER_RELAYER),
+				free_balance + Stake::get() - slashed
+			);
+			assert_eq!(
+				Balances::free_balance(SLASH_DESTINATION_ACCOUNT),
+				destination_balance + slashed
+			);
+			assert_eq!(Pallet::<TestRuntime>::registered_relayer(REGISTER_RELAYER), None);
+			assert_eq!(Pallet::<TestRuntime>::relayer_reputation(REGISTER_RELAYER), None);
+
+			assert_eq!(
+				System::<TestRuntime>::events().last(),
+				Some(&EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Relayers(Event::InactiveRelayerDeregistered {
+						relayer: REGISTER_RELAYER,
+						inactive_periods: MaxInactivePeriods::get() + 1,
+						slashed,
+					}),
+					topics: vec![],
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn deregister_inactive_releases_whole_stake_if_slash_destination_is_missing() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::register(
+				RuntimeOrigin::signed(REGISTER_RELAYER),
+				150
+			));
+			Balances::set_balance(&SLASH_DESTINATION_ACCOUNT, 0);
+
+			System::<TestRuntime>::set_block_number(
+				ReputationPeriod::get() * (MaxInactivePeriods::get() as u64 + 2),
+			);
+
+			let free_balance = Balances::free_balance(REGISTER_RELAYER);
+			assert_ok!(Pallet::<TestRuntime>::deregister_inactive(
+				RuntimeOrigin::signed(REGULAR_RELAYER),
+				REGISTER_RELAYER
+			));
+			assert_eq!(Balances::reserved_balance(REGISTER_RELAYER), 0);
+			assert_eq!(Balances::free_balance(REGISTER_RELAYER), free_balance + Stake::get());
+			assert_eq!(Balances::free_balance(SLASH_DESTINATION_ACCOUNT), 0);
+		});
+	}
 }
@@ -0,0 +1,155 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module that is responsible for migration of storage.
+
+use crate::{Config, Pallet, RegisteredRelayers, RelayerReputations, LOG_TARGET};
+
+use bp_relayers::RelayerReputation;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+#[cfg(feature = "try-runtime")]
+use sp_std::prelude::*;
+
+/// Version 1 migration.
+///
+/// Relayers, that have been registered before the `RelayerReputations` map has been added,
+/// have no reputation entry, so their activity is not tracked and they can't be deregistered
+/// as inactive. This migration starts tracking their reputation from the current period.
+pub mod v1 {
+	use super::*;
+
+	/// Migrates the pallet storage to version 1.
+	pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let onchain_version = Pallet::<T>::on_chain_storage_version();
+			if onchain_version != 0 {
+				log::info!(
+					target: LOG_TARGET,
+					"Migration did not execute. This probably should be removed"
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let current_period = Pallet::<T>::current_reputation_period();
+			let mut registered_relayers = 0_u64;
+			let mut migrated_relayers = 0_u64;
+			for relayer in RegisteredRelayers::<T>::iter_keys() {
+				registered_relayers += 1;
+				if !RelayerReputations::<T>::contains_key(&relayer) {
+					RelayerReputations::<T>::insert(
+						&relayer,
+						RelayerReputation::new(current_period),
+					);
+					migrated_relayers += 1;
+				}
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(
+				target: LOG_TARGET,
+				"Started tracking reputation of {} registered relayers, upgraded storage to version 1",
+				migrated_relayers,
+			);
+
+			// 1 read for `onchain_version`, 1 write to put version, 1 read for every registered
+			// relayer key (+ 1 read for the end of the map), 1 read for every reputation entry
+			// and 1 write for every inserted reputation entry
+			T::DbWeight::get().reads_writes(
+				registered_relayers.saturating_mul(2).saturating_add(2),
+				migrated_relayers.saturating_add(1),
+			)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::DispatchError> {
+			let registered_relayers = RegisteredRelayers::<T>::iter_keys().count() as u32;
+			Ok(registered_relayers.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(registered_relayers: Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+			let registered_relayers: u32 = Decode::decode(&mut registered_relayers.as_slice())
+				.expect(
+					"the state parameter should be something that was generated by pre_upgrade",
+				);
+			ensure!(
+				RegisteredRelayers::<T>::iter_keys().count() as u32 == registered_relayers,
+				"the number of registered relayers has changed",
+			);
+			ensure!(
+				RegisteredRelayers::<T>::iter_keys()
+					.all(|relayer| RelayerReputations::<T>::contains_key(&relayer)),
+				"every registered relayer must have the reputation entry",
+			);
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 1,
+				"the storage version must be upgraded",
+			);
+			Ok(())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+	use bp_relayers::Registration;
+
+	#[test]
+	fn migration_to_v1_tracks_reputation_of_registered_relayers() {
+		run_test(|| {
+			StorageVersion::new(0).put::<Pallet<TestRuntime>>();
+			System::set_block_number(ReputationPeriod::get() * 3);
+
+			// relayer that has been registered before the `RelayerReputations` map was added
+			let registration = Registration { valid_till: 150, stake: Stake::get() };
+			RegisteredRelayers::<TestRuntime>::insert(REGISTER_RELAYER, registration);
+			// and relayer that already has the reputation entry
+			RegisteredRelayers::<TestRuntime>::insert(REGULAR_RELAYER, registration);
+			RelayerReputations::<TestRuntime>::insert(
+				REGULAR_RELAYER,
+				RelayerReputation { score: 5, last_active_period: 2 },
+			);
+
+			assert_eq!(
+				v1::MigrateToV1::<TestRuntime>::on_runtime_upgrade(),
+				DbWeight::get().reads_writes(6, 2),
+			);
+
+			assert_eq!(
+				RelayerReputations::<TestRuntime>::get(REGISTER_RELAYER),
+				Some(RelayerReputation { score: 0, last_active_period: 3 }),
+			);
+			assert_eq!(
+				RelayerReputations::<TestRuntime>::get(REGULAR_RELAYER),
+				Some(RelayerReputation { score: 5, last_active_period: 2 }),
+			);
+			assert_eq!(Pallet::<TestRuntime>::on_chain_storage_version(), StorageVersion::new(1));
+
+			// the second run is a no-op
+			assert_eq!(
+				v1::MigrateToV1::<TestRuntime>::on_runtime_upgrade(),
+				DbWeight::get().reads(1),
+			);
+		});
+	}
+}
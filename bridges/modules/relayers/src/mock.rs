@@ -26,7 +26,7 @@ use frame_support::{parameter_types, traits::fungible::Mutate, weights::RuntimeD
 use sp_core::H256;
 use sp_runtime::{
 	traits::{BlakeTwo256, ConstU32, IdentityLookup},
-	BuildStorage,
+	BuildStorage, Perbill,
 };
 
 pub type AccountId = u64;
@@ -59,6 +59,10 @@ parameter_types! {
 	pub const ReserveId: [u8; 8] = *b"brdgrlrs";
 	pub const Stake: Balance = 1_000;
 	pub const Lease: BlockNumber = 8;
+	pub const ReputationPeriod: BlockNumber = 10;
+	pub const MaxInactivePeriods: u32 = 3;
+	pub const StaleRelayerSlash: Perbill = Perbill::from_percent(20);
+	pub const SlashDestination: AccountId = SLASH_DESTINATION_ACCOUNT;
 }
 
 impl frame_system::Config for TestRuntime {
@@ -108,6 +112,10 @@ impl pallet_bridge_relayers::Config for TestRuntime {
 	type Reward = Balance;
 	type PaymentProcedure = TestPaymentProcedure;
 	type StakeAndSlash = TestStakeAndSlash;
+	type ReputationPeriod = ReputationPeriod;
+	type MaxInactivePeriods = MaxInactivePeriods;
+	type StaleRelayerSlash = StaleRelayerSlash;
+	type SlashDestination = SlashDestination;
 	type WeightInfo = ();
 }
 
@@ -139,6 +147,9 @@ pub const FAILING_RELAYER: AccountId = 2;
 /// Relayer that is able to register.
 pub const REGISTER_RELAYER: AccountId = 42;
 
+/// Account that receives stake, slashed from inactive relayers.
+pub const SLASH_DESTINATION_ACCOUNT: AccountId = 1000;
+
 /// Payment procedure that rejects payments to the `FAILING_RELAYER`.
 pub struct TestPaymentProcedure;
 
@@ -174,6 +185,7 @@ pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
 	new_test_ext().execute_with(|| {
 		Balances::mint_into(&REGISTER_RELAYER, ExistentialDeposit::get() + 10 * Stake::get())
 			.unwrap();
+		Balances::mint_into(&SLASH_DESTINATION_ACCOUNT, ExistentialDeposit::get()).unwrap();
 
 		test()
 	})
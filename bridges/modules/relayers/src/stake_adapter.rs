@@ -60,10 +60,18 @@ where
 	) -> Result<Currency::Balance, DispatchError> {
 		let beneficiary_account =
 			PayRewardFromAccount::<(), AccountId>::rewards_account(beneficiary);
+		Self::repatriate_reserved_to_account(relayer, &beneficiary_account, amount)
+	}
+
+	fn repatriate_reserved_to_account(
+		relayer: &AccountId,
+		beneficiary: &AccountId,
+		amount: Currency::Balance,
+	) -> Result<Currency::Balance, DispatchError> {
 		Currency::repatriate_reserved_named(
 			&ReserveId::get(),
 			relayer,
-			&beneficiary_account,
+			beneficiary,
 			amount,
 			BalanceStatus::Free,
 		)
@@ -168,6 +176,31 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn repatriate_reserved_to_account_works() {
+		run_test(|| {
+			let beneficiary_account = 100;
+			Balances::mint_into(&beneficiary_account, ExistentialDeposit::get()).unwrap();
+
+			Balances::mint_into(&3, test_stake() * 2).unwrap();
+			TestStakeAndSlash::reserve(&3, test_stake()).unwrap();
+			assert_eq!(
+				TestStakeAndSlash::repatriate_reserved_to_account(
+					&3,
+					&beneficiary_account,
+					test_stake() / 3
+				),
+				Ok(0)
+			);
+			assert_eq!(Balances::free_balance(3), test_stake());
+			assert_eq!(Balances::reserved_balance(3), test_stake() - test_stake() / 3);
+			assert_eq!(
+				Balances::free_balance(beneficiary_account),
+				ExistentialDeposit::get() + test_stake() / 3
+			);
+		})
+	}
+
 	#[test]
 	fn repatriate_reserved_doesnt_work_when_beneficiary_account_is_missing() {
 		run_test(|| {
@@ -17,7 +17,9 @@
 //! Pallet that may be used instead of `SovereignPaidRemoteExporter` in the XCM router
 //! configuration. The main thing that the pallet offers is the dynamic message fee,
 //! that is computed based on the bridge queues state. It starts exponentially increasing
-//! if the queue between this chain and the sibling/child bridge hub is congested.
+//! if the queue between this chain and the sibling/child bridge hub is congested. The fee
+//! factor is increased at every block and with every sent message while the queue is
+//! congested, and it decays at every block when the queue is drained.
 //!
 //! All other bridge hub queues offer some backpressure mechanisms. So if at least one
 //! of all queues is congested, it will eventually lead to the growth of the queue at
@@ -30,6 +32,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use bp_messages::{LaneId, MessageNonce};
 use bp_xcm_bridge_hub_router::{
 	BridgeState, XcmChannelStatusProvider, MINIMAL_DELIVERY_FEE_FACTOR,
 };
@@ -48,8 +51,8 @@ pub mod weights;
 
 mod mock;
 
-/// The factor that is used to increase current message fee factor when bridge experiencing
-/// some lags.
+/// The factor that is used to increase current message fee factor for every sent message
+/// when bridge experiencing some lags.
 const EXPONENTIAL_FEE_BASE: FixedU128 = FixedU128::from_rational(105, 100); // 1.05
 /// The factor that is used to increase current message fee factor for every sent kilobyte.
 const MESSAGE_SIZE_FEE_BASE: FixedU128 = FixedU128::from_rational(1, 1000); // 0.001
@@ -76,6 +79,9 @@ pub mod pallet {
 
 	#[pallet::config]
 	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// Benchmarks results from runtime we're plugged into.
 		type WeightInfo: WeightInfo;
 
@@ -97,11 +103,26 @@ pub mod pallet {
 		/// Underlying channel with the sibling bridge hub. It must match the channel, used
 		/// by the `Self::ToBridgeHubSender`.
 		type WithBridgeHubChannel: XcmChannelStatusProvider;
+		/// Lane that is used by the sibling/child bridge hub to deliver messages, sent by this
+		/// router.
+		type BridgeLane: Get<LaneId>;
 
 		/// Additional fee that is paid for every byte of the outbound message.
 		type ByteFee: Get<u128>;
 		/// Asset that is used to paid bridge fee.
 		type FeeAsset: Get<AssetId>;
+
+		/// The factor that is used to increase delivery fee factor at every block, while the
+		/// bridge is congested. Must be larger than one.
+		#[pallet::constant]
+		type FeeFactorIncreasePerBlock: Get<FixedU128>;
+		/// The factor that is used to decrease delivery fee factor at every block, while the
+		/// bridge is not congested. Must be larger than one.
+		#[pallet::constant]
+		type FeeFactorDecreasePerBlock: Get<FixedU128>;
+		/// Maximal delivery fee factor.
+		#[pallet::constant]
+		type MaxDeliveryFeeFactor: Get<FixedU128>;
 	}
 
 	#[pallet::pallet]
@@ -115,15 +136,24 @@ pub mod pallet {
 			// that is definitely congestion. If inbound is suspended, then we are not able to
 			// receive the "report_bridge_status" signal (that maybe sent by the bridge hub).
 
-			// if the channel with sibling/child bridge hub is suspended, we don't change
-			// anything
-			if T::WithBridgeHubChannel::is_congested() {
-				return T::WeightInfo::on_initialize_when_congested()
-			}
-
-			// if bridge has reported congestion, we don't change anything
+			// if the channel with sibling/child bridge hub is suspended or bridge has reported
+			// congestion, we increase the fee factor
 			let mut bridge = Self::bridge();
-			if bridge.is_congested {
+			if T::WithBridgeHubChannel::is_congested() || bridge.is_congested {
+				let previous_factor = bridge.delivery_fee_factor;
+				bridge.delivery_fee_factor = T::MaxDeliveryFeeFactor::get()
+					.min(previous_factor.saturating_mul(T::FeeFactorIncreasePerBlock::get()));
+				if bridge.delivery_fee_factor != previous_factor {
+					log::info!(
+						target: LOG_TARGET,
+						"Bridge queue is congested. Increased fee factor from {} to {}",
+						previous_factor,
+						bridge.delivery_fee_factor,
+					);
+
+					Bridge::<T, I>::put(bridge);
+				}
+
 				return T::WeightInfo::on_initialize_when_congested()
 			}
 
@@ -133,8 +163,8 @@ pub mod pallet {
 			}
 
 			let previous_factor = bridge.delivery_fee_factor;
-			bridge.delivery_fee_factor =
-				MINIMAL_DELIVERY_FEE_FACTOR.max(bridge.delivery_fee_factor / EXPONENTIAL_FEE_BASE);
+			bridge.delivery_fee_factor = MINIMAL_DELIVERY_FEE_FACTOR
+				.max(bridge.delivery_fee_factor / T::FeeFactorDecreasePerBlock::get());
 			log::info!(
 				target: LOG_TARGET,
 				"Bridge queue is uncongested. Decreased fee factor from {} to {}",
@@ -145,6 +175,21 @@ pub mod pallet {
 			Bridge::<T, I>::put(bridge);
 			T::WeightInfo::on_initialize_when_non_congested()
 		}
+
+		fn integrity_test() {
+			assert!(
+				T::FeeFactorIncreasePerBlock::get() > MINIMAL_DELIVERY_FEE_FACTOR,
+				"FeeFactorIncreasePerBlock must be larger than one",
+			);
+			assert!(
+				T::FeeFactorDecreasePerBlock::get() > MINIMAL_DELIVERY_FEE_FACTOR,
+				"FeeFactorDecreasePerBlock must be larger than one",
+			);
+			assert!(
+				T::MaxDeliveryFeeFactor::get() >= MINIMAL_DELIVERY_FEE_FACTOR,
+				"MaxDeliveryFeeFactor must not be less than one",
+			);
+		}
 	}
 
 	#[pallet::call]
@@ -186,7 +231,55 @@ pub mod pallet {
 	#[pallet::getter(fn bridge)]
 	pub type Bridge<T: Config<I>, I: 'static = ()> = StorageValue<_, BridgeState, ValueQuery>;
 
+	/// Nonce of the latest message that has been sent over the bridge by this router.
+	///
+	/// It matches the nonce of the message at the bridge hub outbound lane only if all messages
+	/// of the lane have been sent by this router.
+	#[pallet::storage]
+	pub type LatestMessageNonce<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, MessageNonce, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// Message has been sent to the sibling/child bridge hub.
+		MessageSent {
+			/// Lane that is used to deliver the message over the bridge.
+			lane_id: LaneId,
+			/// Nonce of the message, assigned by this router.
+			nonce: MessageNonce,
+			/// Size of the encoded XCM message.
+			message_size: u32,
+			/// Total message delivery cost, including the bridge fee and the fee for delivering
+			/// message to the sibling/child bridge hub.
+			cost: MultiAssets,
+			/// Delivery fee factor that has been used to compute the bridge fee.
+			delivery_fee_factor: FixedU128,
+		},
+	}
+
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Returns current delivery fee factor, that is used to compute bridge fee.
+		pub fn delivery_fee_factor() -> FixedU128 {
+			Self::bridge().delivery_fee_factor
+		}
+
+		/// Assign nonce to the message, that has been sent over the bridge and emit event with
+		/// message details. Must be called before the fee factor is updated.
+		pub(crate) fn deposit_message_sent_event(message_size: u32, cost: MultiAssets) {
+			let nonce = LatestMessageNonce::<T, I>::mutate(|nonce| {
+				*nonce = nonce.saturating_add(1);
+				*nonce
+			});
+			Self::deposit_event(Event::MessageSent {
+				lane_id: T::BridgeLane::get(),
+				nonce,
+				message_size,
+				cost,
+				delivery_fee_factor: Self::delivery_fee_factor(),
+			});
+		}
+
 		/// Called when new message is sent (queued to local outbound XCM queue) over the bridge.
 		pub(crate) fn on_message_sent_to_bridge(message_size: u32) {
 			let _ = Bridge::<T, I>::try_mutate(|bridge| {
@@ -204,8 +297,8 @@ pub mod pallet {
 					.saturating_mul(MESSAGE_SIZE_FEE_BASE);
 				let total_factor = EXPONENTIAL_FEE_BASE.saturating_add(message_size_factor);
 				let previous_factor = bridge.delivery_fee_factor;
-				bridge.delivery_fee_factor =
-					bridge.delivery_fee_factor.saturating_mul(total_factor);
+				bridge.delivery_fee_factor = T::MaxDeliveryFeeFactor::get()
+					.min(bridge.delivery_fee_factor.saturating_mul(total_factor));
 
 				log::info!(
 					target: LOG_TARGET,
@@ -313,7 +406,7 @@ impl<T: Config<I>, I: 'static> ExporterFor for Pallet<T, I> {
 // XCMP/DMP transport. This allows injecting dynamic message fees into XCM programs that
 // are going to the bridged network.
 impl<T: Config<I>, I: 'static> SendXcm for Pallet<T, I> {
-	type Ticket = (u32, <T::ToBridgeHubSender as SendXcm>::Ticket);
+	type Ticket = (u32, MultiAssets, <T::ToBridgeHubSender as SendXcm>::Ticket);
 
 	fn validate(
 		dest: &mut Option<MultiLocation>,
@@ -339,15 +432,18 @@ impl<T: Config<I>, I: 'static> SendXcm for Pallet<T, I> {
 		// the `Config::ToBridgeHubSender`) and (2) to-bridged bridge hub delivery (returned by
 		// `Self::exporter_for`)
 		ViaBridgeHubExporter::<T, I>::validate(dest, xcm)
-			.map(|(ticket, cost)| ((message_size, ticket), cost))
+			.map(|(ticket, cost)| ((message_size, cost.clone(), ticket), cost))
 	}
 
 	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
 		// use router to enqueue message to the sibling/child bridge hub. This also should handle
 		// payment for passing through this queue.
-		let (message_size, ticket) = ticket;
+		let (message_size, cost, ticket) = ticket;
 		let xcm_hash = ViaBridgeHubExporter::<T, I>::deliver(ticket)?;
 
+		// let everyone know that the message has been sent
+		Self::deposit_message_sent_event(message_size, cost);
+
 		// increase delivery fee factor if required
 		Self::on_message_sent_to_bridge(message_size);
 
@@ -382,27 +478,51 @@ mod tests {
 	}
 
 	#[test]
-	fn fee_factor_is_not_decreased_from_on_initialize_when_xcm_channel_is_congested() {
+	fn fee_factor_is_increased_from_on_initialize_when_xcm_channel_is_congested() {
 		run_test(|| {
 			Bridge::<TestRuntime, ()>::put(uncongested_bridge(FixedU128::from_rational(125, 100)));
 			TestWithBridgeHubChannel::make_congested();
 
-			// it should not decrease, because xcm channel is congested
-			let old_bridge = XcmBridgeHubRouter::bridge();
+			// it should increase, because xcm channel is congested
 			XcmBridgeHubRouter::on_initialize(One::one());
-			assert_eq!(XcmBridgeHubRouter::bridge(), old_bridge);
+			assert_eq!(
+				XcmBridgeHubRouter::bridge(),
+				uncongested_bridge(
+					FixedU128::from_rational(125, 100) * FeeFactorIncreasePerBlock::get()
+				),
+			);
 		})
 	}
 
 	#[test]
-	fn fee_factor_is_not_decreased_from_on_initialize_when_bridge_has_reported_congestion() {
+	fn fee_factor_is_increased_from_on_initialize_when_bridge_has_reported_congestion() {
 		run_test(|| {
 			Bridge::<TestRuntime, ()>::put(congested_bridge(FixedU128::from_rational(125, 100)));
 
-			// it should not decrease, because bridge congested
-			let old_bridge = XcmBridgeHubRouter::bridge();
+			// it should increase, because bridge congested
+			XcmBridgeHubRouter::on_initialize(One::one());
+			assert_eq!(
+				XcmBridgeHubRouter::bridge(),
+				congested_bridge(
+					FixedU128::from_rational(125, 100) * FeeFactorIncreasePerBlock::get()
+				),
+			);
+		})
+	}
+
+	#[test]
+	fn fee_factor_is_not_increased_above_maximal_factor() {
+		run_test(|| {
+			Bridge::<TestRuntime, ()>::put(congested_bridge(MINIMAL_DELIVERY_FEE_FACTOR));
+
+			// it should eventually increase to the maximal factor
+			while XcmBridgeHubRouter::bridge().delivery_fee_factor < MaxDeliveryFeeFactor::get() {
+				XcmBridgeHubRouter::on_initialize(One::one());
+			}
+
+			// verify that it doesn't increase anymore
 			XcmBridgeHubRouter::on_initialize(One::one());
-			assert_eq!(XcmBridgeHubRouter::bridge(), old_bridge);
+			assert_eq!(XcmBridgeHubRouter::bridge(), congested_bridge(MaxDeliveryFeeFactor::get()));
 		})
 	}
 
@@ -505,6 +625,42 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn sent_message_emits_event() {
+		run_test(|| {
+			frame_system::Pallet::<TestRuntime>::set_block_number(1);
+
+			let dest = MultiLocation::new(
+				2,
+				X2(GlobalConsensus(BridgedNetworkId::get()), Parachain(1000)),
+			);
+			let xcm: Xcm<()> = vec![ClearOrigin].into();
+			let message_size = xcm.encoded_size() as u32;
+			let (_, cost) = send_xcm::<XcmBridgeHubRouter>(dest, xcm.clone()).unwrap();
+			send_xcm::<XcmBridgeHubRouter>(dest, xcm).unwrap();
+
+			assert_eq!(LatestMessageNonce::<TestRuntime, ()>::get(), 2);
+			assert_eq!(
+				frame_system::Pallet::<TestRuntime>::events()
+					.into_iter()
+					.map(|record| record.event)
+					.collect::<Vec<_>>(),
+				vec![1, 2]
+					.into_iter()
+					.map(|nonce| {
+						RuntimeEvent::XcmBridgeHubRouter(Event::MessageSent {
+							lane_id: TestBridgeLane::get(),
+							nonce,
+							message_size,
+							cost: cost.clone(),
+							delivery_fee_factor: MINIMAL_DELIVERY_FEE_FACTOR,
+						})
+					})
+					.collect::<Vec<_>>(),
+			);
+		});
+	}
+
 	#[test]
 	fn sent_message_increases_factor_if_xcm_channel_is_congested() {
 		run_test(|| {
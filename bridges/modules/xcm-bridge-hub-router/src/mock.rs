@@ -18,13 +18,14 @@
 
 use crate as pallet_xcm_bridge_hub_router;
 
+use bp_messages::LaneId;
 use bp_xcm_bridge_hub_router::XcmChannelStatusProvider;
 use frame_support::{construct_runtime, parameter_types};
 use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::{
 	traits::{BlakeTwo256, ConstU128, IdentityLookup},
-	BuildStorage,
+	BuildStorage, FixedU128,
 };
 use xcm::prelude::*;
 use xcm_builder::NetworkExportTable;
@@ -43,7 +44,7 @@ construct_runtime! {
 	pub enum TestRuntime
 	{
 		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
-		XcmBridgeHubRouter: pallet_xcm_bridge_hub_router::{Pallet, Storage},
+		XcmBridgeHubRouter: pallet_xcm_bridge_hub_router::{Pallet, Storage, Event<T>},
 	}
 }
 
@@ -55,6 +56,10 @@ parameter_types! {
 	pub BridgeFeeAsset: AssetId = MultiLocation::parent().into();
 	pub BridgeTable: Vec<(NetworkId, MultiLocation, Option<MultiAsset>)>
 		= vec![(BridgedNetworkId::get(), SiblingBridgeHubLocation::get(), Some((BridgeFeeAsset::get(), BASE_FEE).into()))];
+	pub const TestBridgeLane: LaneId = LaneId([0, 0, 0, 1]);
+	pub const FeeFactorIncreasePerBlock: FixedU128 = FixedU128::from_rational(105, 100);
+	pub const FeeFactorDecreasePerBlock: FixedU128 = FixedU128::from_rational(105, 100);
+	pub const MaxDeliveryFeeFactor: FixedU128 = FixedU128::from_u32(10);
 }
 
 impl frame_system::Config for TestRuntime {
@@ -84,6 +89,7 @@ impl frame_system::Config for TestRuntime {
 }
 
 impl pallet_xcm_bridge_hub_router::Config<()> for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
 
 	type UniversalLocation = UniversalLocation;
@@ -93,9 +99,14 @@ impl pallet_xcm_bridge_hub_router::Config<()> for TestRuntime {
 	type BridgeHubOrigin = EnsureRoot<AccountId>;
 	type ToBridgeHubSender = TestToBridgeHubSender;
 	type WithBridgeHubChannel = TestWithBridgeHubChannel;
+	type BridgeLane = TestBridgeLane;
 
 	type ByteFee = ConstU128<BYTE_FEE>;
 	type FeeAsset = BridgeFeeAsset;
+
+	type FeeFactorIncreasePerBlock = FeeFactorIncreasePerBlock;
+	type FeeFactorDecreasePerBlock = FeeFactorDecreasePerBlock;
+	type MaxDeliveryFeeFactor = MaxDeliveryFeeFactor;
 }
 
 pub struct TestToBridgeHubSender;
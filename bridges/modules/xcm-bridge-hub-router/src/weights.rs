@@ -122,14 +122,19 @@ impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
 	///
 	/// Proof: UNKNOWN KEY `0x456d756c617465645369626c696e6758636d704368616e6e656c2e436f6e6765` (r:1
 	/// w:0)
+	///
+	/// Storage: `XcmBridgeHubRouter::LatestMessageNonce` (r:1 w:1)
+	///
+	/// Proof: `XcmBridgeHubRouter::LatestMessageNonce` (`max_values`: Some(1), `max_size`:
+	/// Some(8), added: 503, mode: `MaxEncodedLen`)
 	fn send_message() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `52`
 		//  Estimated: `3517`
 		// Minimum execution time: 19_709 nanoseconds.
 		Weight::from_parts(20_110_000, 3517)
-			.saturating_add(T::DbWeight::get().reads(2_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 }
 
@@ -196,13 +201,18 @@ impl WeightInfo for () {
 	///
 	/// Proof: UNKNOWN KEY `0x456d756c617465645369626c696e6758636d704368616e6e656c2e436f6e6765` (r:1
 	/// w:0)
+	///
+	/// Storage: `XcmBridgeHubRouter::LatestMessageNonce` (r:1 w:1)
+	///
+	/// Proof: `XcmBridgeHubRouter::LatestMessageNonce` (`max_values`: Some(1), `max_size`:
+	/// Some(8), added: 503, mode: `MaxEncodedLen`)
 	fn send_message() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `52`
 		//  Estimated: `3517`
 		// Minimum execution time: 19_709 nanoseconds.
 		Weight::from_parts(20_110_000, 3517)
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
 }
@@ -23,19 +23,25 @@ pub use bp_polkadot_core::{
 };
 
 use bp_messages::*;
-use bp_runtime::extensions::{
-	BridgeRejectObsoleteHeadersAndMessages, ChargeTransactionPayment, CheckEra, CheckGenesis,
-	CheckNonZeroSender, CheckNonce, CheckSpecVersion, CheckTxVersion, CheckWeight,
-	GenericSignedExtension, RefundBridgedParachainMessagesSchema,
+use bp_runtime::{
+	extensions::{
+		BridgeRejectObsoleteHeadersAndMessages, ChargeTransactionPayment, CheckEra, CheckGenesis,
+		CheckNonZeroSender, CheckNonce, CheckSpecVersion, CheckTxVersion, CheckWeight,
+		GenericSignedExtension, RefundBridgedParachainMessagesSchema,
+	},
+	BasicOperatingMode, ChainId, HeaderId,
 };
+use codec::{Decode, Encode};
 use frame_support::{
 	dispatch::DispatchClass,
 	parameter_types,
 	sp_runtime::{MultiAddress, MultiSigner},
 	weights::constants,
+	RuntimeDebug,
 };
 use frame_system::limits;
-use sp_std::time::Duration;
+use scale_info::TypeInfo;
+use sp_std::{prelude::*, time::Duration};
 
 /// Average block interval in Cumulus-based parachains.
 ///
@@ -218,3 +224,41 @@ impl BridgeHubSignedExtension for SignedExtension {
 		self.payload.7 .0
 	}
 }
+
+/// State of an outbound lane with the bridged chain and of the matching inbound lane.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct BridgeLaneInfo {
+	/// Lane identifier.
+	pub lane: LaneId,
+	/// Nonces of the outbound lane.
+	pub outbound: OutboundLaneData,
+	/// Nonce of the latest message, delivered to this chain over the inbound lane.
+	pub last_delivered_inbound_nonce: MessageNonce,
+}
+
+/// State of a single bridge, configured at the bridge hub.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct BridgeInfo {
+	/// Identifier of the bridged chain (the bridged bridge hub).
+	pub bridged_chain_id: ChainId,
+	/// Operating mode of the GRANDPA pallet, tracking the bridged relay chain.
+	pub grandpa_operating_mode: BasicOperatingMode,
+	/// Best finalized header of the bridged relay chain, known to the GRANDPA pallet.
+	pub best_finalized_relay_header: Option<HeaderId<Hash, BlockNumber>>,
+	/// Best head of the bridged bridge hub, known to the parachains pallet.
+	pub best_parachain_head: Option<HeaderId<Hash, BlockNumber>>,
+	/// Operating mode of the messages pallet.
+	pub messages_operating_mode: MessagesOperatingMode,
+	/// All open outbound lanes.
+	pub lanes: Vec<BridgeLaneInfo>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// API for querying the state of all bridges of the bridge hub.
+	///
+	/// It saves relayer dashboards from reading the storage of every bridge pallet separately.
+	pub trait BridgeInfoApi {
+		/// Returns the state of all configured bridges.
+		fn bridges() -> Vec<BridgeInfo>;
+	}
+}
@@ -20,9 +20,11 @@
 
 pub use bp_polkadot_core::*;
 
+use bp_beefy::ChainWithBeefy;
 use bp_header_chain::ChainWithGrandpa;
 use bp_runtime::{decl_bridge_finality_runtime_apis, Chain};
 use frame_support::weights::Weight;
+use sp_runtime::traits::Keccak256;
 use sp_std::prelude::Vec;
 
 /// Kusama Chain
@@ -57,6 +59,15 @@ impl ChainWithGrandpa for Kusama {
 	const AVERAGE_HEADER_SIZE_IN_JUSTIFICATION: u32 = AVERAGE_HEADER_SIZE_IN_JUSTIFICATION;
 }
 
+impl ChainWithBeefy for Kusama {
+	type CommitmentHasher = Keccak256;
+	type MmrHashing = Keccak256;
+	type MmrHash = Hash;
+	type BeefyMmrLeafExtra = Hash;
+	type AuthorityId = bp_beefy::EcdsaValidatorId;
+	type AuthorityIdToMerkleLeaf = bp_beefy::BeefyEcdsaToEthereum;
+}
+
 /// Name of the parachains pallet in the Kusama runtime.
 pub const PARAS_PALLET_NAME: &str = "Paras";
 
@@ -22,12 +22,18 @@
 mod verification;
 
 use crate::ChainWithGrandpa;
+#[cfg(feature = "aggregated-signatures")]
+pub use verification::aggregated::{aggregate_justification, verify_aggregated_justification};
 pub use verification::{
+	aggregated::{AggregatedGrandpaJustification, AggregatedSignedPrecommit},
 	equivocation::{EquivocationsCollector, GrandpaEquivocationsFinder},
-	optimizer::verify_and_optimize_justification,
-	strict::verify_justification,
-	AncestryChain, Error as JustificationVerificationError, JustificationVerificationContext,
-	PrecommitError,
+	optimizer::{
+		optimize_justification, verify_and_optimize_justification, verify_justification_leniently,
+	},
+	strict::{verify_justification, verify_justifications_batch},
+	verify_justification_with_mode, AncestryChain, AncestryHashes,
+	Error as JustificationVerificationError, JustificationVerificationContext, PrecommitError,
+	VerificationMode,
 };
 
 use bp_runtime::{BlockNumberOf, Chain, HashOf, HeaderId};
@@ -0,0 +1,318 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Logic for checking GRANDPA Finality Proofs with half-aggregated precommit signatures.
+//!
+//! Every Ed25519 signature is a pair of the commitment point `R` and the scalar `s`. With
+//! half-aggregation, relayer keeps all commitments, but replaces all scalars with a single
+//! scalar `s = sum(z_i * s_i)`, where `z_i` are coefficients derived from all precommits and
+//! signatures of the justification. So the aggregated justification is `32 * (N - 1)` bytes
+//! smaller than the regular justification with `N` precommits.
+//!
+//! Aggregated signature is verified using the `s * B == sum(z_i * R_i + z_i * k_i * A_i)`
+//! equation, where `B` is the curve base point, `A_i` is the authority public key and `k_i`
+//! is the Ed25519 challenge of the `i`-th signature. Verification is performed without host
+//! functions, so it costs more execution time than verification of regular justification.
+//!
+//! Aggregation and verification are only available with the `aggregated-signatures` feature.
+
+#[cfg(feature = "aggregated-signatures")]
+use crate::justification::{
+	verification::{AncestryChain, Error, JustificationVerificationContext, PrecommitError},
+	GrandpaJustification,
+};
+
+use bp_runtime::HeaderId;
+use codec::{Decode, Encode};
+#[cfg(feature = "aggregated-signatures")]
+use curve25519_dalek::{
+	edwards::{CompressedEdwardsY, EdwardsPoint},
+	scalar::Scalar,
+	traits::VartimeMultiscalarMul,
+};
+use frame_support::RuntimeDebugNoBound;
+use scale_info::TypeInfo;
+#[cfg(feature = "aggregated-signatures")]
+use sha2::{Digest, Sha512};
+use sp_consensus_grandpa::AuthorityId;
+#[cfg(feature = "aggregated-signatures")]
+use sp_consensus_grandpa::SetId;
+use sp_runtime::traits::Header as HeaderT;
+#[cfg(feature = "aggregated-signatures")]
+use sp_std::collections::btree_set::BTreeSet;
+use sp_std::prelude::*;
+
+/// Domain separation tag, used when computing aggregation coefficients.
+#[cfg(feature = "aggregated-signatures")]
+const HALF_AGGREGATION_CONTEXT: &[u8] = b"bridge-grandpa-half-aggregation";
+
+/// GRANDPA precommit, signed by the authority, with only commitment part of the signature.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, RuntimeDebugNoBound)]
+pub struct AggregatedSignedPrecommit<Header: HeaderT> {
+	/// The precommit message which has been signed.
+	pub precommit: finality_grandpa::Precommit<Header::Hash, Header::Number>,
+	/// The commitment (`R`) part of the authority signature.
+	pub commitment: [u8; 32],
+	/// The id of the authority that signed the precommit.
+	pub id: AuthorityId,
+}
+
+/// A GRANDPA Justification with half-aggregated precommit signatures.
+///
+/// It is precomputed off-chain (see [`aggregate_justification`]) from the regular
+/// [`GrandpaJustification`].
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, RuntimeDebugNoBound)]
+pub struct AggregatedGrandpaJustification<Header: HeaderT> {
+	/// The round (voting period) this justification is valid for.
+	pub round: u64,
+	/// The target block hash.
+	pub target_hash: Header::Hash,
+	/// The target block number.
+	pub target_number: Header::Number,
+	/// Precommits for the chain which is to be finalized.
+	pub precommits: Vec<AggregatedSignedPrecommit<Header>>,
+	/// Aggregated scalar (`s`) parts of all precommit signatures.
+	pub aggregated_signature: [u8; 32],
+	/// A proof that the chain of blocks in the commit are related to each other.
+	pub votes_ancestries: Vec<Header>,
+}
+
+impl<H: HeaderT> AggregatedGrandpaJustification<H> {
+	/// Return identifier of header that this justification claims to finalize.
+	pub fn commit_target_id(&self) -> HeaderId<H::Hash, H::Number> {
+		HeaderId(self.target_number, self.target_hash)
+	}
+}
+
+impl<H: HeaderT> crate::FinalityProof<H::Number> for AggregatedGrandpaJustification<H> {
+	fn target_header_number(&self) -> H::Number {
+		self.target_number
+	}
+}
+
+/// Half-aggregation of precommit signatures.
+#[cfg(feature = "aggregated-signatures")]
+struct HalfAggregation {
+	transcript: Sha512,
+	commitments: Vec<EdwardsPoint>,
+	public_keys: Vec<EdwardsPoint>,
+	challenges: Vec<Scalar>,
+}
+
+#[cfg(feature = "aggregated-signatures")]
+impl HalfAggregation {
+	fn new() -> Self {
+		HalfAggregation {
+			transcript: Sha512::new().chain_update(HALF_AGGREGATION_CONTEXT),
+			commitments: Vec::new(),
+			public_keys: Vec::new(),
+			challenges: Vec::new(),
+		}
+	}
+
+	/// Add signature of the `message` to the aggregation.
+	///
+	/// Returns `None` if either commitment or authority key is not a valid curve point.
+	fn push(&mut self, id: &AuthorityId, commitment: &[u8; 32], message: &[u8]) -> Option<()> {
+		let id_bytes: &[u8] = id.as_ref();
+		let public_key = CompressedEdwardsY::from_slice(id_bytes).ok()?.decompress()?;
+		let commitment_point = CompressedEdwardsY(*commitment).decompress()?;
+		let challenge = hash_to_scalar(
+			Sha512::new()
+				.chain_update(commitment)
+				.chain_update(id_bytes)
+				.chain_update(message),
+		);
+
+		self.transcript.update(commitment);
+		self.transcript.update(id_bytes);
+		self.transcript.update((message.len() as u32).to_le_bytes());
+		self.transcript.update(message);
+
+		self.commitments.push(commitment_point);
+		self.public_keys.push(public_key);
+		self.challenges.push(challenge);
+
+		Some(())
+	}
+
+	/// Returns aggregation coefficients of all signatures.
+	fn coefficients(&self) -> Vec<Scalar> {
+		let transcript = self.transcript.clone().finalize();
+		(0..self.commitments.len() as u32)
+			.map(|index| {
+				hash_to_scalar(
+					Sha512::new().chain_update(transcript).chain_update(index.to_le_bytes()),
+				)
+			})
+			.collect()
+	}
+
+	/// Aggregate scalar parts of all signatures.
+	fn aggregate(&self, scalars: &[Scalar]) -> Scalar {
+		self.coefficients().into_iter().zip(scalars).map(|(z, s)| z * s).sum()
+	}
+
+	/// Verify aggregated signature.
+	fn verify(self, aggregated_signature: &[u8; 32]) -> bool {
+		let aggregated_signature =
+			match Option::<Scalar>::from(Scalar::from_canonical_bytes(*aggregated_signature)) {
+				Some(aggregated_signature) => aggregated_signature,
+				None => return false,
+			};
+
+		let coefficients = self.coefficients();
+		let scalars = coefficients
+			.iter()
+			.copied()
+			.chain(coefficients.iter().zip(&self.challenges).map(|(z, k)| z * k));
+		let points = self.commitments.into_iter().chain(self.public_keys);
+
+		EdwardsPoint::mul_base(&aggregated_signature) ==
+			EdwardsPoint::vartime_multiscalar_mul(scalars, points)
+	}
+}
+
+/// Reduce SHA-512 hash to the scalar.
+#[cfg(feature = "aggregated-signatures")]
+fn hash_to_scalar(hasher: Sha512) -> Scalar {
+	let mut hash = [0u8; 64];
+	hash.copy_from_slice(&hasher.finalize());
+	Scalar::from_bytes_mod_order_wide(&hash)
+}
+
+/// Aggregate precommit signatures of the regular GRANDPA justification.
+///
+/// This function doesn't verify the justification, so it must be verified before (or after)
+/// aggregation. Returns `None` if justification contains malformed signature.
+#[cfg(feature = "aggregated-signatures")]
+pub fn aggregate_justification<Header: HeaderT>(
+	justification: &GrandpaJustification<Header>,
+	authority_set_id: SetId,
+) -> Option<AggregatedGrandpaJustification<Header>> {
+	let mut aggregation = HalfAggregation::new();
+	let mut precommits = Vec::with_capacity(justification.commit.precommits.len());
+	let mut scalars = Vec::with_capacity(justification.commit.precommits.len());
+	let mut signature_buffer = Vec::new();
+	for signed in &justification.commit.precommits {
+		let signature: &[u8] = signed.signature.as_ref();
+		let mut commitment = [0u8; 32];
+		commitment.copy_from_slice(&signature[..32]);
+		let mut scalar = [0u8; 32];
+		scalar.copy_from_slice(&signature[32..]);
+
+		sp_consensus_grandpa::localized_payload_with_buffer(
+			justification.round,
+			authority_set_id,
+			&finality_grandpa::Message::Precommit(signed.precommit.clone()),
+			&mut signature_buffer,
+		);
+		aggregation.push(&signed.id, &commitment, &signature_buffer)?;
+		scalars.push(Option::<Scalar>::from(Scalar::from_canonical_bytes(scalar))?);
+		precommits.push(AggregatedSignedPrecommit {
+			precommit: signed.precommit.clone(),
+			commitment,
+			id: signed.id.clone(),
+		});
+	}
+
+	Some(AggregatedGrandpaJustification {
+		round: justification.round,
+		target_hash: justification.commit.target_hash,
+		target_number: justification.commit.target_number,
+		precommits,
+		aggregated_signature: aggregation.aggregate(&scalars).to_bytes(),
+		votes_ancestries: justification.votes_ancestries.clone(),
+	})
+}
+
+/// Verify that aggregated justification, that is generated by given authority set, finalizes
+/// given header.
+///
+/// The justification is verified in the same way as [`super::strict::verify_justification`]
+/// does, so all unknown, duplicate or redundant votes are rejected.
+#[cfg(feature = "aggregated-signatures")]
+pub fn verify_aggregated_justification<Header: HeaderT>(
+	finalized_target: (Header::Hash, Header::Number),
+	context: &JustificationVerificationContext,
+	justification: &AggregatedGrandpaJustification<Header>,
+) -> Result<(), Error> {
+	// ensure that it is justification for the expected header
+	if (justification.target_hash, justification.target_number) != finalized_target {
+		return Err(Error::InvalidJustificationTarget)
+	}
+
+	let threshold = context.voter_set.threshold().get();
+	let mut chain = AncestryChain::<Header>::with_votes_ancestries(
+		justification.commit_target_id(),
+		&justification.votes_ancestries,
+	);
+	let mut votes = BTreeSet::new();
+	let mut cumulative_weight = 0u64;
+	let mut aggregation = HalfAggregation::new();
+	let mut signature_buffer = Vec::new();
+
+	for signed in &justification.precommits {
+		if cumulative_weight >= threshold {
+			return Err(Error::Precommit(PrecommitError::RedundantAuthorityVote))
+		}
+
+		// authority must be in the set
+		let authority_info = context
+			.voter_set
+			.get(&signed.id)
+			.ok_or(Error::Precommit(PrecommitError::UnknownAuthorityVote))?;
+		if !votes.insert(signed.id.clone()) {
+			return Err(Error::Precommit(PrecommitError::DuplicateAuthorityVote))
+		}
+
+		// all precommits must be descendants of the target block
+		let route = chain
+			.ancestry(&signed.precommit.target_hash, &signed.precommit.target_number)
+			.ok_or(Error::Precommit(PrecommitError::UnrelatedAncestryVote))?;
+
+		// remember signature, it'll be verified later
+		sp_consensus_grandpa::localized_payload_with_buffer(
+			justification.round,
+			context.authority_set_id,
+			&finality_grandpa::Message::Precommit(signed.precommit.clone()),
+			&mut signature_buffer,
+		);
+		aggregation
+			.push(&signed.id, &signed.commitment, &signature_buffer)
+			.ok_or(Error::Precommit(PrecommitError::InvalidAuthoritySignature))?;
+
+		chain.mark_route_as_visited(route);
+		cumulative_weight = cumulative_weight.saturating_add(authority_info.weight().get());
+	}
+
+	// check that the cumulative weight of validators that voted for the justification target
+	// (or one of its descendents) is larger than the required threshold.
+	if cumulative_weight < threshold {
+		return Err(Error::TooLowCumulativeWeight)
+	}
+
+	// check that there are no extra headers in the justification
+	if !chain.is_fully_visited() {
+		return Err(Error::RedundantVotesAncestries)
+	}
+
+	if !aggregation.verify(&justification.aggregated_signature) {
+		return Err(Error::InvalidAggregatedSignature)
+	}
+
+	Ok(())
+}
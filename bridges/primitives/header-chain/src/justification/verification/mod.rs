@@ -16,6 +16,7 @@
 
 //! Logic for checking GRANDPA Finality Proofs.
 
+pub mod aggregated;
 pub mod equivocation;
 pub mod optimizer;
 pub mod strict;
@@ -23,7 +24,9 @@ pub mod strict;
 use crate::{justification::GrandpaJustification, AuthoritySet};
 
 use bp_runtime::HeaderId;
+use codec::{Decode, Encode};
 use finality_grandpa::voter_set::VoterSet;
+use scale_info::TypeInfo;
 use sp_consensus_grandpa::{AuthorityId, AuthoritySignature, SetId};
 use sp_runtime::{traits::Header as HeaderT, RuntimeDebug};
 use sp_std::{
@@ -52,15 +55,36 @@ pub struct AncestryChain<Header: HeaderT> {
 impl<Header: HeaderT> AncestryChain<Header> {
 	/// Create new ancestry chain.
 	pub fn new(justification: &GrandpaJustification<Header>) -> AncestryChain<Header> {
+		Self::with_votes_ancestries(
+			justification.commit_target_id(),
+			&justification.votes_ancestries,
+		)
+	}
+
+	/// Create new ancestry chain from given base and votes ancestries.
+	pub fn with_votes_ancestries(
+		base: HeaderId<Header::Hash, Header::Number>,
+		votes_ancestries: &[Header],
+	) -> AncestryChain<Header> {
+		Self::with_votes_ancestries_and_hashes(base, votes_ancestries, &mut AncestryHashes::new())
+	}
+
+	/// Create new ancestry chain from given base and votes ancestries, recording every header in
+	/// `hashes`.
+	pub fn with_votes_ancestries_and_hashes(
+		base: HeaderId<Header::Hash, Header::Number>,
+		votes_ancestries: &[Header],
+		hashes: &mut AncestryHashes<Header>,
+	) -> AncestryChain<Header> {
 		let mut parents = BTreeMap::new();
 		let mut unvisited = BTreeSet::new();
-		for ancestor in &justification.votes_ancestries {
-			let hash = ancestor.hash();
+		for ancestor in votes_ancestries {
+			let hash = hashes.hash(ancestor);
 			let parent_hash = *ancestor.parent_hash();
 			parents.insert(hash, parent_hash);
 			unvisited.insert(hash);
 		}
-		AncestryChain { base: justification.commit_target_id(), parents, unvisited }
+		AncestryChain { base, parents, unvisited }
 	}
 
 	/// Returns a route if the precommit target block is a descendant of the `base` block.
@@ -110,6 +134,47 @@ impl<Header: HeaderT> AncestryChain<Header> {
 	}
 }
 
+/// Votes ancestries headers, seen while verifying several justifications.
+///
+/// Justifications of consecutive headers mostly contain the same votes ancestries. When they are
+/// verified together, every distinct header is only recorded once, so the cost of the batch grows
+/// with the number of distinct headers instead of the total number of votes ancestries.
+#[derive(RuntimeDebug)]
+pub struct AncestryHashes<Header: HeaderT> {
+	/// Header hash => parent header hash mapping.
+	known: BTreeMap<Header::Hash, Header::Hash>,
+}
+
+impl<Header: HeaderT> AncestryHashes<Header> {
+	/// Create new empty cache.
+	pub fn new() -> Self {
+		AncestryHashes { known: BTreeMap::new() }
+	}
+
+	/// Returns hash of the header, recording it if the same header hasn't been seen before.
+	pub fn hash(&mut self, header: &Header) -> Header::Hash {
+		let hash = header.hash();
+		self.known.entry(hash).or_insert_with(|| *header.parent_hash());
+		hash
+	}
+
+	/// Returns the number of distinct headers that have been seen.
+	pub fn len(&self) -> usize {
+		self.known.len()
+	}
+
+	/// Returns true if no headers have been seen yet.
+	pub fn is_empty(&self) -> bool {
+		self.known.is_empty()
+	}
+}
+
+impl<Header: HeaderT> Default for AncestryHashes<Header> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
 /// Justification verification error.
 #[derive(Eq, RuntimeDebug, PartialEq)]
 pub enum Error {
@@ -124,6 +189,8 @@ pub enum Error {
 	TooLowCumulativeWeight,
 	/// The justification contains extra (unused) headers in its `votes_ancestries` field.
 	RedundantVotesAncestries,
+	/// The aggregated signature of the justification precommits is invalid.
+	InvalidAggregatedSignature,
 }
 
 /// Justification verification error.
@@ -160,6 +227,40 @@ impl TryFrom<AuthoritySet> for JustificationVerificationContext {
 	}
 }
 
+/// The way justification verification treats votes that are not required to prove finality.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum VerificationMode {
+	/// Reject justifications with unknown, duplicate or redundant votes and with redundant
+	/// votes ancestries.
+	Strict,
+	/// Accept justifications with unknown, duplicate or redundant votes and with redundant
+	/// votes ancestries, as long as they still prove finality of the target header.
+	Lenient,
+	/// Same as `Lenient`, but additionally remove all unneeded votes and votes ancestries from
+	/// the justification.
+	OptimizeOnly,
+}
+
+/// Verify that justification, that is generated by given authority set, finalizes given header,
+/// using given verification mode.
+///
+/// The justification is only modified if `mode` is `VerificationMode::OptimizeOnly`.
+pub fn verify_justification_with_mode<Header: HeaderT>(
+	mode: VerificationMode,
+	finalized_target: (Header::Hash, Header::Number),
+	context: &JustificationVerificationContext,
+	justification: &mut GrandpaJustification<Header>,
+) -> Result<(), Error> {
+	match mode {
+		VerificationMode::Strict =>
+			strict::verify_justification(finalized_target, context, justification),
+		VerificationMode::Lenient =>
+			optimizer::verify_justification_leniently(finalized_target, context, justification),
+		VerificationMode::OptimizeOnly =>
+			optimizer::verify_and_optimize_justification(finalized_target, context, justification),
+	}
+}
+
 enum IterationFlow {
 	Run,
 	Skip,
@@ -206,6 +307,25 @@ trait JustificationVerifier<Header: HeaderT> {
 		finalized_target: (Header::Hash, Header::Number),
 		context: &JustificationVerificationContext,
 		justification: &GrandpaJustification<Header>,
+	) -> Result<(), Error> {
+		self.verify_justification_with_shared_state(
+			finalized_target,
+			context,
+			justification,
+			&mut Vec::new(),
+			&mut AncestryHashes::new(),
+		)
+	}
+
+	/// Same as `verify_justification`, but reuses given buffer for encoding signed messages and
+	/// given hashes of votes ancestries headers.
+	fn verify_justification_with_shared_state(
+		&mut self,
+		finalized_target: (Header::Hash, Header::Number),
+		context: &JustificationVerificationContext,
+		justification: &GrandpaJustification<Header>,
+		signature_buffer: &mut Vec<u8>,
+		ancestry_hashes: &mut AncestryHashes<Header>,
 	) -> Result<(), Error> {
 		// ensure that it is justification for the expected header
 		if (justification.commit.target_hash, justification.commit.target_number) !=
@@ -215,8 +335,11 @@ trait JustificationVerifier<Header: HeaderT> {
 		}
 
 		let threshold = context.voter_set.threshold().get();
-		let mut chain = AncestryChain::new(justification);
-		let mut signature_buffer = Vec::new();
+		let mut chain = AncestryChain::with_votes_ancestries_and_hashes(
+			justification.commit_target_id(),
+			&justification.votes_ancestries,
+			ancestry_hashes,
+		);
 		let mut cumulative_weight = 0u64;
 
 		for (precommit_idx, signed) in justification.commit.precommits.iter().enumerate() {
@@ -267,7 +390,7 @@ trait JustificationVerifier<Header: HeaderT> {
 				&signed.signature,
 				justification.round,
 				context.authority_set_id,
-				&mut signature_buffer,
+				signature_buffer,
 			) {
 				self.process_invalid_signature_vote(precommit_idx).map_err(Error::Precommit)?;
 				continue
@@ -24,6 +24,7 @@ use crate::justification::{
 use crate::justification::verification::{
 	IterationFlow, JustificationVerificationContext, SignedPrecommit,
 };
+use codec::Encode;
 use sp_consensus_grandpa::AuthorityId;
 use sp_runtime::traits::Header as HeaderT;
 use sp_std::{collections::btree_set::BTreeSet, prelude::*};
@@ -125,3 +126,37 @@ pub fn verify_and_optimize_justification<Header: HeaderT>(
 
 	Ok(())
 }
+
+/// Verify given justification, ignoring unknown, duplicate and redundant votes instead of
+/// rejecting it.
+///
+/// The justification itself is left untouched.
+pub fn verify_justification_leniently<Header: HeaderT>(
+	finalized_target: (Header::Hash, Header::Number),
+	context: &JustificationVerificationContext,
+	justification: &GrandpaJustification<Header>,
+) -> Result<(), Error> {
+	let mut optimizer = JustificationOptimizer {
+		votes: BTreeSet::new(),
+		extra_precommits: vec![],
+		redundant_votes_ancestries: Default::default(),
+	};
+	optimizer.verify_justification(finalized_target, context, justification)
+}
+
+/// Verify given justification and return its minimal version, which contains no unknown,
+/// duplicate and redundant votes and no unneeded votes ancestries.
+///
+/// Along with the optimized justification, returns the number of bytes that are saved by the
+/// optimization, when the justification is encoded.
+pub fn optimize_justification<Header: HeaderT>(
+	finalized_target: (Header::Hash, Header::Number),
+	context: &JustificationVerificationContext,
+	mut justification: GrandpaJustification<Header>,
+) -> Result<(GrandpaJustification<Header>, usize), Error> {
+	let encoded_size_before = justification.encoded_size();
+	verify_and_optimize_justification(finalized_target, context, &mut justification)?;
+	let saved_bytes = encoded_size_before.saturating_sub(justification.encoded_size());
+
+	Ok((justification, saved_bytes))
+}
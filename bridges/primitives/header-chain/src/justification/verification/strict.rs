@@ -22,11 +22,11 @@ use crate::justification::{
 };
 
 use crate::justification::verification::{
-	IterationFlow, JustificationVerificationContext, SignedPrecommit,
+	AncestryHashes, IterationFlow, JustificationVerificationContext, SignedPrecommit,
 };
 use sp_consensus_grandpa::AuthorityId;
 use sp_runtime::traits::Header as HeaderT;
-use sp_std::collections::btree_set::BTreeSet;
+use sp_std::{collections::btree_set::BTreeSet, prelude::*};
 
 /// Verification callbacks that reject all unknown, duplicate or redundant votes.
 struct StrictJustificationVerifier {
@@ -99,3 +99,43 @@ pub fn verify_justification<Header: HeaderT>(
 	let mut verifier = StrictJustificationVerifier { votes: BTreeSet::new() };
 	verifier.verify_justification(finalized_target, context, justification)
 }
+
+/// Verify that every justification of the batch, generated by given authority set, finalizes
+/// its target header.
+///
+/// All justifications must be generated by the same authority set and targets must be given in
+/// ascending order. The authority set and signature encoding buffer are shared by all
+/// justifications of the batch and every votes ancestry header, included in several
+/// justifications, is only recorded once. So it is cheaper than verifying justifications one by
+/// one, see `pallet_bridge_grandpa::WeightInfo::verify_justifications_batch`.
+///
+/// Verification stops at the first invalid justification. Its index in the batch is returned
+/// along with the error.
+pub fn verify_justifications_batch<'a, Header: HeaderT + 'a>(
+	context: &JustificationVerificationContext,
+	batch: impl IntoIterator<Item = ((Header::Hash, Header::Number), &'a GrandpaJustification<Header>)>,
+) -> Result<(), (usize, Error)> {
+	let mut signature_buffer = Vec::new();
+	let mut ancestry_hashes = AncestryHashes::new();
+	let mut previous_target_number = None;
+	for (index, (finalized_target, justification)) in batch.into_iter().enumerate() {
+		// the same header can't be finalized twice and headers must be given in order
+		if previous_target_number.map_or(false, |number| finalized_target.1 <= number) {
+			return Err((index, Error::InvalidJustificationTarget))
+		}
+		previous_target_number = Some(finalized_target.1);
+
+		let mut verifier = StrictJustificationVerifier { votes: BTreeSet::new() };
+		verifier
+			.verify_justification_with_shared_state(
+				finalized_target,
+				context,
+				justification,
+				&mut signature_buffer,
+				&mut ancestry_hashes,
+			)
+			.map_err(|e| (index, e))?;
+	}
+
+	Ok(())
+}
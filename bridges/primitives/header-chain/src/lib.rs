@@ -31,7 +31,10 @@ use core::{clone::Clone, cmp::Eq, default::Default, fmt::Debug};
 use frame_support::PalletError;
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
-use sp_consensus_grandpa::{AuthorityList, ConsensusLog, SetId, GRANDPA_ENGINE_ID};
+use sp_consensus_grandpa::{
+	AuthorityList, ConsensusLog, EquivocationProof, SetId, GRANDPA_ENGINE_ID,
+};
+use sp_core::storage::ChildInfo;
 use sp_runtime::{traits::Header as HeaderT, Digest, RuntimeDebug};
 use sp_std::{boxed::Box, vec::Vec};
 
@@ -84,6 +87,24 @@ pub trait HeaderChain<C: Chain> {
 			.ok_or(HeaderChainError::UnknownHeader)?;
 		StorageProofChecker::new(state_root, storage_proof).map_err(HeaderChainError::StorageProof)
 	}
+	/// Verify storage proof of child trie values at given finalized header and return values
+	/// of all given keys.
+	fn verify_child_storage_proof(
+		header_hash: HashOf<C>,
+		storage_proof: RawStorageProof,
+		child_info: &ChildInfo,
+		keys: &[&[u8]],
+	) -> Result<Vec<Option<Vec<u8>>>, HeaderChainError> {
+		let state_root = Self::finalized_header_state_root(header_hash)
+			.ok_or(HeaderChainError::UnknownHeader)?;
+		bp_runtime::verify_child_storage_proof::<HasherOf<C>>(
+			state_root,
+			storage_proof,
+			child_info,
+			keys,
+		)
+		.map_err(HeaderChainError::StorageProof)
+	}
 }
 
 /// A type that can be used as a parameter in a dispatchable function.
@@ -134,7 +155,7 @@ pub trait FinalityProof<Number>: Clone + Send + Sync + Debug {
 
 /// A trait that provides helper methods for querying the consensus log.
 pub trait ConsensusLogReader {
-	/// Returns true if digest contains item that schedules authorities set change.
+	/// Returns true if digest contains item that schedules or forces authorities set change.
 	fn schedules_authorities_change(digest: &Digest) -> bool;
 }
 
@@ -146,32 +167,33 @@ impl<Number: Codec> GrandpaConsensusLogReader<Number> {
 		digest: &Digest,
 	) -> Option<sp_consensus_grandpa::ScheduledChange<Number>> {
 		// find the first consensus digest with the right ID which converts to
-		// the right kind of consensus log.
-		digest
-			.convert_first(|log| log.consensus_try_to(&GRANDPA_ENGINE_ID))
-			.and_then(|log| match log {
+		// the right kind of consensus log. Other GRANDPA logs may precede it.
+		digest.convert_first(|log| {
+			log.consensus_try_to(&GRANDPA_ENGINE_ID).and_then(|log| match log {
 				ConsensusLog::ScheduledChange(change) => Some(change),
 				_ => None,
 			})
+		})
 	}
 
 	pub fn find_forced_change(
 		digest: &Digest,
 	) -> Option<(Number, sp_consensus_grandpa::ScheduledChange<Number>)> {
 		// find the first consensus digest with the right ID which converts to
-		// the right kind of consensus log.
-		digest
-			.convert_first(|log| log.consensus_try_to(&GRANDPA_ENGINE_ID))
-			.and_then(|log| match log {
+		// the right kind of consensus log. Other GRANDPA logs may precede it.
+		digest.convert_first(|log| {
+			log.consensus_try_to(&GRANDPA_ENGINE_ID).and_then(|log| match log {
 				ConsensusLog::ForcedChange(delay, change) => Some((delay, change)),
 				_ => None,
 			})
+		})
 	}
 }
 
 impl<Number: Codec> ConsensusLogReader for GrandpaConsensusLogReader<Number> {
 	fn schedules_authorities_change(digest: &Digest) -> bool {
-		GrandpaConsensusLogReader::<Number>::find_scheduled_change(digest).is_some()
+		GrandpaConsensusLogReader::<Number>::find_scheduled_change(digest).is_some() ||
+			GrandpaConsensusLogReader::<Number>::find_forced_change(digest).is_some()
 	}
 }
 
@@ -232,6 +254,15 @@ pub enum BridgeGrandpaCall<Header: HeaderT> {
 	/// `pallet-bridge-grandpa::Call::initialize`
 	#[codec(index = 1)]
 	initialize { init_data: InitializationData<Header> },
+	/// `pallet-bridge-grandpa::Call::report_equivocation`
+	#[codec(index = 4)]
+	report_equivocation { equivocation_proof: Box<EquivocationProof<Header::Hash, Header::Number>> },
+	/// `pallet-bridge-grandpa::Call::submit_aggregated_finality_proof`
+	#[codec(index = 5)]
+	submit_aggregated_finality_proof {
+		finality_target: Box<Header>,
+		justification: justification::AggregatedGrandpaJustification<Header>,
+	},
 }
 
 /// The `BridgeGrandpaCall` used by a chain.
@@ -0,0 +1,134 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for Grandpa justification with aggregated signatures verifier code.
+
+use bp_header_chain::justification::{
+	aggregate_justification, verify_aggregated_justification, AggregatedGrandpaJustification,
+	JustificationVerificationError, PrecommitError,
+};
+use bp_test_utils::*;
+use codec::Encode;
+
+type TestHeader = sp_runtime::testing::Header;
+
+fn make_aggregated_justification() -> AggregatedGrandpaJustification<TestHeader> {
+	aggregate_justification(
+		&make_default_justification::<TestHeader>(&test_header(1)),
+		TEST_GRANDPA_SET_ID,
+	)
+	.unwrap()
+}
+
+#[test]
+fn valid_aggregated_justification_accepted() {
+	let justification = make_default_justification::<TestHeader>(&test_header(1));
+	let aggregated = aggregate_justification(&justification, TEST_GRANDPA_SET_ID).unwrap();
+
+	assert_eq!(
+		verify_aggregated_justification::<TestHeader>(
+			header_id::<TestHeader>(1),
+			&verification_context(TEST_GRANDPA_SET_ID),
+			&aggregated,
+		),
+		Ok(()),
+	);
+	assert_eq!(
+		aggregated.encoded_size() + 32 * (justification.commit.precommits.len() - 1),
+		justification.encoded_size(),
+	);
+}
+
+#[test]
+fn aggregated_justification_with_invalid_target_rejected() {
+	assert_eq!(
+		verify_aggregated_justification::<TestHeader>(
+			header_id::<TestHeader>(2),
+			&verification_context(TEST_GRANDPA_SET_ID),
+			&make_aggregated_justification(),
+		),
+		Err(JustificationVerificationError::InvalidJustificationTarget),
+	);
+}
+
+#[test]
+fn aggregated_justification_with_duplicate_vote_rejected() {
+	let mut justification = make_aggregated_justification();
+	justification.precommits.insert(1, justification.precommits[0].clone());
+
+	assert_eq!(
+		verify_aggregated_justification::<TestHeader>(
+			header_id::<TestHeader>(1),
+			&verification_context(TEST_GRANDPA_SET_ID),
+			&justification,
+		),
+		Err(JustificationVerificationError::Precommit(PrecommitError::DuplicateAuthorityVote)),
+	);
+}
+
+#[test]
+fn aggregated_justification_with_too_low_cumulative_weight_rejected() {
+	let mut justification = make_aggregated_justification();
+	justification.precommits.pop();
+
+	assert_eq!(
+		verify_aggregated_justification::<TestHeader>(
+			header_id::<TestHeader>(1),
+			&verification_context(TEST_GRANDPA_SET_ID),
+			&justification,
+		),
+		Err(JustificationVerificationError::TooLowCumulativeWeight),
+	);
+}
+
+#[test]
+fn aggregated_justification_with_invalid_signature_rejected() {
+	// aggregated signature is modified
+	let mut justification = make_aggregated_justification();
+	justification.aggregated_signature[0] ^= 1;
+	assert_eq!(
+		verify_aggregated_justification::<TestHeader>(
+			header_id::<TestHeader>(1),
+			&verification_context(TEST_GRANDPA_SET_ID),
+			&justification,
+		),
+		Err(JustificationVerificationError::InvalidAggregatedSignature),
+	);
+
+	// commitments are swapped
+	let mut justification = make_aggregated_justification();
+	let commitment = justification.precommits[0].commitment;
+	justification.precommits[0].commitment = justification.precommits[1].commitment;
+	justification.precommits[1].commitment = commitment;
+	assert_eq!(
+		verify_aggregated_justification::<TestHeader>(
+			header_id::<TestHeader>(1),
+			&verification_context(TEST_GRANDPA_SET_ID),
+			&justification,
+		),
+		Err(JustificationVerificationError::InvalidAggregatedSignature),
+	);
+
+	// justification is verified using wrong authority set id
+	assert_eq!(
+		verify_aggregated_justification::<TestHeader>(
+			header_id::<TestHeader>(1),
+			&verification_context(TEST_GRANDPA_SET_ID + 1),
+			&make_aggregated_justification(),
+		),
+		Err(JustificationVerificationError::InvalidAggregatedSignature),
+	);
+}
@@ -0,0 +1,118 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for Grandpa justification verification modes.
+
+use bp_header_chain::justification::{
+	verify_justification_with_mode, GrandpaJustification, JustificationVerificationError,
+	PrecommitError, VerificationMode,
+};
+use bp_test_utils::*;
+
+type TestHeader = sp_runtime::testing::Header;
+
+fn justification_with_duplicate_vote() -> GrandpaJustification<TestHeader> {
+	let mut justification = make_default_justification::<TestHeader>(&test_header(1));
+	let duplicate_vote = justification.commit.precommits.first().cloned().unwrap();
+	justification.commit.precommits.insert(1, duplicate_vote);
+	justification
+}
+
+#[test]
+fn minimal_justification_is_accepted_in_all_modes() {
+	for mode in
+		[VerificationMode::Strict, VerificationMode::Lenient, VerificationMode::OptimizeOnly]
+	{
+		let mut justification = make_default_justification::<TestHeader>(&test_header(1));
+		let original_justification = justification.clone();
+
+		assert_eq!(
+			verify_justification_with_mode::<TestHeader>(
+				mode,
+				header_id::<TestHeader>(1),
+				&verification_context(TEST_GRANDPA_SET_ID),
+				&mut justification,
+			),
+			Ok(()),
+		);
+		assert_eq!(justification, original_justification);
+	}
+}
+
+#[test]
+fn strict_mode_rejects_duplicate_votes() {
+	let mut justification = justification_with_duplicate_vote();
+
+	assert_eq!(
+		verify_justification_with_mode::<TestHeader>(
+			VerificationMode::Strict,
+			header_id::<TestHeader>(1),
+			&verification_context(TEST_GRANDPA_SET_ID),
+			&mut justification,
+		),
+		Err(JustificationVerificationError::Precommit(PrecommitError::DuplicateAuthorityVote)),
+	);
+}
+
+#[test]
+fn lenient_mode_accepts_duplicate_votes_without_modifying_justification() {
+	let mut justification = justification_with_duplicate_vote();
+	let original_justification = justification.clone();
+
+	assert_eq!(
+		verify_justification_with_mode::<TestHeader>(
+			VerificationMode::Lenient,
+			header_id::<TestHeader>(1),
+			&verification_context(TEST_GRANDPA_SET_ID),
+			&mut justification,
+		),
+		Ok(()),
+	);
+	assert_eq!(justification, original_justification);
+}
+
+#[test]
+fn optimize_only_mode_removes_duplicate_votes() {
+	let mut justification = justification_with_duplicate_vote();
+	let num_precommits_before = justification.commit.precommits.len();
+
+	assert_eq!(
+		verify_justification_with_mode::<TestHeader>(
+			VerificationMode::OptimizeOnly,
+			header_id::<TestHeader>(1),
+			&verification_context(TEST_GRANDPA_SET_ID),
+			&mut justification,
+		),
+		Ok(()),
+	);
+	assert_eq!(justification.commit.precommits.len(), num_precommits_before - 1);
+}
+
+#[test]
+fn lenient_mode_rejects_justification_without_enough_votes() {
+	let mut justification = make_default_justification::<TestHeader>(&test_header(1));
+	justification.commit.precommits.truncate(1);
+
+	assert_eq!(
+		verify_justification_with_mode::<TestHeader>(
+			VerificationMode::Lenient,
+			header_id::<TestHeader>(1),
+			&verification_context(TEST_GRANDPA_SET_ID),
+			&mut justification,
+		),
+		Err(JustificationVerificationError::TooLowCumulativeWeight),
+	);
+}
@@ -16,8 +16,9 @@
 
 //! Tests for Grandpa Justification optimizer code.
 
-use bp_header_chain::justification::verify_and_optimize_justification;
+use bp_header_chain::justification::{optimize_justification, verify_and_optimize_justification};
 use bp_test_utils::*;
+use codec::Encode;
 use finality_grandpa::SignedPrecommit;
 use sp_consensus_grandpa::AuthoritySignature;
 
@@ -174,3 +175,53 @@ fn redundant_votes_ancestries_are_removed_by_optimizer() {
 
 	assert_eq!(num_votes_ancestries_before - 1, num_votes_ancestries_after);
 }
+
+#[test]
+fn optimize_justification_reports_no_saved_bytes_for_minimal_justification() {
+	let justification = make_default_justification::<TestHeader>(&test_header(1));
+
+	let (optimized_justification, saved_bytes) = optimize_justification::<TestHeader>(
+		header_id::<TestHeader>(1),
+		&verification_context(TEST_GRANDPA_SET_ID),
+		justification.clone(),
+	)
+	.unwrap();
+
+	assert_eq!(optimized_justification, justification);
+	assert_eq!(saved_bytes, 0);
+}
+
+#[test]
+fn optimize_justification_strips_redundant_data_and_reports_saved_bytes() {
+	let minimal_justification = make_default_justification::<TestHeader>(&test_header(1));
+	let mut justification = minimal_justification.clone();
+	justification
+		.commit
+		.precommits
+		.push(justification.commit.precommits.first().cloned().unwrap());
+	justification.votes_ancestries.push(test_header(100));
+
+	let (optimized_justification, saved_bytes) = optimize_justification::<TestHeader>(
+		header_id::<TestHeader>(1),
+		&verification_context(TEST_GRANDPA_SET_ID),
+		justification.clone(),
+	)
+	.unwrap();
+
+	assert_eq!(optimized_justification, minimal_justification);
+	assert_eq!(saved_bytes, justification.encoded_size() - minimal_justification.encoded_size());
+	assert!(saved_bytes > 0);
+}
+
+#[test]
+fn optimize_justification_fails_if_justification_is_invalid() {
+	let mut justification = make_default_justification::<TestHeader>(&test_header(1));
+	justification.round = 42;
+
+	assert!(optimize_justification::<TestHeader>(
+		header_id::<TestHeader>(1),
+		&verification_context(TEST_GRANDPA_SET_ID),
+		justification,
+	)
+	.is_err());
+}
@@ -17,10 +17,12 @@
 //! Tests for Grandpa strict justification verifier code.
 
 use bp_header_chain::justification::{
-	required_justification_precommits, verify_justification, JustificationVerificationContext,
+	required_justification_precommits, verify_justification, verify_justifications_batch,
+	AncestryHashes, GrandpaJustification, JustificationVerificationContext,
 	JustificationVerificationError, PrecommitError,
 };
 use bp_test_utils::*;
+use sp_runtime::traits::Header as HeaderT;
 
 type TestHeader = sp_runtime::testing::Header;
 
@@ -186,3 +188,180 @@ fn justification_is_invalid_if_we_dont_meet_threshold() {
 		Err(JustificationVerificationError::TooLowCumulativeWeight),
 	);
 }
+
+#[test]
+fn valid_justifications_batch_accepted() {
+	let justifications = (1..=3)
+		.map(|n| make_default_justification::<TestHeader>(&test_header(n)))
+		.collect::<Vec<_>>();
+
+	assert_eq!(
+		verify_justifications_batch::<TestHeader>(
+			&verification_context(TEST_GRANDPA_SET_ID),
+			justifications
+				.iter()
+				.enumerate()
+				.map(|(i, justification)| (header_id::<TestHeader>(i as u8 + 1), justification)),
+		),
+		Ok(()),
+	);
+}
+
+#[test]
+fn justifications_batch_with_invalid_justification_rejected() {
+	let mut justifications = (1..=3)
+		.map(|n| make_default_justification::<TestHeader>(&test_header(n)))
+		.collect::<Vec<_>>();
+	justifications[1].commit.precommits.clear();
+
+	assert_eq!(
+		verify_justifications_batch::<TestHeader>(
+			&verification_context(TEST_GRANDPA_SET_ID),
+			justifications
+				.iter()
+				.enumerate()
+				.map(|(i, justification)| (header_id::<TestHeader>(i as u8 + 1), justification)),
+		),
+		Err((1, JustificationVerificationError::TooLowCumulativeWeight)),
+	);
+}
+
+#[test]
+fn justifications_batch_with_unordered_targets_rejected() {
+	let first_justification = make_default_justification::<TestHeader>(&test_header(2));
+	let second_justification = make_default_justification::<TestHeader>(&test_header(1));
+
+	assert_eq!(
+		verify_justifications_batch::<TestHeader>(
+			&verification_context(TEST_GRANDPA_SET_ID),
+			vec![
+				(header_id::<TestHeader>(2), &first_justification),
+				(header_id::<TestHeader>(1), &second_justification),
+			],
+		),
+		Err((1, JustificationVerificationError::InvalidJustificationTarget)),
+	);
+}
+
+#[test]
+fn justification_builder_generates_valid_justification() {
+	let justification = JustificationGeneratorParams::<TestHeader>::new(test_header(1))
+		.with_authorities(vec![(ALICE, 1), (BOB, 1), (CHARLIE, 1)])
+		.with_ancestors(6)
+		.with_forks(3)
+		.build();
+
+	assert_eq!(
+		verify_justification::<TestHeader>(
+			header_id::<TestHeader>(1),
+			&verification_context(TEST_GRANDPA_SET_ID),
+			&justification,
+		),
+		Ok(()),
+	);
+	assert_eq!(justification.votes_ancestries.len(), 6);
+}
+
+#[test]
+fn malformed_justifications_rejected() {
+	let cases = vec![
+		(JustificationMalformation::WrongRound, PrecommitError::InvalidAuthoritySignature),
+		(JustificationMalformation::ForkedAncestry, PrecommitError::UnrelatedAncestryVote),
+		(JustificationMalformation::DuplicateVote, PrecommitError::DuplicateAuthorityVote),
+		(JustificationMalformation::InvalidSignature, PrecommitError::InvalidAuthoritySignature),
+	];
+
+	for (malformation, expected_error) in cases {
+		let justification = JustificationGeneratorParams::<TestHeader>::new(test_header(1))
+			.build_malformed(malformation);
+
+		assert_eq!(
+			verify_justification::<TestHeader>(
+				header_id::<TestHeader>(1),
+				&verification_context(TEST_GRANDPA_SET_ID),
+				&justification,
+			),
+			Err(JustificationVerificationError::Precommit(expected_error)),
+			"Unexpected verification result for {malformation:?}",
+		);
+	}
+}
+
+/// Returns chain of `len` headers, starting at header #1, and justifications of all but the last
+/// header. Every justification precommits the last header, so all justifications share votes
+/// ancestries.
+fn chain_with_shared_votes_ancestries(
+	len: usize,
+) -> (Vec<TestHeader>, Vec<GrandpaJustification<TestHeader>>) {
+	let mut chain = vec![test_header::<TestHeader>(1)];
+	for number in 2..=len as u64 {
+		let mut header = test_header::<TestHeader>(number);
+		header.parent_hash = chain.last().expect("chain is not empty; qed").hash();
+		chain.push(header);
+	}
+
+	let best = chain.last().expect("chain is not empty; qed");
+	let precommit_target = (best.hash(), best.number);
+	let authorities = test_keyring()
+		.into_iter()
+		.take(required_justification_precommits(test_keyring().len() as _) as _);
+	let precommits = authorities
+		.map(|(id, _)| {
+			signed_precommit::<TestHeader>(
+				&id,
+				precommit_target,
+				TEST_GRANDPA_ROUND,
+				TEST_GRANDPA_SET_ID,
+			)
+		})
+		.collect::<Vec<_>>();
+	let justifications = chain[..len - 1]
+		.iter()
+		.enumerate()
+		.map(|(i, target)| GrandpaJustification {
+			round: TEST_GRANDPA_ROUND,
+			commit: finality_grandpa::Commit {
+				target_hash: target.hash(),
+				target_number: target.number,
+				precommits: precommits.clone(),
+			},
+			votes_ancestries: chain[i + 1..].to_vec(),
+		})
+		.collect();
+
+	(chain, justifications)
+}
+
+#[test]
+fn justifications_batch_with_shared_votes_ancestries_accepted() {
+	let (chain, justifications) = chain_with_shared_votes_ancestries(4);
+
+	assert_eq!(
+		verify_justifications_batch::<TestHeader>(
+			&verification_context(TEST_GRANDPA_SET_ID),
+			chain
+				.iter()
+				.zip(justifications.iter())
+				.map(|(target, justification)| { ((target.hash(), target.number), justification) }),
+		),
+		Ok(()),
+	);
+}
+
+#[test]
+fn ancestry_hashes_record_distinct_headers_once() {
+	let (chain, justifications) = chain_with_shared_votes_ancestries(4);
+
+	let mut hashes = AncestryHashes::<TestHeader>::new();
+	for header in justifications.iter().flat_map(|justification| &justification.votes_ancestries) {
+		assert_eq!(hashes.hash(header), header.hash());
+	}
+	// 6 votes ancestries headers, but only headers #2, #3 and #4 are distinct
+	assert_eq!(hashes.len(), 3);
+
+	// header with the same number and parent, but different contents, is recorded separately
+	let mut fork_header = chain[1].clone();
+	fork_header.digest.push(sp_runtime::DigestItem::Other(b"fork".to_vec()));
+	assert_eq!(hashes.hash(&fork_header), fork_header.hash());
+	assert_eq!(hashes.len(), 4);
+}
@@ -1,5 +1,8 @@
 mod justification {
+	#[cfg(feature = "aggregated-signatures")]
+	mod aggregated;
 	mod equivocation;
+	mod mode;
 	mod optimizer;
 	mod strict;
 }
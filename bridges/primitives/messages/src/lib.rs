@@ -81,6 +81,91 @@ impl OperatingMode for MessagesOperatingMode {
 	}
 }
 
+/// State of the outbound lane, that has been opened or closed at runtime.
+///
+/// Lanes that are listed in the `ActiveOutboundLanes` pallet configuration are considered
+/// opened unless they're explicitly closed.
+#[derive(
+	Encode,
+	Decode,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+pub enum LaneState {
+	/// The lane is accepting outbound messages.
+	Opened,
+	/// The lane is closed. It doesn't accept new outbound messages, but its queued messages
+	/// are still delivered to the bridged chain.
+	Closed,
+}
+
+impl LaneState {
+	/// Returns true if the lane is accepting outbound messages.
+	pub fn is_opened(&self) -> bool {
+		*self == LaneState::Opened
+	}
+}
+
+/// Quality of service class of the message lane.
+///
+/// Lanes of different classes may have different limits, so that e.g. governance messages are
+/// not starved by bulk transfers.
+#[derive(
+	Encode,
+	Decode,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+pub enum LaneQosClass {
+	/// Regular lane.
+	Normal,
+	/// Lane that is used for high priority (e.g. governance) messages.
+	Priority,
+}
+
+impl Default for LaneQosClass {
+	fn default() -> Self {
+		LaneQosClass::Normal
+	}
+}
+
+/// Limits that are applied to inbound lanes of some quality of service class.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct LaneQosLimits {
+	/// Maximal number of unrewarded relayer entries at the inbound lane.
+	///
+	/// It can't exceed the `MaxUnrewardedRelayerEntriesAtInboundLane` value of the pallet
+	/// configuration. Larger values are ignored.
+	pub max_unrewarded_relayer_entries: MessageNonce,
+	/// Maximal number of unconfirmed messages at the inbound lane.
+	///
+	/// It can't exceed the `MaxUnconfirmedMessagesAtInboundLane` value of the pallet
+	/// configuration. Larger values are ignored.
+	pub max_unconfirmed_messages: MessageNonce,
+	/// Maximal weight that may be spent on dispatching messages of all lanes of this class
+	/// within a single block.
+	///
+	/// Every class has its own budget, so it works as a block reservation: e.g. bulk transfers
+	/// over lanes of the `Normal` class can't spend the dispatch weight of the `Priority` class.
+	pub max_dispatch_weight: Weight,
+}
+
 /// Lane id which implements `TypeId`.
 #[derive(
 	Clone, Copy, Decode, Default, Encode, Eq, Ord, PartialOrd, PartialEq, TypeInfo, MaxEncodedLen,
@@ -242,6 +327,19 @@ pub struct InboundMessageDetails {
 	pub dispatch_weight: Weight,
 }
 
+/// Limits of the messages delivery transaction, returned by runtime APIs.
+///
+/// Relayer may use these limits to check that its messages delivery transaction will be
+/// accepted by the target chain before submitting it.
+#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct MessageDeliveryProofLimits {
+	/// Maximal size of the messages proof that fits into the delivery transaction.
+	pub max_proof_size: u32,
+	/// Maximal cumulative dispatch weight of messages that may be declared in the delivery
+	/// transaction with the proof of `max_proof_size` bytes.
+	pub max_dispatch_weight: Weight,
+}
+
 /// Unrewarded relayer entry stored in the inbound lane data.
 ///
 /// This struct represents a continuous range of messages that have been delivered by the same
@@ -290,6 +388,9 @@ pub enum ReceivalResult<DispatchLevelResult> {
 	TooManyUnrewardedRelayers,
 	/// There are too many unconfirmed messages at the lane.
 	TooManyUnconfirmedMessages,
+	/// Dispatch weight limit of the lane quality of service class has been reached within the
+	/// current block.
+	QosDispatchWeightLimitReached,
 }
 
 /// Delivered messages with their dispatch result.
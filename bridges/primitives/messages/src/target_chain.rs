@@ -17,12 +17,13 @@
 //! Primitives of messages module, that are used on the target chain.
 
 use crate::{
-	LaneId, Message, MessageKey, MessageNonce, MessagePayload, OutboundLaneData, VerificationError,
+	LaneId, LaneQosClass, LaneQosLimits, Message, MessageKey, MessageNonce, MessagePayload,
+	OutboundLaneData, VerificationError,
 };
 
 use bp_runtime::{messages::MessageDispatchResult, Size};
 use codec::{Decode, Encode, Error as CodecError};
-use frame_support::{weights::Weight, Parameter};
+use frame_support::{traits::Get, weights::Weight, Parameter};
 use scale_info::TypeInfo;
 use sp_core::RuntimeDebug;
 use sp_std::{collections::btree_map::BTreeMap, fmt::Debug, marker::PhantomData, prelude::*};
@@ -136,6 +137,17 @@ pub trait DeliveryPayments<AccountId> {
 	);
 }
 
+/// Quality of service classes of inbound lanes.
+pub trait InboundLanesQos {
+	/// Returns quality of service class of the given lane.
+	fn lane_class(lane: &LaneId) -> LaneQosClass;
+	/// Returns limits of the given quality of service class.
+	///
+	/// If `None` is returned, the limits from the pallet configuration are used and there's
+	/// no additional dispatch weight limit.
+	fn class_limits(class: LaneQosClass) -> Option<LaneQosLimits>;
+}
+
 impl<Message> Default for ProvedLaneMessages<Message> {
 	fn default() -> Self {
 		ProvedLaneMessages { lane_state: None, messages: Vec::new() }
@@ -167,6 +179,45 @@ impl<AccountId> DeliveryPayments<AccountId> for () {
 	}
 }
 
+impl InboundLanesQos for () {
+	fn lane_class(_lane: &LaneId) -> LaneQosClass {
+		LaneQosClass::Normal
+	}
+
+	fn class_limits(_class: LaneQosClass) -> Option<LaneQosLimits> {
+		None
+	}
+}
+
+/// Quality of service classes of inbound lanes, where given lanes are of the
+/// `LaneQosClass::Priority` class and all other lanes are of the `LaneQosClass::Normal` class.
+pub struct PriorityInboundLanes<PriorityLanes, NormalLimits, PriorityLimits>(
+	PhantomData<(PriorityLanes, NormalLimits, PriorityLimits)>,
+);
+
+impl<PriorityLanes, NormalLimits, PriorityLimits> InboundLanesQos
+	for PriorityInboundLanes<PriorityLanes, NormalLimits, PriorityLimits>
+where
+	PriorityLanes: Get<&'static [LaneId]>,
+	NormalLimits: Get<LaneQosLimits>,
+	PriorityLimits: Get<LaneQosLimits>,
+{
+	fn lane_class(lane: &LaneId) -> LaneQosClass {
+		if PriorityLanes::get().contains(lane) {
+			LaneQosClass::Priority
+		} else {
+			LaneQosClass::Normal
+		}
+	}
+
+	fn class_limits(class: LaneQosClass) -> Option<LaneQosLimits> {
+		Some(match class {
+			LaneQosClass::Normal => NormalLimits::get(),
+			LaneQosClass::Priority => PriorityLimits::get(),
+		})
+	}
+}
+
 /// Structure that may be used in place of `SourceHeaderChain` and `MessageDispatch` on chains,
 /// where inbound messages are forbidden.
 pub struct ForbidInboundMessages<MessagesProof, DispatchPayload>(
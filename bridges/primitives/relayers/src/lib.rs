@@ -20,9 +20,10 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use registration::{Registration, StakeAndSlash};
+pub use reputation::{RelayerReputation, RelayerStanding};
 
 use bp_messages::LaneId;
-use bp_runtime::{ChainId, StorageDoubleMapKeyProvider};
+use bp_runtime::{ChainId, StorageDoubleMapKeyProvider, StorageMapKeyProvider};
 use frame_support::{traits::tokens::Preservation, Blake2_128Concat, Identity};
 use scale_info::TypeInfo;
 use sp_runtime::{
@@ -33,6 +34,7 @@ use sp_runtime::{
 use sp_std::{fmt::Debug, marker::PhantomData};
 
 mod registration;
+mod reputation;
 
 /// The owner of the sovereign account that should pay the rewards.
 ///
@@ -152,6 +154,25 @@ where
 	type Value = Reward;
 }
 
+/// Can be use to access the runtime storage key within the `RelayerReputations` map of the
+/// relayers pallet.
+pub struct RelayerReputationsKeyProvider<AccountId, BlockNumber>(
+	PhantomData<(AccountId, BlockNumber)>,
+);
+
+impl<AccountId, BlockNumber> StorageMapKeyProvider
+	for RelayerReputationsKeyProvider<AccountId, BlockNumber>
+where
+	AccountId: Codec + EncodeLike,
+	BlockNumber: Codec + EncodeLike,
+{
+	const MAP_NAME: &'static str = "RelayerReputations";
+
+	type Hasher = Blake2_128Concat;
+	type Key = AccountId;
+	type Value = RelayerReputation<BlockNumber>;
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
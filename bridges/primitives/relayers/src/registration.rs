@@ -93,6 +93,15 @@ pub trait StakeAndSlash<AccountId, BlockNumber, Balance> {
 		beneficiary: RewardsAccountParams,
 		amount: Balance,
 	) -> Result<Balance, DispatchError>;
+	/// Slash up to `amount` from reserved balance of account `relayer` and send funds to given
+	/// `beneficiary` account.
+	///
+	/// Returns `Ok(_)` with non-zero balance if we have failed to repatriate some portion of stake.
+	fn repatriate_reserved_to_account(
+		relayer: &AccountId,
+		beneficiary: &AccountId,
+		amount: Balance,
+	) -> Result<Balance, DispatchError>;
 }
 
 impl<AccountId, BlockNumber, Balance> StakeAndSlash<AccountId, BlockNumber, Balance> for ()
@@ -118,4 +127,12 @@ where
 	) -> Result<Balance, DispatchError> {
 		Ok(Zero::zero())
 	}
+
+	fn repatriate_reserved_to_account(
+		_relayer: &AccountId,
+		_beneficiary: &AccountId,
+		_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		Ok(Zero::zero())
+	}
 }
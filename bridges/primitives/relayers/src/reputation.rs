@@ -0,0 +1,116 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Reputation of registered bridge relayers.
+//!
+//! Registered relayers are getting priority boost for their message delivery transactions. But
+//! if registered relayer stops delivering messages, it is still occupying the slot of the
+//! relayer that is expected to serve the lane. So we're tracking activity of registered
+//! relayers in reputation periods. The reputation score is increased by one for every period
+//! with deliveries and it decays by one for every period without deliveries. If relayer has
+//! been inactive for too many periods, anyone may `deregister` it.
+
+use crate::Registration;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::traits::{AtLeast32BitUnsigned, One, UniqueSaturatedInto};
+
+/// Reputation of the registered relayer.
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+pub struct RelayerReputation<BlockNumber> {
+	/// Reputation score at the `last_active_period`.
+	pub score: u32,
+	/// Index of the last reputation period, when the relayer has delivered messages or has
+	/// been registered.
+	pub last_active_period: BlockNumber,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> RelayerReputation<BlockNumber> {
+	/// Create reputation of newly registered relayer.
+	pub fn new(current_period: BlockNumber) -> Self {
+		RelayerReputation { score: 0, last_active_period: current_period }
+	}
+
+	/// Returns number of full reputation periods without deliveries before the
+	/// `current_period`.
+	pub fn inactive_periods(&self, current_period: BlockNumber) -> u32 {
+		current_period
+			.saturating_sub(self.last_active_period)
+			.saturating_sub(One::one())
+			.unique_saturated_into()
+	}
+
+	/// Returns reputation score at the `current_period`, with decay applied.
+	pub fn score_at(&self, current_period: BlockNumber) -> u32 {
+		self.score.saturating_sub(self.inactive_periods(current_period))
+	}
+
+	/// Note that the relayer has delivered messages at the `current_period`.
+	pub fn note_activity(&mut self, current_period: BlockNumber) {
+		if current_period > self.last_active_period {
+			self.score = self.score_at(current_period).saturating_add(1);
+			self.last_active_period = current_period;
+		}
+	}
+}
+
+/// Standing of the registered relayer, that may be used by relayer operators to monitor
+/// their registration.
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+pub struct RelayerStanding<BlockNumber, Balance> {
+	/// Relayer registration.
+	pub registration: Registration<BlockNumber, Balance>,
+	/// Relayer reputation score at the current reputation period.
+	pub score: u32,
+	/// Number of full reputation periods without deliveries.
+	pub inactive_periods: u32,
+	/// True if relayer may be `deregistered` because of inactivity.
+	pub may_be_deregistered: bool,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reputation_decays_when_relayer_is_inactive() {
+		let mut reputation = RelayerReputation::<u64>::new(10);
+		assert_eq!(reputation.inactive_periods(10), 0);
+		assert_eq!(reputation.inactive_periods(11), 0);
+		assert_eq!(reputation.inactive_periods(12), 1);
+
+		reputation.note_activity(11);
+		reputation.note_activity(12);
+		reputation.note_activity(13);
+		assert_eq!(reputation.score_at(13), 3);
+		assert_eq!(reputation.score_at(14), 3);
+		assert_eq!(reputation.score_at(15), 2);
+		assert_eq!(reputation.score_at(100), 0);
+
+		reputation.note_activity(15);
+		assert_eq!(reputation, RelayerReputation { score: 3, last_active_period: 15 });
+	}
+
+	#[test]
+	fn reputation_is_increased_once_per_period() {
+		let mut reputation = RelayerReputation::<u64>::new(10);
+		reputation.note_activity(10);
+		reputation.note_activity(11);
+		reputation.note_activity(11);
+		assert_eq!(reputation, RelayerReputation { score: 1, last_active_period: 11 });
+	}
+}
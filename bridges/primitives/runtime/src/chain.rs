@@ -330,8 +330,10 @@ macro_rules! decl_bridge_finality_runtime_apis {
 /// - chain-specific bridge runtime APIs:
 ///     - `To<ThisChain>OutboundLaneApi`
 ///     - `From<ThisChain>InboundLaneApi`
+///     - `From<ThisChain>MessageDeliveryProofSizeApi`
 /// - constants that are stringified names of runtime API methods:
 ///     - `FROM_<THIS_CHAIN>_MESSAGE_DETAILS_METHOD`,
+///     - `FROM_<THIS_CHAIN>_MESSAGE_DELIVERY_PROOF_LIMITS_METHOD`,
 /// The name of the chain has to be specified in snake case (e.g. `rialto_parachain`).
 #[macro_export]
 macro_rules! decl_bridge_messages_runtime_apis {
@@ -348,6 +350,11 @@ macro_rules! decl_bridge_messages_runtime_apis {
 				pub const [<FROM_ $chain:upper _MESSAGE_DETAILS_METHOD>]: &str =
 					stringify!([<From $chain:camel InboundLaneApi_message_details>]);
 
+				/// Name of the `From<ThisChain>MessageDeliveryProofSizeApi::delivery_proof_limits`
+				/// runtime method.
+				pub const [<FROM_ $chain:upper _MESSAGE_DELIVERY_PROOF_LIMITS_METHOD>]: &str =
+					stringify!([<From $chain:camel MessageDeliveryProofSizeApi_delivery_proof_limits>]);
+
 				sp_api::decl_runtime_apis! {
 					/// Outbound message lane API for messages that are sent to this chain.
 					///
@@ -380,6 +387,22 @@ macro_rules! decl_bridge_messages_runtime_apis {
 							messages: Vec<(MessagePayload, OutboundMessageDetails)>,
 						) -> Vec<InboundMessageDetails>;
 					}
+
+					/// Messages delivery transaction limits API for messages sent by this chain.
+					///
+					/// This API is implemented by runtimes that are receiving messages from this chain, not by this
+					/// chain's runtime itself.
+					pub trait [<From $chain:camel MessageDeliveryProofSizeApi>] {
+						/// Return limits of the transaction that delivers messages in given inclusive range.
+						///
+						/// Returns `None` if the transaction with given number of messages will be rejected
+						/// anyway.
+						fn delivery_proof_limits(
+							lane: LaneId,
+							begin: MessageNonce,
+							end: MessageNonce,
+						) -> Option<MessageDeliveryProofLimits>;
+					}
 				}
 			}
 
@@ -41,13 +41,14 @@ pub use chain::{
 pub use frame_support::storage::storage_prefix as storage_value_final_key;
 use num_traits::{CheckedAdd, CheckedSub, One, SaturatingAdd, Zero};
 pub use storage_proof::{
-	record_all_keys as record_all_trie_keys, Error as StorageProofError,
-	ProofSize as StorageProofSize, RawStorageProof, StorageProofChecker,
+	record_all_keys as record_all_trie_keys, verify_child_storage_proof,
+	Error as StorageProofError, ProofSize as StorageProofSize, RawStorageProof,
+	StorageProofChecker,
 };
 pub use storage_types::BoundedStorageValue;
 
 #[cfg(feature = "std")]
-pub use storage_proof::craft_valid_storage_proof;
+pub use storage_proof::{craft_valid_child_storage_proof, craft_valid_storage_proof};
 
 pub mod extensions;
 pub mod messages;
@@ -21,6 +21,7 @@ use codec::{Decode, Encode};
 use frame_support::PalletError;
 use hash_db::{HashDB, Hasher, EMPTY_PREFIX};
 use scale_info::TypeInfo;
+use sp_core::storage::ChildInfo;
 use sp_std::{boxed::Box, collections::btree_set::BTreeSet, vec::Vec};
 use sp_trie::{
 	read_trie_value, LayoutV1, MemoryDB, Recorder, StorageProof, Trie, TrieConfiguration,
@@ -113,6 +114,50 @@ where
 			.map_err(|_| Error::StorageValueUnavailable)
 	}
 
+	/// Reads a value from the available subset of the child trie storage. The root of the child
+	/// trie is read from the main trie. If the value cannot be read due to an incomplete or
+	/// otherwise invalid proof, this function returns an error.
+	pub fn read_child_value(
+		&mut self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<Vec<u8>>, Error> {
+		// if there's no child trie root in the main trie, the child trie is empty
+		let encoded_child_root = match self.read_value(&child_info.prefixed_storage_key())? {
+			Some(encoded_child_root) => encoded_child_root,
+			None => return Ok(None),
+		};
+		let mut child_root = H::Out::default();
+		if encoded_child_root.len() != child_root.as_ref().len() {
+			return Err(Error::InvalidChildTrieRoot)
+		}
+		child_root.as_mut().copy_from_slice(&encoded_child_root);
+
+		// LayoutV1 or LayoutV0 is identical for proof that only read values.
+		read_trie_value::<LayoutV1<H>, _>(
+			&self.db,
+			&child_root,
+			key,
+			Some(&mut self.recorder),
+			None,
+		)
+		.map_err(|_| Error::StorageValueUnavailable)
+	}
+
+	/// Reads and decodes a value from the available subset of the child trie storage. If the
+	/// value cannot be read due to an incomplete or otherwise invalid proof, this function returns
+	/// an error. If value is read, but decoding fails, this function returns an error.
+	pub fn read_and_decode_child_value<T: Decode>(
+		&mut self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<T>, Error> {
+		self.read_child_value(child_info, key).and_then(|v| {
+			v.map(|v| T::decode(&mut &v[..]).map_err(|e| Error::StorageValueDecodeFailed(e.into())))
+				.transpose()
+		})
+	}
+
 	/// Reads and decodes a value from the available subset of storage. If the value cannot be read
 	/// due to an incomplete or otherwise invalid proof, this function returns an error. If value is
 	/// read, but decoding fails, this function returns an error.
@@ -157,6 +202,27 @@ pub enum Error {
 	StorageValueEmpty,
 	/// Failed to decode storage value.
 	StorageValueDecodeFailed(StrippableError<codec::Error>),
+	/// The child trie root, stored in the main trie, has invalid length.
+	InvalidChildTrieRoot,
+}
+
+/// Verify storage proof of child trie values and return values of all given keys.
+///
+/// The proof must include the child trie root, stored in the main trie and must not have any
+/// nodes, that are not required to read values of given keys.
+pub fn verify_child_storage_proof<H: Hasher>(
+	root: H::Out,
+	proof: RawStorageProof,
+	child_info: &ChildInfo,
+	keys: &[&[u8]],
+) -> Result<Vec<Option<Vec<u8>>>, Error> {
+	let mut checker = StorageProofChecker::<H>::new(root, proof)?;
+	let values = keys
+		.iter()
+		.map(|key| checker.read_child_value(child_info, key))
+		.collect::<Result<Vec<_>, _>>()?;
+	checker.ensure_no_unused_nodes()?;
+	Ok(values)
 }
 
 /// Return valid storage proof and state root.
@@ -187,6 +253,40 @@ pub fn craft_valid_storage_proof() -> (sp_core::H256, RawStorageProof) {
 	(root, proof.into_nodes().into_iter().collect())
 }
 
+/// Return valid child trie storage proof, state root and child trie info.
+///
+/// NOTE: This should only be used for **testing**.
+#[cfg(feature = "std")]
+pub fn craft_valid_child_storage_proof() -> (sp_core::H256, RawStorageProof, ChildInfo) {
+	use sp_state_machine::{backend::Backend, prove_child_read, InMemoryBackend};
+
+	let state_version = sp_runtime::StateVersion::default();
+	let child_info = ChildInfo::new_default(b"child1");
+
+	// construct storage proof
+	let backend = <InMemoryBackend<sp_core::Blake2Hasher>>::from((
+		vec![
+			// Values are too big to fit in a branch node
+			(None, vec![(b"key1".to_vec(), Some(vec![0u8; 64]))]),
+			(
+				Some(child_info.clone()),
+				vec![
+					(b"key1".to_vec(), Some(vec![1u8; 64])),
+					(b"key2".to_vec(), Some((42u64, 42u32, 42u16, 42u8).encode())),
+					(b"key3".to_vec(), Some(vec![3u8; 64])),
+				],
+			),
+		],
+		state_version,
+	));
+	let root = backend.storage_root(std::iter::empty(), state_version).0;
+	let proof =
+		prove_child_read(backend, &child_info, &[&b"key1"[..], &b"key2"[..], &b"key22"[..]])
+			.unwrap();
+
+	(root, proof.into_nodes().into_iter().collect(), child_info)
+}
+
 /// Record all keys for a given root.
 pub fn record_all_keys<L: TrieConfiguration, DB>(
 	db: &DB,
@@ -243,6 +343,65 @@ pub mod tests {
 		);
 	}
 
+	#[test]
+	fn child_storage_proof_check() {
+		let (root, proof, child_info) = craft_valid_child_storage_proof();
+
+		let mut checker =
+			<StorageProofChecker<sp_core::Blake2Hasher>>::new(root, proof.clone()).unwrap();
+		assert_eq!(checker.read_child_value(&child_info, b"key1"), Ok(Some(vec![1u8; 64])));
+		assert_eq!(
+			checker.read_and_decode_child_value(&child_info, b"key2"),
+			Ok(Some((42u64, 42u32, 42u16, 42u8))),
+		);
+		assert_eq!(checker.read_child_value(&child_info, b"key22"), Ok(None));
+		assert_eq!(
+			checker.read_child_value(&child_info, b"key3"),
+			Err(Error::StorageValueUnavailable)
+		);
+		// values of the main trie are not in the proof
+		assert_eq!(checker.read_value(b"key1"), Err(Error::StorageValueUnavailable));
+		// other child trie is empty
+		assert_eq!(checker.read_child_value(&ChildInfo::new_default(b"child2"), b"key1"), Ok(None));
+	}
+
+	#[test]
+	fn verify_child_storage_proof_works() {
+		let (root, proof, child_info) = craft_valid_child_storage_proof();
+
+		assert_eq!(
+			verify_child_storage_proof::<sp_core::Blake2Hasher>(
+				root,
+				proof.clone(),
+				&child_info,
+				&[&b"key1"[..], &b"key2"[..], &b"key22"[..]],
+			),
+			Ok(vec![Some(vec![1u8; 64]), Some((42u64, 42u32, 42u16, 42u8).encode()), None]),
+		);
+
+		// proof has unused nodes
+		assert_eq!(
+			verify_child_storage_proof::<sp_core::Blake2Hasher>(
+				root,
+				proof.clone(),
+				&child_info,
+				&[&b"key2"[..]],
+			),
+			Err(Error::UnusedNodesInTheProof),
+		);
+
+		// value is missing from the proof
+		assert_eq!(
+			verify_child_storage_proof::<sp_core::Blake2Hasher>(
+				root,
+				proof,
+				&child_info,
+				&[&b"key1"[..], &b"key2"[..], &b"key22"[..], &b"key3"[..]],
+			),
+			Err(Error::StorageValueUnavailable),
+		);
+	}
+
 	#[test]
 	fn proof_with_duplicate_items_is_rejected() {
 		let (root, mut proof) = craft_valid_storage_proof();
@@ -23,7 +23,9 @@ use bp_parachains::parachain_head_storage_key_at_source;
 use bp_polkadot_core::parachains::{ParaHash, ParaHead, ParaHeadsProof, ParaId};
 use bp_runtime::record_all_trie_keys;
 use codec::Encode;
-use sp_consensus_grandpa::{AuthorityId, AuthoritySignature, AuthorityWeight, SetId};
+use sp_consensus_grandpa::{
+	AuthorityId, AuthoritySignature, AuthorityWeight, EquivocationProof, SetId,
+};
 use sp_runtime::traits::{Header as HeaderT, One, Zero};
 use sp_std::prelude::*;
 use sp_trie::{trie_types::TrieDBMutBuilderV1, LayoutV1, MemoryDB, TrieMut};
@@ -74,6 +76,82 @@ impl<H: HeaderT> Default for JustificationGeneratorParams<H> {
 	}
 }
 
+impl<H: HeaderT> JustificationGeneratorParams<H> {
+	/// Create parameters with sensible defaults to generate justification for given header.
+	pub fn new(header: H) -> Self {
+		Self { header, ..Default::default() }
+	}
+
+	/// Set GRANDPA round number.
+	pub fn with_round(mut self, round: u64) -> Self {
+		self.round = round;
+		self
+	}
+
+	/// Set authority set ID.
+	pub fn with_set_id(mut self, set_id: SetId) -> Self {
+		self.set_id = set_id;
+		self
+	}
+
+	/// Set authorities that are signing precommits.
+	pub fn with_authorities(mut self, authorities: Vec<(Account, AuthorityWeight)>) -> Self {
+		self.authorities = authorities;
+		self
+	}
+
+	/// Set total number of precommit ancestors.
+	pub fn with_ancestors(mut self, ancestors: u32) -> Self {
+		self.ancestors = ancestors;
+		self
+	}
+
+	/// Set number of forks.
+	pub fn with_forks(mut self, forks: u32) -> Self {
+		self.forks = forks;
+		self
+	}
+
+	/// Generate valid justification.
+	pub fn build(self) -> GrandpaJustification<H> {
+		make_justification_for_header(self)
+	}
+
+	/// Generate justification with given malformation.
+	///
+	/// The justification is rejected by the strict verifier, using authority set from
+	/// these parameters.
+	pub fn build_malformed(
+		self,
+		malformation: JustificationMalformation,
+	) -> GrandpaJustification<H> {
+		make_malformed_justification_for_header(self, malformation)
+	}
+}
+
+/// The way justification is malformed by the [`make_malformed_justification_for_header`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JustificationMalformation {
+	/// All precommits are signed for the next round, but the justification claims that it
+	/// has been generated at the original round.
+	///
+	/// Verifier rejects it with the `InvalidAuthoritySignature` error.
+	WrongRound,
+	/// First precommit is voting for the header, which is a sibling of the justification
+	/// target. This header is also added to the votes ancestries.
+	///
+	/// Verifier rejects it with the `UnrelatedAncestryVote` error.
+	ForkedAncestry,
+	/// First precommit is duplicated.
+	///
+	/// Verifier rejects it with the `DuplicateAuthorityVote` error.
+	DuplicateVote,
+	/// Signature of the first precommit is invalid.
+	///
+	/// Verifier rejects it with the `InvalidAuthoritySignature` error.
+	InvalidSignature,
+}
+
 /// Make a valid GRANDPA justification with sensible defaults
 pub fn make_default_justification<H: HeaderT>(header: &H) -> GrandpaJustification<H> {
 	let params = JustificationGeneratorParams::<H> { header: header.clone(), ..Default::default() };
@@ -146,6 +224,60 @@ pub fn make_justification_for_header<H: HeaderT>(
 	}
 }
 
+/// Generate justification that has given malformation.
+///
+/// It is useful for testing that bridge configuration is rejecting invalid justifications.
+///
+/// Note: the `params.authorities` must not be empty.
+pub fn make_malformed_justification_for_header<H: HeaderT>(
+	params: JustificationGeneratorParams<H>,
+	malformation: JustificationMalformation,
+) -> GrandpaJustification<H> {
+	let (round, set_id) = (params.round, params.set_id);
+	let first_authority = params
+		.authorities
+		.first()
+		.map(|(id, _)| *id)
+		.expect("Need at least one authority to create malformed justification");
+	match malformation {
+		JustificationMalformation::WrongRound => {
+			let mut justification = make_justification_for_header(JustificationGeneratorParams {
+				round: round + 1,
+				..params
+			});
+			justification.round = round;
+			justification
+		},
+		JustificationMalformation::ForkedAncestry => {
+			// fork header is at the same height as the target, but has different hash
+			let mut fork_header = params.header.clone();
+			fork_header
+				.digest_mut()
+				.logs
+				.push(sp_runtime::DigestItem::Other(b"fork".to_vec()));
+			let fork_target = (fork_header.hash(), *fork_header.number());
+
+			let mut justification = make_justification_for_header(params);
+			justification.commit.precommits[0] =
+				signed_precommit::<H>(&first_authority, fork_target, round, set_id);
+			justification.votes_ancestries.push(fork_header);
+			justification
+		},
+		JustificationMalformation::DuplicateVote => {
+			let mut justification = make_justification_for_header(params);
+			let duplicate = justification.commit.precommits[0].clone();
+			justification.commit.precommits.insert(1, duplicate);
+			justification
+		},
+		JustificationMalformation::InvalidSignature => {
+			let mut justification = make_justification_for_header(params);
+			justification.commit.precommits[0].signature =
+				sp_core::crypto::UncheckedFrom::unchecked_from([1u8; 64]);
+			justification
+		},
+	}
+}
+
 fn generate_chain<H: HeaderT>(fork_id: u32, depth: u32, ancestor: &H) -> Vec<H> {
 	let mut headers = vec![ancestor.clone()];
 
@@ -222,6 +354,29 @@ pub fn signed_precommit<H: HeaderT>(
 	finality_grandpa::SignedPrecommit { precommit, signature, id }
 }
 
+/// Create proof of precommit equivocation, where `signer` has voted for two different targets in
+/// the same round.
+pub fn precommit_equivocation_proof<H: HeaderT>(
+	signer: &Account,
+	first_target: (H::Hash, H::Number),
+	second_target: (H::Hash, H::Number),
+	round: u64,
+	set_id: SetId,
+) -> EquivocationProof<H::Hash, H::Number> {
+	let first = signed_precommit::<H>(signer, first_target, round, set_id);
+	let second = signed_precommit::<H>(signer, second_target, round, set_id);
+
+	EquivocationProof::new(
+		set_id,
+		sp_consensus_grandpa::Equivocation::Precommit(finality_grandpa::Equivocation {
+			round_number: round,
+			identity: first.id,
+			first: (first.precommit, first.signature),
+			second: (second.precommit, second.signature),
+		}),
+	)
+}
+
 /// Get a header for testing.
 ///
 /// The correct parent hash will be used if given a non-zero header.
@@ -64,3 +64,14 @@ pub enum XcmBridgeHubRouterCall {
 	#[codec(index = 0)]
 	report_bridge_status { bridge_id: H256, is_congested: bool },
 }
+
+sp_api::decl_runtime_apis! {
+	/// API for querying state of the XCM bridge hub router.
+	///
+	/// It may be used by wallets to estimate actual bridge fee before sending a message.
+	pub trait XcmBridgeHubRouterApi {
+		/// Returns current delivery fee factor of the bridge. Bridge fee is multiplied by
+		/// this factor.
+		fn delivery_fee_factor() -> FixedU128;
+	}
+}
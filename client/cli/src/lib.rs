@@ -22,17 +22,18 @@ use std::{
 	fs,
 	io::{self, Write},
 	net::SocketAddr,
-	path::PathBuf,
+	path::{Path, PathBuf},
 };
 
 use codec::Encode;
 use sc_chain_spec::ChainSpec;
 use sc_client_api::ExecutorProvider;
+use sc_network::{multiaddr::Protocol, Multiaddr};
 use sc_service::{
 	config::{PrometheusConfig, TelemetryEndpoints},
 	BasePath, TransactionPoolOptions,
 };
-use sp_core::hexdisplay::HexDisplay;
+use sp_core::{hexdisplay::HexDisplay, storage::Storage};
 use sp_runtime::{
 	traits::{Block as BlockT, Hash as HashT, Header as HeaderT, Zero},
 	StateVersion,
@@ -40,6 +41,9 @@ use sp_runtime::{
 use url::Url;
 
 /// The `purge-chain` command used to remove the whole chain: the parachain and the relay chain.
+///
+/// By default both databases are removed, `--keep-relay` and `--keep-para` restrict the command
+/// to one of them.
 #[derive(Debug, clap::Parser)]
 #[group(skip)]
 pub struct PurgeChainCmd {
@@ -48,28 +52,52 @@ pub struct PurgeChainCmd {
 	pub base: sc_cli::PurgeChainCmd,
 
 	/// Only delete the para chain database
-	#[arg(long, aliases = &["para"])]
+	#[arg(long, aliases = &["para"], conflicts_with = "keep_para")]
 	pub parachain: bool,
 
 	/// Only delete the relay chain database
-	#[arg(long, aliases = &["relay"])]
+	#[arg(long, aliases = &["relay"], conflicts_with = "keep_relay")]
 	pub relaychain: bool,
+
+	/// Keep the embedded relay chain database and only delete the para chain database.
+	#[arg(long, conflicts_with = "keep_para")]
+	pub keep_relay: bool,
+
+	/// Keep the para chain database and only delete the embedded relay chain database.
+	#[arg(long)]
+	pub keep_para: bool,
+
+	/// Only print the databases that would be deleted, together with their sizes.
+	#[arg(long)]
+	pub dry_run: bool,
 }
 
 impl PurgeChainCmd {
+	/// Whether the para chain and the relay chain database are deleted.
+	fn selected_databases(&self) -> (bool, bool) {
+		let only_para = self.parachain || self.keep_relay;
+		let only_relay = self.relaychain || self.keep_para;
+		match (only_para, only_relay) {
+			(true, false) => (true, false),
+			(false, true) => (false, true),
+			_ => (true, true),
+		}
+	}
+
 	/// Run the purge command
 	pub fn run(
 		&self,
 		para_config: sc_service::Configuration,
 		relay_config: sc_service::Configuration,
 	) -> sc_cli::Result<()> {
-		let databases = match (self.parachain, self.relaychain) {
-			(true, true) | (false, false) => {
-				vec![("parachain", para_config.database), ("relaychain", relay_config.database)]
-			},
-			(true, false) => vec![("parachain", para_config.database)],
-			(false, true) => vec![("relaychain", relay_config.database)],
-		};
+		let (purge_para, purge_relay) = self.selected_databases();
+		let mut databases = Vec::new();
+		if purge_para {
+			databases.push(("parachain", para_config.database));
+		}
+		if purge_relay {
+			databases.push(("relaychain", relay_config.database));
+		}
 
 		let db_paths = databases
 			.iter()
@@ -83,6 +111,19 @@ impl PurgeChainCmd {
 			})
 			.collect::<sc_cli::Result<Vec<_>>>()?;
 
+		if self.dry_run {
+			for ((chain_label, _), db_path) in databases.iter().zip(&db_paths) {
+				let size = match dir_size(db_path) {
+					Ok(size) => format_size(size),
+					Err(ref err) if err.kind() == io::ErrorKind::NotFound =>
+						"does not exist".into(),
+					Err(err) => return Err(err.into()),
+				};
+				println!("{}: {} ({})", chain_label, db_path.display(), size);
+			}
+			return Ok(())
+		}
+
 		if !self.base.yes {
 			for db_path in &db_paths {
 				println!("{}", db_path.display());
@@ -119,6 +160,34 @@ impl PurgeChainCmd {
 	}
 }
 
+/// The total size of the files in the directory at `path` and its subdirectories.
+fn dir_size(path: &Path) -> io::Result<u64> {
+	let mut size = 0;
+	for entry in fs::read_dir(path)? {
+		let entry = entry?;
+		let metadata = entry.metadata()?;
+		size += if metadata.is_dir() { dir_size(&entry.path())? } else { metadata.len() };
+	}
+	Ok(size)
+}
+
+/// Formats `bytes` with a binary unit, e.g. `1.50 GiB`.
+fn format_size(bytes: u64) -> String {
+	const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+	let mut size = bytes as f64;
+	let mut unit = 0;
+	while size >= 1024.0 && unit < UNITS.len() - 1 {
+		size /= 1024.0;
+		unit += 1;
+	}
+	if unit == 0 {
+		format!("{} B", bytes)
+	} else {
+		format!("{:.2} {}", size, UNITS[unit])
+	}
+}
+
 impl sc_cli::CliConfiguration for PurgeChainCmd {
 	fn shared_params(&self) -> &sc_cli::SharedParams {
 		&self.base.shared_params
@@ -129,6 +198,17 @@ impl sc_cli::CliConfiguration for PurgeChainCmd {
 	}
 }
 
+/// The format in which the genesis state is exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+	/// Hex encoded with a `0x` prefix.
+	Hex,
+	/// Binary.
+	Binary,
+	/// A JSON object with the hex encoded genesis head and the hash of the validation code.
+	Json,
+}
+
 /// Command for exporting the genesis state of the parachain
 #[derive(Debug, clap::Parser)]
 pub struct ExportGenesisStateCommand {
@@ -137,9 +217,17 @@ pub struct ExportGenesisStateCommand {
 	pub output: Option<PathBuf>,
 
 	/// Write output in binary. Default is to write in hex.
-	#[arg(short, long)]
+	#[arg(short, long, conflicts_with = "format")]
 	pub raw: bool,
 
+	/// The format of the output.
+	#[arg(long, value_enum, default_value_t = ExportFormat::Hex)]
+	pub format: ExportFormat,
+
+	/// Use this parachain id instead of the one of the chain spec.
+	#[arg(long = "parachain-id")]
+	pub para_id: Option<u32>,
+
 	#[allow(missing_docs)]
 	#[command(flatten)]
 	pub shared_params: sc_cli::SharedParams,
@@ -152,26 +240,27 @@ impl ExportGenesisStateCommand {
 		chain_spec: &dyn ChainSpec,
 		client: &impl ExecutorProvider<Block>,
 	) -> sc_cli::Result<()> {
-		let state_version = sc_chain_spec::resolve_state_version_from_wasm(
-			&chain_spec.build_storage()?,
-			client.executor(),
-		)?;
+		let storage = genesis_storage(chain_spec, self.para_id)?;
+		let state_version =
+			sc_chain_spec::resolve_state_version_from_wasm(&storage, client.executor())?;
 
-		let block: Block = generate_genesis_block(chain_spec, state_version)?;
+		let block: Block = genesis_block_from_storage(&storage, state_version);
 		let raw_header = block.header().encode();
-		let output_buf = if self.raw {
-			raw_header
-		} else {
-			format!("0x{:?}", HexDisplay::from(&block.header().encode())).into_bytes()
+		let format = if self.raw { ExportFormat::Binary } else { self.format };
+		let output_buf = match format {
+			ExportFormat::Hex => to_hex(&raw_header).into_bytes(),
+			ExportFormat::Binary => raw_header,
+			ExportFormat::Json => {
+				let code = genesis_wasm(&storage)?;
+				let json = serde_json::json!({
+					"head": to_hex(&raw_header),
+					"validationCodeHash": to_hex(&sp_core::blake2_256(code)),
+				});
+				serde_json::to_vec_pretty(&json).map_err(|e| e.to_string())?
+			},
 		};
 
-		if let Some(output) = &self.output {
-			fs::write(output, output_buf)?;
-		} else {
-			io::stdout().write_all(&output_buf)?;
-		}
-
-		Ok(())
+		write_output(self.output.as_deref(), &output_buf)
 	}
 }
 
@@ -180,8 +269,29 @@ pub fn generate_genesis_block<Block: BlockT>(
 	chain_spec: &dyn ChainSpec,
 	genesis_state_version: StateVersion,
 ) -> Result<Block, String> {
-	let storage = chain_spec.build_storage()?;
+	Ok(genesis_block_from_storage(&chain_spec.build_storage()?, genesis_state_version))
+}
+
+/// Build the genesis storage of a given ChainSpec.
+///
+/// If `para_id` is given, it replaces the parachain id that is stored by the `ParachainInfo`
+/// pallet.
+pub fn genesis_storage(
+	chain_spec: &dyn ChainSpec,
+	para_id: Option<u32>,
+) -> Result<Storage, String> {
+	let mut storage = chain_spec.build_storage()?;
+	if let Some(para_id) = para_id {
+		let key = [sp_core::twox_128(b"ParachainInfo"), sp_core::twox_128(b"ParachainId")].concat();
+		storage.top.insert(key, para_id.encode());
+	}
+	Ok(storage)
+}
 
+fn genesis_block_from_storage<Block: BlockT>(
+	storage: &Storage,
+	genesis_state_version: StateVersion,
+) -> Block {
 	let child_roots = storage.children_default.iter().map(|(sk, child_content)| {
 		let state_root = <<<Block as BlockT>::Header as HeaderT>::Hashing as HashT>::trie_root(
 			child_content.data.clone().into_iter().collect(),
@@ -199,7 +309,7 @@ pub fn generate_genesis_block<Block: BlockT>(
 		genesis_state_version,
 	);
 
-	Ok(Block::new(
+	Block::new(
 		<<Block as BlockT>::Header as HeaderT>::new(
 			Zero::zero(),
 			extrinsics_root,
@@ -208,7 +318,7 @@ pub fn generate_genesis_block<Block: BlockT>(
 			Default::default(),
 		),
 		Default::default(),
-	))
+	)
 }
 
 impl sc_cli::CliConfiguration for ExportGenesisStateCommand {
@@ -237,19 +347,9 @@ impl ExportGenesisWasmCommand {
 	/// Run the export-genesis-wasm command
 	pub fn run(&self, chain_spec: &dyn ChainSpec) -> sc_cli::Result<()> {
 		let raw_wasm_blob = extract_genesis_wasm(chain_spec)?;
-		let output_buf = if self.raw {
-			raw_wasm_blob
-		} else {
-			format!("0x{:?}", HexDisplay::from(&raw_wasm_blob)).into_bytes()
-		};
-
-		if let Some(output) = &self.output {
-			fs::write(output, output_buf)?;
-		} else {
-			io::stdout().write_all(&output_buf)?;
-		}
+		let output_buf = if self.raw { raw_wasm_blob } else { to_hex(&raw_wasm_blob).into_bytes() };
 
-		Ok(())
+		write_output(self.output.as_deref(), &output_buf)
 	}
 }
 
@@ -268,6 +368,80 @@ impl sc_cli::CliConfiguration for ExportGenesisWasmCommand {
 	}
 }
 
+/// Command for exporting the genesis head and the genesis wasm of the parachain at once, as
+/// required to register the parachain.
+#[derive(Debug, clap::Parser)]
+pub struct ExportGenesisArtifactsCommand {
+	/// The directory to write the `genesis-head` and `genesis-wasm` files to.
+	#[arg()]
+	pub output_dir: PathBuf,
+
+	/// Write output in binary. Default is to write in hex.
+	#[arg(short, long)]
+	pub raw: bool,
+
+	/// Use this parachain id instead of the one of the chain spec.
+	#[arg(long = "parachain-id")]
+	pub para_id: Option<u32>,
+
+	#[allow(missing_docs)]
+	#[command(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+}
+
+impl ExportGenesisArtifactsCommand {
+	/// Run the export-genesis-artifacts command
+	pub fn run<Block: BlockT>(
+		&self,
+		chain_spec: &dyn ChainSpec,
+		client: &impl ExecutorProvider<Block>,
+	) -> sc_cli::Result<()> {
+		let storage = genesis_storage(chain_spec, self.para_id)?;
+		let state_version =
+			sc_chain_spec::resolve_state_version_from_wasm(&storage, client.executor())?;
+
+		let block: Block = genesis_block_from_storage(&storage, state_version);
+		let head = block.header().encode();
+		let wasm = genesis_wasm(&storage)?;
+
+		fs::create_dir_all(&self.output_dir)?;
+		for (name, data) in [("genesis-head", &head[..]), ("genesis-wasm", wasm)] {
+			let output_buf = if self.raw { data.to_vec() } else { to_hex(data).into_bytes() };
+			write_output(Some(self.output_dir.join(name).as_path()), &output_buf)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl sc_cli::CliConfiguration for ExportGenesisArtifactsCommand {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+}
+
+fn genesis_wasm(storage: &Storage) -> sc_cli::Result<&[u8]> {
+	storage
+		.top
+		.get(sp_core::storage::well_known_keys::CODE)
+		.map(|code| &code[..])
+		.ok_or_else(|| "Could not find wasm file in genesis state!".into())
+}
+
+fn to_hex(data: &[u8]) -> String {
+	format!("0x{:?}", HexDisplay::from(&data))
+}
+
+fn write_output(output: Option<&Path>, data: &[u8]) -> sc_cli::Result<()> {
+	if let Some(output) = output {
+		fs::write(output, data)?;
+	} else {
+		io::stdout().write_all(data)?;
+	}
+
+	Ok(())
+}
+
 fn validate_relay_chain_url(arg: &str) -> Result<Url, String> {
 	let url = Url::parse(arg).map_err(|e| e.to_string())?;
 
@@ -282,6 +456,27 @@ fn validate_relay_chain_url(arg: &str) -> Result<Url, String> {
 	}
 }
 
+fn validate_websocket_listen_addr(arg: &str) -> Result<Multiaddr, String> {
+	let addr = arg.parse::<Multiaddr>().map_err(|e| e.to_string())?;
+
+	match addr.iter().last() {
+		Some(Protocol::Ws(_)) => Ok(addr),
+		_ => Err(format!("'{}' is not a WebSocket address, it needs to end with `/ws`", addr)),
+	}
+}
+
+fn validate_websocket_public_addr(arg: &str) -> Result<Multiaddr, String> {
+	let addr = arg.parse::<Multiaddr>().map_err(|e| e.to_string())?;
+
+	match addr.iter().last() {
+		Some(Protocol::Ws(_)) | Some(Protocol::Wss(_)) => Ok(addr),
+		_ => Err(format!(
+			"'{}' is not a WebSocket address, it needs to end with `/ws` or `/wss`",
+			addr
+		)),
+	}
+}
+
 /// The `run` command used to run a node.
 #[derive(Debug, clap::Parser)]
 #[group(skip)]
@@ -297,6 +492,9 @@ pub struct RunCmd {
 	pub collator: bool,
 
 	/// EXPERIMENTAL: Specify an URL to a relay chain full node to communicate with.
+	///
+	/// Multiple URLs can be given. The node then switches to the healthiest of the other relay
+	/// chain nodes, if the current one is unreachable, fails or lags behind on finality.
 	#[arg(
 		long,
 		value_parser = validate_relay_chain_url,
@@ -305,10 +503,35 @@ pub struct RunCmd {
 	)]
 	pub relay_chain_rpc_urls: Vec<Url>,
 
-	/// EXPERIMENTAL: Embed a light client for the relay chain. Only supported for full-nodes.
-	/// Will use the specified relay chain chainspec.
-	#[arg(long, conflicts_with_all = ["relay_chain_rpc_urls", "collator"])]
+	/// EXPERIMENTAL: Embed a light client for the relay chain. Will use the specified relay chain
+	/// chainspec.
+	///
+	/// The light client doesn't provide the storage proofs of the relay chain state, that
+	/// collators need to build the parachain inherent. They are requested from the bootnodes of
+	/// the relay chain chainspec instead.
+	#[arg(long, conflicts_with_all = ["relay_chain_rpc_urls"])]
 	pub relay_chain_light_client: bool,
+
+	/// The maximum number of PoVs, that are recovered from the relay chain at the same time.
+	#[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+	pub pov_recovery_concurrency: u32,
+
+	/// EXPERIMENTAL: Additional WebSocket listen addresses of the minimal relay chain node, e.g.
+	/// `/ip4/0.0.0.0/tcp/30335/ws`.
+	///
+	/// Allows peers behind firewalls, that only permit WebSocket connections, to use the collation
+	/// and availability-recovery protocols. Only used together with `--relay-chain-rpc-urls` or
+	/// `--relay-chain-light-client`.
+	#[arg(long, value_parser = validate_websocket_listen_addr, num_args = 0..)]
+	pub relay_chain_websocket_listen_addrs: Vec<Multiaddr>,
+
+	/// EXPERIMENTAL: Additional WebSocket addresses, that the minimal relay chain node announces
+	/// to its peers.
+	///
+	/// WSS connections need to be terminated by a reverse proxy in front of one of the
+	/// `--relay-chain-websocket-listen-addrs`, e.g. `/dns/collator.example.com/tcp/443/wss`.
+	#[arg(long, value_parser = validate_websocket_public_addr, num_args = 0..)]
+	pub relay_chain_websocket_public_addrs: Vec<Multiaddr>,
 }
 
 impl RunCmd {
@@ -331,7 +554,12 @@ impl RunCmd {
 				_ => RelayChainMode::Embedded,
 			};
 
-		CollatorOptions { relay_chain_mode }
+		CollatorOptions {
+			relay_chain_mode,
+			pov_recovery_concurrency: self.pov_recovery_concurrency as usize,
+			relay_chain_websocket_listen_addresses: self.relay_chain_websocket_listen_addrs.clone(),
+			relay_chain_websocket_public_addresses: self.relay_chain_websocket_public_addrs.clone(),
+		}
 	}
 }
 
@@ -351,6 +579,12 @@ pub enum RelayChainMode {
 pub struct CollatorOptions {
 	/// How this collator retrieves relay chain information
 	pub relay_chain_mode: RelayChainMode,
+	/// The maximum number of PoVs, that are recovered at the same time.
+	pub pov_recovery_concurrency: usize,
+	/// Additional WebSocket listen addresses of the minimal relay chain node.
+	pub relay_chain_websocket_listen_addresses: Vec<Multiaddr>,
+	/// Additional WebSocket addresses, that the minimal relay chain node announces to its peers.
+	pub relay_chain_websocket_public_addresses: Vec<Multiaddr>,
 }
 
 /// A non-redundant version of the `RunCmd` that sets the `validator` field when the
@@ -37,8 +37,11 @@ use std::sync::Arc;
 
 use crate::service::CollatorService;
 
+pub mod proposer;
 pub mod service;
 
+pub use proposer::{FactoryProposer, ParachainProposerFactory};
+
 /// The logging target.
 const LOG_TARGET: &str = "cumulus-collator";
 
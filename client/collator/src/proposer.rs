@@ -0,0 +1,96 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pluggable block proposers for the collator.
+//!
+//! Downstream chains may need proposers, that for example enforce a transaction ordering policy
+//! or limit the weight of certain classes of transactions. Instead of forking the collation
+//! logic, they implement the [`ParachainProposerFactory`] and pass the [`FactoryProposer`] as
+//! the proposer of the collator, e.g. of the `basic` or the `lookahead` collator of the
+//! `cumulus-client-consensus-aura`. A fresh proposer is then created for every block.
+
+use cumulus_client_consensus_proposer::{Error as ProposerError, ProposerInterface};
+use cumulus_primitives_parachain_inherent::ParachainInherentData;
+use sp_consensus::Proposal;
+use sp_inherents::InherentData;
+use sp_runtime::{traits::Block as BlockT, Digest};
+use sp_state_machine::StorageProof;
+use std::{sync::Arc, time::Duration};
+
+/// A boxed [`ProposerInterface`], as created by a [`ParachainProposerFactory`].
+pub type BoxedProposer<Block> = Box<dyn ProposerInterface<Block> + Send>;
+
+/// A factory for the proposers, that build the parachain blocks of the collator.
+pub trait ParachainProposerFactory<Block: BlockT>: Send + Sync {
+	/// Create a proposer for building a block on top of the `parent_header`.
+	fn init(&self, parent_header: &Block::Header) -> Result<BoxedProposer<Block>, ProposerError>;
+}
+
+impl<Block, F> ParachainProposerFactory<Block> for F
+where
+	Block: BlockT,
+	F: Fn(&Block::Header) -> Result<BoxedProposer<Block>, ProposerError> + Send + Sync,
+{
+	fn init(&self, parent_header: &Block::Header) -> Result<BoxedProposer<Block>, ProposerError> {
+		(self)(parent_header)
+	}
+}
+
+/// A [`ProposerInterface`], that creates a new proposer with the given factory for every
+/// proposal.
+///
+/// This allows to use a [`ParachainProposerFactory`] wherever a proposer is expected.
+pub struct FactoryProposer<Block> {
+	factory: Arc<dyn ParachainProposerFactory<Block>>,
+}
+
+impl<Block> FactoryProposer<Block> {
+	/// Create a new [`FactoryProposer`].
+	pub fn new(factory: Arc<dyn ParachainProposerFactory<Block>>) -> Self {
+		FactoryProposer { factory }
+	}
+}
+
+impl<Block> Clone for FactoryProposer<Block> {
+	fn clone(&self) -> Self {
+		FactoryProposer { factory: self.factory.clone() }
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block: BlockT> ProposerInterface<Block> for FactoryProposer<Block> {
+	async fn propose(
+		&mut self,
+		parent_header: &Block::Header,
+		paras_inherent_data: &ParachainInherentData,
+		other_inherent_data: InherentData,
+		inherent_digests: Digest,
+		max_duration: Duration,
+		block_size_limit: Option<usize>,
+	) -> Result<Proposal<Block, StorageProof>, ProposerError> {
+		self.factory
+			.init(parent_header)?
+			.propose(
+				parent_header,
+				paras_inherent_data,
+				other_inherent_data,
+				inherent_digests,
+				max_duration,
+				block_size_limit,
+			)
+			.await
+	}
+}
@@ -201,6 +201,11 @@ where
 			runtime_api
 				.collect_collation_info_before_version_2(block_hash)?
 				.into_latest(header.encode().into())
+		} else if api_version < 3 {
+			#[allow(deprecated)]
+			runtime_api
+				.collect_collation_info_before_version_3(block_hash, header)?
+				.into_latest()
 		} else {
 			runtime_api.collect_collation_info(block_hash, header)?
 		};
@@ -245,6 +250,17 @@ where
 			.ok()
 			.flatten()?;
 
+		if let Some(resource) = collation_info.exceeded_resource() {
+			tracing::error!(
+				target: LOG_TARGET,
+				?resource,
+				footprint = ?collation_info.footprint.get(&resource),
+				limit = ?collation_info.limits.get(&resource),
+				"Collation exceeds the resource limit of the runtime",
+			);
+			return None
+		}
+
 		let block_data = ParachainBlockData::<Block>::new(header, extrinsics, compact_proof);
 
 		let pov = polkadot_node_primitives::maybe_compress_pov(PoV {
@@ -31,7 +31,8 @@ use cumulus_client_consensus_common::{
 };
 use cumulus_client_consensus_proposer::ProposerInterface;
 use cumulus_primitives_core::{
-	relay_chain::Hash as PHash, DigestItem, ParachainBlockData, PersistedValidationData,
+	authorship_proof_payload, relay_chain::Hash as PHash, CumulusDigestItem, DigestItem,
+	ParachainBlockData, PersistedValidationData,
 };
 use cumulus_primitives_parachain_inherent::ParachainInherentData;
 use cumulus_relay_chain_interface::RelayChainInterface;
@@ -43,7 +44,7 @@ use futures::prelude::*;
 use sc_consensus::{BlockImport, BlockImportParams, ForkChoiceStrategy, StateAction};
 use sc_consensus_aura::standalone as aura_internal;
 use sp_api::ProvideRuntimeApi;
-use sp_application_crypto::AppPublic;
+use sp_application_crypto::{AppCrypto, AppPublic, ByteArray};
 use sp_consensus::BlockOrigin;
 use sp_consensus_aura::{AuraApi, Slot, SlotDuration};
 use sp_core::crypto::Pair;
@@ -51,7 +52,7 @@ use sp_inherents::{CreateInherentDataProviders, InherentData, InherentDataProvid
 use sp_keystore::KeystorePtr;
 use sp_runtime::{
 	generic::Digest,
-	traits::{Block as BlockT, HashingFor, Header as HeaderT, Member},
+	traits::{Block as BlockT, HashingFor, Header as HeaderT, Member, One},
 };
 use sp_state_machine::StorageChanges;
 use sp_timestamp::Timestamp;
@@ -161,7 +162,8 @@ where
 	/// Provide the slot to build at as well as any other necessary pre-digest logs,
 	/// the inherent data, and the proposal duration and PoV size limits.
 	///
-	/// The Aura pre-digest should not be explicitly provided and is set internally.
+	/// The Aura pre-digest and the proof of the block authorship (see
+	/// [`authorship_proof_digest`]) should not be explicitly provided and are set internally.
 	///
 	/// This does not announce the collation to the parachain network or the relay chain.
 	pub async fn collate(
@@ -176,6 +178,10 @@ where
 	{
 		let mut digest = additional_pre_digest.into().unwrap_or_default();
 		digest.push(slot_claim.pre_digest.clone());
+		digest.push(
+			authorship_proof_digest::<Block, P>(parent_header, slot_claim, &self.keystore)
+				.map_err(|e| e as Box<dyn Error + Send>)?,
+		);
 
 		let proposal = self
 			.proposer
@@ -250,6 +256,7 @@ where
 /// A claim on an Aura slot.
 pub struct SlotClaim<Pub> {
 	author_pub: Pub,
+	slot: Slot,
 	pre_digest: DigestItem,
 	timestamp: Timestamp,
 }
@@ -265,7 +272,7 @@ impl<Pub> SlotClaim<Pub> {
 		P::Public: Codec,
 		P::Signature: Codec,
 	{
-		SlotClaim { author_pub, timestamp, pre_digest: aura_internal::pre_digest::<P>(slot) }
+		SlotClaim { author_pub, slot, timestamp, pre_digest: aura_internal::pre_digest::<P>(slot) }
 	}
 
 	/// Get the author's public key.
@@ -273,6 +280,11 @@ impl<Pub> SlotClaim<Pub> {
 		&self.author_pub
 	}
 
+	/// Get the slot, that has been claimed.
+	pub fn slot(&self) -> Slot {
+		self.slot
+	}
+
 	/// Get the Aura pre-digest for this slot.
 	pub fn pre_digest(&self) -> &DigestItem {
 		&self.pre_digest
@@ -338,6 +350,41 @@ where
 	Ok(Some(SlotClaim::unchecked::<P>(author_pub, slot_now, timestamp)))
 }
 
+/// Create the digest item with the proof, that the block built on top of the `parent_header` in
+/// the claimed slot is authored by the owner of the claimed slot.
+///
+/// The [`Collator::collate`] puts the proof into every block. Runtimes verify the proof, e.g. with
+/// the `AuraAuthorshipProof` of the `cumulus-pallet-aura-ext`.
+pub fn authorship_proof_digest<B: BlockT, P>(
+	parent_header: &B::Header,
+	slot_claim: &SlotClaim<P::Public>,
+	keystore: &KeystorePtr,
+) -> Result<DigestItem, Box<dyn Error + Send + Sync + 'static>>
+where
+	P: Pair,
+	P::Signature: Codec + TryFrom<Vec<u8>>,
+	P::Public: AppPublic,
+{
+	let author_pub = slot_claim.author_pub();
+	let number = *parent_header.number() + One::one();
+	let payload =
+		authorship_proof_payload(&parent_header.hash(), &number, u64::from(slot_claim.slot()));
+
+	let signature = keystore
+		.sign_with(
+			<P::Public as AppCrypto>::ID,
+			<P::Public as AppCrypto>::CRYPTO_ID,
+			author_pub.as_slice(),
+			&payload,
+		)
+		.map_err(Box::new)?
+		.ok_or("Could not sign the authorship proof: the key is not in the keystore")?;
+	let signature = P::Signature::try_from(signature)
+		.map_err(|_| "Invalid signature of the authorship proof")?;
+
+	Ok(CumulusDigestItem::AuthorshipProof((author_pub, signature).encode()).to_digest_item())
+}
+
 /// Seal a block with a signature in the header.
 pub fn seal<B: BlockT, P>(
 	pre_sealed: B,
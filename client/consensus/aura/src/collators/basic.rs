@@ -47,7 +47,11 @@ use sp_keystore::KeystorePtr;
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT, Member};
 use std::{convert::TryFrom, sync::Arc, time::Duration};
 
-use crate::collator as collator_util;
+use crate::{
+	collator as collator_util,
+	collators::{should_backoff, RelayFinalityBackoff},
+	key_rotation::{may_author, AuthoringKeys},
+};
 
 /// Parameters for [`run`].
 pub struct Params<BI, CIDP, Client, RClient, SO, Proposer, CS> {
@@ -81,6 +85,12 @@ pub struct Params<BI, CIDP, Client, RClient, SO, Proposer, CS> {
 	pub collator_service: CS,
 	/// The amount of time to spend authoring each block.
 	pub authoring_duration: Duration,
+	/// The Aura keys, that may be used for authoring. All the Aura keys in the keystore are
+	/// used, if it is `None`.
+	pub authoring_keys: Option<AuthoringKeys>,
+	/// Slows down the authoring, when the relay-chain finality lags behind. The authoring is
+	/// never slowed down, if it is `None`.
+	pub finality_backoff: Option<RelayFinalityBackoff>,
 }
 
 /// Run bare Aura consensus as a relay-chain-driven collator.
@@ -167,6 +177,16 @@ where
 					Ok(Some(h)) => h,
 				};
 
+			if should_backoff(
+				params.finality_backoff.as_ref(),
+				&params.relay_client,
+				&relay_parent_header,
+			)
+			.await
+			{
+				continue
+			}
+
 			let claim = match collator_util::claim_slot::<_, _, P>(
 				&*params.para_client,
 				parent_hash,
@@ -182,6 +202,10 @@ where
 				Err(e) => reject_with_error!(e),
 			};
 
+			if !may_author(params.authoring_keys.as_ref(), claim.author_pub()) {
+				continue
+			}
+
 			let (parachain_inherent_data, other_inherent_data) = try_request!(
 				collator
 					.create_inherent_data(
@@ -30,8 +30,12 @@
 //! building the block, as there is some buffer before it can get posted to the relay-chain.
 //! The main limitation is block propagation time - i.e. the new blocks created by an author
 //! must be propagated to the next author before their turn.
+//!
+//! The depth of the ancestry, that is searched for the parents to build upon, is limited by the
+//! asynchronous backing parameters of the relay chain. If multiple availability cores are
+//! scheduled for the para at the relay parent, one candidate is built for every core.
 
-use codec::{Codec, Encode};
+use codec::{Codec, Decode, Encode};
 use cumulus_client_collator::service::ServiceInterface as CollatorServiceInterface;
 use cumulus_client_consensus_common::{
 	self as consensus_common, ParachainBlockImportMarker, ParentSearchParams,
@@ -41,12 +45,16 @@ use cumulus_primitives_aura::AuraUnincludedSegmentApi;
 use cumulus_primitives_core::{
 	relay_chain::Hash as PHash, CollectCollationInfo, PersistedValidationData,
 };
-use cumulus_relay_chain_interface::RelayChainInterface;
+use cumulus_relay_chain_interface::{
+	RelayChainError, RelayChainInterface, RelayChainResult, RuntimeApiCall,
+};
 
 use polkadot_node_primitives::SubmitCollationParams;
 use polkadot_node_subsystem::messages::CollationGenerationMessage;
 use polkadot_overseer::Handle as OverseerHandle;
-use polkadot_primitives::{CollatorPair, Id as ParaId, OccupiedCoreAssumption};
+use polkadot_primitives::{
+	vstaging::AsyncBackingParams, CollatorPair, CoreState, Id as ParaId, OccupiedCoreAssumption,
+};
 
 use futures::prelude::*;
 use sc_client_api::{backend::AuxStore, BlockBackend, BlockOf};
@@ -64,7 +72,11 @@ use sp_runtime::traits::{Block as BlockT, Header as HeaderT, Member};
 use sp_timestamp::Timestamp;
 use std::{convert::TryFrom, sync::Arc, time::Duration};
 
-use crate::collator::{self as collator_util, SlotClaim};
+use crate::{
+	collator::{self as collator_util, SlotClaim},
+	collators::{should_backoff, RelayFinalityBackoff},
+	key_rotation::{may_author, AuthoringKeys},
+};
 
 /// Parameters for [`run`].
 pub struct Params<BI, CIDP, Client, Backend, RClient, CHP, SO, Proposer, CS> {
@@ -102,6 +114,12 @@ pub struct Params<BI, CIDP, Client, Backend, RClient, CHP, SO, Proposer, CS> {
 	pub collator_service: CS,
 	/// The amount of time to spend authoring each block.
 	pub authoring_duration: Duration,
+	/// The Aura keys, that may be used for authoring. All the Aura keys in the keystore are
+	/// used, if it is `None`.
+	pub authoring_keys: Option<AuthoringKeys>,
+	/// Slows down the authoring, when the relay-chain finality lags behind. The authoring is
+	/// never slowed down, if it is `None`.
+	pub finality_backoff: Option<RelayFinalityBackoff>,
 }
 
 /// Run async-backing-friendly Aura.
@@ -180,22 +198,30 @@ where
 		while let Some(relay_parent_header) = import_notifications.next().await {
 			let relay_parent = relay_parent_header.hash();
 
-			let max_pov_size = match params
-				.relay_client
-				.persisted_validation_data(
-					relay_parent,
-					params.para_id,
-					OccupiedCoreAssumption::Included,
-				)
-				.await
+			if should_backoff(
+				params.finality_backoff.as_ref(),
+				&params.relay_client,
+				&relay_parent_header,
+			)
+			.await
+			{
+				continue
+			}
+
+			let relay_parent_data = match relay_parent_data(
+				relay_parent,
+				params.para_id,
+				&params.relay_client,
+			)
+			.await
 			{
-				Ok(None) => continue,
-				Ok(Some(pvd)) => pvd.max_pov_size,
+				Ok(data) => data,
 				Err(err) => {
-					tracing::error!(target: crate::LOG_TARGET, ?err, "Failed to gather information from relay-client");
+					tracing::error!(target: crate::LOG_TARGET, ?relay_parent, ?err, "Failed to gather information from relay-client");
 					continue
 				},
 			};
+			let Some(max_pov_size) = relay_parent_data.max_pov_size else { continue };
 
 			let (slot_now, timestamp) = match consensus_common::relay_slot_and_timestamp(
 				&relay_parent_header,
@@ -220,7 +246,7 @@ where
 			let parent_search_params = ParentSearchParams {
 				relay_parent,
 				para_id: params.para_id,
-				ancestry_lookback: max_ancestry_lookback(relay_parent, &params.relay_client).await,
+				ancestry_lookback: relay_parent_data.ancestry_lookback,
 				max_depth: PARENT_SEARCH_DEPTH,
 				ignore_alternative_branches: true,
 			};
@@ -281,14 +307,21 @@ where
 			let mut parent_header = initial_parent.header;
 			let overseer_handle = &mut params.overseer_handle;
 
-			// This needs to change to support elastic scaling, but for continuously
-			// scheduled chains this ensures that the backlog will grow steadily.
-			for n_built in 0..2 {
+			// Build one block for every core scheduled for us, plus one more, so that
+			// the backlog will grow steadily for continuously scheduled chains. The
+			// unincluded segment can't grow beyond the maximum candidate depth anyway.
+			let cores = scheduled_cores(&relay_parent_data.cores, params.para_id);
+			let max_blocks = (cores.max(1) + 1).min(relay_parent_data.max_candidate_depth + 1);
+			for n_built in 0..max_blocks {
 				let slot_claim = match can_build_upon(parent_hash).await {
 					None => break,
 					Some(c) => c,
 				};
 
+				if !may_author(params.authoring_keys.as_ref(), slot_claim.author_pub()) {
+					break
+				}
+
 				tracing::debug!(
 					target: crate::LOG_TARGET,
 					?relay_parent,
@@ -385,7 +418,7 @@ where
 
 // Checks if we own the slot at the given block and whether there
 // is space in the unincluded segment.
-async fn can_build_upon<Block: BlockT, Client, P>(
+pub(crate) async fn can_build_upon<Block: BlockT, Client, P>(
 	slot: Slot,
 	timestamp: Timestamp,
 	parent_hash: Block::Hash,
@@ -416,16 +449,62 @@ where
 	Some(SlotClaim::unchecked::<P>(author_pub, slot, timestamp))
 }
 
-async fn max_ancestry_lookback(
-	_relay_parent: PHash,
-	_relay_client: &impl RelayChainInterface,
-) -> usize {
-	// TODO [https://github.com/paritytech/cumulus/issues/2706]
-	// We need to read the relay-chain state to know what the maximum
-	// age truly is, but that depends on those pallets existing.
-	//
-	// For now, just provide the conservative value of '2'.
-	// Overestimating can cause problems, as we'd be building on forks of the
-	// chain that can never get included. Underestimating is less of an issue.
-	2
+/// Returns the maximum ancestry lookback and the maximum candidate depth at the given relay
+/// parent.
+/// The data of a relay parent, that the collators need to decide what to build.
+pub(crate) struct RelayParentData {
+	/// The maximum PoV size, `None` if the para has no persisted validation data.
+	pub max_pov_size: Option<u32>,
+	/// The depth of the ancestry, that is searched for the parents to build upon.
+	pub ancestry_lookback: usize,
+	/// The maximum depth of the unincluded candidates.
+	pub max_candidate_depth: usize,
+	/// The availability cores.
+	pub cores: Vec<CoreState>,
+}
+
+/// Fetch the [`RelayParentData`] of the `relay_parent` with one batch of runtime API calls.
+pub(crate) async fn relay_parent_data(
+	relay_parent: PHash,
+	para_id: ParaId,
+	relay_client: &impl RelayChainInterface,
+) -> RelayChainResult<RelayParentData> {
+	let results = relay_client
+		.call_runtime_api_batch(
+			relay_parent,
+			vec![
+				RuntimeApiCall::new(
+					"ParachainHost_persisted_validation_data",
+					(para_id, OccupiedCoreAssumption::Included),
+				),
+				RuntimeApiCall::new("ParachainHost_staging_async_backing_params", ()),
+				RuntimeApiCall::new("ParachainHost_availability_cores", ()),
+			],
+		)
+		.await?;
+	let [validation_data, async_backing_params, cores] = <[Vec<u8>; 3]>::try_from(results)
+		.map_err(|_| RelayChainError::GenericError("Missing runtime API results".into()))?;
+
+	let validation_data = Option::<PersistedValidationData>::decode(&mut &validation_data[..])?;
+	let async_backing_params = AsyncBackingParams::decode(&mut &async_backing_params[..])?;
+
+	Ok(RelayParentData {
+		max_pov_size: validation_data.map(|pvd| pvd.max_pov_size),
+		ancestry_lookback: async_backing_params.allowed_ancestry_len as usize,
+		max_candidate_depth: async_backing_params.max_candidate_depth as usize,
+		cores: Decode::decode(&mut &cores[..])?,
+	})
+}
+
+/// Returns the number of the availability `cores`, that are scheduled for the para.
+fn scheduled_cores(cores: &[CoreState], para_id: ParaId) -> usize {
+	cores
+		.iter()
+		.filter(|core| match core {
+			CoreState::Scheduled(scheduled) => scheduled.para_id == para_id,
+			CoreState::Occupied(occupied) =>
+				occupied.next_up_on_available.as_ref().map_or(false, |s| s.para_id == para_id),
+			CoreState::Free => false,
+		})
+		.count()
 }
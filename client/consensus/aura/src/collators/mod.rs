@@ -18,7 +18,168 @@
 //!
 //! This includes the [`basic`] collator, which only builds on top of the most recently
 //! included parachain block, as well as the [`lookahead`] collator, which prospectively
-//! builds on parachain blocks which have not yet been included in the relay chain.
+//! builds on parachain blocks which have not yet been included in the relay chain. The
+//! [`slot_based`] collator additionally builds one block per core for parachains, that are
+//! assigned multiple cores in the same relay-chain block.
+//!
+//! All of them can slow down the authoring with a [`RelayFinalityBackoff`], when the finality of
+//! the relay chain stalls, to not build a long parachain fork on top of unfinalized relay-chain
+//! blocks.
+
+use cumulus_primitives_core::relay_chain::BlockId as RBlockId;
+use cumulus_relay_chain_interface::{RelayChainInterface, RelayChainResult};
+use polkadot_primitives::{BlockNumber as RBlockNumber, Hash as PHash, Header as PHeader};
+use std::future::Future;
 
 pub mod basic;
 pub mod lookahead;
+pub mod slot_based;
+
+/// A strategy to slow down the authoring, when the finalized relay-chain block lags behind the
+/// best relay-chain block.
+///
+/// Up to `unfinalized_slack` unfinalized relay-chain blocks, every relay parent is built upon.
+/// Afterwards the number of the relay-chain blocks between two authored blocks grows with every
+/// `authoring_bias` further unfinalized blocks, up to `max_interval`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelayFinalityBackoff {
+	/// The number of the unfinalized relay-chain blocks, that don't slow down the authoring.
+	pub unfinalized_slack: RBlockNumber,
+	/// The number of the unfinalized relay-chain blocks, that increase the interval by one.
+	pub authoring_bias: RBlockNumber,
+	/// The maximal number of the relay-chain blocks between two authored blocks.
+	pub max_interval: RBlockNumber,
+}
+
+impl Default for RelayFinalityBackoff {
+	fn default() -> Self {
+		Self { unfinalized_slack: 50, authoring_bias: 2, max_interval: 100 }
+	}
+}
+
+impl RelayFinalityBackoff {
+	/// Returns `true` if the collator should not build upon the relay parent with the given
+	/// `relay_parent_number`, while the relay-chain block `best_number` is the best and the block
+	/// `finalized_number` is finalized.
+	pub fn should_backoff(
+		&self,
+		relay_parent_number: RBlockNumber,
+		best_number: RBlockNumber,
+		finalized_number: RBlockNumber,
+	) -> bool {
+		let unfinalized = best_number.saturating_sub(finalized_number);
+		if unfinalized <= self.unfinalized_slack {
+			return false
+		}
+
+		let interval = ((unfinalized - self.unfinalized_slack) / self.authoring_bias.max(1))
+			.min(self.max_interval);
+		relay_parent_number % (interval + 1) != 0
+	}
+}
+
+/// Returns `true` if the `backoff` requires to skip the authoring on top of the
+/// `relay_parent_header`.
+///
+/// The authoring isn't skipped, if the best or the finalized relay-chain block can't be
+/// determined.
+pub(crate) async fn should_backoff(
+	backoff: Option<&RelayFinalityBackoff>,
+	relay_client: &impl RelayChainInterface,
+	relay_parent_header: &PHeader,
+) -> bool {
+	let backoff = match backoff {
+		Some(backoff) => backoff,
+		None => return false,
+	};
+
+	let (best_number, finalized_number) = match futures::try_join!(
+		block_number(relay_client, relay_client.best_block_hash()),
+		block_number(relay_client, relay_client.finalized_block_hash()),
+	) {
+		Ok((Some(best_number), Some(finalized_number))) => (best_number, finalized_number),
+		Ok(_) => return false,
+		Err(err) => {
+			tracing::debug!(target: crate::LOG_TARGET, ?err, "Failed to fetch the best or the finalized relay-chain block");
+			return false
+		},
+	};
+
+	if backoff.should_backoff(relay_parent_header.number, best_number, finalized_number) {
+		tracing::debug!(
+			target: crate::LOG_TARGET,
+			relay_parent = ?relay_parent_header.hash(),
+			relay_parent_number = relay_parent_header.number,
+			best_number,
+			finalized_number,
+			"Backing off authoring, as the relay-chain finality lags behind",
+		);
+		return true
+	}
+
+	false
+}
+
+/// Returns the number of the relay-chain block, whose `hash` is returned by the given future.
+async fn block_number(
+	relay_client: &impl RelayChainInterface,
+	hash: impl Future<Output = RelayChainResult<PHash>>,
+) -> RelayChainResult<Option<RBlockNumber>> {
+	let hash = hash.await?;
+	Ok(relay_client.header(RBlockId::hash(hash)).await?.map(|header| header.number))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn backoff() -> RelayFinalityBackoff {
+		RelayFinalityBackoff { unfinalized_slack: 10, authoring_bias: 2, max_interval: 5 }
+	}
+
+	#[test]
+	fn no_backoff_within_the_slack() {
+		let backoff = backoff();
+
+		for relay_parent_number in 90..100 {
+			assert!(!backoff.should_backoff(relay_parent_number, 100, 90));
+		}
+	}
+
+	#[test]
+	fn interval_grows_with_the_lag_of_the_best_block() {
+		let backoff = backoff();
+
+		// 14 unfinalized blocks result in an interval of 2, every third relay parent is built upon.
+		let authored =
+			(100..112).filter(|n| !backoff.should_backoff(*n, 114, 100)).collect::<Vec<_>>();
+		assert_eq!(authored, vec![102, 105, 108, 111]);
+	}
+
+	#[test]
+	fn interval_is_capped() {
+		let backoff = backoff();
+
+		// The interval of 45 is capped to 5, every sixth relay parent is built upon.
+		let authored = (0..24).filter(|n| !backoff.should_backoff(*n, 100, 0)).collect::<Vec<_>>();
+		assert_eq!(authored, vec![0, 6, 12, 18]);
+	}
+
+	#[test]
+	fn lag_is_measured_against_the_best_block() {
+		let backoff = backoff();
+
+		// The relay parent is close to the finalized block, but the best block is far ahead.
+		assert!(backoff.should_backoff(101, 200, 100));
+		// The best block is close to the finalized block.
+		assert!(!backoff.should_backoff(101, 105, 100));
+	}
+
+	#[test]
+	fn zero_authoring_bias_does_not_panic() {
+		let backoff = RelayFinalityBackoff { authoring_bias: 0, ..backoff() };
+
+		assert!(!backoff.should_backoff(0, 20, 0));
+		assert!(backoff.should_backoff(1, 20, 0));
+	}
+}
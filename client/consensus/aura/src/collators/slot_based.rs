@@ -0,0 +1,364 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A collator for Aura that supports elastic scaling, i.e. parachains that are assigned
+//! multiple availability cores in the same relay-chain block.
+//!
+//! For every relay-chain block, this collator reads the claim queue of the relay parent and
+//! determines the cores, whose next claim belongs to the para. It then builds a chain of
+//! blocks on top of the best unincluded parent, one block per claimed core, and submits a
+//! candidate for every one of them, in the ascending order of the core indices. The authoring
+//! time is split evenly between the blocks, so that all candidates are ready before the next
+//! relay-chain block.
+//!
+//! The claim queue is read with the `claim_queue` function of the `ParachainHost` runtime API,
+//! so no blocks are built if the relay chain runtime doesn't provide it. The collator is enabled
+//! in the `polkadot-parachain` with the `--experimental-use-slot-based` flag.
+//!
+//! Like the [`lookahead`](super::lookahead) collator, this requires the parachain runtime to
+//! implement the [`AuraUnincludedSegmentApi`], which has to allow for an unincluded segment,
+//! that is long enough to fit the blocks of all the claimed cores.
+
+use codec::{Codec, Encode};
+use cumulus_client_collator::service::ServiceInterface as CollatorServiceInterface;
+use cumulus_client_consensus_common::{
+	self as consensus_common, ParachainBlockImportMarker, ParentSearchParams,
+};
+use cumulus_client_consensus_proposer::ProposerInterface;
+use cumulus_primitives_aura::AuraUnincludedSegmentApi;
+use cumulus_primitives_core::{
+	relay_chain::Hash as PHash, CollectCollationInfo, PersistedValidationData,
+};
+use cumulus_relay_chain_interface::RelayChainInterface;
+
+use polkadot_node_primitives::SubmitCollationParams;
+use polkadot_node_subsystem::messages::CollationGenerationMessage;
+use polkadot_primitives::{CoreIndex, Id as ParaId};
+
+use futures::prelude::*;
+use sc_client_api::{backend::AuxStore, BlockBackend, BlockOf};
+use sc_consensus::BlockImport;
+use sp_api::ProvideRuntimeApi;
+use sp_application_crypto::AppPublic;
+use sp_blockchain::HeaderBackend;
+use sp_consensus::SyncOracle;
+use sp_consensus_aura::{AuraApi, Slot};
+use sp_core::crypto::Pair;
+use sp_inherents::CreateInherentDataProviders;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, Member};
+use std::convert::TryFrom;
+
+use super::lookahead::{can_build_upon, relay_parent_data};
+use crate::{collator as collator_util, collators::should_backoff, key_rotation::may_author};
+
+/// Parameters for [`run`].
+///
+/// These are the same as the parameters of the [`lookahead`](super::lookahead) collator.
+pub use super::lookahead::Params;
+
+/// Run Aura with support for elastic scaling.
+pub fn run<Block, P, BI, CIDP, Client, Backend, RClient, CHP, SO, Proposer, CS>(
+	mut params: Params<BI, CIDP, Client, Backend, RClient, CHP, SO, Proposer, CS>,
+) -> impl Future<Output = ()> + Send + 'static
+where
+	Block: BlockT,
+	Client: ProvideRuntimeApi<Block>
+		+ BlockOf
+		+ AuxStore
+		+ HeaderBackend<Block>
+		+ BlockBackend<Block>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api:
+		AuraApi<Block, P::Public> + CollectCollationInfo<Block> + AuraUnincludedSegmentApi<Block>,
+	Backend: sc_client_api::Backend<Block> + 'static,
+	RClient: RelayChainInterface + Clone + 'static,
+	CIDP: CreateInherentDataProviders<Block, ()> + 'static,
+	CIDP::InherentDataProviders: Send,
+	BI: BlockImport<Block> + ParachainBlockImportMarker + Send + Sync + 'static,
+	SO: SyncOracle + Send + Sync + Clone + 'static,
+	Proposer: ProposerInterface<Block> + Send + Sync + 'static,
+	CS: CollatorServiceInterface<Block> + Send + Sync + 'static,
+	CHP: consensus_common::ValidationCodeHashProvider<Block::Hash> + Send + 'static,
+	P: Pair,
+	P::Public: AppPublic + Member + Codec,
+	P::Signature: TryFrom<Vec<u8>> + Member + Codec,
+{
+	// See the `lookahead` collator. As all imported blocks respect the unincluded segment
+	// rules of the runtime, this only needs to exceed any reasonable limit.
+	const PARENT_SEARCH_DEPTH: usize = 10;
+
+	async move {
+		cumulus_client_collator::initialize_collator_subsystems(
+			&mut params.overseer_handle,
+			params.collator_key,
+			params.para_id,
+		)
+		.await;
+
+		let mut import_notifications = match params.relay_client.import_notification_stream().await
+		{
+			Ok(s) => s,
+			Err(err) => {
+				tracing::error!(
+					target: crate::LOG_TARGET,
+					?err,
+					"Failed to initialize consensus: no relay chain import notification stream"
+				);
+
+				return
+			},
+		};
+
+		let mut collator = {
+			let params = collator_util::Params {
+				create_inherent_data_providers: params.create_inherent_data_providers,
+				block_import: params.block_import,
+				relay_client: params.relay_client.clone(),
+				keystore: params.keystore.clone(),
+				para_id: params.para_id,
+				proposer: params.proposer,
+				collator_service: params.collator_service,
+			};
+
+			collator_util::Collator::<Block, P, _, _, _, _, _>::new(params)
+		};
+
+		while let Some(relay_parent_header) = import_notifications.next().await {
+			let relay_parent = relay_parent_header.hash();
+
+			if should_backoff(
+				params.finality_backoff.as_ref(),
+				&params.relay_client,
+				&relay_parent_header,
+			)
+			.await
+			{
+				continue
+			}
+
+			let cores = claimed_cores(relay_parent, params.para_id, &params.relay_client).await;
+			if cores.is_empty() {
+				tracing::trace!(
+					target: crate::LOG_TARGET,
+					?relay_parent,
+					"No core claimed by the para at the relay parent"
+				);
+				continue
+			}
+
+			let relay_parent_data = match relay_parent_data(
+				relay_parent,
+				params.para_id,
+				&params.relay_client,
+			)
+			.await
+			{
+				Ok(data) => data,
+				Err(err) => {
+					tracing::error!(target: crate::LOG_TARGET, ?relay_parent, ?err, "Failed to gather information from relay-client");
+					continue
+				},
+			};
+			let Some(max_pov_size) = relay_parent_data.max_pov_size else { continue };
+
+			let (slot_now, timestamp) = match consensus_common::relay_slot_and_timestamp(
+				&relay_parent_header,
+				params.relay_chain_slot_duration,
+			) {
+				None => continue,
+				Some((_, t)) => (Slot::from_timestamp(t, params.slot_duration), t),
+			};
+
+			let parent_search_params = ParentSearchParams {
+				relay_parent,
+				para_id: params.para_id,
+				ancestry_lookback: relay_parent_data.ancestry_lookback,
+				max_depth: PARENT_SEARCH_DEPTH,
+				ignore_alternative_branches: true,
+			};
+
+			let potential_parents =
+				cumulus_client_consensus_common::find_potential_parents::<Block>(
+					parent_search_params,
+					&*params.para_backend,
+					&params.relay_client,
+				)
+				.await;
+
+			let mut potential_parents = match potential_parents {
+				Err(e) => {
+					tracing::error!(
+						target: crate::LOG_TARGET,
+						?relay_parent,
+						err = ?e,
+						"Could not fetch potential parents to build upon"
+					);
+
+					continue
+				},
+				Ok(x) => x,
+			};
+
+			let included_block = match potential_parents.iter().find(|x| x.depth == 0) {
+				None => continue, // also serves as an `is_empty` check.
+				Some(b) => b.hash,
+			};
+
+			// Sort by depth, ascending, to choose the longest chain.
+			potential_parents.sort_by_key(|a| a.depth);
+			let initial_parent = match potential_parents.pop() {
+				None => continue,
+				Some(p) => p,
+			};
+
+			let mut parent_hash = initial_parent.hash;
+			let mut parent_header = initial_parent.header;
+			let overseer_handle = &mut params.overseer_handle;
+
+			// All the candidates have to be ready before the next relay-chain block.
+			let authoring_duration = params.authoring_duration / cores.len() as u32;
+
+			// Build one block per claimed core in a chain. The authorities can change at
+			// any block, so we need to re-claim our slot every time.
+			for (n_built, core_index) in cores.into_iter().enumerate() {
+				let slot_claim = match can_build_upon::<_, _, P>(
+					slot_now,
+					timestamp,
+					parent_hash,
+					included_block,
+					&*params.para_client,
+					&params.keystore,
+				)
+				.await
+				{
+					None => break,
+					Some(c) => c,
+				};
+
+				if !may_author(params.authoring_keys.as_ref(), slot_claim.author_pub()) {
+					break
+				}
+
+				tracing::debug!(
+					target: crate::LOG_TARGET,
+					?relay_parent,
+					?core_index,
+					unincluded_segment_len = initial_parent.depth + n_built,
+					"Slot claimed. Building"
+				);
+
+				let validation_data = PersistedValidationData {
+					parent_head: parent_header.encode().into(),
+					relay_parent_number: *relay_parent_header.number(),
+					relay_parent_storage_root: *relay_parent_header.state_root(),
+					max_pov_size,
+				};
+
+				let (parachain_inherent_data, other_inherent_data) = match collator
+					.create_inherent_data(
+						relay_parent,
+						&validation_data,
+						parent_hash,
+						slot_claim.timestamp(),
+					)
+					.await
+				{
+					Err(err) => {
+						tracing::error!(target: crate::LOG_TARGET, ?err);
+						break
+					},
+					Ok(x) => x,
+				};
+
+				let validation_code_hash = match params.code_hash_provider.code_hash_at(parent_hash)
+				{
+					None => {
+						tracing::error!(target: crate::LOG_TARGET, ?parent_hash, "Could not fetch validation code hash");
+						break
+					},
+					Some(v) => v,
+				};
+
+				match collator
+					.collate(
+						&parent_header,
+						&slot_claim,
+						None,
+						(parachain_inherent_data, other_inherent_data),
+						authoring_duration,
+						// Set the block limit to 50% of the maximum PoV size.
+						(validation_data.max_pov_size / 2) as usize,
+					)
+					.await
+				{
+					Ok((collation, block_data, new_block_hash)) => {
+						collator.collator_service().announce_block(new_block_hash, None);
+
+						overseer_handle
+							.send_msg(
+								CollationGenerationMessage::SubmitCollation(
+									SubmitCollationParams {
+										relay_parent,
+										collation,
+										parent_head: parent_header.encode().into(),
+										validation_code_hash,
+										result_sender: None,
+									},
+								),
+								"SubmitCollation",
+							)
+							.await;
+
+						parent_hash = new_block_hash;
+						parent_header = block_data.into_header();
+					},
+					Err(err) => {
+						tracing::error!(target: crate::LOG_TARGET, ?err);
+						break
+					},
+				}
+			}
+		}
+	}
+}
+
+/// Returns the cores, whose next claim in the claim queue of the given relay parent belongs to
+/// the para, in the ascending order of their indices.
+async fn claimed_cores(
+	relay_parent: PHash,
+	para_id: ParaId,
+	relay_client: &impl RelayChainInterface,
+) -> Vec<CoreIndex> {
+	let claim_queue = match relay_client.claim_queue(relay_parent).await {
+		Ok(claim_queue) => claim_queue,
+		Err(err) => {
+			tracing::error!(
+				target: crate::LOG_TARGET,
+				?relay_parent,
+				?err,
+				"Failed to fetch the claim queue",
+			);
+			return Vec::new()
+		},
+	};
+
+	claim_queue
+		.into_iter()
+		.filter_map(|(core_index, claims)| (claims.front() == Some(&para_id)).then_some(core_index))
+		.collect()
+}
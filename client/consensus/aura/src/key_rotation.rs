@@ -0,0 +1,190 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Rotation of the Aura key of a running collator.
+//!
+//! After the new session keys are registered on-chain with `session.set_keys`, they only become
+//! an Aura authority when the session changes. Until then both the current and the new key may
+//! be in the keystore and the collator has to keep authoring with the current key. The
+//! `cumulus_rotateAuraKey` RPC schedules the new key, that has to be present in the keystore.
+//! Once it is one of the Aura authorities of the best parachain block, it becomes the only key,
+//! that the collator authors with, so the collator neither needs a restart nor misses its slots.
+//!
+//! The [`track_aura_authorities`] task follows the authorities of the best parachain block and
+//! activates the scheduled key. The [`AuthoringKeys`] need to be passed to the collator, which
+//! only claims the slots of the keys, that [`AuthoringKeys::may_author`].
+
+use futures::StreamExt;
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+};
+use sc_client_api::BlockchainEvents;
+use sc_rpc_api::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_application_crypto::ByteArray;
+use sp_consensus_aura::AuraApi;
+use sp_core::{crypto::key_types::AURA, Bytes};
+use sp_keystore::KeystorePtr;
+use sp_runtime::traits::Block as BlockT;
+use std::sync::{Arc, Mutex};
+
+const LOG_TARGET: &str = "aura::key-rotation";
+
+/// The state of the rotation of the Aura key.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRotationStatus {
+	/// The key, that is used for authoring. All the Aura keys in the keystore are used, if it
+	/// is not set.
+	pub active_key: Option<Bytes>,
+	/// The key, that becomes active once it is an Aura authority.
+	pub pending_key: Option<Bytes>,
+}
+
+/// The Aura keys, that the collator may author with.
+///
+/// Shared between the collator, the [`track_aura_authorities`] task and the [`AuraKeyRotation`]
+/// RPC.
+#[derive(Clone, Default)]
+pub struct AuthoringKeys(Arc<Mutex<KeyRotationStatus>>);
+
+impl AuthoringKeys {
+	/// Schedule the `key` to become the active key, once it is an Aura authority.
+	///
+	/// Replaces a previously scheduled key.
+	pub fn schedule(&self, key: Vec<u8>) {
+		self.0.lock().expect("poisoned lock").pending_key = Some(key.into());
+	}
+
+	/// Note the Aura `authorities` of the best parachain block.
+	pub fn note_authorities<'a>(&self, mut authorities: impl Iterator<Item = &'a [u8]>) {
+		let mut status = self.0.lock().expect("poisoned lock");
+		let is_authority = match &status.pending_key {
+			Some(pending) => authorities.any(|authority| authority == &pending[..]),
+			None => return,
+		};
+
+		if is_authority {
+			status.active_key = status.pending_key.take();
+			tracing::info!(target: LOG_TARGET, key = ?status.active_key, "🔑 Rotated the Aura key");
+		}
+	}
+
+	/// Returns `true` if the collator may author with the given `key`.
+	///
+	/// The pending key may not be used before it is activated and once a key has been rotated,
+	/// only the active key may be used.
+	pub fn may_author(&self, key: &[u8]) -> bool {
+		let status = self.0.lock().expect("poisoned lock");
+		if status.pending_key.as_ref().map_or(false, |pending| &pending[..] == key) {
+			return false
+		}
+
+		status.active_key.as_ref().map_or(true, |active| &active[..] == key)
+	}
+
+	/// Returns the current [`KeyRotationStatus`].
+	pub fn status(&self) -> KeyRotationStatus {
+		self.0.lock().expect("poisoned lock").clone()
+	}
+}
+
+/// Returns `true` if no `authoring_keys` are given or they allow to author with `author`.
+pub(crate) fn may_author<Pub: ByteArray>(
+	authoring_keys: Option<&AuthoringKeys>,
+	author: &Pub,
+) -> bool {
+	authoring_keys.map_or(true, |keys| keys.may_author(author.as_slice()))
+}
+
+/// Follow the Aura authorities of the best parachain block and activate the scheduled keys of
+/// the `authoring_keys`.
+pub async fn track_aura_authorities<Block, Client, AuthorityId>(
+	client: Arc<Client>,
+	authoring_keys: AuthoringKeys,
+) where
+	Block: BlockT,
+	Client: BlockchainEvents<Block> + ProvideRuntimeApi<Block>,
+	Client::Api: AuraApi<Block, AuthorityId>,
+	AuthorityId: ByteArray + codec::Codec,
+{
+	let mut imported_blocks = client.import_notification_stream();
+
+	while let Some(notification) = imported_blocks.next().await {
+		if !notification.is_new_best || authoring_keys.status().pending_key.is_none() {
+			continue
+		}
+
+		match client.runtime_api().authorities(notification.hash) {
+			Ok(authorities) =>
+				authoring_keys.note_authorities(authorities.iter().map(ByteArray::as_slice)),
+			Err(error) =>
+				tracing::debug!(target: LOG_TARGET, ?error, "Failed to fetch the Aura authorities"),
+		}
+	}
+}
+
+/// The RPC API for the rotation of the Aura key.
+#[rpc(client, server)]
+pub trait AuraKeyRotationApi {
+	/// Author only with the Aura key `public`, once it is an Aura authority.
+	///
+	/// The key needs to be in the keystore, e.g. generated with `author_rotateKeys`, and
+	/// registered on-chain with `session.set_keys`.
+	#[method(name = "cumulus_rotateAuraKey")]
+	fn rotate_aura_key(&self, public: Bytes) -> RpcResult<()>;
+
+	/// Returns the state of the rotation of the Aura key.
+	#[method(name = "cumulus_auraKeyRotation")]
+	fn aura_key_rotation(&self) -> RpcResult<KeyRotationStatus>;
+}
+
+/// Implements the [`AuraKeyRotationApiServer`].
+pub struct AuraKeyRotation {
+	authoring_keys: AuthoringKeys,
+	keystore: KeystorePtr,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl AuraKeyRotation {
+	/// Create a new [`AuraKeyRotation`].
+	pub fn new(
+		authoring_keys: AuthoringKeys,
+		keystore: KeystorePtr,
+		deny_unsafe: DenyUnsafe,
+	) -> Self {
+		Self { authoring_keys, keystore, deny_unsafe }
+	}
+}
+
+impl AuraKeyRotationApiServer for AuraKeyRotation {
+	fn rotate_aura_key(&self, public: Bytes) -> RpcResult<()> {
+		self.deny_unsafe.check_if_safe()?;
+
+		if !self.keystore.has_keys(&[(public.to_vec(), AURA)]) {
+			return Err(JsonRpseeError::Custom("The Aura key is not in the keystore".into()))
+		}
+
+		self.authoring_keys.schedule(public.0);
+		Ok(())
+	}
+
+	fn aura_key_rotation(&self) -> RpcResult<KeyRotationStatus> {
+		Ok(self.authoring_keys.status())
+	}
+}
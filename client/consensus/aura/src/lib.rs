@@ -53,6 +53,8 @@ pub use sc_consensus_slots::InherentDataProviderExt;
 pub mod collator;
 pub mod collators;
 pub mod equivocation_import_queue;
+pub mod key_rotation;
+pub mod shuffling;
 
 const LOG_TARGET: &str = "aura::cumulus";
 
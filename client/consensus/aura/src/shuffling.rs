@@ -0,0 +1,223 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Deterministic shuffling of the extrinsics of parachain blocks.
+//!
+//! See [`cumulus_primitives_core::shuffling`] for the rules of the shuffling. The
+//! [`ShufflingProposer`] builds the blocks with the shuffled extrinsics and the
+//! [`ShufflingBlockImport`] verifies the order of the extrinsics on import. The runtime verifies
+//! the order in `validate_block` as well, if its block executor is wrapped into the
+//! `ShufflingBlockExecutor` of the `cumulus-pallet-aura-ext`.
+//!
+//! As transactions of the same sender may depend on each other, the ones that fail in the
+//! shuffled order are dropped from the block and are included in one of the next blocks.
+
+use cumulus_client_consensus_common::ParachainBlockImportMarker;
+use cumulus_client_consensus_proposer::{
+	storage_proof_size::apply_extrinsics_with_proof_size, Error as ProposerError, ProposerInterface,
+};
+use cumulus_primitives_core::CumulusDigestItem;
+use cumulus_primitives_parachain_inherent::ParachainInherentData;
+use sc_consensus::{BlockCheckParams, BlockImport, BlockImportParams, ImportResult};
+use sp_api::ProvideRuntimeApi;
+use sp_block_builder::BlockBuilder as BlockBuilderApi;
+use sp_consensus::{Error as ConsensusError, Proposal};
+use sp_inherents::InherentData;
+use sp_runtime::{
+	traits::{Block as BlockT, Extrinsic as ExtrinsicT, Header as HeaderT},
+	Digest,
+};
+use sp_state_machine::StorageProof;
+use std::{
+	marker::PhantomData,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+pub use cumulus_primitives_core::shuffling::{
+	shuffle_extrinsics, shuffling_seed, verify_shuffling,
+};
+
+/// A proposer, that shuffles the extrinsics of the blocks built by the `inner` proposer.
+///
+/// The block is rebuilt with the shuffled extrinsics and with the storage proof size exposed to
+/// the runtime, the same way as the
+/// [`ProofSizeRecordingProposer`](cumulus_client_consensus_proposer::storage_proof_size::ProofSizeRecordingProposer)
+/// does, so it is used instead of the latter.
+///
+/// Half of the `max_duration` is given to the `inner` proposer, the rest is reserved for
+/// rebuilding the block. Once the `max_duration` has elapsed, only the inherents are kept.
+pub struct ShufflingProposer<Block, Inner, Client, Backend> {
+	inner: Inner,
+	client: Arc<Client>,
+	backend: Arc<Backend>,
+	_marker: PhantomData<Block>,
+}
+
+impl<Block, Inner, Client, Backend> ShufflingProposer<Block, Inner, Client, Backend> {
+	/// Create a new [`ShufflingProposer`].
+	pub fn new(inner: Inner, client: Arc<Client>, backend: Arc<Backend>) -> Self {
+		ShufflingProposer { inner, client, backend, _marker: PhantomData }
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block, Inner, Client, Backend> ProposerInterface<Block>
+	for ShufflingProposer<Block, Inner, Client, Backend>
+where
+	Block: BlockT,
+	Inner: ProposerInterface<Block> + Send,
+	Client: ProvideRuntimeApi<Block> + Send + Sync,
+	Client::Api: BlockBuilderApi<Block>,
+	Backend: sc_client_api::Backend<Block>,
+{
+	async fn propose(
+		&mut self,
+		parent_header: &Block::Header,
+		paras_inherent_data: &ParachainInherentData,
+		other_inherent_data: InherentData,
+		mut inherent_digests: Digest,
+		max_duration: Duration,
+		block_size_limit: Option<usize>,
+	) -> Result<Proposal<Block, StorageProof>, ProposerError> {
+		let deadline = Instant::now() + max_duration;
+		let seed = shuffling_seed(
+			&paras_inherent_data.validation_data.relay_parent_storage_root,
+			&parent_header.hash(),
+		);
+		inherent_digests.push(CumulusDigestItem::ShufflingSeed(seed).to_digest_item());
+
+		let proposal = self
+			.inner
+			.propose(
+				parent_header,
+				paras_inherent_data,
+				other_inherent_data,
+				inherent_digests.clone(),
+				max_duration / 2,
+				block_size_limit,
+			)
+			.await?;
+
+		let (_, extrinsics) = proposal.block.deconstruct();
+		let mut num_inherents =
+			extrinsics.iter().take_while(|xt| xt.is_signed() == Some(false)).count();
+		let mut extrinsics = shuffle_extrinsics(extrinsics, &seed);
+
+		// Every failed attempt drops one extrinsic, so this ends eventually. The shuffled order
+		// depends on the set of the extrinsics, so they are shuffled again after every drop.
+		loop {
+			if extrinsics.len() > num_inherents && Instant::now() >= deadline {
+				tracing::debug!(
+					target: crate::LOG_TARGET,
+					dropped = extrinsics.len() - num_inherents,
+					"Out of time for rebuilding the block, keeping the inherents only",
+				);
+				extrinsics.truncate(num_inherents);
+			}
+
+			match apply_extrinsics_with_proof_size(
+				&*self.client,
+				&*self.backend,
+				parent_header,
+				inherent_digests.clone(),
+				&extrinsics,
+			)? {
+				Ok(proposal) => return Ok(proposal),
+				Err(index) => {
+					tracing::debug!(
+						target: crate::LOG_TARGET,
+						index,
+						"Dropping extrinsic, that failed in the shuffled order",
+					);
+					extrinsics.remove(index);
+					if index < num_inherents {
+						num_inherents -= 1;
+					}
+					extrinsics = shuffle_extrinsics(extrinsics, &seed);
+				},
+			}
+		}
+	}
+}
+
+/// A block import, that verifies the order of the extrinsics of the imported blocks.
+///
+/// See [`verify_shuffling`].
+pub struct ShufflingBlockImport<Block, BI> {
+	inner: BI,
+	required: bool,
+	_marker: PhantomData<Block>,
+}
+
+impl<Block, BI> ShufflingBlockImport<Block, BI> {
+	/// Create a new [`ShufflingBlockImport`].
+	///
+	/// If the shuffling is `required`, blocks without the shuffling seed are rejected.
+	pub fn new(inner: BI, required: bool) -> Self {
+		ShufflingBlockImport { inner, required, _marker: PhantomData }
+	}
+}
+
+impl<Block, BI: Clone> Clone for ShufflingBlockImport<Block, BI> {
+	fn clone(&self) -> Self {
+		ShufflingBlockImport {
+			inner: self.inner.clone(),
+			required: self.required,
+			_marker: PhantomData,
+		}
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block, BI> BlockImport<Block> for ShufflingBlockImport<Block, BI>
+where
+	Block: BlockT,
+	BI: BlockImport<Block> + Send,
+{
+	type Error = ConsensusError;
+
+	async fn check_block(
+		&mut self,
+		block: BlockCheckParams<Block>,
+	) -> Result<ImportResult, Self::Error> {
+		self.inner
+			.check_block(block)
+			.await
+			.map_err(|e| ConsensusError::ClientImport(e.to_string()))
+	}
+
+	async fn import_block(
+		&mut self,
+		params: BlockImportParams<Block>,
+	) -> Result<ImportResult, Self::Error> {
+		// Blocks without the body, e.g. during the warp sync, can't be verified.
+		if let Some(ref body) = params.body {
+			verify_shuffling::<Block>(&params.header, body, self.required)
+				.map_err(|e| ConsensusError::ClientImport(e.into()))?;
+		}
+
+		self.inner
+			.import_block(params)
+			.await
+			.map_err(|e| ConsensusError::ClientImport(e.to_string()))
+	}
+}
+
+impl<Block, BI: ParachainBlockImportMarker> ParachainBlockImportMarker
+	for ShufflingBlockImport<Block, BI>
+{
+}
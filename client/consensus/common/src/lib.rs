@@ -45,6 +45,7 @@ use level_monitor::LevelMonitor;
 pub use level_monitor::{LevelLimit, MAX_LEAVES_PER_LEVEL_SENSIBLE_DEFAULT};
 
 pub mod import_queue;
+pub mod storage_proof_size;
 
 /// Provides the hash of validation code used for authoring/execution of blocks at a given
 /// hash.
@@ -114,6 +115,11 @@ impl<B: BlockT> ParachainConsensus<B> for Box<dyn ParachainConsensus<B> + Send +
 /// This is used to set `block_import_params.fork_choice` to `false` as long as the block origin is
 /// not `NetworkInitialSync`. The best block for parachains is determined by the relay chain.
 /// Meaning we will update the best block, as it is included by the relay-chain.
+///
+/// The import doesn't access the relay chain. The relay chain state, that a block depends on, is
+/// taken from the relay chain state proof in its parachain inherent. So blocks are also imported,
+/// if their relay parents are in a region, that the relay chain node has only synced the headers
+/// of or has not synced at all yet.
 pub struct ParachainBlockImport<Block: BlockT, BI, BE> {
 	inner: BI,
 	monitor: Option<SharedData<LevelMonitor<Block, BE>>>,
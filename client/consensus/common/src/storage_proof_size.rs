@@ -0,0 +1,138 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Import of the blocks, that have been built with the storage proof size exposed to the runtime.
+
+use cumulus_client_consensus_proposer::storage_proof_size::record_proof_with_size_ext;
+use sc_client_api::Backend;
+use sc_consensus::{
+	BlockCheckParams, BlockImport, BlockImportParams, ImportResult, StateAction, StorageChanges,
+};
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_core::traits::CallContext;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, One, Saturating};
+
+use std::{marker::PhantomData, sync::Arc};
+
+/// Block import, that executes the imported blocks with the storage proof size exposed to the
+/// runtime.
+///
+/// Blocks, that are built by the
+/// [`ProofSizeRecordingProposer`](cumulus_client_consensus_proposer::storage_proof_size::ProofSizeRecordingProposer),
+/// depend on the size of the storage proof, recorded while they were built. The block is
+/// executed with the storage proof recorded the same way and the resulting storage changes are
+/// passed to the `inner` block import, so it doesn't execute the block again. Blocks that come
+/// with their storage changes, like the blocks built by this node, are passed through.
+pub struct ProofSizeRecordingBlockImport<Block, BI, Client, BE> {
+	inner: BI,
+	client: Arc<Client>,
+	backend: Arc<BE>,
+	_marker: PhantomData<Block>,
+}
+
+impl<Block, BI, Client, BE> ProofSizeRecordingBlockImport<Block, BI, Client, BE> {
+	/// Create a new instance.
+	pub fn new(inner: BI, client: Arc<Client>, backend: Arc<BE>) -> Self {
+		ProofSizeRecordingBlockImport { inner, client, backend, _marker: PhantomData }
+	}
+}
+
+impl<Block, BI: Clone, Client, BE> Clone for ProofSizeRecordingBlockImport<Block, BI, Client, BE> {
+	fn clone(&self) -> Self {
+		ProofSizeRecordingBlockImport {
+			inner: self.inner.clone(),
+			client: self.client.clone(),
+			backend: self.backend.clone(),
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<Block, BI, Client, BE> ProofSizeRecordingBlockImport<Block, BI, Client, BE>
+where
+	Block: BlockT,
+	Client: ProvideRuntimeApi<Block>,
+	Client::Api: Core<Block>,
+	BE: Backend<Block>,
+{
+	/// Execute the block with the storage proof size exposed to the runtime.
+	fn execute_block(
+		&self,
+		header: &Block::Header,
+		body: &[Block::Extrinsic],
+	) -> Result<sp_api::StorageChanges<Block>, String> {
+		let parent_hash = *header.parent_hash();
+
+		let mut api = self.client.runtime_api();
+		api.set_call_context(CallContext::Onchain);
+		record_proof_with_size_ext(&mut *api);
+
+		api.execute_block(parent_hash, Block::new(header.clone(), body.to_vec()))
+			.map_err(|e| format!("Failed to execute block: {e}"))?;
+
+		let state = self.backend.state_at(parent_hash).map_err(|e| e.to_string())?;
+		let storage_changes = api.into_storage_changes(&state, parent_hash)?;
+		if storage_changes.transaction_storage_root != *header.state_root() {
+			return Err("Storage root of the executed block doesn't match the header".into())
+		}
+
+		Ok(storage_changes)
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block, BI, Client, BE> BlockImport<Block>
+	for ProofSizeRecordingBlockImport<Block, BI, Client, BE>
+where
+	Block: BlockT,
+	BI: BlockImport<Block, Error = sp_consensus::Error> + Send,
+	Client: ProvideRuntimeApi<Block> + Send + Sync,
+	Client::Api: Core<Block>,
+	BE: Backend<Block>,
+{
+	type Error = BI::Error;
+
+	async fn check_block(
+		&mut self,
+		block: BlockCheckParams<Block>,
+	) -> Result<ImportResult, Self::Error> {
+		self.inner.check_block(block).await
+	}
+
+	async fn import_block(
+		&mut self,
+		mut params: BlockImportParams<Block>,
+	) -> Result<ImportResult, Self::Error> {
+		let execute = match params.state_action {
+			StateAction::Execute => true,
+			StateAction::ExecuteIfPossible => self.backend.have_state_at(
+				*params.header.parent_hash(),
+				(*params.header.number()).saturating_sub(One::one()),
+			),
+			_ => false,
+		};
+
+		if let (true, Some(body)) = (execute, &params.body) {
+			let storage_changes = self
+				.execute_block(&params.header, body)
+				.map_err(sp_consensus::Error::ClientImport)?;
+			params.state_action =
+				StateAction::ApplyChanges(StorageChanges::Changes(storage_changes));
+		}
+
+		self.inner.import_block(params).await
+	}
+}
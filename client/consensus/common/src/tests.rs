@@ -21,8 +21,9 @@ use codec::Encode;
 use cumulus_client_pov_recovery::RecoveryKind;
 use cumulus_primitives_core::{relay_chain::BlockId, InboundDownwardMessage, InboundHrmpMessage};
 use cumulus_relay_chain_interface::{
-	CommittedCandidateReceipt, OccupiedCoreAssumption, OverseerHandle, PHeader, ParaId,
-	RelayChainInterface, RelayChainResult, SessionIndex, StorageValue, ValidatorId,
+	AsyncBackingParams, CommittedCandidateReceipt, CoreState, OccupiedCoreAssumption,
+	OverseerHandle, PHeader, ParaId, RelayChainInterface, RelayChainResult, RuntimeApiCall,
+	SessionIndex, StorageChange, StorageValue, ValidatorId,
 };
 use cumulus_test_client::{
 	runtime::{Block, Hash, Header},
@@ -138,6 +139,17 @@ impl RelayChainInterface for Relaychain {
 		unimplemented!("Not needed for test")
 	}
 
+	async fn availability_cores(
+		&self,
+		_: PHash,
+	) -> RelayChainResult<Vec<CoreState<PHash, polkadot_primitives::BlockNumber>>> {
+		unimplemented!("Not needed for test")
+	}
+
+	async fn async_backing_params(&self, _: PHash) -> RelayChainResult<AsyncBackingParams> {
+		unimplemented!("Not needed for test")
+	}
+
 	async fn import_notification_stream(
 		&self,
 	) -> RelayChainResult<Pin<Box<dyn Stream<Item = PHeader> + Send>>> {
@@ -163,10 +175,18 @@ impl RelayChainInterface for Relaychain {
 			.boxed())
 	}
 
+	async fn proven_finalized_block_hash(&self) -> RelayChainResult<PHash> {
+		unimplemented!("Not needed for test")
+	}
+
 	async fn is_major_syncing(&self) -> RelayChainResult<bool> {
 		Ok(false)
 	}
 
+	async fn peer_count(&self) -> RelayChainResult<Option<usize>> {
+		Ok(None)
+	}
+
 	fn overseer_handle(&self) -> RelayChainResult<OverseerHandle> {
 		unimplemented!("Not needed for test")
 	}
@@ -187,6 +207,21 @@ impl RelayChainInterface for Relaychain {
 		unimplemented!("Not needed for test")
 	}
 
+	async fn subscribe_storage(
+		&self,
+		_: Vec<Vec<u8>>,
+	) -> RelayChainResult<Pin<Box<dyn Stream<Item = StorageChange> + Send>>> {
+		unimplemented!("Not needed for test")
+	}
+
+	async fn call_runtime_api_batch(
+		&self,
+		_: PHash,
+		_: Vec<RuntimeApiCall>,
+	) -> RelayChainResult<Vec<Vec<u8>>> {
+		unimplemented!("Not needed for test")
+	}
+
 	async fn wait_for_block(&self, _: PHash) -> RelayChainResult<()> {
 		Ok(())
 	}
@@ -617,6 +652,34 @@ fn do_not_set_best_block_to_older_block() {
 	build_and_import_block(client, true);
 }
 
+/// The relay chain node may have only synced the headers of the relay chain region, that the relay
+/// parents of the imported blocks are in, e.g. after the gap sync. Their import must not depend on
+/// the relay parents being available.
+#[test]
+fn import_block_with_unavailable_relay_parent() {
+	sp_tracing::try_init_simple();
+
+	let backend = Arc::new(Backend::new_test(1000, 1));
+	let client = Arc::new(TestClientBuilder::with_backend(backend.clone()).build());
+	let mut para_import = ParachainBlockImport::new(client.clone(), backend);
+
+	// Nothing knows the relay parent of the block, only its state proof is available.
+	let validation_data =
+		PersistedValidationData { relay_parent_number: 1_000_000, ..Default::default() };
+	let mut block = client
+		.init_block_builder(Some(validation_data), sproof_with_best_parent(&client))
+		.build()
+		.unwrap()
+		.block;
+	block.header.digest.push(sp_runtime::DigestItem::Other(vec![1, 2, 3]));
+	let hash = block.hash();
+
+	import_block_sync(&mut para_import, block, BlockOrigin::NetworkInitialSync, true);
+
+	assert_eq!(client.block_status(hash).unwrap(), BlockStatus::InChainWithState);
+	assert_eq!(client.usage_info().chain.best_hash, hash);
+}
+
 #[test]
 fn prune_blocks_on_level_overflow() {
 	// Here we are using the timestamp value to generate blocks with different hashes.
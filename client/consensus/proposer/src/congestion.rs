@@ -0,0 +1,344 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Adjustment of the block limits to the congestion of the relay chain.
+//!
+//! A backed candidate normally becomes available in the next relay-chain block. When the
+//! validators are overloaded, e.g. by the distribution of large PoVs, the candidates stay pending
+//! availability for longer and large PoVs are more likely to time out. Cores, that are merely
+//! occupied, don't indicate this, as every core with a candidate is occupied for at least one
+//! block.
+//!
+//! The [`CongestionMonitor`] follows the best relay-chain blocks and measures the share of the
+//! availability cores, whose candidates have been pending availability for longer than one
+//! relay-chain block. The [`CongestionAwareProposer`] reads the measured congestion and shrinks
+//! the block size limit and the proposal duration (which is the soft weight limit of the block) of
+//! the wrapped proposer, so collators produce smaller blocks while the relay chain is congested.
+
+use crate::{Error, ProposerInterface};
+
+use async_trait::async_trait;
+use cumulus_primitives_parachain_inherent::ParachainInherentData;
+use cumulus_relay_chain_interface::{CoreState, RelayChainInterface};
+use futures::StreamExt;
+use sp_consensus::Proposal;
+use sp_inherents::InherentData;
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT},
+	Digest, Permill,
+};
+use sp_state_machine::StorageProof;
+
+use std::{
+	sync::{
+		atomic::{AtomicU32, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+const LOG_TARGET: &str = "cumulus-congestion";
+
+/// The congestion of the relay chain, as measured by the [`CongestionMonitor`].
+///
+/// This is cheap to clone; all clones share the same measurement.
+#[derive(Clone, Default)]
+pub struct Congestion(Arc<AtomicU32>);
+
+impl Congestion {
+	/// The share of the availability cores, whose candidates have been pending availability for
+	/// longer than one relay-chain block at the latest best relay-chain block.
+	pub fn delayed_availability(&self) -> Permill {
+		Permill::from_parts(self.0.load(Ordering::Relaxed))
+	}
+
+	fn set_delayed_availability(&self, delayed: Permill) {
+		self.0.store(delayed.deconstruct(), Ordering::Relaxed)
+	}
+}
+
+/// Returns the share of the `num_cores` availability cores, whose candidates have been pending
+/// availability for longer than one relay-chain block at the block `relay_parent_number`.
+///
+/// `occupied_since` are the numbers of the blocks, in which the candidates pending availability
+/// have been backed.
+fn delayed_availability(
+	occupied_since: impl Iterator<Item = u32>,
+	num_cores: usize,
+	relay_parent_number: u32,
+) -> Permill {
+	// The candidate, that has been backed in the block `n`, is normally made available by the
+	// bitfields of the block `n + 1`.
+	let delayed = occupied_since
+		.filter(|since| relay_parent_number > since.saturating_add(1))
+		.count();
+	Permill::from_rational(delayed as u32, num_cores.max(1) as u32)
+}
+
+/// Follows the best relay-chain blocks and measures the [`Congestion`] of the relay chain.
+pub struct CongestionMonitor<RClient> {
+	relay_client: RClient,
+	congestion: Congestion,
+}
+
+impl<RClient: RelayChainInterface> CongestionMonitor<RClient> {
+	/// Create a new [`CongestionMonitor`].
+	pub fn new(relay_client: RClient) -> Self {
+		CongestionMonitor { relay_client, congestion: Congestion::default() }
+	}
+
+	/// Returns the congestion, that is updated by this monitor.
+	pub fn congestion(&self) -> Congestion {
+		self.congestion.clone()
+	}
+
+	/// Run the monitor.
+	///
+	/// This should be spawned as a task. It only ends, if the best block notification stream of
+	/// the relay chain ends.
+	pub async fn run(self) {
+		let mut best_blocks = match self.relay_client.new_best_notification_stream().await {
+			Ok(stream) => stream,
+			Err(err) => {
+				tracing::error!(
+					target: LOG_TARGET,
+					?err,
+					"Failed to initialize the congestion monitor: no relay chain best block stream",
+				);
+				return
+			},
+		};
+
+		while let Some(header) = best_blocks.next().await {
+			let relay_parent = header.hash();
+			let cores = match self.relay_client.availability_cores(relay_parent).await {
+				Ok(cores) => cores,
+				Err(err) => {
+					tracing::debug!(
+						target: LOG_TARGET,
+						?relay_parent,
+						?err,
+						"Failed to fetch availability cores",
+					);
+					continue
+				},
+			};
+
+			let occupied_since = cores.iter().filter_map(|core| match core {
+				CoreState::Occupied(occupied) => Some(occupied.occupied_since),
+				_ => None,
+			});
+			let delayed = delayed_availability(occupied_since, cores.len(), *header.number());
+			tracing::trace!(
+				target: LOG_TARGET,
+				?relay_parent,
+				?delayed,
+				"Measured delayed availability",
+			);
+
+			self.congestion.set_delayed_availability(delayed);
+		}
+	}
+}
+
+/// Configuration of the [`CongestionAwareProposer`].
+#[derive(Clone, Copy, Debug)]
+pub struct CongestionConfig {
+	/// The share of the cores with delayed availability, above which the block limits are
+	/// reduced.
+	pub threshold: Permill,
+	/// The share of the block limits, that is used when the availability of all cores is
+	/// delayed.
+	///
+	/// The limits are reduced linearly from the full limits at the `threshold` down to this
+	/// share, when the availability of all cores is delayed.
+	pub min_limits: Permill,
+}
+
+impl Default for CongestionConfig {
+	fn default() -> Self {
+		CongestionConfig {
+			threshold: Permill::from_percent(10),
+			min_limits: Permill::from_percent(25),
+		}
+	}
+}
+
+impl CongestionConfig {
+	/// Returns the share of the block limits, that should be used at the given share of the
+	/// cores with delayed availability.
+	pub fn limits_at(&self, delayed: Permill) -> Permill {
+		if delayed <= self.threshold {
+			return Permill::one()
+		}
+
+		let congestion = Permill::from_rational(
+			(delayed - self.threshold).deconstruct(),
+			(Permill::one() - self.threshold).deconstruct(),
+		);
+		Permill::one() - congestion * (Permill::one() - self.min_limits)
+	}
+}
+
+/// A proposer, that reduces the block limits of the `inner` proposer while the relay chain is
+/// congested.
+pub struct CongestionAwareProposer<P> {
+	inner: P,
+	congestion: Congestion,
+	config: CongestionConfig,
+}
+
+impl<P> CongestionAwareProposer<P> {
+	/// Create a new [`CongestionAwareProposer`], that reads the `congestion` measured by a
+	/// [`CongestionMonitor`].
+	pub fn new(inner: P, congestion: Congestion, config: CongestionConfig) -> Self {
+		CongestionAwareProposer { inner, congestion, config }
+	}
+}
+
+#[async_trait]
+impl<B, P> ProposerInterface<B> for CongestionAwareProposer<P>
+where
+	B: BlockT,
+	P: ProposerInterface<B> + Send,
+{
+	async fn propose(
+		&mut self,
+		parent_header: &B::Header,
+		paras_inherent_data: &ParachainInherentData,
+		other_inherent_data: InherentData,
+		inherent_digests: Digest,
+		max_duration: Duration,
+		block_size_limit: Option<usize>,
+	) -> Result<Proposal<B, StorageProof>, Error> {
+		let delayed = self.congestion.delayed_availability();
+		let limits = self.config.limits_at(delayed);
+		if limits < Permill::one() {
+			tracing::debug!(
+				target: LOG_TARGET,
+				?delayed,
+				?limits,
+				"Relay chain is congested, reducing the block limits",
+			);
+		}
+
+		let max_duration = Duration::from_micros(limits * max_duration.as_micros() as u64);
+		let block_size_limit = block_size_limit.map(|limit| limits * limit);
+
+		self.inner
+			.propose(
+				parent_header,
+				paras_inherent_data,
+				other_inherent_data,
+				inherent_digests,
+				max_duration,
+				block_size_limit,
+			)
+			.await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::testing::{Block, ExtrinsicWrapper, Header};
+
+	type TestBlock = Block<ExtrinsicWrapper<u64>>;
+
+	#[test]
+	fn only_candidates_pending_for_more_than_a_block_are_delayed() {
+		// the candidates backed in the blocks 10 and 9 can still become available in time
+		assert_eq!(
+			delayed_availability([10, 9, 8, 7].into_iter(), 10, 10),
+			Permill::from_percent(20)
+		);
+		assert_eq!(delayed_availability(std::iter::empty(), 10, 10), Permill::zero());
+		assert_eq!(delayed_availability(std::iter::empty(), 0, 10), Permill::zero());
+	}
+
+	#[test]
+	fn limits_are_only_reduced_above_the_threshold() {
+		let config = CongestionConfig {
+			threshold: Permill::from_percent(20),
+			min_limits: Permill::from_percent(20),
+		};
+
+		assert_eq!(config.limits_at(Permill::zero()), Permill::one());
+		assert_eq!(config.limits_at(Permill::from_percent(20)), Permill::one());
+		assert_eq!(config.limits_at(Permill::from_percent(60)), Permill::from_percent(60));
+		assert_eq!(config.limits_at(Permill::one()), Permill::from_percent(20));
+	}
+
+	struct LimitsRecorder(Option<(Duration, Option<usize>)>);
+
+	#[async_trait]
+	impl ProposerInterface<TestBlock> for LimitsRecorder {
+		async fn propose(
+			&mut self,
+			_: &Header,
+			_: &ParachainInherentData,
+			_: InherentData,
+			_: Digest,
+			max_duration: Duration,
+			block_size_limit: Option<usize>,
+		) -> Result<Proposal<TestBlock, StorageProof>, Error> {
+			self.0 = Some((max_duration, block_size_limit));
+			Err(Error::proposing(anyhow::anyhow!("only the limits are recorded")))
+		}
+	}
+
+	#[test]
+	fn proposer_reduces_the_limits_while_congested() {
+		let congestion = Congestion::default();
+		let mut proposer = CongestionAwareProposer::new(
+			LimitsRecorder(None),
+			congestion.clone(),
+			CongestionConfig {
+				threshold: Permill::from_percent(20),
+				min_limits: Permill::from_percent(20),
+			},
+		);
+		let parent_header = <Header as HeaderT>::new(
+			1,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+		let paras_inherent_data = ParachainInherentData {
+			validation_data: Default::default(),
+			relay_chain_state: StorageProof::empty(),
+			downward_messages: Default::default(),
+			horizontal_messages: Default::default(),
+		};
+		let mut propose = |proposer: &mut CongestionAwareProposer<LimitsRecorder>| {
+			let _ = futures::executor::block_on(proposer.propose(
+				&parent_header,
+				&paras_inherent_data,
+				Default::default(),
+				Default::default(),
+				Duration::from_millis(500),
+				Some(1000),
+			));
+			proposer.inner.0.take().unwrap()
+		};
+
+		assert_eq!(propose(&mut proposer), (Duration::from_millis(500), Some(1000)));
+
+		congestion.set_delayed_availability(Permill::from_percent(60));
+		assert_eq!(propose(&mut proposer), (Duration::from_millis(300), Some(600)));
+	}
+}
@@ -29,6 +29,9 @@ use sp_state_machine::StorageProof;
 
 use std::{fmt::Debug, time::Duration};
 
+pub mod congestion;
+pub mod storage_proof_size;
+
 /// Errors that can occur when proposing a parachain block.
 #[derive(thiserror::Error, Debug)]
 #[error(transparent)]
@@ -0,0 +1,183 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Exposure of the storage proof size to the runtime while building blocks.
+//!
+//! The `StorageWeightReclaim` signed extension corrects the proof size part of the block weight
+//! with the size of the storage proof, that has actually been recorded while dispatching the
+//! extrinsic. The runtime reads the size from the [`ProofSizeExt`], which the Substrate block
+//! builder doesn't register. The [`ProofSizeRecordingProposer`] therefore applies the extrinsics,
+//! that have been selected by the wrapped proposer, once again with a runtime API instance, that
+//! records the storage proof and exposes its size with the [`ProofSizeExt`].
+//!
+//! Nodes, that import such blocks, have to execute them with the [`ProofSizeExt`] too, otherwise
+//! they end up with a different state root.
+
+use crate::{Error, ProposerInterface};
+
+use async_trait::async_trait;
+use cumulus_primitives_parachain_inherent::ParachainInherentData;
+use cumulus_primitives_storage_weight_reclaim::ProofSizeExt;
+use sc_client_api::Backend;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder as BlockBuilderApi;
+use sp_consensus::Proposal;
+use sp_core::traits::CallContext;
+use sp_inherents::InherentData;
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, One},
+	Digest,
+};
+use sp_state_machine::StorageProof;
+
+use std::{marker::PhantomData, sync::Arc, time::Duration};
+
+const LOG_TARGET: &str = "cumulus-proof-size";
+
+/// Enable the recording of the storage proof on the given runtime API instance and expose the
+/// size of the recorded proof to the runtime with the [`ProofSizeExt`].
+pub fn record_proof_with_size_ext<Block: BlockT, Api: ApiExt<Block>>(api: &mut Api) {
+	api.record_proof();
+	let recorder = api.proof_recorder().expect("Proof recording has been enabled above; qed");
+	api.register_extension(ProofSizeExt::new(recorder));
+}
+
+/// A proposer, that applies the extrinsics of the `inner` proposal once again with the storage
+/// proof size exposed to the runtime.
+///
+/// Since the extrinsics are applied twice, proposing takes longer than with the `inner`
+/// proposer. Extrinsics, that can't be applied once the actual proof sizes are accounted for, are
+/// dropped from the block.
+pub struct ProofSizeRecordingProposer<Block, P, Client, BE> {
+	inner: P,
+	client: Arc<Client>,
+	backend: Arc<BE>,
+	_marker: PhantomData<Block>,
+}
+
+impl<Block, P, Client, BE> ProofSizeRecordingProposer<Block, P, Client, BE> {
+	/// Create a new [`ProofSizeRecordingProposer`].
+	pub fn new(inner: P, client: Arc<Client>, backend: Arc<BE>) -> Self {
+		ProofSizeRecordingProposer { inner, client, backend, _marker: PhantomData }
+	}
+}
+
+/// Apply the `extrinsics` on top of the `parent_header`, with the storage proof size exposed to
+/// the runtime.
+///
+/// Returns the index of the first extrinsic, that can't be applied, if there is any.
+pub fn apply_extrinsics_with_proof_size<Block, Client, BE>(
+	client: &Client,
+	backend: &BE,
+	parent_header: &Block::Header,
+	inherent_digests: Digest,
+	extrinsics: &[Block::Extrinsic],
+) -> Result<Result<Proposal<Block, StorageProof>, usize>, Error>
+where
+	Block: BlockT,
+	Client: ProvideRuntimeApi<Block>,
+	Client::Api: BlockBuilderApi<Block>,
+	BE: Backend<Block>,
+{
+	let parent_hash = parent_header.hash();
+	let header = <Block::Header as HeaderT>::new(
+		*parent_header.number() + One::one(),
+		Default::default(),
+		Default::default(),
+		parent_hash,
+		inherent_digests,
+	);
+
+	let mut api = client.runtime_api();
+	api.set_call_context(CallContext::Onchain);
+	record_proof_with_size_ext(&mut *api);
+
+	api.initialize_block(parent_hash, &header).map_err(Error::proposing)?;
+	for (index, extrinsic) in extrinsics.iter().enumerate() {
+		if let Err(error) =
+			api.apply_extrinsic(parent_hash, extrinsic.clone()).map_err(Error::proposing)?
+		{
+			tracing::debug!(
+				target: LOG_TARGET,
+				?error,
+				index,
+				"Extrinsic can't be applied with the recorded storage proof size",
+			);
+			return Ok(Err(index))
+		}
+	}
+	let header = api.finalize_block(parent_hash).map_err(Error::proposing)?;
+
+	let proof = api.extract_proof().expect("Proof recording has been enabled above; qed");
+	let state = backend.state_at(parent_hash).map_err(Error::proposing)?;
+	let storage_changes = api
+		.into_storage_changes(&state, parent_hash)
+		.map_err(|e| Error::proposing(anyhow::anyhow!(e)))?;
+
+	Ok(Ok(Proposal { block: Block::new(header, extrinsics.to_vec()), proof, storage_changes }))
+}
+
+#[async_trait]
+impl<Block, P, Client, BE> ProposerInterface<Block>
+	for ProofSizeRecordingProposer<Block, P, Client, BE>
+where
+	Block: BlockT,
+	P: ProposerInterface<Block> + Send,
+	Client: ProvideRuntimeApi<Block> + Send + Sync,
+	Client::Api: BlockBuilderApi<Block>,
+	BE: Backend<Block>,
+{
+	async fn propose(
+		&mut self,
+		parent_header: &Block::Header,
+		paras_inherent_data: &ParachainInherentData,
+		other_inherent_data: InherentData,
+		inherent_digests: Digest,
+		max_duration: Duration,
+		block_size_limit: Option<usize>,
+	) -> Result<Proposal<Block, StorageProof>, Error> {
+		let proposal = self
+			.inner
+			.propose(
+				parent_header,
+				paras_inherent_data,
+				other_inherent_data,
+				inherent_digests.clone(),
+				max_duration,
+				block_size_limit,
+			)
+			.await?;
+
+		// Every failed attempt drops one extrinsic, so this ends eventually. Applying the
+		// extrinsics with a fresh runtime API instance ensures, that nothing is recorded for the
+		// dropped extrinsics.
+		let (_, mut extrinsics) = proposal.block.deconstruct();
+		loop {
+			match apply_extrinsics_with_proof_size(
+				&*self.client,
+				&*self.backend,
+				parent_header,
+				inherent_digests.clone(),
+				&extrinsics,
+			)? {
+				Ok(proposal) => return Ok(proposal),
+				Err(index) => {
+					extrinsics.remove(index);
+				},
+			}
+		}
+	}
+}
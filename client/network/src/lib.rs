@@ -30,12 +30,13 @@ use cumulus_relay_chain_interface::RelayChainInterface;
 use polkadot_node_primitives::{CollationSecondedSignal, Statement};
 use polkadot_parachain::primitives::HeadData;
 use polkadot_primitives::{
-	CandidateReceipt, CompactStatement, Hash as PHash, Id as ParaId, OccupiedCoreAssumption,
-	SigningContext, UncheckedSigned,
+	CandidateHash, CandidateReceipt, CompactStatement, Hash as PHash, Id as ParaId,
+	OccupiedCoreAssumption, SigningContext, UncheckedSigned,
 };
 
 use codec::{Decode, DecodeAll, Encode};
-use futures::{channel::oneshot, future::FutureExt, Future};
+use futures::{channel::oneshot, future::FutureExt, Future, StreamExt};
+use sp_blockchain::HeaderBackend;
 use std::{convert::TryFrom, fmt, marker::PhantomData, pin::Pin, sync::Arc};
 
 #[cfg(test)]
@@ -168,6 +169,178 @@ impl BlockAnnounceData {
 	}
 }
 
+/// The compact data that we attach to a block announcement.
+///
+/// Instead of the seconded statement, it points to the relay chain block in which the candidate is
+/// pending availability. As only backed candidates are pending availability, this proves the same
+/// as the statement of [`BlockAnnounceData`], but it is much smaller.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+pub struct CompactBlockAnnounceData {
+	/// The hash of the candidate.
+	candidate_hash: CandidateHash,
+	/// The relay chain block in which the candidate is pending availability.
+	relay_block: PHash,
+}
+
+impl CompactBlockAnnounceData {
+	/// Create a new [`CompactBlockAnnounceData`] for the candidate with the given `candidate_hash`,
+	/// that is pending availability in the relay chain block `relay_block`.
+	pub fn new(candidate_hash: CandidateHash, relay_block: PHash) -> Self {
+		Self { candidate_hash, relay_block }
+	}
+
+	/// Check that the candidate is pending availability in the relay chain block and that it
+	/// matches the announced header.
+	async fn check_inclusion<RCInterface>(
+		self,
+		encoded_header: Vec<u8>,
+		relay_chain_client: &RCInterface,
+		para_id: ParaId,
+	) -> Result<Validation, BlockAnnounceError>
+	where
+		RCInterface: RelayChainInterface + 'static,
+	{
+		relay_chain_client
+			.wait_for_block(self.relay_block)
+			.await
+			.map_err(|e| BlockAnnounceError(e.to_string()))?;
+
+		let receipt = match relay_chain_client
+			.candidate_pending_availability(self.relay_block, para_id)
+			.await
+		{
+			Ok(Some(receipt)) => receipt,
+			Ok(None) => {
+				tracing::debug!(
+					target: LOG_TARGET,
+					relay_block = ?self.relay_block,
+					"No candidate pending availability in the relay chain block of the compact block announcement",
+				);
+				return Ok(Validation::Failure { disconnect: true })
+			},
+			Err(e) => return Err(BlockAnnounceError(format!("{:?}", e))),
+		};
+
+		if receipt.hash() != self.candidate_hash {
+			tracing::debug!(
+				target: LOG_TARGET,
+				"Candidate pending availability doesn't match the candidate hash in the compact block announcement",
+			);
+			return Ok(Validation::Failure { disconnect: true })
+		}
+
+		if HeadData(encoded_header).hash() != receipt.descriptor.para_head {
+			tracing::debug!(
+				target: LOG_TARGET,
+				"Receipt para head hash doesn't match the hash of the header in the compact block announcement",
+			);
+			return Ok(Validation::Failure { disconnect: true })
+		}
+
+		Ok(Validation::Success { is_new_best: true })
+	}
+}
+
+/// Returns the hash of the parachain block and the [`CompactBlockAnnounceData`] for the candidate
+/// of the parachain `para_id`, that is pending availability in the relay chain block `relay_block`.
+async fn compact_block_announcement<Block, RCInterface>(
+	relay_chain_interface: &RCInterface,
+	para_id: ParaId,
+	relay_block: PHash,
+) -> Option<(Block::Hash, CompactBlockAnnounceData)>
+where
+	Block: BlockT,
+	RCInterface: RelayChainInterface,
+{
+	let receipt =
+		match relay_chain_interface.candidate_pending_availability(relay_block, para_id).await {
+			Ok(receipt) => receipt?,
+			Err(error) => {
+				tracing::debug!(
+					target: LOG_TARGET,
+					?relay_block,
+					?error,
+					"Failed to fetch the candidate pending availability",
+				);
+				return None
+			},
+		};
+
+	let header = match Block::Header::decode(&mut &receipt.commitments.head_data.0[..]) {
+		Ok(header) => header,
+		Err(error) => {
+			tracing::debug!(
+				target: LOG_TARGET,
+				?relay_block,
+				?error,
+				"Failed to decode the header of the candidate pending availability",
+			);
+			return None
+		},
+	};
+
+	Some((header.hash(), CompactBlockAnnounceData::new(receipt.hash(), relay_block)))
+}
+
+/// Announce the blocks of the parachain `para_id` with a [`CompactBlockAnnounceData`], as soon as
+/// their candidates are pending availability on the relay chain.
+///
+/// Only the blocks, that are known to the `client`, are announced, as the peers request the
+/// announced blocks from us. Every candidate is announced once. This should be spawned as a task,
+/// it only ends if the import notification stream of the relay chain ends.
+pub async fn announce_backed_candidates<Block, Client, RCInterface>(
+	client: Arc<Client>,
+	relay_chain_interface: RCInterface,
+	para_id: ParaId,
+	announce_block: Arc<dyn Fn(Block::Hash, Option<Vec<u8>>) + Send + Sync>,
+) where
+	Block: BlockT,
+	Client: HeaderBackend<Block>,
+	RCInterface: RelayChainInterface,
+{
+	let mut imported_blocks = match relay_chain_interface.import_notification_stream().await {
+		Ok(stream) => stream,
+		Err(error) => {
+			tracing::error!(
+				target: LOG_TARGET,
+				?error,
+				"Failed to announce the backed candidates: no relay chain import stream",
+			);
+			return
+		},
+	};
+
+	let mut last_announced = None;
+	while let Some(relay_header) = imported_blocks.next().await {
+		let Some((block_hash, data)) = compact_block_announcement::<Block, _>(
+			&relay_chain_interface,
+			para_id,
+			relay_header.hash(),
+		)
+		.await
+		else {
+			continue
+		};
+
+		if last_announced == Some(data.candidate_hash) {
+			continue
+		}
+
+		if !matches!(client.header(block_hash), Ok(Some(_))) {
+			tracing::trace!(
+				target: LOG_TARGET,
+				?block_hash,
+				"Not announcing the unknown block of the candidate pending availability",
+			);
+			continue
+		}
+
+		tracing::debug!(target: LOG_TARGET, ?block_hash, "Announcing backed block");
+		last_announced = Some(data.candidate_hash);
+		announce_block(block_hash, Some(data.encode()));
+	}
+}
+
 impl TryFrom<&'_ CollationSecondedSignal> for BlockAnnounceData {
 	type Error = ();
 
@@ -218,6 +391,10 @@ pub type BlockAnnounceValidator<Block, RCInterface> =
 /// statement is checked to be a [`CompactStatement::Seconded`] and that it is signed by an active
 /// parachain validator.
 ///
+/// Alternatively, the attached data can be a SCALE encoded [`CompactBlockAnnounceData`]. Then it is
+/// checked that the referenced candidate is pending availability in the given relay chain block
+/// and that it belongs to the announced header.
+///
 /// If no justification was provided we check if the block announcement is at the tip of the known
 /// chain. If it is at the tip, it is required to provide a justification or otherwise we reject
 /// it. However, if the announcement is for a block below the tip the announcement is accepted
@@ -338,6 +515,7 @@ where
 		let header = header.clone();
 		let header_encoded = header.encode();
 		let block_announce_validator = self.clone();
+		let para_id = self.para_id;
 
 		async move {
 			let relay_chain_is_syncing = relay_chain_interface
@@ -358,11 +536,18 @@ where
 
 			let block_announce_data = match BlockAnnounceData::decode_all(&mut data.as_slice()) {
 				Ok(r) => r,
-				Err(err) =>
-					return Err(Box::new(BlockAnnounceError(format!(
-						"Can not decode the `BlockAnnounceData`: {:?}",
-						err
-					))) as Box<_>),
+				Err(err) => match CompactBlockAnnounceData::decode_all(&mut data.as_slice()) {
+					Ok(compact) =>
+						return compact
+							.check_inclusion(header_encoded, &relay_chain_interface, para_id)
+							.await
+							.map_err(|e| Box::new(e) as Box<_>),
+					Err(_) =>
+						return Err(Box::new(BlockAnnounceError(format!(
+							"Can not decode the `BlockAnnounceData`: {:?}",
+							err
+						))) as Box<_>),
+				},
 			};
 
 			if let Err(e) = block_announce_data.validate(header_encoded) {
@@ -501,9 +686,12 @@ impl<Block: BlockT> BlockAnnounceValidatorT<Block> for AssumeSybilResistance {
 			} else if !allow_seconded_messages {
 				Validation::Failure { disconnect: false }
 			} else {
-				match BlockAnnounceData::decode_all(&mut data.as_slice()) {
-					Ok(_) => Validation::Success { is_new_best: false },
-					Err(_) => Validation::Failure { disconnect: true },
+				let is_full = BlockAnnounceData::decode_all(&mut data.as_slice()).is_ok();
+				let is_compact = CompactBlockAnnounceData::decode_all(&mut data.as_slice()).is_ok();
+				if is_full || is_compact {
+					Validation::Success { is_new_best: false }
+				} else {
+					Validation::Failure { disconnect: true }
 				}
 			})
 		}
@@ -19,16 +19,18 @@ use async_trait::async_trait;
 use cumulus_primitives_core::relay_chain::BlockId;
 use cumulus_relay_chain_inprocess_interface::{check_block_in_chain, BlockCheckStatus};
 use cumulus_relay_chain_interface::{
-	OverseerHandle, PHeader, ParaId, RelayChainError, RelayChainResult,
+	OverseerHandle, PHeader, ParaId, RelayChainError, RelayChainResult, RuntimeApiCall,
+	StorageChange,
 };
 use cumulus_test_service::runtime::{Block, Hash, Header};
 use futures::{executor::block_on, poll, task::Poll, FutureExt, Stream, StreamExt};
 use parking_lot::Mutex;
 use polkadot_node_primitives::{SignedFullStatement, Statement};
 use polkadot_primitives::{
-	CandidateCommitments, CandidateDescriptor, CollatorPair, CommittedCandidateReceipt,
-	Hash as PHash, HeadData, InboundDownwardMessage, InboundHrmpMessage, OccupiedCoreAssumption,
-	PersistedValidationData, SessionIndex, SigningContext, ValidationCodeHash, ValidatorId,
+	vstaging::AsyncBackingParams, BlockNumber, CandidateCommitments, CandidateDescriptor,
+	CollatorPair, CommittedCandidateReceipt, CoreState, Hash as PHash, HeadData,
+	InboundDownwardMessage, InboundHrmpMessage, OccupiedCoreAssumption, PersistedValidationData,
+	SessionIndex, SigningContext, ValidationCodeHash, ValidatorId,
 };
 use polkadot_test_client::{
 	Client as PClient, ClientBlockImportExt, DefaultTestClientBuilderExt, FullBackend as PBackend,
@@ -123,28 +125,7 @@ impl RelayChainInterface for DummyRelayChainInterface {
 		_: ParaId,
 	) -> RelayChainResult<Option<CommittedCandidateReceipt>> {
 		if self.data.lock().has_pending_availability {
-			Ok(Some(CommittedCandidateReceipt {
-				descriptor: CandidateDescriptor {
-					para_head: polkadot_parachain::primitives::HeadData(default_header().encode())
-						.hash(),
-					para_id: 0u32.into(),
-					relay_parent: PHash::random(),
-					collator: CollatorPair::generate().0.public(),
-					persisted_validation_data_hash: PHash::random(),
-					pov_hash: PHash::random(),
-					erasure_root: PHash::random(),
-					signature: sp_core::sr25519::Signature([0u8; 64]).into(),
-					validation_code_hash: ValidationCodeHash::from(PHash::random()),
-				},
-				commitments: CandidateCommitments {
-					upward_messages: Default::default(),
-					horizontal_messages: Default::default(),
-					new_validation_code: None,
-					head_data: HeadData(Vec::new()),
-					processed_downward_messages: 0,
-					hrmp_watermark: 0,
-				},
-			}))
+			Ok(Some(pending_candidate_receipt()))
 		} else {
 			Ok(None)
 		}
@@ -154,6 +135,17 @@ impl RelayChainInterface for DummyRelayChainInterface {
 		Ok(0)
 	}
 
+	async fn availability_cores(
+		&self,
+		_: PHash,
+	) -> RelayChainResult<Vec<CoreState<PHash, BlockNumber>>> {
+		unimplemented!("Not needed for test")
+	}
+
+	async fn async_backing_params(&self, _: PHash) -> RelayChainResult<AsyncBackingParams> {
+		unimplemented!("Not needed for test")
+	}
+
 	async fn import_notification_stream(
 		&self,
 	) -> RelayChainResult<Pin<Box<dyn Stream<Item = PHeader> + Send>>> {
@@ -174,10 +166,18 @@ impl RelayChainInterface for DummyRelayChainInterface {
 		))
 	}
 
+	async fn proven_finalized_block_hash(&self) -> RelayChainResult<PHash> {
+		unimplemented!("Not needed for test")
+	}
+
 	async fn is_major_syncing(&self) -> RelayChainResult<bool> {
 		Ok(false)
 	}
 
+	async fn peer_count(&self) -> RelayChainResult<Option<usize>> {
+		Ok(None)
+	}
+
 	fn overseer_handle(&self) -> RelayChainResult<OverseerHandle> {
 		unimplemented!("Not needed for test")
 	}
@@ -198,6 +198,21 @@ impl RelayChainInterface for DummyRelayChainInterface {
 		unimplemented!("Not needed for test")
 	}
 
+	async fn subscribe_storage(
+		&self,
+		_: Vec<Vec<u8>>,
+	) -> RelayChainResult<Pin<Box<dyn Stream<Item = StorageChange> + Send>>> {
+		unimplemented!("Not needed for test")
+	}
+
+	async fn call_runtime_api_batch(
+		&self,
+		_: PHash,
+		_: Vec<RuntimeApiCall>,
+	) -> RelayChainResult<Vec<Vec<u8>>> {
+		unimplemented!("Not needed for test")
+	}
+
 	async fn wait_for_block(&self, hash: PHash) -> RelayChainResult<()> {
 		let mut listener = match check_block_in_chain(
 			self.relay_backend.clone(),
@@ -266,6 +281,32 @@ fn make_validator_and_api() -> (
 	)
 }
 
+/// The receipt of the candidate, that is pending availability if
+/// [`ApiData::has_pending_availability`] is set.
+fn pending_candidate_receipt() -> CommittedCandidateReceipt {
+	CommittedCandidateReceipt {
+		descriptor: CandidateDescriptor {
+			para_head: polkadot_parachain::primitives::HeadData(default_header().encode()).hash(),
+			para_id: 0u32.into(),
+			relay_parent: PHash::repeat_byte(1),
+			collator: CollatorPair::from_seed(&[1u8; 32]).public(),
+			persisted_validation_data_hash: PHash::repeat_byte(2),
+			pov_hash: PHash::repeat_byte(3),
+			erasure_root: PHash::repeat_byte(4),
+			signature: sp_core::sr25519::Signature([0u8; 64]).into(),
+			validation_code_hash: ValidationCodeHash::from(PHash::repeat_byte(5)),
+		},
+		commitments: CandidateCommitments {
+			upward_messages: Default::default(),
+			horizontal_messages: Default::default(),
+			new_validation_code: None,
+			head_data: HeadData(default_header().encode()),
+			processed_downward_messages: 0,
+			hrmp_watermark: 0,
+		},
+	}
+}
+
 fn default_header() -> Header {
 	Header {
 		number: 1,
@@ -577,8 +618,83 @@ fn block_announced_without_statement_and_block_only_backed() {
 	});
 }
 
+#[test]
+fn compact_block_announce_is_valid_if_candidate_is_pending_availability() {
+	let (mut validator, api) = make_validator_and_api();
+	api.data.lock().has_pending_availability = true;
+
+	let relay_block = api.relay_client.hash(0).ok().flatten().expect("Genesis hash exists");
+	let data =
+		CompactBlockAnnounceData::new(pending_candidate_receipt().hash(), relay_block).encode();
+
+	let res = block_on(validator.validate(&default_header(), &data));
+	assert_eq!(Validation::Success { is_new_best: true }, res.unwrap());
+}
+
+#[test]
+fn compact_block_announce_fails_if_candidate_is_not_pending_availability() {
+	let (mut validator, api) = make_validator_and_api();
+
+	let relay_block = api.relay_client.hash(0).ok().flatten().expect("Genesis hash exists");
+	let data =
+		CompactBlockAnnounceData::new(pending_candidate_receipt().hash(), relay_block).encode();
+
+	let res = block_on(validator.validate(&default_header(), &data));
+	assert_eq!(Validation::Failure { disconnect: true }, res.unwrap());
+}
+
+#[test]
+fn compact_block_announce_fails_if_candidate_hash_does_not_match() {
+	let (mut validator, api) = make_validator_and_api();
+	api.data.lock().has_pending_availability = true;
+
+	let relay_block = api.relay_client.hash(0).ok().flatten().expect("Genesis hash exists");
+	let data = CompactBlockAnnounceData::new(CandidateHash(PHash::random()), relay_block).encode();
+
+	let res = block_on(validator.validate(&default_header(), &data));
+	assert_eq!(Validation::Failure { disconnect: true }, res.unwrap());
+}
+
+#[test]
+fn compact_block_announce_fails_if_header_does_not_match() {
+	let (mut validator, api) = make_validator_and_api();
+	api.data.lock().has_pending_availability = true;
+
+	let relay_block = api.relay_client.hash(0).ok().flatten().expect("Genesis hash exists");
+	let data =
+		CompactBlockAnnounceData::new(pending_candidate_receipt().hash(), relay_block).encode();
+	let header = Header { number: 300, ..default_header() };
+
+	let res = block_on(validator.validate(&header, &data));
+	assert_eq!(Validation::Failure { disconnect: true }, res.unwrap());
+}
+
 #[derive(Default)]
 struct ApiData {
 	validators: Vec<ValidatorId>,
 	has_pending_availability: bool,
 }
+
+#[test]
+fn compact_block_announcement_is_produced_for_candidate_pending_availability() {
+	let (mut validator, api) = make_validator_and_api();
+	let relay_block = api.relay_client.hash(0).ok().flatten().expect("Genesis hash exists");
+
+	let announcement =
+		block_on(compact_block_announcement::<Block, _>(&api, ParaId::from(56), relay_block));
+	assert!(announcement.is_none());
+
+	api.data.lock().has_pending_availability = true;
+	let (block_hash, data) =
+		block_on(compact_block_announcement::<Block, _>(&api, ParaId::from(56), relay_block))
+			.expect("Candidate is pending availability");
+	assert_eq!(block_hash, default_header().hash());
+	assert_eq!(
+		data,
+		CompactBlockAnnounceData::new(pending_candidate_receipt().hash(), relay_block)
+	);
+
+	// the produced announcement is accepted by the validator
+	let res = block_on(validator.validate(&default_header(), &data.encode()));
+	assert_eq!(Validation::Success { is_new_best: true }, res.unwrap());
+}
@@ -18,29 +18,77 @@ use sp_runtime::traits::Block as BlockT;
 
 use polkadot_node_primitives::AvailableData;
 use polkadot_node_subsystem::messages::AvailabilityRecoveryMessage;
+use polkadot_primitives::{CandidateReceipt, SessionIndex};
 
-use futures::{channel::oneshot, stream::FuturesUnordered, Future, FutureExt, StreamExt};
+use futures::{channel::oneshot, select, stream::FuturesUnordered, Future, FutureExt, StreamExt};
 
-use std::{collections::HashSet, pin::Pin};
+use std::{
+	collections::HashMap,
+	pin::Pin,
+	time::{Duration, Instant},
+};
 
-use crate::RecoveryHandle;
+use crate::{PeerRecovery, RecoveryHandle};
+
+/// The data of a recovered candidate.
+pub(crate) enum RecoveredData<Block> {
+	/// The block, that has been fetched from a parachain peer.
+	Block(Block),
+	/// The data, that has been recovered from the relay chain.
+	AvailableData(AvailableData),
+}
 
 /// The active candidate recovery.
 ///
 /// This handles the candidate recovery and tracks the activate recoveries.
+///
+/// The blocks of the candidates are first fetched from the parachain peers. Only if this fails,
+/// the PoVs are recovered from the relay chain.
 pub(crate) struct ActiveCandidateRecovery<Block: BlockT> {
-	/// The recoveries that are currently being executed.
+	/// The recoveries from the parachain peers that are currently being executed.
+	///
+	/// The candidate is returned together with the result to fall back to the recovery from the
+	/// relay chain.
+	peer_recoveries: FuturesUnordered<
+		Pin<
+			Box<
+				dyn Future<Output = (Block::Hash, CandidateReceipt, SessionIndex, Option<Block>)>
+					+ Send,
+			>,
+		>,
+	>,
+	/// The recoveries from the relay chain that are currently being executed.
 	recoveries: FuturesUnordered<
 		Pin<Box<dyn Future<Output = (Block::Hash, Option<AvailableData>)> + Send>>,
 	>,
-	/// The block hashes of the candidates currently being recovered.
-	candidates: HashSet<Block::Hash>,
+	/// The block hashes of the candidates currently being recovered and the start of their
+	/// recoveries.
+	candidates: HashMap<Block::Hash, Instant>,
 	recovery_handle: Box<dyn RecoveryHandle>,
+	peer_recovery: Option<PeerRecovery<Block>>,
+	/// The maximum number of recoveries, that are executed at the same time.
+	max_concurrent_recoveries: usize,
 }
 
 impl<Block: BlockT> ActiveCandidateRecovery<Block> {
-	pub fn new(recovery_handle: Box<dyn RecoveryHandle>) -> Self {
-		Self { recoveries: Default::default(), candidates: Default::default(), recovery_handle }
+	pub fn new(
+		recovery_handle: Box<dyn RecoveryHandle>,
+		peer_recovery: Option<PeerRecovery<Block>>,
+		max_concurrent_recoveries: usize,
+	) -> Self {
+		Self {
+			peer_recoveries: Default::default(),
+			recoveries: Default::default(),
+			candidates: Default::default(),
+			recovery_handle,
+			peer_recovery,
+			max_concurrent_recoveries: max_concurrent_recoveries.max(1),
+		}
+	}
+
+	/// Returns `true` if another recovery can be started.
+	pub fn has_capacity(&self) -> bool {
+		self.peer_recoveries.len() + self.recoveries.len() < self.max_concurrent_recoveries
 	}
 
 	/// Recover the given `candidate`.
@@ -48,23 +96,50 @@ impl<Block: BlockT> ActiveCandidateRecovery<Block> {
 		&mut self,
 		block_hash: Block::Hash,
 		candidate: &crate::Candidate<Block>,
+	) {
+		self.candidates.insert(block_hash, Instant::now());
+
+		let peer_recovery = match self.peer_recovery.clone() {
+			Some(peer_recovery) => peer_recovery,
+			None => {
+				self.recover_from_relay_chain(
+					block_hash,
+					candidate.receipt.clone(),
+					candidate.session_index,
+				)
+				.await;
+				return
+			},
+		};
+
+		let receipt = candidate.receipt.clone();
+		let session_index = candidate.session_index;
+		let block_number = candidate.block_number;
+		self.peer_recoveries.push(
+			async move {
+				let block = peer_recovery.fetch_block(block_hash, block_number).await;
+				(block_hash, receipt, session_index, block)
+			}
+			.boxed(),
+		);
+	}
+
+	/// Recover the PoV of the given candidate from the relay chain.
+	async fn recover_from_relay_chain(
+		&mut self,
+		block_hash: Block::Hash,
+		receipt: CandidateReceipt,
+		session_index: SessionIndex,
 	) {
 		let (tx, rx) = oneshot::channel();
 
 		self.recovery_handle
 			.send_recovery_msg(
-				AvailabilityRecoveryMessage::RecoverAvailableData(
-					candidate.receipt.clone(),
-					candidate.session_index,
-					None,
-					tx,
-				),
+				AvailabilityRecoveryMessage::RecoverAvailableData(receipt, session_index, None, tx),
 				"ActiveCandidateRecovery",
 			)
 			.await;
 
-		self.candidates.insert(block_hash);
-
 		self.recoveries.push(
 			async move {
 				match rx.await {
@@ -93,15 +168,41 @@ impl<Block: BlockT> ActiveCandidateRecovery<Block> {
 
 	/// Waits for the next recovery.
 	///
-	/// If the returned [`AvailableData`] is `None`, it means that the recovery failed.
-	pub async fn wait_for_recovery(&mut self) -> (Block::Hash, Option<AvailableData>) {
+	/// If the returned [`RecoveredData`] is `None`, it means that the recovery failed. The returned
+	/// duration is the time, that the recovery took.
+	pub async fn wait_for_recovery(
+		&mut self,
+	) -> (Block::Hash, Option<RecoveredData<Block>>, Duration) {
 		loop {
-			if let Some(res) = self.recoveries.next().await {
-				self.candidates.remove(&res.0);
-				return res
-			} else {
-				futures::pending!()
+			select! {
+				(block_hash, receipt, session_index, block) =
+					self.peer_recoveries.select_next_some() =>
+				{
+					if let Some(block) = block {
+						let elapsed = self.elapsed(&block_hash);
+						return (block_hash, Some(RecoveredData::Block(block)), elapsed)
+					}
+
+					tracing::debug!(
+						target: crate::LOG_TARGET,
+						?block_hash,
+						"Failed to fetch block from peers, recovering from the relay chain",
+					);
+					self.recover_from_relay_chain(block_hash, receipt, session_index).await;
+				},
+				(block_hash, available_data) = self.recoveries.select_next_some() => {
+					let elapsed = self.elapsed(&block_hash);
+					return (block_hash, available_data.map(RecoveredData::AvailableData), elapsed)
+				},
+				complete => futures::pending!(),
 			}
 		}
 	}
+	/// Stop tracking the recovery of the given candidate and return the time, that it took.
+	fn elapsed(&mut self, block_hash: &Block::Hash) -> Duration {
+		self.candidates
+			.remove(block_hash)
+			.map(|started| started.elapsed())
+			.unwrap_or_default()
+	}
 }
@@ -36,7 +36,11 @@
 //! 2. If between starting and firing the timer the block is imported, we skip the recovery of the
 //!    PoV.
 //!
-//! 3. If the timer fired we recover the PoV using the relay chain PoV recovery protocol.
+//! 3. If the timer fired we first try to fetch the block from the connected parachain peers. Only
+//!    if none of them returns the block, we recover the PoV using the relay chain PoV recovery
+//!    protocol, which is much more expensive for large PoVs. At most `max_concurrent_recoveries`
+//!    PoVs are recovered at the same time. If more candidates are ready to be recovered, the
+//!    candidate closest to the head of the chain is recovered first.
 //!
 //! 4a. After it is recovered, we restore the block and import it.
 //!
@@ -61,6 +65,8 @@ use polkadot_primitives::{
 use cumulus_primitives_core::ParachainBlockData;
 use cumulus_relay_chain_interface::{RelayChainInterface, RelayChainResult};
 
+use substrate_prometheus_endpoint::Registry;
+
 use codec::Decode;
 use futures::{
 	channel::mpsc::Receiver, select, stream::FuturesUnordered, Future, FutureExt, Stream, StreamExt,
@@ -71,15 +77,44 @@ use rand::{distributions::Uniform, prelude::Distribution, thread_rng};
 use std::{
 	collections::{HashMap, HashSet, VecDeque},
 	pin::Pin,
-	sync::Arc,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
 	time::Duration,
 };
 
 mod active_candidate_recovery;
-use active_candidate_recovery::ActiveCandidateRecovery;
+use active_candidate_recovery::{ActiveCandidateRecovery, RecoveredData};
+
+mod metrics;
+use metrics::Metrics;
+
+mod peer_recovery;
+pub use peer_recovery::{protocol_name, PeerRecovery, PeerRecoveryRequestHandler};
 
 const LOG_TARGET: &str = "cumulus-pov-recovery";
 
+/// The default maximum number of PoVs, that are recovered at the same time.
+pub const DEFAULT_MAX_CONCURRENT_RECOVERIES: usize = 4;
+
+/// The number of the candidates, that wait for their recovery.
+///
+/// The value is updated by the [`PoVRecovery`], clones share the same value.
+#[derive(Clone, Default, Debug)]
+pub struct RecoveryBacklog(Arc<AtomicUsize>);
+
+impl RecoveryBacklog {
+	/// Returns the number of the candidates, that wait for their recovery.
+	pub fn get(&self) -> usize {
+		self.0.load(Ordering::Relaxed)
+	}
+
+	fn set(&self, backlog: usize) {
+		self.0.store(backlog, Ordering::Relaxed);
+	}
+}
+
 /// Test-friendly wrapper trait for the overseer handle.
 /// Can be used to simulate failing recovery requests.
 #[async_trait::async_trait]
@@ -188,6 +223,11 @@ impl<Block: BlockT> RecoveryQueue<Block> {
 		);
 	}
 
+	/// Returns the number of the blocks in the queue.
+	pub fn len(&self) -> usize {
+		self.recovery_queue.len()
+	}
+
 	/// Get the next hash for block recovery.
 	pub async fn next_recovery(&mut self) -> Block::Hash {
 		loop {
@@ -217,6 +257,8 @@ pub struct PoVRecovery<Block: BlockT, PC, RC> {
 	/// available anymore in this map, it means that it was already imported.
 	candidate_recovery_queue: RecoveryQueue<Block>,
 	active_candidate_recovery: ActiveCandidateRecovery<Block>,
+	/// Candidates, whose recovery delay has passed, but that wait for a free recovery slot.
+	ready_to_recover: Vec<Block::Hash>,
 	/// Blocks that wait that the parent is imported.
 	///
 	/// Uses parent -> blocks mapping.
@@ -230,6 +272,8 @@ pub struct PoVRecovery<Block: BlockT, PC, RC> {
 	/// Blocks that we are retrying currently
 	candidates_in_retry: HashSet<Block::Hash>,
 	parachain_sync_service: Arc<dyn SyncOracle + Sync + Send>,
+	metrics: Option<Metrics>,
+	backlog: RecoveryBacklog,
 }
 
 impl<Block: BlockT, PC, RCInterface> PoVRecovery<Block, PC, RCInterface>
@@ -238,8 +282,11 @@ where
 	RCInterface: RelayChainInterface + Clone,
 {
 	/// Create a new instance.
+	///
+	/// If `peer_recovery` is `None`, the PoVs are only recovered from the relay chain.
 	pub fn new(
 		recovery_handle: Box<dyn RecoveryHandle>,
+		peer_recovery: Option<PeerRecovery<Block>>,
 		recovery_delay_range: RecoveryDelayRange,
 		parachain_client: Arc<PC>,
 		parachain_import_queue: Box<dyn ImportQueueService<Block>>,
@@ -247,11 +294,31 @@ where
 		para_id: ParaId,
 		recovery_chan_rx: Receiver<RecoveryRequest<Block>>,
 		parachain_sync_service: Arc<dyn SyncOracle + Sync + Send>,
+		max_concurrent_recoveries: usize,
+		prometheus_registry: Option<&Registry>,
+		backlog: RecoveryBacklog,
 	) -> Self {
+		let metrics = prometheus_registry.and_then(|registry| {
+			Metrics::register(registry)
+				.map_err(|error| {
+					tracing::warn!(
+						target: LOG_TARGET,
+						?error,
+						"Failed to register PoV recovery metrics",
+					)
+				})
+				.ok()
+		});
+
 		Self {
 			candidates: HashMap::new(),
 			candidate_recovery_queue: RecoveryQueue::new(recovery_delay_range),
-			active_candidate_recovery: ActiveCandidateRecovery::new(recovery_handle),
+			active_candidate_recovery: ActiveCandidateRecovery::new(
+				recovery_handle,
+				peer_recovery,
+				max_concurrent_recoveries,
+			),
+			ready_to_recover: Vec::new(),
 			waiting_for_parent: HashMap::new(),
 			parachain_client,
 			parachain_import_queue,
@@ -260,6 +327,8 @@ where
 			candidates_in_retry: HashSet::new(),
 			recovery_chan_rx,
 			parachain_sync_service,
+			metrics,
+			backlog,
 		}
 	}
 
@@ -326,14 +395,41 @@ where
 		match self.candidates.get(&block_hash) {
 			Some(candidate) if candidate.waiting_recovery => {
 				tracing::debug!(target: LOG_TARGET, ?block_hash, "Issuing recovery request");
+				if let Some(metrics) = &self.metrics {
+					metrics.on_recovery_started();
+				}
 				self.active_candidate_recovery.recover_candidate(block_hash, candidate).await;
 			},
 			_ => (),
 		}
 	}
 
-	/// Clear `waiting_for_parent` and `waiting_recovery` for the candidate with `hash`.
-	/// Also clears children blocks waiting for this parent.
+	/// Start the recoveries of the candidates in `ready_to_recover`, as long as there are free
+	/// recovery slots.
+	///
+	/// The candidates with the highest block numbers are recovered first, as recovering the head
+	/// of the chain also triggers the import of the already recovered ancestors.
+	async fn start_ready_recoveries(&mut self) {
+		while self.active_candidate_recovery.has_capacity() {
+			let next = self
+				.ready_to_recover
+				.iter()
+				.enumerate()
+				.max_by_key(|(_, hash)| self.candidates.get(hash).map(|c| c.block_number))
+				.map(|(index, _)| index);
+
+			match next {
+				Some(index) => {
+					let block_hash = self.ready_to_recover.swap_remove(index);
+					self.recover_candidate(block_hash).await;
+				},
+				None => break,
+			}
+		}
+	}
+
+	/// Clear `waiting_for_parent`, `ready_to_recover` and `waiting_recovery` for the candidate
+	/// with `hash`. Also clears children blocks waiting for this parent.
 	fn reset_candidate(&mut self, hash: Block::Hash) {
 		let mut blocks_to_delete = vec![hash];
 
@@ -341,6 +437,7 @@ where
 			if let Some(childs) = self.waiting_for_parent.remove(&delete) {
 				blocks_to_delete.extend(childs.iter().map(BlockT::hash));
 			}
+			self.ready_to_recover.retain(|ready| *ready != delete);
 		}
 		self.clear_waiting_recovery(&hash);
 	}
@@ -349,9 +446,9 @@ where
 	async fn handle_candidate_recovered(
 		&mut self,
 		block_hash: Block::Hash,
-		available_data: Option<AvailableData>,
+		recovered_data: Option<RecoveredData<Block>>,
 	) {
-		let available_data = match available_data {
+		let recovered_data = match recovered_data {
 			Some(data) => {
 				self.candidates_in_retry.remove(&block_hash);
 				data
@@ -373,35 +470,18 @@ where
 				},
 		};
 
-		let raw_block_data = match sp_maybe_compressed_blob::decompress(
-			&available_data.pov.block_data.0,
-			POV_BOMB_LIMIT,
-		) {
-			Ok(r) => r,
-			Err(error) => {
-				tracing::debug!(target: LOG_TARGET, ?error, "Failed to decompress PoV");
-
-				self.reset_candidate(block_hash);
-				return
-			},
-		};
-
-		let block_data = match ParachainBlockData::<Block>::decode(&mut &raw_block_data[..]) {
-			Ok(d) => d,
-			Err(error) => {
-				tracing::warn!(
-					target: LOG_TARGET,
-					?error,
-					"Failed to decode parachain block data from recovered PoV",
-				);
-
-				self.reset_candidate(block_hash);
-				return
-			},
+		let block = match recovered_data {
+			RecoveredData::Block(block) => block,
+			RecoveredData::AvailableData(available_data) =>
+				match decode_available_data::<Block>(&available_data) {
+					Some(block) => block,
+					None => {
+						self.reset_candidate(block_hash);
+						return
+					},
+				},
 		};
 
-		let block = block_data.into_block();
-
 		let parent = *block.header().parent_hash();
 
 		match self.parachain_client.block_status(parent) {
@@ -593,18 +673,55 @@ where
 					}
 				},
 				next_to_recover = self.candidate_recovery_queue.next_recovery().fuse() => {
-						self.recover_candidate(next_to_recover).await;
+						self.ready_to_recover.push(next_to_recover);
+						self.start_ready_recoveries().await;
 				},
-				(block_hash, available_data) =
+				(block_hash, recovered_data, elapsed) =
 					self.active_candidate_recovery.wait_for_recovery().fuse() =>
 				{
-					self.handle_candidate_recovered(block_hash, available_data).await;
+					if let Some(metrics) = &self.metrics {
+						metrics.on_recovery_finished(recovered_data.is_some(), elapsed);
+					}
+					self.handle_candidate_recovered(block_hash, recovered_data).await;
+					self.start_ready_recoveries().await;
 				},
 			}
+
+			let backlog = self.candidate_recovery_queue.len() + self.ready_to_recover.len();
+			self.backlog.set(backlog);
+			if let Some(metrics) = &self.metrics {
+				metrics.set_queue_length(backlog);
+			}
 		}
 	}
 }
 
+/// Decode the block from the PoV in the `available_data`.
+fn decode_available_data<Block: BlockT>(available_data: &AvailableData) -> Option<Block> {
+	let raw_block_data = match sp_maybe_compressed_blob::decompress(
+		&available_data.pov.block_data.0,
+		POV_BOMB_LIMIT,
+	) {
+		Ok(r) => r,
+		Err(error) => {
+			tracing::debug!(target: LOG_TARGET, ?error, "Failed to decompress PoV");
+			return None
+		},
+	};
+
+	match ParachainBlockData::<Block>::decode(&mut &raw_block_data[..]) {
+		Ok(block_data) => Some(block_data.into_block()),
+		Err(error) => {
+			tracing::warn!(
+				target: LOG_TARGET,
+				?error,
+				"Failed to decode parachain block data from recovered PoV",
+			);
+			None
+		},
+	}
+}
+
 /// Returns a stream over pending candidates for the parachain corresponding to `para_id`.
 async fn pending_candidates(
 	relay_chain_client: impl RelayChainInterface + Clone,
@@ -0,0 +1,98 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Prometheus metrics of the PoV recovery.
+
+use substrate_prometheus_endpoint::{
+	register, Counter, Gauge, Histogram, HistogramOpts, PrometheusError, Registry, U64,
+};
+
+use std::time::Duration;
+
+/// The metrics of the PoV recovery.
+pub(crate) struct Metrics {
+	recoveries_started: Counter<U64>,
+	recoveries_succeeded: Counter<U64>,
+	recoveries_failed: Counter<U64>,
+	time_to_recover: Histogram,
+	queue_length: Gauge<U64>,
+}
+
+impl Metrics {
+	/// Register the metrics at the given `registry`.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			recoveries_started: register(
+				Counter::new(
+					"cumulus_pov_recovery_recoveries_started",
+					"Number of the started PoV recoveries",
+				)?,
+				registry,
+			)?,
+			recoveries_succeeded: register(
+				Counter::new(
+					"cumulus_pov_recovery_recoveries_succeeded",
+					"Number of the PoV recoveries, that returned the data of the candidate",
+				)?,
+				registry,
+			)?,
+			recoveries_failed: register(
+				Counter::new(
+					"cumulus_pov_recovery_recoveries_failed",
+					"Number of the failed PoV recoveries",
+				)?,
+				registry,
+			)?,
+			time_to_recover: register(
+				Histogram::with_opts(
+					HistogramOpts::new(
+						"cumulus_pov_recovery_time_to_recover",
+						"Time in seconds from the start of a PoV recovery until it finished",
+					)
+					.buckets(vec![0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 40.0, 60.0, 120.0]),
+				)?,
+				registry,
+			)?,
+			queue_length: register(
+				Gauge::new(
+					"cumulus_pov_recovery_queue_length",
+					"Number of the candidates, that wait for the start of their recovery",
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	/// A recovery has been started.
+	pub fn on_recovery_started(&self) {
+		self.recoveries_started.inc();
+	}
+
+	/// A recovery finished after `elapsed`.
+	pub fn on_recovery_finished(&self, succeeded: bool, elapsed: Duration) {
+		if succeeded {
+			self.recoveries_succeeded.inc();
+		} else {
+			self.recoveries_failed.inc();
+		}
+		self.time_to_recover.observe(elapsed.as_secs_f64());
+	}
+
+	/// Set the number of the candidates, that wait for the start of their recovery.
+	pub fn set_queue_length(&self, queue_length: usize) {
+		self.queue_length.set(queue_length as u64);
+	}
+}
@@ -0,0 +1,305 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Recovery of the candidates from the parachain peers.
+//!
+//! Recovering a PoV from the relay chain requires to fetch and to decode the erasure chunks of a
+//! large number of validators. Before doing so, we ask the connected parachain peers for the block
+//! of the candidate. Every node answers these requests with the blocks from its database.
+//!
+//! The returned block is verified against the hash of the candidate's head data and the extrinsics
+//! root of the header, so a malicious peer can't make us import a different block.
+//!
+//! The blocks can be as large as the PoVs, so every peer may only request
+//! [`MAX_REQUESTS_PER_PEER`] blocks per [`RATE_LIMIT_PERIOD`]. Further requests are refused and
+//! reduce the reputation of the peer.
+
+use codec::{Decode, Encode};
+use futures::channel::oneshot;
+use polkadot_node_primitives::POV_BOMB_LIMIT;
+use sc_client_api::BlockBackend;
+use sc_network::{
+	request_responses::{IfDisconnected, IncomingRequest, OutgoingResponse, ProtocolConfig},
+	NetworkRequest, PeerId, ProtocolName, ReputationChange,
+};
+use sc_network_sync::SyncingService;
+use sp_runtime::{
+	traits::{Block as BlockT, Hash as HashT, Header as HeaderT, NumberFor},
+	StateVersion,
+};
+use std::{
+	collections::HashMap,
+	marker::PhantomData,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+use crate::LOG_TARGET;
+
+/// The maximum number of the requests, that are queued by the request handler.
+const MAX_QUEUED_REQUESTS: usize = 32;
+
+/// The maximum number of the peers, that are asked for the block of a candidate.
+const MAX_PEERS_TO_ASK: usize = 4;
+
+/// The timeout of a request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The maximum number of the requests, that are served to a peer per [`RATE_LIMIT_PERIOD`].
+pub const MAX_REQUESTS_PER_PEER: u32 = 8;
+
+/// The period, in which a peer may send up to [`MAX_REQUESTS_PER_PEER`] requests.
+pub const RATE_LIMIT_PERIOD: Duration = Duration::from_secs(6);
+
+/// The reputation change of a peer, that exceeds the rate limit.
+const RATE_LIMIT_EXCEEDED: ReputationChange =
+	ReputationChange::new(-(1 << 10), "PoV recovery: too many block requests");
+
+/// Returns the name of the request/response protocol for the chain with the given `genesis_hash`.
+pub fn protocol_name<Hash: AsRef<[u8]>>(genesis_hash: &Hash) -> ProtocolName {
+	format!("/{}/pov-recovery/1", hex::encode(genesis_hash)).into()
+}
+
+/// The request for the block of a candidate.
+#[derive(Encode, Decode, Debug)]
+struct BlockRequest<Hash> {
+	block_hash: Hash,
+}
+
+/// Limits the number of the requests, that are served to every peer.
+#[derive(Default)]
+struct RateLimiter {
+	/// The start of the current period and the number of the requests in it, per peer.
+	requests: HashMap<PeerId, (Instant, u32)>,
+}
+
+impl RateLimiter {
+	/// Returns `true` if the request of the `peer` at `now` is within the rate limit.
+	fn allow(&mut self, peer: PeerId, now: Instant) -> bool {
+		// Forget the peers with an elapsed period, so the map doesn't grow with every peer,
+		// that has ever sent a request.
+		if self.requests.len() > MAX_QUEUED_REQUESTS {
+			self.requests
+				.retain(|_, (start, _)| now.duration_since(*start) < RATE_LIMIT_PERIOD);
+		}
+
+		let (start, count) = self.requests.entry(peer).or_insert((now, 0));
+		if now.duration_since(*start) >= RATE_LIMIT_PERIOD {
+			*start = now;
+			*count = 0;
+		}
+
+		if *count >= MAX_REQUESTS_PER_PEER {
+			return false
+		}
+		*count += 1;
+		true
+	}
+}
+
+/// Handles the requests of the parachain peers for the blocks of the candidates.
+pub struct PeerRecoveryRequestHandler<Block, Client> {
+	request_receiver: async_channel::Receiver<IncomingRequest>,
+	client: Arc<Client>,
+	rate_limiter: RateLimiter,
+	_marker: PhantomData<Block>,
+}
+
+impl<Block, Client> PeerRecoveryRequestHandler<Block, Client>
+where
+	Block: BlockT,
+	Client: BlockBackend<Block> + Send + Sync + 'static,
+{
+	/// Create a new [`PeerRecoveryRequestHandler`] for the chain with the given `genesis_hash`.
+	///
+	/// The returned [`ProtocolConfig`] needs to be registered at the network.
+	pub fn new(client: Arc<Client>, genesis_hash: Block::Hash) -> (Self, ProtocolConfig) {
+		let (tx, request_receiver) = async_channel::bounded(MAX_QUEUED_REQUESTS);
+
+		let protocol_config = ProtocolConfig {
+			name: protocol_name(&genesis_hash),
+			fallback_names: Vec::new(),
+			max_request_size: 1024,
+			// The block can't be larger than the decompressed PoV.
+			max_response_size: POV_BOMB_LIMIT as u64,
+			request_timeout: REQUEST_TIMEOUT,
+			inbound_queue: Some(tx),
+		};
+
+		(
+			Self {
+				request_receiver,
+				client,
+				rate_limiter: RateLimiter::default(),
+				_marker: PhantomData,
+			},
+			protocol_config,
+		)
+	}
+
+	/// Run the request handler.
+	pub async fn run(mut self) {
+		while let Ok(IncomingRequest { peer, payload, pending_response }) =
+			self.request_receiver.recv().await
+		{
+			if !self.rate_limiter.allow(peer, Instant::now()) {
+				tracing::debug!(target: LOG_TARGET, ?peer, "Block request rate limit exceeded");
+				let response = OutgoingResponse {
+					result: Err(()),
+					reputation_changes: vec![RATE_LIMIT_EXCEEDED],
+					sent_feedback: None,
+				};
+				let _ = pending_response.send(response);
+				continue
+			}
+
+			let result = match BlockRequest::<Block::Hash>::decode(&mut &payload[..]) {
+				Ok(request) => Ok(self.block(request.block_hash).encode()),
+				Err(error) => {
+					tracing::debug!(target: LOG_TARGET, ?peer, ?error, "Invalid block request");
+					Err(())
+				},
+			};
+
+			let response =
+				OutgoingResponse { result, reputation_changes: Vec::new(), sent_feedback: None };
+			if pending_response.send(response).is_err() {
+				tracing::debug!(
+					target: LOG_TARGET,
+					?peer,
+					"Failed to send the response to the block request",
+				);
+			}
+		}
+	}
+
+	/// Returns the block with the given `block_hash` from the database.
+	fn block(&self, block_hash: Block::Hash) -> Option<Block> {
+		match self.client.block(block_hash) {
+			Ok(block) => block.map(|signed_block| signed_block.block),
+			Err(error) => {
+				tracing::debug!(
+					target: LOG_TARGET,
+					?error,
+					?block_hash,
+					"Failed to read the requested block",
+				);
+				None
+			},
+		}
+	}
+}
+
+/// Fetches the blocks of the candidates from the parachain peers.
+pub struct PeerRecovery<Block: BlockT> {
+	network: Arc<dyn NetworkRequest + Send + Sync>,
+	sync_service: Arc<SyncingService<Block>>,
+	protocol_name: ProtocolName,
+}
+
+impl<Block: BlockT> Clone for PeerRecovery<Block> {
+	fn clone(&self) -> Self {
+		Self {
+			network: self.network.clone(),
+			sync_service: self.sync_service.clone(),
+			protocol_name: self.protocol_name.clone(),
+		}
+	}
+}
+
+impl<Block: BlockT> PeerRecovery<Block> {
+	/// Create a new [`PeerRecovery`] for the chain with the given `genesis_hash`.
+	pub fn new(
+		network: Arc<dyn NetworkRequest + Send + Sync>,
+		sync_service: Arc<SyncingService<Block>>,
+		genesis_hash: Block::Hash,
+	) -> Self {
+		Self { network, sync_service, protocol_name: protocol_name(&genesis_hash) }
+	}
+
+	/// Fetch the block with the given `block_hash` and `block_number` from the peers.
+	///
+	/// Only the peers, whose best block is at least at `block_number`, are asked for the block.
+	/// Returns `None` if none of them returned a valid block.
+	pub(crate) async fn fetch_block(
+		&self,
+		block_hash: Block::Hash,
+		block_number: NumberFor<Block>,
+	) -> Option<Block> {
+		let peers = match self.sync_service.peers_info().await {
+			Ok(peers) => peers,
+			Err(oneshot::Canceled) => {
+				tracing::debug!(target: LOG_TARGET, "Failed to fetch the connected peers");
+				return None
+			},
+		};
+
+		let request = BlockRequest { block_hash }.encode();
+		let peers = peers
+			.into_iter()
+			.filter(|(_, info)| info.best_number >= block_number)
+			.map(|(peer, _)| peer)
+			.take(MAX_PEERS_TO_ASK);
+
+		for peer in peers {
+			let response = match self
+				.network
+				.request(
+					peer,
+					self.protocol_name.clone(),
+					request.clone(),
+					IfDisconnected::ImmediateError,
+				)
+				.await
+			{
+				Ok(response) => response,
+				Err(error) => {
+					tracing::debug!(target: LOG_TARGET, ?peer, ?error, "Block request failed");
+					continue
+				},
+			};
+
+			match Option::<Block>::decode(&mut &response[..]) {
+				Ok(Some(block)) if is_valid_block(&block, block_hash) => {
+					tracing::debug!(target: LOG_TARGET, ?peer, ?block_hash, "Fetched block from peer");
+					return Some(block)
+				},
+				Ok(Some(_)) => {
+					tracing::debug!(target: LOG_TARGET, ?peer, ?block_hash, "Peer returned invalid block")
+				},
+				Ok(None) => {
+					tracing::trace!(target: LOG_TARGET, ?peer, ?block_hash, "Peer doesn't know the block")
+				},
+				Err(error) => {
+					tracing::debug!(target: LOG_TARGET, ?peer, ?error, "Failed to decode block response")
+				},
+			}
+		}
+
+		None
+	}
+}
+
+/// Returns `true` if the `block` has the given `block_hash` and its body matches the header.
+fn is_valid_block<Block: BlockT>(block: &Block, block_hash: Block::Hash) -> bool {
+	let header = block.header();
+	let extrinsics_root = <<Block::Header as HeaderT>::Hashing as HashT>::ordered_trie_root(
+		block.extrinsics().iter().map(Encode::encode).collect(),
+		StateVersion::V0,
+	);
+
+	header.hash() == block_hash && *header.extrinsics_root() == extrinsics_root
+}
@@ -19,31 +19,38 @@ use std::{pin::Pin, sync::Arc, time::Duration};
 use async_trait::async_trait;
 use cumulus_primitives_core::{
 	relay_chain::{
-		runtime_api::ParachainHost, Block as PBlock, BlockId, CommittedCandidateReceipt,
-		Hash as PHash, Header as PHeader, InboundHrmpMessage, OccupiedCoreAssumption, SessionIndex,
-		ValidatorId,
+		runtime_api::ParachainHost, vstaging::AsyncBackingParams, Block as PBlock, BlockId,
+		BlockNumber, CommittedCandidateReceipt, CoreState, Hash as PHash, Header as PHeader,
+		InboundHrmpMessage, OccupiedCoreAssumption, SessionIndex, ValidatorId,
 	},
 	InboundDownwardMessage, ParaId, PersistedValidationData,
 };
-use cumulus_relay_chain_interface::{RelayChainError, RelayChainInterface, RelayChainResult};
+use cumulus_relay_chain_interface::{
+	RelayChainError, RelayChainInterface, RelayChainResult, RuntimeApiCall, StorageChange,
+};
 use futures::{FutureExt, Stream, StreamExt};
 use polkadot_service::{
 	CollatorPair, Configuration, FullBackend, FullClient, Handle, NewFull, TaskManager,
 };
 use sc_cli::SubstrateCli;
 use sc_client_api::{
-	blockchain::BlockStatus, Backend, BlockchainEvents, HeaderBackend, ImportNotifications,
-	StorageProof,
+	blockchain::BlockStatus, Backend, BlockchainEvents, CallExecutor, ExecutorProvider,
+	HeaderBackend, ImportNotifications, StorageProof,
 };
+use sc_network_sync::SyncingService;
 use sc_telemetry::TelemetryWorkerHandle;
 use sp_api::ProvideRuntimeApi;
 use sp_consensus::SyncOracle;
-use sp_core::{sp_std::collections::btree_map::BTreeMap, Pair};
+use sp_core::{
+	sp_std::collections::btree_map::BTreeMap, storage::StorageKey, traits::CallContext, Pair,
+};
 use sp_state_machine::{Backend as StateBackend, StorageValue};
 
 /// The timeout in seconds after that the waiting for a block should be aborted.
 const TIMEOUT_IN_SECONDS: u64 = 6;
 
+const LOG_TARGET: &str = "relay-chain-inprocess-interface";
+
 /// Provides an implementation of the [`RelayChainInterface`] using a local in-process relay chain
 /// node.
 #[derive(Clone)]
@@ -51,6 +58,7 @@ pub struct RelayChainInProcessInterface {
 	full_client: Arc<FullClient>,
 	backend: Arc<FullBackend>,
 	sync_oracle: Arc<dyn SyncOracle + Send + Sync>,
+	sync_service: Option<Arc<SyncingService<PBlock>>>,
 	overseer_handle: Handle,
 }
 
@@ -62,7 +70,28 @@ impl RelayChainInProcessInterface {
 		sync_oracle: Arc<dyn SyncOracle + Send + Sync>,
 		overseer_handle: Handle,
 	) -> Self {
-		Self { full_client, backend, sync_oracle, overseer_handle }
+		Self { full_client, backend, sync_oracle, sync_service: None, overseer_handle }
+	}
+
+	/// Report the peers of the given `sync_service` as the peers of the relay chain node.
+	pub fn with_sync_service(mut self, sync_service: Arc<SyncingService<PBlock>>) -> Self {
+		self.sync_service = Some(sync_service);
+		self
+	}
+
+	/// Returns the proven values of the `keys` at the relay chain block `relay_hash`.
+	async fn storage_change(
+		&self,
+		relay_hash: PHash,
+		keys: &[Vec<u8>],
+	) -> RelayChainResult<StorageChange> {
+		let header = self
+			.full_client
+			.header(relay_hash)?
+			.ok_or_else(|| RelayChainError::GenericError(format!("Unknown block {relay_hash}")))?;
+		let proof = self.prove_read(relay_hash, &keys.to_vec()).await?;
+
+		StorageChange::from_proof(&header, keys, proof)
 	}
 }
 
@@ -127,6 +156,17 @@ impl RelayChainInterface for RelayChainInProcessInterface {
 		Ok(self.full_client.runtime_api().session_index_for_child(hash)?)
 	}
 
+	async fn availability_cores(
+		&self,
+		hash: PHash,
+	) -> RelayChainResult<Vec<CoreState<PHash, BlockNumber>>> {
+		Ok(self.full_client.runtime_api().availability_cores(hash)?)
+	}
+
+	async fn async_backing_params(&self, hash: PHash) -> RelayChainResult<AsyncBackingParams> {
+		Ok(self.full_client.runtime_api().staging_async_backing_params(hash)?)
+	}
+
 	async fn validators(&self, hash: PHash) -> RelayChainResult<Vec<ValidatorId>> {
 		Ok(self.full_client.runtime_api().validators(hash)?)
 	}
@@ -159,10 +199,22 @@ impl RelayChainInterface for RelayChainInProcessInterface {
 		Ok(self.backend.blockchain().info().finalized_hash)
 	}
 
+	async fn proven_finalized_block_hash(&self) -> RelayChainResult<PHash> {
+		// The in-process node verifies the finality itself.
+		self.finalized_block_hash().await
+	}
+
 	async fn is_major_syncing(&self) -> RelayChainResult<bool> {
 		Ok(self.sync_oracle.is_major_syncing())
 	}
 
+	async fn peer_count(&self) -> RelayChainResult<Option<usize>> {
+		Ok(self
+			.sync_service
+			.as_ref()
+			.map(|sync_service| sync_service.num_connected_peers()))
+	}
+
 	fn overseer_handle(&self) -> RelayChainResult<Handle> {
 		Ok(self.overseer_handle.clone())
 	}
@@ -187,6 +239,56 @@ impl RelayChainInterface for RelayChainInProcessInterface {
 			.map_err(RelayChainError::StateMachineError)
 	}
 
+	async fn subscribe_storage(
+		&self,
+		keys: Vec<Vec<u8>>,
+	) -> RelayChainResult<Pin<Box<dyn Stream<Item = StorageChange> + Send>>> {
+		let storage_keys: Vec<StorageKey> = keys.iter().cloned().map(StorageKey).collect();
+		let notifications = self
+			.full_client
+			.storage_changes_notification_stream(Some(&storage_keys), None)?;
+
+		let interface = self.clone();
+		let changes = notifications.filter_map(move |notification| {
+			let interface = interface.clone();
+			let keys = keys.clone();
+			async move {
+				interface
+					.storage_change(notification.block, &keys)
+					.await
+					.map_err(|error| {
+						tracing::debug!(
+							target: LOG_TARGET,
+							?error,
+							relay_hash = ?notification.block,
+							"Failed to prove subscribed storage",
+						)
+					})
+					.ok()
+			}
+		});
+
+		Ok(Box::pin(changes))
+	}
+
+	async fn call_runtime_api_batch(
+		&self,
+		hash: PHash,
+		calls: Vec<RuntimeApiCall>,
+	) -> RelayChainResult<Vec<Vec<u8>>> {
+		// The calls are executed directly by the executor of the local node, which reuses the
+		// runtime instance of the block for all of them.
+		let executor = self.full_client.executor();
+		calls
+			.into_iter()
+			.map(|call| {
+				executor
+					.call(hash, &call.method, &call.payload, CallContext::Offchain)
+					.map_err(RelayChainError::BlockchainError)
+			})
+			.collect()
+	}
+
 	/// Wait for a given relay chain block in an async way.
 	///
 	/// The caller needs to pass the hash of a block it waits for and the function will return when
@@ -325,14 +427,17 @@ pub fn build_inprocess_relay_chain(
 	)
 	.map_err(|e| RelayChainError::Application(Box::new(e) as Box<_>))?;
 
-	let relay_chain_interface = Arc::new(RelayChainInProcessInterface::new(
-		full_node.client,
-		full_node.backend,
-		full_node.sync_service,
-		full_node.overseer_handle.clone().ok_or(RelayChainError::GenericError(
-			"Overseer not running in full node.".to_string(),
-		))?,
-	));
+	let relay_chain_interface = Arc::new(
+		RelayChainInProcessInterface::new(
+			full_node.client,
+			full_node.backend,
+			full_node.sync_service.clone(),
+			full_node.overseer_handle.clone().ok_or(RelayChainError::GenericError(
+				"Overseer not running in full node.".to_string(),
+			))?,
+		)
+		.with_sync_service(full_node.sync_service),
+	);
 
 	task_manager.add_child(full_node.task_manager);
 
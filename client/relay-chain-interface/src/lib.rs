@@ -14,7 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{collections::BTreeMap, pin::Pin, sync::Arc};
+use std::{
+	collections::{BTreeMap, VecDeque},
+	pin::Pin,
+	sync::Arc,
+};
 
 use polkadot_overseer::prometheus::PrometheusError;
 use sc_client_api::StorageProof;
@@ -23,14 +27,16 @@ use futures::Stream;
 
 use async_trait::async_trait;
 use jsonrpsee_core::Error as JsonRpcError;
-use parity_scale_codec::Error as CodecError;
+use parity_scale_codec::{Decode, Encode, Error as CodecError};
 use sp_api::ApiError;
+use sp_runtime::traits::{BlakeTwo256, Header as HeaderT};
 
-use cumulus_primitives_core::relay_chain::BlockId;
+use cumulus_primitives_core::relay_chain::{BlockId, BlockNumber};
 pub use cumulus_primitives_core::{
 	relay_chain::{
-		CommittedCandidateReceipt, Hash as PHash, Header as PHeader, InboundHrmpMessage,
-		OccupiedCoreAssumption, SessionIndex, ValidatorId,
+		vstaging::AsyncBackingParams, CommittedCandidateReceipt, CoreIndex, CoreState,
+		Hash as PHash, Header as PHeader, InboundHrmpMessage, OccupiedCoreAssumption, SessionIndex,
+		ValidatorId,
 	},
 	InboundDownwardMessage, ParaId, PersistedValidationData,
 };
@@ -95,6 +101,59 @@ impl<T: std::error::Error + Send + Sync + 'static> From<Box<T>> for RelayChainEr
 	}
 }
 
+/// The values of subscribed storage keys at a relay chain block.
+#[derive(Debug, Clone)]
+pub struct StorageChange {
+	/// The hash of the relay chain block.
+	pub relay_hash: PHash,
+	/// The subscribed keys and their values at the block.
+	pub values: Vec<(Vec<u8>, Option<StorageValue>)>,
+	/// The proof of the values against the state root of the block.
+	pub proof: StorageProof,
+}
+
+impl StorageChange {
+	/// Read the values of the `keys` from the `proof` of the relay chain block with the given
+	/// `header`.
+	///
+	/// Fails if the `proof` doesn't match the state root of the block.
+	pub fn from_proof(
+		header: &PHeader,
+		keys: &[Vec<u8>],
+		proof: StorageProof,
+	) -> RelayChainResult<Self> {
+		let mut proven_values = sp_state_machine::read_proof_check::<BlakeTwo256, _>(
+			*header.state_root(),
+			proof.clone(),
+			keys,
+		)
+		.map_err(RelayChainError::StateMachineError)?;
+
+		let values = keys
+			.iter()
+			.map(|key| (key.clone(), proven_values.remove(key).flatten()))
+			.collect();
+
+		Ok(Self { relay_hash: header.hash(), values, proof })
+	}
+}
+
+/// A call of a runtime API function of the relay chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeApiCall {
+	/// The name of the function, e.g. `ParachainHost_session_index_for_child`.
+	pub method: String,
+	/// The SCALE encoded arguments of the function.
+	pub payload: Vec<u8>,
+}
+
+impl RuntimeApiCall {
+	/// Create a call of the runtime API function `method` with the given `args`.
+	pub fn new(method: impl Into<String>, args: impl Encode) -> Self {
+		Self { method: method.into(), payload: args.encode() }
+	}
+}
+
 /// Trait that provides all necessary methods for interaction between collator and relay chain.
 #[async_trait]
 pub trait RelayChainInterface: Send + Sync {
@@ -160,6 +219,42 @@ pub trait RelayChainInterface: Send + Sync {
 	/// Returns the session index expected at a child of the block.
 	async fn session_index_for_child(&self, block_id: PHash) -> RelayChainResult<SessionIndex>;
 
+	/// Yields information on all availability cores as relevant to the child block.
+	/// Cores are either free or occupied. Free cores can have paras assigned to them.
+	async fn availability_cores(
+		&self,
+		relay_parent: PHash,
+	) -> RelayChainResult<Vec<CoreState<PHash, BlockNumber>>>;
+
+	/// Returns the asynchronous backing parameters, that are used at the given relay parent.
+	async fn async_backing_params(
+		&self,
+		relay_parent: PHash,
+	) -> RelayChainResult<AsyncBackingParams>;
+
+	/// Returns the claim queue at the given relay parent, i.e. the paras that are going to occupy
+	/// the availability cores next, in the order of their claims.
+	///
+	/// This calls the `claim_queue` function of the `ParachainHost` runtime API, which fails if
+	/// the relay chain runtime doesn't provide it yet.
+	async fn claim_queue(
+		&self,
+		relay_parent: PHash,
+	) -> RelayChainResult<BTreeMap<CoreIndex, VecDeque<ParaId>>> {
+		let encoded = self
+			.call_runtime_api_batch(
+				relay_parent,
+				vec![RuntimeApiCall::new("ParachainHost_claim_queue", ())],
+			)
+			.await?
+			.pop()
+			.ok_or_else(|| {
+				RelayChainError::GenericError("No result of the claim queue call".into())
+			})?;
+
+		Ok(Decode::decode(&mut &encoded[..])?)
+	}
+
 	/// Get a stream of import block notifications.
 	async fn import_notification_stream(
 		&self,
@@ -181,10 +276,19 @@ pub trait RelayChainInterface: Send + Sync {
 		&self,
 	) -> RelayChainResult<Pin<Box<dyn Stream<Item = PHeader> + Send>>>;
 
+	/// Get the hash of the last finalized block, whose finality has been verified by this node.
+	///
+	/// Unlike [`Self::finalized_block_hash`], this doesn't trust an external relay chain node to
+	/// report the finality correctly.
+	async fn proven_finalized_block_hash(&self) -> RelayChainResult<PHash>;
+
 	/// Whether the synchronization service is undergoing major sync.
 	/// Returns true if so.
 	async fn is_major_syncing(&self) -> RelayChainResult<bool>;
 
+	/// The number of the peers, the relay chain node is connected to, if it is known.
+	async fn peer_count(&self) -> RelayChainResult<Option<usize>>;
+
 	/// Get a handle to the overseer.
 	fn overseer_handle(&self) -> RelayChainResult<OverseerHandle>;
 
@@ -194,6 +298,25 @@ pub trait RelayChainInterface: Send + Sync {
 		relay_parent: PHash,
 		relevant_keys: &Vec<Vec<u8>>,
 	) -> RelayChainResult<StorageProof>;
+
+	/// Get a stream of the proven values of the given storage `keys`.
+	///
+	/// An item is returned for every new best block, at which the value of at least one of the
+	/// `keys` changed. It contains the values of all the `keys`.
+	async fn subscribe_storage(
+		&self,
+		keys: Vec<Vec<u8>>,
+	) -> RelayChainResult<Pin<Box<dyn Stream<Item = StorageChange> + Send>>>;
+
+	/// Call several runtime API functions at the relay chain block `hash` at once.
+	///
+	/// Returns the SCALE encoded results in the order of the `calls`. Fails if any of the calls
+	/// fails.
+	async fn call_runtime_api_batch(
+		&self,
+		hash: PHash,
+		calls: Vec<RuntimeApiCall>,
+	) -> RelayChainResult<Vec<Vec<u8>>>;
 }
 
 #[async_trait]
@@ -240,6 +363,27 @@ where
 		(**self).session_index_for_child(block_id).await
 	}
 
+	async fn availability_cores(
+		&self,
+		relay_parent: PHash,
+	) -> RelayChainResult<Vec<CoreState<PHash, BlockNumber>>> {
+		(**self).availability_cores(relay_parent).await
+	}
+
+	async fn async_backing_params(
+		&self,
+		relay_parent: PHash,
+	) -> RelayChainResult<AsyncBackingParams> {
+		(**self).async_backing_params(relay_parent).await
+	}
+
+	async fn claim_queue(
+		&self,
+		relay_parent: PHash,
+	) -> RelayChainResult<BTreeMap<CoreIndex, VecDeque<ParaId>>> {
+		(**self).claim_queue(relay_parent).await
+	}
+
 	async fn validators(&self, block_id: PHash) -> RelayChainResult<Vec<ValidatorId>> {
 		(**self).validators(block_id).await
 	}
@@ -264,10 +408,18 @@ where
 		(**self).finalized_block_hash().await
 	}
 
+	async fn proven_finalized_block_hash(&self) -> RelayChainResult<PHash> {
+		(**self).proven_finalized_block_hash().await
+	}
+
 	async fn is_major_syncing(&self) -> RelayChainResult<bool> {
 		(**self).is_major_syncing().await
 	}
 
+	async fn peer_count(&self) -> RelayChainResult<Option<usize>> {
+		(**self).peer_count().await
+	}
+
 	fn overseer_handle(&self) -> RelayChainResult<OverseerHandle> {
 		(**self).overseer_handle()
 	}
@@ -288,6 +440,21 @@ where
 		(**self).prove_read(relay_parent, relevant_keys).await
 	}
 
+	async fn subscribe_storage(
+		&self,
+		keys: Vec<Vec<u8>>,
+	) -> RelayChainResult<Pin<Box<dyn Stream<Item = StorageChange> + Send>>> {
+		(**self).subscribe_storage(keys).await
+	}
+
+	async fn call_runtime_api_batch(
+		&self,
+		hash: PHash,
+		calls: Vec<RuntimeApiCall>,
+	) -> RelayChainResult<Vec<Vec<u8>>> {
+		(**self).call_runtime_api_batch(hash, calls).await
+	}
+
 	async fn wait_for_block(&self, hash: PHash) -> RelayChainResult<()> {
 		(**self).wait_for_block(hash).await
 	}
@@ -47,6 +47,7 @@ use sp_runtime::traits::Block as BlockT;
 
 use cumulus_primitives_core::relay_chain::{Block, Hash as PHash};
 use cumulus_relay_chain_interface::RelayChainError;
+use cumulus_relay_chain_rpc_interface::StorageProofProvider;
 
 use crate::BlockChainRpcClient;
 
@@ -211,6 +212,8 @@ pub struct NewMinimalNode {
 	pub overseer_handle: Handle,
 	/// Network service
 	pub network: Arc<sc_network::NetworkService<Block, <Block as BlockT>::Hash>>,
+	/// Provider of the storage proofs, that the RPC server can't serve
+	pub storage_proof_provider: Option<Arc<dyn StorageProofProvider>>,
 }
 
 /// Glues together the [`Overseer`] and `BlockchainEvents` by forwarding
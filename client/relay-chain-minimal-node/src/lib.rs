@@ -17,7 +17,9 @@
 use collator_overseer::{CollatorOverseerGenArgs, NewMinimalNode};
 
 use cumulus_relay_chain_interface::{RelayChainError, RelayChainInterface, RelayChainResult};
-use cumulus_relay_chain_rpc_interface::{RelayChainRpcClient, RelayChainRpcInterface, Url};
+use cumulus_relay_chain_rpc_interface::{
+	RelayChainRpcClient, RelayChainRpcInterface, RpcNodeExpectations, StorageProofProvider, Url,
+};
 use network::build_collator_network;
 use polkadot_network_bridge::{peer_sets_info, IsAuthority};
 use polkadot_node_network_protocol::{
@@ -26,6 +28,7 @@ use polkadot_node_network_protocol::{
 		v1, vstaging, IncomingRequest, IncomingRequestReceiver, Protocol, ReqProtocolNames,
 	},
 };
+use storage_proof::NetworkStorageProofProvider;
 
 use polkadot_node_subsystem_util::metrics::prometheus::Registry;
 use polkadot_primitives::CollatorPair;
@@ -33,14 +36,18 @@ use polkadot_primitives::CollatorPair;
 use sc_authority_discovery::Service as AuthorityDiscoveryService;
 use sc_network::{config::FullNetworkConfiguration, Event, NetworkEventStream, NetworkService};
 use sc_service::{Configuration, TaskManager};
+use sp_consensus_grandpa::{AuthorityList, VersionedAuthorityList, GRANDPA_AUTHORITIES_KEY};
+use sp_core::hashing::twox_128;
 use sp_runtime::{app_crypto::Pair, traits::Block as BlockT};
 
+use codec::Decode;
 use futures::StreamExt;
 use std::sync::Arc;
 
 mod blockchain_rpc_client;
 mod collator_overseer;
 mod network;
+mod storage_proof;
 
 pub use blockchain_rpc_client::BlockChainRpcClient;
 
@@ -83,23 +90,60 @@ fn build_authority_discovery_service<Block: BlockT>(
 	service
 }
 
+/// Returns the GRANDPA authorities of the relay chain genesis, that is built from the chain spec.
+fn genesis_grandpa_authorities(config: &Configuration) -> RelayChainResult<AuthorityList> {
+	let storage = config
+		.chain_spec
+		.as_storage_builder()
+		.build_storage()
+		.map_err(RelayChainError::GenericError)?;
+
+	if let Some(encoded) = storage.top.get(GRANDPA_AUTHORITIES_KEY) {
+		return Ok(VersionedAuthorityList::decode(&mut &encoded[..])?.into())
+	}
+
+	// Newer runtimes keep the authorities in the storage of `pallet-grandpa`.
+	let key = [twox_128(b"Grandpa"), twox_128(b"Authorities")].concat();
+	match storage.top.get(&key) {
+		Some(encoded) => Ok(AuthorityList::decode(&mut &encoded[..])?),
+		None => Err(RelayChainError::GenericError(
+			"The relay chain genesis doesn't contain the GRANDPA authorities".into(),
+		)),
+	}
+}
+
+/// Build the minimal relay chain node and the interface to it.
+///
+/// With a `light_client`, the storage proofs are fetched from the relay chain full nodes, as the
+/// light client can't serve them.
 async fn build_interface(
 	polkadot_config: Configuration,
 	task_manager: &mut TaskManager,
 	client: RelayChainRpcClient,
+	light_client: bool,
 ) -> RelayChainResult<(Arc<(dyn RelayChainInterface + 'static)>, Option<CollatorPair>)> {
+	// The light client verifies the finality itself.
+	let genesis_authorities =
+		if light_client { None } else { Some(genesis_grandpa_authorities(&polkadot_config)?) };
+
 	let collator_pair = CollatorPair::generate().0;
 	let collator_node = new_minimal_relay_chain(
 		polkadot_config,
 		collator_pair.clone(),
 		Arc::new(BlockChainRpcClient::new(client.clone())),
+		light_client,
 	)
 	.await?;
 	task_manager.add_child(collator_node.task_manager);
-	Ok((
-		Arc::new(RelayChainRpcInterface::new(client, collator_node.overseer_handle)),
-		Some(collator_pair),
-	))
+
+	let mut interface = RelayChainRpcInterface::new(client, collator_node.overseer_handle);
+	if let Some(provider) = collator_node.storage_proof_provider {
+		interface = interface.with_storage_proof_provider(provider);
+	}
+	if let Some(authorities) = genesis_authorities {
+		interface = interface.with_genesis_grandpa_authorities(authorities);
+	}
+	Ok((Arc::new(interface), Some(collator_pair)))
 }
 
 pub async fn build_minimal_relay_chain_node_with_rpc(
@@ -107,13 +151,22 @@ pub async fn build_minimal_relay_chain_node_with_rpc(
 	task_manager: &mut TaskManager,
 	relay_chain_url: Vec<Url>,
 ) -> RelayChainResult<(Arc<(dyn RelayChainInterface + 'static)>, Option<CollatorPair>)> {
+	// Catch misconfigured RPC nodes before they lead to opaque subscription errors.
+	let expectations = RpcNodeExpectations {
+		chain_name: Some(polkadot_config.chain_spec.name().into()),
+		chain_type: Some(polkadot_config.chain_spec.chain_type()),
+		..Default::default()
+	};
+	cumulus_relay_chain_rpc_interface::check_rpc_nodes(&relay_chain_url, &expectations).await?;
+
 	let client = cumulus_relay_chain_rpc_interface::create_client_and_start_worker(
 		relay_chain_url,
 		task_manager,
+		polkadot_config.prometheus_registry(),
 	)
 	.await?;
 
-	build_interface(polkadot_config, task_manager, client).await
+	build_interface(polkadot_config, task_manager, client, false).await
 }
 
 pub async fn build_minimal_relay_chain_node_light_client(
@@ -135,10 +188,11 @@ pub async fn build_minimal_relay_chain_node_light_client(
 	let client = cumulus_relay_chain_rpc_interface::create_client_and_start_light_client_worker(
 		spec,
 		task_manager,
+		polkadot_config.prometheus_registry(),
 	)
 	.await?;
 
-	build_interface(polkadot_config, task_manager, client).await
+	build_interface(polkadot_config, task_manager, client, true).await
 }
 /// Builds a minimal relay chain node. Chain data is fetched
 /// via [`BlockChainRpcClient`] and fed into the overseer and its subsystems.
@@ -156,6 +210,7 @@ async fn new_minimal_relay_chain(
 	config: Configuration,
 	collator_pair: CollatorPair,
 	relay_chain_rpc_client: Arc<BlockChainRpcClient>,
+	light_client: bool,
 ) -> Result<NewMinimalNode, RelayChainError> {
 	let role = config.role.clone();
 	let mut net_config = sc_network::config::FullNetworkConfiguration::new(&config.network);
@@ -185,6 +240,18 @@ async fn new_minimal_relay_chain(
 	let (collation_req_receiver_v1, collation_req_receiver_vstaging, available_data_req_receiver) =
 		build_request_response_protocol_receivers(&request_protocol_names, &mut net_config);
 
+	// The light client can't serve the storage proofs, they are requested from the full nodes.
+	let storage_proofs_protocol = light_client.then(|| {
+		let protocol_config = sc_network_light::light_client_requests::generate_protocol_config(
+			&config.protocol_id(),
+			genesis_hash,
+			config.chain_spec.fork_id(),
+		);
+		let protocol_name = protocol_config.name.clone();
+		net_config.add_request_response_protocol(protocol_config);
+		protocol_name
+	});
+
 	let best_header = relay_chain_rpc_client
 		.chain_get_header(None)
 		.await?
@@ -226,7 +293,17 @@ async fn new_minimal_relay_chain(
 
 	network_starter.start_network();
 
-	Ok(NewMinimalNode { task_manager, overseer_handle, network })
+	let storage_proof_provider = storage_proofs_protocol.map(|protocol_name| {
+		let peers = config.network.boot_nodes.iter().map(|bootnode| bootnode.peer_id).collect();
+		Arc::new(NetworkStorageProofProvider::new(
+			network.clone(),
+			protocol_name,
+			peers,
+			relay_chain_rpc_client.clone(),
+		)) as Arc<dyn StorageProofProvider>
+	});
+
+	Ok(NewMinimalNode { task_manager, overseer_handle, network, storage_proof_provider })
 }
 
 fn build_request_response_protocol_receivers(
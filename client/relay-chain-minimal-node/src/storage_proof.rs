@@ -0,0 +1,194 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage proofs of the relay chain state for the embedded light client.
+//!
+//! Collators need the storage proofs of the relay chain state to build the parachain inherent.
+//! The embedded light client doesn't provide them, so they are fetched from the relay chain full
+//! nodes with the light client request protocol, that every full node serves.
+
+use async_trait::async_trait;
+use codec::{Decode, Encode};
+use cumulus_relay_chain_interface::{RelayChainError, RelayChainResult};
+use cumulus_relay_chain_rpc_interface::StorageProofProvider;
+use polkadot_core_primitives::{Block, Hash};
+use prost::Message;
+use sc_network::{
+	request_responses::IfDisconnected, NetworkRequest, NetworkService, PeerId, ProtocolName,
+};
+use sp_runtime::traits::{BlakeTwo256, Header as HeaderT};
+use sp_state_machine::StorageProof;
+use std::sync::{
+	atomic::{AtomicUsize, Ordering},
+	Arc,
+};
+
+use crate::{BlockChainRpcClient, LOG_TARGET};
+
+/// The messages of the light client request protocol, see `light.v1.proto` of `sc-network-light`.
+///
+/// Only the remote read requests are used.
+mod schema {
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Request {
+		#[prost(oneof = "request::Request", tags = "2")]
+		pub request: Option<request::Request>,
+	}
+
+	pub mod request {
+		#[derive(Clone, PartialEq, prost::Oneof)]
+		pub enum Request {
+			#[prost(message, tag = "2")]
+			RemoteReadRequest(super::RemoteReadRequest),
+		}
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Response {
+		#[prost(oneof = "response::Response", tags = "2")]
+		pub response: Option<response::Response>,
+	}
+
+	pub mod response {
+		#[derive(Clone, PartialEq, prost::Oneof)]
+		pub enum Response {
+			#[prost(message, tag = "2")]
+			RemoteReadResponse(super::RemoteReadResponse),
+		}
+	}
+
+	/// Remote storage read request.
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct RemoteReadRequest {
+		/// Block at which to perform the call.
+		#[prost(bytes = "vec", tag = "2")]
+		pub block: Vec<u8>,
+		/// Storage keys.
+		#[prost(bytes = "vec", repeated, tag = "3")]
+		pub keys: Vec<Vec<u8>>,
+	}
+
+	/// Remote read response.
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct RemoteReadResponse {
+		/// Read proof. If missing, indicates that the remote couldn't answer, for example because
+		/// it didn't have the requested data.
+		#[prost(bytes = "vec", optional, tag = "2")]
+		pub proof: Option<Vec<u8>>,
+	}
+}
+
+/// Fetches the storage proofs from the relay chain full nodes with the light client request
+/// protocol.
+///
+/// The proofs are checked against the state root of the block, that is provided by the light
+/// client.
+pub(crate) struct NetworkStorageProofProvider {
+	network: Arc<NetworkService<Block, Hash>>,
+	protocol_name: ProtocolName,
+	/// The full nodes, that are asked for the proofs.
+	peers: Vec<PeerId>,
+	/// The index of the peer, that is asked first.
+	next_peer: AtomicUsize,
+	rpc_client: Arc<BlockChainRpcClient>,
+}
+
+impl NetworkStorageProofProvider {
+	/// Create a new [`NetworkStorageProofProvider`], that asks the `peers` for the proofs.
+	pub fn new(
+		network: Arc<NetworkService<Block, Hash>>,
+		protocol_name: ProtocolName,
+		peers: Vec<PeerId>,
+		rpc_client: Arc<BlockChainRpcClient>,
+	) -> Self {
+		Self { network, protocol_name, peers, next_peer: AtomicUsize::new(0), rpc_client }
+	}
+
+	/// Request the proof of the `keys` at the block `relay_parent` from the `peer`.
+	async fn request_proof(
+		&self,
+		peer: PeerId,
+		relay_parent: Hash,
+		keys: &[Vec<u8>],
+	) -> Result<StorageProof, String> {
+		let request = schema::Request {
+			request: Some(schema::request::Request::RemoteReadRequest(schema::RemoteReadRequest {
+				block: relay_parent.encode(),
+				keys: keys.to_vec(),
+			})),
+		};
+
+		let response = self
+			.network
+			.request(
+				peer,
+				self.protocol_name.clone(),
+				request.encode_to_vec(),
+				IfDisconnected::TryConnect,
+			)
+			.await
+			.map_err(|e| format!("Request failed: {e:?}"))?;
+
+		match schema::Response::decode(&response[..]).map_err(|e| e.to_string())?.response {
+			Some(schema::response::Response::RemoteReadResponse(schema::RemoteReadResponse {
+				proof: Some(proof),
+			})) => StorageProof::decode(&mut &proof[..]).map_err(|e| e.to_string()),
+			_ => Err("Peer didn't provide the proof".into()),
+		}
+	}
+}
+
+#[async_trait]
+impl StorageProofProvider for NetworkStorageProofProvider {
+	async fn prove_read(
+		&self,
+		relay_parent: Hash,
+		keys: &[Vec<u8>],
+	) -> RelayChainResult<StorageProof> {
+		let header =
+			self.rpc_client.chain_get_header(Some(relay_parent)).await?.ok_or_else(|| {
+				RelayChainError::GenericError(format!("Unknown block {relay_parent}"))
+			})?;
+
+		let first = self.next_peer.fetch_add(1, Ordering::Relaxed);
+		for index in 0..self.peers.len() {
+			let peer = self.peers[(first + index) % self.peers.len()];
+			let proof = match self.request_proof(peer, relay_parent, keys).await {
+				Ok(proof) => proof,
+				Err(error) => {
+					tracing::debug!(target: LOG_TARGET, ?peer, error, "Failed to fetch storage proof");
+					continue
+				},
+			};
+
+			// A partial proof would only surface as an invalid parachain block.
+			match sp_state_machine::read_proof_check::<BlakeTwo256, _>(
+				*header.state_root(),
+				proof.clone(),
+				keys,
+			) {
+				Ok(_) => return Ok(proof),
+				Err(error) => {
+					tracing::debug!(target: LOG_TARGET, ?peer, ?error, "Peer provided invalid storage proof")
+				},
+			}
+		}
+
+		Err(RelayChainError::GenericError(format!(
+			"No relay chain full node provided the storage proof at {relay_parent}"
+		)))
+	}
+}
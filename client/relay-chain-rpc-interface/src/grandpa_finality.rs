@@ -0,0 +1,124 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Verification of the relay chain finality, that is reported by an external relay chain node.
+//!
+//! Starting with the GRANDPA authorities of the relay chain genesis, the justifications of the
+//! last block of every authority set are verified, until the current authority set is reached.
+//! The header of the last block of an authority set contains the next authority set. This works
+//! like the GRANDPA warp sync, but with the finality proofs from `grandpa_proveFinality`.
+
+use cumulus_primitives_core::relay_chain::{
+	Block as RelayBlock, BlockNumber, Header as RelayHeader,
+};
+use cumulus_relay_chain_interface::{RelayChainError, RelayChainResult};
+use finality_grandpa::voter_set::VoterSet;
+use parity_scale_codec::Decode;
+use sc_consensus_grandpa::{FinalityProof, GrandpaJustification};
+use sp_consensus_grandpa::{AuthorityList, ConsensusLog, ScheduledChange, GRANDPA_ENGINE_ID};
+use sp_runtime::{generic::OpaqueDigestItemId, traits::Header as HeaderT};
+
+use crate::{RelayChainRpcClient, LOG_TARGET};
+
+/// Returns the change of the authority set, that is scheduled in the `header`.
+///
+/// Forced changes are not supported, they are only used to recover from a stalled finality.
+fn scheduled_change(header: &RelayHeader) -> Option<ScheduledChange<BlockNumber>> {
+	let id = OpaqueDigestItemId::Consensus(&GRANDPA_ENGINE_ID);
+	header
+		.digest()
+		.convert_first(|log| match log.try_to::<ConsensusLog<BlockNumber>>(id) {
+			Some(ConsensusLog::ScheduledChange(change)) => Some(change),
+			_ => None,
+		})
+}
+
+/// Returns the header of the latest block of the relay chain, whose GRANDPA justification is
+/// valid for the authority set, that descends from the `genesis_authorities`.
+///
+/// The number of the requests grows with the number of the authority sets of the relay chain.
+pub(crate) async fn prove_finalized_header(
+	rpc_client: &RelayChainRpcClient,
+	genesis_authorities: &AuthorityList,
+) -> RelayChainResult<RelayHeader> {
+	let mut set_id = 0;
+	let mut authorities = genesis_authorities.clone();
+	let mut number: BlockNumber = 1;
+	let mut finalized_header = None;
+
+	// Only bounds the requests, the finality of the blocks is verified below.
+	let finalized_hash = rpc_client.chain_get_finalized_head().await?;
+	let finalized_number = rpc_client
+		.chain_get_header(Some(finalized_hash))
+		.await?
+		.map(|header| header.number)
+		.ok_or_else(|| {
+			RelayChainError::GenericError(format!("Missing relay chain header {finalized_hash:?}"))
+		})?;
+
+	while number <= finalized_number {
+		let encoded = rpc_client.grandpa_prove_finality(number).await?.ok_or_else(|| {
+			RelayChainError::GenericError(format!("No GRANDPA finality proof of block #{number}"))
+		})?;
+		let proof = FinalityProof::<RelayHeader>::decode(&mut &encoded[..])?;
+
+		let header = rpc_client.chain_get_header(Some(proof.block)).await?.ok_or_else(|| {
+			RelayChainError::GenericError(format!("Missing relay chain header {:?}", proof.block))
+		})?;
+		if header.hash() != proof.block {
+			return Err(RelayChainError::GenericError(format!(
+				"Relay chain header doesn't match the hash {:?}",
+				proof.block
+			)))
+		}
+
+		let voters = VoterSet::new(authorities.iter().cloned()).ok_or_else(|| {
+			RelayChainError::GenericError(format!("Invalid GRANDPA authority set {set_id}"))
+		})?;
+		GrandpaJustification::<RelayBlock>::decode_and_verify_finalizes(
+			&proof.justification,
+			(proof.block, *header.number()),
+			set_id,
+			&voters,
+		)
+		.map_err(|e| {
+			RelayChainError::GenericError(format!(
+				"Invalid GRANDPA justification of {:?}: {e}",
+				proof.block
+			))
+		})?;
+
+		match scheduled_change(&header) {
+			Some(change) => {
+				tracing::trace!(
+					target: LOG_TARGET,
+					set_id,
+					number = header.number(),
+					"Verified the last block of the GRANDPA authority set",
+				);
+				set_id += 1;
+				authorities = change.next_authorities;
+				number = header.number() + 1;
+				finalized_header = Some(header);
+			},
+			None => return Ok(header),
+		}
+	}
+
+	// The last block of the previous authority set is the latest justified block.
+	finalized_header
+		.ok_or_else(|| RelayChainError::GenericError("No GRANDPA finality proof available".into()))
+}
@@ -18,31 +18,41 @@ use async_trait::async_trait;
 use core::time::Duration;
 use cumulus_primitives_core::{
 	relay_chain::{
-		CommittedCandidateReceipt, Hash as RelayHash, Header as RelayHeader, InboundHrmpMessage,
-		OccupiedCoreAssumption, SessionIndex, ValidatorId,
+		vstaging::AsyncBackingParams, BlockNumber, CommittedCandidateReceipt, CoreState,
+		Hash as RelayHash, Header as RelayHeader, InboundHrmpMessage, OccupiedCoreAssumption,
+		SessionIndex, ValidatorId,
 	},
 	InboundDownwardMessage, ParaId, PersistedValidationData,
 };
 use cumulus_relay_chain_interface::{
-	PHeader, RelayChainError, RelayChainInterface, RelayChainResult,
+	PHeader, RelayChainError, RelayChainInterface, RelayChainResult, RuntimeApiCall, StorageChange,
 };
 use futures::{FutureExt, Stream, StreamExt};
 use polkadot_overseer::Handle;
 
 use sc_client_api::StorageProof;
+use sp_consensus_grandpa::AuthorityList;
 use sp_core::sp_std::collections::btree_map::BTreeMap;
+use sp_runtime::traits::Header as HeaderT;
 use sp_state_machine::StorageValue;
 use sp_storage::StorageKey;
-use std::pin::Pin;
+use std::{pin::Pin, sync::Arc};
 
 use cumulus_primitives_core::relay_chain::BlockId;
 pub use url::Url;
 
+mod grandpa_finality;
 mod light_client_worker;
+mod probe;
 mod reconnecting_ws_client;
+mod request_cache;
 mod rpc_client;
 mod tokio_platform;
 
+pub use probe::{
+	check_rpc_nodes, probe_rpc_node, probe_rpc_nodes, RpcNodeExpectations, RpcNodeIssue,
+	RpcNodeReport, DEFAULT_STATE_DEPTH,
+};
 pub use rpc_client::{
 	create_client_and_start_light_client_worker, create_client_and_start_worker,
 	RelayChainRpcClient,
@@ -50,17 +60,55 @@ pub use rpc_client::{
 
 const TIMEOUT_IN_SECONDS: u64 = 6;
 
+const LOG_TARGET: &str = "relay-chain-rpc-interface";
+
+/// Provides the storage proofs of the relay chain state, if the RPC server can't serve them.
+///
+/// The embedded light client doesn't support `state_getReadProof`, so the proofs are fetched
+/// from elsewhere, e.g. from the relay chain full nodes.
+#[async_trait]
+pub trait StorageProofProvider: Send + Sync {
+	/// Returns the proof of the values of the `keys` at the relay chain block `relay_parent`.
+	async fn prove_read(
+		&self,
+		relay_parent: RelayHash,
+		keys: &[Vec<u8>],
+	) -> RelayChainResult<StorageProof>;
+}
+
 /// RelayChainRpcInterface is used to interact with a full node that is running locally
 /// in the same process.
 #[derive(Clone)]
 pub struct RelayChainRpcInterface {
 	rpc_client: RelayChainRpcClient,
 	overseer_handle: Handle,
+	storage_proof_provider: Option<Arc<dyn StorageProofProvider>>,
+	genesis_grandpa_authorities: Option<Arc<AuthorityList>>,
 }
 
 impl RelayChainRpcInterface {
 	pub fn new(rpc_client: RelayChainRpcClient, overseer_handle: Handle) -> Self {
-		Self { rpc_client, overseer_handle }
+		Self {
+			rpc_client,
+			overseer_handle,
+			storage_proof_provider: None,
+			genesis_grandpa_authorities: None,
+		}
+	}
+
+	/// Verify the finality, that is reported by the RPC server, with the GRANDPA justifications,
+	/// starting with the authorities of the relay chain genesis.
+	///
+	/// Without them, the RPC server needs to verify the finality, like the embedded light client.
+	pub fn with_genesis_grandpa_authorities(mut self, authorities: AuthorityList) -> Self {
+		self.genesis_grandpa_authorities = Some(Arc::new(authorities));
+		self
+	}
+
+	/// Fetch the storage proofs from the given `provider` instead of the RPC server.
+	pub fn with_storage_proof_provider(mut self, provider: Arc<dyn StorageProofProvider>) -> Self {
+		self.storage_proof_provider = Some(provider);
+		self
 	}
 }
 
@@ -124,6 +172,17 @@ impl RelayChainInterface for RelayChainRpcInterface {
 		self.rpc_client.parachain_host_session_index_for_child(hash).await
 	}
 
+	async fn availability_cores(
+		&self,
+		hash: RelayHash,
+	) -> RelayChainResult<Vec<CoreState<RelayHash, BlockNumber>>> {
+		self.rpc_client.parachain_host_availability_cores(hash).await
+	}
+
+	async fn async_backing_params(&self, hash: RelayHash) -> RelayChainResult<AsyncBackingParams> {
+		self.rpc_client.parachain_host_staging_async_backing_params(hash).await
+	}
+
 	async fn validators(&self, block_id: RelayHash) -> RelayChainResult<Vec<ValidatorId>> {
 		self.rpc_client.parachain_host_validators(block_id).await
 	}
@@ -152,10 +211,24 @@ impl RelayChainInterface for RelayChainRpcInterface {
 		self.rpc_client.chain_get_finalized_head().await
 	}
 
+	async fn proven_finalized_block_hash(&self) -> RelayChainResult<RelayHash> {
+		match &self.genesis_grandpa_authorities {
+			Some(authorities) =>
+				grandpa_finality::prove_finalized_header(&self.rpc_client, authorities)
+					.await
+					.map(|header| header.hash()),
+			None => self.rpc_client.chain_get_finalized_head().await,
+		}
+	}
+
 	async fn is_major_syncing(&self) -> RelayChainResult<bool> {
 		self.rpc_client.system_health().await.map(|h| h.is_syncing)
 	}
 
+	async fn peer_count(&self) -> RelayChainResult<Option<usize>> {
+		self.rpc_client.system_health().await.map(|h| Some(h.peers))
+	}
+
 	fn overseer_handle(&self) -> RelayChainResult<Handle> {
 		Ok(self.overseer_handle.clone())
 	}
@@ -177,6 +250,10 @@ impl RelayChainInterface for RelayChainRpcInterface {
 		relay_parent: RelayHash,
 		relevant_keys: &Vec<Vec<u8>>,
 	) -> RelayChainResult<StorageProof> {
+		if let Some(provider) = &self.storage_proof_provider {
+			return provider.prove_read(relay_parent, relevant_keys).await
+		}
+
 		let cloned = relevant_keys.clone();
 		let storage_keys: Vec<StorageKey> = cloned.into_iter().map(StorageKey).collect();
 
@@ -188,6 +265,54 @@ impl RelayChainInterface for RelayChainRpcInterface {
 			})
 	}
 
+	async fn subscribe_storage(
+		&self,
+		keys: Vec<Vec<u8>>,
+	) -> RelayChainResult<Pin<Box<dyn Stream<Item = StorageChange> + Send>>> {
+		let storage_keys = keys.iter().cloned().map(StorageKey).collect();
+		// Only the blocks, at which a value changed, are proven.
+		let changed_blocks = self.rpc_client.get_storage_changes_stream(storage_keys)?;
+
+		let interface = self.clone();
+		let proof_keys = keys.clone();
+		let changes = changed_blocks
+			.then(move |relay_hash| {
+				let interface = interface.clone();
+				let keys = proof_keys.clone();
+				async move {
+					let header =
+						interface.header(BlockId::Hash(relay_hash)).await?.ok_or_else(|| {
+							RelayChainError::GenericError(format!("Unknown block {relay_hash}"))
+						})?;
+					let proof = interface.prove_read(relay_hash, &keys).await?;
+					StorageChange::from_proof(&header, &keys, proof)
+				}
+			})
+			.filter_map(move |change| {
+				let change = change
+					.map_err(|error| {
+						tracing::debug!(
+							target: LOG_TARGET,
+							?error,
+							?keys,
+							"Failed to prove subscribed storage",
+						)
+					})
+					.ok();
+				futures::future::ready(change)
+			});
+
+		Ok(changes.boxed())
+	}
+
+	async fn call_runtime_api_batch(
+		&self,
+		hash: RelayHash,
+		calls: Vec<RuntimeApiCall>,
+	) -> RelayChainResult<Vec<Vec<u8>>> {
+		self.rpc_client.call_remote_runtime_function_batch(hash, calls).await
+	}
+
 	/// Wait for a given relay chain block
 	///
 	/// The hash of the block to wait for is passed. We wait for the block to arrive or return after
@@ -18,7 +18,11 @@
 //! embedded light client. Even though no networking is involved,
 //! we treat the light-client as a normal JsonRPC target.
 
-use futures::{channel::mpsc::Sender, prelude::*, stream::FuturesUnordered};
+use futures::{
+	channel::mpsc::Sender,
+	prelude::*,
+	stream::{FuturesUnordered, SelectAll},
+};
 use jsonrpsee::core::{
 	client::{
 		Client as JsonRpseeClient, ClientBuilder, ClientT, ReceivedMessage, TransportReceiverT,
@@ -26,6 +30,7 @@ use jsonrpsee::core::{
 	},
 	Error,
 };
+use serde_json::Value as JsonValue;
 use smoldot_light::{ChainId, Client as SmoldotClient, JsonRpcResponses};
 use std::{num::NonZeroU32, sync::Arc};
 use tokio::sync::mpsc::{channel as tokio_channel, Receiver, Sender as TokioSender};
@@ -40,7 +45,10 @@ use sp_runtime::generic::SignedBlock;
 use sc_rpc_api::chain::ChainApiClient;
 use sc_service::SpawnTaskHandle;
 
-use crate::{rpc_client::RpcDispatcherMessage, tokio_platform::TokioPlatform};
+use crate::{
+	rpc_client::{subscribe_storage, RpcDispatcherMessage, StorageSubscription},
+	tokio_platform::TokioPlatform,
+};
 
 const LOG_TARGET: &str = "rpc-light-client-worker";
 const MAX_PENDING_REQUESTS: u32 = 128;
@@ -123,6 +131,7 @@ pub struct LightClientRpcWorker {
 	imported_header_listeners: Vec<Sender<RelayHeader>>,
 	finalized_header_listeners: Vec<Sender<RelayHeader>>,
 	best_header_listeners: Vec<Sender<RelayHeader>>,
+	storage_subscriptions: SelectAll<StorageSubscription>,
 	smoldot_client: Arc<JsonRpseeClient>,
 }
 
@@ -170,6 +179,7 @@ impl LightClientRpcWorker {
 			imported_header_listeners: Default::default(),
 			finalized_header_listeners: Default::default(),
 			best_header_listeners: Default::default(),
+			storage_subscriptions: Default::default(),
 			smoldot_client: smoldot_jsonrpsee_client,
 		};
 		(worker, tx)
@@ -245,6 +255,16 @@ impl LightClientRpcWorker {
 					Some(RpcDispatcherMessage::RegisterFinalizationListener(tx)) => {
 						self.finalized_header_listeners.push(tx)
 					},
+					Some(RpcDispatcherMessage::RegisterStorageListener(keys, tx)) => {
+						match subscribe_storage(&self.smoldot_client, keys, tx).await {
+							Ok(subscription) => self.storage_subscriptions.push(subscription),
+							Err(error) => tracing::error!(
+								target: LOG_TARGET,
+								?error,
+								"Unable to initialize storage subscription"
+							),
+						}
+					},
 					Some(RpcDispatcherMessage::Request(method, params, response_sender)) => {
 						let closure_client = self.smoldot_client.clone();
 						tracing::debug!(
@@ -268,7 +288,35 @@ impl LightClientRpcWorker {
 									"Recipient no longer interested in request result"
 								);
 							};
-						});
+						}.boxed());
+					},
+					Some(RpcDispatcherMessage::BatchRequest(requests, response_sender)) => {
+						let closure_client = self.smoldot_client.clone();
+						tracing::debug!(
+							target: LOG_TARGET,
+							len = pending_requests.len(),
+							num_requests = requests.len(),
+							"Batch request"
+						);
+						// Smoldot doesn't support batches, so the requests are sent individually.
+						pending_requests.push(async move {
+							let response = future::try_join_all(
+								requests
+									.into_iter()
+									.map(|(method, params)| {
+										let client = closure_client.clone();
+										async move { client.request::<JsonValue, _>(&method, params).await }
+									}),
+							)
+							.await;
+							if let Err(err) = response_sender.send(response) {
+								tracing::debug!(
+									target: LOG_TARGET,
+									?err,
+									"Recipient no longer interested in request result"
+								);
+							};
+						}.boxed());
 					},
 					None => {
 						tracing::error!(target: LOG_TARGET, "RPC client receiver closed. Stopping RPC Worker.");
@@ -276,6 +324,11 @@ impl LightClientRpcWorker {
 					}
 				},
 				_ = pending_requests.next(), if !pending_requests.is_empty() => {},
+				storage_event = self.storage_subscriptions.next(), if !self.storage_subscriptions.is_empty() => {
+					if let Some(Err(error)) = storage_event {
+						tracing::error!(target: LOG_TARGET, ?error, "Error in RPC storage subscription.");
+					}
+				},
 				import_event = all_head_subscription.next() => {
 					if handle_notification(import_event, &mut self.imported_header_listeners).is_err() {
 						return
@@ -0,0 +1,321 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Probing of relay chain RPC nodes before they are used.
+//!
+//! A node of another chain, a node without finality subscriptions or a node that has already
+//! pruned the state we need would otherwise only show up later, as opaque errors of subscriptions
+//! and requests.
+
+use cumulus_primitives_core::relay_chain::{BlockNumber, Hash as RelayHash, Header as RelayHeader};
+use cumulus_relay_chain_interface::{RelayChainError, RelayChainResult};
+use futures::{future::Either, Future, StreamExt};
+use futures_timer::Delay;
+use jsonrpsee::{
+	core::{
+		client::{ClientT, SubscriptionClientT},
+		params::ArrayParams,
+		JsonValue,
+	},
+	rpc_params,
+	ws_client::{WsClient, WsClientBuilder},
+};
+use sc_service::ChainType;
+use serde::de::DeserializeOwned;
+use std::{fmt, time::Duration};
+use url::Url;
+
+const LOG_TARGET: &str = "relay-chain-rpc-probe";
+
+/// Time given to every single step of a probe.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The default number of blocks below the finalized block, whose state must still be available.
+pub const DEFAULT_STATE_DEPTH: BlockNumber = 64;
+
+/// What the probed relay chain RPC nodes are expected to serve.
+#[derive(Clone, Debug)]
+pub struct RpcNodeExpectations {
+	/// The genesis hash of the relay chain.
+	///
+	/// If `None`, all probed nodes must serve the chain of the first node that reports its
+	/// genesis hash.
+	pub genesis_hash: Option<RelayHash>,
+	/// The name of the relay chain, as reported by `system_chain`.
+	pub chain_name: Option<String>,
+	/// The type of the relay chain, as reported by `system_chainType`.
+	pub chain_type: Option<ChainType>,
+	/// The number of blocks below the finalized block, whose state must still be available.
+	pub state_depth: BlockNumber,
+}
+
+impl Default for RpcNodeExpectations {
+	fn default() -> Self {
+		Self {
+			genesis_hash: None,
+			chain_name: None,
+			chain_type: None,
+			state_depth: DEFAULT_STATE_DEPTH,
+		}
+	}
+}
+
+/// A problem found while probing a relay chain RPC node.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RpcNodeIssue {
+	/// No connection could be established.
+	Unreachable(String),
+	/// A request failed.
+	RequestFailed { method: &'static str, error: String },
+	/// The node serves another chain.
+	GenesisMismatch { expected: RelayHash, found: RelayHash },
+	/// The name of the chain differs from the one of the relay chain spec.
+	ChainNameMismatch { expected: String, found: String },
+	/// The type of the chain differs from the one of the relay chain spec.
+	ChainTypeMismatch { expected: ChainType, found: ChainType },
+	/// The node doesn't notify about finalized heads.
+	NoFinalitySubscription(String),
+	/// The state of the block `depth` blocks below the finalized block is no longer available.
+	StatePruned { depth: BlockNumber },
+}
+
+impl RpcNodeIssue {
+	/// Whether the node must not be used at all.
+	pub fn is_fatal(&self) -> bool {
+		matches!(self, Self::GenesisMismatch { .. } | Self::NoFinalitySubscription(_))
+	}
+}
+
+impl fmt::Display for RpcNodeIssue {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Unreachable(error) => write!(
+				f,
+				"unable to connect ({}). Check that the node is running and that the URL, \
+				including the `ws://` or `wss://` scheme and the port, is right",
+				error
+			),
+			Self::RequestFailed { method, error } => write!(
+				f,
+				"`{}` failed ({}). Make sure that the node exposes the safe RPC methods",
+				method, error
+			),
+			Self::GenesisMismatch { expected, found } => write!(
+				f,
+				"the node serves the chain with genesis hash {:?}, but the relay chain has the \
+				genesis hash {:?}. Point the URL to a node of the right relay chain",
+				found, expected
+			),
+			Self::ChainNameMismatch { expected, found } => write!(
+				f,
+				"the node serves the chain `{}`, but the relay chain spec is `{}`. Check the \
+				relay chain arguments and the URL",
+				found, expected
+			),
+			Self::ChainTypeMismatch { expected, found } => write!(
+				f,
+				"the node serves a chain of type {:?}, but the relay chain spec is of type {:?}",
+				found, expected
+			),
+			Self::NoFinalitySubscription(error) => write!(
+				f,
+				"no finalized heads received over `chain_subscribeFinalizedHeads` ({}). Make \
+				sure that the node, and any proxy in front of it, supports WebSocket \
+				subscriptions",
+				error
+			),
+			Self::StatePruned { depth } => write!(
+				f,
+				"the state of the block {} blocks below the finalized block is already pruned. \
+				Run the node with `--state-pruning {}` or higher, or as archive node",
+				depth, depth
+			),
+		}
+	}
+}
+
+/// The outcome of probing a relay chain RPC node.
+#[derive(Clone, Debug)]
+pub struct RpcNodeReport {
+	/// The probed node.
+	pub url: Url,
+	/// The genesis hash of the chain the node serves, if it could be requested.
+	pub genesis_hash: Option<RelayHash>,
+	/// The problems found.
+	pub issues: Vec<RpcNodeIssue>,
+}
+
+impl RpcNodeReport {
+	/// Whether the node can be used, perhaps with some limitations.
+	pub fn is_usable(&self) -> bool {
+		self.genesis_hash.is_some() && !self.issues.iter().any(RpcNodeIssue::is_fatal)
+	}
+}
+
+/// Probe the relay chain RPC node at `url`.
+pub async fn probe_rpc_node(url: &Url, expectations: &RpcNodeExpectations) -> RpcNodeReport {
+	let mut report = RpcNodeReport { url: url.clone(), genesis_hash: None, issues: Vec::new() };
+	let client = match within_timeout(WsClientBuilder::default().build(url.as_str())).await {
+		Ok(client) => client,
+		Err(error) => {
+			report.issues.push(RpcNodeIssue::Unreachable(error));
+			return report
+		},
+	};
+
+	if let Err(issue) = probe_client(&client, expectations, &mut report).await {
+		report.issues.push(issue);
+	}
+	report
+}
+
+/// Probe the relay chain RPC nodes at `urls`, one after the other.
+///
+/// Without an expected genesis hash, all nodes must serve the chain of the first node that
+/// reports its genesis hash.
+pub async fn probe_rpc_nodes(
+	urls: &[Url],
+	expectations: &RpcNodeExpectations,
+) -> Vec<RpcNodeReport> {
+	let mut expectations = expectations.clone();
+	let mut reports = Vec::with_capacity(urls.len());
+	for url in urls {
+		let report = probe_rpc_node(url, &expectations).await;
+		expectations.genesis_hash = expectations.genesis_hash.or(report.genesis_hash);
+		reports.push(report);
+	}
+	reports
+}
+
+/// Probe the relay chain RPC nodes at `urls` and log the problems found.
+///
+/// Fails if one of the nodes must not be used at all, see [`RpcNodeIssue::is_fatal`].
+pub async fn check_rpc_nodes(
+	urls: &[Url],
+	expectations: &RpcNodeExpectations,
+) -> RelayChainResult<()> {
+	let mut fatal = Vec::new();
+	for report in probe_rpc_nodes(urls, expectations).await {
+		for issue in &report.issues {
+			if issue.is_fatal() {
+				tracing::error!(target: LOG_TARGET, url = %report.url, "{}", issue);
+				fatal.push(format!("{}: {}", report.url, issue));
+			} else {
+				tracing::warn!(target: LOG_TARGET, url = %report.url, "{}", issue);
+			}
+		}
+	}
+
+	if fatal.is_empty() {
+		Ok(())
+	} else {
+		Err(RelayChainError::GenericError(format!(
+			"Refusing to use the relay chain RPC nodes: {}",
+			fatal.join("; ")
+		)))
+	}
+}
+
+async fn probe_client(
+	client: &WsClient,
+	expectations: &RpcNodeExpectations,
+	report: &mut RpcNodeReport,
+) -> Result<(), RpcNodeIssue> {
+	let genesis_hash = request::<Option<RelayHash>>(client, "chain_getBlockHash", rpc_params![0])
+		.await?
+		.ok_or_else(|| RpcNodeIssue::RequestFailed {
+			method: "chain_getBlockHash",
+			error: "no genesis block".into(),
+		})?;
+	report.genesis_hash = Some(genesis_hash);
+	if let Some(expected) = expectations.genesis_hash.filter(|expected| *expected != genesis_hash) {
+		return Err(RpcNodeIssue::GenesisMismatch { expected, found: genesis_hash })
+	}
+
+	let chain_name = request::<String>(client, "system_chain", rpc_params![]).await?;
+	if let Some(expected) = expectations.chain_name.as_ref().filter(|name| **name != chain_name) {
+		report.issues.push(RpcNodeIssue::ChainNameMismatch {
+			expected: expected.clone(),
+			found: chain_name,
+		});
+	}
+
+	let chain_type = request::<ChainType>(client, "system_chainType", rpc_params![]).await?;
+	if let Some(expected) = expectations.chain_type.as_ref().filter(|ty| **ty != chain_type) {
+		report.issues.push(RpcNodeIssue::ChainTypeMismatch {
+			expected: expected.clone(),
+			found: chain_type,
+		});
+	}
+
+	let finalized = within_timeout(async {
+		let mut subscription = client
+			.subscribe::<RelayHeader, _>(
+				"chain_subscribeFinalizedHeads",
+				rpc_params![],
+				"chain_unsubscribeFinalizedHeads",
+			)
+			.await?;
+		subscription
+			.next()
+			.await
+			.unwrap_or_else(|| Err(jsonrpsee::core::Error::Custom("subscription closed".into())))
+	})
+	.await
+	.map_err(RpcNodeIssue::NoFinalitySubscription)?;
+
+	let depth = expectations.state_depth;
+	if finalized.number > depth {
+		let hash = request::<Option<RelayHash>>(
+			client,
+			"chain_getBlockHash",
+			rpc_params![finalized.number - depth],
+		)
+		.await?;
+		let state_available = match hash {
+			Some(hash) =>
+				request::<JsonValue>(client, "state_getRuntimeVersion", rpc_params![hash])
+					.await
+					.is_ok(),
+			None => false,
+		};
+		if !state_available {
+			report.issues.push(RpcNodeIssue::StatePruned { depth });
+		}
+	}
+
+	Ok(())
+}
+
+async fn request<T: DeserializeOwned>(
+	client: &WsClient,
+	method: &'static str,
+	params: ArrayParams,
+) -> Result<T, RpcNodeIssue> {
+	within_timeout(client.request(method, params))
+		.await
+		.map_err(|error| RpcNodeIssue::RequestFailed { method, error })
+}
+
+async fn within_timeout<T, E: fmt::Display>(
+	future: impl Future<Output = Result<T, E>>,
+) -> Result<T, String> {
+	futures::pin_mut!(future);
+	match futures::future::select(future, Delay::new(PROBE_TIMEOUT)).await {
+		Either::Left((result, _)) => result.map_err(|error| error.to_string()),
+		Either::Right(_) => Err(format!("no response within {} seconds", PROBE_TIMEOUT.as_secs())),
+	}
+}
@@ -20,30 +20,48 @@ use cumulus_primitives_core::relay_chain::{
 use futures::{
 	channel::{mpsc::Sender, oneshot::Sender as OneshotSender},
 	future::BoxFuture,
-	stream::FuturesUnordered,
+	stream::{FuturesUnordered, SelectAll},
 	FutureExt, StreamExt,
 };
 use jsonrpsee::{
 	core::{
 		client::{Client as JsonRpcClient, ClientT, Subscription},
-		params::ArrayParams,
+		params::{ArrayParams, BatchRequestBuilder},
 		Error as JsonRpseeError, JsonValue,
 	},
+	types::error::CallError,
 	ws_client::WsClientBuilder,
 };
 use lru::LruCache;
 use sc_rpc_api::chain::ChainApiClient;
 use sp_runtime::generic::SignedBlock;
-use std::{num::NonZeroUsize, sync::Arc};
+use sp_storage::StorageKey;
+use std::{
+	num::NonZeroUsize,
+	sync::Arc,
+	time::{Duration, Instant},
+};
 use tokio::sync::mpsc::{
 	channel as tokio_channel, Receiver as TokioReceiver, Sender as TokioSender,
 };
 use url::Url;
 
-use crate::rpc_client::{distribute_header, RpcDispatcherMessage};
+use crate::rpc_client::{
+	distribute_header, subscribe_storage, RpcDispatcherMessage, StorageSubscription,
+};
 
 const LOG_TARGET: &str = "reconnecting-websocket-client";
 
+/// The weight of a new sample in the moving averages of the [`ServerHealth`].
+const HEALTH_SAMPLE_WEIGHT: f64 = 0.1;
+/// The error rate above which an RPC server is considered unhealthy.
+const MAX_ERROR_RATE: f64 = 0.5;
+/// The distance between the best and the finalized block, above which an RPC server is considered
+/// unhealthy.
+const MAX_FINALITY_LAG: RelayNumber = 32;
+/// The minimum time between two switches to another RPC server, that are caused by bad health.
+const MIN_TIME_BETWEEN_SWITCHES: Duration = Duration::from_secs(60);
+
 /// Worker that should be used in combination with [`RelayChainRpcClient`].
 ///
 /// Must be polled to distribute header notifications to listeners.
@@ -56,6 +74,12 @@ pub struct ReconnectingWebsocketWorker {
 	imported_header_listeners: Vec<Sender<RelayHeader>>,
 	finalized_header_listeners: Vec<Sender<RelayHeader>>,
 	best_header_listeners: Vec<Sender<RelayHeader>>,
+
+	/// The listeners to the storage changes with their keys, so they can be subscribed again
+	/// after a reconnect.
+	storage_listeners: Vec<(Vec<StorageKey>, Sender<RelayHash>)>,
+	/// The storage subscriptions at the current RPC server.
+	storage_subscriptions: SelectAll<StorageSubscription>,
 }
 
 /// Format url and force addition of a port
@@ -77,6 +101,77 @@ fn url_to_string_with_port(url: Url) -> Option<String> {
 	))
 }
 
+/// Health statistics of an RPC server, that are used to select the server to connect to.
+#[derive(Debug, Default, Clone)]
+struct ServerHealth {
+	/// Moving average of the request latency.
+	latency: Duration,
+	/// Moving average of the share of the failed requests and connection attempts.
+	error_rate: f64,
+	/// The number of the latest best block, that has been reported by the server.
+	best_number: RelayNumber,
+	/// The number of the latest finalized block, that has been reported by the server.
+	finalized_number: RelayNumber,
+}
+
+impl ServerHealth {
+	/// Record the result of a request or a connection attempt.
+	fn record_result(&mut self, success: bool) {
+		let sample = if success { 0.0 } else { 1.0 };
+		self.error_rate =
+			self.error_rate * (1.0 - HEALTH_SAMPLE_WEIGHT) + sample * HEALTH_SAMPLE_WEIGHT;
+	}
+
+	/// Record the latency of a request.
+	fn record_latency(&mut self, latency: Duration) {
+		self.latency = if self.latency.is_zero() {
+			latency
+		} else {
+			self.latency.mul_f64(1.0 - HEALTH_SAMPLE_WEIGHT) + latency.mul_f64(HEALTH_SAMPLE_WEIGHT)
+		};
+	}
+
+	/// The distance between the best and the finalized block of the server.
+	fn finality_lag(&self) -> RelayNumber {
+		self.best_number.saturating_sub(self.finalized_number)
+	}
+
+	/// Returns `true` if the server is considered healthy.
+	fn is_healthy(&self) -> bool {
+		self.error_rate <= MAX_ERROR_RATE && self.finality_lag() <= MAX_FINALITY_LAG
+	}
+
+	/// The score of the server. Servers with a lower score are preferred.
+	fn score(&self) -> f64 {
+		self.latency.as_secs_f64() * 1000.0 +
+			self.error_rate * 1000.0 +
+			self.finality_lag() as f64 * 100.0
+	}
+}
+
+/// Returns the indices of the RPC servers in the order, in which they should be tried.
+///
+/// The healthy servers are tried before the unhealthy ones, and servers with a lower score before
+/// the ones with a higher score. The currently `active` server is tried last.
+fn connection_order(health: &[ServerHealth], active: Option<usize>) -> Vec<usize> {
+	let mut order: Vec<usize> = (0..health.len()).collect();
+	order.sort_by(|a, b| {
+		(Some(*a) == active)
+			.cmp(&(Some(*b) == active))
+			.then_with(|| health[*b].is_healthy().cmp(&health[*a].is_healthy()))
+			.then_with(|| health[*a].score().total_cmp(&health[*b].score()))
+	});
+	order
+}
+
+/// The outcome of a request, that has been sent to an RPC server.
+pub struct RequestOutcome {
+	/// The index of the RPC server, that handled the request.
+	server_index: usize,
+	latency: Duration,
+	success: bool,
+}
+
 /// Manages the active websocket client.
 /// Responsible for creating request futures, subscription streams
 /// and reconnections.
@@ -85,6 +180,10 @@ struct ClientManager {
 	urls: Vec<String>,
 	active_client: Arc<JsonRpcClient>,
 	active_index: usize,
+	/// The health of the RPC servers, in the order of the `urls`.
+	health: Vec<ServerHealth>,
+	/// The time of the last connection to a new RPC server.
+	last_switch: Instant,
 }
 
 struct RelayChainSubscriptions {
@@ -94,22 +193,35 @@ struct RelayChainSubscriptions {
 }
 
 /// Try to find a new RPC server to connect to.
+///
+/// The servers are tried in the [`connection_order`].
 async fn connect_next_available_rpc_server(
-	urls: &Vec<String>,
-	starting_position: usize,
+	urls: &[String],
+	health: &mut [ServerHealth],
+	active: Option<usize>,
 ) -> Result<(usize, Arc<JsonRpcClient>), ()> {
-	tracing::debug!(target: LOG_TARGET, starting_position, "Connecting to RPC server.");
-	for (counter, url) in urls.iter().cycle().skip(starting_position).take(urls.len()).enumerate() {
-		let index = (starting_position + counter) % urls.len();
+	tracing::debug!(target: LOG_TARGET, ?active, "Connecting to RPC server.");
+	for index in connection_order(health, active) {
+		let url = &urls[index];
 		tracing::info!(
 			target: LOG_TARGET,
 			index,
 			url,
 			"Trying to connect to next external relaychain node.",
 		);
-		match WsClientBuilder::default().build(&url).await {
-			Ok(ws_client) => return Ok((index, Arc::new(ws_client))),
-			Err(err) => tracing::debug!(target: LOG_TARGET, url, ?err, "Unable to connect."),
+		match WsClientBuilder::default().build(url).await {
+			Ok(ws_client) => {
+				let server_health = &mut health[index];
+				server_health.record_result(true);
+				// The block numbers are reported again by the new subscriptions.
+				server_health.best_number = 0;
+				server_health.finalized_number = 0;
+				return Ok((index, Arc::new(ws_client)))
+			},
+			Err(err) => {
+				health[index].record_result(false);
+				tracing::debug!(target: LOG_TARGET, url, ?err, "Unable to connect.")
+			},
 		};
 	}
 	Err(())
@@ -120,18 +232,73 @@ impl ClientManager {
 		if urls.is_empty() {
 			return Err(())
 		}
-		let active_client = connect_next_available_rpc_server(&urls, 0).await?;
-		Ok(Self { urls, active_client: active_client.1, active_index: active_client.0 })
+		let mut health = vec![ServerHealth::default(); urls.len()];
+		let active_client = connect_next_available_rpc_server(&urls, &mut health, None).await?;
+		Ok(Self {
+			urls,
+			active_client: active_client.1,
+			active_index: active_client.0,
+			health,
+			last_switch: Instant::now(),
+		})
 	}
 
 	pub async fn connect_to_new_rpc_server(&mut self) -> Result<(), ()> {
-		let new_active =
-			connect_next_available_rpc_server(&self.urls, self.active_index + 1).await?;
+		let new_active = connect_next_available_rpc_server(
+			&self.urls,
+			&mut self.health,
+			Some(self.active_index),
+		)
+		.await?;
 		self.active_client = new_active.1;
 		self.active_index = new_active.0;
+		self.last_switch = Instant::now();
 		Ok(())
 	}
 
+	/// Record the outcome of a request.
+	fn record_request(&mut self, outcome: RequestOutcome) {
+		if let Some(health) = self.health.get_mut(outcome.server_index) {
+			health.record_result(outcome.success);
+			health.record_latency(outcome.latency);
+		}
+	}
+
+	/// Record a new best block of the active RPC server.
+	fn record_best_number(&mut self, number: RelayNumber) {
+		let health = &mut self.health[self.active_index];
+		health.best_number = health.best_number.max(number);
+	}
+
+	/// Record a new finalized block of the active RPC server.
+	fn record_finalized_number(&mut self, number: RelayNumber) {
+		let health = &mut self.health[self.active_index];
+		health.finalized_number = health.finalized_number.max(number);
+	}
+
+	/// Returns `true` if the active RPC server is unhealthy and we should switch to another one.
+	///
+	/// To prevent switching back and forth, we switch at most once per
+	/// [`MIN_TIME_BETWEEN_SWITCHES`].
+	fn should_switch(&self) -> bool {
+		let health = &self.health[self.active_index];
+		if self.urls.len() < 2 ||
+			health.is_healthy() ||
+			self.last_switch.elapsed() < MIN_TIME_BETWEEN_SWITCHES
+		{
+			return false
+		}
+
+		tracing::warn!(
+			target: LOG_TARGET,
+			url = self.urls[self.active_index],
+			error_rate = health.error_rate,
+			finality_lag = health.finality_lag(),
+			"External relaychain node is unhealthy, switching to another one.",
+		);
+		true
+	}
+
 	async fn get_subscriptions(&self) -> Result<RelayChainSubscriptions, JsonRpseeError> {
 		let import_subscription = <JsonRpcClient as ChainApiClient<
 			RelayNumber,
@@ -196,10 +363,14 @@ impl ClientManager {
 		method: String,
 		params: ArrayParams,
 		response_sender: OneshotSender<Result<JsonValue, JsonRpseeError>>,
-	) -> BoxFuture<'static, Result<(), RpcDispatcherMessage>> {
+	) -> BoxFuture<'static, Result<RequestOutcome, RpcDispatcherMessage>> {
 		let future_client = self.active_client.clone();
+		let server_index = self.active_index;
 		async move {
+			let started = Instant::now();
 			let resp = future_client.request(&method, params.clone()).await;
+			let outcome =
+				RequestOutcome { server_index, latency: started.elapsed(), success: resp.is_ok() };
 
 			// We should only return the original request in case
 			// the websocket connection is dead and requires a restart.
@@ -215,10 +386,79 @@ impl ClientManager {
 					"Recipient no longer interested in request result"
 				);
 			}
-			Ok(())
+			Ok(outcome)
 		}
 		.boxed()
 	}
+
+	/// Create a request future that performs a batch of RPC requests and sends the results to the
+	/// caller. Like [`Self::create_request`], it returns the original requests in case of a dead
+	/// websocket connection.
+	fn create_batch_request(
+		&self,
+		requests: Vec<(String, ArrayParams)>,
+		response_sender: OneshotSender<Result<Vec<JsonValue>, JsonRpseeError>>,
+	) -> BoxFuture<'static, Result<RequestOutcome, RpcDispatcherMessage>> {
+		let future_client = self.active_client.clone();
+		let server_index = self.active_index;
+		async move {
+			let started = Instant::now();
+			let resp = batch_request(&future_client, &requests).await;
+			let outcome =
+				RequestOutcome { server_index, latency: started.elapsed(), success: resp.is_ok() };
+
+			if let Err(JsonRpseeError::RestartNeeded(_)) = resp {
+				return Err(RpcDispatcherMessage::BatchRequest(requests, response_sender))
+			}
+
+			if let Err(err) = response_sender.send(resp) {
+				tracing::debug!(
+					target: LOG_TARGET,
+					?err,
+					"Recipient no longer interested in request result"
+				);
+			}
+			Ok(outcome)
+		}
+		.boxed()
+	}
+
+	/// Create the request future for the given `message`.
+	///
+	/// Returns `None` if the `message` is not a request.
+	fn create_request_for(
+		&self,
+		message: RpcDispatcherMessage,
+	) -> Option<BoxFuture<'static, Result<RequestOutcome, RpcDispatcherMessage>>> {
+		match message {
+			RpcDispatcherMessage::Request(method, params, response_sender) =>
+				Some(self.create_request(method, params, response_sender)),
+			RpcDispatcherMessage::BatchRequest(requests, response_sender) =>
+				Some(self.create_batch_request(requests, response_sender)),
+			_ => None,
+		}
+	}
+}
+
+/// Perform the `requests` as one batch.
+///
+/// Fails with the error of the first failed request, if any of the requests fails.
+async fn batch_request(
+	client: &JsonRpcClient,
+	requests: &[(String, ArrayParams)],
+) -> Result<Vec<JsonValue>, JsonRpseeError> {
+	let mut batch = BatchRequestBuilder::new();
+	for (method, params) in requests {
+		batch.insert(method, params.clone())?;
+	}
+
+	let responses = client.batch_request::<JsonValue>(batch).await?;
+	let values: Vec<_> = responses
+		.into_iter()
+		.collect::<Result<_, _>>()
+		.map_err(|error| JsonRpseeError::Call(CallError::Custom(error.into_owned())))?;
+
+	Ok(values)
 }
 
 enum ConnectionStatus {
@@ -240,29 +480,58 @@ impl ReconnectingWebsocketWorker {
 			imported_header_listeners: Vec::new(),
 			finalized_header_listeners: Vec::new(),
 			best_header_listeners: Vec::new(),
+			storage_listeners: Vec::new(),
+			storage_subscriptions: SelectAll::new(),
 		};
 		(worker, tx)
 	}
 
+	/// Subscribe the `listener` to the changes of the storage `keys` at the current RPC server.
+	async fn subscribe_storage(
+		&mut self,
+		client_manager: &ClientManager,
+		keys: Vec<StorageKey>,
+		listener: Sender<RelayHash>,
+	) -> Result<(), JsonRpseeError> {
+		let subscription = subscribe_storage(&client_manager.active_client, keys, listener)
+			.await
+			.map_err(|e| {
+			tracing::error!(
+				target: LOG_TARGET,
+				?e,
+				"Unable to open `state_subscribeStorage` subscription."
+			);
+			e
+		})?;
+		self.storage_subscriptions.push(subscription);
+		Ok(())
+	}
+
 	/// Reconnect via [`ClientManager`] and provide new notification streams.
 	async fn handle_reconnect(
 		&mut self,
 		client_manager: &mut ClientManager,
 		pending_requests: &mut FuturesUnordered<
-			BoxFuture<'static, Result<(), RpcDispatcherMessage>>,
+			BoxFuture<'static, Result<RequestOutcome, RpcDispatcherMessage>>,
 		>,
 		first_failed_request: Option<RpcDispatcherMessage>,
 	) -> Result<RelayChainSubscriptions, String> {
 		let mut requests_to_retry = Vec::new();
-		if let Some(req @ RpcDispatcherMessage::Request(_, _, _)) = first_failed_request {
+		if let Some(
+			req @ (RpcDispatcherMessage::Request(_, _, _) |
+			RpcDispatcherMessage::BatchRequest(_, _)),
+		) = first_failed_request
+		{
 			requests_to_retry.push(req);
 		}
 
 		// At this point, all pending requests will return an error since the
 		// websocket connection is dead. So draining the pending requests should be fast.
 		while !pending_requests.is_empty() {
-			if let Some(Err(req)) = pending_requests.next().await {
-				requests_to_retry.push(req);
+			match pending_requests.next().await {
+				Some(Err(req)) => requests_to_retry.push(req),
+				Some(Ok(outcome)) => client_manager.record_request(outcome),
+				None => (),
 			}
 		}
 
@@ -271,18 +540,26 @@ impl ReconnectingWebsocketWorker {
 		};
 
 		for item in requests_to_retry.into_iter() {
-			if let RpcDispatcherMessage::Request(method, params, response_sender) = item {
-				pending_requests.push(client_manager.create_request(
-					method,
-					params,
-					response_sender,
-				));
-			};
+			if let Some(request) = client_manager.create_request_for(item) {
+				pending_requests.push(request);
+			}
 		}
 
-		client_manager.get_subscriptions().await.map_err(|e| {
+		let subscriptions = client_manager.get_subscriptions().await.map_err(|e| {
 			format!("Not able to create streams from newly connected RPC server, shutting down. err: {:?}", e)
-		})
+		})?;
+
+		// The storage subscriptions of the previous RPC server are dropped, the listeners are
+		// subscribed at the new one.
+		self.storage_subscriptions = SelectAll::new();
+		self.storage_listeners.retain(|(_, listener)| !listener.is_closed());
+		for (keys, listener) in self.storage_listeners.clone() {
+			self.subscribe_storage(client_manager, keys, listener).await.map_err(|e| {
+				format!("Not able to subscribe storage at newly connected RPC server, shutting down. err: {:?}", e)
+			})?;
+		}
+
+		Ok(subscriptions)
 	}
 
 	/// Run this worker to drive notification streams.
@@ -294,6 +571,8 @@ impl ReconnectingWebsocketWorker {
 	///   the sender from the list.
 	/// - Find a new valid RPC server to connect to in case the websocket connection is terminated.
 	///   If the worker is not able to connec to an RPC server from the list, the worker shuts down.
+	/// - Track the health of the RPC servers and switch to the healthiest other server, if the
+	///   current one has a high error rate or lags behind on finality.
 	pub async fn run(mut self) {
 		let mut pending_requests = FuturesUnordered::new();
 
@@ -337,6 +616,11 @@ impl ReconnectingWebsocketWorker {
 				should_reconnect = ConnectionStatus::Connected;
 			}
 
+			if client_manager.should_switch() {
+				should_reconnect = ConnectionStatus::ReconnectRequired(None);
+				continue
+			}
+
 			tokio::select! {
 				evt = self.client_receiver.recv() => match evt {
 					Some(RpcDispatcherMessage::RegisterBestHeadListener(tx)) => {
@@ -348,17 +632,36 @@ impl ReconnectingWebsocketWorker {
 					Some(RpcDispatcherMessage::RegisterFinalizationListener(tx)) => {
 						self.finalized_header_listeners.push(tx)
 					},
+					Some(RpcDispatcherMessage::RegisterStorageListener(keys, tx)) => {
+						self.storage_listeners.push((keys.clone(), tx.clone()));
+						if self.subscribe_storage(&client_manager, keys, tx).await.is_err() {
+							should_reconnect = ConnectionStatus::ReconnectRequired(None);
+						}
+					},
 					Some(RpcDispatcherMessage::Request(method, params, response_sender)) => {
 						pending_requests.push(client_manager.create_request(method, params, response_sender));
 					},
+					Some(RpcDispatcherMessage::BatchRequest(requests, response_sender)) => {
+						pending_requests.push(client_manager.create_batch_request(requests, response_sender));
+					},
 					None => {
 						tracing::error!(target: LOG_TARGET, "RPC client receiver closed. Stopping RPC Worker.");
 						return;
 					}
 				},
 				should_retry = pending_requests.next(), if !pending_requests.is_empty() => {
-					if let Some(Err(req)) = should_retry {
-						should_reconnect = ConnectionStatus::ReconnectRequired(Some(req));
+					match should_retry {
+						Some(Err(req)) => {
+							should_reconnect = ConnectionStatus::ReconnectRequired(Some(req));
+						},
+						Some(Ok(outcome)) => client_manager.record_request(outcome),
+						None => (),
+					}
+				},
+				storage_event = self.storage_subscriptions.next(), if !self.storage_subscriptions.is_empty() => {
+					if let Some(Err(error)) = storage_event {
+						tracing::error!(target: LOG_TARGET, ?error, "Error in RPC subscription.");
+						should_reconnect = ConnectionStatus::ReconnectRequired(None);
 					}
 				},
 				import_event = subscriptions.import_subscription.next() => {
@@ -389,7 +692,10 @@ impl ReconnectingWebsocketWorker {
 				},
 				best_header_event = subscriptions.best_subscription.next() => {
 					match best_header_event {
-						Some(Ok(header)) => distribute_header(header, &mut self.best_header_listeners),
+						Some(Ok(header)) => {
+							client_manager.record_best_number(header.number);
+							distribute_header(header, &mut self.best_header_listeners)
+						},
 						None => {
 							tracing::error!(target: LOG_TARGET, "Subscription closed.");
 							should_reconnect = ConnectionStatus::ReconnectRequired(None);
@@ -401,6 +707,9 @@ impl ReconnectingWebsocketWorker {
 					}
 				}
 				finalized_event = subscriptions.finalized_subscription.next() => {
+					if let Some(Ok(header)) = &finalized_event {
+						client_manager.record_finalized_number(header.number);
+					}
 					match finalized_event {
 						Some(Ok(header)) if header.number > last_seen_finalized_num => {
 							last_seen_finalized_num = header.number;
@@ -431,9 +740,56 @@ impl ReconnectingWebsocketWorker {
 
 #[cfg(test)]
 mod test {
-	use super::url_to_string_with_port;
+	use super::{connection_order, url_to_string_with_port, ServerHealth, MAX_FINALITY_LAG};
+	use std::time::Duration;
 	use url::Url;
 
+	fn health(latency_ms: u64, failures: usize, finality_lag: u32) -> ServerHealth {
+		let mut health = ServerHealth::default();
+		health.record_latency(Duration::from_millis(latency_ms));
+		(0..failures).for_each(|_| health.record_result(false));
+		health.best_number = 100 + finality_lag;
+		health.finalized_number = 100;
+		health
+	}
+
+	#[test]
+	fn server_health_detects_unhealthy_servers() {
+		assert!(health(10, 0, 0).is_healthy());
+		assert!(health(10, 0, MAX_FINALITY_LAG).is_healthy());
+		assert!(!health(10, 0, MAX_FINALITY_LAG + 1).is_healthy());
+		assert!(health(10, 5, 0).is_healthy());
+		assert!(!health(10, 10, 0).is_healthy());
+
+		// Successful requests restore the health.
+		let mut recovering = health(10, 10, 0);
+		(0..10).for_each(|_| recovering.record_result(true));
+		assert!(recovering.is_healthy());
+	}
+
+	#[test]
+	fn connection_order_prefers_healthy_servers_with_low_score() {
+		let servers = vec![
+			health(50, 0, 0),
+			health(10, 0, 0),
+			health(10, 0, MAX_FINALITY_LAG + 1),
+			health(10, 20, 0),
+			health(20, 0, 0),
+		];
+
+		assert_eq!(connection_order(&servers, None), vec![1, 4, 0, 3, 2]);
+		// The active server is tried last, even if it is the healthiest one.
+		assert_eq!(connection_order(&servers, Some(1)), vec![4, 0, 3, 2, 1]);
+	}
+
+	#[test]
+	fn connection_order_without_statistics_keeps_the_order_of_the_urls() {
+		let servers = vec![ServerHealth::default(); 3];
+
+		assert_eq!(connection_order(&servers, None), vec![0, 1, 2]);
+		assert_eq!(connection_order(&servers, Some(0)), vec![1, 2, 0]);
+	}
+
 	#[test]
 	fn url_to_string_works() {
 		let url = Url::parse("wss://something/path").unwrap();
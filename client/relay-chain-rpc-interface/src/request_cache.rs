@@ -0,0 +1,173 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Cache of the responses to the RPC requests, that query the state of a specific relay block.
+//!
+//! The state of a relay block never changes, so the response to a runtime API call or a storage
+//! query at a given block hash can be reused.
+
+use cumulus_primitives_core::relay_chain::Hash as RelayHash;
+use lru::LruCache;
+use serde_json::Value as JsonValue;
+use sp_storage::StorageKey;
+use std::{
+	num::NonZeroUsize,
+	sync::{Arc, Mutex},
+};
+use substrate_prometheus_endpoint::{register, Counter, PrometheusError, Registry, U64};
+
+/// The number of the responses, that are kept in the cache.
+const CACHE_SIZE: usize = 256;
+
+/// A request, that queries the state of a relay block.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum CachedRequest {
+	/// A call of the runtime API function `method` with the encoded `payload`.
+	RuntimeApi { method: String, payload: Vec<u8> },
+	/// A read proof of the given storage keys.
+	ReadProof(Vec<StorageKey>),
+	/// The value of the given storage key.
+	Storage(StorageKey),
+}
+
+/// Metrics of the [`RequestCache`].
+#[derive(Clone)]
+struct Metrics {
+	hits: Counter<U64>,
+	misses: Counter<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			hits: register(
+				Counter::new(
+					"cumulus_relay_chain_rpc_cache_hits",
+					"Number of the relay chain RPC requests, that were answered from the cache",
+				)?,
+				registry,
+			)?,
+			misses: register(
+				Counter::new(
+					"cumulus_relay_chain_rpc_cache_misses",
+					"Number of the cacheable relay chain RPC requests, that were sent to the server",
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// LRU cache of the responses to the [`CachedRequest`]s, keyed by the relay block hash.
+///
+/// Clones share the same cache.
+#[derive(Clone)]
+pub(crate) struct RequestCache {
+	responses: Arc<Mutex<LruCache<(RelayHash, CachedRequest), JsonValue>>>,
+	metrics: Option<Metrics>,
+}
+
+impl RequestCache {
+	/// Create a new [`RequestCache`] and register its metrics at the `prometheus_registry`.
+	pub fn new(prometheus_registry: Option<&Registry>) -> Self {
+		let metrics = prometheus_registry.and_then(|registry| {
+			Metrics::register(registry)
+				.map_err(|error| {
+					tracing::warn!(
+						target: crate::LOG_TARGET,
+						?error,
+						"Failed to register the metrics of the RPC request cache",
+					)
+				})
+				.ok()
+		});
+
+		Self {
+			responses: Arc::new(Mutex::new(LruCache::new(
+				NonZeroUsize::new(CACHE_SIZE).expect("cache size is not zero; qed"),
+			))),
+			metrics,
+		}
+	}
+
+	/// Returns the cached response to the `request` at the relay block `at`.
+	pub fn get(&self, at: RelayHash, request: &CachedRequest) -> Option<JsonValue> {
+		// The request is only cloned for the lookup, as `LruCache` requires the full key.
+		let response = self
+			.responses
+			.lock()
+			.expect("poisoned lock")
+			.get(&(at, request.clone()))
+			.cloned();
+
+		if let Some(metrics) = &self.metrics {
+			if response.is_some() {
+				metrics.hits.inc();
+			} else {
+				metrics.misses.inc();
+			}
+		}
+
+		response
+	}
+
+	/// Cache the `response` to the `request` at the relay block `at`.
+	pub fn insert(&self, at: RelayHash, request: CachedRequest, response: JsonValue) {
+		self.responses.lock().expect("poisoned lock").put((at, request), response);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn responses_are_cached_per_block_and_request() {
+		let cache = RequestCache::new(None);
+		let request = CachedRequest::RuntimeApi { method: "Api_method".into(), payload: vec![1] };
+		let block = RelayHash::repeat_byte(1);
+
+		assert_eq!(cache.get(block, &request), None);
+		cache.insert(block, request.clone(), JsonValue::from("response"));
+		assert_eq!(cache.get(block, &request), Some(JsonValue::from("response")));
+
+		// Other blocks and other payloads are not answered from the cache.
+		assert_eq!(cache.get(RelayHash::repeat_byte(2), &request), None);
+		let other_request =
+			CachedRequest::RuntimeApi { method: "Api_method".into(), payload: vec![2] };
+		assert_eq!(cache.get(block, &other_request), None);
+
+		// Clones share the cache.
+		assert_eq!(cache.clone().get(block, &request), Some(JsonValue::from("response")));
+	}
+
+	#[test]
+	fn cache_hits_are_counted() {
+		let registry = Registry::new();
+		let cache = RequestCache::new(Some(&registry));
+		let request = CachedRequest::Storage(StorageKey(vec![1, 2, 3]));
+		let block = RelayHash::repeat_byte(1);
+
+		cache.get(block, &request);
+		cache.insert(block, request.clone(), JsonValue::Null);
+		cache.get(block, &request);
+		cache.get(block, &request);
+
+		let metrics = cache.metrics.expect("metrics are registered");
+		assert_eq!(metrics.hits.get(), 2);
+		assert_eq!(metrics.misses.get(), 1);
+	}
+}
@@ -14,12 +14,17 @@
 // You should have received a copy of the GNU General Public License
 // along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
 
-use futures::channel::{
-	mpsc::{Receiver, Sender},
-	oneshot::Sender as OneshotSender,
+use futures::{
+	channel::{
+		mpsc::{Receiver, Sender},
+		oneshot::Sender as OneshotSender,
+	},
+	future,
+	stream::BoxStream,
+	StreamExt,
 };
 use jsonrpsee::{
-	core::{params::ArrayParams, Error as JsonRpseeError},
+	core::{client::Client as JsonRpseeClient, params::ArrayParams, Error as JsonRpseeError},
 	rpc_params,
 };
 use serde::de::DeserializeOwned;
@@ -40,25 +45,43 @@ use cumulus_primitives_core::{
 	},
 	InboundDownwardMessage, ParaId, PersistedValidationData,
 };
-use cumulus_relay_chain_interface::{RelayChainError, RelayChainResult};
+use cumulus_relay_chain_interface::{RelayChainError, RelayChainResult, RuntimeApiCall};
 
 use sc_client_api::StorageData;
-use sc_rpc_api::{state::ReadProof, system::Health};
+use sc_rpc_api::{
+	state::{ReadProof, StateApiClient},
+	system::Health,
+};
 use sc_service::TaskManager;
 use sp_api::RuntimeVersion;
 use sp_consensus_babe::Epoch;
 use sp_core::sp_std::collections::btree_map::BTreeMap;
 use sp_storage::StorageKey;
+use substrate_prometheus_endpoint::Registry;
 
 use crate::{
 	light_client_worker::{build_smoldot_client, LightClientRpcWorker},
 	reconnecting_ws_client::ReconnectingWebsocketWorker,
+	request_cache::{CachedRequest, RequestCache},
 };
 pub use url::Url;
 
 const LOG_TARGET: &str = "relay-chain-rpc-client";
 const NOTIFICATION_CHANNEL_SIZE_LIMIT: usize = 20;
 
+/// Runtime API functions with side effects, whose results must not be cached.
+const UNCACHED_RUNTIME_FUNCTIONS: &[&str] =
+	&["ParachainHost_submit_pvf_check_statement", "ParachainHost_submit_report_dispute_lost"];
+
+/// Returns the [`CachedRequest`] of a call of the runtime API function `method`, if its result
+/// can be cached.
+fn cached_runtime_api_request(method: &str, payload: &[u8]) -> Option<CachedRequest> {
+	(!UNCACHED_RUNTIME_FUNCTIONS.contains(&method)).then(|| CachedRequest::RuntimeApi {
+		method: method.to_string(),
+		payload: payload.to_vec(),
+	})
+}
+
 /// Messages for communication between [`RelayChainRpcClient`] and the RPC workers.
 #[derive(Debug)]
 pub enum RpcDispatcherMessage {
@@ -74,12 +97,22 @@ pub enum RpcDispatcherMessage {
 	/// used to send incoming headers.
 	RegisterFinalizationListener(Sender<RelayHeader>),
 
+	/// Register new listener for the changes of the given storage keys. Contains a sender which
+	/// will be used to send the hashes of the blocks, at which at least one of the values changed.
+	RegisterStorageListener(Vec<StorageKey>, Sender<RelayHash>),
+
 	/// Register new listener for the finalized headers stream.
 	/// Contains the following:
 	/// - [`String`] representing the RPC method to be called
 	/// - [`ArrayParams`] for the parameters to the RPC call
 	/// - [`OneshotSender`] for the return value of the request
 	Request(String, ArrayParams, OneshotSender<Result<JsonValue, JsonRpseeError>>),
+
+	/// Perform several RPC requests in one batch.
+	/// Contains the following:
+	/// - The RPC methods to be called with their parameters
+	/// - [`OneshotSender`] for the return values of the requests, in the order of the requests
+	BatchRequest(Vec<(String, ArrayParams)>, OneshotSender<Result<Vec<JsonValue>, JsonRpseeError>>),
 }
 
 /// Entry point to create [`RelayChainRpcClient`] and start a worker that communicates
@@ -87,6 +120,7 @@ pub enum RpcDispatcherMessage {
 pub async fn create_client_and_start_worker(
 	urls: Vec<Url>,
 	task_manager: &mut TaskManager,
+	prometheus_registry: Option<&Registry>,
 ) -> RelayChainResult<RelayChainRpcClient> {
 	let (worker, sender) = ReconnectingWebsocketWorker::new(urls).await;
 
@@ -94,7 +128,7 @@ pub async fn create_client_and_start_worker(
 		.spawn_essential_handle()
 		.spawn("relay-chain-rpc-worker", None, worker.run());
 
-	let client = RelayChainRpcClient::new(sender);
+	let client = RelayChainRpcClient::new(sender, prometheus_registry);
 
 	Ok(client)
 }
@@ -104,6 +138,7 @@ pub async fn create_client_and_start_worker(
 pub async fn create_client_and_start_light_client_worker(
 	chain_spec: String,
 	task_manager: &mut TaskManager,
+	prometheus_registry: Option<&Registry>,
 ) -> RelayChainResult<RelayChainRpcClient> {
 	let (client, chain_id, json_rpc_responses) =
 		build_smoldot_client(task_manager.spawn_handle(), &chain_spec).await?;
@@ -113,7 +148,7 @@ pub async fn create_client_and_start_light_client_worker(
 		.spawn_essential_handle()
 		.spawn("relay-light-client-worker", None, worker.run());
 
-	let client = RelayChainRpcClient::new(sender);
+	let client = RelayChainRpcClient::new(sender, prometheus_registry);
 
 	Ok(client)
 }
@@ -123,6 +158,8 @@ pub async fn create_client_and_start_light_client_worker(
 pub struct RelayChainRpcClient {
 	/// Sender to send messages to the worker.
 	worker_channel: TokioSender<RpcDispatcherMessage>,
+	/// Cache of the responses to the requests, that query the state of a specific relay block.
+	cache: RequestCache,
 }
 
 impl RelayChainRpcClient {
@@ -130,11 +167,16 @@ impl RelayChainRpcClient {
 	///
 	/// This client expects a channel connected to a worker that processes
 	/// requests sent via this channel.
-	pub(crate) fn new(worker_channel: TokioSender<RpcDispatcherMessage>) -> Self {
-		RelayChainRpcClient { worker_channel }
+	pub(crate) fn new(
+		worker_channel: TokioSender<RpcDispatcherMessage>,
+		prometheus_registry: Option<&Registry>,
+	) -> Self {
+		RelayChainRpcClient { worker_channel, cache: RequestCache::new(prometheus_registry) }
 	}
 
 	/// Call a call to `state_call` rpc method.
+	///
+	/// The results of runtime API functions without side effects are cached per block.
 	pub async fn call_remote_runtime_function<R: Decode>(
 		&self,
 		method_name: &str,
@@ -143,25 +185,131 @@ impl RelayChainRpcClient {
 	) -> RelayChainResult<R> {
 		let payload_bytes =
 			payload.map_or(sp_core::Bytes(Vec::new()), |v| sp_core::Bytes(v.encode()));
+		let cached_request = cached_runtime_api_request(method_name, &payload_bytes.0);
 		let params = rpc_params! {
 			method_name,
 			payload_bytes,
 			hash
 		};
 		let res = self
-			.request_tracing::<sp_core::Bytes, _>("state_call", params, |err| {
-				tracing::trace!(
-					target: LOG_TARGET,
-					%method_name,
-					%hash,
-					error = %err,
-					"Error during call to 'state_call'.",
-				);
-			})
+			.cached_request_tracing::<sp_core::Bytes, _>(
+				hash,
+				cached_request,
+				"state_call",
+				params,
+				|err| {
+					tracing::trace!(
+						target: LOG_TARGET,
+						%method_name,
+						%hash,
+						error = %err,
+						"Error during call to 'state_call'.",
+					);
+				},
+			)
 			.await?;
 		Decode::decode(&mut &*res.0).map_err(Into::into)
 	}
 
+	/// Call several runtime API functions at the relay block `hash` with one batch of `state_call`
+	/// requests.
+	///
+	/// Only the calls, whose results are not cached yet, are sent. Returns the SCALE encoded
+	/// results in the order of the `calls`.
+	pub async fn call_remote_runtime_function_batch(
+		&self,
+		hash: RelayHash,
+		calls: Vec<RuntimeApiCall>,
+	) -> RelayChainResult<Vec<Vec<u8>>> {
+		let cached_requests: Vec<_> = calls
+			.iter()
+			.map(|call| cached_runtime_api_request(&call.method, &call.payload))
+			.collect();
+		let mut responses: Vec<_> = cached_requests
+			.iter()
+			.map(|request| request.as_ref().and_then(|request| self.cache.get(hash, request)))
+			.collect();
+
+		let missing: Vec<_> =
+			(0..calls.len()).filter(|index| responses[*index].is_none()).collect();
+		if !missing.is_empty() {
+			let requests = missing
+				.iter()
+				.map(|index| {
+					let call = &calls[*index];
+					let payload_bytes = sp_core::Bytes(call.payload.clone());
+					(
+						"state_call".to_string(),
+						rpc_params![call.method.as_str(), payload_bytes, hash],
+					)
+				})
+				.collect();
+			let values = self.batch_request_value(requests).await?;
+			if values.len() != missing.len() {
+				return Err(RelayChainError::RpcCallError("state_call".to_string()))
+			}
+
+			for (index, value) in missing.into_iter().zip(values) {
+				if let Some(request) = cached_requests[index].clone() {
+					self.cache.insert(hash, request, value.clone());
+				}
+				responses[index] = Some(value);
+			}
+		}
+
+		responses
+			.into_iter()
+			.zip(&calls)
+			.map(|(response, call)| {
+				let res: sp_core::Bytes = Self::deserialize_response(
+					"state_call",
+					response.expect("the missing responses have been fetched; qed"),
+					|err| {
+						tracing::trace!(
+							target: LOG_TARGET,
+							method_name = %call.method,
+							%hash,
+							error = %err,
+							"Error during batched call to 'state_call'.",
+						);
+					},
+				)?;
+				Ok(res.0)
+			})
+			.collect()
+	}
+
+	/// Perform RPC request, whose response is cached for the relay block `at`.
+	///
+	/// The request is always sent, if there is no `cached_request`.
+	async fn cached_request_tracing<'a, R, OR>(
+		&self,
+		at: RelayHash,
+		cached_request: Option<CachedRequest>,
+		method: &'a str,
+		params: ArrayParams,
+		trace_error: OR,
+	) -> Result<R, RelayChainError>
+	where
+		R: DeserializeOwned + std::fmt::Debug,
+		OR: Fn(&RelayChainError),
+	{
+		let cached_response =
+			cached_request.as_ref().and_then(|request| self.cache.get(at, request));
+		let value = match cached_response {
+			Some(value) => value,
+			None => {
+				let value = self.request_value(method, params).await?;
+				if let Some(request) = cached_request {
+					self.cache.insert(at, request, value.clone());
+				}
+				value
+			},
+		};
+
+		Self::deserialize_response(method, value, trace_error)
+	}
+
 	/// Perform RPC request
 	async fn request<'a, R>(
 		&self,
@@ -179,6 +327,27 @@ impl RelayChainRpcClient {
 		.await
 	}
 
+	/// Perform RPC request, whose response is cached, if it queries the relay block `at`.
+	async fn cached_request<'a, R>(
+		&self,
+		method: &'a str,
+		params: ArrayParams,
+		at: Option<RelayHash>,
+		cached_request: CachedRequest,
+	) -> Result<R, RelayChainError>
+	where
+		R: DeserializeOwned + std::fmt::Debug,
+	{
+		let trace_error = |e: &RelayChainError| tracing::trace!(target:LOG_TARGET, error = %e, %method, "Unable to complete RPC request");
+		match at {
+			Some(at) =>
+				self.cached_request_tracing(at, Some(cached_request), method, params, trace_error)
+					.await,
+			// Requests at the best block can't be cached, as the best block changes.
+			None => self.request_tracing(method, params, trace_error).await,
+		}
+	}
+
 	/// Perform RPC request
 	async fn request_tracing<'a, R, OR>(
 		&self,
@@ -190,6 +359,16 @@ impl RelayChainRpcClient {
 		R: DeserializeOwned + std::fmt::Debug,
 		OR: Fn(&RelayChainError),
 	{
+		let value = self.request_value(method, params).await?;
+		Self::deserialize_response(method, value, trace_error)
+	}
+
+	/// Send the RPC request to the worker and wait for the response.
+	async fn request_value(
+		&self,
+		method: &str,
+		params: ArrayParams,
+	) -> Result<JsonValue, RelayChainError> {
 		let (tx, rx) = futures::channel::oneshot::channel();
 
 		let message = RpcDispatcherMessage::Request(method.into(), params, tx);
@@ -207,6 +386,44 @@ impl RelayChainRpcClient {
 			))
 		})??;
 
+		Ok(value)
+	}
+
+	/// Send the RPC `requests` to the worker as one batch and wait for the responses.
+	async fn batch_request_value(
+		&self,
+		requests: Vec<(String, ArrayParams)>,
+	) -> Result<Vec<JsonValue>, RelayChainError> {
+		let (tx, rx) = futures::channel::oneshot::channel();
+
+		let message = RpcDispatcherMessage::BatchRequest(requests, tx);
+		self.worker_channel.send(message).await.map_err(|err| {
+			RelayChainError::WorkerCommunicationError(format!(
+				"Unable to send message to RPC worker: {}",
+				err
+			))
+		})?;
+
+		let values = rx.await.map_err(|err| {
+			RelayChainError::WorkerCommunicationError(format!(
+				"Unexpected channel close on RPC worker side: {}",
+				err
+			))
+		})??;
+
+		Ok(values)
+	}
+
+	/// Deserialize the response to the RPC request `method`.
+	fn deserialize_response<R, OR>(
+		method: &str,
+		value: JsonValue,
+		trace_error: OR,
+	) -> Result<R, RelayChainError>
+	where
+		R: DeserializeOwned + std::fmt::Debug,
+		OR: Fn(&RelayChainError),
+	{
 		serde_json::from_value(value).map_err(|_| {
 			trace_error(&RelayChainError::GenericError("Unable to deserialize value".to_string()));
 			RelayChainError::RpcCallError(method.to_string())
@@ -251,6 +468,17 @@ impl RelayChainRpcClient {
 		.await
 	}
 
+	/// Get the GRANDPA finality proof of the block with the given `number`.
+	///
+	/// The proof justifies the last block of the authority set of the block, or the latest
+	/// justified block, if the block is in the current authority set.
+	pub async fn grandpa_prove_finality(
+		&self,
+		number: BlockNumber,
+	) -> Result<Option<sp_core::Bytes>, RelayChainError> {
+		self.request("grandpa_proveFinality", rpc_params![number]).await
+	}
+
 	/// Get system health information
 	pub async fn system_health(&self) -> Result<Health, RelayChainError> {
 		self.request("system_health", rpc_params![]).await
@@ -262,8 +490,9 @@ impl RelayChainRpcClient {
 		storage_keys: Vec<StorageKey>,
 		at: Option<RelayHash>,
 	) -> Result<ReadProof<RelayHash>, RelayChainError> {
+		let cached_request = CachedRequest::ReadProof(storage_keys.clone());
 		let params = rpc_params![storage_keys, at];
-		self.request("state_getReadProof", params).await
+		self.cached_request("state_getReadProof", params, at, cached_request).await
 	}
 
 	/// Retrieve storage item at `storage_key`
@@ -272,8 +501,9 @@ impl RelayChainRpcClient {
 		storage_key: StorageKey,
 		at: Option<RelayHash>,
 	) -> Result<Option<StorageData>, RelayChainError> {
+		let cached_request = CachedRequest::Storage(storage_key.clone());
 		let params = rpc_params![storage_key, at];
-		self.request("state_getStorage", params).await
+		self.cached_request("state_getStorage", params, at, cached_request).await
 	}
 
 	/// Get hash of the n-th block in the canon chain.
@@ -649,6 +879,56 @@ impl RelayChainRpcClient {
 		))?;
 		Ok(rx)
 	}
+
+	/// Get a stream of the hashes of the relay chain blocks, at which the value of at least one
+	/// of the `storage_keys` changed.
+	///
+	/// Uses the `state_subscribeStorage` subscription, so the first item is the block at which
+	/// the subscription has been opened.
+	pub fn get_storage_changes_stream(
+		&self,
+		storage_keys: Vec<StorageKey>,
+	) -> Result<Receiver<RelayHash>, RelayChainError> {
+		let (tx, rx) =
+			futures::channel::mpsc::channel::<RelayHash>(NOTIFICATION_CHANNEL_SIZE_LIMIT);
+		self.send_register_message_to_worker(RpcDispatcherMessage::RegisterStorageListener(
+			storage_keys,
+			tx,
+		))?;
+		Ok(rx)
+	}
+}
+
+/// A storage subscription, that forwards the hashes of the blocks, at which the subscribed storage
+/// changed, to its listener.
+///
+/// The stream needs to be polled to forward the notifications. It ends once the listener is
+/// dropped.
+pub type StorageSubscription = BoxStream<'static, Result<(), JsonRpseeError>>;
+
+/// Open a `state_subscribeStorage` subscription of the `storage_keys` at the `client`, that
+/// forwards its notifications to the `listener`.
+pub async fn subscribe_storage(
+	client: &JsonRpseeClient,
+	storage_keys: Vec<StorageKey>,
+	listener: Sender<RelayHash>,
+) -> Result<StorageSubscription, JsonRpseeError> {
+	let subscription =
+		StateApiClient::<RelayHash>::subscribe_storage(client, Some(storage_keys)).await?;
+
+	let mut sender = listener.clone();
+	Ok(subscription
+		.take_while(move |_| future::ready(!listener.is_closed()))
+		.map(move |change_set| {
+			change_set.map(|change_set| {
+				if let Err(error) = sender.try_send(change_set.block) {
+					if !error.is_disconnected() {
+						tracing::error!(target: LOG_TARGET, ?error, "Storage change distribution channel has reached its limit. This can lead to missed notifications.");
+					}
+				}
+			})
+		})
+		.boxed())
 }
 
 /// Send `header` through all channels contained in `senders`.
@@ -20,11 +20,21 @@
 
 use cumulus_client_cli::CollatorOptions;
 use cumulus_client_consensus_common::ParachainConsensus;
-use cumulus_client_network::{AssumeSybilResistance, RequireSecondedInBlockAnnounce};
-use cumulus_client_pov_recovery::{PoVRecovery, RecoveryDelayRange, RecoveryHandle};
-use cumulus_primitives_core::{CollectCollationInfo, ParaId};
+use cumulus_client_network::{
+	announce_backed_candidates, AssumeSybilResistance, RequireSecondedInBlockAnnounce,
+};
+use cumulus_client_pov_recovery::{
+	PeerRecovery, PeerRecoveryRequestHandler, PoVRecovery, RecoveryBacklog, RecoveryDelayRange,
+	RecoveryHandle, DEFAULT_MAX_CONCURRENT_RECOVERIES,
+};
+use cumulus_primitives_core::{
+	relay_chain::{well_known_keys, BlockId, Hash as PHash},
+	CollectCollationInfo, ParaId,
+};
 use cumulus_relay_chain_inprocess_interface::build_inprocess_relay_chain;
-use cumulus_relay_chain_interface::{RelayChainInterface, RelayChainResult};
+use cumulus_relay_chain_interface::{
+	RelayChainError, RelayChainInterface, RelayChainResult, StorageChange,
+};
 use cumulus_relay_chain_minimal_node::{
 	build_minimal_relay_chain_node_light_client, build_minimal_relay_chain_node_with_rpc,
 };
@@ -32,7 +42,7 @@ use futures::{
 	channel::{mpsc, oneshot},
 	FutureExt, StreamExt,
 };
-use polkadot_primitives::{CollatorPair, OccupiedCoreAssumption};
+use polkadot_primitives::{CollatorPair, HeadData};
 use sc_client_api::{
 	Backend as BackendT, BlockBackend, BlockchainEvents, Finalizer, ProofProvider, UsageProvider,
 };
@@ -40,7 +50,7 @@ use sc_consensus::{
 	import_queue::{ImportQueue, ImportQueueService},
 	BlockImport,
 };
-use sc_network::{config::SyncMode, NetworkService};
+use sc_network::{config::SyncMode, NetworkRequest, NetworkService};
 use sc_network_sync::SyncingService;
 use sc_network_transactions::TransactionsHandlerController;
 use sc_service::{Configuration, NetworkStarter, SpawnTaskHandle, TaskManager, WarpSyncParams};
@@ -49,8 +59,9 @@ use sc_utils::mpsc::TracingUnboundedSender;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::{HeaderBackend, HeaderMetadata};
 use sp_core::{traits::SpawnNamed, Decode};
-use sp_runtime::traits::{Block as BlockT, BlockIdTo};
+use sp_runtime::traits::{Block as BlockT, BlockIdTo, Header as HeaderT};
 use std::{sync::Arc, time::Duration};
+use substrate_prometheus_endpoint::Registry;
 
 // Given the sporadic nature of the explicit recovery operation and the
 // possibility to retry infinite times this value is more than enough.
@@ -58,6 +69,12 @@ use std::{sync::Arc, time::Duration};
 const RECOVERY_CHAN_SIZE: usize = 8;
 const LOG_TARGET_SYNC: &str = "sync::cumulus";
 
+mod sync_state;
+pub use sync_state::{
+	ChainSyncState, SyncState, SyncStateApiClient, SyncStateApiServer, SyncStateProvider,
+	INFORMANT_INTERVAL,
+};
+
 /// A hint about how long the node should wait before attempting to recover missing block data
 /// from the data availability layer.
 pub enum DARecoveryProfile {
@@ -94,6 +111,16 @@ pub struct StartRelayChainTasksParams<'a, Block: BlockT, Client, RCInterface> {
 	pub relay_chain_interface: RCInterface,
 	pub task_manager: &'a mut TaskManager,
 	pub da_recovery_profile: DARecoveryProfile,
+	/// The maximum number of PoVs, that are recovered at the same time.
+	pub pov_recovery_concurrency: usize,
+	/// The network, that is used to fetch the blocks of the candidates from the parachain peers.
+	///
+	/// If `None`, the PoVs are only recovered from the relay chain.
+	pub network: Option<Arc<dyn NetworkRequest + Send + Sync>>,
+	/// The registry, the PoV recovery metrics are registered at.
+	pub prometheus_registry: Option<&'a Registry>,
+	/// The backlog of the PoV recovery, e.g. of a [`SyncStateProvider`].
+	pub pov_recovery_backlog: Option<RecoveryBacklog>,
 	pub import_queue: Box<dyn ImportQueueService<Block>>,
 	pub relay_chain_slot_duration: Duration,
 	pub recovery_handle: Box<dyn RecoveryHandle>,
@@ -158,12 +185,28 @@ where
 		.overseer_handle()
 		.map_err(|e| sc_service::Error::Application(Box::new(e)))?;
 
+	// Announces the blocks again with the compact announce data, once they are backed.
+	task_manager.spawn_handle().spawn(
+		"cumulus-announce-backed-candidates",
+		None,
+		announce_backed_candidates(
+			client.clone(),
+			relay_chain_interface.clone(),
+			para_id,
+			announce_block.clone(),
+		),
+	);
+
 	start_relay_chain_tasks(StartRelayChainTasksParams {
 		client: client.clone(),
 		announce_block: announce_block.clone(),
 		para_id,
 		task_manager,
 		da_recovery_profile: DARecoveryProfile::Collator,
+		pov_recovery_concurrency: DEFAULT_MAX_CONCURRENT_RECOVERIES,
+		network: None,
+		prometheus_registry: None,
+		pov_recovery_backlog: None,
 		relay_chain_interface,
 		import_queue,
 		relay_chain_slot_duration,
@@ -201,6 +244,10 @@ pub fn start_relay_chain_tasks<Block, Client, Backend, RCInterface>(
 		para_id,
 		task_manager,
 		da_recovery_profile,
+		pov_recovery_concurrency,
+		network,
+		prometheus_registry,
+		pov_recovery_backlog,
 		relay_chain_interface,
 		import_queue,
 		relay_chain_slot_duration,
@@ -259,8 +306,13 @@ where
 		DARecoveryProfile::Other(profile) => profile,
 	};
 
+	let peer_recovery = network.map(|network| {
+		PeerRecovery::new(network, sync_service.clone(), client.usage_info().chain.genesis_hash)
+	});
+
 	let pov_recovery = PoVRecovery::new(
 		recovery_handle,
+		peer_recovery,
 		da_recovery_profile,
 		client.clone(),
 		import_queue,
@@ -268,6 +320,9 @@ where
 		para_id,
 		recovery_chan_rx,
 		sync_service,
+		pov_recovery_concurrency,
+		prometheus_registry,
+		pov_recovery_backlog.unwrap_or_default(),
 	);
 
 	task_manager
@@ -319,6 +374,10 @@ where
 		recovery_handle,
 		sync_service,
 		da_recovery_profile: DARecoveryProfile::FullNode,
+		pov_recovery_concurrency: DEFAULT_MAX_CONCURRENT_RECOVERIES,
+		network: None,
+		prometheus_registry: None,
+		pov_recovery_backlog: None,
 	})
 }
 
@@ -344,13 +403,25 @@ pub fn prepare_node_config(mut parachain_config: Configuration) -> Configuration
 /// Will return a minimal relay chain node with RPC
 /// client or an inprocess node, based on the [`CollatorOptions`] passed in.
 pub async fn build_relay_chain_interface(
-	relay_chain_config: Configuration,
+	mut relay_chain_config: Configuration,
 	parachain_config: &Configuration,
 	telemetry_worker_handle: Option<TelemetryWorkerHandle>,
 	task_manager: &mut TaskManager,
 	collator_options: CollatorOptions,
 	hwbench: Option<sc_sysinfo::HwBench>,
 ) -> RelayChainResult<(Arc<(dyn RelayChainInterface + 'static)>, Option<CollatorPair>)> {
+	if !matches!(collator_options.relay_chain_mode, cumulus_client_cli::RelayChainMode::Embedded) {
+		// The minimal node serves the collation protocols on the additional WebSocket addresses.
+		relay_chain_config
+			.network
+			.listen_addresses
+			.extend(collator_options.relay_chain_websocket_listen_addresses);
+		relay_chain_config
+			.network
+			.public_addresses
+			.extend(collator_options.relay_chain_websocket_public_addresses);
+	}
+
 	match collator_options.relay_chain_mode {
 		cumulus_client_cli::RelayChainMode::Embedded => build_inprocess_relay_chain(
 			relay_chain_config,
@@ -415,7 +486,7 @@ pub struct BuildNetworkParams<
 pub async fn build_network<'a, Block, Client, RCInterface, IQ>(
 	BuildNetworkParams {
 		parachain_config,
-		net_config,
+		mut net_config,
 		client,
 		transaction_pool,
 		para_id,
@@ -475,6 +546,11 @@ where
 		},
 	};
 
+	let (pov_request_handler, pov_request_protocol_config) =
+		PeerRecoveryRequestHandler::new(client.clone(), client.info().genesis_hash);
+	net_config.add_request_response_protocol(pov_request_protocol_config);
+	spawn_handle.spawn("cumulus-pov-request-handler", None, pov_request_handler.run());
+
 	sc_service::build_network(sc_service::BuildNetworkParams {
 		config: parachain_config,
 		net_config,
@@ -526,6 +602,11 @@ where
 
 /// Waits for the relay chain to have finished syncing and then gets the parachain header that
 /// corresponds to the last finalized relay chain block.
+///
+/// The head of the parachain is read from a storage proof of the finalized relay chain block, whose
+/// GRANDPA finality is verified, see [`RelayChainInterface::proven_finalized_block_hash`]. So the
+/// target block is proven by the relay chain finality. The state of the target block is then
+/// downloaded by the state sync, without importing the history of the parachain.
 async fn wait_for_target_block<B, RCInterface>(
 	sender: oneshot::Sender<<B as BlockT>::Header>,
 	para_id: ParaId,
@@ -544,22 +625,18 @@ where
 		})?;
 
 		if !is_syncing {
-			let relay_chain_best_hash = relay_chain_interface
-				.finalized_block_hash()
+			let relay_chain_finalized_hash = relay_chain_interface
+				.proven_finalized_block_hash()
 				.await
 				.map_err(|e| Box::new(e) as Box<_>)?;
 
-			let validation_data = relay_chain_interface
-				.persisted_validation_data(
-					relay_chain_best_hash,
-					para_id,
-					OccupiedCoreAssumption::TimedOut,
-				)
-				.await
-				.map_err(|e| format!("{e:?}"))?
-				.ok_or("Could not find parachain head in relay chain")?;
+			let head_data =
+				proven_para_head(&relay_chain_interface, relay_chain_finalized_hash, para_id)
+					.await
+					.map_err(|e| format!("{e:?}"))?
+					.ok_or("Could not find parachain head in relay chain")?;
 
-			let target_block = B::Header::decode(&mut &validation_data.parent_head.0[..])
+			let target_block = B::Header::decode(&mut &head_data.0[..])
 				.map_err(|e| format!("Failed to decode parachain head: {e}"))?;
 
 			log::debug!(target: LOG_TARGET_SYNC, "Target block reached {:?}", target_block);
@@ -570,3 +647,34 @@ where
 
 	Err("Stopping following imported blocks. Could not determine parachain target block".into())
 }
+
+/// Returns the head of the parachain, that is included in the relay chain block `relay_hash`.
+///
+/// The head is read from a storage proof, that is checked against the state root of the block.
+/// The `relay_hash` needs to be trusted, e.g. because its finality has been verified.
+async fn proven_para_head<RCInterface: RelayChainInterface>(
+	relay_chain_interface: &RCInterface,
+	relay_hash: PHash,
+	para_id: ParaId,
+) -> RelayChainResult<Option<HeadData>> {
+	let relay_header =
+		relay_chain_interface.header(BlockId::Hash(relay_hash)).await?.ok_or_else(|| {
+			RelayChainError::GenericError(format!("Missing relay chain header {relay_hash:?}"))
+		})?;
+	if relay_header.hash() != relay_hash {
+		return Err(RelayChainError::GenericError(format!(
+			"Relay chain header doesn't match the hash {relay_hash:?}"
+		)))
+	}
+
+	let key = well_known_keys::para_head(para_id);
+	let proof = relay_chain_interface.prove_read(relay_hash, &vec![key.clone()]).await?;
+	let change = StorageChange::from_proof(&relay_header, &[key], proof)?;
+
+	match change.values.into_iter().next().and_then(|(_, value)| value) {
+		Some(value) => HeadData::decode(&mut &value[..]).map(Some).map_err(|e| {
+			RelayChainError::GenericError(format!("Failed to decode the head data: {e}"))
+		}),
+		None => Ok(None),
+	}
+}
@@ -0,0 +1,214 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The sync state of the relay chain and the parachain.
+//!
+//! The [`SyncStateProvider`] combines the sync state of the relay chain, the parachain and the
+//! backlog of the PoV recovery. It is reported periodically by the
+//! [`run_informant`](SyncStateProvider::run_informant) and exposed via the `cumulus_syncState` RPC,
+//! so a dashboard can follow both chains with one node connection.
+
+use cumulus_client_pov_recovery::RecoveryBacklog;
+use cumulus_primitives_core::relay_chain::{BlockId, Hash as PHash};
+use cumulus_relay_chain_interface::{RelayChainError, RelayChainInterface, RelayChainResult};
+use futures_timer::Delay;
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+};
+use sc_network_sync::SyncingService;
+use sc_telemetry::log;
+use serde::{Deserialize, Serialize};
+use sp_blockchain::HeaderBackend;
+use sp_consensus::SyncOracle;
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto},
+	SaturatedConversion,
+};
+use std::{sync::Arc, time::Duration};
+
+const LOG_TARGET: &str = "cumulus-informant";
+
+/// The interval, in which the [`SyncStateProvider::run_informant`] reports the sync state.
+pub const INFORMANT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The sync state of one chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainSyncState<Hash> {
+	/// The number of the best block.
+	pub best_number: u64,
+	/// The hash of the best block.
+	pub best_hash: Hash,
+	/// The number of the finalized block.
+	pub finalized_number: u64,
+	/// The hash of the finalized block.
+	pub finalized_hash: Hash,
+	/// The number of the connected peers, if it is known.
+	pub peers: Option<usize>,
+	/// Whether the chain is in the major sync.
+	pub is_major_syncing: bool,
+}
+
+/// The sync state of the relay chain and the parachain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncState<Hash> {
+	/// The sync state of the relay chain.
+	pub relay_chain: ChainSyncState<PHash>,
+	/// The sync state of the parachain.
+	pub parachain: ChainSyncState<Hash>,
+	/// The number of the candidates, that wait for their PoV recovery.
+	pub pov_recovery_backlog: usize,
+}
+
+/// The RPC API for the [`SyncState`].
+#[rpc(client, server)]
+pub trait SyncStateApi<Hash> {
+	/// Returns the sync state of the relay chain and the parachain.
+	///
+	/// Complements `system_syncState`, which only covers the parachain.
+	#[method(name = "cumulus_syncState")]
+	async fn sync_state(&self) -> RpcResult<SyncState<Hash>>;
+}
+
+/// Provides the [`SyncState`] of the relay chain and the parachain.
+pub struct SyncStateProvider<Block: BlockT, Client> {
+	client: Arc<Client>,
+	relay_chain_interface: Arc<dyn RelayChainInterface>,
+	sync_service: Arc<SyncingService<Block>>,
+	pov_recovery_backlog: RecoveryBacklog,
+}
+
+impl<Block: BlockT, Client> Clone for SyncStateProvider<Block, Client> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+			relay_chain_interface: self.relay_chain_interface.clone(),
+			sync_service: self.sync_service.clone(),
+			pov_recovery_backlog: self.pov_recovery_backlog.clone(),
+		}
+	}
+}
+
+impl<Block, Client> SyncStateProvider<Block, Client>
+where
+	Block: BlockT,
+	Client: HeaderBackend<Block> + Send + Sync + 'static,
+{
+	/// Create a new [`SyncStateProvider`].
+	///
+	/// The [`pov_recovery_backlog`](Self::pov_recovery_backlog) needs to be passed to the
+	/// [`start_relay_chain_tasks`](crate::start_relay_chain_tasks), to report the backlog of the
+	/// PoV recovery.
+	pub fn new(
+		client: Arc<Client>,
+		relay_chain_interface: Arc<dyn RelayChainInterface>,
+		sync_service: Arc<SyncingService<Block>>,
+	) -> Self {
+		Self {
+			client,
+			relay_chain_interface,
+			sync_service,
+			pov_recovery_backlog: RecoveryBacklog::default(),
+		}
+	}
+
+	/// Returns the backlog, that is updated by the PoV recovery.
+	pub fn pov_recovery_backlog(&self) -> RecoveryBacklog {
+		self.pov_recovery_backlog.clone()
+	}
+
+	/// Returns the current [`SyncState`].
+	pub async fn sync_state(&self) -> RelayChainResult<SyncState<Block::Hash>> {
+		let info = self.client.info();
+		let parachain = ChainSyncState {
+			best_number: info.best_number.saturated_into(),
+			best_hash: info.best_hash,
+			finalized_number: info.finalized_number.saturated_into(),
+			finalized_hash: info.finalized_hash,
+			peers: Some(self.sync_service.num_connected_peers()),
+			is_major_syncing: self.sync_service.is_major_syncing(),
+		};
+
+		let best_hash = self.relay_chain_interface.best_block_hash().await?;
+		let finalized_hash = self.relay_chain_interface.finalized_block_hash().await?;
+		let relay_chain = ChainSyncState {
+			best_number: self.relay_block_number(best_hash).await?,
+			best_hash,
+			finalized_number: self.relay_block_number(finalized_hash).await?,
+			finalized_hash,
+			peers: self.relay_chain_interface.peer_count().await?,
+			is_major_syncing: self.relay_chain_interface.is_major_syncing().await?,
+		};
+
+		Ok(SyncState {
+			relay_chain,
+			parachain,
+			pov_recovery_backlog: self.pov_recovery_backlog.get(),
+		})
+	}
+
+	/// Returns the number of the relay chain block with the given `hash`.
+	async fn relay_block_number(&self, hash: PHash) -> RelayChainResult<u64> {
+		self.relay_chain_interface
+			.header(BlockId::Hash(hash))
+			.await?
+			.map(|header| header.number().unique_saturated_into())
+			.ok_or_else(|| {
+				RelayChainError::GenericError(format!("Missing relay chain header {:?}", hash))
+			})
+	}
+
+	/// Report the [`SyncState`] every [`INFORMANT_INTERVAL`].
+	pub async fn run_informant(self) {
+		loop {
+			Delay::new(INFORMANT_INTERVAL).await;
+
+			match self.sync_state().await {
+				Ok(state) => log::info!(
+					target: LOG_TARGET,
+					"{} Relay chain #{} (finalized #{}), {} peers | Parachain #{} (finalized #{}), {} peers | PoV recovery backlog: {}",
+					if state.relay_chain.is_major_syncing || state.parachain.is_major_syncing {
+						"⚙️  Syncing"
+					} else {
+						"💤 Idle"
+					},
+					state.relay_chain.best_number,
+					state.relay_chain.finalized_number,
+					state.relay_chain.peers.unwrap_or_default(),
+					state.parachain.best_number,
+					state.parachain.finalized_number,
+					state.parachain.peers.unwrap_or_default(),
+					state.pov_recovery_backlog,
+				),
+				Err(error) =>
+					log::debug!(target: LOG_TARGET, "Failed to fetch the sync state: {:?}", error),
+			}
+		}
+	}
+}
+
+#[async_trait]
+impl<Block, Client> SyncStateApiServer<Block::Hash> for SyncStateProvider<Block, Client>
+where
+	Block: BlockT,
+	Client: HeaderBackend<Block> + Send + Sync + 'static,
+{
+	async fn sync_state(&self) -> RpcResult<SyncState<Block::Hash>> {
+		SyncStateProvider::sync_state(self).await.map_err(JsonRpseeError::to_call_error)
+	}
+}
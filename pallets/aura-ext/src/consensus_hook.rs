@@ -14,8 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
 
-//! The definition of a [`FixedVelocityConsensusHook`] for consensus logic to manage
-//! block velocity.
+//! The definition of a [`FixedVelocityConsensusHook`] and a [`BurstVelocityConsensusHook`] for
+//! consensus logic to manage block velocity.
 //!
 //! The velocity `V` refers to the rate of block processing by the relay chain.
 
@@ -57,13 +57,8 @@ where
 		let (slot, authored) = pallet::Pallet::<T>::slot_info()
 			.expect("slot info is inserted on block initialization");
 
-		// Convert relay chain timestamp.
-		let relay_chain_timestamp =
-			u64::from(RELAY_CHAIN_SLOT_DURATION_MILLIS).saturating_mul(*relay_chain_slot);
-
-		let para_slot_duration = SlotDuration::from_millis(Aura::<T>::slot_duration().into());
 		let para_slot_from_relay =
-			Slot::from_timestamp(relay_chain_timestamp.into(), para_slot_duration);
+			para_slot_from_relay_slot::<T>(relay_chain_slot, RELAY_CHAIN_SLOT_DURATION_MILLIS);
 
 		// Perform checks.
 		assert_eq!(slot, para_slot_from_relay, "slot number mismatch");
@@ -81,6 +76,24 @@ where
 	}
 }
 
+/// Translates the relay chain slot to the parachain slot, that contains its start.
+///
+/// The parachain slot duration, that is in effect, is used. Thus the translation follows the slot
+/// duration changes of the pallet.
+pub fn para_slot_from_relay_slot<T: pallet::Config>(
+	relay_chain_slot: Slot,
+	relay_chain_slot_duration_millis: u32,
+) -> Slot
+where
+	<T as pallet_timestamp::Config>::Moment: Into<u64>,
+{
+	let relay_chain_timestamp =
+		u64::from(relay_chain_slot_duration_millis).saturating_mul(*relay_chain_slot);
+	let para_slot_duration = SlotDuration::from_millis(Aura::<T>::slot_duration().into());
+
+	Slot::from_timestamp(relay_chain_timestamp.into(), para_slot_duration)
+}
+
 impl<
 		T: pallet::Config + parachain_system::Config,
 		const RELAY_CHAIN_SLOT_DURATION_MILLIS: u32,
@@ -121,3 +134,133 @@ impl<
 		}
 	}
 }
+
+/// The velocity credit, that has been accumulated by the [`BurstVelocityConsensusHook`].
+#[derive(Clone, Copy, Debug, Default, Encode, Decode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+pub struct VelocityCredit {
+	/// The slot of the last block.
+	pub slot: Slot,
+	/// The credit, that is available in the `slot` on top of the velocity.
+	pub credit: u32,
+	/// The number of blocks, that have been authored in the `slot`.
+	pub authored: u32,
+}
+
+impl VelocityCredit {
+	/// Returns the credit, that is available at the beginning of the `new_slot`.
+	///
+	/// The velocity, that hasn't been used in the previous slots, is accumulated up to the
+	/// `max_credit`.
+	pub(crate) fn carried_to(&self, new_slot: Slot, velocity: u32, max_credit: u32) -> u32 {
+		let unused = velocity.saturating_add(self.credit).saturating_sub(self.authored);
+		let empty_slots = u64::from(new_slot).saturating_sub(u64::from(self.slot) + 1);
+		let empty_slots_credit =
+			u64::from(velocity).saturating_mul(empty_slots).try_into().unwrap_or(u32::MAX);
+
+		unused.saturating_add(empty_slots_credit).min(max_credit)
+	}
+}
+
+/// A consensus hook for a fixed block processing velocity, that allows bursts of blocks and a fixed
+/// unincluded segment capacity.
+///
+/// The velocity `V`, that hasn't been used in the previous slots, is credited and up to `B`
+/// credited blocks may be authored in a slot on top of the `V + 1` limit of the
+/// [`FixedVelocityConsensusHook`]. This allows chains with bursty load to catch up after a few
+/// empty slots. With `B = 0` the hook behaves as the [`FixedVelocityConsensusHook`].
+///
+/// Relay chain slot duration must be provided in milliseconds.
+pub struct BurstVelocityConsensusHook<
+	T,
+	const RELAY_CHAIN_SLOT_DURATION_MILLIS: u32,
+	const V: u32,
+	const C: u32,
+	const B: u32,
+>(PhantomData<T>);
+
+impl<
+		T: pallet::Config,
+		const RELAY_CHAIN_SLOT_DURATION_MILLIS: u32,
+		const V: u32,
+		const C: u32,
+		const B: u32,
+	> ConsensusHook for BurstVelocityConsensusHook<T, RELAY_CHAIN_SLOT_DURATION_MILLIS, V, C, B>
+where
+	<T as pallet_timestamp::Config>::Moment: Into<u64>,
+{
+	// Validates the number of authored blocks within the slot with respect to the `V + 1` limit
+	// and the accumulated credit.
+	fn on_state_proof(state_proof: &RelayChainStateProof) -> (Weight, UnincludedSegmentCapacity) {
+		// Ensure velocity is non-zero.
+		let velocity = V.max(1);
+		let relay_chain_slot = state_proof.read_slot().expect("failed to read relay chain slot");
+
+		let (slot, authored) = pallet::Pallet::<T>::slot_info()
+			.expect("slot info is inserted on block initialization");
+
+		let para_slot_from_relay =
+			para_slot_from_relay_slot::<T>(relay_chain_slot, RELAY_CHAIN_SLOT_DURATION_MILLIS);
+
+		// Perform checks.
+		assert_eq!(slot, para_slot_from_relay, "slot number mismatch");
+
+		let credit = match pallet::VelocityCredits::<T>::get() {
+			Some(last) if last.slot == slot => last.credit,
+			Some(last) => last.carried_to(slot, velocity, B),
+			None => 0,
+		};
+		if authored > velocity.saturating_add(1).saturating_add(credit) {
+			panic!("authored blocks limit is reached for the slot")
+		}
+		pallet::VelocityCredits::<T>::put(VelocityCredit { slot, credit, authored });
+
+		let weight = T::DbWeight::get().reads_writes(2, 1);
+
+		(
+			weight,
+			NonZeroU32::new(sp_std::cmp::max(C, 1))
+				.expect("1 is the minimum value and non-zero; qed")
+				.into(),
+		)
+	}
+}
+
+impl<
+		T: pallet::Config + parachain_system::Config,
+		const RELAY_CHAIN_SLOT_DURATION_MILLIS: u32,
+		const V: u32,
+		const C: u32,
+		const B: u32,
+	> BurstVelocityConsensusHook<T, RELAY_CHAIN_SLOT_DURATION_MILLIS, V, C, B>
+{
+	/// Whether it is legal to extend the chain, assuming the given block is the most
+	/// recently included one as-of the relay parent that will be built against, and
+	/// the given slot.
+	///
+	/// See [`FixedVelocityConsensusHook::can_build_upon`].
+	pub fn can_build_upon(included_hash: T::Hash, new_slot: Slot) -> bool {
+		let velocity = V.max(1);
+		let (last_slot, authored_so_far) = match pallet::Pallet::<T>::slot_info() {
+			None => return true,
+			Some(x) => x,
+		};
+
+		let size_after_included =
+			parachain_system::Pallet::<T>::unincluded_segment_size_after(included_hash);
+
+		// can never author when the unincluded segment is full.
+		if size_after_included >= C {
+			return false
+		}
+
+		if last_slot == new_slot {
+			let credit = pallet::VelocityCredits::<T>::get()
+				.filter(|last| last.slot == last_slot)
+				.map_or(0, |last| last.credit);
+			authored_so_far < velocity.saturating_add(1).saturating_add(credit)
+		} else {
+			// disallow slot from moving backwards.
+			last_slot < new_slot
+		}
+	}
+}
@@ -31,19 +31,40 @@
 //!     BlockExecutor = cumulus_pallet_aura_ext::BlockExecutor::<Runtime, Executive>,
 //! }
 //! ```
+//!
+//! The pallet also allows changing the slot duration of a running chain, e.g. when switching to
+//! the velocity of asynchronous backing. The change is scheduled with the
+//! [`migration::ScheduleSlotDurationChange`] and is enacted at the end of the scheduled block.
+//! The current slot is then translated to the new slot duration, so that the slots of the
+//! following blocks don't move backwards. The runtime is required to use the
+//! [`MinimumPeriodFromSlotDuration`] as the `MinimumPeriod` of `pallet-timestamp` for the change
+//! to take effect.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::traits::{ExecuteBlock, FindAuthor};
+use codec::Decode;
+use frame_support::{
+	traits::{ExecuteBlock, FindAuthor, Get},
+	weights::Weight,
+};
 use sp_application_crypto::RuntimeAppPublic;
 use sp_consensus_aura::{digests::CompatibleDigestItem, Slot};
-use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use sp_runtime::traits::{
+	Block as BlockT, Header as HeaderT, UniqueSaturatedFrom, UniqueSaturatedInto,
+};
 
 pub mod consensus_hook;
-pub use consensus_hook::FixedVelocityConsensusHook;
+pub mod migration;
+#[cfg(test)]
+mod tests;
+pub use consensus_hook::{BurstVelocityConsensusHook, FixedVelocityConsensusHook};
 
 type Aura<T> = pallet_aura::Pallet<T>;
 
+/// The current slot of `pallet-aura`.
+#[frame_support::storage_alias]
+type CurrentSlot<T: pallet_aura::Config> = StorageValue<pallet_aura::Pallet<T>, Slot, ValueQuery>;
+
 pub use pallet::*;
 
 #[frame_support::pallet]
@@ -61,12 +82,21 @@ pub mod pallet {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_finalize(_: BlockNumberFor<T>) {
+		fn on_finalize(n: BlockNumberFor<T>) {
 			// Update to the latest AuRa authorities.
 			Authorities::<T>::put(Aura::<T>::authorities());
+
+			// The slot duration is changed after the timestamp has been checked against the
+			// current slot, so the next block is the first one to use the new slot duration.
+			if let Some((at, slot_duration)) = ScheduledSlotDuration::<T>::get() {
+				if at <= n {
+					Pallet::<T>::enact_slot_duration(slot_duration);
+					ScheduledSlotDuration::<T>::kill();
+				}
+			}
 		}
 
-		fn on_initialize(_: BlockNumberFor<T>) -> Weight {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
 			// Fetch the authorities once to get them into the storage proof of the PoV.
 			Authorities::<T>::get();
 
@@ -83,7 +113,11 @@ pub mod pallet {
 
 			SlotInfo::<T>::put((new_slot, authored));
 
-			T::DbWeight::get().reads_writes(2, 1)
+			let mut weight = T::DbWeight::get().reads_writes(3, 1);
+			if matches!(ScheduledSlotDuration::<T>::get(), Some((at, _)) if at <= n) {
+				weight += T::DbWeight::get().reads_writes(5, 5);
+			}
+			weight
 		}
 	}
 
@@ -106,6 +140,25 @@ pub mod pallet {
 	#[pallet::getter(fn slot_info)]
 	pub(crate) type SlotInfo<T: Config> = StorageValue<_, (Slot, u32), OptionQuery>;
 
+	/// The velocity credit of the last block.
+	///
+	/// Only used by the [`BurstVelocityConsensusHook`].
+	#[pallet::storage]
+	pub(crate) type VelocityCredits<T: Config> =
+		StorageValue<_, consensus_hook::VelocityCredit, OptionQuery>;
+
+	/// The slot duration in milliseconds, that has been enacted by the last slot duration change.
+	///
+	/// `None` if the slot duration has never been changed.
+	#[pallet::storage]
+	pub(crate) type SlotDurationMillis<T: Config> = StorageValue<_, u64, OptionQuery>;
+
+	/// The slot duration change in milliseconds, that is enacted at the end of the given block.
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled_slot_duration)]
+	pub(crate) type ScheduledSlotDuration<T: Config> =
+		StorageValue<_, (BlockNumberFor<T>, u64), OptionQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -128,6 +181,109 @@ pub mod pallet {
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	/// Schedules the change of the slot duration to `slot_duration_millis` at the end of the block
+	/// `at`.
+	///
+	/// Overwrites the change, that has been scheduled before.
+	pub fn schedule_slot_duration_change(
+		at: frame_system::pallet_prelude::BlockNumberFor<T>,
+		slot_duration_millis: u64,
+	) {
+		ScheduledSlotDuration::<T>::put((at, slot_duration_millis.max(1)));
+	}
+
+	/// Changes the slot duration and translates the current slot to the new slot duration.
+	fn enact_slot_duration(slot_duration_millis: u64) {
+		let old_slot_duration: u64 = Aura::<T>::slot_duration().unique_saturated_into();
+		let new_slot_duration = slot_duration_millis.max(1);
+		let translate = |slot: Slot| {
+			Slot::from(u64::from(slot).saturating_mul(old_slot_duration) / new_slot_duration)
+		};
+
+		CurrentSlot::<T>::mutate(|slot| *slot = translate(*slot));
+		SlotInfo::<T>::mutate(|info| {
+			if let Some((slot, _)) = info {
+				*slot = translate(*slot);
+			}
+		});
+		VelocityCredits::<T>::mutate(|credit| {
+			if let Some(credit) = credit {
+				credit.slot = translate(credit.slot);
+			}
+		});
+		SlotDurationMillis::<T>::put(new_slot_duration);
+	}
+}
+
+/// The `MinimumPeriod` of `pallet-timestamp`, that follows the slot duration changes of this
+/// pallet.
+///
+/// `DefaultSlotDuration` is the slot duration in milliseconds, that is used until the first change
+/// is enacted.
+pub struct MinimumPeriodFromSlotDuration<T, DefaultSlotDuration>(
+	sp_std::marker::PhantomData<(T, DefaultSlotDuration)>,
+);
+
+impl<T: Config, DefaultSlotDuration: Get<u64>> Get<<T as pallet_timestamp::Config>::Moment>
+	for MinimumPeriodFromSlotDuration<T, DefaultSlotDuration>
+{
+	fn get() -> <T as pallet_timestamp::Config>::Moment {
+		let slot_duration = SlotDurationMillis::<T>::get().unwrap_or_else(DefaultSlotDuration::get);
+		UniqueSaturatedFrom::unique_saturated_from(slot_duration / 2)
+	}
+}
+
+/// The weight of the signature verification of the [`AuraAuthorshipProof`].
+///
+/// This is the weight of an `sr25519` signature verification, which is the most expensive one of
+/// the AuRa authority keys.
+const SIGNATURE_VERIFICATION_WEIGHT: Weight = Weight::from_parts(50_000_000, 0);
+
+/// Verifies the block authorship proofs of the AuRa authorities for the `parachain-system` pallet.
+///
+/// The proof is the SCALE encoded public key of the author and its signature over the
+/// [`authorship_proof_payload`](cumulus_primitives_core::authorship_proof_payload) of the block.
+/// The author is required to be the AuRa authority of the slot in the AuRa pre-digest of the
+/// block, among the authorities, that were in charge when the block was built. So the
+/// `parachain-system` pallet has to verify the proof before the `on_finalize` of this pallet
+/// updates the authorities, i.e. it has to come first in the `construct_runtime`.
+pub struct AuraAuthorshipProof<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> cumulus_pallet_parachain_system::VerifyAuthorshipProof for AuraAuthorshipProof<T> {
+	type Author = T::AuthorityId;
+
+	fn verify(mut proof: &[u8]) -> Option<T::AuthorityId> {
+		let (author, signature) =
+			<(T::AuthorityId, <T::AuthorityId as RuntimeAppPublic>::Signature)>::decode(&mut proof)
+				.ok()?;
+
+		let slot = frame_system::Pallet::<T>::digest()
+			.logs()
+			.iter()
+			.find_map(CompatibleDigestItem::<
+				<T::AuthorityId as RuntimeAppPublic>::Signature,
+			>::as_aura_pre_digest)?;
+		let authorities = Authorities::<T>::get();
+		let slot_author_index = *slot % authorities.len().max(1) as u64;
+		let slot_author = authorities.get(slot_author_index as usize)?;
+		if *slot_author != author {
+			return None
+		}
+
+		let payload = cumulus_primitives_core::authorship_proof_payload(
+			&frame_system::Pallet::<T>::parent_hash(),
+			&frame_system::Pallet::<T>::block_number(),
+			*slot,
+		);
+		author.verify(&payload, &signature).then_some(author)
+	}
+
+	fn verification_weight() -> Weight {
+		T::DbWeight::get().reads(3).saturating_add(SIGNATURE_VERIFICATION_WEIGHT)
+	}
+}
+
 /// The block executor used when validating a PoV at the relay chain.
 ///
 /// When executing the block it will verify the block seal to ensure that the correct author created
@@ -182,3 +338,42 @@ where
 		I::execute_block(Block::new(header, extrinsics));
 	}
 }
+
+/// The block executor, that verifies the order of the extrinsics before executing the block
+/// with `I`.
+///
+/// Parachains, whose collators shuffle the extrinsics of their blocks, wrap the executor of the
+/// [`BlockExecutor`] into it, so the order of the extrinsics is enforced when validating the PoV
+/// at the relay chain. Blocks without the shuffling seed are rejected. See
+/// [`cumulus_primitives_core::shuffling`].
+///
+/// ```
+/// # struct Runtime;
+/// # struct Executive;
+/// cumulus_pallet_parachain_system::register_validate_block! {
+///     Runtime = Runtime,
+///     BlockExecutor = cumulus_pallet_aura_ext::BlockExecutor::<
+///         Runtime,
+///         cumulus_pallet_aura_ext::ShufflingBlockExecutor<Executive>,
+///     >,
+/// }
+/// ```
+pub struct ShufflingBlockExecutor<I>(sp_std::marker::PhantomData<I>);
+
+impl<Block, I> ExecuteBlock<Block> for ShufflingBlockExecutor<I>
+where
+	Block: BlockT,
+	I: ExecuteBlock<Block>,
+{
+	fn execute_block(block: Block) {
+		if let Err(error) = cumulus_primitives_core::shuffling::verify_shuffling::<Block>(
+			block.header(),
+			block.extrinsics(),
+			true,
+		) {
+			panic!("Invalid order of the extrinsics: {}", error);
+		}
+
+		I::execute_block(block);
+	}
+}
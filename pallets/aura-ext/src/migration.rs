@@ -0,0 +1,64 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Migrations of the AuRa extension pallet.
+
+use crate::{Config, Pallet};
+use frame_support::{
+	traits::{Get, OnRuntimeUpgrade},
+	weights::Weight,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::Saturating;
+use sp_std::marker::PhantomData;
+
+/// Schedules the change of the slot duration to `NewSlotDuration` milliseconds, `Delay` blocks
+/// after the runtime upgrade.
+///
+/// The delay gives the collators time to observe the change. The consensus hook of the runtime
+/// (e.g. the velocity of the [`FixedVelocityConsensusHook`](crate::FixedVelocityConsensusHook))
+/// may be changed by the same runtime upgrade, because it always checks the slots with the slot
+/// duration, that is in effect.
+///
+/// The change is not scheduled again, if the slot duration is already `NewSlotDuration`, so the
+/// migration may be kept in the runtime for several upgrades.
+pub struct ScheduleSlotDurationChange<T, Delay, NewSlotDuration>(
+	PhantomData<(T, Delay, NewSlotDuration)>,
+);
+
+impl<T, Delay, NewSlotDuration> OnRuntimeUpgrade
+	for ScheduleSlotDurationChange<T, Delay, NewSlotDuration>
+where
+	T: Config,
+	Delay: Get<BlockNumberFor<T>>,
+	NewSlotDuration: Get<u64>,
+{
+	fn on_runtime_upgrade() -> Weight {
+		let new_slot_duration = NewSlotDuration::get();
+		let already_scheduled = matches!(
+			Pallet::<T>::scheduled_slot_duration(),
+			Some((_, slot_duration)) if slot_duration == new_slot_duration
+		);
+		if already_scheduled || crate::SlotDurationMillis::<T>::get() == Some(new_slot_duration) {
+			return T::DbWeight::get().reads(2)
+		}
+
+		let at = frame_system::Pallet::<T>::block_number().saturating_add(Delay::get());
+		Pallet::<T>::schedule_slot_duration_change(at, new_slot_duration);
+
+		T::DbWeight::get().reads_writes(3, 1)
+	}
+}
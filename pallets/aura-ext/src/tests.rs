@@ -0,0 +1,437 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use crate as aura_ext;
+use codec::Encode;
+use consensus_hook::VelocityCredit;
+use cumulus_pallet_parachain_system::{
+	consensus_hook::ConsensusHook, relay_state_snapshot::RelayChainStateProof,
+	VerifyAuthorshipProof,
+};
+use cumulus_test_relay_sproof_builder::RelayStateSproofBuilder;
+use frame_support::{
+	traits::{ConstBool, ConstU32, ConstU64, Hooks, OnRuntimeUpgrade},
+	BoundedVec,
+};
+use sp_core::H256;
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, DigestItem,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		Aura: pallet_aura::{Pallet, Storage, Config<T>},
+		AuraExt: aura_ext::{Pallet, Storage, Config<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = frame_support::weights::constants::RocksDbWeight;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+/// The slot duration of the parachain, until it is changed.
+const DEFAULT_SLOT_DURATION: u64 = 12_000;
+const RELAY_CHAIN_SLOT_DURATION: u32 = 6_000;
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = Aura;
+	type MinimumPeriod = MinimumPeriodFromSlotDuration<Test, ConstU64<DEFAULT_SLOT_DURATION>>;
+	type WeightInfo = ();
+}
+
+impl pallet_aura::Config for Test {
+	type AuthorityId = UintAuthorityId;
+	type DisabledValidators = ();
+	type MaxAuthorities = ConstU32<10>;
+	type AllowMultipleBlocksPerSlot = ConstBool<true>;
+}
+
+impl Config for Test {}
+
+/// Velocity 1, capacity 3 and a burst of up to 2 blocks.
+type BurstHook = BurstVelocityConsensusHook<Test, RELAY_CHAIN_SLOT_DURATION, 1, 3, 2>;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
+}
+
+fn set_slot(slot: u64, authored: u32) {
+	CurrentSlot::<Test>::put(Slot::from(slot));
+	SlotInfo::<Test>::put((Slot::from(slot), authored));
+}
+
+fn relay_state_proof(relay_chain_slot: u64) -> RelayChainStateProof {
+	let sproof_builder =
+		RelayStateSproofBuilder { current_slot: relay_chain_slot.into(), ..Default::default() };
+	let para_id = sproof_builder.para_id;
+	let (relay_parent_storage_root, proof) = sproof_builder.into_state_root_and_proof();
+
+	RelayChainStateProof::new(para_id, relay_parent_storage_root, proof)
+		.expect("The proof is built from the given root")
+}
+
+#[test]
+fn slot_info_is_updated_on_initialize() {
+	new_test_ext().execute_with(|| {
+		CurrentSlot::<Test>::put(Slot::from(10));
+		AuraExt::on_initialize(1);
+		assert_eq!(AuraExt::slot_info(), Some((Slot::from(10), 1)));
+
+		AuraExt::on_initialize(2);
+		assert_eq!(AuraExt::slot_info(), Some((Slot::from(10), 2)));
+
+		CurrentSlot::<Test>::put(Slot::from(12));
+		AuraExt::on_initialize(3);
+		assert_eq!(AuraExt::slot_info(), Some((Slot::from(12), 1)));
+	});
+}
+
+#[test]
+#[should_panic(expected = "slot moved backwards")]
+fn slot_must_not_move_backwards() {
+	new_test_ext().execute_with(|| {
+		set_slot(10, 1);
+		CurrentSlot::<Test>::put(Slot::from(9));
+
+		AuraExt::on_initialize(2);
+	});
+}
+
+#[test]
+fn slot_duration_change_is_enacted_at_the_end_of_the_scheduled_block() {
+	new_test_ext().execute_with(|| {
+		set_slot(100, 1);
+		VelocityCredits::<Test>::put(VelocityCredit {
+			slot: Slot::from(100),
+			credit: 1,
+			authored: 1,
+		});
+		AuraExt::schedule_slot_duration_change(2, 6_000);
+
+		let regular_weight = AuraExt::on_initialize(1);
+		AuraExt::on_finalize(1);
+		assert_eq!(Aura::slot_duration(), DEFAULT_SLOT_DURATION);
+		assert_eq!(CurrentSlot::<Test>::get(), Slot::from(100));
+
+		// the enactment is accounted for in `on_initialize` of the scheduled block
+		assert!(AuraExt::on_initialize(2).any_gt(regular_weight));
+
+		AuraExt::on_finalize(2);
+		assert_eq!(Aura::slot_duration(), 6_000);
+		assert_eq!(AuraExt::scheduled_slot_duration(), None);
+		assert_eq!(SlotDurationMillis::<Test>::get(), Some(6_000));
+
+		// the slots are translated to the new slot duration, so they don't move backwards
+		assert_eq!(CurrentSlot::<Test>::get(), Slot::from(200));
+		assert_eq!(AuraExt::slot_info().map(|(slot, _)| slot), Some(Slot::from(200)));
+		assert_eq!(VelocityCredits::<Test>::get().map(|credit| credit.slot), Some(Slot::from(200)));
+	});
+}
+
+#[test]
+fn schedule_slot_duration_change_migration_works() {
+	type Migration = migration::ScheduleSlotDurationChange<Test, ConstU64<10>, ConstU64<6_000>>;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+
+		Migration::on_runtime_upgrade();
+		assert_eq!(AuraExt::scheduled_slot_duration(), Some((15, 6_000)));
+
+		// the change is not scheduled again by the following upgrades
+		System::set_block_number(7);
+		Migration::on_runtime_upgrade();
+		assert_eq!(AuraExt::scheduled_slot_duration(), Some((15, 6_000)));
+
+		AuraExt::on_finalize(15);
+		Migration::on_runtime_upgrade();
+		assert_eq!(AuraExt::scheduled_slot_duration(), None);
+		assert_eq!(Aura::slot_duration(), 6_000);
+	});
+}
+
+#[test]
+fn para_slot_follows_the_slot_duration_change() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			consensus_hook::para_slot_from_relay_slot::<Test>(
+				200.into(),
+				RELAY_CHAIN_SLOT_DURATION
+			),
+			Slot::from(100)
+		);
+
+		AuraExt::schedule_slot_duration_change(1, 6_000);
+		AuraExt::on_finalize(1);
+
+		assert_eq!(
+			consensus_hook::para_slot_from_relay_slot::<Test>(
+				200.into(),
+				RELAY_CHAIN_SLOT_DURATION
+			),
+			Slot::from(200)
+		);
+	});
+}
+
+#[test]
+fn velocity_credit_is_carried_to_the_next_slots() {
+	let last = VelocityCredit { slot: Slot::from(10), credit: 1, authored: 1 };
+
+	// the unused velocity of the last slot
+	assert_eq!(last.carried_to(Slot::from(11), 2, 5), 2);
+	// the velocity of the empty slots is credited as well, up to the maximum
+	assert_eq!(last.carried_to(Slot::from(12), 2, 5), 4);
+	assert_eq!(last.carried_to(Slot::from(20), 2, 5), 5);
+	// nothing is carried over if the credit has been used up
+	let used_up = VelocityCredit { slot: Slot::from(10), credit: 1, authored: 3 };
+	assert_eq!(used_up.carried_to(Slot::from(11), 2, 5), 0);
+}
+
+#[test]
+fn burst_hook_allows_credited_blocks() {
+	new_test_ext().execute_with(|| {
+		VelocityCredits::<Test>::put(VelocityCredit {
+			slot: Slot::from(97),
+			credit: 0,
+			authored: 1,
+		});
+		// two empty slots are credited on top of the `V + 1` limit
+		set_slot(100, 4);
+
+		BurstHook::on_state_proof(&relay_state_proof(200));
+
+		assert_eq!(
+			VelocityCredits::<Test>::get(),
+			Some(VelocityCredit { slot: Slot::from(100), credit: 2, authored: 4 })
+		);
+	});
+}
+
+#[test]
+#[should_panic(expected = "authored blocks limit is reached for the slot")]
+fn burst_hook_rejects_blocks_above_the_credit() {
+	new_test_ext().execute_with(|| {
+		VelocityCredits::<Test>::put(VelocityCredit {
+			slot: Slot::from(97),
+			credit: 0,
+			authored: 1,
+		});
+		set_slot(100, 5);
+
+		BurstHook::on_state_proof(&relay_state_proof(200));
+	});
+}
+
+#[test]
+#[should_panic(expected = "slot number mismatch")]
+fn burst_hook_rejects_slot_mismatch() {
+	new_test_ext().execute_with(|| {
+		set_slot(101, 1);
+
+		BurstHook::on_state_proof(&relay_state_proof(200));
+	});
+}
+
+fn set_authorities(authorities: &[u64]) {
+	let authorities = authorities.iter().map(|id| UintAuthorityId(*id)).collect::<Vec<_>>();
+	Authorities::<Test>::put(BoundedVec::try_from(authorities).unwrap());
+}
+
+fn deposit_aura_pre_digest(slot: u64) {
+	System::deposit_log(<DigestItem as CompatibleDigestItem<TestSignature>>::aura_pre_digest(
+		Slot::from(slot),
+	));
+}
+
+fn authorship_proof(author: u64, slot: u64) -> Vec<u8> {
+	let payload = cumulus_primitives_core::authorship_proof_payload(
+		&System::parent_hash(),
+		&System::block_number(),
+		slot,
+	);
+	let author = UintAuthorityId(author);
+	let signature = author.sign(&payload).unwrap();
+	(author, signature).encode()
+}
+
+#[test]
+fn authorship_proof_of_the_slot_author_is_accepted() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		set_authorities(&[1, 2]);
+		deposit_aura_pre_digest(3);
+
+		assert_eq!(
+			AuraAuthorshipProof::<Test>::verify(&authorship_proof(2, 3)),
+			Some(UintAuthorityId(2)),
+		);
+	});
+}
+
+#[test]
+fn authorship_proof_of_another_authority_is_rejected() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		set_authorities(&[1, 2]);
+		deposit_aura_pre_digest(3);
+
+		assert_eq!(AuraAuthorshipProof::<Test>::verify(&authorship_proof(1, 3)), None);
+		assert_eq!(AuraAuthorshipProof::<Test>::verify(&authorship_proof(7, 3)), None);
+	});
+}
+
+#[test]
+fn authorship_proof_can_not_be_replayed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		set_authorities(&[1, 2]);
+		// the slot author is the same, but the proof was signed for another slot
+		let proof = authorship_proof(2, 5);
+		deposit_aura_pre_digest(3);
+		assert_eq!(AuraAuthorshipProof::<Test>::verify(&proof), None);
+
+		// the proof was signed for another block
+		let proof = authorship_proof(2, 3);
+		System::set_block_number(6);
+		assert_eq!(AuraAuthorshipProof::<Test>::verify(&proof), None);
+	});
+}
+
+#[test]
+fn authorship_proof_requires_the_slot() {
+	new_test_ext().execute_with(|| {
+		set_authorities(&[1, 2]);
+
+		assert_eq!(AuraAuthorshipProof::<Test>::verify(&authorship_proof(2, 3)), None);
+	});
+}
+
+mod shuffling {
+	use super::*;
+	use cumulus_primitives_core::{
+		rpsr_digest::relay_parent_storage_root_item,
+		shuffling::{shuffle_extrinsics, shuffling_seed},
+		CumulusDigestItem,
+	};
+	use sp_runtime::{
+		testing::{Block as TestBlock, Header, TestXt},
+		Digest,
+	};
+	use std::cell::RefCell;
+
+	type Extrinsic = TestXt<u64, ()>;
+
+	thread_local! {
+		static EXECUTED: RefCell<Vec<Extrinsic>> = RefCell::new(Vec::new());
+	}
+
+	struct RecordingExecutor;
+
+	impl ExecuteBlock<TestBlock<Extrinsic>> for RecordingExecutor {
+		fn execute_block(block: TestBlock<Extrinsic>) {
+			EXECUTED.with(|e| *e.borrow_mut() = block.extrinsics);
+		}
+	}
+
+	type Executor = ShufflingBlockExecutor<RecordingExecutor>;
+
+	fn block(seed: Option<[u8; 32]>, extrinsics: Vec<Extrinsic>) -> TestBlock<Extrinsic> {
+		let mut digest = Digest::default();
+		digest.push(relay_parent_storage_root_item(H256::repeat_byte(1), 1u32));
+		if let Some(seed) = seed {
+			digest.push(CumulusDigestItem::ShufflingSeed(seed).to_digest_item());
+		}
+		let header = <Header as HeaderT>::new(
+			1,
+			Default::default(),
+			Default::default(),
+			H256::zero(),
+			digest,
+		);
+		TestBlock { header, extrinsics }
+	}
+
+	fn transactions() -> Vec<Extrinsic> {
+		(0..10).map(|i| TestXt::new(i, Some((i, ())))).collect()
+	}
+
+	#[test]
+	fn shuffled_block_is_executed() {
+		let seed = shuffling_seed(&H256::repeat_byte(1), &H256::zero());
+		let shuffled = shuffle_extrinsics(transactions(), &seed);
+
+		Executor::execute_block(block(Some(seed), shuffled.clone()));
+
+		assert_eq!(EXECUTED.with(|e| e.borrow().clone()), shuffled);
+	}
+
+	#[test]
+	#[should_panic(expected = "The extrinsics are not shuffled with the committed seed")]
+	fn block_in_another_order_is_rejected() {
+		let seed = shuffling_seed(&H256::repeat_byte(1), &H256::zero());
+		let mut extrinsics = shuffle_extrinsics(transactions(), &seed);
+		extrinsics.swap(0, 1);
+
+		Executor::execute_block(block(Some(seed), extrinsics));
+	}
+
+	#[test]
+	#[should_panic(expected = "Invalid shuffling seed")]
+	fn block_with_a_seed_of_another_relay_parent_is_rejected() {
+		let seed = shuffling_seed(&H256::repeat_byte(2), &H256::zero());
+		let shuffled = shuffle_extrinsics(transactions(), &seed);
+
+		Executor::execute_block(block(Some(seed), shuffled));
+	}
+
+	#[test]
+	#[should_panic(expected = "The block misses the shuffling seed")]
+	fn block_without_the_seed_is_rejected() {
+		Executor::execute_block(block(None, transactions()));
+	}
+}
@@ -28,6 +28,7 @@ use frame_benchmarking::{
 use frame_support::{
 	dispatch::DispatchResult,
 	traits::{Currency, EnsureOrigin, Get, ReservableCurrency},
+	BoundedVec,
 };
 use frame_system::{pallet_prelude::BlockNumberFor, EventRecord, RawOrigin};
 use pallet_authorship::EventHandler;
@@ -99,6 +100,24 @@ fn register_candidates<T: Config>(count: u32) {
 	}
 }
 
+/// Delegate to `candidate` from a new delegator, returning it.
+fn delegate_to<T: Config>(candidate: &T::AccountId, n: u32, amount: BalanceOf<T>) -> T::AccountId {
+	let delegator: T::AccountId = account("delegator", n, SEED);
+	T::Currency::make_free_balance_be(&delegator, amount * 2u32.into());
+	<CollatorSelection<T>>::delegate(
+		RawOrigin::Signed(delegator.clone()).into(),
+		candidate.clone(),
+		amount,
+	)
+	.unwrap();
+	delegator
+}
+
+/// An amount that can be delegated.
+fn delegation<T: Config>() -> BalanceOf<T> {
+	T::MinDelegation::get().max(T::Currency::minimum_balance()) * 10u32.into()
+}
+
 fn min_candidates<T: Config>() -> u32 {
 	let min_collators = T::MinEligibleCollators::get();
 	let invulnerable_length = <Invulnerables<T>>::get().len();
@@ -285,6 +304,211 @@ mod benchmarks {
 		assert_last_event::<T>(Event::CandidateRemoved { account_id: leaving }.into());
 	}
 
+	// worse case is the last candidate moving to the top.
+	#[benchmark]
+	fn update_bond(c: Linear<{ min_candidates::<T>() + 1 }, { T::MaxCandidates::get() }>) {
+		<CandidacyBond<T>>::put(T::Currency::minimum_balance());
+		<DesiredCandidates<T>>::put(c);
+
+		register_validators::<T>(c);
+		register_candidates::<T>(c);
+
+		let caller = <Candidates<T>>::get().last().unwrap().who.clone();
+		v2::whitelist!(caller);
+
+		let bond_amount: BalanceOf<T> = T::Currency::minimum_balance() * 2u32.into();
+		T::Currency::make_free_balance_be(&caller, bond_amount * 10u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), bond_amount);
+
+		assert_last_event::<T>(
+			Event::CandidateBondUpdated { account_id: caller.clone(), deposit: bond_amount }.into(),
+		);
+		assert!(<Candidates<T>>::get().first().unwrap().who == caller);
+	}
+
+	// worse case is the last candidate being replaced by one moving to the top.
+	#[benchmark]
+	fn take_candidate_slot(c: Linear<1, { T::MaxCandidates::get() }>) {
+		<CandidacyBond<T>>::put(T::Currency::minimum_balance());
+		<DesiredCandidates<T>>::put(1);
+
+		register_validators::<T>(c);
+		register_candidates::<T>(c);
+
+		let caller: T::AccountId = whitelisted_caller();
+		let bond_amount: BalanceOf<T> = T::Currency::minimum_balance() * 10u32.into();
+		T::Currency::make_free_balance_be(&caller, bond_amount * 2u32.into());
+		<session::Pallet<T>>::set_keys(
+			RawOrigin::Signed(caller.clone()).into(),
+			keys::<T>(c + 1),
+			Vec::new(),
+		)
+		.unwrap();
+
+		let target = <Candidates<T>>::get().last().unwrap().who.clone();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), bond_amount, target.clone());
+
+		assert_last_event::<T>(
+			Event::CandidateReplaced { old: target, new: caller, deposit: bond_amount }.into(),
+		);
+	}
+
+	#[benchmark]
+	fn set_liveness_exemption() -> Result<(), BenchmarkError> {
+		let who: T::AccountId = whitelisted_caller();
+		let origin =
+			T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone(), true);
+
+		assert_last_event::<T>(
+			Event::LivenessExemptionSet { account_id: who.clone(), exempt: true }.into(),
+		);
+		assert!(<LivenessExempt<T>>::contains_key(&who));
+		Ok(())
+	}
+
+	// worse case is the last candidate being removed.
+	#[benchmark]
+	fn remove_offline_candidate(c: Linear<1, { T::MaxCandidates::get() }>) {
+		<CandidacyBond<T>>::put(T::Currency::minimum_balance());
+		<DesiredCandidates<T>>::put(c);
+
+		register_validators::<T>(c);
+		register_candidates::<T>(c);
+		T::Currency::make_free_balance_be(
+			&<CollatorSelection<T>>::account_id(),
+			T::Currency::minimum_balance(),
+		);
+
+		let offline = <Candidates<T>>::get().last().unwrap().who.clone();
+		<OfflineSessions<T>>::insert(&offline, T::MaxOfflineSessions::get());
+
+		#[block]
+		{
+			<CollatorSelection<T>>::remove_offline_candidate(&offline).unwrap();
+		}
+
+		assert_last_event::<T>(Event::CandidateRemoved { account_id: offline.clone() }.into());
+		assert!(!<OfflineSessions<T>>::contains_key(&offline));
+	}
+
+	// worse case is the last candidate moving to the top.
+	#[benchmark]
+	fn delegate(c: Linear<1, { T::MaxCandidates::get() }>) -> Result<(), BenchmarkError> {
+		if T::MaxDelegatorsPerCandidate::get() == 0 {
+			return Err(BenchmarkError::Weightless)
+		}
+		<CandidacyBond<T>>::put(T::Currency::minimum_balance());
+		<DesiredCandidates<T>>::put(c);
+
+		register_validators::<T>(c);
+		register_candidates::<T>(c);
+
+		let candidate = <Candidates<T>>::get().last().unwrap().who.clone();
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = delegation::<T>();
+		T::Currency::make_free_balance_be(&caller, amount * 2u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), candidate.clone(), amount);
+
+		assert_last_event::<T>(Event::Delegated { delegator: caller, candidate, amount }.into());
+		Ok(())
+	}
+
+	// worse case is the first candidate moving to the bottom.
+	#[benchmark]
+	fn undelegate(c: Linear<1, { T::MaxCandidates::get() }>) -> Result<(), BenchmarkError> {
+		if T::MaxDelegatorsPerCandidate::get() == 0 {
+			return Err(BenchmarkError::Weightless)
+		}
+		<CandidacyBond<T>>::put(T::Currency::minimum_balance());
+		<DesiredCandidates<T>>::put(c);
+
+		register_validators::<T>(c);
+		register_candidates::<T>(c);
+
+		let candidate = <Candidates<T>>::get().last().unwrap().who.clone();
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = delegation::<T>();
+		T::Currency::make_free_balance_be(&caller, amount * 2u32.into());
+		<CollatorSelection<T>>::delegate(
+			RawOrigin::Signed(caller.clone()).into(),
+			candidate.clone(),
+			amount,
+		)
+		.unwrap();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), candidate.clone(), amount);
+
+		let unlock_at =
+			frame_system::Pallet::<T>::block_number() + T::DelegationUnbondingPeriod::get();
+		assert_last_event::<T>(
+			Event::Undelegated { delegator: caller, candidate, amount, unlock_at }.into(),
+		);
+		Ok(())
+	}
+
+	// worse case is withdrawing all chunks at once.
+	#[benchmark]
+	fn withdraw_unbonded() -> Result<(), BenchmarkError> {
+		if T::MaxUnbondingChunks::get() == 0 {
+			return Err(BenchmarkError::Weightless)
+		}
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = delegation::<T>();
+		let chunks = T::MaxUnbondingChunks::get();
+		T::Currency::make_free_balance_be(&caller, amount * (chunks + 1).into());
+		T::Currency::reserve(&caller, amount * chunks.into()).unwrap();
+		let unlock_at = frame_system::Pallet::<T>::block_number();
+		<Unbonding<T>>::insert(
+			&caller,
+			BoundedVec::truncate_from(
+				(0..chunks).map(|_| UnbondingChunk { amount, unlock_at }).collect::<Vec<_>>(),
+			),
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert_last_event::<T>(
+			Event::DelegationWithdrawn { delegator: caller, amount: amount * chunks.into() }.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn reward_delegators(d: Linear<0, { T::MaxDelegatorsPerCandidate::get() }>) {
+		<CandidacyBond<T>>::put(T::Currency::minimum_balance());
+		<DesiredCandidates<T>>::put(1);
+		register_validators::<T>(1);
+		register_candidates::<T>(1);
+
+		let author = <Candidates<T>>::get()[0].who.clone();
+		let pot = <CollatorSelection<T>>::account_id();
+		let reward = T::Currency::minimum_balance() * 1000u32.into();
+		T::Currency::make_free_balance_be(&pot, reward * 2u32.into());
+		let delegators = (0..d)
+			.map(|n| delegate_to::<T>(&author, n, delegation::<T>()))
+			.collect::<Vec<_>>();
+
+		#[block]
+		{
+			<CollatorSelection<T>>::reward_delegators(&pot, &author, reward);
+		}
+
+		for delegator in delegators {
+			assert!(T::Currency::free_balance(&delegator) > delegation::<T>());
+		}
+	}
+
 	// worse case is paying a non-existing candidate account.
 	#[benchmark]
 	fn note_author() {
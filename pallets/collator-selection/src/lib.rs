@@ -38,8 +38,24 @@
 //! 2. [`Candidates`]: these are *candidates to the collation task* and may or may not be elected as
 //!    a final collator.
 //!
-//! The current implementation resolves congestion of [`Candidates`] in a first-come-first-serve
-//! manner.
+//! [`Candidates`] are sorted by their bond and, at every new session, the top
+//! [`DesiredCandidates`] of them are elected. A candidate can raise its deposit at any time with
+//! `update_bond`, and a newcomer can take the slot of a candidate by bonding more than them with
+//! `take_candidate_slot`, e.g. once all [`Config::MaxCandidates`] slots are taken. Candidates with
+//! equal bonds are ordered first-come-first-serve.
+//!
+//! ### Delegations
+//!
+//! Unless [`Config::MaxDelegatorsPerCandidate`] is zero, any account can `delegate` funds to a
+//! candidate. The bond of a candidate is its own deposit plus the funds delegated to it. Delegated
+//! funds stay reserved for [`Config::DelegationUnbondingPeriod`] blocks after they are
+//! `undelegate`d, and can then be withdrawn with `withdraw_unbonded`. Delegations are kept when a
+//! candidate leaves, so that they count again if it comes back.
+//!
+//! The blocks authored by every collator are counted per session. Elected candidates that did not
+//! author any block for [`Config::MaxOfflineSessions`] consecutive sessions are removed, and
+//! [`Config::OfflineSlash`] of their deposit is moved to the Pot. The `UpdateOrigin` can exempt
+//! candidates from this, e.g. while they are known to be under maintenance.
 //!
 //! Candidates will not be allowed to get kicked or `leave_intent` if the total number of collators
 //! would fall below `MinEligibleCollators`. This is to ensure that some collators will always
@@ -54,6 +70,9 @@
 //! - Half the value of the transaction fees within the block. The other half of the transaction
 //!   fees are deposited into the Pot.
 //!
+//! If the author is a candidate with delegations, its delegators get a share of the reward from
+//! the Pot that is proportional to their part of the bond of the candidate.
+//!
 //! To initiate rewards, an ED needs to be transferred to the pot address.
 //!
 //! Note: Eventually the Pot distribution may be modified as discussed in
@@ -84,8 +103,8 @@ pub mod pallet {
 		dispatch::{DispatchClass, DispatchResultWithPostInfo},
 		pallet_prelude::*,
 		traits::{
-			Currency, EnsureOrigin, ExistenceRequirement::KeepAlive, ReservableCurrency,
-			ValidatorRegistration,
+			BalanceStatus, Currency, EnsureOrigin, ExistenceRequirement::KeepAlive,
+			ReservableCurrency, ValidatorRegistration,
 		},
 		BoundedVec, DefaultNoBound, PalletId,
 	};
@@ -93,13 +112,13 @@ pub mod pallet {
 	use pallet_session::SessionManager;
 	use sp_runtime::{
 		traits::{AccountIdConversion, CheckedSub, Convert, Saturating, Zero},
-		RuntimeDebug,
+		Perbill, RuntimeDebug,
 	};
 	use sp_staking::SessionIndex;
-	use sp_std::vec::Vec;
+	use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 
 	/// The current storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as SystemConfig>::AccountId>>::Balance;
@@ -144,6 +163,33 @@ pub mod pallet {
 		// Will be kicked if block is not produced in threshold.
 		type KickThreshold: Get<BlockNumberFor<Self>>;
 
+		/// Number of consecutive sessions in which an elected candidate may not author any block
+		/// before it is removed. Zero disables the liveness tracking.
+		#[pallet::constant]
+		type MaxOfflineSessions: Get<SessionIndex>;
+
+		/// Fraction of the deposit of a candidate removed for being offline that is moved to the
+		/// Pot.
+		#[pallet::constant]
+		type OfflineSlash: Get<Perbill>;
+
+		/// Maximum number of delegators of a candidate. Zero disables delegations.
+		#[pallet::constant]
+		type MaxDelegatorsPerCandidate: Get<u32>;
+
+		/// Minimum amount delegated by a delegator to a candidate.
+		#[pallet::constant]
+		type MinDelegation: Get<BalanceOf<Self>>;
+
+		/// Number of blocks undelegated funds stay reserved before they can be withdrawn.
+		#[pallet::constant]
+		type DelegationUnbondingPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Maximum number of undelegations of a delegator that can be waiting for their unbonding
+		/// period at once.
+		#[pallet::constant]
+		type MaxUnbondingChunks: Get<u32>;
+
 		/// A stable ID for a validator.
 		type ValidatorId: Member + Parameter;
 
@@ -170,6 +216,36 @@ pub mod pallet {
 		pub deposit: Balance,
 	}
 
+	/// The delegations of all delegators of a candidate.
+	#[derive(
+		Default,
+		PartialEq,
+		Eq,
+		Clone,
+		Encode,
+		Decode,
+		RuntimeDebug,
+		scale_info::TypeInfo,
+		MaxEncodedLen,
+	)]
+	pub struct DelegationTotal<Balance> {
+		/// Total delegated funds.
+		pub amount: Balance,
+		/// Number of delegators.
+		pub delegators: u32,
+	}
+
+	/// Undelegated funds waiting for the unbonding period to pass.
+	#[derive(
+		PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
+	)]
+	pub struct UnbondingChunk<Balance, BlockNumber> {
+		/// The undelegated funds.
+		pub amount: Balance,
+		/// The block from which the funds can be withdrawn.
+		pub unlock_at: BlockNumber,
+	}
+
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
@@ -182,6 +258,9 @@ pub mod pallet {
 
 	/// The (community, limited) collation candidates. `Candidates` and `Invulnerables` should be
 	/// mutually exclusive.
+	///
+	/// This list is sorted by bond, i.e. deposit plus delegations, highest first. Candidates with
+	/// equal bonds are kept in the order in which they joined.
 	#[pallet::storage]
 	#[pallet::getter(fn candidates)]
 	pub type Candidates<T: Config> = StorageValue<
@@ -196,6 +275,60 @@ pub mod pallet {
 	pub type LastAuthoredBlock<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, BlockNumberFor<T>, ValueQuery>;
 
+	/// Number of blocks authored by collator in the current session.
+	#[pallet::storage]
+	pub type AuthoredBlocks<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Number of consecutive sessions in which an elected candidate did not author any block.
+	#[pallet::storage]
+	#[pallet::getter(fn offline_sessions)]
+	pub type OfflineSessions<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, SessionIndex, ValueQuery>;
+
+	/// The candidates elected for a session, kept until the session ends.
+	#[pallet::storage]
+	pub type SessionCandidates<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		SessionIndex,
+		BoundedVec<T::AccountId, T::MaxCandidates>,
+		ValueQuery,
+	>;
+
+	/// Candidates that are never removed for being offline.
+	#[pallet::storage]
+	pub type LivenessExempt<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, (), OptionQuery>;
+
+	/// Funds delegated to a candidate, by candidate and delegator.
+	#[pallet::storage]
+	#[pallet::getter(fn delegations)]
+	pub type Delegations<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The delegations of all delegators of a candidate.
+	#[pallet::storage]
+	#[pallet::getter(fn delegation_total)]
+	pub type DelegationTotals<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, DelegationTotal<BalanceOf<T>>, ValueQuery>;
+
+	/// Undelegated funds of a delegator waiting for their unbonding period to pass.
+	#[pallet::storage]
+	#[pallet::getter(fn unbonding)]
+	pub type Unbonding<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<UnbondingChunk<BalanceOf<T>, BlockNumberFor<T>>, T::MaxUnbondingChunks>,
+		ValueQuery,
+	>;
+
 	/// Desired number of candidates.
 	///
 	/// This should ideally always be less than [`Config::MaxCandidates`] for weights to be correct.
@@ -266,6 +399,30 @@ pub mod pallet {
 		/// An account was unable to be added to the Invulnerables because they did not have keys
 		/// registered. Other Invulnerables may have been set.
 		InvalidInvulnerableSkipped { account_id: T::AccountId },
+		/// The deposit of a candidate was updated.
+		CandidateBondUpdated { account_id: T::AccountId, deposit: BalanceOf<T> },
+		/// An account took the slot of a candidate with a lower deposit.
+		CandidateReplaced { old: T::AccountId, new: T::AccountId, deposit: BalanceOf<T> },
+		/// A candidate was removed for not authoring any block in `offline_sessions` consecutive
+		/// sessions. `slashed` of its deposit was moved to the Pot.
+		CandidateOffline {
+			account_id: T::AccountId,
+			offline_sessions: SessionIndex,
+			slashed: BalanceOf<T>,
+		},
+		/// A candidate was exempted from, or subjected again to, the liveness tracking.
+		LivenessExemptionSet { account_id: T::AccountId, exempt: bool },
+		/// Funds were delegated to a candidate.
+		Delegated { delegator: T::AccountId, candidate: T::AccountId, amount: BalanceOf<T> },
+		/// Funds were undelegated from a candidate. They can be withdrawn from block `unlock_at`.
+		Undelegated {
+			delegator: T::AccountId,
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+			unlock_at: BlockNumberFor<T>,
+		},
+		/// Undelegated funds were withdrawn.
+		DelegationWithdrawn { delegator: T::AccountId, amount: BalanceOf<T> },
 	}
 
 	#[pallet::error]
@@ -288,6 +445,26 @@ pub mod pallet {
 		NoAssociatedValidatorId,
 		/// Validator ID is not yet registered.
 		ValidatorNotRegistered,
+		/// The deposit is lower than the candidacy bond.
+		DepositTooLow,
+		/// The new deposit equals the current one.
+		IdenticalDeposit,
+		/// Elected candidates cannot lower their deposit.
+		InvalidUnreserve,
+		/// The bond does not exceed the bond of the candidate to replace.
+		InsufficientBond,
+		/// The account to replace is not a candidate.
+		TargetIsNotCandidate,
+		/// The candidate has too many delegators.
+		TooManyDelegators,
+		/// The delegation would be lower than the minimum delegation.
+		DelegationTooLow,
+		/// Account has not delegated to the candidate.
+		NotDelegator,
+		/// The delegator has too many undelegations waiting for their unbonding period.
+		TooManyUnbondingChunks,
+		/// No undelegated funds have passed their unbonding period yet.
+		NothingToWithdraw,
 	}
 
 	#[pallet::hooks]
@@ -417,6 +594,8 @@ pub mod pallet {
 		/// Register this account as a collator candidate. The account must (a) already have
 		/// registered session keys and (b) be able to reserve the `CandidacyBond`.
 		///
+		/// Only the top `DesiredCandidates` candidates by deposit are elected as collators.
+		///
 		/// This call is not available to `Invulnerable` collators.
 		#[pallet::call_index(3)]
 		#[pallet::weight(T::WeightInfo::register_as_candidate(T::MaxCandidates::get()))]
@@ -425,7 +604,7 @@ pub mod pallet {
 
 			// ensure we are below limit.
 			let length = <Candidates<T>>::decode_len().unwrap_or_default();
-			ensure!((length as u32) < Self::desired_candidates(), Error::<T>::TooManyCandidates);
+			ensure!((length as u32) < T::MaxCandidates::get(), Error::<T>::TooManyCandidates);
 			ensure!(!Self::invulnerables().contains(&who), Error::<T>::AlreadyInvulnerable);
 
 			let validator_key = T::ValidatorIdOf::convert(who.clone())
@@ -445,7 +624,10 @@ pub mod pallet {
 						Err(Error::<T>::AlreadyCandidate)?
 					} else {
 						T::Currency::reserve(&who, deposit)?;
-						candidates.try_push(incoming).map_err(|_| Error::<T>::TooManyCandidates)?;
+						let position = Self::candidate_position(candidates, &incoming);
+						candidates
+							.try_insert(position, incoming)
+							.map_err(|_| Error::<T>::TooManyCandidates)?;
 						<LastAuthoredBlock<T>>::insert(
 							who.clone(),
 							frame_system::Pallet::<T>::block_number() + T::KickThreshold::get(),
@@ -554,6 +736,278 @@ pub mod pallet {
 			Self::deposit_event(Event::InvulnerableRemoved { account_id: who });
 			Ok(())
 		}
+
+		/// Update the deposit of the `origin` candidate to `new_deposit`, which must be at least
+		/// the `CandidacyBond`.
+		///
+		/// Raising the deposit reserves the difference, lowering it unreserves it. Candidates that
+		/// are currently among the top `DesiredCandidates` cannot lower their deposit.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::update_bond(T::MaxCandidates::get()))]
+		pub fn update_bond(
+			origin: OriginFor<T>,
+			new_deposit: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(new_deposit >= Self::candidacy_bond(), Error::<T>::DepositTooLow);
+
+			let current_count =
+				<Candidates<T>>::try_mutate(|candidates| -> Result<usize, DispatchError> {
+					let index = candidates
+						.iter()
+						.position(|candidate| candidate.who == who)
+						.ok_or(Error::<T>::NotCandidate)?;
+					let mut info = candidates.remove(index);
+					let old_deposit = info.deposit;
+					if new_deposit > old_deposit {
+						T::Currency::reserve(&who, new_deposit - old_deposit)?;
+					} else if new_deposit < old_deposit {
+						ensure!(
+							index >= Self::desired_candidates() as usize,
+							Error::<T>::InvalidUnreserve
+						);
+						T::Currency::unreserve(&who, old_deposit - new_deposit);
+					} else {
+						return Err(Error::<T>::IdenticalDeposit.into())
+					}
+
+					info.deposit = new_deposit;
+					let position = Self::candidate_position(candidates, &info);
+					candidates
+						.try_insert(position, info)
+						.map_err(|_| Error::<T>::TooManyCandidates)?;
+					Ok(candidates.len())
+				})?;
+
+			Self::deposit_event(Event::CandidateBondUpdated {
+				account_id: who,
+				deposit: new_deposit,
+			});
+			Ok(Some(T::WeightInfo::update_bond(current_count as u32)).into())
+		}
+
+		/// Take the candidate slot of `target` by reserving `deposit`, which must be higher than
+		/// the deposit of `target`. The deposit of `target` is unreserved.
+		///
+		/// The account must already have registered session keys and this call is not available to
+		/// `Invulnerable` collators.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::take_candidate_slot(T::MaxCandidates::get()))]
+		pub fn take_candidate_slot(
+			origin: OriginFor<T>,
+			deposit: BalanceOf<T>,
+			target: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!Self::invulnerables().contains(&who), Error::<T>::AlreadyInvulnerable);
+			ensure!(deposit >= Self::candidacy_bond(), Error::<T>::DepositTooLow);
+
+			let validator_key = T::ValidatorIdOf::convert(who.clone())
+				.ok_or(Error::<T>::NoAssociatedValidatorId)?;
+			ensure!(
+				T::ValidatorRegistration::is_registered(&validator_key),
+				Error::<T>::ValidatorNotRegistered
+			);
+
+			let current_count =
+				<Candidates<T>>::try_mutate(|candidates| -> Result<usize, DispatchError> {
+					ensure!(
+						!candidates.iter().any(|candidate| candidate.who == who),
+						Error::<T>::AlreadyCandidate
+					);
+					let index = candidates
+						.iter()
+						.position(|candidate| candidate.who == target)
+						.ok_or(Error::<T>::TargetIsNotCandidate)?;
+					let replaced = candidates.remove(index);
+					let incoming = CandidateInfo { who: who.clone(), deposit };
+					ensure!(
+						Self::bond_of(&incoming) > Self::bond_of(&replaced),
+						Error::<T>::InsufficientBond
+					);
+
+					T::Currency::reserve(&who, deposit)?;
+					T::Currency::unreserve(&replaced.who, replaced.deposit);
+					<OfflineSessions<T>>::remove(&replaced.who);
+					<LastAuthoredBlock<T>>::remove(replaced.who);
+
+					let position = Self::candidate_position(candidates, &incoming);
+					candidates
+						.try_insert(position, incoming)
+						.map_err(|_| Error::<T>::TooManyCandidates)?;
+					// First authored block is current block plus kick threshold to handle session
+					// delay
+					<LastAuthoredBlock<T>>::insert(
+						who.clone(),
+						frame_system::Pallet::<T>::block_number() + T::KickThreshold::get(),
+					);
+					Ok(candidates.len())
+				})?;
+
+			Self::deposit_event(Event::CandidateReplaced { old: target, new: who, deposit });
+			Ok(Some(T::WeightInfo::take_candidate_slot(current_count as u32)).into())
+		}
+
+		/// Exempt `who` from being removed for not authoring blocks, or subject them to it again.
+		/// Exempting a candidate also resets its count of offline sessions.
+		///
+		/// Must be called by the `UpdateOrigin`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::set_liveness_exemption())]
+		pub fn set_liveness_exemption(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			exempt: bool,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			if exempt {
+				<LivenessExempt<T>>::insert(&who, ());
+				<OfflineSessions<T>>::remove(&who);
+			} else {
+				<LivenessExempt<T>>::remove(&who);
+			}
+			Self::deposit_event(Event::LivenessExemptionSet { account_id: who, exempt });
+			Ok(())
+		}
+
+		/// Delegate `amount` to `candidate`, adding to its bond. The delegator gets a share of the
+		/// rewards of the blocks authored by `candidate` that is proportional to its delegation.
+		///
+		/// The funds are reserved until they are undelegated and withdrawn.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::delegate(T::MaxCandidates::get()))]
+		pub fn delegate(
+			origin: OriginFor<T>,
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let delegated = <Delegations<T>>::get(&candidate, &who).saturating_add(amount);
+			ensure!(
+				!amount.is_zero() && delegated >= T::MinDelegation::get(),
+				Error::<T>::DelegationTooLow
+			);
+
+			let current_count =
+				<Candidates<T>>::try_mutate(|candidates| -> Result<usize, DispatchError> {
+					let index = candidates
+						.iter()
+						.position(|c| c.who == candidate)
+						.ok_or(Error::<T>::NotCandidate)?;
+
+					let mut total = <DelegationTotals<T>>::get(&candidate);
+					if delegated == amount {
+						ensure!(
+							total.delegators < T::MaxDelegatorsPerCandidate::get(),
+							Error::<T>::TooManyDelegators
+						);
+						total.delegators += 1;
+					}
+					total.amount = total.amount.saturating_add(amount);
+
+					T::Currency::reserve(&who, amount)?;
+					<Delegations<T>>::insert(&candidate, &who, delegated);
+					<DelegationTotals<T>>::insert(&candidate, total);
+					Self::reposition_candidate(candidates, index)?;
+					Ok(candidates.len())
+				})?;
+
+			Self::deposit_event(Event::Delegated { delegator: who, candidate, amount });
+			Ok(Some(T::WeightInfo::delegate(current_count as u32)).into())
+		}
+
+		/// Undelegate `amount`, or all of the delegation if it is lower, from `candidate`.
+		///
+		/// The funds can be withdrawn with `withdraw_unbonded` once the
+		/// [`Config::DelegationUnbondingPeriod`] has passed.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::undelegate(T::MaxCandidates::get()))]
+		pub fn undelegate(
+			origin: OriginFor<T>,
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let delegated = <Delegations<T>>::get(&candidate, &who);
+			ensure!(!delegated.is_zero(), Error::<T>::NotDelegator);
+			let amount = amount.min(delegated);
+			let remaining = delegated.saturating_sub(amount);
+			ensure!(
+				!amount.is_zero() && (remaining.is_zero() || remaining >= T::MinDelegation::get()),
+				Error::<T>::DelegationTooLow
+			);
+
+			let unlock_at =
+				frame_system::Pallet::<T>::block_number() + T::DelegationUnbondingPeriod::get();
+			<Unbonding<T>>::try_mutate(&who, |chunks| {
+				chunks
+					.try_push(UnbondingChunk { amount, unlock_at })
+					.map_err(|_| Error::<T>::TooManyUnbondingChunks)
+			})?;
+
+			let mut total = <DelegationTotals<T>>::get(&candidate);
+			total.amount = total.amount.saturating_sub(amount);
+			if remaining.is_zero() {
+				total.delegators = total.delegators.saturating_sub(1);
+				<Delegations<T>>::remove(&candidate, &who);
+			} else {
+				<Delegations<T>>::insert(&candidate, &who, remaining);
+			}
+			if total.delegators == 0 {
+				<DelegationTotals<T>>::remove(&candidate);
+			} else {
+				<DelegationTotals<T>>::insert(&candidate, total);
+			}
+
+			let current_count =
+				<Candidates<T>>::try_mutate(|candidates| -> Result<usize, DispatchError> {
+					if let Some(index) = candidates.iter().position(|c| c.who == candidate) {
+						Self::reposition_candidate(candidates, index)?;
+					}
+					Ok(candidates.len())
+				})?;
+
+			Self::deposit_event(Event::Undelegated {
+				delegator: who,
+				candidate,
+				amount,
+				unlock_at,
+			});
+			Ok(Some(T::WeightInfo::undelegate(current_count as u32)).into())
+		}
+
+		/// Withdraw all undelegated funds whose unbonding period has passed.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::withdraw_unbonded())]
+		pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut chunks = <Unbonding<T>>::get(&who);
+			let mut withdrawn: BalanceOf<T> = Zero::zero();
+			chunks.retain(|chunk| {
+				let unlocked = chunk.unlock_at <= now;
+				if unlocked {
+					withdrawn = withdrawn.saturating_add(chunk.amount);
+				}
+				!unlocked
+			});
+			ensure!(!withdrawn.is_zero(), Error::<T>::NothingToWithdraw);
+
+			T::Currency::unreserve(&who, withdrawn);
+			if chunks.is_empty() {
+				<Unbonding<T>>::remove(&who);
+			} else {
+				<Unbonding<T>>::insert(&who, chunks);
+			}
+
+			Self::deposit_event(Event::DelegationWithdrawn { delegator: who, amount: withdrawn });
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -570,6 +1024,68 @@ pub mod pallet {
 				.saturating_add(Invulnerables::<T>::decode_len().unwrap_or_default())
 		}
 
+		/// The bond of a candidate: its deposit plus the funds delegated to it.
+		pub fn bond_of(candidate: &CandidateInfo<T::AccountId, BalanceOf<T>>) -> BalanceOf<T> {
+			candidate
+				.deposit
+				.saturating_add(<DelegationTotals<T>>::get(&candidate.who).amount)
+		}
+
+		/// The position at which `candidate` is inserted into `candidates`: after all candidates
+		/// with an equal or higher bond.
+		fn candidate_position(
+			candidates: &[CandidateInfo<T::AccountId, BalanceOf<T>>],
+			candidate: &CandidateInfo<T::AccountId, BalanceOf<T>>,
+		) -> usize {
+			let bond = Self::bond_of(candidate);
+			candidates.partition_point(|other| Self::bond_of(other) >= bond)
+		}
+
+		/// Moves the candidate at `index` to the position matching its current bond.
+		fn reposition_candidate(
+			candidates: &mut BoundedVec<
+				CandidateInfo<T::AccountId, BalanceOf<T>>,
+				T::MaxCandidates,
+			>,
+			index: usize,
+		) -> DispatchResult {
+			let candidate = candidates.remove(index);
+			let position = Self::candidate_position(candidates, &candidate);
+			candidates
+				.try_insert(position, candidate)
+				.map_err(|_| Error::<T>::TooManyCandidates)?;
+			Ok(())
+		}
+
+		/// Pays the delegators of `author` their share of `reward`, proportional to their part of
+		/// the bond of `author`.
+		///
+		/// Returns the amount paid and the number of delegators.
+		pub(crate) fn reward_delegators(
+			pot: &T::AccountId,
+			author: &T::AccountId,
+			reward: BalanceOf<T>,
+		) -> (BalanceOf<T>, u32) {
+			let total = <DelegationTotals<T>>::get(author);
+			if total.amount.is_zero() {
+				return (Zero::zero(), 0)
+			}
+			// delegations only count while their candidate is one.
+			let Some(candidate) = Self::candidates().into_iter().find(|c| c.who == *author) else {
+				return (Zero::zero(), 0)
+			};
+
+			let bond = candidate.deposit.saturating_add(total.amount);
+			let mut paid: BalanceOf<T> = Zero::zero();
+			for (delegator, amount) in <Delegations<T>>::iter_prefix(author) {
+				let share = Perbill::from_rational(amount, bond) * reward;
+				if T::Currency::transfer(pot, &delegator, share, KeepAlive).is_ok() {
+					paid = paid.saturating_add(share);
+				}
+			}
+			(paid, total.delegators)
+		}
+
 		/// Removes a candidate if they exist and sends them back their deposit.
 		fn try_remove_candidate(
 			who: &T::AccountId,
@@ -586,20 +1102,32 @@ pub mod pallet {
 					if remove_last_authored {
 						<LastAuthoredBlock<T>>::remove(who.clone())
 					};
+					<OfflineSessions<T>>::remove(who);
 					Ok(candidates.len())
 				})?;
 			Self::deposit_event(Event::CandidateRemoved { account_id: who.clone() });
 			Ok(current_count)
 		}
 
-		/// Assemble the current set of candidates and invulnerables into the next collator set.
+		/// Assemble the invulnerables and the top `DesiredCandidates` of the given `candidates`,
+		/// which must be sorted by bond, into the next collator set.
 		///
 		/// This is done on the fly, as frequent as we are told to do so, as the session manager.
 		pub fn assemble_collators(
 			candidates: BoundedVec<T::AccountId, T::MaxCandidates>,
 		) -> Vec<T::AccountId> {
+			let desired_candidates = Self::desired_candidates() as usize;
+			let now = frame_system::Pallet::<T>::block_number();
 			let mut collators = Self::invulnerables().to_vec();
-			collators.extend(candidates);
+			for (index, candidate) in candidates.into_iter().enumerate() {
+				if index < desired_candidates {
+					collators.push(candidate);
+				} else {
+					// Candidates that are not elected cannot author blocks, so they are not
+					// considered lazy until they have been elected for the kick threshold.
+					<LastAuthoredBlock<T>>::insert(candidate, now + T::KickThreshold::get());
+				}
+			}
 			collators
 		}
 
@@ -642,6 +1170,93 @@ pub mod pallet {
 				.try_into()
 				.expect("filter_map operation can't result in a bounded vec larger than its original; qed")
 		}
+
+		/// Updates the offline sessions of the candidates elected for the session `index`, which
+		/// just ended, and removes those that have been offline for too long.
+		///
+		/// Returns the weight consumed.
+		fn track_liveness(index: SessionIndex) -> Weight {
+			let elected = <SessionCandidates<T>>::take(index);
+			let authored = <AuthoredBlocks<T>>::drain().collect::<BTreeMap<_, _>>();
+			let mut weight = T::DbWeight::get()
+				.reads_writes(1 + authored.len() as u64, 1 + authored.len() as u64);
+
+			let max_offline = T::MaxOfflineSessions::get();
+			if max_offline.is_zero() || authored.is_empty() {
+				// Either the tracking is disabled, or no block was authored at all in this
+				// session, e.g. because the relay chain stalled. Nobody is to blame then.
+				return weight
+			}
+
+			let candidates = Self::candidates();
+			let min_collators = T::MinEligibleCollators::get() as usize;
+			weight.saturating_accrue(T::DbWeight::get().reads(1));
+			for who in elected {
+				if !candidates.iter().any(|candidate| candidate.who == who) {
+					// left or was kicked in the meantime.
+					continue
+				}
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 1));
+				if authored.contains_key(&who) {
+					<OfflineSessions<T>>::remove(&who);
+					continue
+				}
+				if <LivenessExempt<T>>::contains_key(&who) {
+					continue
+				}
+
+				let offline_sessions = <OfflineSessions<T>>::mutate(&who, |sessions| {
+					sessions.saturating_inc();
+					*sessions
+				});
+				if offline_sessions >= max_offline && Self::eligible_collators() > min_collators {
+					weight.saturating_accrue(T::WeightInfo::remove_offline_candidate(
+						candidates.len() as u32,
+					));
+					if let Ok(slashed) = Self::remove_offline_candidate(&who) {
+						Self::deposit_event(Event::CandidateOffline {
+							account_id: who,
+							offline_sessions,
+							slashed,
+						});
+					}
+				}
+			}
+			weight
+		}
+
+		/// Removes an offline candidate, moves [`Config::OfflineSlash`] of its deposit to the Pot
+		/// and sends it back the rest.
+		///
+		/// Returns the amount slashed.
+		pub(crate) fn remove_offline_candidate(
+			who: &T::AccountId,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let candidate =
+				<Candidates<T>>::try_mutate(|candidates| -> Result<_, DispatchError> {
+					let index = candidates
+						.iter()
+						.position(|candidate| candidate.who == *who)
+						.ok_or(Error::<T>::NotCandidate)?;
+					Ok(candidates.remove(index))
+				})?;
+
+			let slash = T::OfflineSlash::get() * candidate.deposit;
+			let not_slashed = T::Currency::repatriate_reserved(
+				who,
+				&Self::account_id(),
+				slash,
+				BalanceStatus::Free,
+			)
+			.unwrap_or(slash);
+			let slashed = slash.saturating_sub(not_slashed);
+			T::Currency::unreserve(who, candidate.deposit.saturating_sub(slashed));
+			<LastAuthoredBlock<T>>::remove(who);
+			<OfflineSessions<T>>::remove(who);
+
+			Self::deposit_event(Event::CandidateRemoved { account_id: who.clone() });
+			Ok(slashed)
+		}
 	}
 
 	/// Keep track of number of authored blocks per authority, uncles are counted as well since
@@ -656,13 +1271,22 @@ pub mod pallet {
 				.checked_sub(&T::Currency::minimum_balance())
 				.unwrap_or_else(Zero::zero)
 				.div(2u32.into());
+			let (delegators_reward, delegators) = Self::reward_delegators(&pot, &author, reward);
 			// `reward` is half of pot account minus ED, this should never fail.
-			let _success = T::Currency::transfer(&pot, &author, reward, KeepAlive);
+			let _success = T::Currency::transfer(
+				&pot,
+				&author,
+				reward.saturating_sub(delegators_reward),
+				KeepAlive,
+			);
 			debug_assert!(_success.is_ok());
+			<AuthoredBlocks<T>>::mutate(&author, |blocks| blocks.saturating_inc());
 			<LastAuthoredBlock<T>>::insert(author, frame_system::Pallet::<T>::block_number());
 
 			frame_system::Pallet::<T>::register_extra_weight_unchecked(
-				T::WeightInfo::note_author(),
+				T::WeightInfo::note_author()
+					.saturating_add(T::WeightInfo::reward_delegators(delegators))
+					.saturating_add(T::DbWeight::get().reads_writes(1, 1)),
 				DispatchClass::Mandatory,
 			);
 		}
@@ -683,8 +1307,16 @@ pub mod pallet {
 			let removed = candidates_len_before - active_candidates.len();
 			let result = Self::assemble_collators(active_candidates);
 
+			// remember who is expected to author blocks in this session.
+			let invulnerables = Invulnerables::<T>::decode_len().unwrap_or_default();
+			let elected = BoundedVec::<_, T::MaxCandidates>::truncate_from(
+				result.iter().skip(invulnerables).cloned().collect(),
+			);
+			<SessionCandidates<T>>::insert(index, elected);
+
 			frame_system::Pallet::<T>::register_extra_weight_unchecked(
-				T::WeightInfo::new_session(candidates_len_before as u32, removed as u32),
+				T::WeightInfo::new_session(candidates_len_before as u32, removed as u32)
+					.saturating_add(T::DbWeight::get().writes(1)),
 				DispatchClass::Mandatory,
 			);
 			Some(result)
@@ -692,8 +1324,12 @@ pub mod pallet {
 		fn start_session(_: SessionIndex) {
 			// we don't care.
 		}
-		fn end_session(_: SessionIndex) {
-			// we don't care.
+		fn end_session(index: SessionIndex) {
+			let weight = Self::track_liveness(index);
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(
+				weight,
+				DispatchClass::Mandatory,
+			);
 		}
 	}
 }
@@ -90,3 +90,72 @@ pub mod v1 {
 		}
 	}
 }
+
+/// Version 2 Migration
+/// This migration sorts the existing `Candidates` by deposit, keeping the candidates with equal
+/// deposits in the order in which they joined.
+pub mod v2 {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	#[cfg(feature = "try-runtime")]
+	use sp_std::prelude::*;
+
+	pub struct MigrateToV2<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let onchain_version = Pallet::<T>::on_chain_storage_version();
+			if onchain_version == 1 {
+				let candidates_len = Candidates::<T>::decode_len().unwrap_or_default();
+				<Candidates<T>>::mutate(|candidates| {
+					// `sort_by` is stable, so the order of equal deposits is kept.
+					candidates.sort_by(|a, b| b.deposit.cmp(&a.deposit));
+				});
+
+				StorageVersion::new(2).put::<Pallet<T>>();
+				log::info!(
+					target: LOG_TARGET,
+					"Sorted {} Candidates, upgraded storage to version 2",
+					candidates_len,
+				);
+				// 1 read and write for the candidates, 1 read for `onchain_version`, 1 write to put
+				// version
+				T::DbWeight::get().reads_writes(2, 2)
+			} else {
+				log::info!(
+					target: LOG_TARGET,
+					"Migration did not execute. This probably should be removed"
+				);
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::DispatchError> {
+			let number_of_candidates = Candidates::<T>::decode_len().unwrap_or_default();
+			Ok((number_of_candidates as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(number_of_candidates: Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+			let candidates = Candidates::<T>::get();
+			frame_support::ensure!(
+				candidates.windows(2).all(|pair| pair[0].deposit >= pair[1].deposit),
+				"after migration, the stored candidates should be sorted by deposit"
+			);
+
+			let number_of_candidates: u32 = Decode::decode(&mut number_of_candidates.as_slice())
+				.expect(
+					"the state parameter should be something that was generated by pre_upgrade",
+				);
+			frame_support::ensure!(
+				number_of_candidates == candidates.len() as u32,
+				"after migration, there should be the same number of candidates"
+			);
+
+			let onchain_version = Pallet::<T>::on_chain_storage_version();
+			frame_support::ensure!(onchain_version >= 2, "must_upgrade");
+
+			Ok(())
+		}
+	}
+}
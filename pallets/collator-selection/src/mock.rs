@@ -26,7 +26,7 @@ use sp_core::H256;
 use sp_runtime::{
 	testing::UintAuthorityId,
 	traits::{BlakeTwo256, IdentityLookup, OpaqueKeys},
-	BuildStorage, RuntimeAppPublic,
+	BuildStorage, Perbill, RuntimeAppPublic,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -185,6 +185,10 @@ ord_parameter_types! {
 
 parameter_types! {
 	pub const PotId: PalletId = PalletId(*b"PotStake");
+	pub const OfflineSlash: Perbill = Perbill::from_percent(50);
+	pub static KickThreshold: u64 = Period::get();
+	// liveness tracking is disabled unless a test enables it.
+	pub static MaxOfflineSessions: u32 = 0;
 }
 
 pub struct IsRegistered;
@@ -202,7 +206,13 @@ impl Config for Test {
 	type MaxCandidates = ConstU32<20>;
 	type MinEligibleCollators = ConstU32<1>;
 	type MaxInvulnerables = ConstU32<20>;
-	type KickThreshold = Period;
+	type KickThreshold = KickThreshold;
+	type MaxOfflineSessions = MaxOfflineSessions;
+	type OfflineSlash = OfflineSlash;
+	type MaxDelegatorsPerCandidate = ConstU32<2>;
+	type MinDelegation = ConstU64<5>;
+	type DelegationUnbondingPeriod = ConstU64<5>;
+	type MaxUnbondingChunks = ConstU32<2>;
 	type ValidatorId = <Self as frame_system::Config>::AccountId;
 	type ValidatorIdOf = IdentityCollator;
 	type ValidatorRegistration = IsRegistered;
@@ -14,12 +14,14 @@
 // limitations under the License.
 
 use crate as collator_selection;
-use crate::{mock::*, CandidateInfo, Error};
+use crate::{mock::*, AuthoredBlocks, CandidateInfo, DelegationTotal, Error, SessionCandidates};
 use frame_support::{
 	assert_noop, assert_ok,
 	traits::{Currency, OnInitialize},
+	BoundedVec,
 };
 use pallet_balances::Error as BalancesError;
+use pallet_session::SessionManager;
 use sp_runtime::{testing::UintAuthorityId, traits::BadOrigin, BuildStorage};
 
 #[test]
@@ -289,21 +291,28 @@ fn cannot_register_candidate_if_too_many() {
 		// reset desired candidates:
 		<crate::DesiredCandidates<Test>>::put(0);
 
-		// can't accept anyone anymore.
-		assert_noop!(
-			CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)),
-			Error::<Test>::TooManyCandidates,
-		);
-
-		// reset desired candidates:
-		<crate::DesiredCandidates<Test>>::put(1);
-		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+		// candidates are accepted even if none of them would be elected.
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
 
-		// but no more
-		assert_noop!(
-			CollatorSelection::register_as_candidate(RuntimeOrigin::signed(5)),
-			Error::<Test>::TooManyCandidates,
-		);
+		// MaxCandidates: u32 = 20
+		for ii in 4..=23 {
+			// only keys were registered in mock for 1 to 5
+			if ii > 5 {
+				Balances::make_free_balance_be(&ii, 100);
+				let key = MockSessionKeys { aura: UintAuthorityId(ii) };
+				Session::set_keys(RuntimeOrigin::signed(ii).into(), key, Vec::new()).unwrap();
+			}
+			if ii < 23 {
+				assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(ii)));
+			} else {
+				// but no more than `MaxCandidates`
+				assert_noop!(
+					CollatorSelection::register_as_candidate(RuntimeOrigin::signed(ii)),
+					Error::<Test>::TooManyCandidates,
+				);
+			}
+		}
+		assert_eq!(CollatorSelection::candidates().len(), 20);
 	})
 }
 
@@ -421,6 +430,142 @@ fn register_as_candidate_works() {
 	});
 }
 
+#[test]
+fn update_bond_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+
+		// raising the deposit moves 4 to the top.
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(4), 20));
+		System::assert_last_event(RuntimeEvent::CollatorSelection(
+			crate::Event::CandidateBondUpdated { account_id: 4, deposit: 20 },
+		));
+		assert_eq!(
+			CollatorSelection::candidates(),
+			vec![CandidateInfo { who: 4, deposit: 20 }, CandidateInfo { who: 3, deposit: 10 }]
+		);
+		assert_eq!(Balances::free_balance(4), 80);
+
+		// equal deposits keep their order.
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(3), 20));
+		assert_eq!(
+			CollatorSelection::candidates(),
+			vec![CandidateInfo { who: 4, deposit: 20 }, CandidateInfo { who: 3, deposit: 20 }]
+		);
+
+		// once they are no longer elected, candidates can lower their deposit.
+		<crate::DesiredCandidates<Test>>::put(1);
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(3), 15));
+		assert_eq!(Balances::free_balance(3), 85);
+		assert_eq!(
+			CollatorSelection::candidates(),
+			vec![CandidateInfo { who: 4, deposit: 20 }, CandidateInfo { who: 3, deposit: 15 }]
+		);
+	});
+}
+
+#[test]
+fn cannot_update_bond_if_invalid() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(3), 20));
+
+		// not a candidate.
+		assert_noop!(
+			CollatorSelection::update_bond(RuntimeOrigin::signed(4), 20),
+			Error::<Test>::NotCandidate
+		);
+		// below the candidacy bond.
+		assert_noop!(
+			CollatorSelection::update_bond(RuntimeOrigin::signed(3), 5),
+			Error::<Test>::DepositTooLow
+		);
+		// nothing to update.
+		assert_noop!(
+			CollatorSelection::update_bond(RuntimeOrigin::signed(3), 20),
+			Error::<Test>::IdenticalDeposit
+		);
+		// elected candidates cannot lower their deposit.
+		assert_noop!(
+			CollatorSelection::update_bond(RuntimeOrigin::signed(3), 15),
+			Error::<Test>::InvalidUnreserve
+		);
+		// poor
+		assert_noop!(
+			CollatorSelection::update_bond(RuntimeOrigin::signed(3), 200),
+			BalancesError::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn take_candidate_slot_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+		assert_eq!(Balances::free_balance(4), 90);
+
+		assert_ok!(CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(5), 15, 4));
+		System::assert_last_event(RuntimeEvent::CollatorSelection(
+			crate::Event::CandidateReplaced { old: 4, new: 5, deposit: 15 },
+		));
+		assert_eq!(
+			CollatorSelection::candidates(),
+			vec![CandidateInfo { who: 5, deposit: 15 }, CandidateInfo { who: 3, deposit: 10 }]
+		);
+		assert_eq!(CollatorSelection::last_authored_block(5), 10);
+		// the replaced candidate gets its deposit back.
+		assert_eq!(Balances::free_balance(4), 100);
+		assert_eq!(CollatorSelection::last_authored_block(4), 0);
+		assert_eq!(Balances::free_balance(5), 85);
+	});
+}
+
+#[test]
+fn cannot_take_candidate_slot_if_invalid() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+
+		// invulnerables cannot be candidates.
+		assert_noop!(
+			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(1), 15, 4),
+			Error::<Test>::AlreadyInvulnerable
+		);
+		// below the candidacy bond.
+		assert_noop!(
+			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(5), 5, 4),
+			Error::<Test>::DepositTooLow
+		);
+		// keys not registered.
+		assert_noop!(
+			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(42), 15, 4),
+			Error::<Test>::ValidatorNotRegistered
+		);
+		// already a candidate.
+		assert_noop!(
+			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(3), 15, 4),
+			Error::<Test>::AlreadyCandidate
+		);
+		// the target is not a candidate.
+		assert_noop!(
+			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(5), 15, 2),
+			Error::<Test>::TargetIsNotCandidate
+		);
+		// the deposit must exceed the one of the target.
+		assert_noop!(
+			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(5), 10, 4),
+			Error::<Test>::InsufficientBond
+		);
+		// poor
+		assert_noop!(
+			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(5), 200, 4),
+			BalancesError::<Test>::InsufficientBalance
+		);
+	});
+}
+
 #[test]
 fn leave_intent() {
 	new_test_ext().execute_with(|| {
@@ -530,6 +675,29 @@ fn session_management_works() {
 	});
 }
 
+#[test]
+fn top_candidates_by_deposit_are_elected() {
+	new_test_ext().execute_with(|| {
+		<crate::DesiredCandidates<Test>>::put(1);
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(4), 20));
+
+		initialize_to_block(10);
+		// only the top candidate is queued.
+		assert_eq!(
+			Session::queued_keys().iter().map(|(who, _)| *who).collect::<Vec<_>>(),
+			vec![1, 2, 4]
+		);
+
+		initialize_to_block(20);
+		assert_eq!(SessionHandlerCollators::get(), vec![1, 2, 4]);
+		// 3 is not kicked for not authoring, as it was not elected.
+		assert_eq!(CollatorSelection::candidates().len(), 2);
+		assert_eq!(CollatorSelection::last_authored_block(3), 30);
+	});
+}
+
 #[test]
 fn kick_mechanism() {
 	new_test_ext().execute_with(|| {
@@ -623,6 +791,310 @@ fn should_kick_invulnerables_from_candidates_on_session_change() {
 	});
 }
 
+#[test]
+fn delegate_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(5), 4, 10));
+		System::assert_last_event(RuntimeEvent::CollatorSelection(crate::Event::Delegated {
+			delegator: 5,
+			candidate: 4,
+			amount: 10,
+		}));
+		assert_eq!(Balances::reserved_balance(5), 10);
+		assert_eq!(CollatorSelection::delegations(4, 5), 10);
+		assert_eq!(
+			CollatorSelection::delegation_total(4),
+			DelegationTotal { amount: 10, delegators: 1 }
+		);
+		// the delegation moves 4 to the top.
+		assert_eq!(
+			CollatorSelection::candidates(),
+			vec![CandidateInfo { who: 4, deposit: 10 }, CandidateInfo { who: 3, deposit: 10 }]
+		);
+
+		// delegating again adds to the delegation.
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(5), 4, 5));
+		assert_eq!(CollatorSelection::delegations(4, 5), 15);
+		assert_eq!(
+			CollatorSelection::delegation_total(4),
+			DelegationTotal { amount: 15, delegators: 1 }
+		);
+
+		// taking the slot of 4 needs more than its bond.
+		assert_noop!(
+			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(5), 20, 4),
+			Error::<Test>::InsufficientBond
+		);
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(3), 30));
+		assert_eq!(
+			CollatorSelection::candidates(),
+			vec![CandidateInfo { who: 3, deposit: 30 }, CandidateInfo { who: 4, deposit: 10 }]
+		);
+	});
+}
+
+#[test]
+fn cannot_delegate_if_invalid() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+
+		// not a candidate.
+		assert_noop!(
+			CollatorSelection::delegate(RuntimeOrigin::signed(5), 4, 10),
+			Error::<Test>::NotCandidate
+		);
+		// below the minimum delegation.
+		assert_noop!(
+			CollatorSelection::delegate(RuntimeOrigin::signed(5), 3, 4),
+			Error::<Test>::DelegationTooLow
+		);
+		// poor
+		assert_noop!(
+			CollatorSelection::delegate(RuntimeOrigin::signed(5), 3, 200),
+			BalancesError::<Test>::InsufficientBalance
+		);
+
+		// MaxDelegatorsPerCandidate: u32 = 2
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(1), 3, 10));
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(2), 3, 10));
+		assert_noop!(
+			CollatorSelection::delegate(RuntimeOrigin::signed(5), 3, 10),
+			Error::<Test>::TooManyDelegators
+		);
+		// existing delegators can still add to their delegation.
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(1), 3, 1));
+	});
+}
+
+#[test]
+fn undelegate_and_withdraw_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(5), 4, 10));
+
+		assert_noop!(
+			CollatorSelection::undelegate(RuntimeOrigin::signed(5), 3, 10),
+			Error::<Test>::NotDelegator
+		);
+		assert_ok!(CollatorSelection::undelegate(RuntimeOrigin::signed(5), 4, 4));
+		System::assert_last_event(RuntimeEvent::CollatorSelection(crate::Event::Undelegated {
+			delegator: 5,
+			candidate: 4,
+			amount: 4,
+			unlock_at: 5,
+		}));
+		assert_eq!(CollatorSelection::delegations(4, 5), 6);
+
+		// the rest of the delegation would be below the minimum delegation.
+		assert_noop!(
+			CollatorSelection::undelegate(RuntimeOrigin::signed(5), 4, 3),
+			Error::<Test>::DelegationTooLow
+		);
+		// undelegating more than delegated undelegates everything.
+		assert_ok!(CollatorSelection::undelegate(RuntimeOrigin::signed(5), 4, 100));
+		assert_eq!(CollatorSelection::delegations(4, 5), 0);
+		assert_eq!(CollatorSelection::delegation_total(4), DelegationTotal::default());
+		assert_eq!(
+			CollatorSelection::candidates(),
+			vec![CandidateInfo { who: 3, deposit: 10 }, CandidateInfo { who: 4, deposit: 10 }]
+		);
+		// the funds stay reserved until they are withdrawn.
+		assert_eq!(Balances::reserved_balance(5), 10);
+
+		// MaxUnbondingChunks: u32 = 2
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(5), 3, 10));
+		assert_noop!(
+			CollatorSelection::undelegate(RuntimeOrigin::signed(5), 3, 10),
+			Error::<Test>::TooManyUnbondingChunks
+		);
+
+		// DelegationUnbondingPeriod: u64 = 5
+		assert_noop!(
+			CollatorSelection::withdraw_unbonded(RuntimeOrigin::signed(5)),
+			Error::<Test>::NothingToWithdraw
+		);
+		System::set_block_number(5);
+		assert_ok!(CollatorSelection::withdraw_unbonded(RuntimeOrigin::signed(5)));
+		System::assert_last_event(RuntimeEvent::CollatorSelection(
+			crate::Event::DelegationWithdrawn { delegator: 5, amount: 10 },
+		));
+		assert_eq!(CollatorSelection::unbonding(5).len(), 0);
+		assert_eq!(Balances::reserved_balance(5), 10);
+		assert_eq!(Balances::free_balance(5), 90);
+	});
+}
+
+#[test]
+fn delegations_are_kept_when_candidate_leaves() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(5), 3, 10));
+		assert_ok!(CollatorSelection::leave_intent(RuntimeOrigin::signed(3)));
+
+		// they can still be undelegated.
+		assert_eq!(CollatorSelection::delegations(3, 5), 10);
+		assert_ok!(CollatorSelection::undelegate(RuntimeOrigin::signed(5), 3, 5));
+
+		// and count again once the candidate is back.
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_eq!(
+			CollatorSelection::candidates(),
+			vec![CandidateInfo { who: 3, deposit: 10 }, CandidateInfo { who: 4, deposit: 10 }]
+		);
+	});
+}
+
+#[test]
+fn delegators_share_rewards() {
+	new_test_ext().execute_with(|| {
+		// put 100 in the pot + 5 for ED
+		Balances::make_free_balance_be(&CollatorSelection::account_id(), 105);
+
+		// 4 is the default author.
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(5), 4, 10));
+		assert_eq!(Balances::free_balance(4), 90);
+		assert_eq!(Balances::free_balance(5), 90);
+
+		// triggers `note_author`
+		Authorship::on_initialize(1);
+
+		// the reward is half of the pot, split evenly as both bonded 10.
+		assert_eq!(Balances::free_balance(4), 115);
+		assert_eq!(Balances::free_balance(5), 115);
+		assert_eq!(Balances::free_balance(CollatorSelection::account_id()), 55);
+	});
+}
+
+/// Ends the session `index`, in which `elected` were the elected candidates and `authors` authored
+/// one block each.
+fn end_session(index: u32, elected: Vec<u64>, authors: Vec<u64>) {
+	<SessionCandidates<Test>>::insert(index, BoundedVec::truncate_from(elected));
+	for author in authors {
+		<AuthoredBlocks<Test>>::mutate(author, |blocks| *blocks += 1);
+	}
+	<CollatorSelection as SessionManager<_>>::end_session(index);
+}
+
+#[test]
+fn offline_candidates_are_removed_and_slashed() {
+	new_test_ext().execute_with(|| {
+		MaxOfflineSessions::set(1);
+		// make sure the kick mechanism does not remove 3 first.
+		KickThreshold::set(100);
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+
+		// 3 and 4 are elected for session 2.
+		initialize_to_block(20);
+		assert_eq!(CollatorSelection::candidates().len(), 2);
+
+		// 4 authored all blocks of session 2, 3 none.
+		initialize_to_block(30);
+		System::assert_has_event(RuntimeEvent::CollatorSelection(crate::Event::CandidateOffline {
+			account_id: 3,
+			offline_sessions: 1,
+			slashed: 5,
+		}));
+		assert_eq!(CollatorSelection::candidates(), vec![CandidateInfo { who: 4, deposit: 10 }]);
+		assert_eq!(CollatorSelection::offline_sessions(4), 0);
+		// half of the deposit went to the pot, the rest was sent back.
+		assert_eq!(Balances::free_balance(3), 95);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::free_balance(CollatorSelection::account_id()), 5);
+	});
+}
+
+#[test]
+fn offline_sessions_must_be_consecutive() {
+	new_test_ext().execute_with(|| {
+		MaxOfflineSessions::set(2);
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+
+		end_session(5, vec![3, 4], vec![4]);
+		assert_eq!(CollatorSelection::offline_sessions(3), 1);
+		assert_eq!(CollatorSelection::offline_sessions(4), 0);
+
+		// authoring a block resets the count.
+		end_session(6, vec![3, 4], vec![3]);
+		assert_eq!(CollatorSelection::offline_sessions(3), 0);
+		assert_eq!(CollatorSelection::offline_sessions(4), 1);
+
+		// nobody is to blame if no block was authored at all.
+		end_session(7, vec![3, 4], vec![]);
+		assert_eq!(CollatorSelection::offline_sessions(3), 0);
+		assert_eq!(CollatorSelection::offline_sessions(4), 1);
+
+		// unelected candidates are not counted.
+		end_session(8, vec![3], vec![3]);
+		assert_eq!(CollatorSelection::offline_sessions(4), 1);
+
+		end_session(9, vec![3, 4], vec![3]);
+		assert_eq!(CollatorSelection::candidates(), vec![CandidateInfo { who: 3, deposit: 10 }]);
+		assert_eq!(CollatorSelection::offline_sessions(4), 0);
+		assert_eq!(Balances::free_balance(4), 95);
+	});
+}
+
+#[test]
+fn should_not_remove_offline_candidates_if_too_few() {
+	new_test_ext().execute_with(|| {
+		MaxOfflineSessions::set(1);
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::set_invulnerables(
+			RuntimeOrigin::signed(RootAccount::get()),
+			vec![]
+		));
+
+		// MinEligibleCollators: u32 = 1
+		end_session(5, vec![3], vec![4]);
+		assert_eq!(CollatorSelection::candidates().len(), 1);
+		assert_eq!(CollatorSelection::offline_sessions(3), 1);
+	});
+}
+
+#[test]
+fn liveness_exemption_works() {
+	new_test_ext().execute_with(|| {
+		MaxOfflineSessions::set(1);
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+
+		// only the update origin can exempt candidates.
+		assert_noop!(
+			CollatorSelection::set_liveness_exemption(RuntimeOrigin::signed(1), 3, true),
+			BadOrigin
+		);
+		assert_ok!(CollatorSelection::set_liveness_exemption(
+			RuntimeOrigin::signed(RootAccount::get()),
+			3,
+			true
+		));
+		System::assert_last_event(RuntimeEvent::CollatorSelection(
+			crate::Event::LivenessExemptionSet { account_id: 3, exempt: true },
+		));
+
+		end_session(5, vec![3], vec![4]);
+		end_session(6, vec![3], vec![4]);
+		assert_eq!(CollatorSelection::candidates().len(), 1);
+		assert_eq!(CollatorSelection::offline_sessions(3), 0);
+
+		// an offline session after the exemption was lifted gets them removed.
+		assert_ok!(CollatorSelection::set_liveness_exemption(
+			RuntimeOrigin::signed(RootAccount::get()),
+			3,
+			false
+		));
+		end_session(7, vec![3], vec![4]);
+		assert_eq!(CollatorSelection::candidates(), Vec::new());
+	});
+}
+
 #[test]
 #[should_panic = "duplicate invulnerables in genesis."]
 fn cannot_set_genesis_value_twice() {
@@ -25,6 +25,10 @@ use frame_support::{
 use sp_std::marker::PhantomData;
 
 // The weight info trait for `pallet_collator_selection`.
+//
+// The weights of `update_bond`, `take_candidate_slot`, `set_liveness_exemption`,
+// `remove_offline_candidate`, `delegate`, `undelegate`, `withdraw_unbonded` and `reward_delegators`
+// are not benchmarked yet and are rough estimations of the worst case.
 pub trait WeightInfo {
 	fn set_invulnerables(_b: u32) -> Weight;
 	fn add_invulnerable(_b: u32, _c: u32) -> Weight;
@@ -35,6 +39,14 @@ pub trait WeightInfo {
 	fn leave_intent(_c: u32) -> Weight;
 	fn note_author() -> Weight;
 	fn new_session(_c: u32, _r: u32) -> Weight;
+	fn update_bond(_c: u32) -> Weight;
+	fn take_candidate_slot(_c: u32) -> Weight;
+	fn set_liveness_exemption() -> Weight;
+	fn remove_offline_candidate(_c: u32) -> Weight;
+	fn delegate(_c: u32) -> Weight;
+	fn undelegate(_c: u32) -> Weight;
+	fn withdraw_unbonded() -> Weight;
+	fn reward_delegators(_d: u32) -> Weight;
 }
 
 /// Weights for pallet_collator_selection using the Substrate node and recommended hardware.
@@ -66,6 +78,55 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	fn update_bond(c: u32) -> Weight {
+		Weight::from_parts(56_085_000_u64, 0)
+			.saturating_add(Weight::from_parts(160_000_u64, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c as u64)))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn take_candidate_slot(c: u32) -> Weight {
+		Weight::from_parts(82_460_000_u64, 0)
+			.saturating_add(Weight::from_parts(215_000_u64, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c as u64)))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	fn set_liveness_exemption() -> Weight {
+		Weight::from_parts(15_912_000_u64, 0).saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn remove_offline_candidate(c: u32) -> Weight {
+		Weight::from_parts(45_230_000_u64, 0)
+			.saturating_add(Weight::from_parts(180_000_u64, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	fn delegate(c: u32) -> Weight {
+		Weight::from_parts(52_340_000_u64, 0)
+			.saturating_add(Weight::from_parts(1_163_000_u64, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c as u64)))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn undelegate(c: u32) -> Weight {
+		Weight::from_parts(55_021_000_u64, 0)
+			.saturating_add(Weight::from_parts(1_170_000_u64, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c as u64)))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn withdraw_unbonded() -> Weight {
+		Weight::from_parts(38_725_000_u64, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn reward_delegators(d: u32) -> Weight {
+		Weight::from_parts(9_512_000_u64, 0)
+			.saturating_add(Weight::from_parts(33_840_000_u64, 0).saturating_mul(d as u64))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d as u64)))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(d as u64)))
+	}
 	fn note_author() -> Weight {
 		Weight::from_parts(71_461_000_u64, 0)
 			.saturating_add(T::DbWeight::get().reads(3_u64))
@@ -153,6 +214,55 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	fn update_bond(c: u32) -> Weight {
+		Weight::from_parts(56_085_000_u64, 0)
+			.saturating_add(Weight::from_parts(160_000_u64, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn take_candidate_slot(c: u32) -> Weight {
+		Weight::from_parts(82_460_000_u64, 0)
+			.saturating_add(Weight::from_parts(215_000_u64, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn set_liveness_exemption() -> Weight {
+		Weight::from_parts(15_912_000_u64, 0).saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn remove_offline_candidate(c: u32) -> Weight {
+		Weight::from_parts(45_230_000_u64, 0)
+			.saturating_add(Weight::from_parts(180_000_u64, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	fn delegate(c: u32) -> Weight {
+		Weight::from_parts(52_340_000_u64, 0)
+			.saturating_add(Weight::from_parts(1_163_000_u64, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn undelegate(c: u32) -> Weight {
+		Weight::from_parts(55_021_000_u64, 0)
+			.saturating_add(Weight::from_parts(1_170_000_u64, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn withdraw_unbonded() -> Weight {
+		Weight::from_parts(38_725_000_u64, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn reward_delegators(d: u32) -> Weight {
+		Weight::from_parts(9_512_000_u64, 0)
+			.saturating_add(Weight::from_parts(33_840_000_u64, 0).saturating_mul(d as u64))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(d as u64)))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(d as u64)))
+	}
 	fn note_author() -> Weight {
 		Weight::from_parts(71_461_000_u64, 0)
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
@@ -0,0 +1,82 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarking setup for cumulus-pallet-delayed-execution
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_std::vec;
+
+/// Returns the longest call, that fits into the `MaxCallLen`.
+fn longest_call<T: Config>() -> <T as Config>::RuntimeCall {
+	let mut remark = vec![];
+	loop {
+		let call: <T as Config>::RuntimeCall =
+			frame_system::Call::<T>::remark { remark: remark.clone() }.into();
+		if call.encoded_size() >= T::MaxCallLen::get() as usize {
+			remark.pop();
+			return frame_system::Call::<T>::remark { remark }.into()
+		}
+		remark.push(0);
+	}
+}
+
+/// Fills the queue of the block `n` with `c` of the longest calls.
+fn fill_queue<T: Config>(n: BlockNumberFor<T>, c: u32) {
+	let encoded_call = EncodedCallOf::<T>::truncate_from(longest_call::<T>().encode());
+	let calls = (0..c)
+		.map(|i| (account("sender", i, 0), encoded_call.clone()))
+		.collect::<Vec<_>>();
+	Queue::<T>::insert(n, QueuedCallsOf::<T>::truncate_from(calls));
+}
+
+benchmarks! {
+	submit {
+		let caller: T::AccountId = whitelisted_caller();
+		let n = frame_system::Pallet::<T>::block_number();
+		// the new call is appended to the queue, that is almost full
+		fill_queue::<T>(n, T::MaxQueuedCalls::get() - 1);
+		let call = Box::new(longest_call::<T>());
+	}: _(RawOrigin::Signed(caller), call)
+	verify {
+		assert_eq!(Queue::<T>::decode_len(n), Some(T::MaxQueuedCalls::get() as usize));
+	}
+
+	on_initialize {
+		let n: BlockNumberFor<T> = 2u32.into();
+		NextQueue::<T>::put(n);
+	}: {
+		Pallet::<T>::execute_queued_calls(n);
+	} verify {
+		assert_eq!(NextQueue::<T>::get(), Some(n));
+	}
+
+	service_queue {
+		let c in 0 .. T::MaxQueuedCalls::get();
+		let n: BlockNumberFor<T> = 1u32.into();
+		fill_queue::<T>(n, c);
+	}: {
+		Pallet::<T>::take_queue(n);
+	} verify {
+		assert!(!Queue::<T>::contains_key(n));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
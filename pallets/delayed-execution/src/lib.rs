@@ -0,0 +1,306 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet that delays the execution of the calls to the next block.
+//!
+//! The calls, that are submitted in the block `N`, are only stored in the queue. They are executed
+//! at the beginning of the block `N + 1` in an order, that is shuffled with the `ShufflingSeed`.
+//! As the seed is not known when the calls are included, the collator of the block `N` can't order
+//! them to its advantage, which mitigates front-running, e.g. on DEX parachains.
+//!
+//! The [`DigestShufflingSeed`] uses the seed, that the collator has committed in the header of the
+//! block with the [`CumulusDigestItem::ShufflingSeed`] digest. It is derived from the relay parent
+//! storage root, i.e. depends on the randomness of the relay chain, and is checked by the
+//! `ShufflingBlockExecutor` of the `cumulus-pallet-aura-ext`.
+//!
+//! The sender pays for the weight of the call at the submission. The block, that executes the
+//! call, only accounts for the weight, that the call has actually used. At most
+//! `MaxExecutionWeight` is spent on executing the calls in a block. The calls, that don't fit,
+//! are carried over and executed before any calls, that have been submitted later.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use cumulus_primitives_core::{extract_shuffling_seed, CumulusDigestItem};
+use frame_support::{
+	dispatch::{extract_actual_weight, GetDispatchInfo, PostDispatchInfo},
+	pallet_prelude::*,
+	traits::DefensiveTruncateFrom,
+};
+use frame_system::pallet_prelude::*;
+use sp_io::hashing::blake2_256;
+use sp_runtime::traits::{Dispatchable, One, Saturating};
+use sp_std::{boxed::Box, marker::PhantomData, vec::Vec};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+/// The context of the fallback seeds of the [`DigestShufflingSeed`].
+const SHUFFLING_SEED_CONTEXT: &[u8] = b"cumulus-delayed-execution";
+
+/// Shuffles the `items` with the `seed`.
+pub fn shuffle<Item>(items: &mut [Item], seed: &[u8; 32]) {
+	let mut counter = 0u64;
+	let mut next_random = || {
+		counter += 1;
+		let random = (seed, counter).using_encoded(blake2_256);
+		u64::from_le_bytes(random[..8].try_into().expect("the hash has 32 bytes; qed"))
+	};
+	for i in (1..items.len()).rev() {
+		let j = (next_random() % (i as u64 + 1)) as usize;
+		items.swap(i, j);
+	}
+}
+
+/// The shuffling seed, that has been committed in the header of the current block.
+///
+/// The digest is available from the beginning of the block, when the calls are executed. Blocks
+/// without the committed seed fall back to a seed, that is derived from the parent hash, which is
+/// known to the collator in advance.
+pub struct DigestShufflingSeed<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> Get<[u8; 32]> for DigestShufflingSeed<T> {
+	fn get() -> [u8; 32] {
+		extract_shuffling_seed(&frame_system::Pallet::<T>::digest()).unwrap_or_else(|| {
+			(SHUFFLING_SEED_CONTEXT, frame_system::Pallet::<T>::parent_hash())
+				.using_encoded(blake2_256)
+		})
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	/// The encoded call, that has been submitted for the execution.
+	pub type EncodedCallOf<T> = BoundedVec<u8, <T as Config>::MaxCallLen>;
+
+	/// The queued calls and their senders.
+	pub type QueuedCallsOf<T> = BoundedVec<
+		(<T as frame_system::Config>::AccountId, EncodedCallOf<T>),
+		<T as Config>::MaxQueuedCalls,
+	>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The overarching call type.
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>;
+
+		/// The seed, that is used to shuffle the calls at the time of the execution.
+		type ShufflingSeed: Get<[u8; 32]>;
+
+		/// The maximal number of the calls, that may be queued for the execution in one block.
+		#[pallet::constant]
+		type MaxQueuedCalls: Get<u32>;
+
+		/// The maximal encoded length of a submitted call.
+		#[pallet::constant]
+		type MaxCallLen: Get<u32>;
+
+		/// The maximal weight, that is spent on executing the queued calls in one block.
+		///
+		/// Calls, whose weight exceeds it, can't be submitted.
+		#[pallet::constant]
+		type MaxExecutionWeight: Get<Weight>;
+
+		/// Weight information for extrinsics and hooks of this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The calls, that are executed in the block after the one with the given number, and their
+	/// senders.
+	#[pallet::storage]
+	pub(super) type Queue<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, QueuedCallsOf<T>, ValueQuery>;
+
+	/// The calls, that didn't fit into the `MaxExecutionWeight` of the previous block, and their
+	/// senders.
+	///
+	/// They are executed before the calls of the `Queue`, in the order, in which they have been
+	/// carried over.
+	#[pallet::storage]
+	pub(super) type CarriedOver<T: Config> = StorageValue<_, QueuedCallsOf<T>, ValueQuery>;
+
+	/// The number of the block, whose `Queue` is executed next.
+	///
+	/// The queues of the later blocks wait, until all carried over calls have been executed.
+	#[pallet::storage]
+	pub(super) type NextQueue<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The call has been submitted and is executed in the next block.
+		CallSubmitted { who: T::AccountId },
+		/// The submitted call has been executed.
+		CallExecuted { who: T::AccountId, result: DispatchResult },
+		/// The submitted call can't be decoded anymore, e.g. after a runtime upgrade, and has
+		/// been dropped.
+		CallUndecodable { who: T::AccountId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Too many calls have been queued for the execution in the next block.
+		QueueFull,
+		/// The encoded call is longer than `MaxCallLen`.
+		CallTooLong,
+		/// The weight of the call exceeds `MaxExecutionWeight`.
+		CallTooHeavy,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			Self::execute_queued_calls(n)
+		}
+
+		fn integrity_test() {
+			assert!(
+				T::MaxExecutionWeight::get().all_lte(T::BlockWeights::get().max_block / 2),
+				"`MaxExecutionWeight` must leave the space for the other extrinsics of the block",
+			);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Submit the `call` for the execution in the next block.
+		///
+		/// The call is dispatched with the signed origin of the sender. The sender pays for the
+		/// weight of the call at the submission.
+		#[pallet::call_index(0)]
+		#[pallet::weight({
+			let di = call.get_dispatch_info();
+			T::WeightInfo::submit().saturating_add(di.weight)
+		})]
+		pub fn submit(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				call.get_dispatch_info().weight.all_lte(T::MaxExecutionWeight::get()),
+				Error::<T>::CallTooHeavy
+			);
+			let encoded_call =
+				EncodedCallOf::<T>::try_from(call.encode()).map_err(|_| Error::<T>::CallTooLong)?;
+
+			let n = frame_system::Pallet::<T>::block_number();
+			Queue::<T>::try_mutate(n, |queue| queue.try_push((who.clone(), encoded_call)))
+				.map_err(|_| Error::<T>::QueueFull)?;
+
+			Self::deposit_event(Event::CallSubmitted { who });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Execute the carried over calls and then the calls of the queues, that haven't been
+		/// executed yet, in the shuffled order.
+		///
+		/// The calls, that don't fit into the `MaxExecutionWeight`, are carried over to the next
+		/// block. The queues of the later blocks are kept, until the carried over calls have been
+		/// executed.
+		pub(crate) fn execute_queued_calls(n: BlockNumberFor<T>) -> Weight {
+			let max_weight = T::MaxExecutionWeight::get();
+			let mut calls = CarriedOver::<T>::take().into_inner();
+			let mut weight = T::WeightInfo::on_initialize()
+				.saturating_add(T::WeightInfo::service_queue(calls.len() as u32));
+			let mut next_queue =
+				NextQueue::<T>::get().unwrap_or_else(|| n.saturating_sub(One::one()));
+			let mut executed_weight = Weight::zero();
+			loop {
+				calls = Self::execute_calls(calls, max_weight, &mut executed_weight);
+				if !calls.is_empty() || next_queue >= n {
+					break
+				}
+
+				calls = Self::take_queue(next_queue);
+				weight.saturating_accrue(T::WeightInfo::service_queue(calls.len() as u32));
+				next_queue.saturating_inc();
+			}
+
+			if !calls.is_empty() {
+				// The calls are either the remaining carried over calls of the previous block or
+				// the remaining calls of a single queue, so they don't exceed the capacity.
+				CarriedOver::<T>::put(QueuedCallsOf::<T>::defensive_truncate_from(calls));
+			}
+			NextQueue::<T>::put(next_queue);
+
+			weight.saturating_add(executed_weight)
+		}
+
+		/// Take the calls, that have been queued in the block `n`, from the storage and shuffle
+		/// them.
+		pub(crate) fn take_queue(n: BlockNumberFor<T>) -> Vec<(T::AccountId, EncodedCallOf<T>)> {
+			let mut calls = Queue::<T>::take(n).into_inner();
+			shuffle(&mut calls, &T::ShufflingSeed::get());
+			calls
+		}
+
+		/// Execute the `calls` in the given order, as long as they fit into the `max_weight`.
+		///
+		/// Returns the calls, that don't fit, in the same order.
+		fn execute_calls(
+			calls: Vec<(T::AccountId, EncodedCallOf<T>)>,
+			max_weight: Weight,
+			executed_weight: &mut Weight,
+		) -> Vec<(T::AccountId, EncodedCallOf<T>)> {
+			let mut remaining = Vec::new();
+			for (who, encoded_call) in calls {
+				let call = match <T as Config>::RuntimeCall::decode(&mut &encoded_call[..]) {
+					Ok(call) => call,
+					Err(_) => {
+						Self::deposit_event(Event::CallUndecodable { who });
+						continue
+					},
+				};
+
+				let info = call.get_dispatch_info();
+				if !executed_weight.saturating_add(info.weight).all_lte(max_weight) {
+					remaining.push((who, encoded_call));
+					continue
+				}
+
+				let result = call.dispatch(frame_system::RawOrigin::Signed(who.clone()).into());
+				executed_weight.saturating_accrue(extract_actual_weight(&result, &info));
+				Self::deposit_event(Event::CallExecuted {
+					who,
+					result: result.map(|_| ()).map_err(|e| e.error),
+				});
+			}
+			remaining
+		}
+	}
+}
@@ -0,0 +1,243 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use mock::*;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+fn remark(remark: u8) -> Box<RuntimeCall> {
+	Box::new(RuntimeCall::System(frame_system::Call::remark_with_event { remark: vec![remark] }))
+}
+
+/// Returns the remarks, that have been executed in the current block, in the execution order.
+fn executed_remarks(remarks: &[u8]) -> Vec<u8> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			RuntimeEvent::System(frame_system::Event::Remarked { hash, .. }) =>
+				remarks.iter().copied().find(|r| BlakeTwo256::hash(&[*r]) == hash),
+			_ => None,
+		})
+		.collect()
+}
+
+fn next_block() {
+	let n = System::block_number();
+	System::reset_events();
+	System::set_block_number(n + 1);
+	DelayedExecution::on_initialize(n + 1);
+}
+
+#[test]
+fn calls_are_executed_in_the_next_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(DelayedExecution::submit(RuntimeOrigin::signed(1), remark(1)));
+		System::assert_last_event(Event::CallSubmitted { who: 1 }.into());
+		// the call is not executed in the block, where it has been submitted
+		assert!(executed_remarks(&[1]).is_empty());
+
+		next_block();
+		assert_eq!(executed_remarks(&[1]), vec![1]);
+		System::assert_last_event(Event::CallExecuted { who: 1, result: Ok(()) }.into());
+		assert!(Queue::<Test>::get(1).is_empty());
+		assert!(Queue::<Test>::get(2).is_empty());
+	});
+}
+
+#[test]
+fn calls_are_executed_in_shuffled_order() {
+	new_test_ext().execute_with(|| {
+		let remarks = [1, 2, 3, 4];
+		for r in remarks {
+			assert_ok!(DelayedExecution::submit(RuntimeOrigin::signed(r.into()), remark(r)));
+		}
+		Seed::set([7; 32]);
+		next_block();
+
+		let mut expected = remarks;
+		shuffle(&mut expected, &[7; 32]);
+		assert_eq!(executed_remarks(&remarks), expected.to_vec());
+	});
+}
+
+#[test]
+fn weight_of_the_calls_is_paid_at_the_submission() {
+	new_test_ext().execute_with(|| {
+		let weight = remark(1).get_dispatch_info().weight;
+
+		let submit = RuntimeCall::DelayedExecution(Call::submit { call: remark(1) });
+		assert_eq!(submit.get_dispatch_info().weight, <() as WeightInfo>::submit() + weight);
+		assert_ok!(DelayedExecution::submit(RuntimeOrigin::signed(1), remark(1)));
+		assert_ok!(DelayedExecution::submit(RuntimeOrigin::signed(2), remark(2)));
+
+		// the executing block only accounts for the weight, that the calls have used
+		System::set_block_number(2);
+		let service_weight =
+			<() as WeightInfo>::on_initialize() + <() as WeightInfo>::service_queue(0);
+		assert_eq!(
+			DelayedExecution::on_initialize(2),
+			service_weight + <() as WeightInfo>::service_queue(2) + weight * 2,
+		);
+		System::set_block_number(3);
+		assert_eq!(
+			DelayedExecution::on_initialize(3),
+			service_weight + <() as WeightInfo>::service_queue(0),
+		);
+	});
+}
+
+#[test]
+fn calls_exceeding_the_execution_weight_are_carried_over() {
+	new_test_ext().execute_with(|| {
+		let weight = remark(1).get_dispatch_info().weight;
+		MaxExecutionWeight::set(weight * 2);
+		let remarks = [1, 2, 3];
+		for r in remarks {
+			assert_ok!(DelayedExecution::submit(RuntimeOrigin::signed(r.into()), remark(r)));
+		}
+
+		next_block();
+		let mut executed = executed_remarks(&remarks);
+		assert_eq!(executed.len(), 2);
+		assert_eq!(CarriedOver::<Test>::get().len(), 1);
+		assert!(Queue::<Test>::get(2).is_empty());
+
+		next_block();
+		executed.extend(executed_remarks(&remarks));
+		executed.sort();
+		assert_eq!(executed, remarks.to_vec());
+		assert!(CarriedOver::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn carried_over_calls_are_executed_first() {
+	new_test_ext().execute_with(|| {
+		let weight = remark(1).get_dispatch_info().weight;
+		MaxExecutionWeight::set(weight * 2);
+		let remarks = [1, 2, 3, 4, 5, 6, 7];
+		for r in &remarks[..3] {
+			assert_ok!(DelayedExecution::submit(RuntimeOrigin::signed(1), remark(*r)));
+		}
+
+		next_block();
+		let mut executed = executed_remarks(&remarks);
+		let carried_over: Vec<u8> =
+			remarks[..3].iter().copied().filter(|r| !executed.contains(r)).collect();
+		assert_eq!(carried_over.len(), 1);
+
+		// the carried over call doesn't limit the submissions of the block
+		for r in &remarks[3..] {
+			assert_ok!(DelayedExecution::submit(RuntimeOrigin::signed(1), remark(*r)));
+		}
+
+		next_block();
+		let executed_next = executed_remarks(&remarks);
+		assert_eq!(executed_next.len(), 2);
+		assert_eq!(executed_next[0], carried_over[0]);
+		executed.extend(executed_next);
+
+		while executed.len() < remarks.len() {
+			next_block();
+			executed.extend(executed_remarks(&remarks));
+		}
+		executed.sort();
+		assert_eq!(executed, remarks.to_vec());
+	});
+}
+
+#[test]
+fn calls_are_not_dropped_when_the_backlog_exceeds_the_queue_capacity() {
+	new_test_ext().execute_with(|| {
+		let weight = remark(1).get_dispatch_info().weight;
+		MaxExecutionWeight::set(weight);
+		let remarks = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+
+		// every block fills its queue, but only a single call is executed per block
+		let mut executed = Vec::new();
+		for queue in remarks.chunks(4) {
+			for r in queue {
+				assert_ok!(DelayedExecution::submit(RuntimeOrigin::signed(1), remark(*r)));
+			}
+			next_block();
+			executed.extend(executed_remarks(&remarks));
+		}
+		assert_eq!(executed.len(), 3);
+		// the queues of the blocks 2 and 3 wait for the carried over calls
+		assert_eq!(NextQueue::<Test>::get(), Some(2));
+		assert_eq!(Queue::<Test>::get(2).len(), 4);
+		assert_eq!(Queue::<Test>::get(3).len(), 4);
+
+		while executed.len() < remarks.len() {
+			next_block();
+			let executed_in_block = executed_remarks(&remarks);
+			assert_eq!(executed_in_block.len(), 1);
+			executed.extend(executed_in_block);
+		}
+		executed.sort();
+		assert_eq!(executed, remarks.to_vec());
+		assert!(CarriedOver::<Test>::get().is_empty());
+		assert_eq!(NextQueue::<Test>::get(), Some(System::block_number()));
+	});
+}
+
+#[test]
+fn submitting_fails_if_queue_is_full() {
+	new_test_ext().execute_with(|| {
+		for r in 0..4 {
+			assert_ok!(DelayedExecution::submit(RuntimeOrigin::signed(1), remark(r)));
+		}
+		assert_noop!(
+			DelayedExecution::submit(RuntimeOrigin::signed(1), remark(4)),
+			Error::<Test>::QueueFull
+		);
+
+		// the queue of the next block is empty
+		next_block();
+		assert_ok!(DelayedExecution::submit(RuntimeOrigin::signed(1), remark(4)));
+	});
+}
+
+#[test]
+fn submitting_fails_for_too_long_or_too_heavy_calls() {
+	new_test_ext().execute_with(|| {
+		let too_long = Box::new(RuntimeCall::System(frame_system::Call::remark_with_event {
+			remark: vec![0; 64],
+		}));
+		assert_noop!(
+			DelayedExecution::submit(RuntimeOrigin::signed(1), too_long),
+			Error::<Test>::CallTooLong
+		);
+
+		MaxExecutionWeight::set(Weight::zero());
+		assert_noop!(
+			DelayedExecution::submit(RuntimeOrigin::signed(1), remark(1)),
+			Error::<Test>::CallTooHeavy
+		);
+	});
+}
+
+#[test]
+fn committed_seed_is_used() {
+	new_test_ext().execute_with(|| {
+		let mut digest = sp_runtime::Digest::default();
+		digest.push(CumulusDigestItem::ShufflingSeed([3; 32]).to_digest_item());
+		System::initialize(&2, &Default::default(), &digest);
+
+		assert_eq!(DigestShufflingSeed::<Test>::get(), [3; 32]);
+	});
+}
@@ -0,0 +1,96 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for cumulus_pallet_delayed_execution.
+//!
+//! The weights are not generated by the benchmark CLI yet. They are rough estimations of the
+//! worst case of the `submit`, `on_initialize` and `service_queue` benchmarks. The execution of
+//! the queued calls is accounted for separately, using the weights of the calls.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for cumulus_pallet_delayed_execution.
+pub trait WeightInfo {
+	/// Weight of queueing a call, excluding the weight of the call itself.
+	fn submit() -> Weight;
+	/// Weight of reading and writing the execution state in `on_initialize`.
+	fn on_initialize() -> Weight;
+	/// Weight of taking a queue of `c` calls from the storage and shuffling it, excluding the
+	/// execution of the calls.
+	fn service_queue(c: u32) -> Weight;
+}
+
+/// Weights for cumulus_pallet_delayed_execution using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: DelayedExecution Queue (r:1 w:1)
+	fn submit() -> Weight {
+		Weight::from_parts(25_000_000, 140_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: DelayedExecution CarriedOver (r:1 w:1)
+	///
+	/// Storage: DelayedExecution NextQueue (r:1 w:1)
+	fn on_initialize() -> Weight {
+		Weight::from_parts(8_000_000, 1_500)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: DelayedExecution Queue (r:1 w:1)
+	///
+	/// The range of component `c` is `[0, 128]`.
+	fn service_queue(c: u32) -> Weight {
+		Weight::from_parts(6_000_000, 1_500)
+			.saturating_add(Weight::from_parts(1_500_000, 1_100).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: DelayedExecution Queue (r:1 w:1)
+	fn submit() -> Weight {
+		Weight::from_parts(25_000_000, 140_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: DelayedExecution CarriedOver (r:1 w:1)
+	///
+	/// Storage: DelayedExecution NextQueue (r:1 w:1)
+	fn on_initialize() -> Weight {
+		Weight::from_parts(8_000_000, 1_500)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: DelayedExecution Queue (r:1 w:1)
+	///
+	/// The range of component `c` is `[0, 128]`.
+	fn service_queue(c: u32) -> Weight {
+		Weight::from_parts(6_000_000, 1_500)
+			.saturating_add(Weight::from_parts(1_500_000, 1_100).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
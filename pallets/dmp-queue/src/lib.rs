@@ -18,21 +18,28 @@
 //! Executes downward messages if there is enough weight available and schedules the rest for later
 //! execution (by `on_idle` or another `handle_dmp_messages` call). Individual overweight messages
 //! are scheduled into a separate queue that is only serviced by explicit extrinsic calls.
+//!
+//! Alternatively, the [`EnqueueDmpMessages`] handler may be used to put all downward messages into
+//! the `pallet-message-queue`, which then services them. In that case the pallet only services
+//! messages that have been queued before switching to the new handler.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod migration;
 
 use codec::{Decode, DecodeLimit, Encode};
-use cumulus_primitives_core::{relay_chain::BlockNumber as RelayBlockNumber, DmpMessageHandler};
+use cumulus_primitives_core::{
+	relay_chain::BlockNumber as RelayBlockNumber, AggregateMessageOrigin, DmpMessageHandler,
+};
 use frame_support::{
-	traits::EnsureOrigin,
+	traits::{EnqueueMessage, EnsureOrigin, Get},
 	weights::{constants::WEIGHT_REF_TIME_PER_MILLIS, Weight},
+	BoundedSlice,
 };
 pub use pallet::*;
 use scale_info::TypeInfo;
 use sp_runtime::RuntimeDebug;
-use sp_std::{convert::TryFrom, prelude::*};
+use sp_std::{convert::TryFrom, marker::PhantomData, prelude::*};
 use xcm::{latest::prelude::*, VersionedXcm, MAX_XCM_DECODE_DEPTH};
 
 const DEFAULT_POV_SIZE: u64 = 64 * 1024; // 64 KB
@@ -42,6 +49,8 @@ const DEFAULT_POV_SIZE: u64 = 64 * 1024; // 64 KB
 const MAX_MESSAGES_PER_BLOCK: u8 = 10;
 // Maximum amount of messages that can exist in the overweight queue at any given time.
 const MAX_OVERWEIGHT_MESSAGES: u32 = 1000;
+// Maximum amount of instructions that are recorded in the overweight message summary.
+const MAX_SUMMARY_INSTRUCTIONS: usize = 8;
 
 #[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct ConfigData {
@@ -73,6 +82,17 @@ pub struct PageIndexData {
 	overweight_count: OverweightIndex,
 }
 
+/// Summary of the overweight message, that is stored along with the message itself.
+#[derive(Clone, Eq, PartialEq, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct OverweightSummary {
+	/// The weight that was required to execute the message when it was enqueued.
+	pub required_weight: Weight,
+	/// The number of instructions in the message.
+	pub instruction_count: u32,
+	/// The indices of the first few instructions of the message, in the latest XCM version.
+	pub instructions: Vec<u8>,
+}
+
 /// Simple type used to identify messages for the purpose of reporting events. Secure if and only
 /// if the message content is unique.
 pub type MessageId = XcmHash;
@@ -129,12 +149,19 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The summaries of the overweight messages.
+	#[pallet::storage]
+	pub(super) type OverweightSummaries<T> =
+		StorageMap<_, Blake2_128Concat, OverweightIndex, OverweightSummary, OptionQuery>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// The message index given is unknown.
 		Unknown,
 		/// The amount of weight given is possibly not enough for executing the message.
 		OverLimit,
+		/// The message still requires more weight than any individual message may consume.
+		StillOverweight,
 	}
 
 	#[pallet::hooks]
@@ -161,9 +188,37 @@ pub mod pallet {
 			let weight_used = Self::try_service_message(weight_limit, sent_at, &data[..])
 				.map_err(|_| Error::<T>::OverLimit)?;
 			Overweight::<T>::remove(index);
+			OverweightSummaries::<T>::remove(index);
 			Self::deposit_event(Event::OverweightServiced { overweight_index: index, weight_used });
 			Ok(Some(weight_used.saturating_add(Weight::from_parts(1_000_000, 0))).into())
 		}
+
+		/// Push a single overweight message back to the tail of the queue, so that it is serviced
+		/// normally.
+		///
+		/// This is meant to be used when the message weight has been changed (e.g. by the runtime
+		/// upgrade) and the message doesn't exceed the `max_individual` weight anymore.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 4))]
+		pub fn requeue_overweight(origin: OriginFor<T>, index: OverweightIndex) -> DispatchResult {
+			T::ExecuteOverweightOrigin::ensure_origin(origin)?;
+
+			let (sent_at, data) = Overweight::<T>::get(index).ok_or(Error::<T>::Unknown)?;
+			let required_weight = Self::message_weight(&data[..]).unwrap_or_default();
+			ensure!(
+				required_weight.all_lte(Configuration::<T>::get().max_individual),
+				Error::<T>::StillOverweight,
+			);
+
+			Overweight::<T>::remove(index);
+			OverweightSummaries::<T>::remove(index);
+			PageIndex::<T>::mutate(|page_index| {
+				Pages::<T>::insert(page_index.end_used, vec![(sent_at, data)]);
+				page_index.end_used += 1;
+			});
+			Self::deposit_event(Event::OverweightRequeued { overweight_index: index });
+			Ok(())
+		}
 	}
 
 	#[pallet::event]
@@ -191,8 +246,12 @@ pub mod pallet {
 		},
 		/// Downward message from the overweight queue was executed.
 		OverweightServiced { overweight_index: OverweightIndex, weight_used: Weight },
+		/// Downward message from the overweight queue was pushed back to the normal queue.
+		OverweightRequeued { overweight_index: OverweightIndex },
 		/// The maximum number of downward messages was reached.
 		MaxMessagesExhausted { message_hash: XcmHash },
+		/// Downward message is too large to be put into the message queue.
+		MessageTooLarge { message_hash: XcmHash },
 	}
 
 	/// Error type when a message was failed to be serviced.
@@ -253,6 +312,53 @@ pub mod pallet {
 			used
 		}
 
+		/// Decode the message into the latest XCM version.
+		fn decode_message(mut data: &[u8]) -> Option<Xcm<T::RuntimeCall>> {
+			VersionedXcm::<T::RuntimeCall>::decode_all_with_depth_limit(
+				MAX_XCM_DECODE_DEPTH,
+				&mut data,
+			)
+			.ok()
+			.and_then(|xcm| Xcm::<T::RuntimeCall>::try_from(xcm).ok())
+		}
+
+		/// Returns the weight, required to execute the message, or `None` if the message is
+		/// invalid.
+		pub(crate) fn message_weight(data: &[u8]) -> Option<Weight> {
+			let xcm = Self::decode_message(data)?;
+			T::XcmExecutor::prepare(xcm).ok().map(|prepared| prepared.weight_of())
+		}
+
+		/// Returns the summary of the overweight message with given index, or `None` if there's
+		/// no such message.
+		///
+		/// Summaries of messages, that have been put into the overweight queue before summaries
+		/// were stored, are computed from the message itself.
+		pub fn overweight_message_summary(index: OverweightIndex) -> Option<OverweightSummary> {
+			OverweightSummaries::<T>::get(index).or_else(|| {
+				let (_, data) = Overweight::<T>::get(index)?;
+				let required_weight = Self::message_weight(&data[..]).unwrap_or_default();
+				Some(Self::overweight_summary(&data[..], required_weight))
+			})
+		}
+
+		/// Returns the summary of the overweight message.
+		pub(crate) fn overweight_summary(
+			data: &[u8],
+			required_weight: Weight,
+		) -> OverweightSummary {
+			let instructions = Self::decode_message(data).map(|xcm| xcm.0).unwrap_or_default();
+			OverweightSummary {
+				required_weight,
+				instruction_count: instructions.len() as u32,
+				instructions: instructions
+					.iter()
+					.take(MAX_SUMMARY_INSTRUCTIONS)
+					.map(|instruction| instruction.using_encoded(|encoded| encoded[0]))
+					.collect(),
+			}
+		}
+
 		/// Attempt to service an individual message. Will return `Ok` with the execution weight
 		/// consumed unless the message was found to need more weight than `limit`.
 		///
@@ -359,6 +465,10 @@ pub mod pallet {
 									// overweight - add to overweight queue and continue with
 									// message execution.
 									let overweight_index = page_index.overweight_count;
+									OverweightSummaries::<T>::insert(
+										overweight_index,
+										Self::overweight_summary(&data[..], required_weight),
+									);
 									Overweight::<T>::insert(overweight_index, (sent_at, data));
 									Self::deposit_event(Event::OverweightEnqueued {
 										message_hash,
@@ -405,6 +515,43 @@ pub mod pallet {
 	}
 }
 
+/// Downward messages handler, that puts all messages into the `Queue` (which is supposed to be the
+/// `pallet-message-queue`) with the `Parent` origin.
+///
+/// The `Queue` is responsible for weighing and executing messages, so messages are never executed
+/// by the handler itself.
+pub struct EnqueueDmpMessages<T, Queue>(PhantomData<(T, Queue)>);
+
+impl<T: Config, Queue: EnqueueMessage<AggregateMessageOrigin>> DmpMessageHandler
+	for EnqueueDmpMessages<T, Queue>
+{
+	fn handle_dmp_messages(
+		iter: impl Iterator<Item = (RelayBlockNumber, Vec<u8>)>,
+		_limit: Weight,
+	) -> Weight {
+		let mut used = Weight::zero();
+		for (_sent_at, data) in iter {
+			used.saturating_accrue(T::DbWeight::get().reads_writes(3, 3));
+			match BoundedSlice::try_from(&data[..]) {
+				Ok(message) => Queue::enqueue_message(message, AggregateMessageOrigin::Parent),
+				Err(_) => Pallet::<T>::deposit_event(Event::MessageTooLarge {
+					message_hash: sp_io::hashing::blake2_256(&data),
+				}),
+			}
+		}
+		used
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// API for inspecting messages in the overweight queue of the DMP queue pallet.
+	pub trait DmpQueueApi {
+		/// Returns the summary of the overweight message with given index, or `None` if there's
+		/// no such message.
+		fn overweight_message_summary(index: OverweightIndex) -> Option<OverweightSummary>;
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -412,7 +559,10 @@ mod tests {
 
 	use codec::Encode;
 	use cumulus_primitives_core::ParaId;
-	use frame_support::{assert_noop, parameter_types, traits::OnIdle};
+	use frame_support::{
+		assert_noop, assert_ok, parameter_types,
+		traits::{ConstU32, Footprint, OnIdle},
+	};
 	use sp_core::H256;
 	use sp_runtime::{
 		traits::{BlakeTwo256, IdentityLookup},
@@ -537,6 +687,45 @@ mod tests {
 		type ExecuteOverweightOrigin = frame_system::EnsureRoot<AccountId>;
 	}
 
+	thread_local! {
+		pub static MESSAGE_QUEUE: RefCell<Vec<(AggregateMessageOrigin, Vec<u8>)>> =
+			RefCell::new(Vec::new());
+	}
+
+	pub struct MockMessageQueue;
+	impl EnqueueMessage<AggregateMessageOrigin> for MockMessageQueue {
+		type MaxMessageLen = ConstU32<64>;
+
+		fn enqueue_message(
+			message: BoundedSlice<u8, Self::MaxMessageLen>,
+			origin: AggregateMessageOrigin,
+		) {
+			MESSAGE_QUEUE.with(|q| q.borrow_mut().push((origin, message.to_vec())));
+		}
+
+		fn enqueue_messages<'a>(
+			messages: impl Iterator<Item = BoundedSlice<'a, u8, Self::MaxMessageLen>>,
+			origin: AggregateMessageOrigin,
+		) {
+			messages.for_each(|message| Self::enqueue_message(message, origin));
+		}
+
+		fn sweep_queue(origin: AggregateMessageOrigin) {
+			MESSAGE_QUEUE.with(|q| q.borrow_mut().retain(|(o, _)| *o != origin));
+		}
+
+		fn footprint(origin: AggregateMessageOrigin) -> Footprint {
+			MESSAGE_QUEUE.with(|q| {
+				let q = q.borrow();
+				let messages = q.iter().filter(|(o, _)| *o == origin);
+				Footprint {
+					count: messages.clone().count() as u64,
+					size: messages.map(|(_, message)| message.len() as u64).sum(),
+				}
+			})
+		}
+	}
+
 	pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
 		frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
 	}
@@ -861,6 +1050,170 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn overweight_summary_is_stored() {
+		new_test_ext().execute_with(|| {
+			Configuration::<Test>::put(ConfigData {
+				max_individual: Weight::from_parts(9999, 9999),
+			});
+
+			let incoming = vec![msg(10000)];
+			handle_messages(&incoming, Weight::from_parts(2500, 2500));
+			assert_eq!(overweights(), vec![0]);
+			assert_eq!(
+				OverweightSummaries::<Test>::get(0),
+				Some(OverweightSummary {
+					required_weight: Weight::from_parts(10000, 10000),
+					instruction_count: 1,
+					// the index of `Transact` instruction
+					instructions: vec![6],
+				}),
+			);
+
+			assert_ok!(DmpQueue::service_overweight(
+				RuntimeOrigin::root(),
+				0,
+				Weight::from_parts(20000, 20000)
+			));
+			assert_eq!(OverweightSummaries::<Test>::get(0), None);
+		});
+	}
+
+	#[test]
+	fn overweight_message_summary_works() {
+		new_test_ext().execute_with(|| {
+			Configuration::<Test>::put(ConfigData {
+				max_individual: Weight::from_parts(9999, 9999),
+			});
+
+			let incoming = vec![msg(10000), msg(20000)];
+			handle_messages(&incoming, Weight::from_parts(2500, 2500));
+			assert_eq!(overweights(), vec![0, 1]);
+			assert_eq!(DmpQueue::overweight_message_summary(2), None);
+
+			// stored summary is returned
+			let expected_summary = |required_weight| OverweightSummary {
+				required_weight,
+				instruction_count: 1,
+				instructions: vec![6],
+			};
+			assert_eq!(
+				DmpQueue::overweight_message_summary(0),
+				Some(expected_summary(Weight::from_parts(10000, 10000))),
+			);
+
+			// summary of the message, that has been enqueued before summaries were stored, is
+			// computed from the message
+			OverweightSummaries::<Test>::remove(1);
+			assert_eq!(
+				DmpQueue::overweight_message_summary(1),
+				Some(expected_summary(Weight::from_parts(20000, 20000))),
+			);
+		});
+	}
+
+	#[test]
+	fn overweight_summary_is_truncated() {
+		new_test_ext().execute_with(|| {
+			let message = Xcm(vec![ClearOrigin; MAX_SUMMARY_INSTRUCTIONS + 2]);
+			let data = VersionedXcm::<RuntimeCall>::from(message).encode();
+			assert_eq!(
+				DmpQueue::overweight_summary(&data, Weight::from_parts(1, 1)),
+				OverweightSummary {
+					required_weight: Weight::from_parts(1, 1),
+					instruction_count: MAX_SUMMARY_INSTRUCTIONS as u32 + 2,
+					// the index of `ClearOrigin` instruction
+					instructions: vec![10; MAX_SUMMARY_INSTRUCTIONS],
+				},
+			);
+		});
+	}
+
+	#[test]
+	fn overweights_should_be_requeued() {
+		new_test_ext().execute_with(|| {
+			Configuration::<Test>::put(ConfigData {
+				max_individual: Weight::from_parts(9999, 9999),
+			});
+
+			let incoming = vec![msg(10000)];
+			handle_messages(&incoming, Weight::from_parts(2500, 2500));
+			assert_eq!(overweights(), vec![0]);
+
+			assert_noop!(DmpQueue::requeue_overweight(RuntimeOrigin::signed(1), 0), BadOrigin);
+			assert_noop!(
+				DmpQueue::requeue_overweight(RuntimeOrigin::root(), 1),
+				Error::<Test>::Unknown
+			);
+			assert_noop!(
+				DmpQueue::requeue_overweight(RuntimeOrigin::root(), 0),
+				Error::<Test>::StillOverweight
+			);
+
+			// the message is not overweight anymore
+			Configuration::<Test>::put(ConfigData {
+				max_individual: Weight::from_parts(10000, 10000),
+			});
+			assert_ok!(DmpQueue::requeue_overweight(RuntimeOrigin::root(), 0));
+			assert!(overweights().is_empty());
+			assert_eq!(OverweightSummaries::<Test>::get(0), None);
+			assert_eq!(pages_queued(), 1);
+			assert_eq!(take_trace(), vec![]);
+
+			let weight_used = handle_messages(&[], Weight::from_parts(20000, 20000));
+			assert_eq!(weight_used, Weight::from_parts(10000, 10000));
+			assert_eq!(take_trace(), vec![msg_complete(10000)]);
+			assert!(queue_is_empty());
+		});
+	}
+
+	#[test]
+	fn messages_are_put_into_message_queue() {
+		new_test_ext().execute_with(|| {
+			frame_system::Pallet::<Test>::set_block_number(1);
+
+			let large_message = Xcm(vec![Transact {
+				origin_kind: OriginKind::Native,
+				require_weight_at_most: Weight::from_parts(1000, 1000),
+				call: vec![0u8; 64].into(),
+			}]);
+			let incoming = vec![msg(1000), large_message.clone(), msg(1001)];
+			let weight_used = EnqueueDmpMessages::<Test, MockMessageQueue>::handle_dmp_messages(
+				incoming
+					.iter()
+					.map(|m| (0, VersionedXcm::<RuntimeCall>::from(m.clone()).encode())),
+				Weight::from_parts(2500, 2500),
+			);
+
+			// messages are not executed, but queued
+			assert_eq!(
+				weight_used,
+				<Test as frame_system::Config>::DbWeight::get().reads_writes(9, 9)
+			);
+			assert!(take_trace().is_empty());
+			assert!(queue_is_empty());
+			assert_eq!(
+				MESSAGE_QUEUE.with(|q| q.borrow().clone()),
+				vec![
+					(
+						AggregateMessageOrigin::Parent,
+						VersionedXcm::<RuntimeCall>::from(msg(1000)).encode()
+					),
+					(
+						AggregateMessageOrigin::Parent,
+						VersionedXcm::<RuntimeCall>::from(msg(1001)).encode()
+					),
+				],
+			);
+			let message_hash = sp_io::hashing::blake2_256(
+				&VersionedXcm::<RuntimeCall>::from(large_message).encode(),
+			);
+			frame_system::Pallet::<Test>::assert_last_event(
+				Event::<Test>::MessageTooLarge { message_hash }.into(),
+			);
+		});
+	}
+
 	#[test]
 	fn on_idle_should_service_queue() {
 		new_test_ext().execute_with(|| {
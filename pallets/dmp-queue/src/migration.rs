@@ -16,12 +16,15 @@
 
 //! A module that is responsible for migration of storage.
 
-use crate::{Config, Configuration, Overweight, Pallet, DEFAULT_POV_SIZE};
+use crate::{Config, Configuration, Event, Overweight, PageIndex, Pages, Pallet, DEFAULT_POV_SIZE};
+use cumulus_primitives_core::AggregateMessageOrigin;
 use frame_support::{
 	pallet_prelude::*,
-	traits::{OnRuntimeUpgrade, StorageVersion},
+	traits::{EnqueueMessage, OnRuntimeUpgrade, StorageVersion},
 	weights::{constants::WEIGHT_REF_TIME_PER_MILLIS, Weight},
+	BoundedSlice,
 };
+use sp_std::convert::TryFrom;
 
 /// The current storage version.
 pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
@@ -49,6 +52,54 @@ impl<T: Config> OnRuntimeUpgrade for Migration<T> {
 	}
 }
 
+/// Moves the downward messages, that are still waiting in the pallet queue, to the message
+/// `Queue` (which is supposed to be the `pallet-message-queue`).
+///
+/// It must be used along with switching the `DmpMessageHandler` to the
+/// [`crate::EnqueueDmpMessages`], so that the queued messages are not serviced after the newer
+/// ones. Overweight messages are kept in the pallet and may still be serviced with the
+/// `service_overweight` call. The migration does nothing once the pallet queue is empty.
+pub struct MigrateToMessageQueue<T, Queue>(PhantomData<(T, Queue)>);
+
+impl<T: Config, Queue: EnqueueMessage<AggregateMessageOrigin>> OnRuntimeUpgrade
+	for MigrateToMessageQueue<T, Queue>
+{
+	fn on_runtime_upgrade() -> Weight {
+		let mut page_index = PageIndex::<T>::get();
+		let mut weight = T::DbWeight::get().reads(1);
+		let mut migrated = 0u32;
+
+		for page in page_index.begin_used..page_index.end_used {
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			for (_sent_at, data) in Pages::<T>::take(page) {
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(3, 3));
+				match BoundedSlice::try_from(&data[..]) {
+					Ok(message) => {
+						Queue::enqueue_message(message, AggregateMessageOrigin::Parent);
+						migrated += 1;
+					},
+					Err(_) => Pallet::<T>::deposit_event(Event::MessageTooLarge {
+						message_hash: sp_io::hashing::blake2_256(&data),
+					}),
+				}
+			}
+		}
+
+		if page_index.begin_used != page_index.end_used {
+			page_index.begin_used = page_index.end_used;
+			PageIndex::<T>::put(page_index);
+			weight.saturating_accrue(T::DbWeight::get().writes(1));
+		}
+
+		log::info!(
+			target: "dmp_queue",
+			"Moved {} downward messages to the message queue",
+			migrated,
+		);
+		weight
+	}
+}
+
 mod v0 {
 	use super::*;
 	use codec::{Decode, Encode};
@@ -100,7 +151,8 @@ pub fn migrate_to_v2<T: Config>() -> Weight {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::tests::{new_test_ext, Test};
+	use crate::tests::{new_test_ext, MockMessageQueue, RuntimeCall, Test, MESSAGE_QUEUE};
+	use xcm::{latest::prelude::*, VersionedXcm};
 
 	#[test]
 	fn test_migration_to_v1() {
@@ -120,4 +172,36 @@ mod tests {
 			assert_eq!(v1.max_individual.proof_size(), DEFAULT_POV_SIZE);
 		});
 	}
+
+	#[test]
+	fn queued_messages_are_moved_to_message_queue() {
+		let message = |n: u64| {
+			VersionedXcm::<RuntimeCall>::from(Xcm::<RuntimeCall>(vec![ClearOrigin; n as usize]))
+				.encode()
+		};
+
+		new_test_ext().execute_with(|| {
+			Pages::<Test>::insert(0, vec![(0, message(1)), (0, message(2))]);
+			Pages::<Test>::insert(1, vec![(1, message(3))]);
+			PageIndex::<Test>::mutate(|index| index.end_used = 2);
+
+			MigrateToMessageQueue::<Test, MockMessageQueue>::on_runtime_upgrade();
+
+			assert_eq!(
+				MESSAGE_QUEUE.with(|q| q.borrow().clone()),
+				vec![
+					(AggregateMessageOrigin::Parent, message(1)),
+					(AggregateMessageOrigin::Parent, message(2)),
+					(AggregateMessageOrigin::Parent, message(3)),
+				],
+			);
+			assert_eq!(Pages::<Test>::iter().count(), 0);
+			let index = PageIndex::<Test>::get();
+			assert_eq!((index.begin_used, index.end_used), (2, 2));
+
+			// the second run does nothing
+			MigrateToMessageQueue::<Test, MockMessageQueue>::on_runtime_upgrade();
+			assert_eq!(MESSAGE_QUEUE.with(|q| q.borrow().len()), 3);
+		});
+	}
 }
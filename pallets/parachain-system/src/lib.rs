@@ -29,9 +29,10 @@
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use cumulus_primitives_core::{
-	relay_chain, AbridgedHostConfiguration, ChannelStatus, CollationInfo, DmpMessageHandler,
-	GetChannelInfo, InboundDownwardMessage, InboundHrmpMessage, MessageSendError,
-	OutboundHrmpMessage, ParaId, PersistedValidationData, UpwardMessage, UpwardMessageSender,
+	message_footprint, relay_chain, AbridgedHostConfiguration, ChannelStatus, CollationInfo,
+	CollationResource, DmpMessageHandler, GetChannelInfo, InboundDownwardMessage,
+	InboundHrmpMessage, MessageSendError, OutboundHrmpMessage, ParaId, PersistedValidationData,
+	RelayChainGenesisHashStore, ResourceFootprint, UpwardMessage, UpwardMessageSender,
 	XcmpMessageHandler, XcmpMessageSource,
 };
 use cumulus_primitives_parachain_inherent::{MessageQueueChain, ParachainInherentData};
@@ -40,8 +41,9 @@ use frame_support::{
 	ensure,
 	inherent::{InherentData, InherentIdentifier, ProvideInherent},
 	storage,
-	traits::Get,
+	traits::{Contains, Get, UnixTime},
 	weights::Weight,
+	Parameter,
 };
 use frame_system::{ensure_none, ensure_root, pallet_prelude::HeaderFor};
 use polkadot_parachain::primitives::RelayChainBlockNumber;
@@ -52,10 +54,13 @@ use sp_runtime::{
 		InvalidTransaction, TransactionLongevity, TransactionSource, TransactionValidity,
 		ValidTransaction,
 	},
-	RuntimeDebug,
+	RuntimeDebug, SaturatedConversion,
 };
 use sp_std::{cmp, collections::btree_map::BTreeMap, prelude::*};
-use xcm::latest::XcmHash;
+use xcm::{
+	latest::{Instruction, MultiLocation, Xcm, XcmHash},
+	VersionedXcm, MAX_XCM_DECODE_DEPTH,
+};
 
 pub mod migration;
 
@@ -97,7 +102,9 @@ pub use consensus_hook::{ConsensusHook, ExpectParentIncluded};
 /// # fn main() {}
 /// ```
 pub use cumulus_pallet_parachain_system_proc_macro::register_validate_block;
-pub use relay_state_snapshot::{MessagingStateSnapshot, RelayChainStateProof};
+pub use relay_state_snapshot::{
+	MessagingStateSnapshot, RelayAccountBalances, RelayChainStateProof,
+};
 
 pub use pallet::*;
 
@@ -163,6 +170,56 @@ impl CheckAssociatedRelayNumber for RelayNumberMonotonicallyIncreases {
 	}
 }
 
+/// Something that can check the timestamp of the block against the relay parent slot.
+///
+/// By default `()`, which doesn't check anything, and [`TimestampMatchesRelaySlot`] are provided.
+pub trait CheckRelayTimestamp {
+	/// Check the timestamp of the current block against the slot of its relay parent, which is
+	/// `None` if the relay chain state proof does not contain the slot.
+	///
+	/// The implementation should panic when there is something wrong.
+	fn check_relay_timestamp(relay_parent_slot: Option<relay_chain::Slot>);
+}
+
+impl CheckRelayTimestamp for () {
+	fn check_relay_timestamp(_: Option<relay_chain::Slot>) {}
+}
+
+/// Provides an implementation of [`CheckRelayTimestamp`].
+///
+/// It will ensure that the timestamp, as returned by `Time`, is at most `Tolerance` milliseconds
+/// away from `relay_parent_slot * RelaySlotDuration + Offset`. This is the timestamp that is
+/// provided by `cumulus_primitives_timestamp::InherentDataProvider` with the same offset, so the
+/// tolerance must be at least the drift correction bound that the collators use.
+pub struct TimestampMatchesRelaySlot<Time, RelaySlotDuration, Offset, Tolerance>(
+	sp_std::marker::PhantomData<(Time, RelaySlotDuration, Offset, Tolerance)>,
+);
+
+impl<Time, RelaySlotDuration, Offset, Tolerance> CheckRelayTimestamp
+	for TimestampMatchesRelaySlot<Time, RelaySlotDuration, Offset, Tolerance>
+where
+	Time: UnixTime,
+	RelaySlotDuration: Get<u64>,
+	Offset: Get<u64>,
+	Tolerance: Get<u64>,
+{
+	fn check_relay_timestamp(relay_parent_slot: Option<relay_chain::Slot>) {
+		let relay_parent_slot =
+			relay_parent_slot.expect("Relay chain state proof must contain the relay chain slot!");
+		let expected = u64::from(relay_parent_slot)
+			.saturating_mul(RelaySlotDuration::get())
+			.saturating_add(Offset::get());
+		let timestamp: u64 = Time::now().as_millis().saturated_into();
+
+		if timestamp.abs_diff(expected) > Tolerance::get() {
+			panic!(
+				"Timestamp {} is too far away from {} derived from the relay parent slot!",
+				timestamp, expected,
+			)
+		}
+	}
+}
+
 /// Information needed when a new runtime binary is submitted and needs to be authorized before
 /// replacing the current runtime.
 #[derive(Decode, Encode, Default, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
@@ -175,6 +232,8 @@ where
 	code_hash: T::Hash,
 	/// Whether or not to carry out version checks.
 	check_version: bool,
+	/// The relay chain block number, starting from which the upgrade can be enacted.
+	enactable_at: Option<RelayChainBlockNumber>,
 }
 
 #[frame_support::pallet]
@@ -202,10 +261,19 @@ pub mod pallet {
 		/// The place where outbound XCMP messages come from. This is queried in `finalize_block`.
 		type OutboundXcmpMessageSource: XcmpMessageSource;
 
+		/// Something that decides, which upward messages are sent to the relay chain ahead of the
+		/// other pending upward messages.
+		///
+		/// Use `()` to send all upward messages in the order they have been sent.
+		type PriorityUpwardMessageSource: PriorityUpwardMessageSource;
+
 		/// The message handler that will be invoked when messages are received via DMP.
 		type DmpMessageHandler: DmpMessageHandler;
 
 		/// The weight we reserve at the beginning of the block for processing DMP messages.
+		///
+		/// This is the default, that can be overridden by the `set_message_processing_budget`
+		/// call.
 		type ReservedDmpWeight: Get<Weight>;
 
 		/// The message handler that will be invoked when messages are received via XCMP.
@@ -216,11 +284,40 @@ pub mod pallet {
 		type XcmpMessageHandler: XcmpMessageHandler;
 
 		/// The weight we reserve at the beginning of the block for processing XCMP messages.
+		///
+		/// This is the default, that can be overridden by the `set_message_processing_budget`
+		/// call.
 		type ReservedXcmpWeight: Get<Weight>;
 
+		/// The proof size of the block, above which no more inbound messages are processed.
+		///
+		/// The proof size consumed by the `set_validation_data` inherent and by processing of the
+		/// inbound messages is tracked and the remaining budget is available to other pallets
+		/// via [`Pallet::remaining_pov_budget`].
+		type PovSizeWatermark: Get<u64>;
+
 		/// Something that can check the associated relay parent block number.
 		type CheckAssociatedRelayNumber: CheckAssociatedRelayNumber;
 
+		/// Something that can check the timestamp of the block against the relay parent slot.
+		///
+		/// The check runs in `on_finalize`, after the timestamp inherent was applied. Use `()` to
+		/// not check the timestamp.
+		type CheckRelayTimestamp: CheckRelayTimestamp;
+
+		/// Something that verifies the proof of the block authorship, that the collator may put
+		/// into the header digest.
+		///
+		/// Blocks with an invalid proof are rejected in `on_finalize`. The verified author is
+		/// available via [`Pallet::block_author`]. Use `()` to not verify the proofs.
+		type AuthorshipProofVerifier: VerifyAuthorshipProof;
+
+		/// Something that stores the genesis hash of the relay chain.
+		///
+		/// As long as no genesis hash is stored, it is read from the relay chain state proof and
+		/// stored. Use `()` to not store the genesis hash.
+		type RelayChainGenesisHashStore: RelayChainGenesisHashStore;
+
 		/// An entry-point for higher-level logic to manage the backlog of unincluded parachain
 		/// blocks and authorship rights for those blocks.
 		///
@@ -250,6 +347,13 @@ pub mod pallet {
 				"set_validation_data inherent needs to be present in every block!"
 			);
 
+			let digest = frame_system::Pallet::<T>::digest();
+			if cumulus_primitives_core::extract_authorship_proof(&digest).is_some() {
+				assert!(Self::block_author().is_some(), "Invalid block authorship proof!");
+			}
+
+			T::CheckRelayTimestamp::check_relay_timestamp(<RelayParentSlot<T>>::take());
+
 			let host_config = match Self::host_configuration() {
 				Some(ok) => ok,
 				None => {
@@ -296,6 +400,12 @@ pub mod pallet {
 					},
 				};
 
+				// Priority messages are sent ahead of the regular ones.
+				let mut pending = <PendingPriorityUpwardMessages<T>>::take();
+				let num_priority = pending.len();
+				pending.append(up);
+				*up = pending;
+
 				let available_capacity =
 					cmp::min(available_capacity, host_config.max_upward_message_num_per_candidate);
 
@@ -326,6 +436,14 @@ pub mod pallet {
 				UpwardMessages::<T>::put(&up[..num as usize]);
 				*up = up.split_off(num as usize);
 
+				// Priority messages that didn't fit stay ahead of the regular ones.
+				let num_priority_left = num_priority.saturating_sub(num as usize);
+				if num_priority_left > 0 {
+					<PendingPriorityUpwardMessages<T>>::put(
+						up.drain(..num_priority_left).collect::<Vec<_>>(),
+					);
+				}
+
 				(num, total_size)
 			});
 
@@ -427,8 +545,9 @@ pub mod pallet {
 			UpwardMessages::<T>::kill();
 			HrmpOutboundMessages::<T>::kill();
 			CustomValidationHeadData::<T>::kill();
+			PovSizeUsed::<T>::kill();
 
-			weight += T::DbWeight::get().writes(6);
+			weight += T::DbWeight::get().writes(7);
 
 			// Here, in `on_initialize` we must report the weight for both `on_initialize` and
 			// `on_finalize`.
@@ -456,8 +575,8 @@ pub mod pallet {
 
 			// NOTE that the actual weight consumed by `on_finalize` may turn out lower.
 			weight += T::DbWeight::get().reads_writes(
-				3 + hrmp_max_message_num_per_candidate as u64,
 				4 + hrmp_max_message_num_per_candidate as u64,
+				5 + hrmp_max_message_num_per_candidate as u64,
 			);
 
 			// Weight for adjusting the unincluded segment in `on_finalize`.
@@ -466,6 +585,10 @@ pub mod pallet {
 			// Always try to read `UpgradeGoAhead` in `on_finalize`.
 			weight += T::DbWeight::get().reads(1);
 
+			// Verify the block authorship proof in `on_finalize`.
+			weight += T::DbWeight::get().reads(1);
+			weight += T::AuthorshipProofVerifier::verification_weight();
+
 			weight
 		}
 	}
@@ -496,6 +619,8 @@ pub mod pallet {
 
 			// TODO: This is more than zero, but will need benchmarking to figure out what.
 			let mut total_weight = Weight::zero();
+			// The inherent itself is a part of the PoV.
+			let inherent_size = data.encoded_size() as u64;
 
 			// NOTE: the inherent data is expected to be unique, even if this block is built
 			// in the context of the same relay parent as the previous one. In particular,
@@ -524,12 +649,17 @@ pub mod pallet {
 			)
 			.expect("Invalid relay chain state proof");
 
+			<RelayParentSlot<T>>::set(relay_state_proof.read_slot().ok());
+			total_weight += T::DbWeight::get().writes(1);
+
 			// Update the desired maximum capacity according to the consensus hook.
 			#[cfg(feature = "parameterized-consensus-hook")]
 			let (consensus_hook_weight, capacity) = T::ConsensusHook::on_state_proof(&relay_state_proof);
 			#[cfg(not(feature = "parameterized-consensus-hook"))]
 			let (consensus_hook_weight, capacity) = ExpectParentIncluded::on_state_proof(&relay_state_proof);
 			total_weight += consensus_hook_weight;
+			UnincludedSegmentCapacityLimit::<T>::put(capacity.get());
+			total_weight += T::DbWeight::get().writes(1);
 			total_weight += Self::maybe_drop_included_ancestors(&relay_state_proof, capacity);
 			// Deposit a log indicating the relay-parent storage root.
 			// TODO: remove this in favor of the relay-parent's hash after
@@ -601,6 +731,9 @@ pub mod pallet {
 
 			<T::OnSystemEvent as OnSystemEvent>::on_validation_data(&vfp);
 
+			total_weight += Self::maybe_store_relay_chain_genesis_hash(&relay_state_proof);
+
+			Self::note_pov_size_used(inherent_size.saturating_add(total_weight.proof_size()));
 			total_weight += Self::process_inbound_downward_messages(
 				relevant_messaging_state.dmq_mqc_head,
 				downward_messages,
@@ -641,12 +774,44 @@ pub mod pallet {
 			check_version: bool,
 		) -> DispatchResult {
 			ensure_root(origin)?;
-			AuthorizedUpgrade::<T>::put(CodeUpgradeAuthorization { code_hash, check_version });
+			AuthorizedUpgrade::<T>::put(CodeUpgradeAuthorization {
+				code_hash,
+				check_version,
+				enactable_at: None,
+			});
 
 			Self::deposit_event(Event::UpgradeAuthorized { code_hash });
 			Ok(())
 		}
 
+		/// Authorize an upgrade to a given `code_hash` for the runtime, that can only be enacted
+		/// once the relay chain reaches the given `relay_block_number`. The runtime can be
+		/// supplied later.
+		///
+		/// The relay chain block number is checked against the relay parent of the block, that
+		/// includes the `enact_authorized_upgrade` call. See [`Pallet::authorize_upgrade`] for the
+		/// meaning of the `check_version` parameter.
+		///
+		/// This call requires Root origin.
+		#[pallet::call_index(4)]
+		#[pallet::weight((1_000_000, DispatchClass::Operational))]
+		pub fn authorize_upgrade_at(
+			origin: OriginFor<T>,
+			relay_block_number: RelayChainBlockNumber,
+			code_hash: T::Hash,
+			check_version: bool,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			AuthorizedUpgrade::<T>::put(CodeUpgradeAuthorization {
+				code_hash,
+				check_version,
+				enactable_at: Some(relay_block_number),
+			});
+
+			Self::deposit_event(Event::UpgradeAuthorizedAt { code_hash, relay_block_number });
+			Ok(())
+		}
+
 		/// Provide the preimage (runtime binary) `code` for an upgrade that has been authorized.
 		///
 		/// If the authorization required a version check, this call will ensure the spec name
@@ -667,6 +832,30 @@ pub mod pallet {
 			AuthorizedUpgrade::<T>::kill();
 			Ok(Pays::No.into())
 		}
+
+		/// Set the weight, that is reserved at the beginning of the block for processing the
+		/// inbound DMP and XCMP messages.
+		///
+		/// `None` resets the corresponding weight to the default, configured in the runtime.
+		///
+		/// This call requires Root origin.
+		#[pallet::call_index(5)]
+		#[pallet::weight((T::DbWeight::get().writes(2), DispatchClass::Operational))]
+		pub fn set_message_processing_budget(
+			origin: OriginFor<T>,
+			dmp_weight: Option<Weight>,
+			xcmp_weight: Option<Weight>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ReservedDmpWeightOverride::<T>::set(dmp_weight);
+			ReservedXcmpWeightOverride::<T>::set(xcmp_weight);
+
+			Self::deposit_event(Event::MessageProcessingBudgetSet {
+				dmp_weight: Self::reserved_dmp_weight(),
+				xcmp_weight: Self::reserved_xcmp_weight(),
+			});
+			Ok(())
+		}
 	}
 
 	#[pallet::event]
@@ -680,12 +869,17 @@ pub mod pallet {
 		ValidationFunctionDiscarded,
 		/// An upgrade has been authorized.
 		UpgradeAuthorized { code_hash: T::Hash },
+		/// An upgrade has been authorized, that can be enacted starting from the given relay
+		/// chain block.
+		UpgradeAuthorizedAt { code_hash: T::Hash, relay_block_number: RelayChainBlockNumber },
 		/// Some downward messages have been received and will be processed.
 		DownwardMessagesReceived { count: u32 },
 		/// Downward messages were processed using the given weight.
 		DownwardMessagesProcessed { weight_used: Weight, dmq_head: relay_chain::Hash },
 		/// An upward message was sent to the relay chain.
 		UpwardMessageSent { message_hash: Option<XcmHash> },
+		/// The weight reserved for processing the inbound messages has been changed.
+		MessageProcessingBudgetSet { dmp_weight: Weight, xcmp_weight: Weight },
 	}
 
 	#[pallet::error]
@@ -707,6 +901,8 @@ pub mod pallet {
 		NothingAuthorized,
 		/// The given code upgrade has not been authorized.
 		Unauthorized,
+		/// The authorized code upgrade can't be enacted at the current relay chain block yet.
+		UpgradeNotEnactableYet,
 	}
 
 	/// Latest included block descendants the runtime accepted. In other words, these are
@@ -726,6 +922,13 @@ pub mod pallet {
 	pub(super) type AggregatedUnincludedSegment<T: Config> =
 		StorageValue<_, SegmentTracker<T::Hash>, OptionQuery>;
 
+	/// The maximum capacity of the unincluded segment, returned by the [`ConsensusHook`] in the
+	/// latest block.
+	///
+	/// This is the number of blocks, including the current one, that may be unincluded at once.
+	#[pallet::storage]
+	pub(super) type UnincludedSegmentCapacityLimit<T: Config> = StorageValue<_, u32, ValueQuery>;
+
 	/// In case of a scheduled upgrade, this storage field contains the validation code to be
 	/// applied.
 	///
@@ -771,6 +974,13 @@ pub mod pallet {
 	pub(super) type UpgradeRestrictionSignal<T: Config> =
 		StorageValue<_, Option<relay_chain::UpgradeRestriction>, ValueQuery>;
 
+	/// The slot of the relay parent, if the relay chain state proof contains it.
+	///
+	/// This value is ephemeral which means it doesn't hit the storage. This value is set after the
+	/// inherent.
+	#[pallet::storage]
+	pub(super) type RelayParentSlot<T: Config> = StorageValue<_, relay_chain::Slot, OptionQuery>;
+
 	/// Optional upgrade go-ahead signal from the relay-chain.
 	///
 	/// This storage item is a mirror of the corresponding value for the current parachain from the
@@ -857,21 +1067,39 @@ pub mod pallet {
 	pub(super) type PendingUpwardMessages<T: Config> =
 		StorageValue<_, Vec<UpwardMessage>, ValueQuery>;
 
+	/// Upward messages that are still pending and are sent to the relay chain ahead of the
+	/// [`PendingUpwardMessages`].
+	///
+	/// See [`Config::PriorityUpwardMessageSource`].
+	#[pallet::storage]
+	pub(super) type PendingPriorityUpwardMessages<T: Config> =
+		StorageValue<_, Vec<UpwardMessage>, ValueQuery>;
+
 	/// The number of HRMP messages we observed in `on_initialize` and thus used that number for
 	/// announcing the weight of `on_initialize` and `on_finalize`.
 	#[pallet::storage]
 	pub(super) type AnnouncedHrmpMessagesPerCandidate<T: Config> = StorageValue<_, u32, ValueQuery>;
 
 	/// The weight we reserve at the beginning of the block for processing XCMP messages. This
-	/// overrides the amount set in the Config trait.
+	/// overrides the amount set in the Config trait and is set by the
+	/// `set_message_processing_budget` call.
 	#[pallet::storage]
 	pub(super) type ReservedXcmpWeightOverride<T: Config> = StorageValue<_, Weight>;
 
 	/// The weight we reserve at the beginning of the block for processing DMP messages. This
-	/// overrides the amount set in the Config trait.
+	/// overrides the amount set in the Config trait and is set by the
+	/// `set_message_processing_budget` call.
 	#[pallet::storage]
 	pub(super) type ReservedDmpWeightOverride<T: Config> = StorageValue<_, Weight>;
 
+	/// The proof size that has been consumed in the current block by the `set_validation_data`
+	/// inherent, by processing of the inbound messages and by other pallets (see
+	/// [`Pallet::note_pov_size_used`]).
+	///
+	/// This is killed at the beginning of every block.
+	#[pallet::storage]
+	pub(super) type PovSizeUsed<T: Config> = StorageValue<_, u64, ValueQuery>;
+
 	/// The next authorized upgrade, if there is one.
 	#[pallet::storage]
 	pub(super) type AuthorizedUpgrade<T: Config> = StorageValue<_, CodeUpgradeAuthorization<T>>;
@@ -952,6 +1180,14 @@ impl<T: Config> Pallet<T> {
 		let actual_hash = T::Hashing::hash(code);
 		ensure!(actual_hash == authorization.code_hash, Error::<T>::Unauthorized);
 
+		// ensure that the relay chain has reached the authorized block
+		if let Some(enactable_at) = authorization.enactable_at {
+			ensure!(
+				LastRelayChainBlockNumber::<T>::get() >= enactable_at,
+				Error::<T>::UpgradeNotEnactableYet
+			);
+		}
+
 		// check versions if required as part of the authorization
 		if authorization.check_version {
 			frame_system::Pallet::<T>::can_set_code(code)?;
@@ -960,6 +1196,40 @@ impl<T: Config> Pallet<T> {
 		Ok(actual_hash)
 	}
 
+	/// Returns the hash of the code of the authorized upgrade and the relay chain block number,
+	/// starting from which it can be enacted, if any.
+	///
+	/// This is intended to be used by tooling to display the pending upgrade.
+	pub fn authorized_upgrade() -> Option<(T::Hash, Option<RelayChainBlockNumber>)> {
+		AuthorizedUpgrade::<T>::get()
+			.map(|authorization| (authorization.code_hash, authorization.enactable_at))
+	}
+
+	/// The weight, that is reserved at the beginning of the block for processing DMP messages.
+	pub fn reserved_dmp_weight() -> Weight {
+		ReservedDmpWeightOverride::<T>::get().unwrap_or_else(T::ReservedDmpWeight::get)
+	}
+
+	/// The weight, that is reserved at the beginning of the block for processing XCMP messages.
+	pub fn reserved_xcmp_weight() -> Weight {
+		ReservedXcmpWeightOverride::<T>::get().unwrap_or_else(T::ReservedXcmpWeight::get)
+	}
+
+	/// Returns the reader of the relay chain state proof, that has been provided by the
+	/// `set_validation_data` inherent of the latest block.
+	///
+	/// Returns `None` if the inherent has not been applied yet or the proof is invalid.
+	pub fn relay_chain_state_proof() -> Option<RelayChainStateProof> {
+		let validation_data = ValidationData::<T>::get()?;
+		let proof = RelayStateProof::<T>::get()?;
+		RelayChainStateProof::new(
+			T::SelfParaId::get(),
+			validation_data.relay_parent_storage_root,
+			proof,
+		)
+		.ok()
+	}
+
 	/// Get the unincluded segment size after the given hash.
 	///
 	/// If the unincluded segment doesn't contain the given hash, this returns the
@@ -971,6 +1241,17 @@ impl<T: Config> Pallet<T> {
 		let segment = UnincludedSegment::<T>::get();
 		crate::unincluded_segment::size_after_included(included_hash, &segment)
 	}
+
+	/// Get the maximum capacity of the unincluded segment, as returned by the consensus hook in
+	/// the latest block.
+	///
+	/// Before the first block is built, this returns `1`, i.e. only the parent block being
+	/// included is assumed.
+	///
+	/// This is intended to be used in runtime APIs related to authoring.
+	pub fn unincluded_segment_capacity() -> u32 {
+		UnincludedSegmentCapacityLimit::<T>::get().max(1)
+	}
 }
 
 impl<T: Config> GetChannelInfo for Pallet<T> {
@@ -1074,8 +1355,7 @@ impl<T: Config> Pallet<T> {
 		let mut weight_used = Weight::zero();
 		if dm_count != 0 {
 			Self::deposit_event(Event::DownwardMessagesReceived { count: dm_count });
-			let max_weight =
-				<ReservedDmpWeightOverride<T>>::get().unwrap_or_else(T::ReservedDmpWeight::get);
+			let max_weight = Self::limit_to_pov_budget(Self::reserved_dmp_weight());
 
 			let message_iter = downward_messages
 				.into_iter()
@@ -1085,6 +1365,7 @@ impl<T: Config> Pallet<T> {
 				.map(|m| (m.sent_at, m.msg));
 			weight_used += T::DmpMessageHandler::handle_dmp_messages(message_iter, max_weight);
 			<LastDmqMqcHead<T>>::put(&dmq_head);
+			Self::note_pov_size_used(weight_used.proof_size());
 
 			Self::deposit_event(Event::DownwardMessagesProcessed {
 				weight_used,
@@ -1169,9 +1450,9 @@ impl<T: Config> Pallet<T> {
 			.iter()
 			.map(|&(sender, ref message)| (sender, message.sent_at, &message.data[..]));
 
-		let max_weight =
-			<ReservedXcmpWeightOverride<T>>::get().unwrap_or_else(T::ReservedXcmpWeight::get);
+		let max_weight = Self::limit_to_pov_budget(Self::reserved_xcmp_weight());
 		let weight_used = T::XcmpMessageHandler::handle_xcmp_messages(message_iter, max_weight);
+		Self::note_pov_size_used(weight_used.proof_size());
 
 		// Check that the MQC heads for each channel provided by the relay chain match the MQC
 		// heads we have after processing all incoming messages.
@@ -1199,6 +1480,27 @@ impl<T: Config> Pallet<T> {
 		weight_used
 	}
 
+	/// Limits the proof size of the `weight` to the remaining PoV budget.
+	fn limit_to_pov_budget(weight: Weight) -> Weight {
+		weight.min(Weight::from_parts(u64::MAX, Self::remaining_pov_budget()))
+	}
+
+	/// Store the genesis hash of the relay chain, if it is not stored yet and the collator has
+	/// put it into the relay chain state proof.
+	fn maybe_store_relay_chain_genesis_hash(relay_state_proof: &RelayChainStateProof) -> Weight {
+		if T::RelayChainGenesisHashStore::relay_chain_genesis_hash().is_some() {
+			return T::DbWeight::get().reads(1)
+		}
+
+		match relay_state_proof.read_genesis_hash() {
+			Ok(Some(genesis_hash)) => {
+				T::RelayChainGenesisHashStore::set_relay_chain_genesis_hash(genesis_hash);
+				T::DbWeight::get().reads_writes(1, 2)
+			},
+			_ => T::DbWeight::get().reads(1),
+		}
+	}
+
 	/// Drop blocks from the unincluded segment with respect to the latest parachain head.
 	fn maybe_drop_included_ancestors(
 		relay_state_proof: &RelayChainStateProof,
@@ -1336,6 +1638,23 @@ impl<T: Config> Pallet<T> {
 		<HostConfiguration<T>>::get().map(|cfg| cfg.max_code_size)
 	}
 
+	/// The proof size that may still be consumed in the current block, before the
+	/// `PovSizeWatermark` is reached.
+	///
+	/// Inbound messages are not processed once the budget is exhausted.
+	pub fn remaining_pov_budget() -> u64 {
+		T::PovSizeWatermark::get().saturating_sub(PovSizeUsed::<T>::get())
+	}
+
+	/// Notes that `proof_size` has been consumed in the current block.
+	///
+	/// Pallets should use this to report the proof size that is consumed outside of the
+	/// inherent and messages processing, so that it is taken into account by
+	/// [`Self::remaining_pov_budget`].
+	pub fn note_pov_size_used(proof_size: u64) {
+		PovSizeUsed::<T>::mutate(|used| *used = used.saturating_add(proof_size));
+	}
+
 	/// The implementation of the runtime upgrade functionality for parachains.
 	pub fn schedule_code_upgrade(validation_function: Vec<u8>) -> DispatchResult {
 		// Ensure that `ValidationData` exists. We do not care about the validation data per se,
@@ -1370,20 +1689,61 @@ impl<T: Config> Pallet<T> {
 	/// This is expected to be used by the
 	/// [`CollectCollationInfo`](cumulus_primitives_core::CollectCollationInfo) runtime api.
 	pub fn collect_collation_info(header: &HeaderFor<T>) -> CollationInfo {
+		let horizontal_messages = HrmpOutboundMessages::<T>::get();
+		let upward_messages = UpwardMessages::<T>::get();
+		let new_validation_code = NewValidationCode::<T>::get();
+
+		let mut footprint = message_footprint(
+			&upward_messages,
+			&horizontal_messages,
+			new_validation_code.is_some(),
+		);
+		footprint.insert(
+			CollationResource::ProofSize,
+			frame_system::Pallet::<T>::block_weight().total().proof_size(),
+		);
+
 		CollationInfo {
 			hrmp_watermark: HrmpWatermark::<T>::get(),
-			horizontal_messages: HrmpOutboundMessages::<T>::get(),
-			upward_messages: UpwardMessages::<T>::get(),
+			horizontal_messages,
+			upward_messages,
 			processed_downward_messages: ProcessedDownwardMessages::<T>::get(),
-			new_validation_code: NewValidationCode::<T>::get().map(Into::into),
+			new_validation_code: new_validation_code.map(Into::into),
 			// Check if there is a custom header that will also be returned by the validation phase.
 			// If so, we need to also return it here.
 			head_data: CustomValidationHeadData::<T>::get()
 				.map_or_else(|| header.encode(), |v| v)
 				.into(),
+			footprint,
+			limits: Self::collation_limits(),
 		}
 	}
 
+	/// The limits of the resources a collation of the current block may consume.
+	///
+	/// The messaging limits are taken from the relay parent, minus what is used by the unincluded
+	/// segment.
+	fn collation_limits() -> ResourceFootprint {
+		let mut limits = ResourceFootprint::new();
+		if let Some(messaging_state) = Self::relevant_messaging_state() {
+			limits.insert(
+				CollationResource::UmpBytes,
+				messaging_state.relay_dispatch_queue_remaining_capacity.remaining_size.into(),
+			);
+			for (recipient, channel) in messaging_state.egress_channels {
+				limits.insert(
+					CollationResource::HrmpBytes(recipient),
+					channel.max_total_size.saturating_sub(channel.total_size).into(),
+				);
+			}
+		}
+		limits.insert(
+			CollationResource::ProofSize,
+			<T as frame_system::Config>::BlockWeights::get().max_block.proof_size(),
+		);
+		limits
+	}
+
 	/// Set a custom head data that should be returned as result of `validate_block`.
 	///
 	/// This will overwrite the head data that is returned as result of `validate_block` while
@@ -1493,7 +1853,13 @@ impl<T: Config> Pallet<T> {
 			//
 			// Thus fall through here.
 		};
-		<PendingUpwardMessages<T>>::append(message.clone());
+		let is_priority = Self::upward_message_origin(&message)
+			.map_or(false, |origin| T::PriorityUpwardMessageSource::is_priority(&origin, &message));
+		if is_priority {
+			<PendingPriorityUpwardMessages<T>>::append(message.clone());
+		} else {
+			<PendingUpwardMessages<T>>::append(message.clone());
+		}
 
 		// The relay ump does not use using_encoded
 		// We apply the same this to use the same hash
@@ -1501,6 +1867,44 @@ impl<T: Config> Pallet<T> {
 		Self::deposit_event(Event::UpwardMessageSent { message_hash: Some(hash) });
 		Ok((0, hash))
 	}
+
+	/// Returns the origin of the upward XCM `message`, relative to this parachain.
+	///
+	/// `pallet-xcm` prepends a `DescendOrigin` to the messages of all origins but the root one,
+	/// so the message is sent by the parachain itself, unless it starts with a `DescendOrigin`.
+	/// Returns `None` if the `message` is not a valid XCM.
+	fn upward_message_origin(message: &[u8]) -> Option<MultiLocation> {
+		let message =
+			VersionedXcm::<()>::decode_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut &message[..])
+				.ok()?;
+		let message = Xcm::<()>::try_from(message).ok()?;
+		match message.0.first() {
+			Some(Instruction::DescendOrigin(interior)) => Some(MultiLocation::new(0, *interior)),
+			_ => Some(MultiLocation::here()),
+		}
+	}
+
+	/// Removes and returns the pending upward messages, the priority ones first.
+	///
+	/// Only meant for runtime APIs that simulate the messages sent by a call or an XCM program and
+	/// discard all changes to the state afterwards.
+	pub fn take_pending_upward_messages() -> Vec<UpwardMessage> {
+		let mut messages = <PendingPriorityUpwardMessages<T>>::take();
+		messages.extend(<PendingUpwardMessages<T>>::take());
+		messages
+	}
+
+	/// Returns the author of the current block, that is proven by the authorship proof in the
+	/// header digest.
+	///
+	/// Returns `None` if there is no proof or it is invalid.
+	pub fn block_author() -> Option<<T::AuthorshipProofVerifier as VerifyAuthorshipProof>::Author> {
+		let proof = cumulus_primitives_core::extract_authorship_proof(
+			&frame_system::Pallet::<T>::digest(),
+		)?;
+
+		T::AuthorshipProofVerifier::verify(&proof)
+	}
 }
 
 impl<T: Config> UpwardMessageSender for Pallet<T> {
@@ -1509,6 +1913,72 @@ impl<T: Config> UpwardMessageSender for Pallet<T> {
 	}
 }
 
+/// Something that decides, whether an upward message should be sent to the relay chain ahead of
+/// the other pending upward messages.
+///
+/// This allows e.g. messages from the governance origin to reach the relay chain even when the
+/// upward message queue is congested by the regular traffic.
+pub trait PriorityUpwardMessageSource {
+	/// Returns `true` if the `message`, sent by the `origin`, should be sent ahead of the regular
+	/// messages.
+	///
+	/// The `origin` is relative to this parachain. Messages, that are not valid XCMs, are never
+	/// sent ahead of the regular messages.
+	fn is_priority(origin: &MultiLocation, message: &[u8]) -> bool;
+}
+
+impl PriorityUpwardMessageSource for () {
+	fn is_priority(_origin: &MultiLocation, _message: &[u8]) -> bool {
+		false
+	}
+}
+
+/// Sends the upward messages of the origins, that are contained in `Origins`, ahead of the
+/// regular messages.
+///
+/// Messages sent by the XCM executor on behalf of remote origins have no `DescendOrigin`, so
+/// they can't be told apart from the messages of the root origin. Prefer local non-root origins,
+/// e.g. the pluralities of the governance bodies, over `Here`.
+pub struct PriorityOrigins<Origins>(sp_std::marker::PhantomData<Origins>);
+
+impl<Origins: Contains<MultiLocation>> PriorityUpwardMessageSource for PriorityOrigins<Origins> {
+	fn is_priority(origin: &MultiLocation, _message: &[u8]) -> bool {
+		Origins::contains(origin)
+	}
+}
+
+/// Something that verifies the proof of the block authorship.
+///
+/// See [`CumulusDigestItem::AuthorshipProof`](cumulus_primitives_core::CumulusDigestItem).
+pub trait VerifyAuthorshipProof {
+	/// The author of the block.
+	type Author: Parameter;
+
+	/// Returns the author, if the `proof` is a valid proof of the authorship of the current
+	/// block.
+	///
+	/// A valid proof is bound to the current block, i.e. it signs the
+	/// [`authorship_proof_payload`](cumulus_primitives_core::authorship_proof_payload) of the
+	/// current block.
+	fn verify(proof: &[u8]) -> Option<Self::Author>;
+
+	/// The weight of a single [`verify`](Self::verify).
+	fn verification_weight() -> Weight;
+}
+
+/// No proofs are verified, i.e. every authorship proof is accepted without proving any author.
+impl VerifyAuthorshipProof for () {
+	type Author = ();
+
+	fn verify(_proof: &[u8]) -> Option<()> {
+		Some(())
+	}
+
+	fn verification_weight() -> Weight {
+		Weight::zero()
+	}
+}
+
 /// Something that can check the inherents of a block.
 #[cfg_attr(
 	feature = "parameterized-consensus-hook",
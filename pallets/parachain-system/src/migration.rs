@@ -22,7 +22,7 @@ use frame_support::{
 };
 
 /// The current storage version.
-pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
 /// Migrates the pallet storage to the most recent version.
 pub struct Migration<T: Config>(PhantomData<T>);
@@ -45,10 +45,41 @@ impl<T: Config> OnRuntimeUpgrade for Migration<T> {
 			StorageVersion::new(2).put::<Pallet<T>>();
 		}
 
+		if StorageVersion::get::<Pallet<T>>() == 2 {
+			weight = weight
+				.saturating_add(v3::migrate::<T>())
+				.saturating_add(T::DbWeight::get().writes(1));
+			StorageVersion::new(3).put::<Pallet<T>>();
+		}
+
 		weight
 	}
 }
 
+/// V3: `AuthorizedUpgrade` may specify the relay chain block number, starting from which the
+/// upgrade can be enacted. Existing authorizations are enactable immediately.
+mod v3 {
+	use super::*;
+	use crate::{AuthorizedUpgrade, CodeUpgradeAuthorization};
+
+	pub fn migrate<T: Config>() -> Weight {
+		let translate = |(code_hash, check_version): (T::Hash, bool)| CodeUpgradeAuthorization::<T> {
+			code_hash,
+			check_version,
+			enactable_at: None,
+		};
+
+		if AuthorizedUpgrade::<T>::translate(|pre| pre.map(translate)).is_err() {
+			log::error!(
+				target: "parachain_system",
+				"unexpected error when performing translation of the AuthorizedUpgrade type during storage upgrade to v3"
+			);
+		}
+
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+}
+
 /// V2: Migrate to 2D weights for ReservedXcmpWeightOverride and ReservedDmpWeightOverride.
 mod v2 {
 	use super::*;
@@ -15,6 +15,9 @@
 // along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
 
 //! Relay chain state proof provides means for accessing part of relay chain storage for reads.
+//!
+//! The proof is provided by the `set_validation_data` inherent of every block, so other pallets
+//! can read the proven relay chain data through [`crate::Pallet::relay_chain_state_proof`].
 
 use codec::{Decode, Encode};
 use cumulus_primitives_core::{
@@ -70,6 +73,32 @@ pub struct MessagingStateSnapshot {
 	pub egress_channels: Vec<(ParaId, AbridgedHrmpChannel)>,
 }
 
+pub use cumulus_primitives_parachain_inherent::relay_storage_keys;
+
+/// Balances of the relay chain account.
+#[derive(Clone, Encode, Decode, TypeInfo, Default, PartialEq, Eq, Debug)]
+pub struct RelayAccountBalances {
+	/// Non-reserved part of the balance.
+	pub free: relay_chain::Balance,
+	/// Balance which is reserved and may not be used at all.
+	pub reserved: relay_chain::Balance,
+	/// The amount that `free` may not drop below when reducing the balance.
+	pub frozen: relay_chain::Balance,
+}
+
+/// The account information, as it is stored by the relay chain `System` pallet.
+// The field order should stay the same as in the relay chain to ensure both have the same encoded
+// representation.
+#[derive(Decode)]
+struct RelayAccountInfo {
+	_nonce: relay_chain::Nonce,
+	_consumers: u32,
+	_providers: u32,
+	_sufficients: u32,
+	balances: RelayAccountBalances,
+	_flags: relay_chain::Balance,
+}
+
 #[derive(Debug)]
 pub enum Error {
 	/// The provided proof was created against unexpected storage root.
@@ -98,6 +127,14 @@ pub enum Error {
 	HrmpChannel(ParaId, ParaId, ReadEntryErr),
 	/// The latest included parachain head cannot be extracted.
 	ParaHead(ReadEntryErr),
+	/// The epoch index cannot be extracted.
+	EpochIndex(ReadEntryErr),
+	/// The timestamp cannot be extracted.
+	Timestamp(ReadEntryErr),
+	/// The account balances cannot be extracted.
+	AccountBalances(ReadEntryErr),
+	/// The genesis hash cannot be extracted.
+	GenesisHash(ReadEntryErr),
 }
 
 #[derive(Debug)]
@@ -182,12 +219,7 @@ impl RelayChainStateProof {
 		&self,
 		host_config: &AbridgedHostConfiguration,
 	) -> Result<MessagingStateSnapshot, Error> {
-		let dmq_mqc_head: relay_chain::Hash = read_entry(
-			&self.trie_backend,
-			&relay_chain::well_known_keys::dmq_mqc_head(self.para_id),
-			Some(Default::default()),
-		)
-		.map_err(Error::DmqMqcHead)?;
+		let dmq_mqc_head = self.read_dmq_mqc_head()?;
 
 		let relay_dispatch_queue_remaining_capacity = read_optional_entry::<
 			RelayDispatchQueueRemainingCapacity,
@@ -303,6 +335,65 @@ impl RelayChainStateProof {
 			.map_err(Error::Slot)
 	}
 
+	/// Read the index of the current relay chain epoch from the relay chain state proof.
+	///
+	/// Returns an error if anything failed at reading or decoding.
+	pub fn read_epoch_index(&self) -> Result<u64, Error> {
+		read_entry(&self.trie_backend, relay_chain::well_known_keys::EPOCH_INDEX, None)
+			.map_err(Error::EpochIndex)
+	}
+
+	/// Read the timestamp (in milliseconds) of the relay chain block this state proof was
+	/// extracted from.
+	///
+	/// Returns an error if anything failed at reading or decoding.
+	pub fn read_timestamp(&self) -> Result<u64, Error> {
+		read_entry(&self.trie_backend, &relay_storage_keys::timestamp_now(), None)
+			.map_err(Error::Timestamp)
+	}
+
+	/// Read the head of the downward message queue chain of this parachain from the relay chain
+	/// state proof.
+	///
+	/// Returns an error if anything failed at reading or decoding.
+	pub fn read_dmq_mqc_head(&self) -> Result<relay_chain::Hash, Error> {
+		read_entry(
+			&self.trie_backend,
+			&relay_chain::well_known_keys::dmq_mqc_head(self.para_id),
+			Some(Default::default()),
+		)
+		.map_err(Error::DmqMqcHead)
+	}
+
+	/// Read the balances of the given relay chain `account` from the relay chain state proof.
+	///
+	/// Balances of the accounts, that don't exist on the relay chain, are zero. The accounts are
+	/// only part of the proof, if the collator includes them with
+	/// `ParachainInherentData::create_at_with_additional_keys`.
+	/// Returns an error if anything failed at reading or decoding.
+	pub fn read_account_balances(
+		&self,
+		account: &relay_chain::AccountId,
+	) -> Result<RelayAccountBalances, Error> {
+		read_optional_entry::<RelayAccountInfo, _>(
+			&self.trie_backend,
+			&relay_storage_keys::account(account),
+		)
+		.map(|info| info.map(|info| info.balances).unwrap_or_default())
+		.map_err(Error::AccountBalances)
+	}
+
+	/// Read the genesis hash of the relay chain from the relay chain state proof.
+	///
+	/// The genesis hash is only part of the proof, if the collator includes it. Returns `None`
+	/// otherwise.
+	///
+	/// Returns an error if anything failed at reading or decoding.
+	pub fn read_genesis_hash(&self) -> Result<Option<relay_chain::Hash>, Error> {
+		read_optional_entry(&self.trie_backend, &relay_storage_keys::genesis_hash())
+			.map_err(Error::GenesisHash)
+	}
+
 	/// Read the go-ahead signal for the upgrade from the relay chain state proof.
 	///
 	/// The go-ahead specifies whether the parachain can apply the upgrade or should abort it. If
@@ -17,16 +17,16 @@ use super::*;
 
 use codec::Encode;
 use cumulus_primitives_core::{
-	relay_chain::BlockNumber as RelayBlockNumber, AbridgedHrmpChannel, InboundDownwardMessage,
-	InboundHrmpMessage, PersistedValidationData,
+	relay_chain::BlockNumber as RelayBlockNumber, AbridgedHrmpChannel, CumulusDigestItem,
+	DigestItem, InboundDownwardMessage, InboundHrmpMessage, PersistedValidationData,
 };
 use cumulus_test_relay_sproof_builder::RelayStateSproofBuilder;
 use frame_support::{
-	assert_ok,
+	assert_noop, assert_ok,
 	dispatch::UnfilteredDispatchable,
 	inherent::{InherentData, ProvideInherent},
 	parameter_types,
-	traits::{OnFinalize, OnInitialize},
+	traits::{ConstU64, Equals, OnFinalize, OnInitialize},
 	weights::Weight,
 };
 use frame_system::{
@@ -43,6 +43,11 @@ use sp_runtime::{
 use sp_std::{collections::vec_deque::VecDeque, num::NonZeroU32};
 use sp_version::RuntimeVersion;
 use std::cell::RefCell;
+use xcm::latest::{
+	Instruction::{DescendOrigin, SetTopic},
+	Junction::PalletInstance,
+	Junctions::X1,
+};
 
 use crate as parachain_system;
 use crate::consensus_hook::UnincludedSegmentCapacity;
@@ -70,8 +75,10 @@ parameter_types! {
 		state_version: 1,
 	};
 	pub const ParachainId: ParaId = ParaId::new(200);
+	pub const PriorityOrigin: MultiLocation = MultiLocation { parents: 0, interior: X1(PalletInstance(1)) };
 	pub const ReservedXcmpWeight: Weight = Weight::zero();
 	pub const ReservedDmpWeight: Weight = Weight::zero();
+	pub static PovSizeWatermark: u64 = u64::MAX;
 }
 impl frame_system::Config for Test {
 	type RuntimeOrigin = RuntimeOrigin;
@@ -101,13 +108,18 @@ impl frame_system::Config for Test {
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type OnSystemEvent = ();
+	type RelayChainGenesisHashStore = GenesisHashInThreadLocal;
 	type SelfParaId = ParachainId;
 	type OutboundXcmpMessageSource = FromThreadLocal;
+	type PriorityUpwardMessageSource = PriorityOrigins<Equals<PriorityOrigin>>;
 	type DmpMessageHandler = SaveIntoThreadLocal;
 	type ReservedDmpWeight = ReservedDmpWeight;
 	type XcmpMessageHandler = SaveIntoThreadLocal;
 	type ReservedXcmpWeight = ReservedXcmpWeight;
+	type PovSizeWatermark = PovSizeWatermark;
 	type CheckAssociatedRelayNumber = AnyRelayNumber;
+	type CheckRelayTimestamp = ();
+	type AuthorshipProofVerifier = TestAuthorshipProof;
 	type ConsensusHook = TestConsensusHook;
 }
 
@@ -118,10 +130,25 @@ std::thread_local! {
 	static HANDLED_DMP_MESSAGES: RefCell<Vec<(relay_chain::BlockNumber, Vec<u8>)>> = RefCell::new(Vec::new());
 	static HANDLED_XCMP_MESSAGES: RefCell<Vec<(ParaId, relay_chain::BlockNumber, Vec<u8>)>> = RefCell::new(Vec::new());
 	static SENT_MESSAGES: RefCell<Vec<(ParaId, Vec<u8>)>> = RefCell::new(Vec::new());
+	static XCMP_MAX_WEIGHT: RefCell<Weight> = RefCell::new(Weight::zero());
+	static INHERENT_SIZE: RefCell<u64> = RefCell::new(0);
+	static RELAY_CHAIN_GENESIS_HASH: RefCell<Option<relay_chain::Hash>> = RefCell::new(None);
 	static CONSENSUS_HOOK: RefCell<Box<dyn Fn(&RelayChainStateProof) -> (Weight, UnincludedSegmentCapacity)>>
 		= RefCell::new(Box::new(|_| (Weight::zero(), NonZeroU32::new(1).unwrap().into())));
 }
 
+pub struct GenesisHashInThreadLocal;
+
+impl RelayChainGenesisHashStore for GenesisHashInThreadLocal {
+	fn relay_chain_genesis_hash() -> Option<relay_chain::Hash> {
+		RELAY_CHAIN_GENESIS_HASH.with(|h| *h.borrow())
+	}
+
+	fn set_relay_chain_genesis_hash(genesis_hash: relay_chain::Hash) {
+		RELAY_CHAIN_GENESIS_HASH.with(|h| *h.borrow_mut() = Some(genesis_hash));
+	}
+}
+
 pub struct TestConsensusHook;
 
 impl ConsensusHook for TestConsensusHook {
@@ -170,16 +197,48 @@ impl XcmpMessageSource for FromThreadLocal {
 	}
 }
 
+/// Accepts the proofs, that consist of the author and the hash of the payload.
+pub struct TestAuthorshipProof;
+
+impl VerifyAuthorshipProof for TestAuthorshipProof {
+	type Author = u64;
+
+	fn verify(mut proof: &[u8]) -> Option<u64> {
+		let (author, payload_hash) = <(u64, [u8; 32])>::decode(&mut proof).ok()?;
+		let payload = cumulus_primitives_core::authorship_proof_payload(
+			&System::parent_hash(),
+			&System::block_number(),
+			0,
+		);
+		(payload_hash == blake2_256(&payload)).then_some(author)
+	}
+
+	fn verification_weight() -> Weight {
+		Weight::zero()
+	}
+}
+
+fn authorship_proof(author: u64) -> DigestItem {
+	let payload = cumulus_primitives_core::authorship_proof_payload(
+		&System::parent_hash(),
+		&System::block_number(),
+		0,
+	);
+	CumulusDigestItem::AuthorshipProof((author, blake2_256(&payload)).encode()).to_digest_item()
+}
+
 impl DmpMessageHandler for SaveIntoThreadLocal {
 	fn handle_dmp_messages(
 		iter: impl Iterator<Item = (RelayBlockNumber, Vec<u8>)>,
 		_max_weight: Weight,
 	) -> Weight {
 		HANDLED_DMP_MESSAGES.with(|m| {
+			let mut proof_size = 0;
 			for i in iter {
+				proof_size += i.1.len() as u64;
 				m.borrow_mut().push(i);
 			}
-			Weight::zero()
+			Weight::from_parts(0, proof_size)
 		})
 	}
 }
@@ -187,8 +246,9 @@ impl DmpMessageHandler for SaveIntoThreadLocal {
 impl XcmpMessageHandler for SaveIntoThreadLocal {
 	fn handle_xcmp_messages<'a, I: Iterator<Item = (ParaId, RelayBlockNumber, &'a [u8])>>(
 		iter: I,
-		_max_weight: Weight,
+		max_weight: Weight,
 	) -> Weight {
+		XCMP_MAX_WEIGHT.with(|w| *w.borrow_mut() = max_weight);
 		HANDLED_XCMP_MESSAGES.with(|m| {
 			for (sender, sent_at, message) in iter {
 				m.borrow_mut().push((sender, sent_at, message.to_vec()));
@@ -525,6 +585,34 @@ fn unincluded_segment_works() {
 		);
 }
 
+#[test]
+fn unincluded_segment_capacity_is_stored() {
+	CONSENSUS_HOOK.with(|c| {
+		*c.borrow_mut() = Box::new(|_| (Weight::zero(), NonZeroU32::new(3).unwrap().into()))
+	});
+
+	BlockTests::new()
+		.with_inclusion_delay(2)
+		.add_with_post_test(
+			123,
+			|| {},
+			|| {
+				assert_eq!(ParachainSystem::unincluded_segment_capacity(), 3);
+				assert_eq!(ParachainSystem::unincluded_segment_size_after(Default::default()), 1);
+			},
+		)
+		.add_with_post_test(
+			124,
+			|| {},
+			|| {
+				let segment = <UnincludedSegment<Test>>::get();
+				let first = *segment[0].para_head_hash().unwrap();
+				assert_eq!(ParachainSystem::unincluded_segment_size_after(Default::default()), 2);
+				assert_eq!(ParachainSystem::unincluded_segment_size_after(first), 1);
+			},
+		);
+}
+
 #[test]
 #[should_panic = "no space left for the block in the unincluded segment"]
 fn unincluded_segment_is_limited() {
@@ -1023,6 +1111,90 @@ fn send_upward_message_num_per_candidate() {
 		);
 }
 
+/// Upward XCM, sent by the pallet with the given `index`, or by the root origin if `None`.
+fn upward_xcm(pallet_index: Option<u8>, topic: u8) -> Vec<u8> {
+	let mut message = Xcm(vec![SetTopic([topic; 32])]);
+	if let Some(index) = pallet_index {
+		message.0.insert(0, DescendOrigin(X1(PalletInstance(index))));
+	}
+	VersionedXcm::<()>::V3(message).encode()
+}
+
+#[test]
+fn upward_message_origin_works() {
+	assert_eq!(
+		ParachainSystem::upward_message_origin(&upward_xcm(None, 1)),
+		Some(MultiLocation::here())
+	);
+	assert_eq!(
+		ParachainSystem::upward_message_origin(&upward_xcm(Some(1), 1)),
+		Some(PriorityOrigin::get())
+	);
+	assert_eq!(ParachainSystem::upward_message_origin(b"not an xcm"), None);
+}
+
+#[test]
+fn send_upward_message_priority_messages_first() {
+	// messages of the root origin, of other pallets and invalid XCMs are not prioritized
+	let message_1 = upward_xcm(None, 1);
+	let message_2 = upward_xcm(Some(2), 2);
+	let message_3 = b"not an xcm".to_vec();
+	let priority = |topic| upward_xcm(Some(1), topic);
+
+	BlockTests::new()
+		.with_relay_sproof_builder(|_, _, sproof| {
+			sproof.host_config.max_upward_message_num_per_candidate = 2;
+			sproof.relay_dispatch_queue_remaining_capacity = None;
+		})
+		.add_with_post_test(
+			1,
+			{
+				let message_1 = message_1.clone();
+				move || {
+					ParachainSystem::send_upward_message(message_1.clone()).unwrap();
+					ParachainSystem::send_upward_message(priority(1)).unwrap();
+					ParachainSystem::send_upward_message(priority(2)).unwrap();
+					ParachainSystem::send_upward_message(priority(3)).unwrap();
+				}
+			},
+			move || {
+				let v = UpwardMessages::<Test>::get();
+				assert_eq!(v, vec![priority(1), priority(2)]);
+			},
+		)
+		.add_with_post_test(
+			2,
+			{
+				let (message_2, message_3) = (message_2.clone(), message_3.clone());
+				move || {
+					ParachainSystem::send_upward_message(message_2.clone()).unwrap();
+					ParachainSystem::send_upward_message(priority(4)).unwrap();
+					ParachainSystem::send_upward_message(message_3.clone()).unwrap();
+				}
+			},
+			move || {
+				let v = UpwardMessages::<Test>::get();
+				assert_eq!(v, vec![priority(3), priority(4)]);
+			},
+		)
+		.add_with_post_test(
+			3,
+			|| { /* do nothing within block */ },
+			move || {
+				let v = UpwardMessages::<Test>::get();
+				assert_eq!(v, vec![message_1.clone(), message_2.clone()]);
+			},
+		)
+		.add_with_post_test(
+			4,
+			|| { /* do nothing within block */ },
+			move || {
+				let v = UpwardMessages::<Test>::get();
+				assert_eq!(v, vec![message_3.clone()]);
+			},
+		);
+}
+
 #[test]
 fn send_upward_message_relay_bottleneck() {
 	BlockTests::new()
@@ -1057,6 +1229,37 @@ fn send_upward_message_relay_bottleneck() {
 		);
 }
 
+#[test]
+fn collation_info_has_footprint_and_limits() {
+	BlockTests::new()
+		.with_relay_sproof_builder(|_, _, sproof| {
+			sproof.host_config.max_upward_message_num_per_candidate = 2;
+			sproof.relay_dispatch_queue_remaining_capacity = Some((5, 2048));
+		})
+		.add_with_post_test(
+			1,
+			|| {
+				ParachainSystem::send_upward_message(vec![0u8; 8]).unwrap();
+				ParachainSystem::send_upward_message(vec![0u8; 4]).unwrap();
+			},
+			|| {
+				let header = HeaderFor::<Test>::new_from_number(1);
+				let info = ParachainSystem::collect_collation_info(&header);
+
+				assert_eq!(info.footprint.get(&CollationResource::UmpBytes), Some(&12));
+				assert_eq!(info.footprint.get(&CollationResource::CodeUpgrade), None);
+				assert_eq!(info.limits.get(&CollationResource::UmpBytes), Some(&2048));
+				assert!(info.limits.contains_key(&CollationResource::ProofSize));
+				assert_eq!(info.exceeded_resource(), None);
+
+				// A limit below the footprint is detected.
+				let mut info = info;
+				info.limits.insert(CollationResource::UmpBytes, 10);
+				assert_eq!(info.exceeded_resource(), Some(CollationResource::UmpBytes));
+			},
+		);
+}
+
 #[test]
 fn send_hrmp_message_buffer_channel_close() {
 	BlockTests::new()
@@ -1207,6 +1410,97 @@ fn receive_dmp() {
 		});
 }
 
+#[test]
+fn pov_budget_is_consumed_by_inherent_and_messages() {
+	lazy_static::lazy_static! {
+		static ref MSG: InboundDownwardMessage = InboundDownwardMessage {
+			sent_at: 1,
+			msg: b"down".to_vec(),
+		};
+	}
+
+	BlockTests::new()
+		.with_relay_sproof_builder(|_, _, sproof| {
+			sproof.dmq_mqc_head = Some(MessageQueueChain::default().extend_downward(&MSG).head());
+		})
+		.with_inherent_data(|_, _, data| {
+			data.downward_messages.push(MSG.clone());
+			INHERENT_SIZE.with(|s| *s.borrow_mut() = data.encoded_size() as u64);
+		})
+		.add(1, || {
+			let inherent_size = INHERENT_SIZE.with(|s| *s.borrow());
+			assert_eq!(
+				ParachainSystem::remaining_pov_budget(),
+				u64::MAX - inherent_size - MSG.msg.len() as u64,
+			);
+
+			ParachainSystem::note_pov_size_used(u64::MAX);
+			assert_eq!(ParachainSystem::remaining_pov_budget(), 0);
+		});
+}
+
+#[test]
+fn messages_are_not_processed_above_pov_watermark() {
+	BlockTests::new()
+		.with_relay_sproof_builder(|_, relay_block_num, _| {
+			ReservedXcmpWeightOverride::<Test>::put(Weight::from_parts(1_000, 2_000_000));
+			// the inherent alone exceeds the watermark in the second block
+			PovSizeWatermark::set(if relay_block_num == 1 { 1_000_000 } else { 1 });
+		})
+		.add(1, || {
+			// the proof size is limited by the remaining budget
+			let max_weight = XCMP_MAX_WEIGHT.with(|w| *w.borrow());
+			assert_eq!(max_weight.ref_time(), 1_000);
+			assert!(max_weight.proof_size() < 1_000_000);
+			assert_eq!(max_weight.proof_size(), ParachainSystem::remaining_pov_budget());
+		})
+		.add(2, || {
+			// no more messages are processed once the watermark is hit
+			assert_eq!(XCMP_MAX_WEIGHT.with(|w| *w.borrow()), Weight::from_parts(1_000, 0));
+			assert_eq!(ParachainSystem::remaining_pov_budget(), 0);
+		});
+}
+
+#[test]
+fn message_processing_budget_can_be_set_by_root() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let dmp_weight = Weight::from_parts(1_000, 1_000);
+
+		assert_noop!(
+			ParachainSystem::set_message_processing_budget(
+				RawOrigin::None.into(),
+				Some(dmp_weight),
+				None,
+			),
+			sp_runtime::DispatchError::BadOrigin,
+		);
+
+		assert_ok!(ParachainSystem::set_message_processing_budget(
+			RawOrigin::Root.into(),
+			Some(dmp_weight),
+			None,
+		));
+		assert_eq!(ParachainSystem::reserved_dmp_weight(), dmp_weight);
+		assert_eq!(ParachainSystem::reserved_xcmp_weight(), ReservedXcmpWeight::get());
+		System::assert_last_event(
+			crate::Event::MessageProcessingBudgetSet {
+				dmp_weight,
+				xcmp_weight: ReservedXcmpWeight::get(),
+			}
+			.into(),
+		);
+
+		// the default is used again
+		assert_ok!(ParachainSystem::set_message_processing_budget(
+			RawOrigin::Root.into(),
+			None,
+			None,
+		));
+		assert_eq!(ParachainSystem::reserved_dmp_weight(), ReservedDmpWeight::get());
+	});
+}
+
 #[test]
 fn receive_dmp_after_pause() {
 	lazy_static::lazy_static! {
@@ -1483,6 +1777,116 @@ fn upgrade_version_checks_should_work() {
 	}
 }
 
+#[test]
+fn authorized_upgrade_is_enactable_at_relay_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let new_code = vec![1, 2, 3, 4];
+		let new_code_hash = sp_core::H256(blake2_256(&new_code));
+
+		assert_ok!(ParachainSystem::authorize_upgrade_at(
+			RawOrigin::Root.into(),
+			100,
+			new_code_hash,
+			false,
+		));
+		System::assert_last_event(
+			crate::Event::UpgradeAuthorizedAt { code_hash: new_code_hash, relay_block_number: 100 }
+				.into(),
+		);
+		assert_eq!(ParachainSystem::authorized_upgrade(), Some((new_code_hash, Some(100))));
+
+		LastRelayChainBlockNumber::<Test>::put(99);
+		assert_noop!(
+			ParachainSystem::enact_authorized_upgrade(RawOrigin::None.into(), new_code.clone()),
+			Error::<Test>::UpgradeNotEnactableYet,
+		);
+
+		LastRelayChainBlockNumber::<Test>::put(100);
+		assert_eq!(ParachainSystem::validate_authorized_upgrade(&new_code), Ok(new_code_hash));
+	});
+}
+
+#[test]
+fn relay_chain_state_proof_is_readable_by_other_pallets() {
+	use crate::relay_state_snapshot::relay_storage_keys;
+
+	let account = relay_chain::AccountId::from([1u8; 32]);
+	let proof_account = account.clone();
+	BlockTests::new()
+		.with_relay_sproof_builder(move |_, _, sproof| {
+			sproof.current_epoch = 7;
+			sproof.additional_key_values = vec![
+				(relay_storage_keys::timestamp_now(), 1_000u64.encode()),
+				(
+					relay_storage_keys::account(&proof_account),
+					(0u32, 0u32, 1u32, 0u32, 100u128, 20u128, 5u128, 0u128).encode(),
+				),
+			];
+		})
+		.add(123, move || {
+			let proof = ParachainSystem::relay_chain_state_proof().expect("proof is set");
+			assert_eq!(proof.read_epoch_index().unwrap(), 7);
+			assert_eq!(proof.read_timestamp().unwrap(), 1_000);
+			assert_eq!(proof.read_dmq_mqc_head().unwrap(), H256::zero());
+			assert_eq!(
+				proof.read_account_balances(&account).unwrap(),
+				RelayAccountBalances { free: 100, reserved: 20, frozen: 5 },
+			);
+			assert_eq!(
+				proof.read_account_balances(&relay_chain::AccountId::from([2u8; 32])).unwrap(),
+				Default::default(),
+			);
+		});
+}
+
+#[test]
+fn relay_chain_genesis_hash_is_stored_from_the_state_proof() {
+	use crate::relay_state_snapshot::relay_storage_keys;
+
+	BlockTests::new()
+		.with_relay_sproof_builder(|_, relay_block_num, sproof| {
+			// the collators of the first block don't put the genesis hash into the proof
+			if relay_block_num > 1 {
+				let genesis_hash = relay_chain::Hash::repeat_byte(relay_block_num as u8);
+				sproof
+					.additional_key_values
+					.push((relay_storage_keys::genesis_hash(), genesis_hash.encode()));
+			}
+		})
+		.add(1, || {
+			assert_eq!(GenesisHashInThreadLocal::relay_chain_genesis_hash(), None);
+		})
+		.add(2, || {
+			assert_eq!(
+				GenesisHashInThreadLocal::relay_chain_genesis_hash(),
+				Some(relay_chain::Hash::repeat_byte(2)),
+			);
+		})
+		.add(3, || {
+			// the stored genesis hash is not replaced
+			assert_eq!(
+				GenesisHashInThreadLocal::relay_chain_genesis_hash(),
+				Some(relay_chain::Hash::repeat_byte(2)),
+			);
+			let proof = ParachainSystem::relay_chain_state_proof().expect("proof is set");
+			assert_eq!(proof.read_genesis_hash().unwrap(), Some(relay_chain::Hash::repeat_byte(3)));
+		});
+}
+
+#[test]
+fn relay_chain_state_proof_is_only_available_after_validation_data() {
+	new_test_ext().execute_with(|| {
+		assert!(ParachainSystem::relay_chain_state_proof().is_none());
+	});
+
+	BlockTests::new().add(123, || {
+		let proof = ParachainSystem::relay_chain_state_proof().expect("proof is set");
+		// keys that have not been proven can't be read
+		assert!(matches!(proof.read_timestamp(), Err(relay_state_snapshot::Error::Timestamp(_))));
+	});
+}
+
 #[test]
 fn deposits_relay_parent_storage_root() {
 	BlockTests::new().add_with_post_test(
@@ -1497,3 +1901,76 @@ fn deposits_relay_parent_storage_root() {
 		},
 	);
 }
+
+#[test]
+fn block_author_is_proven_by_digest() {
+	BlockTests::new().add(1, || {
+		assert_eq!(ParachainSystem::block_author(), None);
+
+		System::deposit_log(authorship_proof(42));
+		assert_eq!(ParachainSystem::block_author(), Some(42));
+	});
+}
+
+#[test]
+#[should_panic = "Invalid block authorship proof!"]
+fn invalid_authorship_proof_is_rejected() {
+	BlockTests::new().add(1, || {
+		System::deposit_log(CumulusDigestItem::AuthorshipProof(vec![1, 2, 3]).to_digest_item());
+	});
+}
+
+#[test]
+#[should_panic = "Invalid block authorship proof!"]
+fn authorship_proof_of_another_block_is_rejected() {
+	BlockTests::new().add(1, || {}).add(2, || {
+		let payload =
+			cumulus_primitives_core::authorship_proof_payload(&System::parent_hash(), &1u64, 0);
+		System::deposit_log(
+			CumulusDigestItem::AuthorshipProof((42u64, blake2_256(&payload)).encode())
+				.to_digest_item(),
+		);
+	});
+}
+
+std::thread_local! {
+	static NOW: RefCell<u64> = RefCell::new(0);
+}
+
+struct MockTime;
+impl UnixTime for MockTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_millis(NOW.with(|n| *n.borrow()))
+	}
+}
+
+type CheckTimestamp =
+	TimestampMatchesRelaySlot<MockTime, ConstU64<6000>, ConstU64<6000>, ConstU64<1000>>;
+
+#[test]
+fn timestamp_within_tolerance_of_relay_slot_is_accepted() {
+	for now in [65_000, 66_000, 67_000] {
+		NOW.with(|n| *n.borrow_mut() = now);
+		CheckTimestamp::check_relay_timestamp(Some(10.into()));
+	}
+}
+
+#[test]
+#[should_panic = "Timestamp 67001 is too far away from 66000 derived from the relay parent slot!"]
+fn timestamp_ahead_of_relay_slot_is_rejected() {
+	NOW.with(|n| *n.borrow_mut() = 67_001);
+	CheckTimestamp::check_relay_timestamp(Some(10.into()));
+}
+
+#[test]
+#[should_panic = "Timestamp 64999 is too far away from 66000 derived from the relay parent slot!"]
+fn timestamp_behind_relay_slot_is_rejected() {
+	NOW.with(|n| *n.borrow_mut() = 64_999);
+	CheckTimestamp::check_relay_timestamp(Some(10.into()));
+}
+
+#[test]
+#[should_panic = "Relay chain state proof must contain the relay chain slot!"]
+fn timestamp_check_requires_relay_slot() {
+	CheckTimestamp::check_relay_timestamp(None);
+}
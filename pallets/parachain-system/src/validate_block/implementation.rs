@@ -16,7 +16,11 @@
 
 //! The actual implementation of the validate block functionality.
 
-use super::{trie_cache, MemoryOptimizedValidationParams};
+use super::{
+	trie_cache,
+	trie_recorder::{RecordedProofSize, RecordingStorage, SizeOnlyRecorder},
+	MemoryOptimizedValidationParams,
+};
 use cumulus_primitives_core::{
 	relay_chain::Hash as RHash, ParachainBlockData, PersistedValidationData,
 };
@@ -31,15 +35,17 @@ use sp_core::storage::{ChildInfo, StateVersion};
 use sp_externalities::{set_and_run_with_externalities, Externalities};
 use sp_io::KillStorageResult;
 use sp_runtime::traits::{Block as BlockT, Extrinsic, HashingFor, Header as HeaderT};
-use sp_std::prelude::*;
-use sp_trie::MemoryDB;
+use sp_std::{prelude::*, rc::Rc};
 
 type TrieBackend<B> = sp_state_machine::TrieBackend<
-	MemoryDB<HashingFor<B>>,
+	RecordingStorage<HashingFor<B>>,
 	HashingFor<B>,
 	trie_cache::CacheProvider<HashingFor<B>>,
 >;
 
+// The size of the storage proof, that is recorded while the block is executed.
+environmental::environmental!(recorded_proof_size: RecordedProofSize);
+
 type Ext<'a, B> = sp_state_machine::Ext<'a, HashingFor<B>, TrieBackend<B>>;
 
 fn with_externalities<F: FnOnce(&mut dyn Externalities) -> R, R>(f: F) -> R {
@@ -118,11 +124,12 @@ where
 
 	sp_std::mem::drop(storage_proof);
 
-	let cache_provider = trie_cache::CacheProvider::new();
+	let recorder = Rc::new(SizeOnlyRecorder::new());
+	let cache_provider = trie_cache::CacheProvider::new(recorder.clone());
 	// We use the storage root of the `parent_head` to ensure that it is the correct root.
 	// This is already being done above while creating the in-memory db, but let's be paranoid!!
 	let backend = sp_state_machine::TrieBackendBuilder::new_with_cache(
-		db,
+		RecordingStorage::new(db, recorder.clone()),
 		*parent_header.state_root(),
 		cache_provider,
 	)
@@ -165,6 +172,8 @@ where
 			.replace_implementation(host_default_child_storage_next_key),
 		sp_io::offchain_index::host_set.replace_implementation(host_offchain_index_set),
 		sp_io::offchain_index::host_clear.replace_implementation(host_offchain_index_clear),
+		cumulus_primitives_storage_weight_reclaim::storage_proof_size::host_storage_proof_size
+			.replace_implementation(host_storage_proof_size),
 	);
 
 	run_with_externalities::<B, _, _>(&backend, || {
@@ -188,7 +197,7 @@ where
 		}
 	});
 
-	run_with_externalities::<B, _, _>(&backend, || {
+	run_with_externalities_and_recorder::<B, _, _>(&backend, &recorder, || {
 		let head_data = HeadData(block.header().encode());
 
 		E::execute_block(block);
@@ -274,6 +283,24 @@ fn run_with_externalities<B: BlockT, R, F: FnOnce() -> R>(
 	set_and_run_with_externalities(&mut ext, || execute())
 }
 
+/// Run the given closure with the externalities set, while the size of the storage proof is
+/// recorded by the `recorder`.
+///
+/// The collator records the storage proof from the start of the block execution, so everything
+/// recorded before is forgotten.
+fn run_with_externalities_and_recorder<B: BlockT, R, F: FnOnce() -> R>(
+	backend: &TrieBackend<B>,
+	recorder: &SizeOnlyRecorder<HashingFor<B>>,
+	execute: F,
+) -> R {
+	recorder.reset();
+	let mut proof_size = recorder.recorded_proof_size();
+
+	recorded_proof_size::using(&mut proof_size, || {
+		run_with_externalities::<B, _, _>(backend, execute)
+	})
+}
+
 fn host_storage_read(key: &[u8], value_out: &mut [u8], value_offset: u32) -> Option<u32> {
 	match with_externalities(|ext| ext.storage(key)) {
 		Some(value) => {
@@ -303,6 +330,11 @@ fn host_storage_clear(key: &[u8]) {
 	with_externalities(|ext| ext.place_storage(key.to_vec(), None))
 }
 
+fn host_storage_proof_size() -> u64 {
+	recorded_proof_size::with(|proof_size| proof_size.get() as u64)
+		.unwrap_or(cumulus_primitives_storage_weight_reclaim::PROOF_RECORDING_DISABLED)
+}
+
 fn host_storage_root(version: StateVersion) -> Vec<u8> {
 	with_externalities(|ext| ext.storage_root(version))
 }
@@ -22,9 +22,12 @@ pub mod implementation;
 #[cfg(test)]
 mod tests;
 
-#[cfg(not(feature = "std"))]
+#[cfg(any(test, not(feature = "std")))]
 #[doc(hidden)]
 mod trie_cache;
+#[cfg(any(test, not(feature = "std")))]
+#[doc(hidden)]
+mod trie_recorder;
 
 #[cfg(not(feature = "std"))]
 #[doc(hidden)]
@@ -15,11 +15,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::trie_recorder::SizeOnlyRecorder;
 use sp_state_machine::TrieCacheProvider;
 use sp_std::{
 	boxed::Box,
 	cell::{RefCell, RefMut},
 	collections::btree_map::{BTreeMap, Entry},
+	rc::Rc,
 };
 use sp_trie::NodeCodec;
 use trie_db::{node::NodeOwned, Hasher};
@@ -27,17 +29,27 @@ use trie_db::{node::NodeOwned, Hasher};
 /// Special purpose trie cache implementation that is able to cache an unlimited number
 /// of values. To be used in `validate_block` to serve values and nodes that
 /// have already been loaded and decoded from the storage proof.
+///
+/// Every node served from the cache is recorded with the [`SizeOnlyRecorder`], as if it was
+/// fetched from the storage proof.
 pub(crate) struct TrieCache<'a, H: Hasher> {
 	node_cache: RefMut<'a, BTreeMap<H::Out, NodeOwned<H::Out>>>,
 	value_cache: Option<RefMut<'a, BTreeMap<Box<[u8]>, trie_db::CachedValue<H::Out>>>>,
+	recorder: &'a SizeOnlyRecorder<H>,
 }
 
 impl<'a, H: Hasher> trie_db::TrieCache<NodeCodec<H>> for TrieCache<'a, H> {
 	fn lookup_value_for_key(&mut self, key: &[u8]) -> Option<&trie_db::CachedValue<H::Out>> {
+		// The nodes on the path to the value must be recorded, so the value is only served from
+		// the cache if it has been looked up in the trie since the recorder has been reset.
+		if !self.recorder.is_key_recorded(key) {
+			return None
+		}
 		self.value_cache.as_ref().and_then(|cache| cache.get(key))
 	}
 
 	fn cache_value_for_key(&mut self, key: &[u8], value: trie_db::CachedValue<H::Out>) {
+		self.recorder.record_key(key);
 		self.value_cache.as_mut().and_then(|cache| cache.insert(key.into(), value));
 	}
 
@@ -51,7 +63,12 @@ impl<'a, H: Hasher> trie_db::TrieCache<NodeCodec<H>> for TrieCache<'a, H> {
 		>,
 	) -> trie_db::Result<&NodeOwned<H::Out>, H::Out, <NodeCodec<H> as trie_db::NodeCodec>::Error> {
 		match self.node_cache.entry(hash) {
-			Entry::Occupied(entry) => Ok(entry.into_mut()),
+			Entry::Occupied(entry) => {
+				let node = entry.into_mut();
+				self.recorder.record_node(&hash, node);
+				Ok(node)
+			},
+			// The node is fetched from the storage, which records it.
 			Entry::Vacant(entry) => Ok(entry.insert(fetch_node()?)),
 		}
 	}
@@ -60,7 +77,11 @@ impl<'a, H: Hasher> trie_db::TrieCache<NodeCodec<H>> for TrieCache<'a, H> {
 		&mut self,
 		hash: &H::Out,
 	) -> Option<&NodeOwned<<NodeCodec<H> as trie_db::NodeCodec>::HashOut>> {
-		self.node_cache.get(hash)
+		let node = self.node_cache.get(hash);
+		if let Some(node) = node {
+			self.recorder.record_node(hash, node);
+		}
+		node
 	}
 }
 
@@ -68,23 +89,28 @@ impl<'a, H: Hasher> trie_db::TrieCache<NodeCodec<H>> for TrieCache<'a, H> {
 pub(crate) struct CacheProvider<H: Hasher> {
 	node_cache: RefCell<BTreeMap<H::Out, NodeOwned<H::Out>>>,
 	value_cache: RefCell<BTreeMap<Box<[u8]>, trie_db::CachedValue<H::Out>>>,
+	recorder: Rc<SizeOnlyRecorder<H>>,
 }
 
 impl<H: Hasher> CacheProvider<H> {
 	/// Constructs a new instance of [`CacheProvider`] with an uninitialized state
-	/// and empty node and value caches.
-	pub fn new() -> Self {
-		CacheProvider { node_cache: Default::default(), value_cache: Default::default() }
+	/// and empty node and value caches, that records the served nodes with the `recorder`.
+	pub fn new(recorder: Rc<SizeOnlyRecorder<H>>) -> Self {
+		CacheProvider { node_cache: Default::default(), value_cache: Default::default(), recorder }
 	}
 }
 
 impl<H: Hasher> TrieCacheProvider<H> for CacheProvider<H> {
-	type Cache<'a> = TrieCache<'a, H> where H: 'a;
+	type Cache<'a>
+		= TrieCache<'a, H>
+	where
+		H: 'a;
 
 	fn as_trie_db_cache(&self, _storage_root: <H as Hasher>::Out) -> Self::Cache<'_> {
 		TrieCache {
 			value_cache: Some(self.value_cache.borrow_mut()),
 			node_cache: self.node_cache.borrow_mut(),
+			recorder: &self.recorder,
 		}
 	}
 
@@ -93,7 +119,11 @@ impl<H: Hasher> TrieCacheProvider<H> for CacheProvider<H> {
 		// Since we are using a simplified cache architecture,
 		// we do not have separate key spaces for different storage roots.
 		// The value cache is therefore disabled here.
-		TrieCache { value_cache: None, node_cache: self.node_cache.borrow_mut() }
+		TrieCache {
+			value_cache: None,
+			node_cache: self.node_cache.borrow_mut(),
+			recorder: &self.recorder,
+		}
 	}
 
 	fn merge<'a>(&'a self, _other: Self::Cache<'a>, _new_root: <H as Hasher>::Out) {}
@@ -0,0 +1,216 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Recording of the storage proof size in `validate_block`.
+//!
+//! Collators and importing nodes record the storage proof with the
+//! [`sp_trie::recorder::Recorder`], that provides the size of the proof to the runtime through
+//! the `ProofSizeExt`. While validating the block the runtime has to observe exactly the same
+//! proof sizes, otherwise the storage weight reclaim leads to a different state root. The
+//! [`SizeOnlyRecorder`] replicates the size estimation of the recorder: every trie node (and
+//! every hashed value) is accounted for with its encoded size once, when it is accessed for the
+//! first time.
+
+use codec::Encode;
+use sp_state_machine::{DefaultError, TrieBackendStorage};
+use sp_std::{
+	boxed::Box,
+	cell::{Cell, RefCell},
+	collections::btree_set::BTreeSet,
+	rc::Rc,
+	vec::Vec,
+};
+use sp_trie::{MemoryDB, NodeCodec};
+use trie_db::{node::NodeOwned, Hasher};
+
+/// The size of the storage proof, recorded by the [`SizeOnlyRecorder`].
+///
+/// This is cheap to clone; all clones share the same size.
+#[derive(Clone, Default)]
+pub(crate) struct RecordedProofSize(Rc<Cell<usize>>);
+
+impl RecordedProofSize {
+	/// Returns the estimated encoded size of the storage proof, recorded so far.
+	pub fn get(&self) -> usize {
+		self.0.get()
+	}
+}
+
+/// Recorder, that only estimates the encoded size of the storage proof, without collecting the
+/// proof itself.
+pub(crate) struct SizeOnlyRecorder<H: Hasher> {
+	seen_nodes: RefCell<BTreeSet<H::Out>>,
+	recorded_keys: RefCell<BTreeSet<Box<[u8]>>>,
+	encoded_size: RecordedProofSize,
+}
+
+impl<H: Hasher> SizeOnlyRecorder<H> {
+	/// Creates a new recorder, that hasn't recorded anything yet.
+	pub fn new() -> Self {
+		SizeOnlyRecorder {
+			seen_nodes: Default::default(),
+			recorded_keys: Default::default(),
+			encoded_size: Default::default(),
+		}
+	}
+
+	/// Returns the size of the storage proof, that is updated by this recorder.
+	pub fn recorded_proof_size(&self) -> RecordedProofSize {
+		self.encoded_size.clone()
+	}
+
+	/// Forget everything, that has been recorded so far.
+	///
+	/// The collator starts to record the storage proof right before the block is executed, so
+	/// the accesses done while checking the inherents must not be accounted for.
+	pub fn reset(&self) {
+		self.seen_nodes.borrow_mut().clear();
+		self.recorded_keys.borrow_mut().clear();
+		self.encoded_size.0.set(0);
+	}
+
+	/// Record the access to the trie node (or the hashed value) with the given `encoded` form.
+	pub fn record_encoded(&self, hash: &H::Out, encoded: &[u8]) {
+		self.record_with(hash, || encoded.encoded_size())
+	}
+
+	/// Record the access to the decoded trie node.
+	pub fn record_node(&self, hash: &H::Out, node: &NodeOwned<H::Out>) {
+		self.record_with(hash, || node.to_encoded::<NodeCodec<H>>().encoded_size())
+	}
+
+	/// Remember, that the value of the given `key` has been looked up in the trie, so all the
+	/// nodes on its path have been recorded.
+	pub fn record_key(&self, key: &[u8]) {
+		self.recorded_keys.borrow_mut().insert(key.into());
+	}
+
+	/// Returns `true` if the value of the given `key` has been looked up in the trie since the
+	/// last [`reset`](Self::reset).
+	///
+	/// Value served from the cache for any other key would skip recording of the nodes on its
+	/// path.
+	pub fn is_key_recorded(&self, key: &[u8]) -> bool {
+		self.recorded_keys.borrow().contains(key)
+	}
+
+	fn record_with(&self, hash: &H::Out, encoded_size: impl FnOnce() -> usize) {
+		if self.seen_nodes.borrow_mut().insert(*hash) {
+			let size = self.encoded_size.get().saturating_add(encoded_size());
+			self.encoded_size.0.set(size);
+		}
+	}
+}
+
+/// Trie backend storage, that records the size of every node, fetched from the in-memory
+/// database.
+pub(crate) struct RecordingStorage<H: Hasher> {
+	db: MemoryDB<H>,
+	recorder: Rc<SizeOnlyRecorder<H>>,
+}
+
+impl<H: Hasher> RecordingStorage<H> {
+	/// Wraps the given `db`, recording the fetched nodes with the `recorder`.
+	pub fn new(db: MemoryDB<H>, recorder: Rc<SizeOnlyRecorder<H>>) -> Self {
+		RecordingStorage { db, recorder }
+	}
+}
+
+impl<H: Hasher> TrieBackendStorage<H> for RecordingStorage<H> {
+	fn get(
+		&self,
+		key: &H::Out,
+		prefix: (&[u8], Option<u8>),
+	) -> Result<Option<Vec<u8>>, DefaultError> {
+		let value = TrieBackendStorage::<H>::get(&self.db, key, prefix)?;
+		if let Some(ref value) = value {
+			self.recorder.record_encoded(key, value);
+		}
+		Ok(value)
+	}
+}
+
+// This is safe here since we are single-threaded in WASM
+unsafe impl<H: Hasher> Send for RecordingStorage<H> {}
+unsafe impl<H: Hasher> Sync for RecordingStorage<H> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::validate_block::trie_cache::CacheProvider;
+	use sp_core::Blake2Hasher;
+	use sp_state_machine::{Backend, TrieBackendBuilder};
+	use sp_trie::{recorder::Recorder, TrieDBMutBuilderV1, TrieMut};
+
+	fn create_trie() -> (MemoryDB<Blake2Hasher>, <Blake2Hasher as Hasher>::Out, Vec<Vec<u8>>) {
+		let mut db = MemoryDB::default();
+		let mut root = Default::default();
+		let keys = (0u32..64)
+			.map(|i| i.to_le_bytes().repeat(i as usize % 5 + 1))
+			.collect::<Vec<_>>();
+		{
+			let mut trie = TrieDBMutBuilderV1::<Blake2Hasher>::new(&mut db, &mut root).build();
+			// values larger than 32 bytes are stored as separate nodes in the state version 1
+			for (i, key) in keys.iter().enumerate() {
+				trie.insert(key, &vec![i as u8; i]).expect("Inserts the value");
+			}
+		}
+		(db, root, keys)
+	}
+
+	#[test]
+	fn recorded_size_matches_the_size_estimated_by_the_recorder() {
+		let (db, root, keys) = create_trie();
+
+		let recorder = Recorder::<Blake2Hasher>::default();
+		let recording_backend = TrieBackendBuilder::new(db.clone(), root)
+			.with_recorder(recorder.clone())
+			.build();
+
+		let size_only_recorder = Rc::new(SizeOnlyRecorder::<Blake2Hasher>::new());
+		let size_only_backend = TrieBackendBuilder::new_with_cache(
+			RecordingStorage::new(db, size_only_recorder.clone()),
+			root,
+			CacheProvider::new(size_only_recorder.clone()),
+		)
+		.build();
+
+		// accesses before the reset are not accounted for
+		size_only_backend.storage(&keys[1]).unwrap();
+		size_only_recorder.reset();
+		assert_eq!(size_only_recorder.recorded_proof_size().get(), 0);
+
+		// some keys are accessed twice, the repeated access must not change the size
+		for key in keys.iter().step_by(3).chain(keys.iter().step_by(2)) {
+			assert_eq!(
+				recording_backend.storage(key).unwrap(),
+				size_only_backend.storage(key).unwrap(),
+			);
+			assert_eq!(
+				size_only_recorder.recorded_proof_size().get(),
+				recorder.estimate_encoded_size(),
+			);
+		}
+		assert_eq!(
+			recording_backend.storage(b"missing").unwrap(),
+			size_only_backend.storage(b"missing").unwrap(),
+		);
+		assert_eq!(
+			size_only_recorder.recorded_proof_size().get(),
+			recorder.estimate_encoded_size()
+		);
+	}
+}
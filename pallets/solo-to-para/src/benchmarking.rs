@@ -0,0 +1,80 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarking setup for cumulus-pallet-solo-to-para
+
+use crate::*;
+
+use frame_benchmarking::benchmarks;
+use frame_system::RawOrigin;
+use sp_std::vec;
+use sp_trie::{MemoryDB, TrieDBMutBuilder, TrieMut};
+
+/// Returns the final head data of a solo chain, that has exported `items`, and the storage proof
+/// of `n` nodes, that proves the `items`.
+fn solo_chain_state<T: Config>(
+	n: u32,
+	items: &[(Vec<u8>, Option<Vec<u8>>)],
+) -> (Vec<u8>, Vec<Vec<u8>>) {
+	let mut db = MemoryDB::<T::Hashing>::default();
+	let mut root = Default::default();
+	{
+		let mut trie = TrieDBMutBuilder::<LayoutV1<T::Hashing>>::new(&mut db, &mut root).build();
+		for (key, value) in items {
+			if let Some(value) = value {
+				trie.insert(key, value).expect("Inserts into the in-memory trie");
+			}
+		}
+	}
+
+	let mut proof = db.drain().into_values().map(|(node, _)| node).collect::<Vec<_>>();
+	// nodes, that are not needed by the verification, are still decoded into the database
+	let mut junk = 0u32;
+	while proof.len() < n as usize {
+		proof.push(junk.encode().repeat(16));
+		junk += 1;
+	}
+
+	let header = HeaderFor::<T>::new(
+		Default::default(),
+		Default::default(),
+		root,
+		Default::default(),
+		Default::default(),
+	);
+	(header.encode(), proof)
+}
+
+fn items(i: u32) -> Vec<(Vec<u8>, Option<Vec<u8>>)> {
+	(0..i).map(|i| (i.encode(), Some(vec![i as u8; 64]))).collect()
+}
+
+benchmarks! {
+	record_checkpoint {
+		let (head_data, _) = solo_chain_state::<T>(0, &[]);
+	}: _(RawOrigin::Root, head_data)
+	verify {
+		assert!(Checkpoint::<T>::get().is_some());
+	}
+
+	verify_checkpoint {
+		let i in 1 .. 100;
+		let n in 256 .. 2_048;
+		let items = items(i);
+		let (head_data, proof) = solo_chain_state::<T>(n, &items);
+		Pallet::<T>::record_checkpoint(RawOrigin::Root.into(), head_data)?;
+	}: _(RawOrigin::Root, proof, items)
+}
@@ -21,7 +21,23 @@ use frame_support::pallet_prelude::*;
 use frame_system::pallet_prelude::*;
 pub use pallet::*;
 use polkadot_primitives::PersistedValidationData;
+use sp_runtime::traits::{Hash, Header as HeaderT};
 use sp_std::vec::Vec;
+use sp_trie::{read_trie_value, LayoutV1, StorageProof};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+/// The checkpoint of the solo chain, that is migrated to the parachain.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MigrationCheckpoint<Hash> {
+	/// The hash of the final head data of the solo chain.
+	pub head_hash: Hash,
+	/// The state root of the final head of the solo chain, i.e. the root of the exported state.
+	pub state_root: Hash,
+}
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -32,6 +48,9 @@ pub mod pallet {
 		frame_system::Config + parachain_system::Config + pallet_sudo::Config
 	{
 		type RuntimeEvent: From<Event> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
@@ -44,6 +63,14 @@ pub mod pallet {
 	pub(super) type PendingCustomValidationHeadData<T: Config> =
 		StorageValue<_, Vec<u8>, OptionQuery>;
 
+	/// The checkpoint of the solo chain, that is checked against the head data of the migration.
+	///
+	/// It is kept after the migration, so that the continuity can be verified later on.
+	#[pallet::storage]
+	#[pallet::getter(fn checkpoint)]
+	pub(super) type Checkpoint<T: Config> =
+		StorageValue<_, MigrationCheckpoint<T::Hash>, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event {
@@ -52,12 +79,26 @@ pub mod pallet {
 		/// The custom validation head data was applied as of the contained relay chain block
 		/// number.
 		CustomValidationHeadDataApplied,
+		/// The checkpoint of the solo chain has been recorded.
+		CheckpointRecorded,
+		/// The items have been proven to be part of the exported state of the recorded checkpoint.
+		CheckpointVerified,
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		/// CustomHeadData is not stored in storage.
 		NoCustomHeadData,
+		/// No checkpoint has been recorded.
+		NoCheckpoint,
+		/// The head data doesn't match the recorded checkpoint.
+		HeadDataMismatch,
+		/// The head data is not an encoded header.
+		InvalidHeadData,
+		/// The storage proof doesn't prove the items against the state root of the checkpoint.
+		InvalidProof,
+		/// The exported state doesn't match the recorded checkpoint.
+		StateMismatch,
 	}
 
 	#[pallet::call]
@@ -71,10 +112,69 @@ pub mod pallet {
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			if let Some(checkpoint) = Checkpoint::<T>::get() {
+				ensure!(
+					T::Hashing::hash(&head_data) == checkpoint.head_hash,
+					Error::<T>::HeadDataMismatch
+				);
+			}
+
 			parachain_system::Pallet::<T>::schedule_code_upgrade(code)?;
 			Self::store_pending_custom_validation_head_data(head_data);
 			Ok(())
 		}
+
+		/// Record the checkpoint of the solo chain, i.e. the hash of its final head data and the
+		/// state root of the final head.
+		///
+		/// The `head_data` is the encoded final header of the solo chain. Once recorded, only this
+		/// head data can be scheduled by the `schedule_migration`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::record_checkpoint())]
+		pub fn record_checkpoint(origin: OriginFor<T>, head_data: Vec<u8>) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let header = HeaderFor::<T>::decode(&mut &head_data[..])
+				.map_err(|_| Error::<T>::InvalidHeadData)?;
+			Checkpoint::<T>::put(MigrationCheckpoint {
+				head_hash: T::Hashing::hash(&head_data),
+				state_root: *header.state_root(),
+			});
+			Self::deposit_event(Event::CheckpointRecorded);
+			Ok(())
+		}
+
+		/// Verify that the `items` have been exported from the solo chain.
+		///
+		/// The `proof` is checked against the state root of the recorded checkpoint. An item
+		/// with the value `None` is proven to be absent from the exported state. This allows
+		/// anyone to prove on-chain, that the parachain continues the solo chain.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::verify_checkpoint(proof.len() as u32, items.len() as u32))]
+		pub fn verify_checkpoint(
+			origin: OriginFor<T>,
+			proof: Vec<Vec<u8>>,
+			items: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+		) -> DispatchResult {
+			ensure_signed_or_root(origin)?;
+
+			let checkpoint = Checkpoint::<T>::get().ok_or(Error::<T>::NoCheckpoint)?;
+			let db = StorageProof::new(proof).into_memory_db::<T::Hashing>();
+			for (key, expected_value) in &items {
+				let value = read_trie_value::<LayoutV1<T::Hashing>, _>(
+					&db,
+					&checkpoint.state_root,
+					key,
+					None,
+					None,
+				)
+				.map_err(|_| Error::<T>::InvalidProof)?;
+				ensure!(value == *expected_value, Error::<T>::StateMismatch);
+			}
+
+			Self::deposit_event(Event::CheckpointVerified);
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -0,0 +1,79 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for the extrinsics of the solo to para migration.
+//!
+//! The weights are not generated by the benchmark CLI yet. They are rough estimations of the
+//! worst case of the `record_checkpoint` and `verify_checkpoint` benchmarks. The cost of
+//! `verify_checkpoint` is dominated by hashing and inserting each of the `n` proof nodes into the
+//! in-memory database, and by the trie lookup of each of the `i` items.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for cumulus_pallet_solo_to_para.
+pub trait WeightInfo {
+	/// Weight of recording the checkpoint of the solo chain.
+	fn record_checkpoint() -> Weight;
+	/// Weight of verifying `i` items with a storage proof of `n` trie nodes against the
+	/// recorded checkpoint.
+	fn verify_checkpoint(n: u32, i: u32) -> Weight;
+}
+
+/// Weights for cumulus_pallet_solo_to_para using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: SoloToPara Checkpoint (r:0 w:1)
+	fn record_checkpoint() -> Weight {
+		Weight::from_parts(12_000_000, 0).saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: SoloToPara Checkpoint (r:1 w:0)
+	///
+	/// The range of component `n` is `[256, 2048]`.
+	///
+	/// The range of component `i` is `[1, 100]`.
+	fn verify_checkpoint(n: u32, i: u32) -> Weight {
+		Weight::from_parts(20_000_000, 1_564)
+			.saturating_add(Weight::from_parts(4_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(i.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: SoloToPara Checkpoint (r:0 w:1)
+	fn record_checkpoint() -> Weight {
+		Weight::from_parts(12_000_000, 0).saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: SoloToPara Checkpoint (r:1 w:0)
+	///
+	/// The range of component `n` is `[256, 2048]`.
+	///
+	/// The range of component `i` is `[1, 100]`.
+	fn verify_checkpoint(n: u32, i: u32) -> Weight {
+		Weight::from_parts(20_000_000, 1_564)
+			.saturating_add(Weight::from_parts(4_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(i.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+}
@@ -20,9 +20,56 @@ use crate::*;
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
 use frame_system::RawOrigin;
 
+/// Returns concatenated XCMs that fill exactly `pages` pages of the inbound XCMP message.
+fn messages_filling_pages<T: Config>(pages: u32) -> Vec<u8> {
+	let instruction = Instruction::<T::RuntimeCall>::SetTopic([0u8; 32]);
+	let message = VersionedXcm::<T::RuntimeCall>::from(Xcm(vec![instruction])).encode();
+	let messages_per_page = (T::MaxInboundPageSize::get() as usize / message.len()).max(1);
+	message.repeat(messages_per_page * pages as usize)
+}
+
 benchmarks! {
 	set_config_with_u32 {}: update_resume_threshold(RawOrigin::Root, 100)
 	set_config_with_weight {}: update_weight_restrict_decay(RawOrigin::Root, Weight::from_parts(3_000_000, 0))
+
+	enqueue_pages {
+		let p in 1 .. 16;
+		let sender = ParaId::from(2000);
+		let data = messages_filling_pages::<T>(p);
+	}: {
+		Pallet::<T>::enqueue_pages(sender, 1, XcmpMessageFormat::ConcatenatedVersionedXcm, &data);
+	} verify {
+		assert_eq!(InboundXcmpPageRange::<T>::get(sender, 1), Some((0, p)));
+	}
+
+	merge_pages {
+		let sender = ParaId::from(2000);
+		let data = messages_filling_pages::<T>(1);
+		let (remaining, next) = data.split_at(data.len() / 2);
+		InboundXcmpPages::<T>::insert((sender, 1, 1), InboundPage::<T>::truncate_from(next.to_vec()));
+	}: {
+		Pallet::<T>::merge_pages(sender, 1, 0, remaining.to_vec());
+	} verify {
+		assert_eq!(InboundXcmpPages::<T>::get((sender, 1, 1)).map(|page| page.into_inner()), Some(data));
+	}
+
+	service_deferred_queue {
+		let m in 1 .. MAX_DEFERRED_MESSAGES_PER_CHANNEL;
+		let sender = ParaId::from(2000);
+		// the XCMs can't be decoded, so the messages are serviced without being executed
+		let queue = (0..m as DeferredIndex)
+			.map(|index| DeferredMessage { index, sent_at: 0, deferred_to: 0, xcm: vec![] })
+			.collect::<Vec<_>>();
+		DeferredQueue::<T>::insert(sender, queue);
+		let mut messages_processed = 0;
+	}: {
+		Pallet::<T>::service_deferred_queues(Weight::MAX, Weight::MAX, &mut messages_processed);
+	} verify {
+		assert_eq!(
+			DeferredQueue::<T>::get(sender).len() as u32,
+			m.saturating_sub(MAX_MESSAGES_PER_BLOCK as u32),
+		);
+	}
 }
 
 impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
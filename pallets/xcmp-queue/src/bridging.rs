@@ -0,0 +1,83 @@
+// Copyright Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Adapters that let bridge components observe the state of XCMP channels.
+
+use crate::{pallet, InboundState, OutboundState};
+use cumulus_primitives_core::ParaId;
+use frame_support::traits::Get;
+
+/// Maximal number of pages that may be queued in the outbound channel with the sibling bridge
+/// hub before we consider the channel congested.
+pub const MAX_QUEUED_PAGES_BEFORE_CONGESTION: u16 = 4;
+
+/// Adapter implementation for `bp_xcm_bridge_hub_router::XcmChannelStatusProvider`, which checks
+/// both `OutboundXcmpStatus` and `InboundXcmpStatus` of the channels with the given sibling.
+///
+/// The channel is considered congested if any of them is suspended.
+pub struct InAndOutXcmpChannelStatusProvider<SiblingBridgeHubParaId, Runtime>(
+	sp_std::marker::PhantomData<(SiblingBridgeHubParaId, Runtime)>,
+);
+
+impl<SiblingBridgeHubParaId: Get<ParaId>, Runtime: crate::Config>
+	bp_xcm_bridge_hub_router::XcmChannelStatusProvider
+	for InAndOutXcmpChannelStatusProvider<SiblingBridgeHubParaId, Runtime>
+{
+	fn is_congested() -> bool {
+		// if the inbound channel with the bridge hub is suspended, we are unable to receive
+		// congestion reports from it, so we assume that the bridge is congested too
+		let is_inbound_suspended = pallet::InboundXcmpStatus::<Runtime>::get().iter().any(|c| {
+			c.sender == SiblingBridgeHubParaId::get() && c.state == InboundState::Suspended
+		});
+		if is_inbound_suspended {
+			return true
+		}
+
+		OutXcmpChannelStatusProvider::<SiblingBridgeHubParaId, Runtime>::is_congested()
+	}
+}
+
+/// Adapter implementation for `bp_xcm_bridge_hub_router::XcmChannelStatusProvider`, which only
+/// checks `OutboundXcmpStatus` of the channel with the given sibling.
+///
+/// The channel is considered congested if it is suspended or if there are more than
+/// [`MAX_QUEUED_PAGES_BEFORE_CONGESTION`] pages queued.
+pub struct OutXcmpChannelStatusProvider<SiblingBridgeHubParaId, Runtime>(
+	sp_std::marker::PhantomData<(SiblingBridgeHubParaId, Runtime)>,
+);
+
+impl<SiblingBridgeHubParaId: Get<ParaId>, Runtime: crate::Config>
+	bp_xcm_bridge_hub_router::XcmChannelStatusProvider
+	for OutXcmpChannelStatusProvider<SiblingBridgeHubParaId, Runtime>
+{
+	fn is_congested() -> bool {
+		let sibling_bridge_hub_id = SiblingBridgeHubParaId::get();
+		let outbound_status = pallet::OutboundXcmpStatus::<Runtime>::get();
+		let Some(channel) = outbound_status.iter().find(|c| c.recipient == sibling_bridge_hub_id)
+		else {
+			return false
+		};
+
+		// suspended channel => it is congested
+		if channel.state == OutboundState::Suspended {
+			return true
+		}
+
+		// it takes some time for the bridge hub to suspend the channel, so let's also consider
+		// it congested if there are too many queued pages
+		channel.last_index.saturating_sub(channel.first_index) > MAX_QUEUED_PAGES_BEFORE_CONGESTION
+	}
+}
@@ -22,9 +22,14 @@
 //! Also provides an implementation of `SendXcm` which can be placed in a router tuple for relaying
 //! XCM over XCMP if the destination is `Parent/Parachain`. It requires an implementation of
 //! `XcmExecutor` for dispatching incoming XCM messages.
+//!
+//! Inbound messages may instead be queued and executed by `pallet-message-queue`, using the
+//! [`EnqueueXcmpMessages`] and [`ProcessXcmpMessage`] adapters.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod bridging;
+mod message_queue;
 pub mod migration;
 
 #[cfg(test)]
@@ -44,8 +49,9 @@ use cumulus_primitives_core::{
 	ParaId, XcmpMessageFormat, XcmpMessageHandler, XcmpMessageSource,
 };
 use frame_support::{
-	traits::{EnsureOrigin, Get},
+	traits::{Contains, EnsureOrigin, Get},
 	weights::{constants::WEIGHT_REF_TIME_PER_MILLIS, Weight},
+	BoundedVec,
 };
 use polkadot_runtime_common::xcm_sender::PriceForParachainDelivery;
 use rand_chacha::{
@@ -53,16 +59,26 @@ use rand_chacha::{
 	ChaChaRng,
 };
 use scale_info::TypeInfo;
-use sp_runtime::RuntimeDebug;
-use sp_std::{convert::TryFrom, prelude::*};
+use sp_runtime::{traits::BlockNumberProvider, RuntimeDebug};
+use sp_std::{convert::TryFrom, marker::PhantomData, prelude::*};
 use xcm::{latest::prelude::*, VersionedXcm, WrapVersion, MAX_XCM_DECODE_DEPTH};
 use xcm_executor::traits::ConvertOrigin;
 
+pub use message_queue::{EnqueueXcmpMessages, ProcessXcmpMessage};
 pub use pallet::*;
 
 /// Index used to identify overweight XCMs.
 pub type OverweightIndex = u64;
 
+/// Index used to identify deferred XCMs.
+pub type DeferredIndex = u64;
+
+/// Index used to identify pages of the inbound XCMP message.
+pub type PageIndex = u32;
+
+/// Page of the inbound XCMP message.
+pub type InboundPage<T> = BoundedVec<u8, <T as Config>::MaxInboundPageSize>;
+
 const LOG_TARGET: &str = "xcmp_queue";
 const DEFAULT_POV_SIZE: u64 = 64 * 1024; // 64 KB
 
@@ -71,6 +87,9 @@ const DEFAULT_POV_SIZE: u64 = 64 * 1024; // 64 KB
 const MAX_MESSAGES_PER_BLOCK: u8 = 10;
 // Maximum amount of messages that can exist in the overweight queue at any given time.
 const MAX_OVERWEIGHT_MESSAGES: u32 = 1000;
+// Maximum amount of messages from a single sibling that can exist in the deferred queue at any
+// given time.
+const MAX_DEFERRED_MESSAGES_PER_CHANNEL: u32 = 100;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -109,6 +128,27 @@ pub mod pallet {
 		/// The price for delivering an XCM to a sibling parachain destination.
 		type PriceForSiblingDelivery: PriceForParachainDelivery;
 
+		/// Decides whether inbound XCMs must be deferred for later execution.
+		type XcmDeferFilter: XcmDeferFilter<Self::RuntimeCall>;
+
+		/// Priority of the outbound XCMP channels. Channels with higher priority are serviced
+		/// first when there's not enough relay block bandwidth to service all channels.
+		type OutboundChannelPriority: OutboundChannelPriority;
+
+		/// Limits inbound XCMs that may be executed. It is invoked right before executing every
+		/// inbound XCM.
+		type XcmRateLimiter: XcmRateLimiter<Self::RuntimeCall>;
+
+		/// Provider of the current relay chain block number. It is used to decide when deferred
+		/// XCMs may be executed.
+		type RelayChainBlockNumberProvider: BlockNumberProvider<BlockNumber = RelayBlockNumber>;
+
+		/// The maximal size of the page of the inbound XCMP message. Inbound messages are split
+		/// into pages of at most this size, so only a single page is decoded at once. Individual
+		/// XCMs that don't fit into a single page are dropped.
+		#[pallet::constant]
+		type MaxInboundPageSize: Get<u32>;
+
 		/// The weight information of this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -274,6 +314,176 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Executes a single deferred XCM, even if its deferral period has not yet ended. The
+		/// `XcmRateLimiter` is not consulted.
+		///
+		/// - `origin`: Must pass `ControllerOrigin`.
+		/// - `sender`: The sibling parachain that has sent the deferred XCM.
+		/// - `index`: The index of the deferred XCM to execute.
+		/// - `weight_limit`: The amount of weight that XCM execution may take.
+		///
+		/// Errors:
+		/// - `BadDeferredIndex`: XCM under `index` is not found in the `DeferredQueue` of `sender`.
+		/// - `BadXcm`: XCM under `index` cannot be properly decoded into a valid XCM format.
+		/// - `WeightOverLimit`: XCM execution may use greater `weight_limit`.
+		///
+		/// Events:
+		/// - `DeferredServiced`: On success.
+		#[pallet::call_index(9)]
+		#[pallet::weight((
+			weight_limit.saturating_add(
+				T::WeightInfo::service_deferred_queue(MAX_DEFERRED_MESSAGES_PER_CHANNEL),
+			),
+			DispatchClass::Operational,
+		))]
+		pub fn service_deferred(
+			origin: OriginFor<T>,
+			sender: ParaId,
+			index: DeferredIndex,
+			weight_limit: Weight,
+		) -> DispatchResultWithPostInfo {
+			T::ControllerOrigin::ensure_origin(origin)?;
+
+			let mut queue = DeferredQueue::<T>::get(sender);
+			let queue_weight = T::WeightInfo::service_deferred_queue(queue.len() as u32);
+			let position = queue
+				.iter()
+				.position(|message| message.index == index)
+				.ok_or(Error::<T>::BadDeferredIndex)?;
+			let message = &queue[position];
+			let xcm = VersionedXcm::<T::RuntimeCall>::decode_all_with_depth_limit(
+				MAX_XCM_DECODE_DEPTH,
+				&mut message.xcm.as_slice(),
+			)
+			.map_err(|_| Error::<T>::BadXcm)?;
+			let used = Self::handle_xcm_message(sender, message.sent_at, xcm, weight_limit)
+				.map_err(|_| Error::<T>::WeightOverLimit)?;
+			queue.remove(position);
+			Self::put_deferred_queue(sender, queue);
+			Self::deposit_event(Event::DeferredServiced { sender, index, used });
+			Ok(Some(used.saturating_add(queue_weight)).into())
+		}
+
+		/// Discards a single deferred XCM without executing it.
+		///
+		/// - `origin`: Must pass `ControllerOrigin`.
+		/// - `sender`: The sibling parachain that has sent the deferred XCM.
+		/// - `index`: The index of the deferred XCM to discard.
+		///
+		/// Errors:
+		/// - `BadDeferredIndex`: XCM under `index` is not found in the `DeferredQueue` of `sender`.
+		///
+		/// Events:
+		/// - `DeferredDiscarded`: On success.
+		#[pallet::call_index(10)]
+		#[pallet::weight((
+			T::WeightInfo::service_deferred_queue(MAX_DEFERRED_MESSAGES_PER_CHANNEL),
+			DispatchClass::Operational,
+		))]
+		pub fn discard_deferred(
+			origin: OriginFor<T>,
+			sender: ParaId,
+			index: DeferredIndex,
+		) -> DispatchResult {
+			T::ControllerOrigin::ensure_origin(origin)?;
+
+			let mut queue = DeferredQueue::<T>::get(sender);
+			let position = queue
+				.iter()
+				.position(|message| message.index == index)
+				.ok_or(Error::<T>::BadDeferredIndex)?;
+			queue.remove(position);
+			Self::put_deferred_queue(sender, queue);
+			Self::deposit_event(Event::DeferredDiscarded { sender, index });
+
+			Ok(())
+		}
+
+		/// Suspends execution of the XCM messages, received from the given sibling parachain.
+		///
+		/// Messages from the sibling are still accepted and queued, but they're not executed until
+		/// the channel is resumed with `resume_channel_with`. The `Suspend` signal is sent to the
+		/// sibling, so it should stop sending messages over the channel.
+		///
+		/// - `origin`: Must pass `ControllerOrigin`.
+		/// - `sibling`: The sibling parachain, whose inbound channel is suspended.
+		#[pallet::call_index(11)]
+		#[pallet::weight((T::DbWeight::get().reads_writes(5, 4), DispatchClass::Operational,))]
+		pub fn suspend_channel_with(origin: OriginFor<T>, sibling: ParaId) -> DispatchResult {
+			T::ControllerOrigin::ensure_origin(origin)?;
+
+			let mut suspended = SuspendedInboundChannels::<T>::get();
+			let position =
+				suspended.binary_search(&sibling).err().ok_or(Error::<T>::AlreadySuspended)?;
+			suspended.insert(position, sibling);
+			SuspendedInboundChannels::<T>::put(suspended);
+
+			// the signal may have already been sent if the channel is suspended because of
+			// too many queued messages
+			let mut status = InboundXcmpStatus::<T>::get();
+			match status.binary_search_by_key(&sibling, |item| item.sender) {
+				Ok(index) =>
+					if status[index].state == InboundState::Ok {
+						Self::send_signal(sibling, ChannelSignal::Suspend)
+							.map_err(|_| Error::<T>::FailedToSend)?;
+						status[index].state = InboundState::Suspended;
+						InboundXcmpStatus::<T>::put(status);
+					},
+				Err(_) =>
+					if !Self::is_overloaded(sibling) {
+						Self::send_signal(sibling, ChannelSignal::Suspend)
+							.map_err(|_| Error::<T>::FailedToSend)?
+					},
+			}
+
+			Self::deposit_event(Event::ChannelSuspended { sibling });
+
+			Ok(())
+		}
+
+		/// Resumes execution of the XCM messages, received from the given sibling parachain.
+		///
+		/// The `Resume` signal is sent to the sibling, unless the channel is still overloaded
+		/// with queued messages. In that case, the signal is sent when the queue is drained.
+		///
+		/// - `origin`: Must pass `ControllerOrigin`.
+		/// - `sibling`: The sibling parachain, whose inbound channel has been suspended with
+		///   `suspend_channel_with`.
+		#[pallet::call_index(12)]
+		#[pallet::weight((T::DbWeight::get().reads_writes(6, 4), DispatchClass::Operational,))]
+		pub fn resume_channel_with(origin: OriginFor<T>, sibling: ParaId) -> DispatchResult {
+			T::ControllerOrigin::ensure_origin(origin)?;
+
+			let mut suspended = SuspendedInboundChannels::<T>::get();
+			let position =
+				suspended.binary_search(&sibling).map_err(|_| Error::<T>::NotSuspended)?;
+			suspended.remove(position);
+			SuspendedInboundChannels::<T>::put(suspended);
+
+			let resume_threshold = QueueConfig::<T>::get().resume_threshold;
+			let mut status = InboundXcmpStatus::<T>::get();
+			match status.binary_search_by_key(&sibling, |item| item.sender) {
+				Ok(index) => {
+					let details = &mut status[index];
+					if details.message_metadata.len() as u32 <= resume_threshold {
+						Self::send_signal(sibling, ChannelSignal::Resume)
+							.map_err(|_| Error::<T>::FailedToSend)?;
+						details.state = InboundState::Ok;
+						InboundXcmpStatus::<T>::put(status);
+					}
+				},
+				Err(_) =>
+					if !Self::is_overloaded(sibling) {
+						Self::send_signal(sibling, ChannelSignal::Resume)
+							.map_err(|_| Error::<T>::FailedToSend)?
+					},
+			}
+
+			Self::deposit_event(Event::ChannelResumed { sibling });
+
+			Ok(())
+		}
 	}
 
 	#[pallet::event]
@@ -298,6 +508,36 @@ pub mod pallet {
 		},
 		/// An XCM from the overweight queue was executed with the given actual weight used.
 		OverweightServiced { index: OverweightIndex, used: Weight },
+		/// An XCM has been deferred until the given relay chain block.
+		XcmDeferred {
+			sender: ParaId,
+			sent_at: RelayBlockNumber,
+			index: DeferredIndex,
+			deferred_to: RelayBlockNumber,
+		},
+		/// A deferred XCM was executed with the given actual weight used.
+		DeferredServiced { sender: ParaId, index: DeferredIndex, used: Weight },
+		/// A deferred XCM was discarded.
+		DeferredDiscarded { sender: ParaId, index: DeferredIndex },
+		/// An XCM has been rate limited. It is either deferred until the given relay chain
+		/// block, or dropped.
+		XcmRateLimited {
+			sender: ParaId,
+			sent_at: RelayBlockNumber,
+			message_hash: XcmHash,
+			deferred_to: Option<RelayBlockNumber>,
+		},
+		/// Execution of the XCMs from the sibling parachain has been suspended.
+		ChannelSuspended { sibling: ParaId },
+		/// Execution of the XCMs from the sibling parachain has been resumed.
+		ChannelResumed { sibling: ParaId },
+		/// An inbound XCMP message has been dropped, because it doesn't fit into a single page.
+		MessageTooLarge {
+			sender: ParaId,
+			sent_at: RelayBlockNumber,
+			message_hash: XcmHash,
+			size: u32,
+		},
 	}
 
 	#[pallet::error]
@@ -312,6 +552,12 @@ pub mod pallet {
 		BadOverweightIndex,
 		/// Provided weight is possibly not enough to execute the message.
 		WeightOverLimit,
+		/// Bad deferred index.
+		BadDeferredIndex,
+		/// The inbound channel with the sibling is already suspended.
+		AlreadySuspended,
+		/// The inbound channel with the sibling is not suspended.
+		NotSuspended,
 	}
 
 	/// Status of the inbound XCMP channels.
@@ -320,6 +566,10 @@ pub mod pallet {
 		StorageValue<_, Vec<InboundChannelDetails>, ValueQuery>;
 
 	/// Inbound aggregate XCMP messages. It can only be one per ParaId/block.
+	///
+	/// This is the legacy storage, which is only used by messages that have been received before
+	/// the paged storage has been introduced. Such messages are lazily split into pages when they
+	/// are serviced.
 	#[pallet::storage]
 	pub(super) type InboundXcmpMessages<T: Config> = StorageDoubleMap<
 		_,
@@ -331,6 +581,35 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Pages of the inbound aggregate XCMP messages.
+	///
+	/// Every page contains only complete messages of the format, recorded in the channel
+	/// metadata, so it may be decoded independently of other pages.
+	#[pallet::storage]
+	pub(super) type InboundXcmpPages<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, ParaId>,
+			NMapKey<Twox64Concat, RelayBlockNumber>,
+			NMapKey<Twox64Concat, PageIndex>,
+		),
+		InboundPage<T>,
+		OptionQuery,
+	>;
+
+	/// The range (`begin..end`) of pages of the inbound aggregate XCMP message, that are still
+	/// waiting to be serviced.
+	#[pallet::storage]
+	pub(super) type InboundXcmpPageRange<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ParaId,
+		Twox64Concat,
+		RelayBlockNumber,
+		(PageIndex, PageIndex),
+		OptionQuery,
+	>;
+
 	/// The non-empty XCMP channels in order of becoming non-empty, and the index of the first
 	/// and last outbound message. If the two indices are equal, then it indicates an empty
 	/// queue and there must be a non-`Ok` `OutboundStatus`. We assume queues grow no greater
@@ -372,6 +651,244 @@ pub mod pallet {
 	/// Whether or not the XCMP queue is suspended from executing incoming XCMs or not.
 	#[pallet::storage]
 	pub(super) type QueueSuspended<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The siblings, whose inbound channels have been suspended by the `ControllerOrigin`.
+	///
+	/// Messages from these siblings are queued, but not executed. The vector is sorted.
+	#[pallet::storage]
+	pub(super) type SuspendedInboundChannels<T: Config> = StorageValue<_, Vec<ParaId>, ValueQuery>;
+
+	/// The siblings, which have been asked to suspend their channels, because too many of their
+	/// messages are queued in the message queue (see [`crate::EnqueueXcmpMessages`]).
+	///
+	/// The vector is sorted.
+	#[pallet::storage]
+	pub(super) type OverloadedInboundQueues<T: Config> = StorageValue<_, Vec<ParaId>, ValueQuery>;
+
+	/// The messages that have been deferred by the `XcmDeferFilter`, in order of deferral.
+	///
+	/// These messages stay in this storage map until their deferral period ends, or until they
+	/// are manually executed via `service_deferred` or discarded via `discard_deferred`.
+	#[pallet::storage]
+	pub(super) type DeferredQueue<T: Config> =
+		StorageMap<_, Blake2_128Concat, ParaId, Vec<DeferredMessage>, ValueQuery>;
+
+	/// The number of messages ever recorded in `DeferredQueue`. Also doubles as the next
+	/// available free deferred index.
+	#[pallet::storage]
+	pub(super) type DeferredCount<T: Config> = StorageValue<_, DeferredIndex, ValueQuery>;
+
+	/// The index of the current rate limit epoch and the amount of the rate limited asset that has
+	/// been received from the sibling during that epoch.
+	///
+	/// Used by the `AssetValueRateLimiter`.
+	#[pallet::storage]
+	pub(super) type InboundRateLimits<T: Config> =
+		StorageMap<_, Blake2_128Concat, ParaId, (RelayBlockNumber, u128), ValueQuery>;
+}
+
+/// Decides whether an inbound XCM must be deferred for later execution.
+pub trait XcmDeferFilter<RuntimeCall> {
+	/// Returns the weight spent on the decision and the number of relay chain blocks (counting
+	/// from the block where the message has been sent) that the message must be deferred for.
+	///
+	/// `None` means that the message is not deferred.
+	fn deferred_by(
+		para: ParaId,
+		sent_at: RelayBlockNumber,
+		xcm: &VersionedXcm<RuntimeCall>,
+	) -> (Weight, Option<RelayBlockNumber>);
+}
+
+impl<RuntimeCall> XcmDeferFilter<RuntimeCall> for () {
+	fn deferred_by(
+		_: ParaId,
+		_: RelayBlockNumber,
+		_: &VersionedXcm<RuntimeCall>,
+	) -> (Weight, Option<RelayBlockNumber>) {
+		(Weight::zero(), None)
+	}
+}
+
+/// Defers all messages from the `Siblings` by `Period` relay chain blocks.
+pub struct DeferSiblings<Siblings, Period>(PhantomData<(Siblings, Period)>);
+
+impl<RuntimeCall, Siblings: Contains<ParaId>, Period: Get<RelayBlockNumber>>
+	XcmDeferFilter<RuntimeCall> for DeferSiblings<Siblings, Period>
+{
+	fn deferred_by(
+		para: ParaId,
+		_: RelayBlockNumber,
+		_: &VersionedXcm<RuntimeCall>,
+	) -> (Weight, Option<RelayBlockNumber>) {
+		(Weight::zero(), Siblings::contains(&para).then(Period::get))
+	}
+}
+
+/// Priority of the outbound XCMP channel.
+pub trait OutboundChannelPriority {
+	/// Returns priority of the channel with the `recipient`. Channels with higher priority are
+	/// serviced first.
+	fn priority(recipient: ParaId) -> u8;
+}
+
+impl OutboundChannelPriority for () {
+	fn priority(_: ParaId) -> u8 {
+		0
+	}
+}
+
+/// Gives priority `1` to channels with the `Recipients` and `0` to all other channels.
+pub struct PrioritizeRecipients<Recipients>(PhantomData<Recipients>);
+
+impl<Recipients: Contains<ParaId>> OutboundChannelPriority for PrioritizeRecipients<Recipients> {
+	fn priority(recipient: ParaId) -> u8 {
+		Recipients::contains(&recipient).into()
+	}
+}
+
+/// What to do with the inbound XCM that is about to be executed.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum RateLimitVerdict<Ticket> {
+	/// The message may be executed. The ticket must be passed to the
+	/// [`XcmRateLimiter::note_executed`] after execution.
+	Execute(Ticket),
+	/// The message must be deferred until the given relay chain block.
+	Defer(RelayBlockNumber),
+	/// The message must be dropped.
+	Drop,
+}
+
+/// Limits inbound XCMs that may be executed.
+pub trait XcmRateLimiter<RuntimeCall> {
+	/// Data that is passed from the `check` to the `note_executed`.
+	type Ticket;
+
+	/// Returns the weight spent on the check and the verdict on the XCM that is about to be
+	/// executed.
+	fn check(
+		sender: ParaId,
+		xcm: &VersionedXcm<RuntimeCall>,
+	) -> (Weight, RateLimitVerdict<Self::Ticket>);
+
+	/// Note that the XCM, that has been allowed by the `check`, has been executed. Returns the
+	/// weight spent.
+	fn note_executed(sender: ParaId, ticket: Self::Ticket) -> Weight;
+}
+
+impl<RuntimeCall> XcmRateLimiter<RuntimeCall> for () {
+	type Ticket = ();
+
+	fn check(_: ParaId, _: &VersionedXcm<RuntimeCall>) -> (Weight, RateLimitVerdict<()>) {
+		(Weight::zero(), RateLimitVerdict::Execute(()))
+	}
+
+	fn note_executed(_: ParaId, _: ()) -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Caps the total amount of the `Asset` that may be received from a single sibling during one
+/// epoch of `EpochLength` relay chain blocks by `MaxAmountPerEpoch`.
+///
+/// Received amount is the sum of all `Asset` amounts in the `ReserveAssetDeposited`,
+/// `ReceiveTeleportedAsset` and `WithdrawAsset` instructions of the message. Messages that
+/// exceed the limit are deferred until the next epoch if `DeferExcess` is true. Otherwise they
+/// are dropped. Messages that exceed the `MaxAmountPerEpoch` on their own are always dropped.
+pub struct AssetValueRateLimiter<T, Asset, MaxAmountPerEpoch, EpochLength, DeferExcess>(
+	PhantomData<(T, Asset, MaxAmountPerEpoch, EpochLength, DeferExcess)>,
+);
+
+impl<T, Asset, MaxAmountPerEpoch, EpochLength, DeferExcess> XcmRateLimiter<T::RuntimeCall>
+	for AssetValueRateLimiter<T, Asset, MaxAmountPerEpoch, EpochLength, DeferExcess>
+where
+	T: Config,
+	Asset: Get<MultiLocation>,
+	MaxAmountPerEpoch: Get<u128>,
+	EpochLength: Get<RelayBlockNumber>,
+	DeferExcess: Get<bool>,
+{
+	/// Epoch index and the received amount.
+	type Ticket = (RelayBlockNumber, u128);
+
+	fn check(
+		sender: ParaId,
+		xcm: &VersionedXcm<T::RuntimeCall>,
+	) -> (Weight, RateLimitVerdict<Self::Ticket>) {
+		let epoch_length = EpochLength::get().max(1);
+		let epoch = T::RelayChainBlockNumberProvider::current_block_number() / epoch_length;
+		let amount = Xcm::<T::RuntimeCall>::try_from(xcm.clone())
+			.map(|xcm| Self::received_amount(&xcm))
+			.unwrap_or(0);
+		if amount == 0 {
+			return (Weight::zero(), RateLimitVerdict::Execute((epoch, 0)))
+		}
+
+		let max_amount = MaxAmountPerEpoch::get();
+		let (stored_epoch, received) = InboundRateLimits::<T>::get(sender);
+		let received = if stored_epoch == epoch { received } else { 0 };
+		let verdict = if received.saturating_add(amount) <= max_amount {
+			RateLimitVerdict::Execute((epoch, amount))
+		} else if amount <= max_amount && DeferExcess::get() {
+			RateLimitVerdict::Defer(epoch.saturating_add(1).saturating_mul(epoch_length))
+		} else {
+			RateLimitVerdict::Drop
+		};
+
+		(T::DbWeight::get().reads(1), verdict)
+	}
+
+	fn note_executed(sender: ParaId, (epoch, amount): Self::Ticket) -> Weight {
+		if amount == 0 {
+			return Weight::zero()
+		}
+
+		InboundRateLimits::<T>::mutate(sender, |(stored_epoch, received)| {
+			if *stored_epoch != epoch {
+				*stored_epoch = epoch;
+				*received = 0;
+			}
+			*received = received.saturating_add(amount);
+		});
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+}
+
+impl<T, Asset: Get<MultiLocation>, MaxAmountPerEpoch, EpochLength, DeferExcess>
+	AssetValueRateLimiter<T, Asset, MaxAmountPerEpoch, EpochLength, DeferExcess>
+{
+	/// Returns the amount of the `Asset` that is received by the message.
+	fn received_amount<RuntimeCall>(xcm: &Xcm<RuntimeCall>) -> u128 {
+		let asset = Asset::get();
+		xcm.0
+			.iter()
+			.flat_map(|instruction| match instruction {
+				ReserveAssetDeposited(assets) |
+				ReceiveTeleportedAsset(assets) |
+				WithdrawAsset(assets) => assets.inner().as_slice(),
+				_ => &[],
+			})
+			.filter_map(|multi_asset| match multi_asset {
+				MultiAsset { id: Concrete(location), fun: Fungible(amount) }
+					if *location == asset =>
+					Some(*amount),
+				_ => None,
+			})
+			.fold(0u128, |total, amount| total.saturating_add(amount))
+	}
+}
+
+/// Inbound XCM that is deferred for later execution.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct DeferredMessage {
+	/// The index of the deferred message.
+	index: DeferredIndex,
+	/// The relay block number that the message was sent at.
+	sent_at: RelayBlockNumber,
+	/// The relay block number starting from which the message may be executed.
+	deferred_to: RelayBlockNumber,
+	/// The encoded `VersionedXcm`.
+	xcm: Vec<u8>,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, TypeInfo)]
@@ -585,6 +1102,39 @@ impl<T: Config> Pallet<T> {
 		Self::send_fragment(recipient, XcmpMessageFormat::ConcatenatedVersionedXcm, xcm)
 	}
 
+	/// Removes all queued outbound messages and returns the XCMs among them, by recipient.
+	///
+	/// Blobs and signals are dropped. Only meant for runtime APIs that simulate the messages sent
+	/// by a call or an XCM program and discard all changes to the state afterwards.
+	pub fn take_outbound_xcms() -> Vec<(ParaId, Vec<VersionedXcm<()>>)> {
+		<OutboundXcmpStatus<T>>::take()
+			.into_iter()
+			.filter_map(|details| {
+				let _ = <SignalMessages<T>>::take(details.recipient);
+				let mut xcms = Vec::new();
+				for page_index in details.first_index..details.last_index {
+					let page = <OutboundXcmpMessages<T>>::take(details.recipient, page_index);
+					let mut data = &page[..];
+					if XcmpMessageFormat::decode_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut data) !=
+						Ok(XcmpMessageFormat::ConcatenatedVersionedXcm)
+					{
+						continue
+					}
+					while !data.is_empty() {
+						match VersionedXcm::<()>::decode_with_depth_limit(
+							MAX_XCM_DECODE_DEPTH,
+							&mut data,
+						) {
+							Ok(xcm) => xcms.push(xcm),
+							Err(_) => break,
+						}
+					}
+				}
+				(!xcms.is_empty()).then_some((details.recipient, xcms))
+			})
+			.collect()
+	}
+
 	fn create_shuffle(len: usize) -> Vec<usize> {
 		// Create a shuffled order for use to iterate through.
 		// Not a great random seed, but good enough for our purposes.
@@ -649,6 +1199,126 @@ impl<T: Config> Pallet<T> {
 		result
 	}
 
+	/// Split the aggregate inbound XCMP message into pages.
+	///
+	/// Messages are packed into pages in order, so multiple small messages are merged into a
+	/// single page. Messages that don't fit into a single page are dropped and the
+	/// `MessageTooLarge` event is deposited for each of them. If the data is malformed, the rest
+	/// of it is put into the last page (unless it is too large), so that it's handled when the
+	/// page is serviced.
+	fn split_into_pages(
+		sender: ParaId,
+		sent_at: RelayBlockNumber,
+		format: XcmpMessageFormat,
+		data: &[u8],
+	) -> Vec<InboundPage<T>> {
+		let max_page_size = T::MaxInboundPageSize::get() as usize;
+		let mut pages = Vec::new();
+		let mut page = Vec::new();
+		let mut remaining_fragments = data;
+		while !remaining_fragments.is_empty() {
+			let last_remaining_fragments = remaining_fragments;
+			let is_valid = match format {
+				XcmpMessageFormat::ConcatenatedVersionedXcm =>
+					VersionedXcm::<T::RuntimeCall>::decode_with_depth_limit(
+						MAX_XCM_DECODE_DEPTH,
+						&mut remaining_fragments,
+					)
+					.is_ok(),
+				XcmpMessageFormat::ConcatenatedEncodedBlob =>
+					<Vec<u8>>::decode(&mut remaining_fragments).is_ok(),
+				XcmpMessageFormat::Signals => false,
+			};
+			if !is_valid {
+				remaining_fragments = &b""[..];
+			}
+
+			let msg_len = last_remaining_fragments.len().saturating_sub(remaining_fragments.len());
+			let encoded_msg = &last_remaining_fragments[..msg_len];
+			if encoded_msg.len() > max_page_size {
+				log::warn!(
+					target: LOG_TARGET,
+					"Dropping inbound XCMP message of {} bytes. It doesn't fit into a single page",
+					encoded_msg.len(),
+				);
+				Self::deposit_event(Event::MessageTooLarge {
+					sender,
+					sent_at,
+					message_hash: sp_io::hashing::blake2_256(encoded_msg),
+					size: encoded_msg.len().min(u32::MAX as usize) as u32,
+				});
+				continue
+			}
+			if page.len() + encoded_msg.len() > max_page_size {
+				pages.push(BoundedVec::truncate_from(sp_std::mem::take(&mut page)));
+			}
+			page.extend_from_slice(encoded_msg);
+		}
+		if !page.is_empty() {
+			pages.push(BoundedVec::truncate_from(page));
+		}
+		pages
+	}
+
+	/// Split the aggregate inbound XCMP message into pages and put them into the storage.
+	fn enqueue_pages(
+		sender: ParaId,
+		sent_at: RelayBlockNumber,
+		format: XcmpMessageFormat,
+		data: &[u8],
+	) -> Weight {
+		let pages = Self::split_into_pages(sender, sent_at, format, data);
+		let weight_used = T::WeightInfo::enqueue_pages(pages.len() as u32);
+		if pages.is_empty() {
+			return weight_used
+		}
+
+		let (begin, mut end) = <InboundXcmpPageRange<T>>::get(sender, sent_at).unwrap_or((0, 0));
+		for page in pages {
+			<InboundXcmpPages<T>>::insert((sender, sent_at, end), page);
+			end = end.saturating_add(1);
+		}
+		<InboundXcmpPageRange<T>>::insert(sender, sent_at, (begin, end));
+
+		weight_used
+	}
+
+	/// Merge the partially serviced page with the next page, if both fit into a single page.
+	///
+	/// Returns the index of the page, that shall be serviced next.
+	fn merge_pages(
+		sender: ParaId,
+		sent_at: RelayBlockNumber,
+		index: PageIndex,
+		mut page: Vec<u8>,
+	) -> PageIndex {
+		let next_index = index.saturating_add(1);
+		let next_page = <InboundXcmpPages<T>>::get((sender, sent_at, next_index));
+		match next_page {
+			Some(next_page)
+				if page.len() + next_page.len() <= T::MaxInboundPageSize::get() as usize =>
+			{
+				page.extend_from_slice(&next_page);
+				<InboundXcmpPages<T>>::remove((sender, sent_at, index));
+				<InboundXcmpPages<T>>::insert(
+					(sender, sent_at, next_index),
+					BoundedVec::truncate_from(page),
+				);
+				next_index
+			},
+			_ => {
+				<InboundXcmpPages<T>>::insert(
+					(sender, sent_at, index),
+					BoundedVec::truncate_from(page),
+				);
+				index
+			},
+		}
+	}
+
+	/// Service pages of the aggregate inbound XCMP message.
+	///
+	/// Returns the weight used and whether all pages have been serviced.
 	fn process_xcmp_message(
 		sender: ParaId,
 		(sent_at, format): (RelayBlockNumber, XcmpMessageFormat),
@@ -656,9 +1326,68 @@ impl<T: Config> Pallet<T> {
 		max_weight: Weight,
 		max_individual_weight: Weight,
 	) -> (Weight, bool) {
-		let data = <InboundXcmpMessages<T>>::get(sender, sent_at);
+		let mut weight_used = T::DbWeight::get().reads(1);
+		let mut range = <InboundXcmpPageRange<T>>::get(sender, sent_at);
+		if range.is_none() {
+			// The message may have been received before the paged storage has been introduced.
+			let data = <InboundXcmpMessages<T>>::take(sender, sent_at);
+			weight_used.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			weight_used.saturating_accrue(Self::enqueue_pages(sender, sent_at, format, &data));
+			range = <InboundXcmpPageRange<T>>::get(sender, sent_at);
+		}
+		let (mut begin, end) = match range {
+			Some(range) => range,
+			None => return (weight_used, true),
+		};
+
+		while begin < end && *messages_processed < MAX_MESSAGES_PER_BLOCK {
+			let page = <InboundXcmpPages<T>>::get((sender, sent_at, begin)).unwrap_or_default();
+			weight_used.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			let (page_weight_used, remaining_fragments) = Self::process_xcmp_page(
+				sender,
+				(sent_at, format),
+				&page,
+				messages_processed,
+				max_weight.saturating_sub(weight_used),
+				max_individual_weight,
+			);
+			weight_used.saturating_accrue(page_weight_used);
+
+			if remaining_fragments.is_empty() {
+				<InboundXcmpPages<T>>::remove((sender, sent_at, begin));
+				begin = begin.saturating_add(1);
+				continue
+			}
+
+			if remaining_fragments.len() != page.len() {
+				weight_used.saturating_accrue(T::WeightInfo::merge_pages());
+				begin = Self::merge_pages(sender, sent_at, begin, remaining_fragments.to_vec());
+			}
+			break
+		}
+
+		let is_empty = begin >= end;
+		if is_empty {
+			<InboundXcmpPageRange<T>>::remove(sender, sent_at);
+		} else {
+			<InboundXcmpPageRange<T>>::insert(sender, sent_at, (begin, end));
+		}
+		(weight_used, is_empty)
+	}
+
+	/// Service the single page of the aggregate inbound XCMP message.
+	///
+	/// Returns the weight used and the part of the page, that hasn't been serviced.
+	fn process_xcmp_page<'a>(
+		sender: ParaId,
+		(sent_at, format): (RelayBlockNumber, XcmpMessageFormat),
+		data: &'a [u8],
+		messages_processed: &mut u8,
+		max_weight: Weight,
+		max_individual_weight: Weight,
+	) -> (Weight, &'a [u8]) {
 		let mut last_remaining_fragments;
-		let mut remaining_fragments = &data[..];
+		let mut remaining_fragments = data;
 		let mut weight_used = Weight::zero();
 		match format {
 			XcmpMessageFormat::ConcatenatedVersionedXcm => {
@@ -670,50 +1399,85 @@ impl<T: Config> Pallet<T> {
 						MAX_XCM_DECODE_DEPTH,
 						&mut remaining_fragments,
 					) {
-						let weight = max_weight - weight_used;
+						let msg_len = last_remaining_fragments
+							.len()
+							.saturating_sub(remaining_fragments.len());
+						let encoded_xcm = &last_remaining_fragments[..msg_len];
 						*messages_processed += 1;
-						match Self::handle_xcm_message(sender, sent_at, xcm, weight) {
-							Ok(used) => weight_used = weight_used.saturating_add(used),
-							Err(XcmError::WeightLimitReached(required))
-								if required.any_gt(max_individual_weight) =>
-							{
-								let is_under_limit =
-									Overweight::<T>::count() < MAX_OVERWEIGHT_MESSAGES;
-								weight_used.saturating_accrue(T::DbWeight::get().reads(1));
-								if is_under_limit {
-									// overweight - add to overweight queue and continue with
-									// message execution consuming the message.
-									let msg_len = last_remaining_fragments
-										.len()
-										.saturating_sub(remaining_fragments.len());
-									let overweight_xcm =
-										last_remaining_fragments[..msg_len].to_vec();
-									let index =
-										Self::stash_overweight(sender, sent_at, overweight_xcm);
-									let e = Event::OverweightEnqueued {
-										sender,
-										sent_at,
-										index,
-										required,
-									};
-									Self::deposit_event(e);
+
+						let (defer_weight, deferred_by) =
+							T::XcmDeferFilter::deferred_by(sender, sent_at, &xcm);
+						weight_used.saturating_accrue(defer_weight);
+						let deferred_to = deferred_by
+							.map(|deferred_by| sent_at.saturating_add(deferred_by))
+							.filter(|deferred_to| {
+								*deferred_to >
+									T::RelayChainBlockNumberProvider::current_block_number()
+							});
+						if let Some(deferred_to) = deferred_to {
+							weight_used.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
+							if Self::defer_message(sender, sent_at, deferred_to, encoded_xcm) {
+								continue
+							}
+							// The deferred queue is full. We leave the message around for next
+							// time and bail.
+							remaining_fragments = last_remaining_fragments;
+							break
+						}
+
+						let (limiter_weight, verdict) = T::XcmRateLimiter::check(sender, &xcm);
+						weight_used.saturating_accrue(limiter_weight);
+						let ticket = match verdict {
+							RateLimitVerdict::Execute(ticket) => ticket,
+							RateLimitVerdict::Defer(deferred_to) => {
+								Self::deposit_rate_limited_event(
+									sender,
+									sent_at,
+									encoded_xcm,
+									Some(deferred_to),
+								);
+								weight_used
+									.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
+								if Self::defer_message(sender, sent_at, deferred_to, encoded_xcm) {
+									continue
 								}
+								// The deferred queue is full. We leave the message around for
+								// next time and bail.
+								remaining_fragments = last_remaining_fragments;
+								break
+							},
+							RateLimitVerdict::Drop => {
+								Self::deposit_rate_limited_event(
+									sender,
+									sent_at,
+									encoded_xcm,
+									None,
+								);
+								continue
 							},
-							Err(XcmError::WeightLimitReached(required))
-								if required.all_lte(max_weight) =>
-							{
+						};
+
+						let weight = max_weight.saturating_sub(weight_used);
+						match Self::execute_or_stash_xcm_message(
+							sender,
+							sent_at,
+							xcm,
+							encoded_xcm,
+							weight,
+							max_individual_weight,
+						) {
+							Some(used) => {
+								weight_used.saturating_accrue(used);
+								weight_used.saturating_accrue(T::XcmRateLimiter::note_executed(
+									sender, ticket,
+								));
+							},
+							None => {
 								// That message didn't get processed this time because of being
 								// too heavy. We leave it around for next time and bail.
 								remaining_fragments = last_remaining_fragments;
 								break
 							},
-							Err(error) => {
-								log::error!(
-									"Failed to process XCMP-XCM message, caused by {:?}",
-									error
-								);
-								// Message looks invalid; don't attempt to retry
-							},
 						}
 					} else {
 						debug_assert!(false, "Invalid incoming XCMP message data");
@@ -751,13 +1515,199 @@ impl<T: Config> Pallet<T> {
 				remaining_fragments = &b""[..];
 			},
 		}
-		let is_empty = remaining_fragments.is_empty();
-		if is_empty {
-			<InboundXcmpMessages<T>>::remove(sender, sent_at);
+		(weight_used, remaining_fragments)
+	}
+
+	/// Executes the XCM, putting it into the list of overweight messages if it requires more than
+	/// `max_individual_weight`.
+	///
+	/// Returns the weight used, or `None` if the message requires more than `max_weight`, but no
+	/// more than `max_individual_weight`. Then it should be retried later.
+	fn execute_or_stash_xcm_message(
+		sender: ParaId,
+		sent_at: RelayBlockNumber,
+		xcm: VersionedXcm<T::RuntimeCall>,
+		encoded_xcm: &[u8],
+		max_weight: Weight,
+		max_individual_weight: Weight,
+	) -> Option<Weight> {
+		match Self::handle_xcm_message(sender, sent_at, xcm, max_weight) {
+			Ok(used) => Some(used),
+			Err(XcmError::WeightLimitReached(required))
+				if required.any_gt(max_individual_weight) =>
+			{
+				let is_under_limit = Overweight::<T>::count() < MAX_OVERWEIGHT_MESSAGES;
+				if is_under_limit {
+					// overweight - add to overweight queue and continue with message execution
+					// consuming the message.
+					let index = Self::stash_overweight(sender, sent_at, encoded_xcm.to_vec());
+					let e = Event::OverweightEnqueued { sender, sent_at, index, required };
+					Self::deposit_event(e);
+				}
+				Some(T::DbWeight::get().reads(1))
+			},
+			Err(XcmError::WeightLimitReached(required)) if required.all_lte(max_weight) => None,
+			Err(error) => {
+				log::error!("Failed to process XCMP-XCM message, caused by {:?}", error);
+				// Message looks invalid; don't attempt to retry
+				Some(Weight::zero())
+			},
+		}
+	}
+
+	/// Puts a given XCM into the list of deferred messages of the `sender`.
+	///
+	/// Returns `false` if the deferred queue of the `sender` is full.
+	fn defer_message(
+		sender: ParaId,
+		sent_at: RelayBlockNumber,
+		deferred_to: RelayBlockNumber,
+		xcm: &[u8],
+	) -> bool {
+		let result = DeferredQueue::<T>::try_mutate(sender, |queue| {
+			if queue.len() as u32 >= MAX_DEFERRED_MESSAGES_PER_CHANNEL {
+				return Err(())
+			}
+
+			let index = DeferredCount::<T>::mutate(|count| {
+				let index = *count;
+				*count += 1;
+				index
+			});
+			queue.push(DeferredMessage { index, sent_at, deferred_to, xcm: xcm.to_vec() });
+			Ok(index)
+		});
+
+		match result {
+			Ok(index) => {
+				Self::deposit_event(Event::XcmDeferred { sender, sent_at, index, deferred_to });
+				true
+			},
+			Err(()) => false,
+		}
+	}
+
+	/// Deposits the `XcmRateLimited` event.
+	fn deposit_rate_limited_event(
+		sender: ParaId,
+		sent_at: RelayBlockNumber,
+		xcm: &[u8],
+		deferred_to: Option<RelayBlockNumber>,
+	) {
+		let message_hash = sp_io::hashing::blake2_256(xcm);
+		Self::deposit_event(Event::XcmRateLimited { sender, sent_at, message_hash, deferred_to });
+	}
+
+	/// Writes deferred queue of the `sender`, removing it if it is empty.
+	fn put_deferred_queue(sender: ParaId, queue: Vec<DeferredMessage>) {
+		if queue.is_empty() {
+			DeferredQueue::<T>::remove(sender);
 		} else {
-			<InboundXcmpMessages<T>>::insert(sender, sent_at, remaining_fragments);
+			DeferredQueue::<T>::insert(sender, queue);
 		}
-		(weight_used, is_empty)
+	}
+
+	/// Service the deferred messages whose deferral period has ended, attempting to execute up
+	/// to `max_weight` execution weight of messages.
+	///
+	/// Messages of every sibling are executed in order of deferral. The deferred queue of a sibling
+	/// is only read if the remaining weight allows to read and write back a full queue.
+	fn service_deferred_queues(
+		max_weight: Weight,
+		max_individual_weight: Weight,
+		messages_processed: &mut u8,
+	) -> Weight {
+		let now = T::RelayChainBlockNumberProvider::current_block_number();
+		let suspended_channels = SuspendedInboundChannels::<T>::get();
+		let max_queue_weight =
+			T::WeightInfo::service_deferred_queue(MAX_DEFERRED_MESSAGES_PER_CHANNEL);
+		// 1 read for `SuspendedInboundChannels` and 1 read for the end of the `DeferredQueue` map
+		let mut weight_used = T::DbWeight::get().reads(2);
+		for sender in DeferredQueue::<T>::iter_keys() {
+			if *messages_processed >= MAX_MESSAGES_PER_BLOCK ||
+				!max_weight.saturating_sub(weight_used).all_gte(max_queue_weight)
+			{
+				break
+			}
+
+			if suspended_channels.binary_search(&sender).is_ok() {
+				weight_used.saturating_accrue(T::DbWeight::get().reads(1));
+				continue
+			}
+
+			let mut queue = DeferredQueue::<T>::get(sender);
+			weight_used
+				.saturating_accrue(T::WeightInfo::service_deferred_queue(queue.len() as u32));
+
+			let mut serviced = 0;
+			let mut is_rate_limited = false;
+			for message in queue.iter_mut() {
+				if message.deferred_to > now || *messages_processed >= MAX_MESSAGES_PER_BLOCK {
+					break
+				}
+
+				*messages_processed += 1;
+				if let Ok(xcm) = VersionedXcm::<T::RuntimeCall>::decode_all_with_depth_limit(
+					MAX_XCM_DECODE_DEPTH,
+					&mut message.xcm.as_slice(),
+				) {
+					let (limiter_weight, verdict) = T::XcmRateLimiter::check(sender, &xcm);
+					weight_used.saturating_accrue(limiter_weight);
+					let ticket = match verdict {
+						RateLimitVerdict::Execute(ticket) => ticket,
+						RateLimitVerdict::Defer(deferred_to) => {
+							// The message is deferred again. Following messages of the sender
+							// are kept after it.
+							Self::deposit_rate_limited_event(
+								sender,
+								message.sent_at,
+								&message.xcm,
+								Some(deferred_to),
+							);
+							message.deferred_to = deferred_to;
+							is_rate_limited = true;
+							break
+						},
+						RateLimitVerdict::Drop => {
+							Self::deposit_rate_limited_event(
+								sender,
+								message.sent_at,
+								&message.xcm,
+								None,
+							);
+							serviced += 1;
+							continue
+						},
+					};
+
+					let weight = max_weight.saturating_sub(weight_used);
+					match Self::execute_or_stash_xcm_message(
+						sender,
+						message.sent_at,
+						xcm,
+						&message.xcm,
+						weight,
+						max_individual_weight,
+					) {
+						Some(used) => {
+							weight_used.saturating_accrue(used);
+							weight_used.saturating_accrue(T::XcmRateLimiter::note_executed(
+								sender, ticket,
+							));
+						},
+						None => break,
+					}
+				}
+				serviced += 1;
+			}
+
+			if serviced != 0 || is_rate_limited {
+				queue.drain(..serviced);
+				Self::put_deferred_queue(sender, queue);
+			}
+		}
+
+		weight_used
 	}
 
 	/// Puts a given XCM into the list of overweight messages, allowing it to be executed later.
@@ -807,11 +1757,6 @@ impl<T: Config> Pallet<T> {
 		let suspended = QueueSuspended::<T>::get();
 		let mut messages_processed = 0;
 
-		let mut status = <InboundXcmpStatus<T>>::get(); // <- sorted.
-		if status.is_empty() {
-			return Weight::zero()
-		}
-
 		let QueueConfigData {
 			resume_threshold,
 			threshold_weight,
@@ -820,8 +1765,25 @@ impl<T: Config> Pallet<T> {
 			..
 		} = <QueueConfig<T>>::get();
 
+		// Deferred messages have been received earlier than any message in the queue, so they
+		// are serviced first.
+		let mut weight_used = if suspended {
+			Weight::zero()
+		} else {
+			Self::service_deferred_queues(
+				max_weight,
+				xcmp_max_individual_weight,
+				&mut messages_processed,
+			)
+		};
+
+		let mut status = <InboundXcmpStatus<T>>::get(); // <- sorted.
+		if status.is_empty() {
+			return weight_used
+		}
+		let suspended_channels = <SuspendedInboundChannels<T>>::get();
+
 		let mut shuffled = Self::create_shuffle(status.len());
-		let mut weight_used = Weight::zero();
 		let mut weight_available = Weight::zero();
 
 		// We don't want the possibility of a chain sending a series of really heavy messages and
@@ -839,14 +1801,10 @@ impl<T: Config> Pallet<T> {
 		{
 			let index = shuffled[shuffle_index];
 			let sender = status[index].sender;
-			let sender_origin = T::ControllerOriginConverter::convert_origin(
-				(Parent, Parachain(sender.into())),
-				OriginKind::Superuser,
-			);
-			let is_controller = sender_origin
-				.map_or(false, |origin| T::ControllerOrigin::try_origin(origin).is_ok());
 
-			if suspended && !is_controller {
+			if (suspended && !Self::is_controller(sender)) ||
+				suspended_channels.binary_search(&sender).is_ok()
+			{
 				shuffle_index += 1;
 				continue
 			}
@@ -917,6 +1875,33 @@ impl<T: Config> Pallet<T> {
 		weight_used
 	}
 
+	/// Returns true if the sibling has been asked to suspend its channel, because too many of its
+	/// messages are queued in the message queue.
+	fn is_overloaded(sibling: ParaId) -> bool {
+		OverloadedInboundQueues::<T>::get().binary_search(&sibling).is_ok()
+	}
+
+	/// Returns true if the `sender` passes the `ControllerOrigin`.
+	fn is_controller(sender: ParaId) -> bool {
+		let sender_origin = T::ControllerOriginConverter::convert_origin(
+			(Parent, Parachain(sender.into())),
+			OriginKind::Superuser,
+		);
+		sender_origin.map_or(false, |origin| T::ControllerOrigin::try_origin(origin).is_ok())
+	}
+
+	/// Handles signals, received from the `sender`.
+	fn handle_signals(sender: ParaId, mut data: &[u8]) {
+		while !data.is_empty() {
+			use ChannelSignal::*;
+			match ChannelSignal::decode(&mut data) {
+				Ok(Suspend) => Self::suspend_channel(sender),
+				Ok(Resume) => Self::resume_channel(sender),
+				Err(_) => break,
+			}
+		}
+	}
+
 	fn suspend_channel(target: ParaId) {
 		<OutboundXcmpStatus<T>>::mutate(|s| {
 			if let Some(details) = s.iter_mut().find(|item| item.recipient == target) {
@@ -955,6 +1940,7 @@ impl<T: Config> XcmpMessageHandler for Pallet<T> {
 		max_weight: Weight,
 	) -> Weight {
 		let mut status = <InboundXcmpStatus<T>>::get();
+		let mut weight_used = Weight::zero();
 
 		let QueueConfigData { suspend_threshold, drop_threshold, .. } = <QueueConfig<T>>::get();
 
@@ -972,14 +1958,7 @@ impl<T: Config> XcmpMessageHandler for Pallet<T> {
 				},
 			};
 			if format == XcmpMessageFormat::Signals {
-				while !data_ref.is_empty() {
-					use ChannelSignal::*;
-					match ChannelSignal::decode(&mut data_ref) {
-						Ok(Suspend) => Self::suspend_channel(sender),
-						Ok(Resume) => Self::resume_channel(sender),
-						Err(_) => break,
-					}
-				}
+				Self::handle_signals(sender, data_ref);
 			} else {
 				// Record the fact we received it.
 				match status.binary_search_by_key(&sender, |item| item.sender) {
@@ -1011,7 +1990,8 @@ impl<T: Config> XcmpMessageHandler for Pallet<T> {
 					}),
 				}
 				// Queue the payload for later execution.
-				<InboundXcmpMessages<T>>::insert(sender, sent_at, data_ref);
+				weight_used
+					.saturating_accrue(Self::enqueue_pages(sender, sent_at, format, data_ref));
 			}
 
 			// Optimization note; it would make sense to execute messages immediately if
@@ -1020,13 +2000,18 @@ impl<T: Config> XcmpMessageHandler for Pallet<T> {
 		status.sort();
 		<InboundXcmpStatus<T>>::put(status);
 
-		Self::service_xcmp_queue(max_weight)
+		weight_used.saturating_add(Self::service_xcmp_queue(max_weight.saturating_sub(weight_used)))
 	}
 }
 
 impl<T: Config> XcmpMessageSource for Pallet<T> {
 	fn take_outbound_messages(maximum_channels: usize) -> Vec<(ParaId, Vec<u8>)> {
 		let mut statuses = <OutboundXcmpStatus<T>>::get();
+		// Channels with higher priority are serviced first. The sort is stable, so channels with
+		// the same priority are still serviced in the round-robin order (see below).
+		statuses.sort_by_cached_key(|status| {
+			sp_std::cmp::Reverse(T::OutboundChannelPriority::priority(status.recipient))
+		});
 		let old_statuses_len = statuses.len();
 		let max_message_count = statuses.len().min(maximum_channels);
 		let mut result = Vec::with_capacity(max_message_count);
@@ -0,0 +1,260 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Adapters, that allow using `pallet-message-queue` as the backing store and processor of the
+//! inbound XCMP messages.
+//!
+//! The [`EnqueueXcmpMessages`] should be used as the `XcmpMessageHandler` of the
+//! `parachain-system` pallet and the [`ProcessXcmpMessage`] should wrap the `MessageProcessor` of
+//! the `pallet-message-queue`. The `pallet-message-queue` should also report queue changes to
+//! this pallet (`QueueChangeHandler = XcmpQueue`), so that suspended channels are resumed once
+//! their queues are drained.
+//!
+//! Queue sizes are measured in pages of `MaxInboundPageSize` bytes, so the `suspend_threshold`,
+//! `drop_threshold` and `resume_threshold` keep their meaning.
+
+use crate::{
+	ChannelSignal, Config, OverloadedInboundQueues, Pallet, QueueConfig, QueueConfigData,
+	QueueSuspended, RateLimitVerdict, SuspendedInboundChannels, XcmRateLimiter, LOG_TARGET,
+};
+use codec::DecodeLimit;
+use cumulus_primitives_core::{
+	relay_chain::BlockNumber as RelayBlockNumber, AggregateMessageOrigin, ParaId,
+	XcmpMessageFormat, XcmpMessageHandler,
+};
+use frame_support::{
+	traits::{EnqueueMessage, Get, OnQueueChanged, ProcessMessage, ProcessMessageError},
+	weights::{Weight, WeightMeter},
+	BoundedSlice,
+};
+use sp_runtime::traits::BlockNumberProvider;
+use sp_std::{convert::TryFrom, marker::PhantomData};
+use xcm::{VersionedXcm, MAX_XCM_DECODE_DEPTH};
+
+/// Returns the number of inbound pages, that would be used to store `size` bytes of messages.
+fn queued_pages<T: Config>(size: u64) -> u32 {
+	let page_size = T::MaxInboundPageSize::get().max(1) as u64;
+	(size.saturating_add(page_size - 1) / page_size).try_into().unwrap_or(u32::MAX)
+}
+
+/// An `XcmpMessageHandler`, that puts every inbound XCM into the message `Queue`.
+///
+/// Signals are handled immediately. Messages in the `ConcatenatedEncodedBlob` format are not
+/// supported and dropped.
+pub struct EnqueueXcmpMessages<T, Queue>(PhantomData<(T, Queue)>);
+
+impl<T, Queue> XcmpMessageHandler for EnqueueXcmpMessages<T, Queue>
+where
+	T: Config,
+	Queue: EnqueueMessage<AggregateMessageOrigin>,
+{
+	fn handle_xcmp_messages<'a, I: Iterator<Item = (ParaId, RelayBlockNumber, &'a [u8])>>(
+		iter: I,
+		_max_weight: Weight,
+	) -> Weight {
+		let QueueConfigData { suspend_threshold, drop_threshold, .. } = QueueConfig::<T>::get();
+		let mut overloaded = OverloadedInboundQueues::<T>::get();
+		let suspended_channels = SuspendedInboundChannels::<T>::get();
+		let mut weight_used = T::DbWeight::get().reads_writes(3, 1);
+
+		for (sender, _sent_at, mut data) in iter {
+			let format =
+				match XcmpMessageFormat::decode_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut data) {
+					Ok(f) => f,
+					Err(_) => {
+						debug_assert!(
+							false,
+							"Unknown XCMP message format. Silently dropping message"
+						);
+						continue
+					},
+				};
+
+			match format {
+				XcmpMessageFormat::Signals => {
+					weight_used.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+					Pallet::<T>::handle_signals(sender, data);
+				},
+				XcmpMessageFormat::ConcatenatedEncodedBlob => {
+					log::warn!(
+						target: LOG_TARGET,
+						"Dropping blob XCMP messages from {:?}. They're not supported",
+						sender,
+					);
+				},
+				XcmpMessageFormat::ConcatenatedVersionedXcm =>
+					while !data.is_empty() {
+						let remaining = data;
+						if VersionedXcm::<T::RuntimeCall>::decode_with_depth_limit(
+							MAX_XCM_DECODE_DEPTH,
+							&mut data,
+						)
+						.is_err()
+						{
+							debug_assert!(false, "Invalid incoming XCMP message data");
+							break
+						}
+						let encoded_xcm = &remaining[..remaining.len() - data.len()];
+						weight_used.saturating_accrue(T::DbWeight::get().reads_writes(3, 3));
+
+						let origin = AggregateMessageOrigin::Sibling(sender);
+						let pages = queued_pages::<T>(Queue::footprint(origin).size);
+						if pages >= drop_threshold {
+							log::warn!(
+								target: LOG_TARGET,
+								"XCMP queue of {:?} is full. Dropping message",
+								sender,
+							);
+							continue
+						}
+						if pages >= suspend_threshold {
+							if let Err(position) = overloaded.binary_search(&sender) {
+								overloaded.insert(position, sender);
+								// the signal has already been sent if the channel is suspended
+								// by the `ControllerOrigin`
+								if suspended_channels.binary_search(&sender).is_err() &&
+									Pallet::<T>::send_signal(sender, ChannelSignal::Suspend)
+										.is_err()
+								{
+									log::warn!(
+										target: LOG_TARGET,
+										"Attempt to suspend channel failed. Messages may be dropped."
+									);
+								}
+							}
+						}
+
+						match BoundedSlice::try_from(encoded_xcm) {
+							Ok(message) => Queue::enqueue_message(message, origin),
+							Err(_) => log::warn!(
+								target: LOG_TARGET,
+								"Dropping XCM of {} bytes from {:?}. It is too large",
+								encoded_xcm.len(),
+								sender,
+							),
+						}
+					},
+			}
+		}
+
+		OverloadedInboundQueues::<T>::put(overloaded);
+		weight_used
+	}
+}
+
+/// Resumes the channel, that has been suspended by the [`EnqueueXcmpMessages`], once its message
+/// queue is drained to the `resume_threshold`.
+impl<T: Config> OnQueueChanged<AggregateMessageOrigin> for Pallet<T> {
+	fn on_queue_changed(origin: AggregateMessageOrigin, _items_count: u64, items_size: u64) {
+		let sibling = match origin {
+			AggregateMessageOrigin::Sibling(sibling) => sibling,
+			_ => return,
+		};
+		if queued_pages::<T>(items_size) > QueueConfig::<T>::get().resume_threshold {
+			return
+		}
+
+		let mut overloaded = OverloadedInboundQueues::<T>::get();
+		if let Ok(position) = overloaded.binary_search(&sibling) {
+			overloaded.remove(position);
+			OverloadedInboundQueues::<T>::put(overloaded);
+			// the channel is resumed by the `ControllerOrigin` later
+			if SuspendedInboundChannels::<T>::get().binary_search(&sibling).is_err() &&
+				Self::send_signal(sibling, ChannelSignal::Resume).is_err()
+			{
+				log::warn!(
+					target: LOG_TARGET,
+					"Attempt to resume channel failed. Channel may remain suspended."
+				);
+			}
+		}
+	}
+}
+
+/// A `ProcessMessage` adapter, that applies the pallet policies to XCMs from sibling parachains
+/// and passes them to the inner `Processor`.
+///
+/// Messages from the suspended channels (or all siblings, when the XCM execution is suspended)
+/// are yielded, so they stay in the queue. Messages are checked by the `XcmRateLimiter`, but the
+/// `XcmDeferFilter` is not consulted, because the relay chain block, at which the message has been
+/// sent, is not known here. Deferred messages are yielded as well, blocking all subsequent
+/// messages from the same sibling, until the limit is lifted.
+///
+/// Messages from other origins are passed to the `Processor` as-is.
+pub struct ProcessXcmpMessage<T, Processor>(PhantomData<(T, Processor)>);
+
+impl<T, Processor> ProcessMessage for ProcessXcmpMessage<T, Processor>
+where
+	T: Config,
+	Processor: ProcessMessage<Origin = AggregateMessageOrigin>,
+{
+	type Origin = AggregateMessageOrigin;
+
+	fn process_message(
+		message: &[u8],
+		origin: Self::Origin,
+		meter: &mut WeightMeter,
+		id: &mut [u8; 32],
+	) -> Result<bool, ProcessMessageError> {
+		let sender = match origin {
+			AggregateMessageOrigin::Sibling(sender) => sender,
+			_ => return Processor::process_message(message, origin, meter, id),
+		};
+
+		let suspended_weight = T::DbWeight::get().reads(2);
+		if !meter.check_accrue(suspended_weight) {
+			return Err(ProcessMessageError::Overweight(suspended_weight))
+		}
+		if (QueueSuspended::<T>::get() && !Pallet::<T>::is_controller(sender)) ||
+			SuspendedInboundChannels::<T>::get().binary_search(&sender).is_ok()
+		{
+			return Err(ProcessMessageError::Yield)
+		}
+
+		let xcm = VersionedXcm::<T::RuntimeCall>::decode_with_depth_limit(
+			MAX_XCM_DECODE_DEPTH,
+			&mut &message[..],
+		)
+		.map_err(|_| ProcessMessageError::Corrupt)?;
+		let (limiter_weight, verdict) = T::XcmRateLimiter::check(sender, &xcm);
+		if !meter.check_accrue(limiter_weight) {
+			return Err(ProcessMessageError::Overweight(limiter_weight))
+		}
+
+		match verdict {
+			RateLimitVerdict::Execute(ticket) => {
+				let processed = Processor::process_message(message, origin, meter, id)?;
+				if processed {
+					meter.defensive_saturating_accrue(T::XcmRateLimiter::note_executed(
+						sender, ticket,
+					));
+				}
+				Ok(processed)
+			},
+			RateLimitVerdict::Defer(_) => Err(ProcessMessageError::Yield),
+			RateLimitVerdict::Drop => {
+				// the message is dropped at the current relay chain block
+				Pallet::<T>::deposit_rate_limited_event(
+					sender,
+					T::RelayChainBlockNumberProvider::current_block_number(),
+					message,
+					None,
+				);
+				Ok(true)
+			},
+		}
+	}
+}
@@ -16,12 +16,21 @@
 
 //! A module that is responsible for migration of storage.
 
-use crate::{Config, Overweight, Pallet, QueueConfig, DEFAULT_POV_SIZE};
+use crate::{
+	Config, InboundState, InboundXcmpMessages, InboundXcmpPageRange, InboundXcmpPages,
+	InboundXcmpStatus, OverloadedInboundQueues, Overweight, Pallet, QueueConfig, DEFAULT_POV_SIZE,
+	LOG_TARGET,
+};
+use codec::DecodeLimit;
+use cumulus_primitives_core::{AggregateMessageOrigin, XcmpMessageFormat};
 use frame_support::{
 	pallet_prelude::*,
-	traits::{OnRuntimeUpgrade, StorageVersion},
+	traits::{EnqueueMessage, OnRuntimeUpgrade, StorageVersion},
 	weights::{constants::WEIGHT_REF_TIME_PER_MILLIS, Weight},
+	BoundedSlice,
 };
+use sp_std::convert::TryFrom;
+use xcm::{VersionedXcm, MAX_XCM_DECODE_DEPTH};
 
 /// The current storage version.
 pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
@@ -49,6 +58,106 @@ impl<T: Config> OnRuntimeUpgrade for Migration<T> {
 	}
 }
 
+/// Moves the inbound XCMP messages, that are still waiting in the pallet queues, to the message
+/// `Queue` (which is supposed to be the `pallet-message-queue`).
+///
+/// It must be used along with switching the `XcmpMessageHandler` to the
+/// [`crate::EnqueueXcmpMessages`], so that the queued messages are not serviced after the newer
+/// ones. Suspended channels are resumed by the pallet once their message queues are drained.
+/// Overweight and deferred messages are kept in the pallet. The migration does nothing once the
+/// pallet inbound queues are empty.
+pub struct MigrateInboundToMessageQueue<T, Queue>(PhantomData<(T, Queue)>);
+
+impl<T: Config, Queue: EnqueueMessage<AggregateMessageOrigin>> OnRuntimeUpgrade
+	for MigrateInboundToMessageQueue<T, Queue>
+{
+	fn on_runtime_upgrade() -> Weight {
+		let mut weight = T::DbWeight::get().reads(1);
+		let status = InboundXcmpStatus::<T>::get();
+		if status.is_empty() {
+			return weight
+		}
+
+		let mut overloaded = OverloadedInboundQueues::<T>::get();
+		let mut migrated = 0u32;
+		weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 2));
+
+		for channel in status {
+			let sender = channel.sender;
+			for (sent_at, format) in channel.message_metadata {
+				// messages, received before the paged storage has been introduced, are only
+				// split into pages when they're serviced
+				let mut data = InboundXcmpMessages::<T>::take(sender, sent_at);
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
+				if let Some((begin, end)) = InboundXcmpPageRange::<T>::take(sender, sent_at) {
+					for index in begin..end {
+						if let Some(page) = InboundXcmpPages::<T>::take((sender, sent_at, index)) {
+							data.extend_from_slice(&page);
+						}
+						weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+					}
+				}
+
+				if format != XcmpMessageFormat::ConcatenatedVersionedXcm {
+					log::warn!(
+						target: LOG_TARGET,
+						"Dropping {:?} XCMP message from {:?}. It is not supported",
+						format,
+						sender,
+					);
+					continue
+				}
+
+				let mut remaining = &data[..];
+				while !remaining.is_empty() {
+					let message = remaining;
+					if VersionedXcm::<T::RuntimeCall>::decode_with_depth_limit(
+						MAX_XCM_DECODE_DEPTH,
+						&mut remaining,
+					)
+					.is_err()
+					{
+						log::warn!(
+							target: LOG_TARGET,
+							"Dropping invalid XCMP message data from {:?}",
+							sender,
+						);
+						break
+					}
+					let encoded_xcm = &message[..message.len() - remaining.len()];
+					weight.saturating_accrue(T::DbWeight::get().reads_writes(3, 3));
+					match BoundedSlice::try_from(encoded_xcm) {
+						Ok(message) => {
+							Queue::enqueue_message(
+								message,
+								AggregateMessageOrigin::Sibling(sender),
+							);
+							migrated += 1;
+						},
+						Err(_) => log::warn!(
+							target: LOG_TARGET,
+							"Dropping XCM of {} bytes from {:?}. It is too large",
+							encoded_xcm.len(),
+							sender,
+						),
+					}
+				}
+			}
+
+			if channel.state == InboundState::Suspended {
+				if let Err(position) = overloaded.binary_search(&sender) {
+					overloaded.insert(position, sender);
+				}
+			}
+		}
+
+		InboundXcmpStatus::<T>::kill();
+		OverloadedInboundQueues::<T>::put(overloaded);
+		log::info!(target: LOG_TARGET, "Moved {} XCMP messages to the message queue", migrated);
+		weight
+	}
+}
+
 mod v1 {
 	use super::*;
 	use codec::{Decode, Encode};
@@ -116,7 +225,12 @@ pub fn migrate_to_v3<T: Config>() -> Weight {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::mock::{new_test_ext, Test};
+	use crate::{
+		mock::{new_test_ext, EnqueuedMessages, MockedMessageQueue, RuntimeCall, Test},
+		InboundChannelDetails,
+	};
+	use cumulus_primitives_core::ParaId;
+	use xcm::latest::prelude::*;
 
 	#[test]
 	fn test_migration_to_v2() {
@@ -147,4 +261,57 @@ mod tests {
 			assert_eq!(v1.xcmp_max_individual_weight, v2.xcmp_max_individual_weight.ref_time());
 		});
 	}
+
+	#[test]
+	fn inbound_messages_are_moved_to_message_queue() {
+		let message = |n: usize| {
+			VersionedXcm::<RuntimeCall>::from(Xcm::<RuntimeCall>(vec![ClearOrigin; n])).encode()
+		};
+		let (first, second) = (ParaId::from(1000), ParaId::from(2000));
+
+		new_test_ext().execute_with(|| {
+			InboundXcmpStatus::<Test>::put(vec![
+				InboundChannelDetails {
+					sender: first,
+					state: InboundState::Ok,
+					message_metadata: vec![
+						(1, XcmpMessageFormat::ConcatenatedVersionedXcm),
+						(2, XcmpMessageFormat::ConcatenatedVersionedXcm),
+					],
+				},
+				InboundChannelDetails {
+					sender: second,
+					state: InboundState::Suspended,
+					message_metadata: vec![(1, XcmpMessageFormat::ConcatenatedVersionedXcm)],
+				},
+			]);
+			// a legacy aggregate message
+			InboundXcmpMessages::<Test>::insert(first, 1, [message(1), message(2)].concat());
+			// a paged aggregate message
+			InboundXcmpPages::<Test>::insert((first, 2, 0), BoundedVec::truncate_from(message(3)));
+			InboundXcmpPages::<Test>::insert((first, 2, 1), BoundedVec::truncate_from(message(4)));
+			InboundXcmpPageRange::<Test>::insert(first, 2, (0, 2));
+			InboundXcmpMessages::<Test>::insert(second, 1, message(5));
+
+			MigrateInboundToMessageQueue::<Test, MockedMessageQueue>::on_runtime_upgrade();
+
+			let first = AggregateMessageOrigin::Sibling(first);
+			assert_eq!(
+				EnqueuedMessages::get(),
+				vec![
+					(first, message(1)),
+					(first, message(2)),
+					(first, message(3)),
+					(first, message(4)),
+					(AggregateMessageOrigin::Sibling(second), message(5)),
+				],
+			);
+			assert!(InboundXcmpStatus::<Test>::get().is_empty());
+			assert_eq!(InboundXcmpMessages::<Test>::iter().count(), 0);
+			assert_eq!(InboundXcmpPages::<Test>::iter().count(), 0);
+			assert_eq!(InboundXcmpPageRange::<Test>::iter().count(), 0);
+			// the suspended channel is resumed once its message queue is drained
+			assert_eq!(OverloadedInboundQueues::<Test>::get(), vec![second]);
+		});
+	}
 }
@@ -17,10 +17,15 @@ use super::*;
 use crate as xcmp_queue;
 use core::marker::PhantomData;
 use cumulus_pallet_parachain_system::AnyRelayNumber;
-use cumulus_primitives_core::{IsSystem, ParaId};
+use cumulus_primitives_core::{AggregateMessageOrigin, IsSystem, ParaId};
 use frame_support::{
 	parameter_types,
-	traits::{ConstU32, Everything, Nothing, OriginTrait},
+	traits::{
+		ConstU32, EnqueueMessage, Equals, Everything, Footprint, Nothing, OriginTrait,
+		ProcessMessage, ProcessMessageError,
+	},
+	weights::WeightMeter,
+	BoundedSlice,
 };
 use frame_system::EnsureRoot;
 use sp_core::H256;
@@ -104,13 +109,18 @@ impl pallet_balances::Config for Test {
 impl cumulus_pallet_parachain_system::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type OnSystemEvent = ();
+	type RelayChainGenesisHashStore = ();
 	type SelfParaId = ();
 	type OutboundXcmpMessageSource = XcmpQueue;
+	type PriorityUpwardMessageSource = ();
 	type DmpMessageHandler = ();
 	type ReservedDmpWeight = ();
 	type XcmpMessageHandler = XcmpQueue;
 	type ReservedXcmpWeight = ();
+	type PovSizeWatermark = frame_support::traits::ConstU64<{ u64::MAX }>;
 	type CheckAssociatedRelayNumber = AnyRelayNumber;
+	type CheckRelayTimestamp = ();
+	type AuthorshipProofVerifier = ();
 	type ConsensusHook = cumulus_pallet_parachain_system::consensus_hook::ExpectParentIncluded;
 }
 
@@ -196,16 +206,118 @@ impl<RuntimeOrigin: OriginTrait> ConvertOrigin<RuntimeOrigin>
 	}
 }
 
+parameter_types! {
+	pub static RelayBlockNumberValue: RelayBlockNumber = 0;
+	pub DeferredSibling: ParaId = ParaId::from(3000);
+	pub const XcmDeferralPeriod: RelayBlockNumber = 5;
+	pub const RateLimitedAmount: u128 = 100;
+	pub const RateLimitEpochLength: RelayBlockNumber = 10;
+	pub static DeferRateLimitedMessages: bool = true;
+	pub static OpenOutboundChannels: Vec<ParaId> = vec![];
+	pub PriorityRecipient: ParaId = ParaId::from(4000);
+	pub static EnqueuedMessages: Vec<(AggregateMessageOrigin, Vec<u8>)> = vec![];
+	pub static ProcessedMessages: Vec<(AggregateMessageOrigin, Vec<u8>)> = vec![];
+}
+
+/// Message queue for tests. Messages are stored in the `EnqueuedMessages`.
+pub struct MockedMessageQueue;
+impl EnqueueMessage<AggregateMessageOrigin> for MockedMessageQueue {
+	type MaxMessageLen = ConstU32<64>;
+
+	fn enqueue_message(
+		message: BoundedSlice<u8, Self::MaxMessageLen>,
+		origin: AggregateMessageOrigin,
+	) {
+		EnqueuedMessages::mutate(|messages| messages.push((origin, message.to_vec())));
+	}
+
+	fn enqueue_messages<'a>(
+		iter: impl Iterator<Item = BoundedSlice<'a, u8, Self::MaxMessageLen>>,
+		origin: AggregateMessageOrigin,
+	) {
+		iter.for_each(|message| Self::enqueue_message(message, origin));
+	}
+
+	fn sweep_queue(origin: AggregateMessageOrigin) {
+		EnqueuedMessages::mutate(|messages| messages.retain(|(o, _)| *o != origin));
+	}
+
+	fn footprint(origin: AggregateMessageOrigin) -> Footprint {
+		let messages = EnqueuedMessages::get();
+		let queued = messages.iter().filter(|(o, _)| *o == origin);
+		Footprint {
+			count: queued.clone().count() as u64,
+			size: queued.map(|(_, message)| message.len() as u64).sum(),
+		}
+	}
+}
+
+/// Message processor for tests. Processed messages are stored in the `ProcessedMessages`.
+pub struct MockedProcessor;
+impl ProcessMessage for MockedProcessor {
+	type Origin = AggregateMessageOrigin;
+
+	fn process_message(
+		message: &[u8],
+		origin: Self::Origin,
+		meter: &mut WeightMeter,
+		_id: &mut [u8; 32],
+	) -> Result<bool, ProcessMessageError> {
+		let weight = Weight::from_parts(1, 1);
+		if !meter.check_accrue(weight) {
+			return Err(ProcessMessageError::Overweight(weight))
+		}
+		ProcessedMessages::mutate(|messages| messages.push((origin, message.to_vec())));
+		Ok(true)
+	}
+}
+
+/// Channel info for tests. Opened channels are always ready.
+pub struct MockedChannelInfo;
+impl GetChannelInfo for MockedChannelInfo {
+	fn get_channel_status(id: ParaId) -> ChannelStatus {
+		if OpenOutboundChannels::get().contains(&id) {
+			ChannelStatus::Ready(1024, 1024)
+		} else {
+			ChannelStatus::Closed
+		}
+	}
+
+	fn get_channel_max(id: ParaId) -> Option<usize> {
+		OpenOutboundChannels::get().contains(&id).then_some(1024)
+	}
+}
+
+pub struct MockedRelayBlockNumberProvider;
+impl BlockNumberProvider for MockedRelayBlockNumberProvider {
+	type BlockNumber = RelayBlockNumber;
+
+	fn current_block_number() -> RelayBlockNumber {
+		RelayBlockNumberValue::get()
+	}
+}
+
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type XcmExecutor = xcm_executor::XcmExecutor<XcmConfig>;
-	type ChannelInfo = ParachainSystem;
+	type ChannelInfo = MockedChannelInfo;
 	type VersionWrapper = ();
 	type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
 	type ControllerOrigin = EnsureRoot<AccountId>;
 	type ControllerOriginConverter = SystemParachainAsSuperuser<RuntimeOrigin>;
 	type WeightInfo = ();
 	type PriceForSiblingDelivery = ();
+	type XcmDeferFilter = DeferSiblings<Equals<DeferredSibling>, XcmDeferralPeriod>;
+	type OutboundChannelPriority = PrioritizeRecipients<Equals<PriorityRecipient>>;
+	type XcmRateLimiter = AssetValueRateLimiter<
+		Test,
+		RelayChain,
+		RateLimitedAmount,
+		RateLimitEpochLength,
+		DeferRateLimitedMessages,
+	>;
+	type RelayChainBlockNumberProvider = MockedRelayBlockNumberProvider;
+	type MaxInboundPageSize = ConstU32<128>;
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
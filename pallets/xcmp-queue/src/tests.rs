@@ -14,9 +14,18 @@
 // limitations under the License.
 
 use super::*;
-use cumulus_primitives_core::XcmpMessageHandler;
-use frame_support::{assert_noop, assert_ok};
-use mock::{new_test_ext, RuntimeCall, RuntimeOrigin, Test, XcmpQueue};
+use cumulus_primitives_core::{AggregateMessageOrigin, XcmpMessageHandler};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{OnQueueChanged, ProcessMessage, ProcessMessageError},
+	weights::WeightMeter,
+};
+use mock::{
+	new_test_ext, DeferRateLimitedMessages, DeferredSibling, EnqueuedMessages, MockedMessageQueue,
+	MockedProcessor, OpenOutboundChannels, PriorityRecipient, ProcessedMessages,
+	RateLimitEpochLength, RateLimitedAmount, RelayBlockNumberValue, RuntimeCall, RuntimeEvent,
+	RuntimeOrigin, System, Test, XcmDeferralPeriod, XcmpQueue,
+};
 use sp_runtime::traits::BadOrigin;
 
 #[test]
@@ -118,6 +127,479 @@ fn service_overweight_bad_xcm_format() {
 	});
 }
 
+fn queued_messages(sender: ParaId, sent_at: RelayBlockNumber) -> Vec<u8> {
+	let (begin, end) = InboundXcmpPageRange::<Test>::get(sender, sent_at).unwrap_or_default();
+	(begin..end)
+		.filter_map(|index| InboundXcmpPages::<Test>::get((sender, sent_at, index)))
+		.flat_map(|page| page.into_inner())
+		.collect()
+}
+
+fn topic_message(topic: u8) -> Vec<u8> {
+	VersionedXcm::from(Xcm::<RuntimeCall>(vec![Instruction::<RuntimeCall>::SetTopic([topic; 32])]))
+		.encode()
+}
+
+#[test]
+fn messages_are_split_into_pages() {
+	new_test_ext().execute_with(|| {
+		// every message is 35 bytes long, so there are 3 messages in every page
+		let data = (0..7).map(topic_message).collect::<Vec<_>>().concat();
+		let pages = XcmpQueue::split_into_pages(
+			ParaId::from(2000),
+			1,
+			XcmpMessageFormat::ConcatenatedVersionedXcm,
+			&data,
+		);
+		assert_eq!(
+			pages.into_iter().map(|page| page.into_inner()).collect::<Vec<_>>(),
+			vec![data[..105].to_vec(), data[105..210].to_vec(), data[210..].to_vec()],
+		);
+	});
+}
+
+#[test]
+fn messages_larger_than_page_are_dropped() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let large_message = VersionedXcm::from(Xcm::<RuntimeCall>(vec![
+				Instruction::<RuntimeCall>::SetTopic(
+					[0; 32]
+				);
+				5
+			]))
+		.encode();
+		let data = [large_message.clone(), topic_message(1)].concat();
+		let pages = XcmpQueue::split_into_pages(
+			ParaId::from(2000),
+			1,
+			XcmpMessageFormat::ConcatenatedVersionedXcm,
+			&data,
+		);
+		assert_eq!(
+			pages.into_iter().map(|page| page.into_inner()).collect::<Vec<_>>(),
+			vec![topic_message(1)],
+		);
+		System::assert_has_event(
+			Event::MessageTooLarge {
+				sender: ParaId::from(2000),
+				sent_at: 1,
+				message_hash: sp_io::hashing::blake2_256(&large_message),
+				size: large_message.len() as u32,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn inbound_messages_are_stored_in_pages() {
+	new_test_ext().execute_with(|| {
+		let sender = ParaId::from(2000);
+		let data = (0..7).map(topic_message).collect::<Vec<_>>().concat();
+		let message = [XcmpMessageFormat::ConcatenatedVersionedXcm.encode(), data].concat();
+		let messages = vec![(sender, 1u32, message.as_slice())];
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::zero());
+
+		assert_eq!(InboundXcmpPageRange::<Test>::get(sender, 1), Some((0, 3)));
+		assert_eq!(InboundXcmpPages::<Test>::get((sender, 1, 0)).unwrap().len(), 105);
+		assert_eq!(InboundXcmpPages::<Test>::get((sender, 1, 1)).unwrap().len(), 105);
+		assert_eq!(InboundXcmpPages::<Test>::get((sender, 1, 2)).unwrap().len(), 35);
+		assert!(InboundXcmpMessages::<Test>::get(sender, 1).is_empty());
+	});
+}
+
+#[test]
+fn partially_serviced_page_is_merged_with_next_page() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let sender = ParaId::from(2000);
+		let format = XcmpMessageFormat::ConcatenatedVersionedXcm;
+		let data = (0..5).map(topic_message).collect::<Vec<_>>().concat();
+		XcmpQueue::enqueue_pages(sender, 1, format, &data);
+		assert_eq!(InboundXcmpPageRange::<Test>::get(sender, 1), Some((0, 2)));
+
+		// only two messages may be serviced, so the rest of first page is merged with the second
+		let (_, is_empty) = XcmpQueue::process_xcmp_message(
+			sender,
+			(1, format),
+			&mut (MAX_MESSAGES_PER_BLOCK - 2),
+			Weight::MAX,
+			Weight::MAX,
+		);
+		assert!(!is_empty);
+		assert_eq!(executed_messages(), 2);
+		assert_eq!(InboundXcmpPageRange::<Test>::get(sender, 1), Some((1, 2)));
+		assert_eq!(InboundXcmpPages::<Test>::get((sender, 1, 0)), None);
+		assert_eq!(InboundXcmpPages::<Test>::get((sender, 1, 1)).unwrap().into_inner(), data[70..]);
+
+		let (_, is_empty) =
+			XcmpQueue::process_xcmp_message(sender, (1, format), &mut 0, Weight::MAX, Weight::MAX);
+		assert!(is_empty);
+		assert_eq!(executed_messages(), 5);
+		assert_eq!(InboundXcmpPageRange::<Test>::get(sender, 1), None);
+		assert_eq!(InboundXcmpPages::<Test>::get((sender, 1, 1)), None);
+	});
+}
+
+#[test]
+fn legacy_messages_are_lazily_split_into_pages() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let sender = ParaId::from(2000);
+		let format = XcmpMessageFormat::ConcatenatedVersionedXcm;
+		let data = (0..7).map(topic_message).collect::<Vec<_>>().concat();
+		InboundXcmpMessages::<Test>::insert(sender, 1, data);
+
+		let (_, is_empty) = XcmpQueue::process_xcmp_message(
+			sender,
+			(1, format),
+			&mut (MAX_MESSAGES_PER_BLOCK - 4),
+			Weight::MAX,
+			Weight::MAX,
+		);
+		assert!(!is_empty);
+		assert_eq!(executed_messages(), 4);
+		assert!(!InboundXcmpMessages::<Test>::contains_key(sender, 1));
+		assert_eq!(InboundXcmpPageRange::<Test>::get(sender, 1), Some((2, 3)));
+
+		let (_, is_empty) =
+			XcmpQueue::process_xcmp_message(sender, (1, format), &mut 0, Weight::MAX, Weight::MAX);
+		assert!(is_empty);
+		assert_eq!(executed_messages(), 7);
+		assert_eq!(InboundXcmpPageRange::<Test>::get(sender, 1), None);
+	});
+}
+
+fn deferred_sibling_message() -> (Vec<u8>, Vec<u8>) {
+	let xcm = VersionedXcm::from(Xcm::<RuntimeCall>(vec![Instruction::<RuntimeCall>::ClearOrigin]))
+		.encode();
+	let mut message = XcmpMessageFormat::ConcatenatedVersionedXcm.encode();
+	message.extend(xcm.clone());
+	(xcm, message)
+}
+
+fn executed_messages() -> usize {
+	System::events()
+		.into_iter()
+		.filter(|record| {
+			matches!(
+				record.event,
+				RuntimeEvent::XcmpQueue(Event::Success { .. }) |
+					RuntimeEvent::XcmpQueue(Event::Fail { .. })
+			)
+		})
+		.count()
+}
+
+#[test]
+fn messages_from_deferred_sibling_are_deferred() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		RelayBlockNumberValue::set(1);
+
+		let (xcm, message) = deferred_sibling_message();
+		let messages = vec![(DeferredSibling::get(), 1u32, message.as_slice())];
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+
+		// message is moved to the deferred queue
+		let deferred_to = 1 + XcmDeferralPeriod::get();
+		assert!(queued_messages(DeferredSibling::get(), 1u32).is_empty());
+		assert_eq!(
+			DeferredQueue::<Test>::get(DeferredSibling::get()),
+			vec![DeferredMessage { index: 0, sent_at: 1, deferred_to, xcm }],
+		);
+		System::assert_has_event(
+			Event::XcmDeferred {
+				sender: DeferredSibling::get(),
+				sent_at: 1,
+				index: 0,
+				deferred_to,
+			}
+			.into(),
+		);
+		assert_eq!(executed_messages(), 0);
+
+		// message is not executed until its deferral period ends
+		RelayBlockNumberValue::set(deferred_to - 1);
+		XcmpQueue::service_xcmp_queue(Weight::MAX);
+		assert_eq!(DeferredQueue::<Test>::get(DeferredSibling::get()).len(), 1);
+		assert_eq!(executed_messages(), 0);
+
+		// and is executed after that
+		RelayBlockNumberValue::set(deferred_to);
+		XcmpQueue::service_xcmp_queue(Weight::MAX);
+		assert!(!DeferredQueue::<Test>::contains_key(DeferredSibling::get()));
+		assert_eq!(executed_messages(), 1);
+	});
+}
+
+#[test]
+fn messages_are_not_deferred_when_deferral_period_has_ended() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		RelayBlockNumberValue::set(1 + XcmDeferralPeriod::get());
+
+		let (_, message) = deferred_sibling_message();
+		let messages = vec![(DeferredSibling::get(), 1u32, message.as_slice())];
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+
+		assert!(!DeferredQueue::<Test>::contains_key(DeferredSibling::get()));
+		assert_eq!(executed_messages(), 1);
+	});
+}
+
+#[test]
+fn messages_from_other_siblings_are_not_deferred() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		RelayBlockNumberValue::set(1);
+
+		let (_, message) = deferred_sibling_message();
+		let messages = vec![(ParaId::from(2000), 1u32, message.as_slice())];
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+
+		assert!(!DeferredQueue::<Test>::contains_key(ParaId::from(2000)));
+		assert_eq!(executed_messages(), 1);
+	});
+}
+
+#[test]
+fn messages_are_left_in_queue_when_deferred_queue_is_full() {
+	new_test_ext().execute_with(|| {
+		RelayBlockNumberValue::set(1);
+		DeferredQueue::<Test>::insert(
+			DeferredSibling::get(),
+			(0..MAX_DEFERRED_MESSAGES_PER_CHANNEL as DeferredIndex)
+				.map(|index| DeferredMessage { index, sent_at: 1, deferred_to: 100, xcm: vec![] })
+				.collect::<Vec<_>>(),
+		);
+
+		let (xcm, message) = deferred_sibling_message();
+		let messages = vec![(DeferredSibling::get(), 1u32, message.as_slice())];
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+
+		assert_eq!(queued_messages(DeferredSibling::get(), 1u32), xcm);
+		assert_eq!(
+			DeferredQueue::<Test>::get(DeferredSibling::get()).len() as u32,
+			MAX_DEFERRED_MESSAGES_PER_CHANNEL,
+		);
+	});
+}
+
+#[test]
+fn deferred_queues_are_only_serviced_with_enough_weight() {
+	new_test_ext().execute_with(|| {
+		// the XCM can't be decoded, so the message is serviced without being executed
+		let message = DeferredMessage { index: 0, sent_at: 1, deferred_to: 0, xcm: vec![] };
+		DeferredQueue::<Test>::insert(DeferredSibling::get(), vec![message]);
+
+		// not enough weight to read and write back the full deferred queue
+		let max_queue_weight =
+			<Test as Config>::WeightInfo::service_deferred_queue(MAX_DEFERRED_MESSAGES_PER_CHANNEL);
+		let mut messages_processed = 0;
+		XcmpQueue::service_deferred_queues(
+			max_queue_weight.saturating_sub(Weight::from_parts(1, 0)),
+			Weight::MAX,
+			&mut messages_processed,
+		);
+		assert_eq!(messages_processed, 0);
+		assert_eq!(DeferredQueue::<Test>::get(DeferredSibling::get()).len(), 1);
+
+		// enough weight
+		let weight_used = XcmpQueue::service_deferred_queues(
+			max_queue_weight,
+			Weight::MAX,
+			&mut messages_processed,
+		);
+		assert_eq!(messages_processed, 1);
+		assert!(!DeferredQueue::<Test>::contains_key(DeferredSibling::get()));
+		assert!(max_queue_weight.all_gte(weight_used));
+	});
+}
+
+#[test]
+fn service_deferred_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		RelayBlockNumberValue::set(1);
+
+		let (_, message) = deferred_sibling_message();
+		let messages = vec![
+			(DeferredSibling::get(), 1u32, message.as_slice()),
+			(DeferredSibling::get(), 2u32, message.as_slice()),
+		];
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+		assert_eq!(DeferredQueue::<Test>::get(DeferredSibling::get()).len(), 2);
+
+		assert_noop!(
+			XcmpQueue::service_deferred(
+				RuntimeOrigin::signed(1),
+				DeferredSibling::get(),
+				1,
+				Weight::MAX,
+			),
+			BadOrigin,
+		);
+		assert_noop!(
+			XcmpQueue::service_deferred(
+				RuntimeOrigin::root(),
+				DeferredSibling::get(),
+				2,
+				Weight::MAX
+			),
+			Error::<Test>::BadDeferredIndex,
+		);
+
+		// message may be executed before its deferral period ends and out of order
+		assert_ok!(XcmpQueue::service_deferred(
+			RuntimeOrigin::root(),
+			DeferredSibling::get(),
+			1,
+			Weight::MAX,
+		));
+		assert_eq!(executed_messages(), 1);
+		assert_eq!(
+			DeferredQueue::<Test>::get(DeferredSibling::get())
+				.into_iter()
+				.map(|message| message.index)
+				.collect::<Vec<_>>(),
+			vec![0],
+		);
+	});
+}
+
+#[test]
+fn discard_deferred_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		RelayBlockNumberValue::set(1);
+
+		let (_, message) = deferred_sibling_message();
+		let messages = vec![(DeferredSibling::get(), 1u32, message.as_slice())];
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+
+		assert_noop!(
+			XcmpQueue::discard_deferred(RuntimeOrigin::signed(1), DeferredSibling::get(), 0),
+			BadOrigin,
+		);
+		assert_noop!(
+			XcmpQueue::discard_deferred(RuntimeOrigin::root(), ParaId::from(2000), 0),
+			Error::<Test>::BadDeferredIndex,
+		);
+
+		assert_ok!(XcmpQueue::discard_deferred(RuntimeOrigin::root(), DeferredSibling::get(), 0));
+		assert!(!DeferredQueue::<Test>::contains_key(DeferredSibling::get()));
+		System::assert_has_event(
+			Event::DeferredDiscarded { sender: DeferredSibling::get(), index: 0 }.into(),
+		);
+
+		// discarded message is never executed
+		RelayBlockNumberValue::set(100);
+		XcmpQueue::service_xcmp_queue(Weight::MAX);
+		assert_eq!(executed_messages(), 0);
+	});
+}
+
+fn reserve_transfer_message(amount: u128) -> (Vec<u8>, Vec<u8>) {
+	let xcm = VersionedXcm::from(Xcm::<RuntimeCall>(vec![ReserveAssetDeposited(
+		MultiAsset::from((Parent, amount)).into(),
+	)]))
+	.encode();
+	let mut message = XcmpMessageFormat::ConcatenatedVersionedXcm.encode();
+	message.extend(xcm.clone());
+	(xcm, message)
+}
+
+#[test]
+fn rate_limiter_defers_excess_messages() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		RelayBlockNumberValue::set(1);
+
+		let sender = ParaId::from(2000);
+		let (_, first_message) = reserve_transfer_message(RateLimitedAmount::get() - 10);
+		let (xcm, second_message) = reserve_transfer_message(20);
+		let messages = vec![
+			(sender, 1u32, first_message.as_slice()),
+			(sender, 2u32, second_message.as_slice()),
+		];
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+
+		// first message is executed and second is deferred until the next epoch
+		let deferred_to = RateLimitEpochLength::get();
+		assert_eq!(executed_messages(), 1);
+		assert_eq!(InboundRateLimits::<Test>::get(sender), (0, RateLimitedAmount::get() - 10));
+		System::assert_has_event(
+			Event::XcmRateLimited {
+				sender,
+				sent_at: 2,
+				message_hash: sp_io::hashing::blake2_256(&xcm),
+				deferred_to: Some(deferred_to),
+			}
+			.into(),
+		);
+		assert_eq!(
+			DeferredQueue::<Test>::get(sender),
+			vec![DeferredMessage { index: 0, sent_at: 2, deferred_to, xcm }],
+		);
+
+		// second message is executed at the next epoch
+		RelayBlockNumberValue::set(deferred_to);
+		XcmpQueue::service_xcmp_queue(Weight::MAX);
+		assert_eq!(executed_messages(), 2);
+		assert!(!DeferredQueue::<Test>::contains_key(sender));
+		assert_eq!(InboundRateLimits::<Test>::get(sender), (1, 20));
+	});
+}
+
+#[test]
+fn rate_limiter_drops_excess_messages() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		RelayBlockNumberValue::set(1);
+		DeferRateLimitedMessages::set(false);
+
+		let sender = ParaId::from(2000);
+		let (_, first_message) = reserve_transfer_message(RateLimitedAmount::get());
+		let (xcm, second_message) = reserve_transfer_message(1);
+		let messages = vec![
+			(sender, 1u32, first_message.as_slice()),
+			(sender, 2u32, second_message.as_slice()),
+		];
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+
+		assert_eq!(executed_messages(), 1);
+		assert!(!DeferredQueue::<Test>::contains_key(sender));
+		System::assert_has_event(
+			Event::XcmRateLimited {
+				sender,
+				sent_at: 2,
+				message_hash: sp_io::hashing::blake2_256(&xcm),
+				deferred_to: None,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn rate_limiter_drops_messages_above_limit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		RelayBlockNumberValue::set(1);
+
+		let sender = ParaId::from(2000);
+		let (_, message) = reserve_transfer_message(RateLimitedAmount::get() + 1);
+		let messages = vec![(sender, 1u32, message.as_slice())];
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+
+		assert_eq!(executed_messages(), 0);
+		assert!(!DeferredQueue::<Test>::contains_key(sender));
+		assert!(!InboundRateLimits::<Test>::contains_key(sender));
+	});
+}
+
 #[test]
 fn suspend_xcm_execution_works() {
 	new_test_ext().execute_with(|| {
@@ -133,7 +615,7 @@ fn suspend_xcm_execution_works() {
 		// This should have executed the incoming XCM, because it came from a system parachain
 		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
 
-		let queued_xcm = InboundXcmpMessages::<Test>::get(ParaId::from(999), 1u32);
+		let queued_xcm = queued_messages(ParaId::from(999), 1u32);
 		assert!(queued_xcm.is_empty());
 
 		let messages = vec![(ParaId::from(2000), 1u32, message_format.as_slice())];
@@ -141,11 +623,74 @@ fn suspend_xcm_execution_works() {
 		// This shouldn't have executed the incoming XCM
 		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
 
-		let queued_xcm = InboundXcmpMessages::<Test>::get(ParaId::from(2000), 1u32);
+		let queued_xcm = queued_messages(ParaId::from(2000), 1u32);
 		assert_eq!(queued_xcm, xcm);
 	});
 }
 
+#[test]
+fn suspend_channel_with_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let sibling = ParaId::from(2000);
+
+		assert_noop!(XcmpQueue::suspend_channel_with(RuntimeOrigin::signed(2), sibling), BadOrigin);
+		assert_ok!(XcmpQueue::suspend_channel_with(RuntimeOrigin::root(), sibling));
+		assert_noop!(
+			XcmpQueue::suspend_channel_with(RuntimeOrigin::root(), sibling),
+			Error::<Test>::AlreadySuspended,
+		);
+		assert_eq!(
+			SignalMessages::<Test>::get(sibling),
+			(XcmpMessageFormat::Signals, ChannelSignal::Suspend).encode(),
+		);
+
+		// message from the suspended sibling is queued
+		let (xcm, message) = deferred_sibling_message();
+		let messages = vec![(sibling, 1u32, message.as_slice())];
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+		assert_eq!(queued_messages(sibling, 1u32), xcm);
+		assert_eq!(executed_messages(), 0);
+
+		// while messages from other siblings are executed
+		let messages = vec![(ParaId::from(2001), 1u32, message.as_slice())];
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+		assert!(queued_messages(ParaId::from(2001), 1u32).is_empty());
+		assert_eq!(executed_messages(), 1);
+	});
+}
+
+#[test]
+fn resume_channel_with_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let sibling = ParaId::from(2000);
+
+		assert_noop!(
+			XcmpQueue::resume_channel_with(RuntimeOrigin::root(), sibling),
+			Error::<Test>::NotSuspended,
+		);
+		assert_ok!(XcmpQueue::suspend_channel_with(RuntimeOrigin::root(), sibling));
+
+		let (_, message) = deferred_sibling_message();
+		let messages = vec![(sibling, 1u32, message.as_slice())];
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+		assert_eq!(executed_messages(), 0);
+
+		assert_noop!(XcmpQueue::resume_channel_with(RuntimeOrigin::signed(2), sibling), BadOrigin);
+		assert_ok!(XcmpQueue::resume_channel_with(RuntimeOrigin::root(), sibling));
+		assert_eq!(
+			SignalMessages::<Test>::get(sibling),
+			(XcmpMessageFormat::Signals, ChannelSignal::Suspend, ChannelSignal::Resume).encode(),
+		);
+
+		// queued message is executed after the channel is resumed
+		XcmpQueue::service_xcmp_queue(Weight::MAX);
+		assert!(queued_messages(sibling, 1u32).is_empty());
+		assert_eq!(executed_messages(), 1);
+	});
+}
+
 #[test]
 fn update_suspend_threshold_works() {
 	new_test_ext().execute_with(|| {
@@ -257,6 +802,202 @@ fn update_xcmp_max_individual_weight() {
 	});
 }
 
+type MockedEnqueueXcmpMessages = EnqueueXcmpMessages<Test, MockedMessageQueue>;
+type MockedProcessXcmpMessage = ProcessXcmpMessage<Test, MockedProcessor>;
+
+fn process_message(
+	xcm: &[u8],
+	origin: AggregateMessageOrigin,
+) -> Result<bool, ProcessMessageError> {
+	MockedProcessXcmpMessage::process_message(
+		xcm,
+		origin,
+		&mut WeightMeter::from_limit(Weight::MAX),
+		&mut [0u8; 32],
+	)
+}
+
+#[test]
+fn xcms_are_put_into_message_queue() {
+	new_test_ext().execute_with(|| {
+		let sibling = ParaId::from(2000);
+		let mut message = XcmpMessageFormat::ConcatenatedVersionedXcm.encode();
+		message.extend(topic_message(1));
+		message.extend(topic_message(2));
+		let mut blob = XcmpMessageFormat::ConcatenatedEncodedBlob.encode();
+		blob.extend(vec![1u8, 2, 3].encode());
+		let messages = vec![(sibling, 1u32, message.as_slice()), (sibling, 1u32, blob.as_slice())];
+		MockedEnqueueXcmpMessages::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+
+		// every XCM is queued separately and blobs are dropped
+		let origin = AggregateMessageOrigin::Sibling(sibling);
+		assert_eq!(
+			EnqueuedMessages::get(),
+			vec![(origin, topic_message(1)), (origin, topic_message(2))],
+		);
+		// nothing is executed right away
+		assert_eq!(executed_messages(), 0);
+		assert!(InboundXcmpStatus::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn overloaded_message_queue_suspends_and_resumes_channel() {
+	new_test_ext().execute_with(|| {
+		let sibling = ParaId::from(2000);
+		let origin = AggregateMessageOrigin::Sibling(sibling);
+		let mut message = XcmpMessageFormat::ConcatenatedVersionedXcm.encode();
+		for topic in 0..20 {
+			message.extend(topic_message(topic));
+		}
+		let messages = vec![(sibling, 1u32, message.as_slice())];
+		MockedEnqueueXcmpMessages::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+
+		// messages are dropped once the queue reaches `drop_threshold` pages
+		assert_eq!(EnqueuedMessages::get().len(), 15);
+		assert_eq!(OverloadedInboundQueues::<Test>::get(), vec![sibling]);
+		assert_eq!(
+			SignalMessages::<Test>::get(sibling),
+			(XcmpMessageFormat::Signals, ChannelSignal::Suspend).encode(),
+		);
+
+		// the channel is not resumed until the queue is drained to `resume_threshold` pages
+		XcmpQueue::on_queue_changed(origin, 4, 140);
+		assert_eq!(OverloadedInboundQueues::<Test>::get(), vec![sibling]);
+		XcmpQueue::on_queue_changed(origin, 3, 105);
+		assert!(OverloadedInboundQueues::<Test>::get().is_empty());
+		assert_eq!(
+			SignalMessages::<Test>::get(sibling),
+			(XcmpMessageFormat::Signals, ChannelSignal::Suspend, ChannelSignal::Resume).encode(),
+		);
+	});
+}
+
+#[test]
+fn overloaded_channel_is_not_resumed_while_suspended_by_controller() {
+	new_test_ext().execute_with(|| {
+		let sibling = ParaId::from(2000);
+		OverloadedInboundQueues::<Test>::put(vec![sibling]);
+
+		// the signal has already been sent because of overloaded queue
+		assert_ok!(XcmpQueue::suspend_channel_with(RuntimeOrigin::root(), sibling));
+		assert!(SignalMessages::<Test>::get(sibling).is_empty());
+
+		// the queue is drained, but the channel is still suspended by the controller
+		XcmpQueue::on_queue_changed(AggregateMessageOrigin::Sibling(sibling), 0, 0);
+		assert!(OverloadedInboundQueues::<Test>::get().is_empty());
+		assert!(SignalMessages::<Test>::get(sibling).is_empty());
+
+		assert_ok!(XcmpQueue::resume_channel_with(RuntimeOrigin::root(), sibling));
+		assert_eq!(
+			SignalMessages::<Test>::get(sibling),
+			(XcmpMessageFormat::Signals, ChannelSignal::Resume).encode(),
+		);
+	});
+}
+
+#[test]
+fn process_xcmp_message_yields_messages_from_suspended_channels() {
+	new_test_ext().execute_with(|| {
+		let sibling = ParaId::from(2000);
+		let xcm = topic_message(1);
+
+		assert_eq!(process_message(&xcm, AggregateMessageOrigin::Sibling(sibling)), Ok(true));
+		assert_ok!(XcmpQueue::suspend_channel_with(RuntimeOrigin::root(), sibling));
+		assert_eq!(
+			process_message(&xcm, AggregateMessageOrigin::Sibling(sibling)),
+			Err(ProcessMessageError::Yield),
+		);
+
+		// other origins are not affected
+		assert_ok!(XcmpQueue::suspend_xcm_execution(RuntimeOrigin::root()));
+		assert_eq!(process_message(&xcm, AggregateMessageOrigin::Parent), Ok(true));
+		assert_eq!(
+			process_message(&xcm, AggregateMessageOrigin::Sibling(ParaId::from(2001))),
+			Err(ProcessMessageError::Yield),
+		);
+		assert_eq!(
+			ProcessedMessages::get(),
+			vec![
+				(AggregateMessageOrigin::Sibling(sibling), xcm.clone()),
+				(AggregateMessageOrigin::Parent, xcm),
+			],
+		);
+	});
+}
+
+#[test]
+fn process_xcmp_message_applies_rate_limiter() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		RelayBlockNumberValue::set(1);
+		let origin = AggregateMessageOrigin::Sibling(ParaId::from(2000));
+		let (first_xcm, _) = reserve_transfer_message(RateLimitedAmount::get());
+		let (second_xcm, _) = reserve_transfer_message(1);
+
+		assert_eq!(process_message(&first_xcm, origin), Ok(true));
+		assert_eq!(process_message(&second_xcm, origin), Err(ProcessMessageError::Yield));
+
+		DeferRateLimitedMessages::set(false);
+		assert_eq!(process_message(&second_xcm, origin), Ok(true));
+		assert_eq!(ProcessedMessages::get(), vec![(origin, first_xcm)]);
+		System::assert_has_event(
+			Event::XcmRateLimited {
+				sender: ParaId::from(2000),
+				sent_at: 1,
+				message_hash: sp_io::hashing::blake2_256(&second_xcm),
+				deferred_to: None,
+			}
+			.into(),
+		);
+
+		assert_eq!(process_message(&[0xff], origin), Err(ProcessMessageError::Corrupt));
+	});
+}
+
+#[test]
+fn priority_outbound_channels_are_serviced_first() {
+	new_test_ext().execute_with(|| {
+		let recipients = vec![ParaId::from(2000), ParaId::from(3000), PriorityRecipient::get()];
+		OpenOutboundChannels::set(recipients.clone());
+		for recipient in &recipients {
+			assert_ok!(XcmpQueue::send_xcm_message(
+				*recipient,
+				VersionedXcm::from(Xcm::<()>(vec![ClearOrigin])),
+			));
+		}
+
+		let take_recipient = || {
+			let messages = XcmpQueue::take_outbound_messages(1);
+			assert_eq!(messages.len(), 1);
+			messages[0].0
+		};
+
+		// priority channel is serviced first, even though it has become non-empty last
+		assert_eq!(take_recipient(), PriorityRecipient::get());
+		assert_eq!(take_recipient(), ParaId::from(2000));
+		assert_eq!(take_recipient(), ParaId::from(3000));
+		assert!(XcmpQueue::take_outbound_messages(1).is_empty());
+	});
+}
+
+#[test]
+fn take_outbound_xcms_returns_the_xcms_by_recipient() {
+	new_test_ext().execute_with(|| {
+		let recipients = vec![ParaId::from(2000), ParaId::from(3000)];
+		OpenOutboundChannels::set(recipients.clone());
+		let first = VersionedXcm::from(Xcm::<()>(vec![ClearOrigin]));
+		let second = VersionedXcm::from(Xcm::<()>(vec![ClearTopic]));
+		assert_ok!(XcmpQueue::send_xcm_message(recipients[0], first.clone()));
+		assert_ok!(XcmpQueue::send_xcm_message(recipients[0], second.clone()));
+		assert_ok!(XcmpQueue::send_blob_message(recipients[1], vec![1, 2, 3]));
+
+		// the blob is dropped
+		assert_eq!(XcmpQueue::take_outbound_xcms(), vec![(recipients[0], vec![first, second])]);
+		assert!(XcmpQueue::take_outbound_xcms().is_empty());
+	});
+}
+
 /// Validates [`validate`] for required Some(destination) and Some(message)
 struct OkFixedXcmHashWithAssertingRequiredInputsSender;
 impl OkFixedXcmHashWithAssertingRequiredInputsSender {
@@ -341,3 +1082,59 @@ fn xcmp_queue_consumes_dest_and_msg_on_ok_validate() {
 		);
 	});
 }
+
+#[test]
+fn xcmp_channel_status_providers_detect_congestion() {
+	use bp_xcm_bridge_hub_router::XcmChannelStatusProvider;
+	use bridging::{InAndOutXcmpChannelStatusProvider, OutXcmpChannelStatusProvider};
+
+	frame_support::parameter_types! {
+		pub SiblingBridgeHubParaId: ParaId = 1002.into();
+	}
+	type InAndOut = InAndOutXcmpChannelStatusProvider<SiblingBridgeHubParaId, Test>;
+	type Out = OutXcmpChannelStatusProvider<SiblingBridgeHubParaId, Test>;
+
+	new_test_ext().execute_with(|| {
+		// there are no channels => not congested
+		assert!(!InAndOut::is_congested());
+		assert!(!Out::is_congested());
+
+		// channel with other sibling is suspended => not congested
+		OutboundXcmpStatus::<Test>::put(vec![
+			OutboundChannelDetails::new(2000.into()).with_suspended_state()
+		]);
+		assert!(!InAndOut::is_congested());
+		assert!(!Out::is_congested());
+
+		// few pages are queued => not congested
+		let mut channel = OutboundChannelDetails::new(SiblingBridgeHubParaId::get());
+		channel.last_index = bridging::MAX_QUEUED_PAGES_BEFORE_CONGESTION;
+		OutboundXcmpStatus::<Test>::put(vec![channel.clone()]);
+		assert!(!InAndOut::is_congested());
+		assert!(!Out::is_congested());
+
+		// too many pages are queued => congested
+		channel.last_index += 1;
+		OutboundXcmpStatus::<Test>::put(vec![channel]);
+		assert!(InAndOut::is_congested());
+		assert!(Out::is_congested());
+
+		// outbound channel is suspended => congested
+		OutboundXcmpStatus::<Test>::put(vec![OutboundChannelDetails::new(
+			SiblingBridgeHubParaId::get(),
+		)
+		.with_suspended_state()]);
+		assert!(InAndOut::is_congested());
+		assert!(Out::is_congested());
+
+		// inbound channel is suspended => only `InAndOut` reports congestion
+		OutboundXcmpStatus::<Test>::kill();
+		InboundXcmpStatus::<Test>::put(vec![InboundChannelDetails {
+			sender: SiblingBridgeHubParaId::get(),
+			state: InboundState::Suspended,
+			message_metadata: vec![],
+		}]);
+		assert!(InAndOut::is_congested());
+		assert!(!Out::is_congested());
+	});
+}
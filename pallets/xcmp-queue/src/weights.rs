@@ -11,6 +11,28 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn set_config_with_u32() -> Weight;
 	fn set_config_with_weight() -> Weight;
+	/// Weight of putting `p` pages of the inbound XCMP message into the storage.
+	///
+	/// Until the `enqueue_pages` benchmark results are available for the runtime, only the
+	/// storage accesses are accounted for.
+	fn enqueue_pages(p: u32) -> Weight {
+		RocksDbWeight::get().reads_writes(1, (p as u64).saturating_add(1))
+	}
+	/// Weight of merging the partially serviced page with the next page.
+	///
+	/// Until the `merge_pages` benchmark results are available for the runtime, only the
+	/// storage accesses are accounted for.
+	fn merge_pages() -> Weight {
+		RocksDbWeight::get().reads_writes(1, 2)
+	}
+	/// Weight of reading and writing back the deferred queue of a single sibling, that has `m`
+	/// messages, excluding the execution of the messages.
+	///
+	/// Until the `service_deferred_queue` benchmark results are available for the runtime, only
+	/// the storage accesses are accounted for.
+	fn service_deferred_queue(_m: u32) -> Weight {
+		RocksDbWeight::get().reads_writes(1, 1)
+	}
 }
 
 pub struct SubstrateWeight<T>(PhantomData<T>);
@@ -12,11 +12,22 @@ use parachain_template_runtime::{
 
 // Cumulus Imports
 use cumulus_client_collator::service::CollatorService;
-use cumulus_client_consensus_common::ParachainBlockImport as TParachainBlockImport;
-use cumulus_client_consensus_proposer::Proposer;
+use cumulus_client_consensus_aura::{
+	key_rotation::{self, AuraKeyRotation, AuraKeyRotationApiServer, AuthoringKeys},
+	shuffling::{ShufflingBlockImport, ShufflingProposer},
+};
+use cumulus_client_consensus_common::{
+	storage_proof_size::ProofSizeRecordingBlockImport,
+	ParachainBlockImport as TParachainBlockImport,
+};
+use cumulus_client_consensus_proposer::{
+	congestion::{CongestionAwareProposer, CongestionConfig, CongestionMonitor},
+	Proposer,
+};
 use cumulus_client_service::{
 	build_network, build_relay_chain_interface, prepare_node_config, start_relay_chain_tasks,
 	BuildNetworkParams, CollatorSybilResistance, DARecoveryProfile, StartRelayChainTasksParams,
+	SyncStateApiServer, SyncStateProvider,
 };
 use cumulus_primitives_core::{relay_chain::CollatorPair, ParaId};
 use cumulus_relay_chain_interface::{OverseerHandle, RelayChainInterface};
@@ -40,7 +51,10 @@ use substrate_prometheus_endpoint::Registry;
 pub struct ParachainNativeExecutor;
 
 impl sc_executor::NativeExecutionDispatch for ParachainNativeExecutor {
-	type ExtendHostFunctions = frame_benchmarking::benchmarking::HostFunctions;
+	type ExtendHostFunctions = (
+		frame_benchmarking::benchmarking::HostFunctions,
+		cumulus_primitives_storage_weight_reclaim::storage_proof_size::HostFunctions,
+	);
 
 	fn dispatch(method: &str, data: &[u8]) -> Option<Vec<u8>> {
 		parachain_template_runtime::api::dispatch(method, data)
@@ -57,7 +71,21 @@ type ParachainClient = TFullClient<Block, RuntimeApi, ParachainExecutor>;
 
 type ParachainBackend = TFullBackend<Block>;
 
-type ParachainBlockImport = TParachainBlockImport<Block, Arc<ParachainClient>, ParachainBackend>;
+// The runtime reclaims the storage weight, so the blocks are executed with the storage proof size
+// exposed to the runtime. The extrinsics of the blocks are required to be shuffled.
+type ParachainBlockImport = TParachainBlockImport<
+	Block,
+	ShufflingBlockImport<
+		Block,
+		ProofSizeRecordingBlockImport<
+			Block,
+			Arc<ParachainClient>,
+			ParachainClient,
+			ParachainBackend,
+		>,
+	>,
+	ParachainBackend,
+>;
 
 /// Starts a `ServiceBuilder` for a full service.
 ///
@@ -124,7 +152,13 @@ pub fn new_partial(
 		client.clone(),
 	);
 
-	let block_import = ParachainBlockImport::new(client.clone(), backend.clone());
+	let block_import = ParachainBlockImport::new(
+		ShufflingBlockImport::new(
+			ProofSizeRecordingBlockImport::new(client.clone(), client.clone(), backend.clone()),
+			true,
+		),
+		backend.clone(),
+	);
 
 	let import_queue = build_import_queue(
 		client.clone(),
@@ -179,6 +213,7 @@ async fn start_node_impl(
 	.map_err(|e| sc_service::Error::Application(Box::new(e) as Box<_>))?;
 
 	let validator = parachain_config.role.is_authority();
+	let force_authoring = parachain_config.force_authoring;
 	let prometheus_registry = parachain_config.prometheus_registry().cloned();
 	let transaction_pool = params.transaction_pool.clone();
 	let import_queue_service = params.import_queue.service();
@@ -220,9 +255,17 @@ async fn start_node_impl(
 		);
 	}
 
+	let sync_state_provider =
+		SyncStateProvider::new(client.clone(), relay_chain_interface.clone(), sync_service.clone());
+
+	let authoring_keys = AuthoringKeys::default();
+
 	let rpc_builder = {
 		let client = client.clone();
 		let transaction_pool = transaction_pool.clone();
+		let sync_state_provider = sync_state_provider.clone();
+		let authoring_keys = authoring_keys.clone();
+		let keystore = params.keystore_container.keystore();
 
 		Box::new(move |deny_unsafe, _| {
 			let deps = crate::rpc::FullDeps {
@@ -231,7 +274,17 @@ async fn start_node_impl(
 				deny_unsafe,
 			};
 
-			crate::rpc::create_full(deps).map_err(Into::into)
+			let mut module = crate::rpc::create_full(deps)?;
+			module
+				.merge(sync_state_provider.clone().into_rpc())
+				.map_err(|e| sc_service::Error::Application(Box::new(e)))?;
+			module
+				.merge(
+					AuraKeyRotation::new(authoring_keys.clone(), keystore.clone(), deny_unsafe)
+						.into_rpc(),
+				)
+				.map_err(|e| sc_service::Error::Application(Box::new(e)))?;
+			Ok(module)
 		})
 	};
 
@@ -242,7 +295,7 @@ async fn start_node_impl(
 		task_manager: &mut task_manager,
 		config: parachain_config,
 		keystore: params.keystore_container.keystore(),
-		backend,
+		backend: backend.clone(),
 		network: network.clone(),
 		sync_service: sync_service.clone(),
 		system_rpc_tx,
@@ -276,6 +329,12 @@ async fn start_node_impl(
 		Arc::new(move |hash, data| sync_service.announce_block(hash, data))
 	};
 
+	task_manager.spawn_handle().spawn(
+		"cumulus-informant",
+		None,
+		sync_state_provider.clone().run_informant(),
+	);
+
 	let relay_chain_slot_duration = Duration::from_secs(6);
 
 	let overseer_handle = relay_chain_interface
@@ -288,6 +347,10 @@ async fn start_node_impl(
 		para_id,
 		relay_chain_interface: relay_chain_interface.clone(),
 		task_manager: &mut task_manager,
+		pov_recovery_concurrency: collator_options.pov_recovery_concurrency,
+		network: Some(network.clone()),
+		prometheus_registry: prometheus_registry.as_ref(),
+		pov_recovery_backlog: Some(sync_state_provider.pov_recovery_backlog()),
 		da_recovery_profile: if validator {
 			DARecoveryProfile::Collator
 		} else {
@@ -302,6 +365,7 @@ async fn start_node_impl(
 	if validator {
 		start_consensus(
 			client.clone(),
+			backend,
 			block_import,
 			prometheus_registry.as_ref(),
 			telemetry.as_ref().map(|t| t.handle()),
@@ -315,6 +379,8 @@ async fn start_node_impl(
 			collator_key.expect("Command line arguments do not allow this. qed"),
 			overseer_handle,
 			announce_block,
+			authoring_keys,
+			force_authoring,
 		)?;
 	}
 
@@ -355,6 +421,7 @@ fn build_import_queue(
 
 fn start_consensus(
 	client: Arc<ParachainClient>,
+	backend: Arc<ParachainBackend>,
 	block_import: ParachainBlockImport,
 	prometheus_registry: Option<&Registry>,
 	telemetry: Option<TelemetryHandle>,
@@ -368,9 +435,12 @@ fn start_consensus(
 	collator_key: CollatorPair,
 	overseer_handle: OverseerHandle,
 	announce_block: Arc<dyn Fn(Hash, Option<Vec<u8>>) + Send + Sync>,
+	authoring_keys: AuthoringKeys,
+	force_authoring: bool,
 ) -> Result<(), sc_service::Error> {
-	use cumulus_client_consensus_aura::collators::basic::{
-		self as basic_aura, Params as BasicAuraParams,
+	use cumulus_client_consensus_aura::collators::{
+		basic::{self as basic_aura, Params as BasicAuraParams},
+		RelayFinalityBackoff,
 	};
 
 	// NOTE: because we use Aura here explicitly, we can use `CollatorSybilResistance::Resistant`
@@ -386,7 +456,25 @@ fn start_consensus(
 		telemetry.clone(),
 	);
 
-	let proposer = Proposer::new(proposer_factory);
+	// Measures the delayed availability of the candidates on the relay chain, so smaller blocks
+	// are built while the relay chain is congested.
+	let congestion_monitor = CongestionMonitor::new(relay_chain_interface.clone());
+	let congestion = congestion_monitor.congestion();
+	task_manager
+		.spawn_handle()
+		.spawn("relay-congestion-monitor", None, congestion_monitor.run());
+
+	// Rebuilds the block with the shuffled extrinsics and the storage proof size exposed to the
+	// runtime.
+	let proposer = ShufflingProposer::new(
+		CongestionAwareProposer::new(
+			Proposer::new(proposer_factory),
+			congestion,
+			CongestionConfig::default(),
+		),
+		client.clone(),
+		backend,
+	);
 
 	let collator_service = CollatorService::new(
 		client.clone(),
@@ -395,6 +483,16 @@ fn start_consensus(
 		client.clone(),
 	);
 
+	// Activates the Aura keys, that are scheduled with `cumulus_rotateAuraKey`.
+	task_manager.spawn_handle().spawn(
+		"aura-key-rotation",
+		None,
+		key_rotation::track_aura_authorities::<_, _, sp_consensus_aura::sr25519::AuthorityId>(
+			client.clone(),
+			authoring_keys.clone(),
+		),
+	);
+
 	let params = BasicAuraParams {
 		create_inherent_data_providers: move |_, ()| async move { Ok(()) },
 		block_import,
@@ -411,6 +509,9 @@ fn start_consensus(
 		collator_service,
 		// Very limited proposal time.
 		authoring_duration: Duration::from_millis(500),
+		authoring_keys: Some(authoring_keys),
+		// `--force-authoring` keeps authoring, even if the relay-chain finality stalls.
+		finality_backoff: (!force_authoring).then(RelayFinalityBackoff::default),
 	};
 
 	let fut =
@@ -29,7 +29,7 @@ use frame_support::{
 	construct_runtime,
 	dispatch::DispatchClass,
 	parameter_types,
-	traits::{ConstBool, ConstU32, ConstU64, ConstU8, EitherOfDiverse, Everything},
+	traits::{ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, EitherOfDiverse, Everything},
 	weights::{
 		constants::WEIGHT_REF_TIME_PER_SECOND, ConstantMultiplier, Weight, WeightToFeeCoefficient,
 		WeightToFeeCoefficients, WeightToFeePolynomial,
@@ -104,12 +104,19 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	cumulus_primitives_storage_weight_reclaim::StorageWeightReclaim<Runtime>,
 );
 
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+	pallet_collator_selection::migration::v1::MigrateToV1<Runtime>,
+	pallet_collator_selection::migration::v2::MigrateToV2<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -117,6 +124,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 /// Handles converting a weight scalar to a fee value, based on the scale and granularity of the
@@ -182,7 +190,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_version: 1,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -378,18 +386,32 @@ impl pallet_sudo::Config for Runtime {
 parameter_types! {
 	pub const ReservedXcmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
 	pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
+	pub const PovSizeWatermark: u64 = MAXIMUM_BLOCK_WEIGHT.proof_size() / 2;
+	/// The timestamp may be at most five relay chain slots away from the one derived from the
+	/// relay parent slot.
+	pub const RelayTimestampTolerance: u64 = 5 * RELAY_CHAIN_SLOT_DURATION_MILLIS as u64;
 }
 
 impl cumulus_pallet_parachain_system::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type OnSystemEvent = ();
+	type RelayChainGenesisHashStore = ParachainInfo;
 	type SelfParaId = parachain_info::Pallet<Runtime>;
 	type OutboundXcmpMessageSource = XcmpQueue;
+	type PriorityUpwardMessageSource = ();
 	type DmpMessageHandler = DmpQueue;
 	type ReservedDmpWeight = ReservedDmpWeight;
 	type XcmpMessageHandler = XcmpQueue;
 	type ReservedXcmpWeight = ReservedXcmpWeight;
+	type PovSizeWatermark = PovSizeWatermark;
 	type CheckAssociatedRelayNumber = RelayNumberStrictlyIncreases;
+	type CheckRelayTimestamp = cumulus_pallet_parachain_system::TimestampMatchesRelaySlot<
+		Timestamp,
+		ConstU64<{ RELAY_CHAIN_SLOT_DURATION_MILLIS as u64 }>,
+		ConstU64<{ RELAY_CHAIN_SLOT_DURATION_MILLIS as u64 }>,
+		RelayTimestampTolerance,
+	>;
+	type AuthorshipProofVerifier = cumulus_pallet_aura_ext::AuraAuthorshipProof<Runtime>;
 	type ConsensusHook = cumulus_pallet_aura_ext::FixedVelocityConsensusHook<
 		Runtime,
 		RELAY_CHAIN_SLOT_DURATION_MILLIS,
@@ -412,6 +434,12 @@ impl cumulus_pallet_xcmp_queue::Config for Runtime {
 	type ControllerOriginConverter = XcmOriginToTransactDispatchOrigin;
 	type WeightInfo = ();
 	type PriceForSiblingDelivery = ();
+	type XcmDeferFilter = ();
+	type OutboundChannelPriority = ();
+	type XcmRateLimiter = ();
+	type RelayChainBlockNumberProvider =
+		cumulus_pallet_parachain_system::RelaychainDataProvider<Runtime>;
+	type MaxInboundPageSize = ConstU32<{ 64 * 1024 }>;
 }
 
 impl cumulus_pallet_dmp_queue::Config for Runtime {
@@ -450,6 +478,7 @@ impl pallet_aura::Config for Runtime {
 
 parameter_types! {
 	pub const PotId: PalletId = PalletId(*b"PotStake");
+	pub const CollatorOfflineSlash: Perbill = Perbill::from_percent(10);
 	pub const SessionLength: BlockNumber = 6 * HOURS;
 	// StakingAdmin pluralistic body.
 	pub const StakingAdminBodyId: BodyId = BodyId::Defense;
@@ -471,12 +500,35 @@ impl pallet_collator_selection::Config for Runtime {
 	type MaxInvulnerables = ConstU32<20>;
 	// should be a multiple of session or things will get inconsistent
 	type KickThreshold = Period;
+	// remove candidates that authored no block for four sessions in a row
+	type MaxOfflineSessions = ConstU32<4>;
+	type OfflineSlash = CollatorOfflineSlash;
+	type MaxDelegatorsPerCandidate = ConstU32<64>;
+	type MinDelegation = ConstU128<UNIT>;
+	type DelegationUnbondingPeriod = ConstU32<DAYS>;
+	type MaxUnbondingChunks = ConstU32<8>;
 	type ValidatorId = <Self as frame_system::Config>::AccountId;
 	type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
 	type ValidatorRegistration = Session;
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub DelayedExecutionMaxWeight: Weight =
+		Perbill::from_percent(25) * RuntimeBlockWeights::get().max_block;
+}
+
+impl cumulus_pallet_delayed_execution::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	// The seed is committed by the `ShufflingProposer` of the collator.
+	type ShufflingSeed = cumulus_pallet_delayed_execution::DigestShufflingSeed<Runtime>;
+	type MaxQueuedCalls = ConstU32<128>;
+	type MaxCallLen = ConstU32<1024>;
+	type MaxExecutionWeight = DelayedExecutionMaxWeight;
+	type WeightInfo = ();
+}
+
 /// Configure the pallet template in pallets/template.
 impl pallet_parachain_template::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
@@ -512,6 +564,9 @@ construct_runtime!(
 		CumulusXcm: cumulus_pallet_xcm = 32,
 		DmpQueue: cumulus_pallet_dmp_queue = 33,
 
+		// Execution of the calls in the shuffled order of the next block.
+		DelayedExecution: cumulus_pallet_delayed_execution = 40,
+
 		// Template
 		TemplatePallet: pallet_parachain_template = 50,
 	}
@@ -527,6 +582,7 @@ mod benches {
 		[pallet_sudo, Sudo]
 		[pallet_collator_selection, CollatorSelection]
 		[cumulus_pallet_xcmp_queue, XcmpQueue]
+		[cumulus_pallet_delayed_execution, DelayedExecution]
 	);
 }
 
@@ -674,6 +730,16 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl cumulus_primitives_core::UnincludedSegmentApi<Block> for Runtime {
+		fn unincluded_segment_capacity() -> u32 {
+			ParachainSystem::unincluded_segment_capacity()
+		}
+
+		fn unincluded_segment_size_after(included_hash: <Block as BlockT>::Hash) -> u32 {
+			ParachainSystem::unincluded_segment_size_after(included_hash)
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
@@ -746,5 +812,8 @@ impl_runtime_apis! {
 
 cumulus_pallet_parachain_system::register_validate_block! {
 	Runtime = Runtime,
-	BlockExecutor = cumulus_pallet_aura_ext::BlockExecutor::<Runtime, Executive>,
+	BlockExecutor = cumulus_pallet_aura_ext::BlockExecutor::<
+		Runtime,
+		cumulus_pallet_aura_ext::ShufflingBlockExecutor<Executive>,
+	>,
 }
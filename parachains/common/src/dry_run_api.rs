@@ -0,0 +1,190 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API for dry-running calls and XCM programs on the chain.
+//!
+//! A dry run executes the call or the program in a storage transaction that is rolled back, and
+//! reports the result, the emitted events and the XCMs that are sent to the relay chain (UMP) and
+//! to other parachains (HRMP). Messages that were already queued at the block the API is called at
+//! are not reported.
+
+use codec::{Codec, Decode, DecodeLimit, Encode};
+use frame_support::{
+	dispatch::{DispatchResultWithPostInfo, PostDispatchInfo},
+	storage::{with_transaction, TransactionOutcome},
+	weights::Weight,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Dispatchable, DispatchError, RuntimeDebug};
+use sp_std::vec::Vec;
+use xcm::{latest::prelude::*, VersionedMultiLocation, VersionedXcm, MAX_XCM_DECODE_DEPTH};
+use xcm_executor::XcmExecutor;
+
+/// The XCMs sent to a destination.
+pub type ForwardedXcms = Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)>;
+
+/// The errors of the [`DryRunApi`].
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum DryRunError {
+	/// The origin or the program can't be converted to the XCM version of the chain.
+	VersionedConversionFailed,
+	/// No storage transaction can be opened for the dry run.
+	NestingLimitReached,
+}
+
+/// The effects of dry-running a call.
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct CallDryRunEffects<Event> {
+	/// The result of dispatching the call.
+	pub execution_result: DispatchResultWithPostInfo,
+	/// The events emitted by the call.
+	pub emitted_events: Vec<Event>,
+	/// The XCMs sent by the call, by destination.
+	pub forwarded_xcms: ForwardedXcms,
+}
+
+/// The effects of dry-running an XCM program.
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct XcmDryRunEffects<Event> {
+	/// The outcome of executing the program.
+	pub execution_result: Outcome,
+	/// The events emitted by the program.
+	pub emitted_events: Vec<Event>,
+	/// The XCMs sent by the program, by destination.
+	pub forwarded_xcms: ForwardedXcms,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API for dry-running calls and XCM programs on the chain.
+	pub trait DryRunApi<Call, Event, OriginCaller>
+	where
+		Call: Codec,
+		Event: Codec,
+		OriginCaller: Codec,
+	{
+		/// Dispatches `call` from `origin` and returns its effects.
+		fn dry_run_call(
+			origin: OriginCaller,
+			call: Call,
+		) -> Result<CallDryRunEffects<Event>, DryRunError>;
+
+		/// Executes `xcm` as if it was received from `origin` and returns its effects.
+		fn dry_run_xcm(
+			origin: VersionedMultiLocation,
+			xcm: VersionedXcm<Call>,
+		) -> Result<XcmDryRunEffects<Event>, DryRunError>;
+	}
+}
+
+/// Dispatches `call` from `origin` on `Runtime` and rolls back all changes.
+pub fn dry_run_call<Runtime, Call, Origin>(
+	origin: Origin,
+	call: Call,
+) -> Result<CallDryRunEffects<<Runtime as frame_system::Config>::RuntimeEvent>, DryRunError>
+where
+	Runtime: frame_system::Config
+		+ cumulus_pallet_parachain_system::Config
+		+ cumulus_pallet_xcmp_queue::Config,
+	Call: Dispatchable<PostInfo = PostDispatchInfo>,
+	Call::RuntimeOrigin: From<Origin>,
+{
+	let (execution_result, emitted_events, forwarded_xcms) =
+		sandboxed::<Runtime, _>(|| call.dispatch(origin.into()))?;
+	Ok(CallDryRunEffects { execution_result, emitted_events, forwarded_xcms })
+}
+
+/// Executes `xcm` from `origin` with the XCM executor of `Config` and rolls back all changes.
+pub fn dry_run_xcm<Runtime, Config>(
+	origin: VersionedMultiLocation,
+	xcm: VersionedXcm<Config::RuntimeCall>,
+) -> Result<XcmDryRunEffects<<Runtime as frame_system::Config>::RuntimeEvent>, DryRunError>
+where
+	Runtime: frame_system::Config
+		+ cumulus_pallet_parachain_system::Config
+		+ cumulus_pallet_xcmp_queue::Config,
+	Config: xcm_executor::Config,
+{
+	let origin =
+		MultiLocation::try_from(origin).map_err(|_| DryRunError::VersionedConversionFailed)?;
+	let xcm = Xcm::<Config::RuntimeCall>::try_from(xcm)
+		.map_err(|_| DryRunError::VersionedConversionFailed)?;
+	let hash = xcm.using_encoded(sp_io::hashing::blake2_256);
+	let (execution_result, emitted_events, forwarded_xcms) = sandboxed::<Runtime, _>(|| {
+		XcmExecutor::<Config>::execute_xcm(origin, xcm, hash, Weight::MAX)
+	})?;
+	Ok(XcmDryRunEffects { execution_result, emitted_events, forwarded_xcms })
+}
+
+/// Runs `execute` in a storage transaction that is rolled back and returns its result together
+/// with the events it emitted and the XCMs it sent.
+fn sandboxed<Runtime, R>(
+	execute: impl FnOnce() -> R,
+) -> Result<(R, Vec<<Runtime as frame_system::Config>::RuntimeEvent>, ForwardedXcms), DryRunError>
+where
+	Runtime: frame_system::Config
+		+ cumulus_pallet_parachain_system::Config
+		+ cumulus_pallet_xcmp_queue::Config,
+{
+	with_transaction(|| {
+		// Only the messages sent by `execute` are reported.
+		let _ = take_queued_xcms::<Runtime>();
+		frame_system::Pallet::<Runtime>::reset_events();
+
+		let result = execute();
+
+		let events = frame_system::Pallet::<Runtime>::read_events_no_consensus()
+			.map(|record| record.event)
+			.collect();
+		TransactionOutcome::Rollback(Ok::<_, DispatchError>((
+			result,
+			events,
+			take_queued_xcms::<Runtime>(),
+		)))
+	})
+	.map_err(|_| DryRunError::NestingLimitReached)
+}
+
+/// Removes the XCMs queued for the relay chain and for other parachains.
+fn take_queued_xcms<Runtime>() -> ForwardedXcms
+where
+	Runtime: cumulus_pallet_parachain_system::Config + cumulus_pallet_xcmp_queue::Config,
+{
+	let mut forwarded = Vec::new();
+
+	let upward: Vec<_> =
+		cumulus_pallet_parachain_system::Pallet::<Runtime>::take_pending_upward_messages()
+			.into_iter()
+			.filter_map(|message| {
+				VersionedXcm::<()>::decode_all_with_depth_limit(
+					MAX_XCM_DECODE_DEPTH,
+					&mut &message[..],
+				)
+				.ok()
+			})
+			.collect();
+	if !upward.is_empty() {
+		forwarded.push((MultiLocation::parent().into(), upward));
+	}
+
+	forwarded.extend(
+		cumulus_pallet_xcmp_queue::Pallet::<Runtime>::take_outbound_xcms()
+			.into_iter()
+			.map(|(para_id, xcms)| {
+				(MultiLocation::new(1, X1(Parachain(para_id.into()))).into(), xcms)
+			}),
+	);
+
+	forwarded
+}
@@ -21,7 +21,7 @@ use frame_support::traits::{
 	Contains, ContainsPair, Currency, Get, Imbalance, OnUnbalanced,
 };
 use pallet_asset_tx_payment::HandleCredit;
-use sp_runtime::traits::Zero;
+use sp_runtime::{traits::Zero, PerThing, Percent};
 use sp_std::marker::PhantomData;
 use xcm::latest::{AssetId, Fungibility::Fungible, MultiAsset, MultiLocation};
 
@@ -47,23 +47,63 @@ where
 	}
 }
 
-/// Implementation of `OnUnbalanced` that deals with the fees by combining tip and fee and passing
-/// the result on to `ToStakingPot`.
-pub struct DealWithFees<R>(PhantomData<R>);
-impl<R> OnUnbalanced<NegativeImbalance<R>> for DealWithFees<R>
+/// Implementation of `OnUnbalanced` that splits the fees between burning, a treasury and the
+/// collators.
+///
+/// `BurnPercent` of the fees is burnt and `TreasuryPercent` of them is passed on to `ToTreasury`.
+/// The rest of the fees, together with the whole tip, is passed on to `ToCollators`. If the two
+/// percentages add up to more than 100%, burning takes precedence.
+///
+/// With the default parameters nothing is burnt and everything goes to `ToStakingPot`.
+pub struct DealWithFees<
+	R,
+	BurnPercent = (),
+	TreasuryPercent = (),
+	ToTreasury = (),
+	ToCollators = ToStakingPot<R>,
+>(PhantomData<(R, BurnPercent, TreasuryPercent, ToTreasury, ToCollators)>);
+impl<R, BurnPercent, TreasuryPercent, ToTreasury, ToCollators> OnUnbalanced<NegativeImbalance<R>>
+	for DealWithFees<R, BurnPercent, TreasuryPercent, ToTreasury, ToCollators>
 where
-	R: pallet_balances::Config + pallet_collator_selection::Config,
-	AccountIdOf<R>: From<polkadot_primitives::AccountId> + Into<polkadot_primitives::AccountId>,
-	<R as frame_system::Config>::RuntimeEvent: From<pallet_balances::Event<R>>,
+	R: pallet_balances::Config,
+	BurnPercent: Get<Percent>,
+	TreasuryPercent: Get<Percent>,
+	ToTreasury: OnUnbalanced<NegativeImbalance<R>>,
+	ToCollators: OnUnbalanced<NegativeImbalance<R>>,
 {
 	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance<R>>) {
-		if let Some(mut fees) = fees_then_tips.next() {
+		if let Some(fees) = fees_then_tips.next() {
+			let (to_treasury, mut to_collators) = Self::split(fees);
 			if let Some(tips) = fees_then_tips.next() {
-				tips.merge_into(&mut fees);
+				tips.merge_into(&mut to_collators);
 			}
-			<ToStakingPot<R> as OnUnbalanced<_>>::on_unbalanced(fees);
+			ToTreasury::on_unbalanced(to_treasury);
+			ToCollators::on_unbalanced(to_collators);
 		}
 	}
+
+	fn on_nonzero_unbalanced(fees: NegativeImbalance<R>) {
+		let (to_treasury, to_collators) = Self::split(fees);
+		ToTreasury::on_unbalanced(to_treasury);
+		ToCollators::on_unbalanced(to_collators);
+	}
+}
+
+impl<R, BurnPercent, TreasuryPercent, ToTreasury, ToCollators>
+	DealWithFees<R, BurnPercent, TreasuryPercent, ToTreasury, ToCollators>
+where
+	R: pallet_balances::Config,
+	BurnPercent: Get<Percent>,
+	TreasuryPercent: Get<Percent>,
+{
+	/// Burns `BurnPercent` of `fees` and splits the rest into the treasury and collator parts.
+	fn split(fees: NegativeImbalance<R>) -> (NegativeImbalance<R>, NegativeImbalance<R>) {
+		let amount = fees.peek();
+		let (burnt, rest) = fees.split(BurnPercent::get().mul_floor(amount));
+		// Dropping the imbalance reduces the total issuance.
+		drop(burnt);
+		rest.split(TreasuryPercent::get().mul_floor(amount))
+	}
 }
 
 /// A `HandleCredit` implementation that naively transfers the fees to the block author.
@@ -138,6 +178,7 @@ mod tests {
 
 	type Block = frame_system::mocking::MockBlock<Test>;
 	const TEST_ACCOUNT: AccountId = AccountId::new([1; 32]);
+	const TREASURY: AccountId = AccountId::new([2; 32]);
 
 	frame_support::construct_runtime!(
 		pub enum Test
@@ -230,6 +271,12 @@ mod tests {
 		type ValidatorIdOf = IdentityCollator;
 		type ValidatorRegistration = IsRegistered;
 		type KickThreshold = ();
+		type MaxOfflineSessions = ();
+		type OfflineSlash = ();
+		type MaxDelegatorsPerCandidate = ();
+		type MinDelegation = ();
+		type DelegationUnbondingPeriod = ();
+		type MaxUnbondingChunks = ();
 		type WeightInfo = ();
 	}
 
@@ -238,6 +285,19 @@ mod tests {
 		type EventHandler = ();
 	}
 
+	pub struct ToTreasuryAccount;
+	impl OnUnbalanced<NegativeImbalance<Test>> for ToTreasuryAccount {
+		fn on_nonzero_unbalanced(amount: NegativeImbalance<Test>) {
+			Balances::resolve_creating(&TREASURY, amount);
+		}
+	}
+
+	parameter_types! {
+		pub const TwentyPercent: Percent = Percent::from_percent(20);
+		pub const ThirtyPercent: Percent = Percent::from_percent(30);
+		pub const NinetyPercent: Percent = Percent::from_percent(90);
+	}
+
 	pub fn new_test_ext() -> sp_io::TestExternalities {
 		let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
 		// We use default for brevity, but you can configure as desired if needed.
@@ -255,13 +315,62 @@ mod tests {
 
 			assert_eq!(Balances::free_balance(TEST_ACCOUNT), 0);
 
-			DealWithFees::on_unbalanceds(vec![fee, tip].into_iter());
+			DealWithFees::<Test>::on_unbalanceds(vec![fee, tip].into_iter());
 
 			// Author gets 100% of tip and 100% of fee = 30
 			assert_eq!(Balances::free_balance(CollatorSelection::account_id()), 30);
 		});
 	}
 
+	#[test]
+	fn fees_are_split_between_burn_treasury_and_collators() {
+		new_test_ext().execute_with(|| {
+			let fee = Balances::issue(100);
+			let tip = Balances::issue(20);
+			assert_eq!(Balances::total_issuance(), 120);
+
+			DealWithFees::<Test, TwentyPercent, ThirtyPercent, ToTreasuryAccount>::on_unbalanceds(
+				vec![fee, tip].into_iter(),
+			);
+
+			// 20% of the fee is burnt, 30% goes to the treasury and the rest along with the tip
+			// goes to the collators.
+			assert_eq!(Balances::total_issuance(), 100);
+			assert_eq!(Balances::free_balance(TREASURY), 30);
+			assert_eq!(Balances::free_balance(CollatorSelection::account_id()), 70);
+		});
+	}
+
+	#[test]
+	fn burning_takes_precedence_over_treasury() {
+		new_test_ext().execute_with(|| {
+			let fee = Balances::issue(100);
+
+			DealWithFees::<Test, NinetyPercent, ThirtyPercent, ToTreasuryAccount>::on_unbalanceds(
+				vec![fee].into_iter(),
+			);
+
+			assert_eq!(Balances::total_issuance(), 10);
+			assert_eq!(Balances::free_balance(TREASURY), 10);
+			assert_eq!(Balances::free_balance(CollatorSelection::account_id()), 0);
+		});
+	}
+
+	#[test]
+	fn single_imbalance_is_split_too() {
+		new_test_ext().execute_with(|| {
+			let fee = Balances::issue(100);
+
+			DealWithFees::<Test, TwentyPercent, ThirtyPercent, ToTreasuryAccount>::on_unbalanced(
+				fee,
+			);
+
+			assert_eq!(Balances::total_issuance(), 80);
+			assert_eq!(Balances::free_balance(TREASURY), 30);
+			assert_eq!(Balances::free_balance(CollatorSelection::account_id()), 50);
+		});
+	}
+
 	#[test]
 	fn assets_from_filters_correctly() {
 		parameter_types! {
@@ -15,8 +15,12 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod dry_run_api;
 pub mod impls;
+pub mod message_queue;
+pub mod session_keys;
 pub mod xcm_config;
+pub mod xcm_payment_api;
 pub use constants::*;
 pub use opaque::*;
 pub use types::*;
@@ -0,0 +1,73 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for servicing the inbound XCM messages with the `pallet-message-queue`.
+
+pub use cumulus_primitives_core::AggregateMessageOrigin;
+use cumulus_primitives_core::ParaId;
+use sp_runtime::traits::{Convert, MaybeEquivalence};
+
+/// Converts the `ParaId` of a sibling parachain into the origin of its message queue and back.
+pub struct ParaIdToSibling;
+
+impl Convert<ParaId, AggregateMessageOrigin> for ParaIdToSibling {
+	fn convert(para_id: ParaId) -> AggregateMessageOrigin {
+		AggregateMessageOrigin::Sibling(para_id)
+	}
+}
+
+impl MaybeEquivalence<AggregateMessageOrigin, ParaId> for ParaIdToSibling {
+	fn convert(origin: &AggregateMessageOrigin) -> Option<ParaId> {
+		match origin {
+			AggregateMessageOrigin::Sibling(para_id) => Some(*para_id),
+			_ => None,
+		}
+	}
+
+	fn convert_back(para_id: &ParaId) -> Option<AggregateMessageOrigin> {
+		Some(AggregateMessageOrigin::Sibling(*para_id))
+	}
+}
+
+/// The `MessageProcessor` of the `pallet-message-queue`, that executes messages of all origins
+/// with the `XcmExecutor` of the `XcmConfig`.
+///
+/// Messages from the sibling parachains are subject to the `cumulus-pallet-xcmp-queue` policies
+/// (suspended channels and rate limits) before they're executed.
+pub type XcmMessageProcessor<Runtime, XcmConfig> = cumulus_pallet_xcmp_queue::ProcessXcmpMessage<
+	Runtime,
+	xcm_builder::ProcessXcmMessage<
+		AggregateMessageOrigin,
+		xcm_executor::XcmExecutor<XcmConfig>,
+		<Runtime as frame_system::Config>::RuntimeCall,
+	>,
+>;
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn para_id_to_sibling_works() {
+		let para_id = ParaId::from(1000);
+		let origin = <ParaIdToSibling as Convert<_, _>>::convert(para_id);
+		assert_eq!(origin, AggregateMessageOrigin::Sibling(para_id));
+		assert_eq!(<ParaIdToSibling as MaybeEquivalence<_, _>>::convert(&origin), Some(para_id));
+		assert_eq!(
+			<ParaIdToSibling as MaybeEquivalence<_, _>>::convert(&AggregateMessageOrigin::Parent),
+			None
+		);
+	}
+}
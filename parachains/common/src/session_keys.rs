@@ -0,0 +1,228 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Proof of possession of session keys.
+//!
+//! `pallet_session::set_keys` takes a `proof` that the session pallet itself ignores. With
+//! [`CheckSessionKeysProof`] in the `SignedExtra` of a runtime, `set_keys` is only accepted if the
+//! proof holds a signature of every session key over the account id of the caller, so that
+//! nobody can register, and thereby block, a key that somebody else controls.
+//!
+//! The extension only sees the calls of the transaction itself. [`NoNestedSetKeys`] is the base
+//! call filter that rejects the calls dispatching `set_keys` on their own, e.g. batches, so that
+//! the proof cannot be bypassed by them. Neither does it see the calls that XCM `Transact`
+//! dispatches, which [`NoSetKeys`] keeps from calling `set_keys` at all.
+
+use codec::{Decode, Encode};
+use core::marker::PhantomData;
+use frame_support::{
+	dispatch::DispatchInfo,
+	traits::{Contains, IsSubType},
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, SignedExtension},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	RuntimeAppPublic,
+};
+use sp_std::vec::Vec;
+
+/// Session keys whose owner can prove that it controls them.
+pub trait ProveOwnership<AccountId> {
+	/// Whether `proof` holds a signature of every key over `owner`.
+	fn verify_ownership(&self, owner: &AccountId, proof: &[u8]) -> bool;
+}
+
+/// Whether `proof` starts with a signature of `key` over `owner`. The signature is removed from
+/// `proof`, so the signatures of several keys can be checked one after the other.
+pub fn verify_key<Key: RuntimeAppPublic, AccountId: Encode>(
+	key: &Key,
+	owner: &AccountId,
+	proof: &mut &[u8],
+) -> bool {
+	let Ok(signature) = Key::Signature::decode(proof) else { return false };
+	owner.using_encoded(|owner| key.verify(&owner, &signature))
+}
+
+/// Rejects `pallet_session::set_keys` calls whose proof does not show that the caller controls
+/// the session keys, see [`ProveOwnership`].
+///
+/// Only calls that are dispatched directly by a transaction, not the ones nested in other calls or
+/// dispatched by XCM, are checked. The latter must be rejected by [`NoNestedSetKeys`] and
+/// [`NoSetKeys`].
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckSessionKeysProof<T>(PhantomData<T>);
+
+impl<T> CheckSessionKeysProof<T> {
+	/// Creates a new instance of the extension.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T> Default for CheckSessionKeysProof<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> sp_std::fmt::Debug for CheckSessionKeysProof<T> {
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "CheckSessionKeysProof")
+	}
+}
+
+impl<T> SignedExtension for CheckSessionKeysProof<T>
+where
+	T: pallet_session::Config + Send + Sync,
+	T::Keys: ProveOwnership<T::AccountId>,
+	<T as frame_system::Config>::RuntimeCall:
+		Dispatchable<Info = DispatchInfo> + IsSubType<pallet_session::Call<T>>,
+{
+	const IDENTIFIER: &'static str = "CheckSessionKeysProof";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		if let Some(pallet_session::Call::set_keys { keys, proof }) = call.is_sub_type() {
+			if !keys.verify_ownership(who, proof) {
+				return Err(InvalidTransaction::BadProof.into())
+			}
+		}
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		self.validate(who, call, info, len).map(|_| ())
+	}
+}
+
+/// Calls that dispatch other calls, e.g. batches, proxy and multisig calls.
+pub trait NestedCalls {
+	/// The calls that this call dispatches.
+	fn nested_calls(&self) -> Vec<&Self>;
+}
+
+/// Base call filter that rejects the calls dispatching `pallet_session::set_keys`, at any depth,
+/// since [`CheckSessionKeysProof`] does not check the proof of those.
+pub struct NoNestedSetKeys<T>(PhantomData<T>);
+
+impl<T> Contains<<T as frame_system::Config>::RuntimeCall> for NoNestedSetKeys<T>
+where
+	T: pallet_session::Config,
+	<T as frame_system::Config>::RuntimeCall: NestedCalls + IsSubType<pallet_session::Call<T>>,
+{
+	fn contains(call: &<T as frame_system::Config>::RuntimeCall) -> bool {
+		call.nested_calls().into_iter().all(|nested| {
+			!matches!(nested.is_sub_type(), Some(pallet_session::Call::set_keys { .. })) &&
+				Self::contains(nested)
+		})
+	}
+}
+
+/// Call filter for XCM `Transact` that rejects `pallet_session::set_keys` and the calls that `F`
+/// rejects.
+///
+/// Signed extensions do not run for the calls of XCM messages, so nothing would check that the
+/// dispatching location, e.g. the sovereign account of a sibling, controls the keys. The nested
+/// `set_keys` calls are rejected by [`NoNestedSetKeys`] already.
+pub struct NoSetKeys<T, F>(PhantomData<(T, F)>);
+
+impl<T, F> Contains<<T as frame_system::Config>::RuntimeCall> for NoSetKeys<T, F>
+where
+	T: pallet_session::Config,
+	<T as frame_system::Config>::RuntimeCall: IsSubType<pallet_session::Call<T>>,
+	F: Contains<<T as frame_system::Config>::RuntimeCall>,
+{
+	fn contains(call: &<T as frame_system::Config>::RuntimeCall) -> bool {
+		!matches!(call.is_sub_type(), Some(pallet_session::Call::set_keys { .. })) &&
+			F::contains(call)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{AccountId, AuraId};
+	use sp_core::{sr25519, Pair};
+
+	fn signed_by(pair: &sr25519::Pair, owner: &AccountId) -> Vec<u8> {
+		pair.sign(&owner.encode()).encode()
+	}
+
+	#[test]
+	fn verify_key_accepts_signature_of_the_key_over_the_owner() {
+		let pair = sr25519::Pair::from_seed(&[1u8; 32]);
+		let key = AuraId::from(pair.public());
+		let owner = AccountId::from([7u8; 32]);
+
+		let proof = signed_by(&pair, &owner);
+		assert!(verify_key(&key, &owner, &mut &proof[..]));
+	}
+
+	#[test]
+	fn verify_key_rejects_other_owners_and_keys() {
+		let pair = sr25519::Pair::from_seed(&[1u8; 32]);
+		let other = sr25519::Pair::from_seed(&[2u8; 32]);
+		let key = AuraId::from(pair.public());
+		let owner = AccountId::from([7u8; 32]);
+		let someone_else = AccountId::from([8u8; 32]);
+
+		// signed for another account, e.g. copied from somebody else's `set_keys`.
+		let proof = signed_by(&pair, &someone_else);
+		assert!(!verify_key(&key, &owner, &mut &proof[..]));
+
+		// signed by another key.
+		let proof = signed_by(&other, &owner);
+		assert!(!verify_key(&key, &owner, &mut &proof[..]));
+
+		// no signature at all.
+		assert!(!verify_key(&key, &owner, &mut &[][..]));
+	}
+
+	#[test]
+	fn verify_key_consumes_the_signature() {
+		let first = sr25519::Pair::from_seed(&[1u8; 32]);
+		let second = sr25519::Pair::from_seed(&[2u8; 32]);
+		let owner = AccountId::from([7u8; 32]);
+
+		let proof = [signed_by(&first, &owner), signed_by(&second, &owner)].concat();
+		let mut input = &proof[..];
+		assert!(verify_key(&AuraId::from(first.public()), &owner, &mut input));
+		assert!(verify_key(&AuraId::from(second.public()), &owner, &mut input));
+		assert!(input.is_empty());
+	}
+}
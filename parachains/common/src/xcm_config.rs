@@ -1,12 +1,21 @@
 use crate::impls::AccountIdOf;
 use core::marker::PhantomData;
 use frame_support::{
-	traits::{fungibles::Inspect, tokens::ConversionToAssetBalance, ContainsPair},
+	parameter_types,
+	traits::{
+		fungibles::Inspect, tokens::ConversionToAssetBalance, ConstU32, ContainsPair, Everything,
+	},
 	weights::Weight,
 };
 use log;
 use sp_runtime::traits::Get;
 use xcm::latest::prelude::*;
+use xcm_builder::{
+	AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses, AllowSubscriptionsFrom,
+	AllowTopLevelPaidExecutionFrom, DenyReserveTransferToRelayChain, DenyThenTry, TakeWeightCredit,
+	TrailingSetTopicAsId, WithComputedOrigin,
+};
+use xcm_executor::traits::{Properties, ShouldExecute};
 
 /// A `ChargeFeeInFungibles` implementation that converts the output of
 /// a given WeightToFee implementation an amount charged in
@@ -63,3 +72,181 @@ impl<Location: Get<MultiLocation>> ContainsPair<MultiAsset, MultiLocation>
 		matches!(asset.id, Concrete(ref id) if id == origin && origin == &Location::get())
 	}
 }
+
+/// A `ShouldExecute` wrapper that logs the messages rejected by `Barrier`, tagged with `Name`.
+///
+/// The decision of `Barrier` is passed on unchanged.
+pub struct LogRejections<Name, Barrier>(PhantomData<(Name, Barrier)>);
+impl<Name: Get<&'static str>, Barrier: ShouldExecute> ShouldExecute
+	for LogRejections<Name, Barrier>
+{
+	fn should_execute<RuntimeCall>(
+		origin: &MultiLocation,
+		instructions: &mut [Instruction<RuntimeCall>],
+		max_weight: Weight,
+		properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		Barrier::should_execute(origin, instructions, max_weight, properties).map_err(|error| {
+			log::debug!(
+				target: "xcm::barriers",
+				"{} rejected message from {:?}: {:?}",
+				Name::get(),
+				origin,
+				error,
+			);
+			error
+		})
+	}
+}
+
+parameter_types! {
+	pub const DenyBarrierName: &'static str = "DenyReserveTransferToRelayChain";
+	pub const AllowBarrierName: &'static str = "SystemParachainAllowBarriers";
+}
+
+/// The barrier stack shared by the system parachains.
+///
+/// Reserve transfers to the relay chain are always denied. Otherwise the message must either
+/// be covered by the local weight credit, be an expected response to a `ResponseHandler` query,
+/// or, with its origin computed against `UniversalLocation`:
+/// - pay for its execution upfront,
+/// - ask for unpaid execution from a location in `UnpaidExecutionFrom`,
+/// - subscribe for version changes from a location in `SubscriptionsFrom`.
+///
+/// Runtimes allowing some more messages may pass further barriers as `Extra`. Rejections are
+/// logged under the `xcm::barriers` target.
+pub type SystemParachainBarrier<
+	ResponseHandler,
+	UniversalLocation,
+	UnpaidExecutionFrom,
+	SubscriptionsFrom,
+	Extra = (),
+> = TrailingSetTopicAsId<
+	DenyThenTry<
+		LogRejections<DenyBarrierName, DenyReserveTransferToRelayChain>,
+		LogRejections<
+			AllowBarrierName,
+			(
+				TakeWeightCredit,
+				AllowKnownQueryResponses<ResponseHandler>,
+				WithComputedOrigin<
+					(
+						AllowTopLevelPaidExecutionFrom<Everything>,
+						AllowExplicitUnpaidExecutionFrom<UnpaidExecutionFrom>,
+						AllowSubscriptionsFrom<SubscriptionsFrom>,
+					),
+					UniversalLocation,
+					ConstU32<8>,
+				>,
+				Extra,
+			),
+		>,
+	>,
+>;
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::traits::{Equals, Nothing};
+
+	parameter_types! {
+		pub UniversalLocation: InteriorMultiLocation =
+			X2(GlobalConsensus(Polkadot), Parachain(1000));
+		pub ParentLocation: MultiLocation = MultiLocation::parent();
+		pub SiblingLocation: MultiLocation = MultiLocation::new(1, X1(Parachain(2000)));
+	}
+
+	type Barrier =
+		SystemParachainBarrier<(), UniversalLocation, Equals<ParentLocation>, Everything>;
+
+	fn should_execute(
+		origin: MultiLocation,
+		mut message: Xcm<()>,
+		weight_credit: Weight,
+	) -> Result<(), ProcessMessageError> {
+		let mut properties = Properties { weight_credit, message_id: None };
+		Barrier::should_execute(
+			&origin,
+			&mut message.0,
+			Weight::from_parts(1_000, 1_000),
+			&mut properties,
+		)
+	}
+
+	fn relay_assets() -> MultiAssets {
+		vec![(Parent, 100u128).into()].into()
+	}
+
+	#[test]
+	fn reserve_transfers_to_relay_are_denied() {
+		let message = Xcm(vec![TransferReserveAsset {
+			assets: relay_assets(),
+			dest: Parent.into(),
+			xcm: Xcm(vec![]),
+		}]);
+		// Even with enough weight credit to pay for it.
+		assert_eq!(
+			should_execute(Here.into(), message, Weight::MAX),
+			Err(ProcessMessageError::Unsupported),
+		);
+	}
+
+	#[test]
+	fn weight_credit_allows_local_messages() {
+		let message = Xcm(vec![ClearOrigin]);
+		assert_eq!(should_execute(Here.into(), message.clone(), Weight::MAX), Ok(()));
+		assert!(should_execute(Here.into(), message, Weight::zero()).is_err());
+	}
+
+	#[test]
+	fn paid_execution_is_allowed_from_everywhere() {
+		let message = Xcm(vec![
+			WithdrawAsset(relay_assets()),
+			BuyExecution { fees: (Parent, 100u128).into(), weight_limit: Unlimited },
+			DepositAsset { assets: AllCounted(1).into(), beneficiary: Here.into() },
+		]);
+		assert_eq!(should_execute(SiblingLocation::get(), message.clone(), Weight::zero()), Ok(()));
+		assert_eq!(should_execute(ParentLocation::get(), message, Weight::zero()), Ok(()));
+	}
+
+	#[test]
+	fn unpaid_execution_is_only_allowed_from_configured_locations() {
+		let message =
+			Xcm(vec![UnpaidExecution { weight_limit: Unlimited, check_origin: None }, ClearOrigin]);
+		assert_eq!(should_execute(ParentLocation::get(), message.clone(), Weight::zero()), Ok(()));
+		assert!(should_execute(SiblingLocation::get(), message, Weight::zero()).is_err());
+	}
+
+	#[test]
+	fn trailing_topic_is_accepted() {
+		let message = Xcm(vec![
+			UnpaidExecution { weight_limit: Unlimited, check_origin: None },
+			ClearOrigin,
+			SetTopic([1; 32]),
+		]);
+		assert_eq!(should_execute(ParentLocation::get(), message, Weight::zero()), Ok(()));
+	}
+
+	#[test]
+	fn extra_barriers_are_tried_last() {
+		type BarrierWithExtra = SystemParachainBarrier<
+			(),
+			UniversalLocation,
+			Nothing,
+			Nothing,
+			xcm_builder::AllowUnpaidExecutionFrom<Everything>,
+		>;
+
+		let mut message: Xcm<()> = Xcm(vec![ClearOrigin]);
+		let mut properties = Properties { weight_credit: Weight::zero(), message_id: None };
+		assert_eq!(
+			BarrierWithExtra::should_execute(
+				&SiblingLocation::get(),
+				&mut message.0,
+				Weight::from_parts(1_000, 1_000),
+				&mut properties,
+			),
+			Ok(()),
+		);
+	}
+}
@@ -0,0 +1,150 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API for estimating the fees of XCM programs executed on the chain.
+//!
+//! The fees are quoted with the `Weigher` and the `Trader` of the XCM executor configuration, so
+//! that they match what the executor charges for a `BuyExecution` of the program.
+
+use codec::{Decode, Encode};
+use frame_support::weights::Weight;
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+use xcm::{latest::prelude::*, IntoVersion, VersionedMultiLocation, VersionedXcm};
+use xcm_executor::{
+	traits::{WeightBounds, WeightTrader},
+	Assets, XcmExecutor,
+};
+
+/// The weight, whose fee decides whether an asset is accepted for fee payment, see
+/// [`acceptable_payment_assets`].
+pub const PROBE_WEIGHT: Weight = Weight::from_parts(1_000_000_000, 64 * 1024);
+
+/// The errors of the [`XcmPaymentApi`].
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum XcmPaymentApiError {
+	/// The message or the location can't be converted to the XCM version of the chain, or the
+	/// result can't be converted to the requested XCM version.
+	VersionedConversionFailed,
+	/// The weight of the message can't be computed, e.g. because it has too many instructions.
+	WeightNotComputable,
+	/// The asset is not accepted for fee payment.
+	AssetNotFound,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API for estimating the fees of XCM programs executed on the chain.
+	pub trait XcmPaymentApi {
+		/// Returns the locations of the assets, in which fees can be paid, in `xcm_version`.
+		fn query_acceptable_payment_assets(
+			xcm_version: u32,
+		) -> Result<Vec<VersionedMultiLocation>, XcmPaymentApiError>;
+
+		/// Returns the weight of executing `message`.
+		fn query_xcm_weight(message: VersionedXcm<()>) -> Result<Weight, XcmPaymentApiError>;
+
+		/// Returns the fee for `weight`, in the asset with the location `asset`.
+		fn query_weight_to_asset_fee(
+			weight: Weight,
+			asset: VersionedMultiLocation,
+		) -> Result<u128, XcmPaymentApiError>;
+
+		/// Executes `message` as if it was received from `origin` and returns the outcome.
+		///
+		/// Nothing of the execution is persisted, the outcome shows whether the fees can be paid
+		/// and how much of the weight is used.
+		fn dry_run_xcm(
+			origin: VersionedMultiLocation,
+			message: VersionedXcm<()>,
+		) -> Result<Outcome, XcmPaymentApiError>;
+	}
+}
+
+/// Returns the `candidates`, in which fees can be paid with the `Trader` of `Config`, in
+/// `xcm_version`.
+pub fn acceptable_payment_assets<Config: xcm_executor::Config>(
+	candidates: impl IntoIterator<Item = MultiLocation>,
+	xcm_version: u32,
+) -> Result<Vec<VersionedMultiLocation>, XcmPaymentApiError> {
+	candidates
+		.into_iter()
+		.filter(|asset| weight_to_asset_fee::<Config>(PROBE_WEIGHT, *asset).is_ok())
+		.map(|asset| {
+			VersionedMultiLocation::from(asset)
+				.into_version(xcm_version)
+				.map_err(|_| XcmPaymentApiError::VersionedConversionFailed)
+		})
+		.collect()
+}
+
+/// Returns the weight of `message` according to the `Weigher` of `Config`.
+pub fn query_xcm_weight<Config: xcm_executor::Config>(
+	message: VersionedXcm<()>,
+) -> Result<Weight, XcmPaymentApiError> {
+	let mut message = runtime_message::<Config>(message)?;
+	Config::Weigher::weight(&mut message).map_err(|_| XcmPaymentApiError::WeightNotComputable)
+}
+
+/// Returns the fee for `weight` in `asset`, as charged by the `Trader` of `Config`.
+pub fn query_weight_to_asset_fee<Config: xcm_executor::Config>(
+	weight: Weight,
+	asset: VersionedMultiLocation,
+) -> Result<u128, XcmPaymentApiError> {
+	let asset = MultiLocation::try_from(asset)
+		.map_err(|_| XcmPaymentApiError::VersionedConversionFailed)?;
+	weight_to_asset_fee::<Config>(weight, asset)
+}
+
+/// Executes `message` from `origin` with the XCM executor of `Config`.
+///
+/// Must only be called from within a runtime API, whose changes to the state are discarded.
+pub fn dry_run_xcm<Config: xcm_executor::Config>(
+	origin: VersionedMultiLocation,
+	message: VersionedXcm<()>,
+) -> Result<Outcome, XcmPaymentApiError> {
+	let origin = MultiLocation::try_from(origin)
+		.map_err(|_| XcmPaymentApiError::VersionedConversionFailed)?;
+	let message = runtime_message::<Config>(message)?;
+	let hash = message.using_encoded(sp_io::hashing::blake2_256);
+	Ok(XcmExecutor::<Config>::execute_xcm(origin, message, hash, Weight::MAX))
+}
+
+fn runtime_message<Config: xcm_executor::Config>(
+	message: VersionedXcm<()>,
+) -> Result<Xcm<Config::RuntimeCall>, XcmPaymentApiError> {
+	let message =
+		Xcm::<()>::try_from(message).map_err(|_| XcmPaymentApiError::VersionedConversionFailed)?;
+	Ok(Xcm::<Config::RuntimeCall>::from(message))
+}
+
+/// Buys `weight` with as much of `asset` as possible and returns the amount that is charged.
+///
+/// The trader is dropped at the end, which may deposit the fee. This is fine within a runtime API.
+fn weight_to_asset_fee<Config: xcm_executor::Config>(
+	weight: Weight,
+	asset: MultiLocation,
+) -> Result<u128, XcmPaymentApiError> {
+	let offered = u128::MAX;
+	let payment: Assets = MultiAsset { id: Concrete(asset), fun: Fungible(offered) }.into();
+	let context = XcmContext { origin: None, message_id: [0; 32], topic: None };
+
+	let mut trader = Config::Trader::new();
+	let unused = trader
+		.buy_weight(weight, payment, &context)
+		.map_err(|_| XcmPaymentApiError::AssetNotFound)?;
+	let left = unused.fungible.get(&Concrete(asset)).copied().unwrap_or_default();
+	Ok(offered.saturating_sub(left))
+}
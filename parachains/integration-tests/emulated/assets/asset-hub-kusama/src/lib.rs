@@ -40,6 +40,9 @@ pub use integration_tests_common::{
 	PenpalKusamaBSender, PenpalPolkadotA, PenpalPolkadotAReceiver, PenpalPolkadotASender, Polkadot,
 	PolkadotMockNet, PolkadotPallet, PolkadotReceiver, PolkadotSender,
 };
+pub use integration_tests_flows::{
+	test_reserve_transfer, test_teleport, test_transact, TransactFlow, TransferFlow,
+};
 pub use parachains_common::Balance;
 pub use polkadot_core_primitives::InboundDownwardMessage;
 pub use polkadot_parachain::primitives::{HrmpChannelId, Id};
@@ -51,8 +54,8 @@ pub use xcm::{
 };
 pub use xcm_emulator::{
 	assert_expected_events, bx, cumulus_pallet_dmp_queue, helpers::weight_within_threshold,
-	AccountId32Junction, Chain, ParaId, Parachain as Para, RelayChain as Relay, Test, TestArgs,
-	TestContext, TestExt, TestExternalities,
+	AccountId32Junction, Chain, ParaId, Parachain as Para, RelayChain as Relay, TestExt,
+	TestExternalities,
 };
 
 pub const ASSET_ID: u32 = 1;
@@ -60,45 +63,5 @@ pub const ASSET_MIN_BALANCE: u128 = 1000;
 // `Assets` pallet index
 pub const ASSETS_PALLET_ID: u8 = 50;
 
-pub type RelayToSystemParaTest = Test<Kusama, AssetHubKusama>;
-pub type SystemParaToRelayTest = Test<AssetHubKusama, Kusama>;
-pub type SystemParaToParaTest = Test<AssetHubKusama, PenpalKusamaA>;
-
-/// Returns a `TestArgs` instance to de used for the Relay Chain accross integraton tests
-pub fn relay_test_args(amount: Balance) -> TestArgs {
-	TestArgs {
-		dest: Kusama::child_location_of(AssetHubKusama::para_id()),
-		beneficiary: AccountId32Junction {
-			network: None,
-			id: AssetHubKusamaReceiver::get().into(),
-		}
-		.into(),
-		amount,
-		assets: (Here, amount).into(),
-		asset_id: None,
-		fee_asset_item: 0,
-		weight_limit: WeightLimit::Unlimited,
-	}
-}
-
-/// Returns a `TestArgs` instance to de used for the System Parachain accross integraton tests
-pub fn system_para_test_args(
-	dest: MultiLocation,
-	beneficiary_id: AccountId32,
-	amount: Balance,
-	assets: MultiAssets,
-	asset_id: Option<u32>,
-) -> TestArgs {
-	TestArgs {
-		dest,
-		beneficiary: AccountId32Junction { network: None, id: beneficiary_id.into() }.into(),
-		amount,
-		assets,
-		asset_id,
-		fee_asset_item: 0,
-		weight_limit: WeightLimit::Unlimited,
-	}
-}
-
 #[cfg(test)]
 mod tests;
@@ -20,85 +20,66 @@ use crate::*;
 /// when `OriginKind::Superuser` and signer is `sudo`
 #[test]
 fn send_transact_sudo_from_relay_to_system_para_works() {
-	// Init tests variables
-	let root_origin = <Kusama as Chain>::RuntimeOrigin::root();
-	let system_para_destination = Kusama::child_location_of(AssetHubKusama::para_id()).into();
-	let asset_owner: AccountId = AssetHubKusamaSender::get().into();
-	let xcm = AssetHubKusama::force_create_asset_xcm(
-		OriginKind::Superuser,
-		ASSET_ID,
-		asset_owner.clone(),
-		true,
-		1000,
-	);
-	// Send XCM message from Relay Chain
-	Kusama::execute_with(|| {
-		assert_ok!(<Kusama as KusamaPallet>::XcmPallet::send(
-			root_origin,
-			bx!(system_para_destination),
-			bx!(xcm),
-		));
-
-		Kusama::assert_xcm_pallet_sent();
-	});
-
-	// Receive XCM message in Assets Parachain
-	AssetHubKusama::execute_with(|| {
-		type RuntimeEvent = <AssetHubKusama as Chain>::RuntimeEvent;
-
-		AssetHubKusama::assert_dmp_queue_complete(Some(Weight::from_parts(1_019_445_000, 200_000)));
-
-		assert_expected_events!(
-			AssetHubKusama,
-			vec![
-				RuntimeEvent::Assets(pallet_assets::Event::ForceCreated { asset_id, owner }) => {
-					asset_id: *asset_id == ASSET_ID,
-					owner: *owner == asset_owner,
-				},
-			]
-		);
-
-		assert!(<AssetHubKusama as AssetHubKusamaPallet>::Assets::asset_exists(ASSET_ID));
-	});
+	let asset_owner: AccountId = AssetHubKusamaSender::get();
+	let call = AssetHubKusama::force_create_asset_call(ASSET_ID, asset_owner.clone(), true, 1000);
+
+	test_transact(Kusama, AssetHubKusama, call)
+		.assert_sent()
+		.assert_destination_complete(Some(Weight::from_parts(1_019_445_000, 200_000)))
+		.assert_on_destination(move || {
+			type RuntimeEvent = <AssetHubKusama as Chain>::RuntimeEvent;
+
+			assert_expected_events!(
+				AssetHubKusama,
+				vec![
+					RuntimeEvent::Assets(pallet_assets::Event::ForceCreated { asset_id, owner }) => {
+						asset_id: *asset_id == ASSET_ID,
+						owner: *owner == asset_owner,
+					},
+				]
+			);
+
+			assert!(<AssetHubKusama as AssetHubKusamaPallet>::Assets::asset_exists(ASSET_ID));
+		})
+		.run();
+}
+
+/// A `Transact` from the Relay Chain, that requires more weight than the message queue of the
+/// System Parachain may ever use in a block, should be kept as overweight instead of executed
+#[test]
+fn send_overweight_transact_from_relay_to_system_para_is_kept_as_overweight() {
+	let asset_owner: AccountId = AssetHubKusamaSender::get();
+	let call = AssetHubKusama::force_create_asset_call(ASSET_ID, asset_owner, true, 1000);
+
+	test_transact(Kusama, AssetHubKusama, call)
+		// the whole block weight
+		.require_weight_at_most(Weight::from_parts(500_000_000_000, 200_000))
+		.assert_sent()
+		.assert_destination_overweight()
+		.assert_on_destination(|| {
+			assert!(!<AssetHubKusama as AssetHubKusamaPallet>::Assets::asset_exists(ASSET_ID));
+		})
+		.run();
 }
 
 /// Relay Chain shouldn't be able to execute `Transact` instructions in System Parachain
 /// when `OriginKind::Native`
 #[test]
 fn send_transact_native_from_relay_to_system_para_fails() {
-	// Init tests variables
-	let signed_origin = <Kusama as Chain>::RuntimeOrigin::signed(KusamaSender::get().into());
-	let system_para_destination = Kusama::child_location_of(AssetHubKusama::para_id()).into();
-	let asset_owner = AssetHubKusamaSender::get().into();
-	let xcm = AssetHubKusama::force_create_asset_xcm(
-		OriginKind::Native,
-		ASSET_ID,
-		asset_owner,
-		true,
-		1000,
-	);
-
-	// Send XCM message from Relay Chain
-	Kusama::execute_with(|| {
-		assert_err!(
-			<Kusama as KusamaPallet>::XcmPallet::send(
-				signed_origin,
-				bx!(system_para_destination),
-				bx!(xcm)
-			),
-			DispatchError::BadOrigin
-		);
-	});
+	let asset_owner = AssetHubKusamaSender::get();
+	let call = AssetHubKusama::force_create_asset_call(ASSET_ID, asset_owner, true, 1000);
+
+	test_transact(Kusama, AssetHubKusama, call)
+		.signed_by(KusamaSender::get())
+		.origin_kind(OriginKind::Native)
+		.assert_send_error(DispatchError::BadOrigin)
+		.run();
 }
 
 /// System Parachain shouldn't be able to execute `Transact` instructions in Relay Chain
 /// when `OriginKind::Native`
 #[test]
 fn send_transact_native_from_system_para_to_relay_fails() {
-	// Init tests variables
-	let signed_origin =
-		<AssetHubKusama as Chain>::RuntimeOrigin::signed(AssetHubKusamaSender::get().into());
-	let relay_destination = AssetHubKusama::parent_location().into();
 	let call = <Kusama as Chain>::RuntimeCall::System(frame_system::Call::<
 		<Kusama as Chain>::Runtime,
 	>::remark_with_event {
@@ -106,21 +87,12 @@ fn send_transact_native_from_system_para_to_relay_fails() {
 	})
 	.encode()
 	.into();
-	let origin_kind = OriginKind::Native;
-
-	let xcm = xcm_transact_unpaid_execution(call, origin_kind);
-
-	// Send XCM message from Relay Chain
-	AssetHubKusama::execute_with(|| {
-		assert_err!(
-			<AssetHubKusama as AssetHubKusamaPallet>::PolkadotXcm::send(
-				signed_origin,
-				bx!(relay_destination),
-				bx!(xcm)
-			),
-			DispatchError::BadOrigin
-		);
-	});
+
+	test_transact(AssetHubKusama, Kusama, call)
+		.signed_by(AssetHubKusamaSender::get())
+		.origin_kind(OriginKind::Native)
+		.assert_send_error(DispatchError::BadOrigin)
+		.run();
 }
 
 /// Parachain should be able to send XCM paying its fee with sufficient asset
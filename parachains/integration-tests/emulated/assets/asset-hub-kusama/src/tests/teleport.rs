@@ -18,34 +18,32 @@
 
 use crate::*;
 
-fn relay_origin_assertions(t: RelayToSystemParaTest) {
+fn relay_origin_assertions(amount_to_send: Balance) {
 	type RuntimeEvent = <Kusama as Chain>::RuntimeEvent;
 
-	Kusama::assert_xcm_pallet_attempted_complete(Some(Weight::from_parts(631_531_000, 7_186)));
-
 	assert_expected_events!(
 		Kusama,
 		vec![
 			// Amount to teleport is withdrawn from Sender
 			RuntimeEvent::Balances(pallet_balances::Event::Withdraw { who, amount }) => {
-				who: *who == t.sender.account_id,
-				amount: *amount == t.args.amount,
+				who: *who == KusamaSender::get(),
+				amount: *amount == amount_to_send,
 			},
 			// Amount to teleport is deposited in Relay's `CheckAccount`
 			RuntimeEvent::Balances(pallet_balances::Event::Deposit { who, amount }) => {
 				who: *who == <Kusama as KusamaPallet>::XcmPallet::check_account(),
-				amount:  *amount == t.args.amount,
+				amount:  *amount == amount_to_send,
 			},
 		]
 	);
 }
 
-fn relay_dest_assertions(t: SystemParaToRelayTest) {
+fn relay_dest_assertions(para_id: ParaId, amount_to_send: Balance) {
 	type RuntimeEvent = <Kusama as Chain>::RuntimeEvent;
 
 	Kusama::assert_ump_queue_processed(
 		true,
-		Some(AssetHubKusama::para_id()),
+		Some(para_id),
 		Some(Weight::from_parts(307_225_000, 7_186)),
 	);
 
@@ -55,32 +53,27 @@ fn relay_dest_assertions(t: SystemParaToRelayTest) {
 			// Amount is witdrawn from Relay Chain's `CheckAccount`
 			RuntimeEvent::Balances(pallet_balances::Event::Withdraw { who, amount }) => {
 				who: *who == <Kusama as KusamaPallet>::XcmPallet::check_account(),
-				amount: *amount == t.args.amount,
+				amount: *amount == amount_to_send,
 			},
 			// Amount minus fees are deposited in Receiver's account
 			RuntimeEvent::Balances(pallet_balances::Event::Deposit { who, .. }) => {
-				who: *who == t.receiver.account_id,
+				who: *who == KusamaReceiver::get(),
 			},
 		]
 	);
 }
 
-fn relay_dest_assertions_fail(_t: SystemParaToRelayTest) {
+fn relay_dest_assertions_fail(para_id: ParaId) {
 	Kusama::assert_ump_queue_processed(
 		false,
-		Some(AssetHubKusama::para_id()),
+		Some(para_id),
 		Some(Weight::from_parts(148_433_000, 3_593)),
 	);
 }
 
-fn para_origin_assertions(t: SystemParaToRelayTest) {
+fn para_origin_assertions(amount_to_send: Balance) {
 	type RuntimeEvent = <AssetHubKusama as Chain>::RuntimeEvent;
 
-	AssetHubKusama::assert_xcm_pallet_attempted_complete(Some(Weight::from_parts(
-		534_872_000,
-		7_133,
-	)));
-
 	AssetHubKusama::assert_parachain_system_ump_sent();
 
 	assert_expected_events!(
@@ -88,100 +81,78 @@ fn para_origin_assertions(t: SystemParaToRelayTest) {
 		vec![
 			// Amount is withdrawn from Sender's account
 			RuntimeEvent::Balances(pallet_balances::Event::Withdraw { who, amount }) => {
-				who: *who == t.sender.account_id,
-				amount: *amount == t.args.amount,
+				who: *who == AssetHubKusamaSender::get(),
+				amount: *amount == amount_to_send,
 			},
 		]
 	);
 }
 
-fn para_dest_assertions(t: RelayToSystemParaTest) {
+fn para_dest_assertions() {
 	type RuntimeEvent = <AssetHubKusama as Chain>::RuntimeEvent;
 
-	AssetHubKusama::assert_dmp_queue_complete(Some(Weight::from_parts(165_592_000, 0)));
-
 	assert_expected_events!(
 		AssetHubKusama,
 		vec![
 			// Amount minus fees are deposited in Receiver's account
 			RuntimeEvent::Balances(pallet_balances::Event::Deposit { who, .. }) => {
-				who: *who == t.receiver.account_id,
+				who: *who == AssetHubKusamaReceiver::get(),
 			},
 		]
 	);
 }
 
-fn relay_limited_teleport_assets(t: RelayToSystemParaTest) -> DispatchResult {
-	<Kusama as KusamaPallet>::XcmPallet::limited_teleport_assets(
-		t.signed_origin,
-		bx!(t.args.dest.into()),
-		bx!(t.args.beneficiary.into()),
-		bx!(t.args.assets.into()),
-		t.args.fee_asset_item,
-		t.args.weight_limit,
-	)
-}
+/// Teleport of native asset from Relay Chain to the System Parachain
+fn relay_to_system_para_teleport() -> TransferFlow<Kusama, AssetHubKusama> {
+	let amount_to_send: Balance = KUSAMA_ED * 1000;
 
-fn relay_teleport_assets(t: RelayToSystemParaTest) -> DispatchResult {
-	<Kusama as KusamaPallet>::XcmPallet::teleport_assets(
-		t.signed_origin,
-		bx!(t.args.dest.into()),
-		bx!(t.args.beneficiary.into()),
-		bx!(t.args.assets.into()),
-		t.args.fee_asset_item,
-	)
+	test_teleport(Kusama, AssetHubKusama, Here, amount_to_send)
+		.assert_origin_complete(Some(Weight::from_parts(631_531_000, 7_186)))
+		.assert_on_origin(move || relay_origin_assertions(amount_to_send))
+		.assert_destination_complete(Some(Weight::from_parts(165_592_000, 0)))
+		.assert_on_destination(para_dest_assertions)
+		// Sender's balance is reduced
+		.assert_sender_charged()
+		// Receiver's balance is increased
+		.assert_receiver_credited()
 }
 
-fn system_para_limited_teleport_assets(t: SystemParaToRelayTest) -> DispatchResult {
-	<AssetHubKusama as AssetHubKusamaPallet>::PolkadotXcm::limited_teleport_assets(
-		t.signed_origin,
-		bx!(t.args.dest.into()),
-		bx!(t.args.beneficiary.into()),
-		bx!(t.args.assets.into()),
-		t.args.fee_asset_item,
-		t.args.weight_limit,
-	)
+/// Teleport of native asset from System Parachain to Relay Chain, which is expected to work when
+/// there is enough balance in Relay Chain's `CheckAccount`
+fn system_para_to_relay_teleport() -> TransferFlow<AssetHubKusama, Kusama> {
+	let amount_to_send: Balance = ASSET_HUB_KUSAMA_ED * 1000;
+	let para_id = AssetHubKusama::para_id();
+
+	test_teleport(AssetHubKusama, Kusama, Parent, amount_to_send)
+		.assert_origin_complete(Some(Weight::from_parts(534_872_000, 7_133)))
+		.assert_on_origin(move || para_origin_assertions(amount_to_send))
+		.assert_on_destination(move || relay_dest_assertions(para_id, amount_to_send))
+		// Sender's balance is reduced
+		.assert_sender_charged()
+		// Receiver's balance is increased
+		.assert_receiver_credited()
 }
 
-// TODO: Uncomment when https://github.com/paritytech/polkadot/pull/7424 is merged
-// fn system_para_teleport_assets(t: SystemParaToRelayTest) -> DispatchResult {
-// 	<AssetHubKusama as AssetHubKusamaPallet>::PolkadotXcm::teleport_assets(
-// 		t.signed_origin,
-// 		bx!(t.args.dest),
-// 		bx!(t.args.beneficiary),
-// 		bx!(t.args.assets),
-// 		t.args.fee_asset_item,
-// 	)
-// }
+/// Teleport of native asset from System Parachain to Relay Chain, which is expected to fail when
+/// there is not enough balance in Relay Chain's `CheckAccount`
+fn system_para_to_relay_teleport_fails() -> TransferFlow<AssetHubKusama, Kusama> {
+	let amount_to_send: Balance = ASSET_HUB_KUSAMA_ED * 1000;
+	let para_id = AssetHubKusama::para_id();
+
+	test_teleport(AssetHubKusama, Kusama, Parent, amount_to_send)
+		.assert_origin_complete(Some(Weight::from_parts(534_872_000, 7_133)))
+		.assert_on_origin(move || para_origin_assertions(amount_to_send))
+		.assert_on_destination(move || relay_dest_assertions_fail(para_id))
+		// Sender's balance is reduced
+		.assert_sender_charged()
+		// Receiver's balance does not change
+		.assert_receiver_unchanged()
+}
 
 /// Limited Teleport of native asset from Relay Chain to the System Parachain should work
 #[test]
 fn limited_teleport_native_assets_from_relay_to_system_para_works() {
-	// Init values for Relay Chain
-	let amount_to_send: Balance = KUSAMA_ED * 1000;
-	let test_args = TestContext {
-		sender: KusamaSender::get(),
-		receiver: AssetHubKusamaReceiver::get(),
-		args: relay_test_args(amount_to_send),
-	};
-
-	let mut test = RelayToSystemParaTest::new(test_args);
-
-	let sender_balance_before = test.sender.balance;
-	let receiver_balance_before = test.receiver.balance;
-
-	test.set_assertion::<Kusama>(relay_origin_assertions);
-	test.set_assertion::<AssetHubKusama>(para_dest_assertions);
-	test.set_dispatchable::<Kusama>(relay_limited_teleport_assets);
-	test.assert();
-
-	let sender_balance_after = test.sender.balance;
-	let receiver_balance_after = test.receiver.balance;
-
-	// Sender's balance is reduced
-	assert_eq!(sender_balance_before - amount_to_send, sender_balance_after);
-	// Receiver's balance is increased
-	assert!(receiver_balance_after > receiver_balance_before);
+	relay_to_system_para_teleport().run();
 }
 
 /// Limited Teleport of native asset from System Parachain to Relay Chain
@@ -192,35 +163,7 @@ fn limited_teleport_native_assets_back_from_system_para_to_relay_works() {
 	// Dependency - Relay Chain's `CheckAccount` should have enough balance
 	limited_teleport_native_assets_from_relay_to_system_para_works();
 
-	// Init values for Relay Chain
-	let amount_to_send: Balance = ASSET_HUB_KUSAMA_ED * 1000;
-	let destination = AssetHubKusama::parent_location();
-	let beneficiary_id = KusamaReceiver::get();
-	let assets = (Parent, amount_to_send).into();
-
-	let test_args = TestContext {
-		sender: AssetHubKusamaSender::get(),
-		receiver: KusamaReceiver::get(),
-		args: system_para_test_args(destination, beneficiary_id, amount_to_send, assets, None),
-	};
-
-	let mut test = SystemParaToRelayTest::new(test_args);
-
-	let sender_balance_before = test.sender.balance;
-	let receiver_balance_before = test.receiver.balance;
-
-	test.set_assertion::<AssetHubKusama>(para_origin_assertions);
-	test.set_assertion::<Kusama>(relay_dest_assertions);
-	test.set_dispatchable::<AssetHubKusama>(system_para_limited_teleport_assets);
-	test.assert();
-
-	let sender_balance_after = test.sender.balance;
-	let receiver_balance_after = test.receiver.balance;
-
-	// Sender's balance is reduced
-	assert_eq!(sender_balance_before - amount_to_send, sender_balance_after);
-	// Receiver's balance is increased
-	assert!(receiver_balance_after > receiver_balance_before);
+	system_para_to_relay_teleport().run();
 }
 
 /// Limited Teleport of native asset from System Parachain to Relay Chain
@@ -228,65 +171,13 @@ fn limited_teleport_native_assets_back_from_system_para_to_relay_works() {
 #[test]
 #[cfg(feature = "FIXME-IGNORED")] // <https://github.com/paritytech/cumulus/issues/3027>
 fn limited_teleport_native_assets_from_system_para_to_relay_fails() {
-	// Init values for Relay Chain
-	let amount_to_send: Balance = ASSET_HUB_KUSAMA_ED * 1000;
-	let destination = AssetHubKusama::parent_location().into();
-	let beneficiary_id = KusamaReceiver::get().into();
-	let assets = (Parent, amount_to_send).into();
-
-	let test_args = TestContext {
-		sender: AssetHubKusamaSender::get(),
-		receiver: KusamaReceiver::get(),
-		args: system_para_test_args(destination, beneficiary_id, amount_to_send, assets, None),
-	};
-
-	let mut test = SystemParaToRelayTest::new(test_args);
-
-	let sender_balance_before = test.sender.balance;
-	let receiver_balance_before = test.receiver.balance;
-
-	test.set_assertion::<AssetHubKusama>(para_origin_assertions);
-	test.set_assertion::<Kusama>(relay_dest_assertions_fail);
-	test.set_dispatchable::<AssetHubKusama>(system_para_limited_teleport_assets);
-	test.assert();
-
-	let sender_balance_after = test.sender.balance;
-	let receiver_balance_after = test.receiver.balance;
-
-	// Sender's balance is reduced
-	assert_eq!(sender_balance_before - amount_to_send, sender_balance_after);
-	// Receiver's balance does not change
-	assert_eq!(receiver_balance_after, receiver_balance_before);
+	system_para_to_relay_teleport_fails().run();
 }
 
 /// Teleport of native asset from Relay Chain to the System Parachain should work
 #[test]
 fn teleport_native_assets_from_relay_to_system_para_works() {
-	// Init values for Relay Chain
-	let amount_to_send: Balance = KUSAMA_ED * 1000;
-	let test_args = TestContext {
-		sender: KusamaSender::get(),
-		receiver: AssetHubKusamaReceiver::get(),
-		args: relay_test_args(amount_to_send),
-	};
-
-	let mut test = RelayToSystemParaTest::new(test_args);
-
-	let sender_balance_before = test.sender.balance;
-	let receiver_balance_before = test.receiver.balance;
-
-	test.set_assertion::<Kusama>(relay_origin_assertions);
-	test.set_assertion::<AssetHubKusama>(para_dest_assertions);
-	test.set_dispatchable::<Kusama>(relay_teleport_assets);
-	test.assert();
-
-	let sender_balance_after = test.sender.balance;
-	let receiver_balance_after = test.receiver.balance;
-
-	// Sender's balance is reduced
-	assert_eq!(sender_balance_before - amount_to_send, sender_balance_after);
-	// Receiver's balance is increased
-	assert!(receiver_balance_after > receiver_balance_before);
+	relay_to_system_para_teleport().without_weight_limit().run();
 }
 
 // TODO: Uncomment when https://github.com/paritytech/polkadot/pull/7424 is merged
@@ -303,64 +194,12 @@ fn teleport_native_assets_from_relay_to_system_para_works() {
 // 	// Dependency - Relay Chain's `CheckAccount` should have enough balance
 // 	teleport_native_assets_from_relay_to_system_para_works();
 
-// 	// Init values for Relay Chain
-// 	let amount_to_send: Balance = ASSET_HUB_KUSAMA_ED * 1000;
-// 	let test_args = TestContext {
-// 		sender: AssetHubKusamaSender::get(),
-// 		receiver: KusamaReceiver::get(),
-// 		args: get_para_dispatch_args(amount_to_send),
-// 	};
-
-// 	let mut test = SystemParaToRelayTest::new(test_args);
-
-// 	let sender_balance_before = test.sender.balance;
-// 	let receiver_balance_before = test.receiver.balance;
-
-// 	test.set_assertion::<AssetHubKusama>(para_origin_assertions);
-// 	test.set_assertion::<Kusama>(relay_dest_assertions);
-// 	test.set_dispatchable::<AssetHubKusama>(system_para_teleport_assets);
-// 	test.assert();
-
-// 	let sender_balance_after = test.sender.balance;
-// 	let receiver_balance_after = test.receiver.balance;
-
-// 	// Sender's balance is reduced
-// 	assert_eq!(sender_balance_before - amount_to_send, sender_balance_after);
-// 	// Receiver's balance is increased
-// 	assert!(receiver_balance_after > receiver_balance_before);
+// 	system_para_to_relay_teleport().without_weight_limit().run();
 // }
 
 // /// Teleport of native asset from System Parachain to Relay Chain
 // /// shouldn't work when there is not enough balance in Relay Chain's `CheckAccount`
 // #[test]
 // fn teleport_native_assets_from_system_para_to_relay_fails() {
-// 	// Init values for Relay Chain
-// 	let amount_to_send: Balance = ASSET_HUB_KUSAMA_ED * 1000;
-//  let assets = (Parent, amount_to_send).into();
-//
-// 	let test_args = TestContext {
-// 		sender: AssetHubKusamaSender::get(),
-// 		receiver: KusamaReceiver::get(),
-// 		args: system_para_test_args(amount_to_send),
-//      assets,
-//      None
-// 	};
-
-// 	let mut test = SystemParaToRelayTest::new(test_args);
-
-// 	let sender_balance_before = test.sender.balance;
-// 	let receiver_balance_before = test.receiver.balance;
-
-// 	test.set_assertion::<AssetHubKusama>(para_origin_assertions);
-// 	test.set_assertion::<Kusama>(relay_dest_assertions);
-// 	test.set_dispatchable::<AssetHubKusama>(system_para_teleport_assets);
-// 	test.assert();
-
-// 	let sender_balance_after = test.sender.balance;
-// 	let receiver_balance_after = test.receiver.balance;
-
-// 	// Sender's balance is reduced
-// 	assert_eq!(sender_balance_before - amount_to_send, sender_balance_after);
-// 	// Receiver's balance does not change
-// 	assert_eq!(receiver_balance_after, receiver_balance_before);
+// 	system_para_to_relay_teleport_fails().without_weight_limit().run();
 // }
@@ -204,3 +204,65 @@ fn force_open_hrmp_channel_for_system_para_works() {
 
 	Polkadot::force_process_hrmp_open(system_para_id, para_a_id);
 }
+
+/// Opening HRMP channels from System Parachains with the channel manager should work
+#[test]
+fn request_open_hrmp_channel_from_system_para_works() {
+	// System Para init values
+	let system_para_id = AssetHubPolkadot::para_id();
+	let system_para_root_origin = <AssetHubPolkadot as Chain>::RuntimeOrigin::root();
+
+	// Parachain A init values
+	let para_a_id = PenpalPolkadotA::para_id();
+
+	let fund_amount = POLKADOT_ED * 1000_000_000;
+
+	// Fund System Parachain's Sovereign account to be able to pay the fees and reserve the deposit
+	let system_para_sovereign_account = Polkadot::fund_para_sovereign(fund_amount, system_para_id);
+
+	AssetHubPolkadot::execute_with(|| {
+		assert_ok!(
+			<AssetHubPolkadot as AssetHubPolkadotPallet>::HrmpChannelManager::request_open_channel(
+				system_para_root_origin,
+				para_a_id,
+				MAX_CAPACITY,
+				MAX_MESSAGE_SIZE,
+			)
+		);
+
+		assert_eq!(
+			<AssetHubPolkadot as AssetHubPolkadotPallet>::HrmpChannelManager::channel_state(
+				system_para_id,
+				para_a_id
+			),
+			Some(pallet_hrmp_channel_manager::ChannelState::OpenRequested),
+		);
+	});
+
+	Polkadot::execute_with(|| {
+		type RuntimeEvent = <Polkadot as Chain>::RuntimeEvent;
+
+		Polkadot::assert_ump_queue_processed(true, Some(system_para_id), None);
+
+		assert_expected_events!(
+			Polkadot,
+			vec![
+				// Sender deposit is reserved for System Parachain's Sovereign account
+				RuntimeEvent::Balances(pallet_balances::Event::Reserved { who, .. }) =>{
+					who: *who == system_para_sovereign_account,
+				},
+				// Open channel requested from System Para to Para A
+				RuntimeEvent::Hrmp(
+					polkadot_runtime_parachains::hrmp::Event::OpenChannelRequested(
+						sender, recipient, max_capacity, max_message_size
+					)
+				) => {
+					sender: *sender == system_para_id.into(),
+					recipient: *recipient == para_a_id.into(),
+					max_capacity: *max_capacity == MAX_CAPACITY,
+					max_message_size: *max_message_size == MAX_MESSAGE_SIZE,
+				},
+			]
+		);
+	});
+}
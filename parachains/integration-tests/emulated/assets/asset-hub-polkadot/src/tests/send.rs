@@ -20,88 +20,48 @@ use crate::*;
 /// when `OriginKind::Superuser` and signer is `sudo`
 #[test]
 fn send_transact_sudo_from_relay_to_system_para_works() {
-	// Init tests variables
-	let root_origin = <Polkadot as Chain>::RuntimeOrigin::root();
-	let system_para_destination = Polkadot::child_location_of(AssetHubPolkadot::para_id()).into();
-	let asset_owner: AccountId = AssetHubPolkadotSender::get().into();
-	let xcm = AssetHubPolkadot::force_create_asset_xcm(
-		OriginKind::Superuser,
-		ASSET_ID,
-		asset_owner.clone(),
-		true,
-		1000,
-	);
-	// Send XCM message from Relay Chain
-	Polkadot::execute_with(|| {
-		assert_ok!(<Polkadot as PolkadotPallet>::XcmPallet::send(
-			root_origin,
-			bx!(system_para_destination),
-			bx!(xcm),
-		));
-
-		Polkadot::assert_xcm_pallet_sent();
-	});
-
-	// Receive XCM message in Assets Parachain
-	AssetHubPolkadot::execute_with(|| {
-		type RuntimeEvent = <AssetHubPolkadot as Chain>::RuntimeEvent;
-
-		AssetHubPolkadot::assert_dmp_queue_complete(Some(Weight::from_parts(
-			1_019_445_000,
-			200_000,
-		)));
-
-		assert_expected_events!(
-			AssetHubPolkadot,
-			vec![
-				RuntimeEvent::Assets(pallet_assets::Event::ForceCreated { asset_id, owner }) => {
-					asset_id: *asset_id == ASSET_ID,
-					owner: *owner == asset_owner,
-				},
-			]
-		);
-
-		assert!(<AssetHubPolkadot as AssetHubPolkadotPallet>::Assets::asset_exists(ASSET_ID));
-	});
+	let asset_owner: AccountId = AssetHubPolkadotSender::get();
+	let call = AssetHubPolkadot::force_create_asset_call(ASSET_ID, asset_owner.clone(), true, 1000);
+
+	test_transact(Polkadot, AssetHubPolkadot, call)
+		.assert_sent()
+		.assert_destination_complete(Some(Weight::from_parts(1_019_445_000, 200_000)))
+		.assert_on_destination(move || {
+			type RuntimeEvent = <AssetHubPolkadot as Chain>::RuntimeEvent;
+
+			assert_expected_events!(
+				AssetHubPolkadot,
+				vec![
+					RuntimeEvent::Assets(pallet_assets::Event::ForceCreated { asset_id, owner }) => {
+						asset_id: *asset_id == ASSET_ID,
+						owner: *owner == asset_owner,
+					},
+				]
+			);
+
+			assert!(<AssetHubPolkadot as AssetHubPolkadotPallet>::Assets::asset_exists(ASSET_ID));
+		})
+		.run();
 }
 
 /// Relay Chain shouldn't be able to execute `Transact` instructions in System Parachain
 /// when `OriginKind::Native`
 #[test]
 fn send_transact_native_from_relay_to_system_para_fails() {
-	// Init tests variables
-	let signed_origin = <Polkadot as Chain>::RuntimeOrigin::signed(PolkadotSender::get().into());
-	let system_para_destination = Polkadot::child_location_of(AssetHubPolkadot::para_id()).into();
-	let asset_owner = AssetHubPolkadotSender::get().into();
-	let xcm = AssetHubPolkadot::force_create_asset_xcm(
-		OriginKind::Native,
-		ASSET_ID,
-		asset_owner,
-		true,
-		1000,
-	);
-
-	// Send XCM message from Relay Chain
-	Polkadot::execute_with(|| {
-		assert_err!(
-			<Polkadot as PolkadotPallet>::XcmPallet::send(
-				signed_origin,
-				bx!(system_para_destination),
-				bx!(xcm)
-			),
-			DispatchError::BadOrigin
-		);
-	});
+	let asset_owner = AssetHubPolkadotSender::get();
+	let call = AssetHubPolkadot::force_create_asset_call(ASSET_ID, asset_owner, true, 1000);
+
+	test_transact(Polkadot, AssetHubPolkadot, call)
+		.signed_by(PolkadotSender::get())
+		.origin_kind(OriginKind::Native)
+		.assert_send_error(DispatchError::BadOrigin)
+		.run();
 }
 
 /// System Parachain shouldn't be able to execute `Transact` instructions in Relay Chain
 /// when `OriginKind::Native`
 #[test]
 fn send_transact_native_from_system_para_to_relay_fails() {
-	// Init tests variables
-	let signed_origin =
-		<AssetHubPolkadot as Chain>::RuntimeOrigin::signed(AssetHubPolkadotSender::get().into());
-	let relay_destination = AssetHubPolkadot::parent_location().into();
 	let call = <Polkadot as Chain>::RuntimeCall::System(frame_system::Call::<
 		<Polkadot as Chain>::Runtime,
 	>::remark_with_event {
@@ -109,21 +69,12 @@ fn send_transact_native_from_system_para_to_relay_fails() {
 	})
 	.encode()
 	.into();
-	let origin_kind = OriginKind::Native;
-
-	let xcm = xcm_transact_unpaid_execution(call, origin_kind);
-
-	// Send XCM message from Relay Chain
-	AssetHubPolkadot::execute_with(|| {
-		assert_err!(
-			<AssetHubPolkadot as AssetHubPolkadotPallet>::PolkadotXcm::send(
-				signed_origin,
-				bx!(relay_destination),
-				bx!(xcm)
-			),
-			DispatchError::BadOrigin
-		);
-	});
+
+	test_transact(AssetHubPolkadot, Polkadot, call)
+		.signed_by(AssetHubPolkadotSender::get())
+		.origin_kind(OriginKind::Native)
+		.assert_send_error(DispatchError::BadOrigin)
+		.run();
 }
 
 /// Parachain should be able to send XCM paying its fee with sufficient asset
@@ -16,45 +16,25 @@
 
 use crate::*;
 
-fn relay_origin_assertions(t: RelayToSystemParaTest) {
+fn relay_origin_assertions(dest: MultiLocation, amount_to_send: Balance) {
 	type RuntimeEvent = <Westend as Chain>::RuntimeEvent;
 
-	Westend::assert_xcm_pallet_attempted_complete(Some(Weight::from_parts(629_384_000, 6_196)));
-
 	assert_expected_events!(
 		Westend,
 		vec![
 			// Amount to reserve transfer is transferred to System Parachain's Sovereign account
 			RuntimeEvent::Balances(pallet_balances::Event::Transfer { from, to, amount }) => {
-				from: *from == t.sender.account_id,
-				to: *to == Westend::sovereign_account_id_of(
-					t.args.dest
-				),
-				amount:  *amount == t.args.amount,
+				from: *from == WestendSender::get(),
+				to: *to == Westend::sovereign_account_id_of(dest),
+				amount: *amount == amount_to_send,
 			},
 		]
 	);
 }
 
-fn system_para_dest_assertions_incomplete(_t: RelayToSystemParaTest) {
-	AssetHubWestend::assert_dmp_queue_incomplete(
-		Some(Weight::from_parts(1_000_000_000, 0)),
-		Some(Error::UntrustedReserveLocation),
-	);
-}
-
-fn system_para_to_relay_assertions(_t: SystemParaToRelayTest) {
-	AssetHubWestend::assert_xcm_pallet_attempted_error(Some(XcmError::Barrier))
-}
-
-fn system_para_to_para_assertions(t: SystemParaToParaTest) {
+fn system_para_to_para_assertions(dest: MultiLocation, amount_to_send: Balance) {
 	type RuntimeEvent = <AssetHubWestend as Chain>::RuntimeEvent;
 
-	AssetHubWestend::assert_xcm_pallet_attempted_complete(Some(Weight::from_parts(
-		676_119_000,
-		6196,
-	)));
-
 	assert_expected_events!(
 		AssetHubWestend,
 		vec![
@@ -62,24 +42,17 @@ fn system_para_to_para_assertions(t: SystemParaToParaTest) {
 			RuntimeEvent::Balances(
 				pallet_balances::Event::Transfer { from, to, amount }
 			) => {
-				from: *from == t.sender.account_id,
-				to: *to == AssetHubWestend::sovereign_account_id_of(
-					t.args.dest
-				),
-				amount: *amount == t.args.amount,
+				from: *from == AssetHubWestendSender::get(),
+				to: *to == AssetHubWestend::sovereign_account_id_of(dest),
+				amount: *amount == amount_to_send,
 			},
 		]
 	);
 }
 
-fn system_para_to_para_assets_assertions(t: SystemParaToParaTest) {
+fn system_para_to_para_assets_assertions(dest: MultiLocation, amount_to_send: Balance) {
 	type RuntimeEvent = <AssetHubWestend as Chain>::RuntimeEvent;
 
-	AssetHubWestend::assert_xcm_pallet_attempted_complete(Some(Weight::from_parts(
-		676_119_000,
-		6196,
-	)));
-
 	assert_expected_events!(
 		AssetHubWestend,
 		vec![
@@ -88,328 +61,120 @@ fn system_para_to_para_assets_assertions(t: SystemParaToParaTest) {
 				pallet_assets::Event::Transferred { asset_id, from, to, amount }
 			) => {
 				asset_id: *asset_id == ASSET_ID,
-				from: *from == t.sender.account_id,
-				to: *to == AssetHubWestend::sovereign_account_id_of(
-					t.args.dest
-				),
-				amount: *amount == t.args.amount,
+				from: *from == AssetHubWestendSender::get(),
+				to: *to == AssetHubWestend::sovereign_account_id_of(dest),
+				amount: *amount == amount_to_send,
 			},
 		]
 	);
 }
 
-fn relay_limited_reserve_transfer_assets(t: RelayToSystemParaTest) -> DispatchResult {
-	<Westend as WestendPallet>::XcmPallet::limited_reserve_transfer_assets(
-		t.signed_origin,
-		bx!(t.args.dest.into()),
-		bx!(t.args.beneficiary.into()),
-		bx!(t.args.assets.into()),
-		t.args.fee_asset_item,
-		t.args.weight_limit,
-	)
-}
+/// Reserve transfer of native asset from Relay Chain to the System Parachain, which is expected to
+/// fail at the System Parachain
+fn relay_to_system_para_reserve_transfer() -> TransferFlow<Westend, AssetHubWestend> {
+	let amount_to_send: Balance = WESTEND_ED * 1000;
+	let destination = Westend::child_location_of(AssetHubWestend::para_id());
 
-fn relay_reserve_transfer_assets(t: RelayToSystemParaTest) -> DispatchResult {
-	<Westend as WestendPallet>::XcmPallet::reserve_transfer_assets(
-		t.signed_origin,
-		bx!(t.args.dest.into()),
-		bx!(t.args.beneficiary.into()),
-		bx!(t.args.assets.into()),
-		t.args.fee_asset_item,
-	)
+	test_reserve_transfer(Westend, AssetHubWestend, Here, amount_to_send)
+		.assert_origin_complete(Some(Weight::from_parts(629_384_000, 6_196)))
+		.assert_on_origin(move || relay_origin_assertions(destination, amount_to_send))
+		.assert_destination_incomplete(Some(Weight::from_parts(1_000_000_000, 0)))
+		.assert_sender_charged()
+		.assert_receiver_unchanged()
 }
 
-fn system_para_limited_reserve_transfer_assets(t: SystemParaToRelayTest) -> DispatchResult {
-	<AssetHubWestend as AssetHubWestendPallet>::PolkadotXcm::limited_reserve_transfer_assets(
-		t.signed_origin,
-		bx!(t.args.dest.into()),
-		bx!(t.args.beneficiary.into()),
-		bx!(t.args.assets.into()),
-		t.args.fee_asset_item,
-		t.args.weight_limit,
-	)
+/// Reserve transfer of native asset from System Parachain to Relay Chain, which is expected to be
+/// stopped by the `Barrier` at the System Parachain
+fn system_para_to_relay_reserve_transfer() -> TransferFlow<AssetHubWestend, Westend> {
+	test_reserve_transfer(AssetHubWestend, Westend, Parent, ASSET_HUB_WESTEND_ED * 1000)
+		.assert_origin_error(XcmError::Barrier)
+		.assert_sender_unchanged()
+		.assert_receiver_unchanged()
 }
 
-fn system_para_reserve_transfer_assets(t: SystemParaToRelayTest) -> DispatchResult {
-	<AssetHubWestend as AssetHubWestendPallet>::PolkadotXcm::reserve_transfer_assets(
-		t.signed_origin,
-		bx!(t.args.dest.into()),
-		bx!(t.args.beneficiary.into()),
-		bx!(t.args.assets.into()),
-		t.args.fee_asset_item,
-	)
-}
+/// Reserve transfer of native asset from System Parachain to Parachain
+fn system_para_to_para_reserve_transfer() -> TransferFlow<AssetHubWestend, PenpalWestendA> {
+	let amount_to_send: Balance = ASSET_HUB_WESTEND_ED * 1000;
+	let destination = AssetHubWestend::sibling_location_of(PenpalWestendA::para_id());
 
-fn system_para_to_para_limited_reserve_transfer_assets(t: SystemParaToParaTest) -> DispatchResult {
-	<AssetHubWestend as AssetHubWestendPallet>::PolkadotXcm::limited_reserve_transfer_assets(
-		t.signed_origin,
-		bx!(t.args.dest.into()),
-		bx!(t.args.beneficiary.into()),
-		bx!(t.args.assets.into()),
-		t.args.fee_asset_item,
-		t.args.weight_limit,
-	)
+	// TODO: Add assertion for Penpal runtime. Right now message is failing with
+	// `UntrustedReserveLocation`
+	// TODO: Check receiver balance when Penpal runtime is improved to propery handle reserve
+	// transfers
+	test_reserve_transfer(AssetHubWestend, PenpalWestendA, Parent, amount_to_send)
+		.assert_origin_complete(Some(Weight::from_parts(676_119_000, 6196)))
+		.assert_on_origin(move || system_para_to_para_assertions(destination, amount_to_send))
+		.assert_sender_charged()
 }
 
-fn system_para_to_para_reserve_transfer_assets(t: SystemParaToParaTest) -> DispatchResult {
-	<AssetHubWestend as AssetHubWestendPallet>::PolkadotXcm::reserve_transfer_assets(
-		t.signed_origin,
-		bx!(t.args.dest.into()),
-		bx!(t.args.beneficiary.into()),
-		bx!(t.args.assets.into()),
-		t.args.fee_asset_item,
-	)
+/// Reserve transfer of a local asset from System Parachain to Parachain
+fn system_para_to_para_assets_reserve_transfer() -> TransferFlow<AssetHubWestend, PenpalWestendA> {
+	// Force create asset from Relay Chain and mint assets for System Parachain's sender account
+	AssetHubWestend::force_create_and_mint_asset(
+		ASSET_ID,
+		ASSET_MIN_BALANCE,
+		true,
+		AssetHubWestendSender::get(),
+		ASSET_MIN_BALANCE * 1000000,
+	);
+
+	let amount_to_send = ASSET_MIN_BALANCE * 1000;
+	let destination = AssetHubWestend::sibling_location_of(PenpalWestendA::para_id());
+	let asset = X2(PalletInstance(ASSETS_PALLET_ID), GeneralIndex(ASSET_ID.into()));
+
+	// TODO: Add assertions when Penpal is able to manage assets
+	test_reserve_transfer(AssetHubWestend, PenpalWestendA, asset, amount_to_send)
+		.assert_origin_complete(Some(Weight::from_parts(676_119_000, 6196)))
+		.assert_on_origin(move || {
+			system_para_to_para_assets_assertions(destination, amount_to_send)
+		})
 }
 
 /// Limited Reserve Transfers of native asset from Relay Chain to the System Parachain shouldn't
 /// work
 #[test]
 fn limited_reserve_transfer_native_asset_from_relay_to_system_para_fails() {
-	// Init values for Relay Chain
-	let amount_to_send: Balance = WESTEND_ED * 1000;
-	let test_args = TestContext {
-		sender: WestendSender::get(),
-		receiver: AssetHubWestendReceiver::get(),
-		args: relay_test_args(amount_to_send),
-	};
-
-	let mut test = RelayToSystemParaTest::new(test_args);
-
-	let sender_balance_before = test.sender.balance;
-	let receiver_balance_before = test.receiver.balance;
-
-	test.set_assertion::<Westend>(relay_origin_assertions);
-	test.set_assertion::<AssetHubWestend>(system_para_dest_assertions_incomplete);
-	test.set_dispatchable::<Westend>(relay_limited_reserve_transfer_assets);
-	test.assert();
-
-	let sender_balance_after = test.sender.balance;
-	let receiver_balance_after = test.receiver.balance;
-
-	assert_eq!(sender_balance_before - amount_to_send, sender_balance_after);
-	assert_eq!(receiver_balance_before, receiver_balance_after);
+	relay_to_system_para_reserve_transfer().run();
 }
 
 /// Limited Reserve Transfers of native asset from System Parachain to Relay Chain shoudln't work
 #[test]
 fn limited_reserve_transfer_native_asset_from_system_para_to_relay_fails() {
-	// Init values for System Parachain
-	let destination = AssetHubWestend::parent_location();
-	let beneficiary_id = WestendReceiver::get();
-	let amount_to_send: Balance = ASSET_HUB_WESTEND_ED * 1000;
-	let assets = (Parent, amount_to_send).into();
-
-	let test_args = TestContext {
-		sender: AssetHubWestendSender::get(),
-		receiver: WestendReceiver::get(),
-		args: system_para_test_args(destination, beneficiary_id, amount_to_send, assets, None),
-	};
-
-	let mut test = SystemParaToRelayTest::new(test_args);
-
-	let sender_balance_before = test.sender.balance;
-	let receiver_balance_before = test.receiver.balance;
-
-	test.set_assertion::<AssetHubWestend>(system_para_to_relay_assertions);
-	test.set_dispatchable::<AssetHubWestend>(system_para_limited_reserve_transfer_assets);
-	test.assert();
-
-	let sender_balance_after = test.sender.balance;
-	let receiver_balance_after = test.receiver.balance;
-
-	assert_eq!(sender_balance_before, sender_balance_after);
-	assert_eq!(receiver_balance_before, receiver_balance_after);
+	system_para_to_relay_reserve_transfer().run();
 }
 
 /// Reserve Transfers of native asset from Relay Chain to the System Parachain shouldn't work
 #[test]
 fn reserve_transfer_native_asset_from_relay_to_system_para_fails() {
-	// Init values for Relay Chain
-	let amount_to_send: Balance = WESTEND_ED * 1000;
-	let test_args = TestContext {
-		sender: WestendSender::get(),
-		receiver: AssetHubWestendReceiver::get(),
-		args: relay_test_args(amount_to_send),
-	};
-
-	let mut test = RelayToSystemParaTest::new(test_args);
-
-	let sender_balance_before = test.sender.balance;
-	let receiver_balance_before = test.receiver.balance;
-
-	test.set_assertion::<Westend>(relay_origin_assertions);
-	test.set_assertion::<AssetHubWestend>(system_para_dest_assertions_incomplete);
-	test.set_dispatchable::<Westend>(relay_reserve_transfer_assets);
-	test.assert();
-
-	let sender_balance_after = test.sender.balance;
-	let receiver_balance_after = test.receiver.balance;
-
-	assert_eq!(sender_balance_before - amount_to_send, sender_balance_after);
-	assert_eq!(receiver_balance_before, receiver_balance_after);
+	relay_to_system_para_reserve_transfer().without_weight_limit().run();
 }
 
 /// Reserve Transfers of native asset from System Parachain to Relay Chain shouldn't work
 #[test]
 fn reserve_transfer_native_asset_from_system_para_to_relay_fails() {
-	// Init values for System Parachain
-	let destination = AssetHubWestend::parent_location();
-	let beneficiary_id = WestendReceiver::get();
-	let amount_to_send: Balance = ASSET_HUB_WESTEND_ED * 1000;
-	let assets = (Parent, amount_to_send).into();
-
-	let test_args = TestContext {
-		sender: AssetHubWestendSender::get(),
-		receiver: WestendReceiver::get(),
-		args: system_para_test_args(destination, beneficiary_id, amount_to_send, assets, None),
-	};
-
-	let mut test = SystemParaToRelayTest::new(test_args);
-
-	let sender_balance_before = test.sender.balance;
-	let receiver_balance_before = test.receiver.balance;
-
-	test.set_assertion::<AssetHubWestend>(system_para_to_relay_assertions);
-	test.set_dispatchable::<AssetHubWestend>(system_para_reserve_transfer_assets);
-	test.assert();
-
-	let sender_balance_after = test.sender.balance;
-	let receiver_balance_after = test.receiver.balance;
-
-	assert_eq!(sender_balance_before, sender_balance_after);
-	assert_eq!(receiver_balance_before, receiver_balance_after);
+	system_para_to_relay_reserve_transfer().without_weight_limit().run();
 }
 
 /// Limited Reserve Transfers of native asset from System Parachain to Parachain should work
 #[test]
 fn limited_reserve_transfer_native_asset_from_system_para_to_para() {
-	// Init values for System Parachain
-	let destination = AssetHubWestend::sibling_location_of(PenpalWestendA::para_id());
-	let beneficiary_id = PenpalWestendAReceiver::get();
-	let amount_to_send: Balance = ASSET_HUB_WESTEND_ED * 1000;
-	let assets = (Parent, amount_to_send).into();
-
-	let test_args = TestContext {
-		sender: AssetHubWestendSender::get(),
-		receiver: PenpalWestendAReceiver::get(),
-		args: system_para_test_args(destination, beneficiary_id, amount_to_send, assets, None),
-	};
-
-	let mut test = SystemParaToParaTest::new(test_args);
-
-	let sender_balance_before = test.sender.balance;
-
-	test.set_assertion::<AssetHubWestend>(system_para_to_para_assertions);
-	// TODO: Add assertion for Penpal runtime. Right now message is failing with
-	// `UntrustedReserveLocation`
-	test.set_dispatchable::<AssetHubWestend>(system_para_to_para_limited_reserve_transfer_assets);
-	test.assert();
-
-	let sender_balance_after = test.sender.balance;
-
-	assert_eq!(sender_balance_before - amount_to_send, sender_balance_after);
-	// TODO: Check receiver balance when Penpal runtime is improved to propery handle reserve
-	// transfers
+	system_para_to_para_reserve_transfer().run();
 }
 
 /// Reserve Transfers of native asset from System Parachain to Parachain should work
 #[test]
 fn reserve_transfer_native_asset_from_system_para_to_para() {
-	// Init values for System Parachain
-	let destination = AssetHubWestend::sibling_location_of(PenpalWestendA::para_id());
-	let beneficiary_id = PenpalWestendAReceiver::get();
-	let amount_to_send: Balance = ASSET_HUB_WESTEND_ED * 1000;
-	let assets = (Parent, amount_to_send).into();
-
-	let test_args = TestContext {
-		sender: AssetHubWestendSender::get(),
-		receiver: PenpalWestendAReceiver::get(),
-		args: system_para_test_args(destination, beneficiary_id, amount_to_send, assets, None),
-	};
-
-	let mut test = SystemParaToParaTest::new(test_args);
-
-	let sender_balance_before = test.sender.balance;
-
-	test.set_assertion::<AssetHubWestend>(system_para_to_para_assertions);
-	// TODO: Add assertion for Penpal runtime. Right now message is failing with
-	// `UntrustedReserveLocation`
-	test.set_dispatchable::<AssetHubWestend>(system_para_to_para_reserve_transfer_assets);
-	test.assert();
-
-	let sender_balance_after = test.sender.balance;
-
-	assert_eq!(sender_balance_before - amount_to_send, sender_balance_after);
-	// TODO: Check receiver balance when Penpal runtime is improved to propery handle reserve
-	// transfers
+	system_para_to_para_reserve_transfer().without_weight_limit().run();
 }
 
 /// Limited Reserve Transfers of a local asset from System Parachain to Parachain should work
 #[test]
 fn limited_reserve_transfer_asset_from_system_para_to_para() {
-	// Force create asset from Relay Chain and mint assets for System Parachain's sender account
-	AssetHubWestend::force_create_and_mint_asset(
-		ASSET_ID,
-		ASSET_MIN_BALANCE,
-		true,
-		AssetHubWestendSender::get(),
-		ASSET_MIN_BALANCE * 1000000,
-	);
-
-	// Init values for System Parachain
-	let destination = AssetHubWestend::sibling_location_of(PenpalWestendA::para_id());
-	let beneficiary_id = PenpalWestendAReceiver::get();
-	let amount_to_send = ASSET_MIN_BALANCE * 1000;
-	let assets =
-		(X2(PalletInstance(ASSETS_PALLET_ID), GeneralIndex(ASSET_ID.into())), amount_to_send)
-			.into();
-
-	let system_para_test_args = TestContext {
-		sender: AssetHubWestendSender::get(),
-		receiver: PenpalWestendAReceiver::get(),
-		args: system_para_test_args(destination, beneficiary_id, amount_to_send, assets, None),
-	};
-
-	let mut system_para_test = SystemParaToParaTest::new(system_para_test_args);
-
-	system_para_test.set_assertion::<AssetHubWestend>(system_para_to_para_assets_assertions);
-	// TODO: Add assertions when Penpal is able to manage assets
-	system_para_test
-		.set_dispatchable::<AssetHubWestend>(system_para_to_para_limited_reserve_transfer_assets);
-	system_para_test.assert();
+	system_para_to_para_assets_reserve_transfer().run();
 }
 
 /// Reserve Transfers of a local asset from System Parachain to Parachain should work
 #[test]
 fn reserve_transfer_asset_from_system_para_to_para() {
-	// Force create asset from Relay Chain and mint assets for System Parachain's sender account
-	AssetHubWestend::force_create_and_mint_asset(
-		ASSET_ID,
-		ASSET_MIN_BALANCE,
-		true,
-		AssetHubWestendSender::get(),
-		ASSET_MIN_BALANCE * 1000000,
-	);
-
-	// Init values for System Parachain
-	let destination = AssetHubWestend::sibling_location_of(PenpalWestendA::para_id());
-	let beneficiary_id = PenpalWestendAReceiver::get();
-	let amount_to_send = ASSET_MIN_BALANCE * 1000;
-	let assets =
-		(X2(PalletInstance(ASSETS_PALLET_ID), GeneralIndex(ASSET_ID.into())), amount_to_send)
-			.into();
-
-	let system_para_test_args = TestContext {
-		sender: AssetHubWestendSender::get(),
-		receiver: PenpalWestendAReceiver::get(),
-		args: system_para_test_args(destination, beneficiary_id, amount_to_send, assets, None),
-	};
-
-	let mut system_para_test = SystemParaToParaTest::new(system_para_test_args);
-
-	system_para_test.set_assertion::<AssetHubWestend>(system_para_to_para_assets_assertions);
-	// TODO: Add assertions when Penpal is able to manage assets
-	system_para_test
-		.set_dispatchable::<AssetHubWestend>(system_para_to_para_reserve_transfer_assets);
-	system_para_test.assert();
+	system_para_to_para_assets_reserve_transfer().without_weight_limit().run();
 }
@@ -0,0 +1,93 @@
+// Copyright Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::*;
+use bp_messages::{source_chain::MessagesBridge, MessageNonce, OutboundLaneData};
+use pallet_bridge_messages::{OutboundLanes, Pallet as BridgeMessages};
+use xcm::VersionedInteriorMultiLocation;
+
+type BridgeHubRococoRuntime = <BridgeHubRococo as Chain>::Runtime;
+
+/// The lane of Bridge Hub Rococo to Bridge Hub Wococo.
+const LANE: LaneId = LaneId([0, 0, 0, 1]);
+
+/// Queues a message for the Wococo relay chain at the outbound lane of Bridge Hub Rococo, as an
+/// `ExportMessage` would, and returns its nonce.
+fn send_message_to_wococo() -> MessageNonce {
+	let universal_dest: InteriorMultiLocation = X1(GlobalConsensus(WococoId));
+	let blob = (
+		VersionedInteriorMultiLocation::from(universal_dest),
+		VersionedXcm::<()>::from(Xcm(vec![ClearOrigin])),
+	)
+		.encode();
+
+	<BridgeMessages<BridgeHubRococoRuntime, Instance1> as MessagesBridge<_>>::send_message(
+		LANE, blob,
+	)
+	.expect("the lane to Wococo is active")
+	.nonce
+}
+
+fn outbound_lane() -> OutboundLaneData {
+	BridgeHubRococo::execute_with(|| OutboundLanes::<BridgeHubRococoRuntime, Instance1>::get(LANE))
+}
+
+/// The number of messages that Bridge Hub Wococo sent to its relay chain, i.e. the number of
+/// bridged messages dispatched there, since it last produced a block.
+fn messages_dispatched_at_wococo() -> usize {
+	BridgeHubWococo::execute_with(|| {
+		type RuntimeEvent = <BridgeHubWococo as Chain>::RuntimeEvent;
+
+		BridgeHubWococo::events()
+			.iter()
+			.filter(|event| {
+				matches!(
+					event,
+					RuntimeEvent::ParachainSystem(
+						cumulus_pallet_parachain_system::Event::UpwardMessageSent { .. }
+					)
+				)
+			})
+			.count()
+	})
+}
+
+#[test]
+fn rococo_wococo_messages_are_confirmed_by_nonce() {
+	// Bridge Hub Wococo needs the host configuration of its relay chain to send messages there.
+	BridgeHubWococo::execute_with(|| {});
+
+	// The messages are relayed through the `RococoWococoMessageHandler` once the block is done.
+	let nonces =
+		BridgeHubRococo::execute_with(|| vec![send_message_to_wococo(), send_message_to_wococo()]);
+	assert_eq!(nonces, vec![1, 2]);
+
+	// Both messages are dispatched at Wococo, and their delivery is confirmed at Rococo.
+	assert_eq!(messages_dispatched_at_wococo(), 2);
+	let lane = outbound_lane();
+	assert_eq!(lane.latest_generated_nonce, 2);
+	assert_eq!(lane.latest_received_nonce, 2);
+	assert_eq!(lane.oldest_unpruned_nonce, 3);
+
+	// Only the messages after the confirmed nonce are relayed again.
+	let nonce = BridgeHubRococo::execute_with(send_message_to_wococo);
+	assert_eq!(nonce, 3);
+
+	assert_eq!(messages_dispatched_at_wococo(), 1);
+	let lane = outbound_lane();
+	assert_eq!(lane.latest_received_nonce, 3);
+	assert_eq!(lane.oldest_unpruned_nonce, 4);
+}
@@ -11,8 +11,11 @@ use pallet_bridge_messages::{Config, Instance1, Instance2, OutboundLanes, Pallet
 use sp_core::Get;
 use xcm_emulator::{BridgeMessage, BridgeMessageDispatchError, BridgeMessageHandler, Chain};
 
-pub struct BridgeHubMessageHandler<S, T, I> {
-	_marker: std::marker::PhantomData<(S, T, I)>,
+/// Relays the messages of the outbound lanes of the messages pallet instance `SI` of the source
+/// bridge hub runtime `S` to the messages pallet instance `TI` of the target bridge hub runtime
+/// `T`.
+pub struct BridgeHubMessageHandler<S, SI, T, TI> {
+	_marker: std::marker::PhantomData<(S, SI, T, TI)>,
 }
 
 struct LaneIdWrapper(LaneId);
@@ -32,48 +35,37 @@ impl From<u32> for LaneIdWrapper {
 type BridgeHubRococoRuntime = <BridgeHubRococo as Chain>::Runtime;
 type BridgeHubWococoRuntime = <BridgeHubWococo as Chain>::Runtime;
 
-// TODO: uncomment when https://github.com/paritytech/cumulus/pull/2528 is merged
-// type BridgeHubPolkadotRuntime = <BridgeHubPolkadot as Chain>::Runtime;
-// type BridgeHubKusamaRuntime = <BridgeHubKusama as Chain>::Runtime;
-
+// Both bridge hubs run the Bridge Hub Rococo runtime, in which `Instance1` bridges with Wococo
+// and `Instance2` bridges with Rococo.
 pub type RococoWococoMessageHandler =
-	BridgeHubMessageHandler<BridgeHubRococoRuntime, BridgeHubWococoRuntime, Instance2>;
+	BridgeHubMessageHandler<BridgeHubRococoRuntime, Instance1, BridgeHubWococoRuntime, Instance2>;
 pub type WococoRococoMessageHandler =
-	BridgeHubMessageHandler<BridgeHubWococoRuntime, BridgeHubRococoRuntime, Instance2>;
-
-// TODO: uncomment when https://github.com/paritytech/cumulus/pull/2528 is merged
-// pub type PolkadotKusamaMessageHandler
-//	= BridgeHubMessageHandler<BridgeHubPolkadotRuntime, BridgeHubKusamaRuntime, Instance1>;
-// pub type KusamaPolkadotMessageHandler
-//	= BridgeHubMessageHandler<BridgeHubKusamaRuntime, BridgeHubPolkadoRuntime, Instance1>;
+	BridgeHubMessageHandler<BridgeHubWococoRuntime, Instance2, BridgeHubRococoRuntime, Instance1>;
 
-impl<S, T, I> BridgeMessageHandler for BridgeHubMessageHandler<S, T, I>
+impl<S, SI, T, TI> BridgeMessageHandler for BridgeHubMessageHandler<S, SI, T, TI>
 where
-	S: Config<Instance1>,
-	T: Config<I>,
-	I: 'static,
-	<T as Config<I>>::InboundPayload: From<Vec<u8>>,
-	<T as Config<I>>::MessageDispatch:
+	S: Config<SI>,
+	SI: 'static,
+	T: Config<TI>,
+	TI: 'static,
+	<T as Config<TI>>::InboundPayload: From<Vec<u8>>,
+	<T as Config<TI>>::MessageDispatch:
 		MessageDispatch<DispatchLevelResult = XcmBlobMessageDispatchResult>,
 {
 	fn get_source_outbound_messages() -> Vec<BridgeMessage> {
 		// get the source active outbound lanes
-		let active_lanes = S::ActiveOutboundLanes::get();
+		let active_lanes = <S as Config<SI>>::ActiveOutboundLanes::get();
 
 		let mut messages: Vec<BridgeMessage> = Default::default();
 
 		// collect messages from `OutboundMessages` for each active outbound lane in the source
 		for lane in active_lanes {
-			let latest_generated_nonce =
-				OutboundLanes::<S, Instance1>::get(lane).latest_generated_nonce;
-			let latest_received_nonce =
-				OutboundLanes::<S, Instance1>::get(lane).latest_received_nonce;
-
-			(latest_received_nonce + 1..=latest_generated_nonce).for_each(|nonce| {
-				let encoded_payload: Vec<u8> =
-					Pallet::<S, Instance1>::outbound_message_data(*lane, nonce)
-						.expect("Bridge message does not exist")
-						.into();
+			let queued_messages = OutboundLanes::<S, SI>::get(lane).queued_messages();
+
+			queued_messages.for_each(|nonce| {
+				let encoded_payload: Vec<u8> = Pallet::<S, SI>::outbound_message_data(*lane, nonce)
+					.expect("Bridge message does not exist")
+					.into();
 				let payload = Vec::<u8>::decode(&mut &encoded_payload[..])
 					.expect("Decodign XCM message failed");
 				let id: u32 = LaneIdWrapper(*lane).into();
@@ -88,8 +80,8 @@ where
 	fn dispatch_target_inbound_message(
 		message: BridgeMessage,
 	) -> Result<(), BridgeMessageDispatchError> {
-		type TargetMessageDispatch<T, I> = <T as Config<I>>::MessageDispatch;
-		type InboundPayload<T, I> = <T as Config<I>>::InboundPayload;
+		type TargetMessageDispatch<T, TI> = <T as Config<TI>>::MessageDispatch;
+		type InboundPayload<T, TI> = <T as Config<TI>>::InboundPayload;
 
 		let lane_id = LaneIdWrapper::from(message.id).0;
 		let nonce = message.nonce;
@@ -97,9 +89,9 @@ where
 
 		// Directly dispatch outbound messages assuming everything is correct
 		// and bypassing the `Relayers`  and `InboundLane` logic
-		let dispatch_result = TargetMessageDispatch::<T, I>::dispatch(DispatchMessage {
+		let dispatch_result = TargetMessageDispatch::<T, TI>::dispatch(DispatchMessage {
 			key: MessageKey { lane_id, nonce },
-			data: DispatchMessageData::<InboundPayload<T, I>> { payload },
+			data: DispatchMessageData::<InboundPayload<T, TI>> { payload },
 		});
 
 		let result = match dispatch_result.dispatch_level_result {
@@ -114,15 +106,16 @@ where
 		result
 	}
 
-	fn notify_source_message_delivery(lane_id: u32) {
-		let data = OutboundLanes::<S, Instance1>::get(LaneIdWrapper::from(lane_id).0);
+	fn notify_source_message_delivery(lane_id: u32, nonce: u64) {
+		let lane_id = LaneIdWrapper::from(lane_id).0;
+		let data = OutboundLanes::<S, SI>::get(lane_id);
 		let new_data = OutboundLaneData {
-			oldest_unpruned_nonce: data.oldest_unpruned_nonce + 1,
-			latest_received_nonce: data.latest_received_nonce + 1,
+			oldest_unpruned_nonce: data.oldest_unpruned_nonce.max(nonce + 1),
+			latest_received_nonce: data.latest_received_nonce.max(nonce),
 			..data
 		};
 
-		OutboundLanes::<S, Instance1>::insert(LaneIdWrapper::from(lane_id).0, new_data);
+		OutboundLanes::<S, SI>::insert(lane_id, new_data);
 	}
 }
 
@@ -414,13 +407,16 @@ macro_rules! impl_assert_events_helpers_for_parachain {
 					assert_expected_events!(
 						Self,
 						vec![
-							[<$chain RuntimeEvent>]::DmpQueue(cumulus_pallet_dmp_queue::Event::ExecutedDownward {
-								outcome: Outcome::Complete(weight), ..
+							[<$chain RuntimeEvent>]::MessageQueue(pallet_message_queue::Event::Processed {
+								origin: cumulus_primitives_core::AggregateMessageOrigin::Parent,
+								weight_used,
+								success: true,
+								..
 							}) => {
-								weight: weight_within_threshold(
+								weight_used: weight_within_threshold(
 									(REF_TIME_THRESHOLD, PROOF_SIZE_THRESHOLD),
-									expected_weight.unwrap_or(*weight),
-									*weight
+									expected_weight.unwrap_or(*weight_used),
+									*weight_used
 								),
 							},
 						]
@@ -428,39 +424,57 @@ macro_rules! impl_assert_events_helpers_for_parachain {
 				}
 
 				/// Asserts a XCM from Relay Chain is incompletely executed
-				pub fn assert_dmp_queue_incomplete(
-					expected_weight: Option<Weight>,
-					expected_error: Option<Error>,
-				) {
+				///
+				/// The `pallet-message-queue` doesn't report the error of the execution.
+				pub fn assert_dmp_queue_incomplete(expected_weight: Option<Weight>) {
 					assert_expected_events!(
 						Self,
 						vec![
-							[<$chain RuntimeEvent>]::DmpQueue(cumulus_pallet_dmp_queue::Event::ExecutedDownward {
-								outcome: Outcome::Incomplete(weight, error), ..
+							[<$chain RuntimeEvent>]::MessageQueue(pallet_message_queue::Event::Processed {
+								origin: cumulus_primitives_core::AggregateMessageOrigin::Parent,
+								weight_used,
+								success: false,
+								..
 							}) => {
-								weight: weight_within_threshold(
+								weight_used: weight_within_threshold(
 									(REF_TIME_THRESHOLD, PROOF_SIZE_THRESHOLD),
-									expected_weight.unwrap_or(*weight),
-									*weight
+									expected_weight.unwrap_or(*weight_used),
+									*weight_used
 								),
-								error: *error == expected_error.unwrap_or(*error),
 							},
 						]
 					);
 				}
 
+				/// Asserts a XCM from Relay Chain is too heavy to be ever executed by the message
+				/// queue and is left for manual execution
+				pub fn assert_dmp_queue_overweight() {
+					assert_expected_events!(
+						Self,
+						vec![
+							[<$chain RuntimeEvent>]::MessageQueue(pallet_message_queue::Event::OverweightEnqueued {
+								origin: cumulus_primitives_core::AggregateMessageOrigin::Parent,
+								..
+							}) => {},
+						]
+					);
+				}
+
 				/// Asserts a XCM from another Parachain is completely executed
 				pub fn assert_xcmp_queue_success(expected_weight: Option<Weight>) {
 					assert_expected_events!(
 						Self,
 						vec![
-							[<$chain RuntimeEvent>]::XcmpQueue(
-								cumulus_pallet_xcmp_queue::Event::Success { weight, .. }
-							) => {
-								weight: weight_within_threshold(
+							[<$chain RuntimeEvent>]::MessageQueue(pallet_message_queue::Event::Processed {
+								origin: cumulus_primitives_core::AggregateMessageOrigin::Sibling(_),
+								weight_used,
+								success: true,
+								..
+							}) => {
+								weight_used: weight_within_threshold(
 									(REF_TIME_THRESHOLD, PROOF_SIZE_THRESHOLD),
-									expected_weight.unwrap_or(*weight),
-									*weight
+									expected_weight.unwrap_or(*weight_used),
+									*weight_used
 								),
 							},
 						]
@@ -126,15 +126,15 @@ decl_test_parachains! {
 		},
 		runtime = asset_hub_polkadot_runtime,
 		core = {
-			XcmpMessageHandler: asset_hub_polkadot_runtime::XcmpQueue,
-			DmpMessageHandler: asset_hub_polkadot_runtime::DmpQueue,
 			LocationToAccountId: asset_hub_polkadot_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: asset_hub_polkadot_runtime::ParachainInfo,
+			MessageQueue: asset_hub_polkadot_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: asset_hub_polkadot_runtime::PolkadotXcm,
 			Assets: asset_hub_polkadot_runtime::Assets,
 			Balances: asset_hub_polkadot_runtime::Balances,
+			HrmpChannelManager: asset_hub_polkadot_runtime::HrmpChannelManager,
 		}
 	},
 	pub struct Collectives {
@@ -144,10 +144,9 @@ decl_test_parachains! {
 		},
 		runtime = collectives_polkadot_runtime,
 		core = {
-			XcmpMessageHandler: collectives_polkadot_runtime::XcmpQueue,
-			DmpMessageHandler: collectives_polkadot_runtime::DmpQueue,
 			LocationToAccountId: collectives_polkadot_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: collectives_polkadot_runtime::ParachainInfo,
+			MessageQueue: collectives_polkadot_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: collectives_polkadot_runtime::PolkadotXcm,
@@ -161,10 +160,9 @@ decl_test_parachains! {
 		},
 		runtime = bridge_hub_polkadot_runtime,
 		core = {
-			XcmpMessageHandler: bridge_hub_polkadot_runtime::XcmpQueue,
-			DmpMessageHandler: bridge_hub_polkadot_runtime::DmpQueue,
 			LocationToAccountId: bridge_hub_polkadot_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: bridge_hub_polkadot_runtime::ParachainInfo,
+			MessageQueue: bridge_hub_polkadot_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: bridge_hub_polkadot_runtime::PolkadotXcm,
@@ -177,10 +175,9 @@ decl_test_parachains! {
 		},
 		runtime = penpal_runtime,
 		core = {
-			XcmpMessageHandler: penpal_runtime::XcmpQueue,
-			DmpMessageHandler: penpal_runtime::DmpQueue,
 			LocationToAccountId: penpal_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: penpal_runtime::ParachainInfo,
+			MessageQueue: penpal_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: penpal_runtime::PolkadotXcm,
@@ -194,10 +191,9 @@ decl_test_parachains! {
 		},
 		runtime = penpal_runtime,
 		core = {
-			XcmpMessageHandler: penpal_runtime::XcmpQueue,
-			DmpMessageHandler: penpal_runtime::DmpQueue,
 			LocationToAccountId: penpal_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: penpal_runtime::ParachainInfo,
+			MessageQueue: penpal_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: penpal_runtime::PolkadotXcm,
@@ -212,10 +208,9 @@ decl_test_parachains! {
 		},
 		runtime = asset_hub_kusama_runtime,
 		core = {
-			XcmpMessageHandler: asset_hub_kusama_runtime::XcmpQueue,
-			DmpMessageHandler: asset_hub_kusama_runtime::DmpQueue,
 			LocationToAccountId: asset_hub_kusama_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: asset_hub_kusama_runtime::ParachainInfo,
+			MessageQueue: asset_hub_kusama_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: asset_hub_kusama_runtime::PolkadotXcm,
@@ -224,6 +219,7 @@ decl_test_parachains! {
 			PoolAssets: asset_hub_kusama_runtime::PoolAssets,
 			AssetConversion: asset_hub_kusama_runtime::AssetConversion,
 			Balances: asset_hub_kusama_runtime::Balances,
+			HrmpChannelManager: asset_hub_kusama_runtime::HrmpChannelManager,
 		}
 	},
 	pub struct BridgeHubKusama {
@@ -233,10 +229,9 @@ decl_test_parachains! {
 		},
 		runtime = bridge_hub_kusama_runtime,
 		core = {
-			XcmpMessageHandler: bridge_hub_kusama_runtime::XcmpQueue,
-			DmpMessageHandler: bridge_hub_kusama_runtime::DmpQueue,
 			LocationToAccountId: bridge_hub_kusama_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: bridge_hub_kusama_runtime::ParachainInfo,
+			MessageQueue: bridge_hub_kusama_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: bridge_hub_kusama_runtime::PolkadotXcm,
@@ -249,10 +244,9 @@ decl_test_parachains! {
 		},
 		runtime = penpal_runtime,
 		core = {
-			XcmpMessageHandler: penpal_runtime::XcmpQueue,
-			DmpMessageHandler: penpal_runtime::DmpQueue,
 			LocationToAccountId: penpal_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: penpal_runtime::ParachainInfo,
+			MessageQueue: penpal_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: penpal_runtime::PolkadotXcm,
@@ -266,10 +260,9 @@ decl_test_parachains! {
 		},
 		runtime = penpal_runtime,
 		core = {
-			XcmpMessageHandler: penpal_runtime::XcmpQueue,
-			DmpMessageHandler: penpal_runtime::DmpQueue,
 			LocationToAccountId: penpal_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: penpal_runtime::ParachainInfo,
+			MessageQueue: penpal_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: penpal_runtime::PolkadotXcm,
@@ -284,10 +277,9 @@ decl_test_parachains! {
 		},
 		runtime = asset_hub_westend_runtime,
 		core = {
-			XcmpMessageHandler: asset_hub_westend_runtime::XcmpQueue,
-			DmpMessageHandler: asset_hub_westend_runtime::DmpQueue,
 			LocationToAccountId: asset_hub_westend_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: asset_hub_westend_runtime::ParachainInfo,
+			MessageQueue: asset_hub_westend_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: asset_hub_westend_runtime::PolkadotXcm,
@@ -305,10 +297,9 @@ decl_test_parachains! {
 		},
 		runtime = penpal_runtime,
 		core = {
-			XcmpMessageHandler: penpal_runtime::XcmpQueue,
-			DmpMessageHandler: penpal_runtime::DmpQueue,
 			LocationToAccountId: penpal_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: penpal_runtime::ParachainInfo,
+			MessageQueue: penpal_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: penpal_runtime::PolkadotXcm,
@@ -323,10 +314,9 @@ decl_test_parachains! {
 		},
 		runtime = bridge_hub_rococo_runtime,
 		core = {
-			XcmpMessageHandler: bridge_hub_rococo_runtime::XcmpQueue,
-			DmpMessageHandler: bridge_hub_rococo_runtime::DmpQueue,
 			LocationToAccountId: bridge_hub_rococo_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: bridge_hub_rococo_runtime::ParachainInfo,
+			MessageQueue: bridge_hub_rococo_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: bridge_hub_rococo_runtime::PolkadotXcm,
@@ -341,10 +331,9 @@ decl_test_parachains! {
 		},
 		runtime = asset_hub_kusama_runtime,
 		core = {
-			XcmpMessageHandler: asset_hub_kusama_runtime::XcmpQueue,
-			DmpMessageHandler: asset_hub_kusama_runtime::DmpQueue,
 			LocationToAccountId: asset_hub_kusama_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: asset_hub_kusama_runtime::ParachainInfo,
+			MessageQueue: asset_hub_kusama_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: asset_hub_kusama_runtime::PolkadotXcm,
@@ -359,10 +348,9 @@ decl_test_parachains! {
 		},
 		runtime = bridge_hub_rococo_runtime,
 		core = {
-			XcmpMessageHandler: bridge_hub_rococo_runtime::XcmpQueue,
-			DmpMessageHandler: bridge_hub_rococo_runtime::DmpQueue,
 			LocationToAccountId: bridge_hub_rococo_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: bridge_hub_rococo_runtime::ParachainInfo,
+			MessageQueue: bridge_hub_rococo_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: bridge_hub_rococo_runtime::PolkadotXcm,
@@ -375,10 +363,9 @@ decl_test_parachains! {
 		},
 		runtime = asset_hub_polkadot_runtime,
 		core = {
-			XcmpMessageHandler: asset_hub_polkadot_runtime::XcmpQueue,
-			DmpMessageHandler: asset_hub_polkadot_runtime::DmpQueue,
 			LocationToAccountId: asset_hub_polkadot_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: asset_hub_polkadot_runtime::ParachainInfo,
+			MessageQueue: asset_hub_polkadot_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: asset_hub_polkadot_runtime::PolkadotXcm,
@@ -392,10 +379,9 @@ decl_test_parachains! {
 		},
 		runtime = penpal_runtime,
 		core = {
-			XcmpMessageHandler: penpal_runtime::XcmpQueue,
-			DmpMessageHandler: penpal_runtime::DmpQueue,
 			LocationToAccountId: penpal_runtime::xcm_config::LocationToAccountId,
 			ParachainInfo: penpal_runtime::ParachainInfo,
+			MessageQueue: penpal_runtime::MessageQueue,
 		},
 		pallets = {
 			PolkadotXcm: penpal_runtime::PolkadotXcm,
@@ -414,8 +400,6 @@ decl_test_networks! {
 			PenpalPolkadotA,
 			PenpalPolkadotB,
 		],
-		// TODO: uncomment when https://github.com/paritytech/cumulus/pull/2528 is merged
-		// bridge = PolkadotKusamaMockBridge
 		bridge = ()
 	},
 	pub struct KusamaMockNet {
@@ -426,8 +410,6 @@ decl_test_networks! {
 			BridgeHubKusama,
 			PenpalKusamaB,
 		],
-		// TODO: uncomment when https://github.com/paritytech/cumulus/pull/2528 is merged
-		// bridge = KusamaPolkadotMockBridge
 		bridge = ()
 	},
 	pub struct WestendMockNet {
@@ -468,17 +450,6 @@ decl_test_bridges! {
 		target = BridgeHubRococo,
 		handler = WococoRococoMessageHandler
 	}
-	// TODO: uncomment when https://github.com/paritytech/cumulus/pull/2528 is merged
-	// pub struct PolkadotKusamaMockBridge {
-	// 	source = BridgeHubPolkadot,
-	// 	target = BridgeHubKusama,
-	//  handler = PolkadotKusamaMessageHandler
-	// },
-	// pub struct KusamaPolkadotMockBridge {
-	// 	source = BridgeHubKusama,
-	// 	target = BridgeHubPolkadot,
-	// 	handler = KusamaPolkadotMessageHandler
-	// }
 }
 
 // Polkadot implementation
@@ -0,0 +1,111 @@
+// Copyright Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The expectations on the execution of a flow at its origin and at its destination.
+
+use crate::WEIGHT_THRESHOLD;
+use xcm_emulator::{helpers::weight_within_threshold, Chain, Outcome, Weight, XcmError};
+
+/// A check run within the externalities of a chain.
+pub(crate) type Assertion = Box<dyn Fn()>;
+
+/// The expected outcome of the local execution of the flow at its origin.
+#[derive(Clone, Debug)]
+pub(crate) enum OriginExpectation {
+	/// The XCM is completely executed, with about `weight`, if given.
+	Complete(Option<Weight>),
+	/// The XCM is executed up to `error`.
+	Incomplete(Option<Weight>, Option<XcmError>),
+	/// The XCM isn't executed at all, because of `error`.
+	Error(Option<XcmError>),
+}
+
+impl OriginExpectation {
+	/// Checks the `Attempted` events of `pallet-xcm` at `C`.
+	pub(crate) fn check<C>(&self)
+	where
+		C: Chain,
+		C::Runtime: pallet_xcm::Config,
+		C::RuntimeEvent: TryInto<pallet_xcm::Event<C::Runtime>>,
+	{
+		let outcomes: Vec<Outcome> = C::events()
+			.into_iter()
+			.filter_map(|event| match TryInto::<pallet_xcm::Event<C::Runtime>>::try_into(event) {
+				Ok(pallet_xcm::Event::Attempted { outcome }) => Some(outcome),
+				_ => None,
+			})
+			.collect();
+
+		let met = outcomes.iter().any(|outcome| match (self, outcome) {
+			(Self::Complete(expected), Outcome::Complete(weight)) =>
+				weight_matches(*expected, *weight),
+			(Self::Incomplete(expected, expected_error), Outcome::Incomplete(weight, error)) =>
+				weight_matches(*expected, *weight) && expected_error.map_or(true, |e| e == *error),
+			(Self::Error(expected_error), Outcome::Error(error)) =>
+				expected_error.map_or(true, |e| e == *error),
+			_ => false,
+		});
+		assert!(
+			met,
+			"\n\n{}: expected the XCM to be executed with `{:?}`, but got {:?}\n",
+			std::any::type_name::<C>(),
+			self,
+			outcomes,
+		);
+	}
+}
+
+/// The expected outcome of processing the XCM of the flow at its destination.
+#[derive(Clone, Debug)]
+pub(crate) enum DestinationExpectation {
+	/// The XCM is processed by the message queue, with about `weight`, if given.
+	Processed { success: bool, weight: Option<Weight> },
+	/// The XCM is too heavy to be processed by the message queue and left for manual execution.
+	Overweight,
+}
+
+impl DestinationExpectation {
+	/// Checks the events of `pallet-message-queue` at `C`.
+	pub(crate) fn check<C>(&self)
+	where
+		C: Chain,
+		C::Runtime: pallet_message_queue::Config,
+		C::RuntimeEvent: TryInto<pallet_message_queue::Event<C::Runtime>>,
+	{
+		let events: Vec<pallet_message_queue::Event<C::Runtime>> =
+			C::events().into_iter().filter_map(|event| event.try_into().ok()).collect();
+
+		let met = events.iter().any(|event| match (self, event) {
+			(
+				Self::Processed { success: expected_success, weight: expected },
+				pallet_message_queue::Event::Processed { success, weight_used, .. },
+			) => success == expected_success && weight_matches(*expected, *weight_used),
+			(Self::Overweight, pallet_message_queue::Event::OverweightEnqueued { .. }) => true,
+			_ => false,
+		});
+		assert!(
+			met,
+			"\n\n{}: expected the XCM to be `{:?}` by the message queue, but got {:?}\n",
+			std::any::type_name::<C>(),
+			self,
+			events,
+		);
+	}
+}
+
+fn weight_matches(expected: Option<Weight>, weight: Weight) -> bool {
+	expected.map_or(true, |expected| weight_within_threshold(WEIGHT_THRESHOLD, expected, weight))
+}
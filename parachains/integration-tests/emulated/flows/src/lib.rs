@@ -0,0 +1,65 @@
+// Copyright Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Declarative tests of cross-chain flows between the chains of an `xcm-emulator` network.
+//!
+//! A flow is started with `pallet-xcm` at its origin chain and followed to its destination chain,
+//! where the expectations set on the flow are checked:
+//!
+//! ```ignore
+//! test_teleport(Kusama, AssetHubKusama, Here, KUSAMA_ED * 1000)
+//! 	.assert_origin_complete(Some(Weight::from_parts(631_531_000, 7_186)))
+//! 	.assert_destination_complete(None)
+//! 	.assert_sender_charged()
+//! 	.assert_fees_le(KUSAMA_ED)
+//! 	.run();
+//! ```
+//!
+//! The flows only make use of `pallet-xcm`, `pallet-message-queue` and the native balances of the
+//! chains, so that they can be run against any chain declared with `xcm-emulator`.
+
+mod expectations;
+mod transact;
+mod transfer;
+
+pub use transact::{test_transact, TransactFlow};
+pub use transfer::{
+	test_reserve_transfer, test_teleport, TransferFlow, TransferKind, TransferOutcome,
+};
+
+use xcm_emulator::{Chain, MultiLocation};
+
+/// The tolerated deviation from the expected weights, in percent of the ref time and of the proof
+/// size.
+pub const WEIGHT_THRESHOLD: (u64, u64) = (33, 33);
+
+/// The seed of the account that starts a flow, unless set otherwise.
+pub const DEFAULT_SENDER: &str = "Alice";
+
+/// The seed of the account that receives the assets of a transfer, unless set otherwise.
+pub const DEFAULT_RECEIVER: &str = "Bob";
+
+/// Returns the location of `To` as seen from `From`, both chains of the same network.
+///
+/// Must not be called from within the externalities of a parachain.
+pub fn location_of<From: Chain, To: Chain>() -> MultiLocation {
+	let to = To::location_in_network();
+	if From::location_in_network() == MultiLocation::here() {
+		to
+	} else {
+		MultiLocation::new(1, to.interior)
+	}
+}
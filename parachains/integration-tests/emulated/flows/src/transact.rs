@@ -0,0 +1,192 @@
+// Copyright Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Calls dispatched at another chain with `Transact`.
+
+use crate::{
+	expectations::{Assertion, DestinationExpectation},
+	location_of,
+};
+use frame_support::{
+	assert_err, assert_ok,
+	sp_runtime::{DispatchError, DispatchResult},
+};
+use std::marker::PhantomData;
+use xcm::{DoubleEncoded, VersionedXcm};
+use xcm_emulator::{
+	AccountId, Chain, MultiLocation, OriginKind, Transact, UnpaidExecution, VersionedMultiLocation,
+	Weight, WeightLimit, Xcm,
+};
+
+/// Starts sending `call` from the chain `from` to be dispatched with `Transact` at the chain `to`.
+///
+/// The call is sent by the root origin of `from` with `OriginKind::Superuser` in an unpaid XCM,
+/// unless set otherwise.
+pub fn test_transact<From: Chain, To: Chain>(
+	_from: From,
+	_to: To,
+	call: DoubleEncoded<()>,
+) -> TransactFlow<From, To> {
+	TransactFlow::new(call)
+}
+
+/// A `Transact` sent from one chain to another and the expectations on it.
+///
+/// The XCM is only sent by [`Self::run`].
+pub struct TransactFlow<Origin, Destination> {
+	call: DoubleEncoded<()>,
+	signer: Option<AccountId>,
+	origin_kind: OriginKind,
+	require_weight_at_most: Weight,
+	dispatch_error: Option<DispatchError>,
+	expect_sent: bool,
+	destination_expectation: Option<DestinationExpectation>,
+	destination_assertions: Vec<Assertion>,
+	_marker: PhantomData<(Origin, Destination)>,
+}
+
+impl<Origin, Destination> TransactFlow<Origin, Destination>
+where
+	Origin: Chain,
+	Origin::Runtime: pallet_xcm::Config + frame_system::Config<AccountId = AccountId>,
+	Origin::RuntimeEvent: TryInto<pallet_xcm::Event<Origin::Runtime>>,
+	Destination: Chain,
+	Destination::Runtime: pallet_message_queue::Config,
+	Destination::RuntimeEvent: TryInto<pallet_message_queue::Event<Destination::Runtime>>,
+{
+	fn new(call: DoubleEncoded<()>) -> Self {
+		Self {
+			call,
+			signer: None,
+			origin_kind: OriginKind::Superuser,
+			require_weight_at_most: Weight::from_parts(1_000_000_000, 200_000),
+			dispatch_error: None,
+			expect_sent: false,
+			destination_expectation: None,
+			destination_assertions: Vec::new(),
+			_marker: Default::default(),
+		}
+	}
+
+	/// Sends the XCM signed by `signer` instead of the root origin.
+	pub fn signed_by(mut self, signer: AccountId) -> Self {
+		self.signer = Some(signer);
+		self
+	}
+
+	/// Dispatches the call with `origin_kind`.
+	pub fn origin_kind(mut self, origin_kind: OriginKind) -> Self {
+		self.origin_kind = origin_kind;
+		self
+	}
+
+	/// Allows the call to use at most `weight`.
+	pub fn require_weight_at_most(mut self, weight: Weight) -> Self {
+		self.require_weight_at_most = weight;
+		self
+	}
+
+	/// Expects the XCM not to be sent, because `pallet_xcm::send` fails with `error`.
+	pub fn assert_send_error(mut self, error: impl Into<DispatchError>) -> Self {
+		self.dispatch_error = Some(error.into());
+		self
+	}
+
+	/// Expects `pallet-xcm` to report the XCM as sent.
+	pub fn assert_sent(mut self) -> Self {
+		self.expect_sent = true;
+		self
+	}
+
+	/// Expects the XCM to be successfully processed at the destination, with about `weight`, if
+	/// given.
+	pub fn assert_destination_complete(mut self, weight: Option<Weight>) -> Self {
+		self.destination_expectation =
+			Some(DestinationExpectation::Processed { success: true, weight });
+		self
+	}
+
+	/// Expects the processing of the XCM at the destination to fail, after about `weight`, if
+	/// given.
+	pub fn assert_destination_incomplete(mut self, weight: Option<Weight>) -> Self {
+		self.destination_expectation =
+			Some(DestinationExpectation::Processed { success: false, weight });
+		self
+	}
+
+	/// Expects the XCM to be left for manual execution, because it is too heavy for the message
+	/// queue of the destination.
+	pub fn assert_destination_overweight(mut self) -> Self {
+		self.destination_expectation = Some(DestinationExpectation::Overweight);
+		self
+	}
+
+	/// Runs `assertion` within the externalities of the destination, after the XCM is sent.
+	pub fn assert_on_destination(mut self, assertion: impl Fn() + 'static) -> Self {
+		self.destination_assertions.push(Box::new(assertion));
+		self
+	}
+
+	/// Sends the XCM and checks the expectations.
+	pub fn run(self) {
+		let dest = location_of::<Origin, Destination>();
+
+		Origin::execute_with(|| {
+			match &self.dispatch_error {
+				Some(error) => assert_err!(self.send(dest), *error),
+				None => assert_ok!(self.send(dest)),
+			}
+			if self.expect_sent {
+				assert!(
+					Origin::events().into_iter().any(|event| matches!(
+						TryInto::<pallet_xcm::Event<Origin::Runtime>>::try_into(event),
+						Ok(pallet_xcm::Event::Sent { .. })
+					)),
+					"\n\n{}: expected the XCM to be sent\n",
+					std::any::type_name::<Origin>(),
+				);
+			}
+		});
+
+		Destination::execute_with(|| {
+			if let Some(expectation) = &self.destination_expectation {
+				expectation.check::<Destination>();
+			}
+			self.destination_assertions.iter().for_each(|assertion| assertion());
+		});
+	}
+
+	fn send(&self, dest: MultiLocation) -> DispatchResult {
+		let origin = match &self.signer {
+			Some(signer) => frame_system::RawOrigin::Signed(signer.clone()),
+			None => frame_system::RawOrigin::Root,
+		};
+		let message = VersionedXcm::from(Xcm(vec![
+			UnpaidExecution { weight_limit: WeightLimit::Unlimited, check_origin: None },
+			Transact {
+				require_weight_at_most: self.require_weight_at_most,
+				origin_kind: self.origin_kind,
+				call: self.call.clone(),
+			},
+		]));
+
+		pallet_xcm::Pallet::<Origin::Runtime>::send(
+			origin.into(),
+			Box::new(VersionedMultiLocation::from(dest)),
+			Box::new(message),
+		)
+	}
+}
@@ -0,0 +1,358 @@
+// Copyright Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Teleports and reserve transfers of assets.
+
+use crate::{
+	expectations::{Assertion, DestinationExpectation, OriginExpectation},
+	location_of, DEFAULT_RECEIVER, DEFAULT_SENDER,
+};
+use frame_support::{assert_ok, sp_runtime::DispatchResult};
+use std::marker::PhantomData;
+use xcm_emulator::{
+	AccountId, AccountId32Junction, Balance, Chain, MultiAssets, MultiLocation,
+	VersionedMultiAssets, VersionedMultiLocation, Weight, WeightLimit, XcmError,
+};
+
+/// How the assets are transferred.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferKind {
+	/// The assets are teleported.
+	Teleport,
+	/// The assets are transferred with the origin as their reserve.
+	ReserveTransfer,
+}
+
+/// Starts a teleport of `amount` of `asset` from the chain `from` to the chain `to`.
+///
+/// `asset` is the location of the asset as seen from `from`.
+pub fn test_teleport<From: Chain, To: Chain>(
+	_from: From,
+	_to: To,
+	asset: impl Into<MultiLocation>,
+	amount: Balance,
+) -> TransferFlow<From, To> {
+	TransferFlow::new(TransferKind::Teleport, asset.into(), amount)
+}
+
+/// Starts a reserve transfer of `amount` of `asset` from the chain `from` to the chain `to`.
+///
+/// `asset` is the location of the asset as seen from `from`.
+pub fn test_reserve_transfer<From: Chain, To: Chain>(
+	_from: From,
+	_to: To,
+	asset: impl Into<MultiLocation>,
+	amount: Balance,
+) -> TransferFlow<From, To> {
+	TransferFlow::new(TransferKind::ReserveTransfer, asset.into(), amount)
+}
+
+/// A transfer of an asset between two chains and the expectations on it.
+///
+/// The transfer is only dispatched by [`Self::run`]. The balances, which the expectations refer
+/// to, are the native balances of the sender at the origin and of the receiver at the destination.
+pub struct TransferFlow<Origin, Destination> {
+	kind: TransferKind,
+	asset: MultiLocation,
+	amount: Balance,
+	sender: AccountId,
+	receiver: AccountId,
+	weight_limit: Option<WeightLimit>,
+	origin_expectation: Option<OriginExpectation>,
+	destination_expectation: Option<DestinationExpectation>,
+	origin_assertions: Vec<Assertion>,
+	destination_assertions: Vec<Assertion>,
+	balance_checks: Vec<fn(&TransferOutcome)>,
+	max_fees: Option<Balance>,
+	_marker: PhantomData<(Origin, Destination)>,
+}
+
+/// The native balances of the sender and of the receiver before and after a transfer.
+#[derive(Clone, Debug)]
+pub struct TransferOutcome {
+	/// The transferred amount.
+	pub amount: Balance,
+	/// The balance of the sender at the origin before the transfer.
+	pub sender_balance_before: Balance,
+	/// The balance of the sender at the origin after the transfer.
+	pub sender_balance_after: Balance,
+	/// The balance of the receiver at the destination before the transfer.
+	pub receiver_balance_before: Balance,
+	/// The balance of the receiver at the destination after the transfer.
+	pub receiver_balance_after: Balance,
+}
+
+impl TransferOutcome {
+	/// The amount the receiver got.
+	pub fn received(&self) -> Balance {
+		self.receiver_balance_after.saturating_sub(self.receiver_balance_before)
+	}
+
+	/// The part of the transferred amount, that the receiver didn't get.
+	pub fn fees(&self) -> Balance {
+		self.amount.saturating_sub(self.received())
+	}
+}
+
+impl<Origin, Destination> TransferFlow<Origin, Destination>
+where
+	Origin: Chain,
+	Origin::Runtime: pallet_xcm::Config + frame_system::Config<AccountId = AccountId>,
+	Origin::RuntimeEvent: TryInto<pallet_xcm::Event<Origin::Runtime>>,
+	Destination: Chain,
+	Destination::Runtime: pallet_message_queue::Config,
+	Destination::RuntimeEvent: TryInto<pallet_message_queue::Event<Destination::Runtime>>,
+{
+	fn new(kind: TransferKind, asset: MultiLocation, amount: Balance) -> Self {
+		Self {
+			kind,
+			asset,
+			amount,
+			sender: Origin::account_id_of(DEFAULT_SENDER),
+			receiver: Destination::account_id_of(DEFAULT_RECEIVER),
+			weight_limit: Some(WeightLimit::Unlimited),
+			origin_expectation: None,
+			destination_expectation: None,
+			origin_assertions: Vec::new(),
+			destination_assertions: Vec::new(),
+			balance_checks: Vec::new(),
+			max_fees: None,
+			_marker: Default::default(),
+		}
+	}
+
+	/// Transfers from `sender` instead of the default sender.
+	pub fn sender(mut self, sender: AccountId) -> Self {
+		self.sender = sender;
+		self
+	}
+
+	/// Transfers to `receiver` instead of the default receiver.
+	pub fn receiver(mut self, receiver: AccountId) -> Self {
+		self.receiver = receiver;
+		self
+	}
+
+	/// Limits the weight bought at the destination to `weight_limit`.
+	pub fn weight_limit(mut self, weight_limit: WeightLimit) -> Self {
+		self.weight_limit = Some(weight_limit);
+		self
+	}
+
+	/// Transfers with `teleport_assets` or `reserve_transfer_assets`, which take no weight limit,
+	/// instead of their `limited_` variants.
+	pub fn without_weight_limit(mut self) -> Self {
+		self.weight_limit = None;
+		self
+	}
+
+	/// Expects the XCM to be completely executed at the origin, with about `weight`, if given.
+	pub fn assert_origin_complete(mut self, weight: Option<Weight>) -> Self {
+		self.origin_expectation = Some(OriginExpectation::Complete(weight));
+		self
+	}
+
+	/// Expects the XCM to be executed at the origin up to `error`, if given.
+	pub fn assert_origin_incomplete(
+		mut self,
+		weight: Option<Weight>,
+		error: Option<XcmError>,
+	) -> Self {
+		self.origin_expectation = Some(OriginExpectation::Incomplete(weight, error));
+		self
+	}
+
+	/// Expects the XCM not to be executed at the origin at all, because of `error`.
+	pub fn assert_origin_error(mut self, error: XcmError) -> Self {
+		self.origin_expectation = Some(OriginExpectation::Error(Some(error)));
+		self
+	}
+
+	/// Expects the XCM to be successfully processed at the destination, with about `weight`, if
+	/// given.
+	pub fn assert_destination_complete(mut self, weight: Option<Weight>) -> Self {
+		self.destination_expectation =
+			Some(DestinationExpectation::Processed { success: true, weight });
+		self
+	}
+
+	/// Expects the processing of the XCM at the destination to fail, after about `weight`, if
+	/// given.
+	pub fn assert_destination_incomplete(mut self, weight: Option<Weight>) -> Self {
+		self.destination_expectation =
+			Some(DestinationExpectation::Processed { success: false, weight });
+		self
+	}
+
+	/// Runs `assertion` within the externalities of the origin, after the transfer.
+	pub fn assert_on_origin(mut self, assertion: impl Fn() + 'static) -> Self {
+		self.origin_assertions.push(Box::new(assertion));
+		self
+	}
+
+	/// Runs `assertion` within the externalities of the destination, after the transfer.
+	pub fn assert_on_destination(mut self, assertion: impl Fn() + 'static) -> Self {
+		self.destination_assertions.push(Box::new(assertion));
+		self
+	}
+
+	/// Expects the balance of the sender to be reduced by the transferred amount.
+	pub fn assert_sender_charged(mut self) -> Self {
+		self.balance_checks.push(|outcome| {
+			assert_eq!(
+				outcome.sender_balance_before - outcome.amount,
+				outcome.sender_balance_after,
+				"the sender is not charged the transferred amount"
+			)
+		});
+		self
+	}
+
+	/// Expects the balance of the sender not to change.
+	pub fn assert_sender_unchanged(mut self) -> Self {
+		self.balance_checks.push(|outcome| {
+			assert_eq!(
+				outcome.sender_balance_before, outcome.sender_balance_after,
+				"the balance of the sender changed"
+			)
+		});
+		self
+	}
+
+	/// Expects the balance of the receiver to increase.
+	pub fn assert_receiver_credited(mut self) -> Self {
+		self.balance_checks.push(|outcome| {
+			assert!(
+				outcome.receiver_balance_after > outcome.receiver_balance_before,
+				"the receiver didn't get anything"
+			)
+		});
+		self
+	}
+
+	/// Expects the balance of the receiver not to change.
+	pub fn assert_receiver_unchanged(mut self) -> Self {
+		self.balance_checks.push(|outcome| {
+			assert_eq!(
+				outcome.receiver_balance_before, outcome.receiver_balance_after,
+				"the balance of the receiver changed"
+			)
+		});
+		self
+	}
+
+	/// Expects the receiver to get the transferred amount minus at most `max_fees`.
+	pub fn assert_fees_le(mut self, max_fees: Balance) -> Self {
+		self.max_fees = Some(max_fees);
+		self
+	}
+
+	/// Dispatches the transfer and checks the expectations.
+	pub fn run(self) -> TransferOutcome {
+		let dest = location_of::<Origin, Destination>();
+		let beneficiary: MultiLocation =
+			AccountId32Junction { network: None, id: self.receiver.clone().into() }.into();
+		let assets: MultiAssets = (self.asset, self.amount).into();
+
+		let sender_balance_before = Origin::account_data_of(self.sender.clone()).free;
+		let receiver_balance_before = Destination::account_data_of(self.receiver.clone()).free;
+
+		Origin::execute_with(|| {
+			assert_ok!(self.dispatch(dest, beneficiary, assets));
+			if let Some(expectation) = &self.origin_expectation {
+				expectation.check::<Origin>();
+			}
+			self.origin_assertions.iter().for_each(|assertion| assertion());
+		});
+
+		Destination::execute_with(|| {
+			if let Some(expectation) = &self.destination_expectation {
+				expectation.check::<Destination>();
+			}
+			self.destination_assertions.iter().for_each(|assertion| assertion());
+		});
+
+		let outcome = TransferOutcome {
+			amount: self.amount,
+			sender_balance_before,
+			sender_balance_after: Origin::account_data_of(self.sender.clone()).free,
+			receiver_balance_before,
+			receiver_balance_after: Destination::account_data_of(self.receiver.clone()).free,
+		};
+		self.balance_checks.iter().for_each(|check| check(&outcome));
+		if let Some(max_fees) = self.max_fees {
+			assert!(
+				outcome.fees() <= max_fees,
+				"the receiver got {} of {}, the fees of {} exceed {}",
+				outcome.received(),
+				outcome.amount,
+				outcome.fees(),
+				max_fees,
+			);
+		}
+		outcome
+	}
+
+	fn dispatch(
+		&self,
+		dest: MultiLocation,
+		beneficiary: MultiLocation,
+		assets: MultiAssets,
+	) -> DispatchResult {
+		type XcmPallet<Origin> = pallet_xcm::Pallet<<Origin as Chain>::Runtime>;
+
+		let origin = frame_system::RawOrigin::Signed(self.sender.clone()).into();
+		let dest = Box::new(VersionedMultiLocation::from(dest));
+		let beneficiary = Box::new(VersionedMultiLocation::from(beneficiary));
+		let assets = Box::new(VersionedMultiAssets::from(assets));
+		let fee_asset_item = 0;
+
+		match (self.kind, self.weight_limit.clone()) {
+			(TransferKind::Teleport, Some(weight_limit)) =>
+				XcmPallet::<Origin>::limited_teleport_assets(
+					origin,
+					dest,
+					beneficiary,
+					assets,
+					fee_asset_item,
+					weight_limit,
+				),
+			(TransferKind::Teleport, None) => XcmPallet::<Origin>::teleport_assets(
+				origin,
+				dest,
+				beneficiary,
+				assets,
+				fee_asset_item,
+			),
+			(TransferKind::ReserveTransfer, Some(weight_limit)) =>
+				XcmPallet::<Origin>::limited_reserve_transfer_assets(
+					origin,
+					dest,
+					beneficiary,
+					assets,
+					fee_asset_item,
+					weight_limit,
+				),
+			(TransferKind::ReserveTransfer, None) => XcmPallet::<Origin>::reserve_transfer_assets(
+				origin,
+				dest,
+				beneficiary,
+				assets,
+				fee_asset_item,
+			),
+		}
+	}
+}
@@ -0,0 +1,352 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet to manage the HRMP channels of the parachain with its sibling parachains.
+//!
+//! The HRMP channels are opened, accepted and closed by the `Hrmp` pallet of the relay chain.
+//! This pallet wraps these calls into the XCM `Transact` instructions, that are executed by the
+//! relay chain with the origin of this parachain. The execution fee is withdrawn from the
+//! sovereign account of the parachain on the relay chain and the surplus is refunded to the same
+//! account. The deposits, required to open and accept channels, are reserved from the sovereign
+//! account by the relay chain itself, so it must be funded accordingly.
+//!
+//! Requests, that can't be sent to the relay chain (e.g. because the upward message queue is full),
+//! are retried every `RetryDelay` blocks, up to `MaxRetries` times.
+//!
+//! The pallet keeps the registry of the channel states, as known by this parachain. Outbound
+//! channels are reported as open as soon as the relay chain reports them in the validation data.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use cumulus_primitives_core::{ChannelStatus, GetChannelInfo, ParaId};
+use frame_support::{traits::Get, BoundedVec};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Saturating, RuntimeDebug};
+use sp_std::prelude::*;
+use xcm::latest::prelude::*;
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Index of the `hrmp_init_open_channel` call in the `Hrmp` pallet of the relay chain.
+const INIT_OPEN_CHANNEL_CALL_INDEX: u8 = 0;
+/// Index of the `hrmp_accept_open_channel` call in the `Hrmp` pallet of the relay chain.
+const ACCEPT_OPEN_CHANNEL_CALL_INDEX: u8 = 1;
+/// Index of the `hrmp_close_channel` call in the `Hrmp` pallet of the relay chain.
+const CLOSE_CHANNEL_CALL_INDEX: u8 = 2;
+
+/// A request to the `Hrmp` pallet of the relay chain.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum HrmpRequest {
+	/// Request to open the channel from this parachain to the `recipient`.
+	InitOpenChannel { recipient: ParaId, max_capacity: u32, max_message_size: u32 },
+	/// Accept the request to open the channel from the `sender` to this parachain.
+	AcceptOpenChannel { sender: ParaId },
+	/// Close the channel from the `sender` to the `recipient`. One of them is this parachain.
+	CloseChannel { sender: ParaId, recipient: ParaId },
+}
+
+impl HrmpRequest {
+	/// Returns the sender and the recipient of the channel, this request is about.
+	fn channel(&self, self_para_id: ParaId) -> (ParaId, ParaId) {
+		match *self {
+			HrmpRequest::InitOpenChannel { recipient, .. } => (self_para_id, recipient),
+			HrmpRequest::AcceptOpenChannel { sender } => (sender, self_para_id),
+			HrmpRequest::CloseChannel { sender, recipient } => (sender, recipient),
+		}
+	}
+
+	/// Returns the state of the channel, once the request is sent to the relay chain.
+	fn requested_state(&self) -> ChannelState {
+		match self {
+			HrmpRequest::InitOpenChannel { .. } => ChannelState::OpenRequested,
+			HrmpRequest::AcceptOpenChannel { .. } => ChannelState::Accepted,
+			HrmpRequest::CloseChannel { .. } => ChannelState::CloseRequested,
+		}
+	}
+
+	/// Returns the encoded call of the `Hrmp` pallet with the given `pallet_index`.
+	fn encode_call(&self, pallet_index: u8) -> Vec<u8> {
+		match *self {
+			HrmpRequest::InitOpenChannel { recipient, max_capacity, max_message_size } => (
+				pallet_index,
+				INIT_OPEN_CHANNEL_CALL_INDEX,
+				recipient,
+				max_capacity,
+				max_message_size,
+			)
+				.encode(),
+			HrmpRequest::AcceptOpenChannel { sender } =>
+				(pallet_index, ACCEPT_OPEN_CHANNEL_CALL_INDEX, sender).encode(),
+			// the `HrmpChannelId` is encoded as the sender followed by the recipient
+			HrmpRequest::CloseChannel { sender, recipient } =>
+				(pallet_index, CLOSE_CHANNEL_CALL_INDEX, sender, recipient).encode(),
+		}
+	}
+}
+
+/// The state of the HRMP channel, as known by this parachain.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ChannelState {
+	/// The request to open the channel has been sent to the relay chain.
+	OpenRequested,
+	/// The request to accept the channel has been sent to the relay chain.
+	Accepted,
+	/// The outbound channel is open.
+	Open,
+	/// The request to close the channel has been sent to the relay chain.
+	CloseRequested,
+	/// The request couldn't be sent to the relay chain and will be retried.
+	Pending,
+	/// The request couldn't be sent to the relay chain and won't be retried.
+	Failed,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin, that is allowed to manage the channels.
+		type ControllerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The sender of the requests to the relay chain.
+		type XcmSender: SendXcm;
+
+		/// The id of this parachain.
+		type SelfParaId: Get<ParaId>;
+
+		/// Information about the outbound channels of this parachain.
+		type ChannelInfo: GetChannelInfo;
+
+		/// The index of the `Hrmp` pallet in the relay chain runtime.
+		#[pallet::constant]
+		type RelayHrmpPalletIndex: Get<u8>;
+
+		/// The amount of the relay chain native asset, that is withdrawn from the sovereign
+		/// account of this parachain to pay for the execution of a request.
+		#[pallet::constant]
+		type RelayExecutionFee: Get<u128>;
+
+		/// The maximal weight of the `Hrmp` pallet call on the relay chain.
+		#[pallet::constant]
+		type RelayCallWeight: Get<Weight>;
+
+		/// The number of blocks, after which the request, that couldn't be sent, is retried.
+		#[pallet::constant]
+		type RetryDelay: Get<BlockNumberFor<Self>>;
+
+		/// The maximal number of times the request is retried.
+		#[pallet::constant]
+		type MaxRetries: Get<u32>;
+
+		/// The maximal number of requests, that are retried at the same block.
+		#[pallet::constant]
+		type MaxRetriesPerBlock: Get<u32>;
+	}
+
+	/// The states of the channels, that have been requested by this parachain, keyed by the
+	/// sender and the recipient of the channel.
+	#[pallet::storage]
+	pub(super) type Channels<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ParaId, Twox64Concat, ParaId, ChannelState, OptionQuery>;
+
+	/// The requests, that are retried at the given block, along with the number of failed attempts.
+	#[pallet::storage]
+	pub(super) type Retries<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<(HrmpRequest, u32), T::MaxRetriesPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The request has been sent to the relay chain.
+		RequestSent { request: HrmpRequest, message_hash: XcmHash },
+		/// The request couldn't be sent to the relay chain. It is retried at the `retry_at`
+		/// block, if any.
+		RequestFailed {
+			request: HrmpRequest,
+			error: SendError,
+			retry_at: Option<BlockNumberFor<T>>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The channel from the parachain to itself can't be managed.
+		ChannelToSelf,
+		/// This parachain is neither the sender nor the recipient of the channel.
+		NotParticipant,
+		/// The outbound channel is already open.
+		ChannelAlreadyOpen,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			let retries = Retries::<T>::take(n);
+			let weight = T::DbWeight::get()
+				.reads_writes(2, 2)
+				.saturating_mul(retries.len() as u64)
+				.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+
+			for (request, failed_attempts) in retries {
+				Self::send_request(request, failed_attempts);
+			}
+
+			weight
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Request to open the channel from this parachain to the `recipient`.
+		///
+		/// The `max_capacity` and `max_message_size` are the proposed limits of the channel.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2))]
+		pub fn request_open_channel(
+			origin: OriginFor<T>,
+			recipient: ParaId,
+			max_capacity: u32,
+			max_message_size: u32,
+		) -> DispatchResult {
+			T::ControllerOrigin::ensure_origin(origin)?;
+			ensure!(recipient != T::SelfParaId::get(), Error::<T>::ChannelToSelf);
+			ensure!(
+				matches!(T::ChannelInfo::get_channel_status(recipient), ChannelStatus::Closed),
+				Error::<T>::ChannelAlreadyOpen
+			);
+
+			Self::send_request(
+				HrmpRequest::InitOpenChannel { recipient, max_capacity, max_message_size },
+				0,
+			);
+			Ok(())
+		}
+
+		/// Accept the request to open the channel from the `sender` to this parachain.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2))]
+		pub fn accept_open_channel(origin: OriginFor<T>, sender: ParaId) -> DispatchResult {
+			T::ControllerOrigin::ensure_origin(origin)?;
+			ensure!(sender != T::SelfParaId::get(), Error::<T>::ChannelToSelf);
+
+			Self::send_request(HrmpRequest::AcceptOpenChannel { sender }, 0);
+			Ok(())
+		}
+
+		/// Close the channel from the `sender` to the `recipient`.
+		///
+		/// Either the `sender` or the `recipient` must be this parachain.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2))]
+		pub fn close_channel(
+			origin: OriginFor<T>,
+			sender: ParaId,
+			recipient: ParaId,
+		) -> DispatchResult {
+			T::ControllerOrigin::ensure_origin(origin)?;
+			let self_para_id = T::SelfParaId::get();
+			ensure!(sender != recipient, Error::<T>::ChannelToSelf);
+			ensure!(
+				sender == self_para_id || recipient == self_para_id,
+				Error::<T>::NotParticipant
+			);
+
+			Self::send_request(HrmpRequest::CloseChannel { sender, recipient }, 0);
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns the state of the channel from the `sender` to the `recipient`, if it is known.
+	pub fn channel_state(sender: ParaId, recipient: ParaId) -> Option<ChannelState> {
+		let state = Channels::<T>::get(sender, recipient);
+		let is_outbound_open = sender == T::SelfParaId::get() &&
+			!matches!(T::ChannelInfo::get_channel_status(recipient), ChannelStatus::Closed);
+		match state {
+			Some(ChannelState::OpenRequested) | None if is_outbound_open =>
+				Some(ChannelState::Open),
+			state => state,
+		}
+	}
+
+	/// Sends the `request` to the relay chain, scheduling a retry if it fails.
+	fn send_request(request: HrmpRequest, failed_attempts: u32) {
+		let (sender, recipient) = request.channel(T::SelfParaId::get());
+		match Self::send_to_relay(&request) {
+			Ok(message_hash) => {
+				Channels::<T>::insert(sender, recipient, request.requested_state());
+				Self::deposit_event(Event::RequestSent { request, message_hash });
+			},
+			Err(error) => {
+				let failed_attempts = failed_attempts.saturating_add(1);
+				let retry_at = frame_system::Pallet::<T>::block_number()
+					.saturating_add(T::RetryDelay::get().max(1u32.into()));
+				let retry_at = (failed_attempts <= T::MaxRetries::get() &&
+					Retries::<T>::try_mutate(retry_at, |retries| {
+						retries.try_push((request.clone(), failed_attempts))
+					})
+					.is_ok())
+				.then_some(retry_at);
+
+				let state =
+					if retry_at.is_some() { ChannelState::Pending } else { ChannelState::Failed };
+				Channels::<T>::insert(sender, recipient, state);
+				Self::deposit_event(Event::RequestFailed { request, error, retry_at });
+			},
+		}
+	}
+
+	/// Wraps the `request` into the XCM program and sends it to the relay chain.
+	fn send_to_relay(request: &HrmpRequest) -> Result<XcmHash, SendError> {
+		let fee: MultiAsset = (Here, T::RelayExecutionFee::get()).into();
+		let sovereign_account: MultiLocation = Parachain(T::SelfParaId::get().into()).into();
+		let message = Xcm(vec![
+			WithdrawAsset(fee.clone().into()),
+			BuyExecution { fees: fee, weight_limit: Unlimited },
+			Transact {
+				origin_kind: OriginKind::Native,
+				require_weight_at_most: T::RelayCallWeight::get(),
+				call: request.encode_call(T::RelayHrmpPalletIndex::get()).into(),
+			},
+			RefundSurplus,
+			DepositAsset { assets: Wild(AllCounted(1)), beneficiary: sovereign_account },
+		]);
+
+		send_xcm::<T::XcmSender>(MultiLocation::parent(), message).map(|(hash, _)| hash)
+	}
+}
@@ -0,0 +1,133 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use crate as hrmp_channel_manager;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, ConstU64, Everything},
+	weights::Weight,
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		HrmpChannelManager: hrmp_channel_manager::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+	pub const SelfParaId: ParaId = ParaId::new(1000);
+	pub const RelayHrmpPalletIndex: u8 = 60;
+	pub const RelayExecutionFee: u128 = 1_000_000;
+	pub const RelayCallWeight: Weight = Weight::from_parts(1_000_000, 10_000);
+	pub static SentMessages: Vec<(MultiLocation, Xcm<()>)> = vec![];
+	pub static SendingFails: bool = false;
+	pub static OpenChannels: Vec<ParaId> = vec![];
+}
+
+/// Sender, that stores sent messages in the `SentMessages`, unless the `SendingFails`.
+pub struct TestSender;
+impl SendXcm for TestSender {
+	type Ticket = (MultiLocation, Xcm<()>);
+
+	fn validate(
+		dest: &mut Option<MultiLocation>,
+		msg: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		if SendingFails::get() {
+			return Err(SendError::Transport("queue is full"))
+		}
+		Ok(((dest.take().unwrap(), msg.take().unwrap()), MultiAssets::new()))
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		SentMessages::mutate(|messages| messages.push(ticket));
+		Ok([0u8; 32])
+	}
+}
+
+/// Reports channels from the `OpenChannels` as open.
+pub struct TestChannelInfo;
+impl GetChannelInfo for TestChannelInfo {
+	fn get_channel_status(id: ParaId) -> ChannelStatus {
+		if OpenChannels::get().contains(&id) {
+			ChannelStatus::Ready(100, 100)
+		} else {
+			ChannelStatus::Closed
+		}
+	}
+
+	fn get_channel_max(id: ParaId) -> Option<usize> {
+		OpenChannels::get().contains(&id).then_some(100)
+	}
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ControllerOrigin = EnsureRoot<u64>;
+	type XcmSender = TestSender;
+	type SelfParaId = SelfParaId;
+	type ChannelInfo = TestChannelInfo;
+	type RelayHrmpPalletIndex = RelayHrmpPalletIndex;
+	type RelayExecutionFee = RelayExecutionFee;
+	type RelayCallWeight = RelayCallWeight;
+	type RetryDelay = ConstU64<5>;
+	type MaxRetries = ConstU32<2>;
+	type MaxRetriesPerBlock = ConstU32<2>;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities =
+		frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
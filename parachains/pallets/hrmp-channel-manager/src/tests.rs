@@ -0,0 +1,181 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use frame_system::RawOrigin;
+use mock::*;
+use sp_runtime::DispatchError;
+
+const SIBLING: ParaId = ParaId::new(2000);
+
+fn sent_call() -> Vec<u8> {
+	let messages = SentMessages::get();
+	let (dest, message) = messages.last().expect("message has been sent");
+	assert_eq!(*dest, MultiLocation::parent());
+	assert!(matches!(message.0[0], WithdrawAsset(_)));
+	assert!(matches!(message.0[1], BuyExecution { .. }));
+	assert!(matches!(message.0[3], RefundSurplus));
+	assert!(matches!(
+		message.0[4],
+		DepositAsset {
+			beneficiary: MultiLocation { parents: 0, interior: X1(Parachain(1000)) },
+			..
+		}
+	));
+	match &message.0[2] {
+		Transact { origin_kind: OriginKind::Native, call, .. } => call.clone().into_encoded(),
+		instruction => panic!("unexpected instruction: {:?}", instruction),
+	}
+}
+
+#[test]
+fn open_channel_request_is_sent_to_relay_chain() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(HrmpChannelManager::request_open_channel(
+			RawOrigin::Root.into(),
+			SIBLING,
+			8,
+			1024
+		));
+
+		assert_eq!(sent_call(), (60u8, 0u8, SIBLING, 8u32, 1024u32).encode());
+		assert_eq!(
+			HrmpChannelManager::channel_state(SelfParaId::get(), SIBLING),
+			Some(ChannelState::OpenRequested)
+		);
+
+		// the relay chain has opened the channel
+		OpenChannels::set(vec![SIBLING]);
+		assert_eq!(
+			HrmpChannelManager::channel_state(SelfParaId::get(), SIBLING),
+			Some(ChannelState::Open)
+		);
+		assert_noop!(
+			HrmpChannelManager::request_open_channel(RawOrigin::Root.into(), SIBLING, 8, 1024),
+			Error::<Test>::ChannelAlreadyOpen
+		);
+	});
+}
+
+#[test]
+fn accept_and_close_requests_are_sent_to_relay_chain() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(HrmpChannelManager::accept_open_channel(RawOrigin::Root.into(), SIBLING));
+		assert_eq!(sent_call(), (60u8, 1u8, SIBLING).encode());
+		assert_eq!(
+			HrmpChannelManager::channel_state(SIBLING, SelfParaId::get()),
+			Some(ChannelState::Accepted)
+		);
+
+		assert_ok!(HrmpChannelManager::close_channel(
+			RawOrigin::Root.into(),
+			SIBLING,
+			SelfParaId::get()
+		));
+		assert_eq!(sent_call(), (60u8, 2u8, SIBLING, SelfParaId::get()).encode());
+		assert_eq!(
+			HrmpChannelManager::channel_state(SIBLING, SelfParaId::get()),
+			Some(ChannelState::CloseRequested)
+		);
+	});
+}
+
+#[test]
+fn invalid_requests_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let other = ParaId::new(3000);
+		assert_noop!(
+			HrmpChannelManager::request_open_channel(RawOrigin::None.into(), SIBLING, 8, 1024),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			HrmpChannelManager::request_open_channel(
+				RawOrigin::Root.into(),
+				SelfParaId::get(),
+				8,
+				1024
+			),
+			Error::<Test>::ChannelToSelf
+		);
+		assert_noop!(
+			HrmpChannelManager::close_channel(RawOrigin::Root.into(), SIBLING, other),
+			Error::<Test>::NotParticipant
+		);
+	});
+}
+
+#[test]
+fn failed_requests_are_retried() {
+	new_test_ext().execute_with(|| {
+		SendingFails::set(true);
+		assert_ok!(HrmpChannelManager::accept_open_channel(RawOrigin::Root.into(), SIBLING));
+		let request = HrmpRequest::AcceptOpenChannel { sender: SIBLING };
+		System::assert_last_event(
+			Event::RequestFailed {
+				request: request.clone(),
+				error: SendError::Transport("queue is full"),
+				retry_at: Some(6),
+			}
+			.into(),
+		);
+		assert_eq!(
+			HrmpChannelManager::channel_state(SIBLING, SelfParaId::get()),
+			Some(ChannelState::Pending)
+		);
+
+		// the first retry fails as well
+		System::set_block_number(6);
+		HrmpChannelManager::on_initialize(6);
+		assert_eq!(Retries::<Test>::get(11).into_inner(), vec![(request.clone(), 2)]);
+
+		// the second retry succeeds
+		SendingFails::set(false);
+		System::set_block_number(11);
+		HrmpChannelManager::on_initialize(11);
+		assert_eq!(sent_call(), (60u8, 1u8, SIBLING).encode());
+		assert_eq!(
+			HrmpChannelManager::channel_state(SIBLING, SelfParaId::get()),
+			Some(ChannelState::Accepted)
+		);
+	});
+}
+
+#[test]
+fn requests_are_not_retried_above_limit() {
+	new_test_ext().execute_with(|| {
+		SendingFails::set(true);
+		assert_ok!(HrmpChannelManager::accept_open_channel(RawOrigin::Root.into(), SIBLING));
+		for block in [6, 11] {
+			System::set_block_number(block);
+			HrmpChannelManager::on_initialize(block);
+		}
+
+		System::assert_last_event(
+			Event::RequestFailed {
+				request: HrmpRequest::AcceptOpenChannel { sender: SIBLING },
+				error: SendError::Transport("queue is full"),
+				retry_at: None,
+			}
+			.into(),
+		);
+		assert_eq!(
+			HrmpChannelManager::channel_state(SIBLING, SelfParaId::get()),
+			Some(ChannelState::Failed)
+		);
+		assert!(Retries::<Test>::iter().next().is_none());
+	});
+}
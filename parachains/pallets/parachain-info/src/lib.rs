@@ -15,16 +15,69 @@
 // along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
 
 //! Minimal Pallet that injects a ParachainId into Runtime storage from
+//!
+//! It also stores the genesis hash and the network of the relay chain, so that the XCM
+//! configuration can derive the network of the relay chain (see [`RelayNetworkOf`] and
+//! [`UniversalLocationOf`]) instead of hardcoding it. The genesis hash is either set in the chain
+//! spec or stored by the `parachain-system` pallet from the relay chain state proof.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use cumulus_primitives_core::{relay_chain, RelayChainGenesisHashStore};
+use frame_support::traits::Get;
+use sp_std::marker::PhantomData;
+use xcm::latest::prelude::*;
+
+pub mod migration;
+#[cfg(test)]
+mod tests;
+
 pub use pallet::*;
 
+/// The genesis hash of Polkadot.
+pub const POLKADOT_GENESIS_HASH: [u8; 32] =
+	hex_literal::hex!("91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3");
+/// The genesis hash of Kusama.
+pub const KUSAMA_GENESIS_HASH: [u8; 32] =
+	hex_literal::hex!("b0a8d493285c2df73290dfb7e61f870f17b41801197a149ca93654499ea3dafe");
+/// The genesis hash of Westend.
+pub const WESTEND_GENESIS_HASH: [u8; 32] =
+	hex_literal::hex!("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e");
+/// The genesis hash of Rococo.
+pub const ROCOCO_GENESIS_HASH: [u8; 32] =
+	hex_literal::hex!("6408de7737c59c238890533af25896a2c20608d8b380bb01029acb392781063e");
+
+/// Returns the network of the relay chain with the given `genesis_hash`.
+///
+/// The well-known relay chains are mapped to their named networks, all others are identified by
+/// their genesis hash.
+pub fn relay_network_of(genesis_hash: relay_chain::Hash) -> NetworkId {
+	match genesis_hash.0 {
+		POLKADOT_GENESIS_HASH => NetworkId::Polkadot,
+		KUSAMA_GENESIS_HASH => NetworkId::Kusama,
+		WESTEND_GENESIS_HASH => NetworkId::Westend,
+		ROCOCO_GENESIS_HASH => NetworkId::Rococo,
+		genesis_hash => NetworkId::ByGenesis(genesis_hash),
+	}
+}
+
+/// Something that knows the relay chain, that the parachain is connected to.
+pub trait RelayNetworkInfo {
+	/// Returns the genesis hash of the relay chain, if it is known.
+	fn relay_chain_genesis_hash() -> Option<relay_chain::Hash>;
+
+	/// Returns the network of the relay chain, if it is known.
+	fn relay_network() -> Option<NetworkId> {
+		Self::relay_chain_genesis_hash().map(relay_network_of)
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
-	use cumulus_primitives_core::ParaId;
+	use cumulus_primitives_core::{relay_chain, ParaId};
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
+	use xcm::latest::NetworkId;
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
@@ -43,11 +96,18 @@ pub mod pallet {
 		#[serde(skip)]
 		pub _config: sp_std::marker::PhantomData<T>,
 		pub parachain_id: ParaId,
+		/// The genesis hash of the relay chain, if it is known.
+		#[serde(default)]
+		pub relay_chain_genesis_hash: Option<relay_chain::Hash>,
 	}
 
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			Self { parachain_id: 100.into(), _config: Default::default() }
+			Self {
+				parachain_id: 100.into(),
+				relay_chain_genesis_hash: None,
+				_config: Default::default(),
+			}
 		}
 	}
 
@@ -55,6 +115,9 @@ pub mod pallet {
 	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
 		fn build(&self) {
 			<ParachainId<T>>::put(self.parachain_id);
+			if let Some(genesis_hash) = self.relay_chain_genesis_hash {
+				Pallet::<T>::set_relay_chain_genesis_hash(genesis_hash);
+			}
 		}
 	}
 
@@ -68,9 +131,81 @@ pub mod pallet {
 	pub(super) type ParachainId<T: Config> =
 		StorageValue<_, ParaId, ValueQuery, DefaultForParachainId>;
 
+	/// The genesis hash of the relay chain, that the parachain is connected to.
+	#[pallet::storage]
+	#[pallet::getter(fn relay_chain_genesis_hash)]
+	pub(super) type RelayChainGenesisHash<T: Config> =
+		StorageValue<_, relay_chain::Hash, OptionQuery>;
+
+	/// The network of the relay chain, that is derived from the [`RelayChainGenesisHash`].
+	#[pallet::storage]
+	#[pallet::getter(fn relay_network)]
+	pub(super) type RelayNetwork<T: Config> = StorageValue<_, NetworkId, OptionQuery>;
+
 	impl<T: Config> Get<ParaId> for Pallet<T> {
 		fn get() -> ParaId {
 			Self::parachain_id()
 		}
 	}
+
+	impl<T: Config> Pallet<T> {
+		/// Set the genesis hash of the relay chain and the network, that is derived from it.
+		pub fn set_relay_chain_genesis_hash(genesis_hash: relay_chain::Hash) {
+			<RelayChainGenesisHash<T>>::put(genesis_hash);
+			<RelayNetwork<T>>::put(crate::relay_network_of(genesis_hash));
+		}
+	}
+}
+
+impl<T: Config> RelayNetworkInfo for Pallet<T> {
+	fn relay_chain_genesis_hash() -> Option<relay_chain::Hash> {
+		Pallet::<T>::relay_chain_genesis_hash()
+	}
+
+	fn relay_network() -> Option<NetworkId> {
+		Pallet::<T>::relay_network()
+	}
+}
+
+impl<T: Config> RelayChainGenesisHashStore for Pallet<T> {
+	fn relay_chain_genesis_hash() -> Option<relay_chain::Hash> {
+		Pallet::<T>::relay_chain_genesis_hash()
+	}
+
+	fn set_relay_chain_genesis_hash(genesis_hash: relay_chain::Hash) {
+		Pallet::<T>::set_relay_chain_genesis_hash(genesis_hash)
+	}
+}
+
+/// The network of the relay chain, that is known to the [`Pallet`], or `Fallback`, if the genesis
+/// hash of the relay chain is not stored.
+///
+/// Can be used as the `RelayNetwork` of the XCM configuration.
+pub struct RelayNetworkOf<T, Fallback>(PhantomData<(T, Fallback)>);
+
+impl<T: Config, Fallback: Get<Option<NetworkId>>> Get<Option<NetworkId>>
+	for RelayNetworkOf<T, Fallback>
+{
+	fn get() -> Option<NetworkId> {
+		<Pallet<T> as RelayNetworkInfo>::relay_network().or_else(Fallback::get)
+	}
+}
+
+/// The universal location of the parachain, derived from the network of the relay chain and the
+/// parachain id, that are known to the [`Pallet`].
+///
+/// The global consensus is omitted, if the network of the relay chain is not known and `Fallback`
+/// is `None`.
+pub struct UniversalLocationOf<T, Fallback>(PhantomData<(T, Fallback)>);
+
+impl<T: Config, Fallback: Get<Option<NetworkId>>> Get<InteriorMultiLocation>
+	for UniversalLocationOf<T, Fallback>
+{
+	fn get() -> InteriorMultiLocation {
+		let para_id = Parachain(Pallet::<T>::parachain_id().into());
+		match RelayNetworkOf::<T, Fallback>::get() {
+			Some(network) => X2(GlobalConsensus(network), para_id),
+			None => X1(para_id),
+		}
+	}
 }
@@ -0,0 +1,44 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Migrations of the parachain info pallet.
+
+use crate::{Config, Pallet};
+use cumulus_primitives_core::relay_chain;
+use frame_support::{
+	traits::{Get, OnRuntimeUpgrade},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+
+/// Stores the `GenesisHash` of the relay chain for chains, that have been started before the
+/// genesis hash was part of the genesis config.
+///
+/// The stored genesis hash is not overwritten.
+pub struct SetRelayChainGenesisHash<T, GenesisHash>(PhantomData<(T, GenesisHash)>);
+
+impl<T: Config, GenesisHash: Get<relay_chain::Hash>> OnRuntimeUpgrade
+	for SetRelayChainGenesisHash<T, GenesisHash>
+{
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::relay_chain_genesis_hash().is_some() {
+			return T::DbWeight::get().reads(1)
+		}
+
+		Pallet::<T>::set_relay_chain_genesis_hash(GenesisHash::get());
+		T::DbWeight::get().reads_writes(1, 2)
+	}
+}
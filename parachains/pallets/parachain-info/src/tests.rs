@@ -0,0 +1,148 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use crate as parachain_info;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, ConstU64, OnRuntimeUpgrade},
+};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		ParachainInfo: parachain_info::{Pallet, Storage, Config<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl Config for Test {}
+
+parameter_types! {
+	pub const FallbackNetwork: Option<NetworkId> = Some(NetworkId::Westend);
+	pub const NoFallbackNetwork: Option<NetworkId> = None;
+	pub const KusamaGenesisHash: relay_chain::Hash = relay_chain::Hash(KUSAMA_GENESIS_HASH);
+}
+
+fn new_test_ext(relay_chain_genesis_hash: Option<relay_chain::Hash>) -> sp_io::TestExternalities {
+	RuntimeGenesisConfig {
+		parachain_info: parachain_info::GenesisConfig {
+			parachain_id: 2000.into(),
+			relay_chain_genesis_hash,
+			..Default::default()
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap()
+	.into()
+}
+
+#[test]
+fn relay_network_is_derived_from_the_genesis_config() {
+	new_test_ext(Some(relay_chain::Hash(POLKADOT_GENESIS_HASH))).execute_with(|| {
+		assert_eq!(ParachainInfo::relay_network(), Some(NetworkId::Polkadot));
+		assert_eq!(RelayNetworkOf::<Test, FallbackNetwork>::get(), Some(NetworkId::Polkadot));
+		assert_eq!(
+			UniversalLocationOf::<Test, NoFallbackNetwork>::get(),
+			X2(GlobalConsensus(NetworkId::Polkadot), Parachain(2000)),
+		);
+	});
+}
+
+#[test]
+fn unknown_relay_chain_is_identified_by_its_genesis_hash() {
+	new_test_ext(None).execute_with(|| {
+		let genesis_hash = relay_chain::Hash::repeat_byte(1);
+		<ParachainInfo as RelayChainGenesisHashStore>::set_relay_chain_genesis_hash(genesis_hash);
+
+		assert_eq!(
+			<ParachainInfo as RelayChainGenesisHashStore>::relay_chain_genesis_hash(),
+			Some(genesis_hash),
+		);
+		assert_eq!(ParachainInfo::relay_network(), Some(NetworkId::ByGenesis([1; 32])));
+		assert_eq!(
+			RelayNetworkOf::<Test, FallbackNetwork>::get(),
+			Some(NetworkId::ByGenesis([1; 32])),
+		);
+	});
+}
+
+#[test]
+fn fallback_is_used_until_the_relay_network_is_known() {
+	new_test_ext(None).execute_with(|| {
+		assert_eq!(ParachainInfo::relay_network(), None);
+		assert_eq!(RelayNetworkOf::<Test, FallbackNetwork>::get(), Some(NetworkId::Westend));
+		assert_eq!(RelayNetworkOf::<Test, NoFallbackNetwork>::get(), None);
+		assert_eq!(
+			UniversalLocationOf::<Test, FallbackNetwork>::get(),
+			X2(GlobalConsensus(NetworkId::Westend), Parachain(2000)),
+		);
+		assert_eq!(UniversalLocationOf::<Test, NoFallbackNetwork>::get(), X1(Parachain(2000)));
+	});
+}
+
+#[test]
+fn migration_does_not_overwrite_the_genesis_hash() {
+	type Migration = migration::SetRelayChainGenesisHash<Test, KusamaGenesisHash>;
+
+	new_test_ext(None).execute_with(|| {
+		Migration::on_runtime_upgrade();
+		assert_eq!(ParachainInfo::relay_network(), Some(NetworkId::Kusama));
+	});
+
+	new_test_ext(Some(relay_chain::Hash(ROCOCO_GENESIS_HASH))).execute_with(|| {
+		Migration::on_runtime_upgrade();
+		assert_eq!(ParachainInfo::relay_network(), Some(NetworkId::Rococo));
+		assert_eq!(
+			ParachainInfo::relay_chain_genesis_hash(),
+			Some(relay_chain::Hash(ROCOCO_GENESIS_HASH)),
+		);
+	});
+}
@@ -41,7 +41,7 @@ use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, Verify},
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, Permill,
+	ApplyExtrinsicResult, FixedU128, Perbill, Permill,
 };
 
 use sp_std::prelude::*;
@@ -57,7 +57,7 @@ use frame_support::{
 	ord_parameter_types, parameter_types,
 	traits::{
 		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, EitherOfDiverse,
-		InstanceFilter,
+		Equals, InstanceFilter,
 	},
 	weights::{ConstantMultiplier, Weight},
 	BoundedVec, PalletId,
@@ -102,6 +102,38 @@ impl_opaque_keys! {
 	}
 }
 
+impl parachains_common::session_keys::ProveOwnership<AccountId> for SessionKeys {
+	fn verify_ownership(&self, owner: &AccountId, proof: &[u8]) -> bool {
+		parachains_common::session_keys::verify_key(&self.aura, owner, &mut &proof[..])
+	}
+}
+
+impl parachains_common::session_keys::NestedCalls for RuntimeCall {
+	fn nested_calls(&self) -> Vec<&Self> {
+		match self {
+			RuntimeCall::Utility(
+				pallet_utility::Call::batch { calls } |
+				pallet_utility::Call::batch_all { calls } |
+				pallet_utility::Call::force_batch { calls },
+			) => calls.iter().collect(),
+			RuntimeCall::Utility(
+				pallet_utility::Call::as_derivative { call, .. } |
+				pallet_utility::Call::dispatch_as { call, .. } |
+				pallet_utility::Call::with_weight { call, .. },
+			) => sp_std::vec![&**call],
+			RuntimeCall::Multisig(
+				pallet_multisig::Call::as_multi_threshold_1 { call, .. } |
+				pallet_multisig::Call::as_multi { call, .. },
+			) => sp_std::vec![&**call],
+			RuntimeCall::Proxy(
+				pallet_proxy::Call::proxy { call, .. } |
+				pallet_proxy::Call::proxy_announced { call, .. },
+			) => sp_std::vec![&**call],
+			_ => Vec::new(),
+		}
+	}
+}
+
 #[cfg(feature = "state-trie-version-1")]
 #[sp_version::runtime_version]
 pub const VERSION: RuntimeVersion = RuntimeVersion {
@@ -114,7 +146,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_version: 10000,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 13,
+	transaction_version: 14,
 	state_version: 1,
 };
 
@@ -130,7 +162,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_version: 10000,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 13,
+	transaction_version: 14,
 	state_version: 0,
 };
 
@@ -167,7 +199,7 @@ parameter_types! {
 
 // Configure FRAME pallets to include in runtime.
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = parachains_common::session_keys::NoNestedSetKeys<Runtime>;
 	type BlockWeights = RuntimeBlockWeights;
 	type BlockLength = RuntimeBlockLength;
 	type AccountId = AccountId;
@@ -496,7 +528,8 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 						RuntimeCall::Utility { .. } |
 						RuntimeCall::Multisig { .. } |
 						RuntimeCall::NftFractionalization { .. } |
-						RuntimeCall::Nfts { .. } | RuntimeCall::Uniques { .. }
+						RuntimeCall::Nfts { .. } |
+						RuntimeCall::Uniques { .. }
 				)
 			},
 			ProxyType::AssetOwner => matches!(
@@ -603,18 +636,24 @@ impl pallet_proxy::Config for Runtime {
 parameter_types! {
 	pub const ReservedXcmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
 	pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
+	pub const PovSizeWatermark: u64 = MAXIMUM_BLOCK_WEIGHT.proof_size() / 2;
 }
 
 impl cumulus_pallet_parachain_system::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type OnSystemEvent = ();
+	type RelayChainGenesisHashStore = ParachainInfo;
 	type SelfParaId = parachain_info::Pallet<Runtime>;
-	type DmpMessageHandler = DmpQueue;
+	type DmpMessageHandler = cumulus_pallet_dmp_queue::EnqueueDmpMessages<Runtime, MessageQueue>;
 	type ReservedDmpWeight = ReservedDmpWeight;
 	type OutboundXcmpMessageSource = XcmpQueue;
-	type XcmpMessageHandler = XcmpQueue;
+	type PriorityUpwardMessageSource = ();
+	type XcmpMessageHandler = cumulus_pallet_xcmp_queue::EnqueueXcmpMessages<Runtime, MessageQueue>;
 	type ReservedXcmpWeight = ReservedXcmpWeight;
+	type PovSizeWatermark = PovSizeWatermark;
 	type CheckAssociatedRelayNumber = RelayNumberStrictlyIncreases;
+	type CheckRelayTimestamp = ();
+	type AuthorshipProofVerifier = ();
 	type ConsensusHook = cumulus_pallet_aura_ext::FixedVelocityConsensusHook<
 		Runtime,
 		RELAY_CHAIN_SLOT_DURATION_MILLIS,
@@ -623,6 +662,30 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
 	>;
 }
 
+parameter_types! {
+	pub MessageQueueServiceWeight: Weight =
+		Perbill::from_percent(35) * RuntimeBlockWeights::get().max_block;
+}
+
+impl pallet_message_queue::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_message_queue::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type MessageProcessor = pallet_message_queue::mock_helpers::NoopMessageProcessor<
+		parachains_common::message_queue::AggregateMessageOrigin,
+	>;
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	type MessageProcessor =
+		parachains_common::message_queue::XcmMessageProcessor<Runtime, xcm_config::XcmConfig>;
+	type Size = u32;
+	// Resumes the XCMP channels, that have been suspended because of the queue size.
+	type QueueChangeHandler = XcmpQueue;
+	type QueuePausedQuery = ();
+	type HeapSize = sp_core::ConstU32<{ 64 * 1024 }>;
+	type MaxStale = sp_core::ConstU32<8>;
+	type ServiceWeight = MessageQueueServiceWeight;
+}
+
 impl parachain_info::Config for Runtime {}
 
 impl cumulus_pallet_aura_ext::Config for Runtime {}
@@ -645,6 +708,12 @@ impl cumulus_pallet_xcmp_queue::Config for Runtime {
 	type ControllerOriginConverter = xcm_config::XcmOriginToTransactDispatchOrigin;
 	type WeightInfo = weights::cumulus_pallet_xcmp_queue::WeightInfo<Runtime>;
 	type PriceForSiblingDelivery = ();
+	type XcmDeferFilter = ();
+	type OutboundChannelPriority = ();
+	type XcmRateLimiter = ();
+	type RelayChainBlockNumberProvider =
+		cumulus_pallet_parachain_system::RelaychainDataProvider<Runtime>;
+	type MaxInboundPageSize = ConstU32<{ 64 * 1024 }>;
 }
 
 impl cumulus_pallet_dmp_queue::Config for Runtime {
@@ -653,6 +722,28 @@ impl cumulus_pallet_dmp_queue::Config for Runtime {
 	type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
 }
 
+parameter_types! {
+	/// The index of the `Hrmp` pallet in the Kusama runtime.
+	pub const RelayHrmpPalletIndex: u8 = 60;
+	/// One unit of the relay chain native token pays for the execution of HRMP requests.
+	pub const RelayExecutionFee: u128 = UNITS;
+	pub const RelayCallWeight: Weight = Weight::from_parts(1_000_000_000, 200_000);
+}
+
+impl pallet_hrmp_channel_manager::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ControllerOrigin = EnsureRoot<AccountId>;
+	type XcmSender = xcm_config::XcmRouter;
+	type SelfParaId = ParachainInfo;
+	type ChannelInfo = ParachainSystem;
+	type RelayHrmpPalletIndex = RelayHrmpPalletIndex;
+	type RelayExecutionFee = RelayExecutionFee;
+	type RelayCallWeight = RelayCallWeight;
+	type RetryDelay = ConstU32<10>;
+	type MaxRetries = ConstU32<3>;
+	type MaxRetriesPerBlock = ConstU32<10>;
+}
+
 parameter_types! {
 	pub const Period: u32 = 6 * HOURS;
 	pub const Offset: u32 = 0;
@@ -683,6 +774,7 @@ impl pallet_aura::Config for Runtime {
 
 parameter_types! {
 	pub const PotId: PalletId = PalletId(*b"PotStake");
+	pub const CollatorOfflineSlash: Perbill = Perbill::from_percent(10);
 	pub const SessionLength: BlockNumber = 6 * HOURS;
 	// StakingAdmin pluralistic body.
 	pub const StakingAdminBodyId: BodyId = BodyId::Defense;
@@ -704,6 +796,14 @@ impl pallet_collator_selection::Config for Runtime {
 	type MaxInvulnerables = ConstU32<20>;
 	// should be a multiple of session or things will get inconsistent
 	type KickThreshold = Period;
+	// liveness tracking is disabled until `remove_offline_candidate` is benchmarked
+	type MaxOfflineSessions = ConstU32<0>;
+	type OfflineSlash = CollatorOfflineSlash;
+	// delegations are disabled until they are benchmarked
+	type MaxDelegatorsPerCandidate = ConstU32<0>;
+	type MinDelegation = ();
+	type DelegationUnbondingPeriod = ();
+	type MaxUnbondingChunks = ();
 	type ValidatorId = <Self as frame_system::Config>::AccountId;
 	type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
 	type ValidatorRegistration = Session;
@@ -815,6 +915,34 @@ impl pallet_nfts::Config for Runtime {
 	type Helper = ();
 }
 
+/// XCM router instance to BridgeHub with bridging capabilities for `Polkadot` global
+/// consensus with dynamic fees and back-pressure.
+pub type ToPolkadotXcmRouterInstance = pallet_xcm_bridge_hub_router::Instance1;
+impl pallet_xcm_bridge_hub_router::Config<ToPolkadotXcmRouterInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_xcm_bridge_hub_router::weights::BridgeWeight<Runtime>;
+
+	type UniversalLocation = xcm_config::UniversalLocation;
+	type BridgedNetworkId = xcm_config::bridging::BridgedNetworkId;
+	type Bridges = xcm_builder::NetworkExportTable<xcm_config::bridging::BridgeTable>;
+
+	type BridgeHubOrigin = EnsureXcm<Equals<xcm_config::bridging::SiblingBridgeHub>>;
+	type ToBridgeHubSender = XcmpQueue;
+	type WithBridgeHubChannel =
+		cumulus_pallet_xcmp_queue::bridging::InAndOutXcmpChannelStatusProvider<
+			xcm_config::bridging::SiblingBridgeHubParaId,
+			Runtime,
+		>;
+	type BridgeLane = xcm_config::bridging::XcmBridgeLane;
+
+	type ByteFee = xcm_config::bridging::XcmBridgeHubRouterByteFee;
+	type FeeAsset = xcm_config::bridging::XcmBridgeHubRouterFeeAssetId;
+
+	type FeeFactorIncreasePerBlock = xcm_config::bridging::FeeFactorIncreasePerBlock;
+	type FeeFactorDecreasePerBlock = xcm_config::bridging::FeeFactorDecreasePerBlock;
+	type MaxDeliveryFeeFactor = xcm_config::bridging::MaxDeliveryFeeFactor;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime
@@ -845,12 +973,17 @@ construct_runtime!(
 		PolkadotXcm: pallet_xcm::{Pallet, Call, Storage, Event<T>, Origin, Config<T>} = 31,
 		CumulusXcm: cumulus_pallet_xcm::{Pallet, Event<T>, Origin} = 32,
 		DmpQueue: cumulus_pallet_dmp_queue::{Pallet, Call, Storage, Event<T>} = 33,
+		MessageQueue: pallet_message_queue::{Pallet, Call, Storage, Event<T>} = 34,
+		HrmpChannelManager: pallet_hrmp_channel_manager::{Pallet, Call, Storage, Event<T>} = 35,
 
 		// Handy utilities.
 		Utility: pallet_utility::{Pallet, Call, Event} = 40,
 		Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>} = 41,
 		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>} = 42,
 
+		// Bridge utilities.
+		ToPolkadotXcmRouter: pallet_xcm_bridge_hub_router::<Instance1>::{Pallet, Storage, Call, Event<T>} = 43,
+
 		// The main stage.
 		Assets: pallet_assets::<Instance1>::{Pallet, Call, Storage, Event<T>} = 50,
 		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>} = 51,
@@ -884,12 +1017,18 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_asset_conversion_tx_payment::ChargeAssetTxPayment<Runtime>,
+	parachains_common::session_keys::CheckSessionKeysProof<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// Migrations to apply on runtime upgrade.
-pub type Migrations = (pallet_collator_selection::migration::v1::MigrateToV1<Runtime>,);
+pub type Migrations = (
+	pallet_collator_selection::migration::v1::MigrateToV1<Runtime>,
+	pallet_collator_selection::migration::v2::MigrateToV2<Runtime>,
+	cumulus_pallet_dmp_queue::migration::MigrateToMessageQueue<Runtime, MessageQueue>,
+	cumulus_pallet_xcmp_queue::migration::MigrateInboundToMessageQueue<Runtime, MessageQueue>,
+);
 
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
@@ -914,6 +1053,7 @@ mod benches {
 		[pallet_assets, Pool]
 		[pallet_asset_conversion, AssetConversion]
 		[pallet_balances, Balances]
+		[pallet_message_queue, MessageQueue]
 		[pallet_multisig, Multisig]
 		[pallet_nft_fractionalization, NftFractionalization]
 		[pallet_nfts, Nfts]
@@ -1128,6 +1268,80 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl bp_xcm_bridge_hub_router::XcmBridgeHubRouterApi<Block> for Runtime {
+		fn delivery_fee_factor() -> FixedU128 {
+			ToPolkadotXcmRouter::delivery_fee_factor()
+		}
+	}
+
+	impl parachains_common::xcm_payment_api::XcmPaymentApi<Block> for Runtime {
+		fn query_acceptable_payment_assets(
+			xcm_version: u32,
+		) -> Result<Vec<xcm::VersionedMultiLocation>, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			use frame_support::traits::fungibles::InspectEnumerable;
+			use sp_runtime::traits::MaybeEquivalence;
+
+			parachains_common::xcm_payment_api::acceptable_payment_assets::<xcm_config::XcmConfig>(
+				// The native asset, the trust backed and the foreign assets.
+				[xcm_config::KsmLocation::get()]
+					.into_iter()
+					.chain(
+						<Assets as InspectEnumerable<AccountId>>::asset_ids().filter_map(|id| {
+							assets_common::AssetIdForTrustBackedAssetsConvert::<
+								xcm_config::TrustBackedAssetsPalletLocation,
+							>::convert_back(&id)
+						}),
+					)
+					.chain(<ForeignAssets as InspectEnumerable<AccountId>>::asset_ids()),
+				xcm_version,
+			)
+		}
+
+		fn query_xcm_weight(
+			message: xcm::VersionedXcm<()>,
+		) -> Result<Weight, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			parachains_common::xcm_payment_api::query_xcm_weight::<xcm_config::XcmConfig>(message)
+		}
+
+		fn query_weight_to_asset_fee(
+			weight: Weight,
+			asset: xcm::VersionedMultiLocation,
+		) -> Result<u128, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			parachains_common::xcm_payment_api::query_weight_to_asset_fee::<xcm_config::XcmConfig>(weight, asset)
+		}
+
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			message: xcm::VersionedXcm<()>,
+		) -> Result<xcm::latest::Outcome, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			parachains_common::xcm_payment_api::dry_run_xcm::<xcm_config::XcmConfig>(origin, message)
+		}
+	}
+
+	impl parachains_common::dry_run_api::DryRunApi<Block, RuntimeCall, RuntimeEvent, OriginCaller> for Runtime {
+		fn dry_run_call(
+			origin: OriginCaller,
+			call: RuntimeCall,
+		) -> Result<parachains_common::dry_run_api::CallDryRunEffects<RuntimeEvent>, parachains_common::dry_run_api::DryRunError> {
+			parachains_common::dry_run_api::dry_run_call::<Runtime, _, _>(origin, call)
+		}
+
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			xcm: xcm::VersionedXcm<RuntimeCall>,
+		) -> Result<parachains_common::dry_run_api::XcmDryRunEffects<RuntimeEvent>, parachains_common::dry_run_api::DryRunError> {
+			parachains_common::dry_run_api::dry_run_xcm::<Runtime, xcm_config::XcmConfig>(origin, xcm)
+		}
+	}
+
+	impl cumulus_pallet_dmp_queue::DmpQueueApi<Block> for Runtime {
+		fn overweight_message_summary(
+			index: cumulus_pallet_dmp_queue::OverweightIndex,
+		) -> Option<cumulus_pallet_dmp_queue::OverweightSummary> {
+			DmpQueue::overweight_message_summary(index)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
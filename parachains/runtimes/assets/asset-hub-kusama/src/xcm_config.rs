@@ -27,23 +27,23 @@ use assets_common::{
 };
 use frame_support::{
 	match_types, parameter_types,
-	traits::{ConstU32, Contains, Everything, Nothing, PalletInfoAccess},
+	traits::{ConstU32, Contains, Equals, Everything, Nothing, PalletInfoAccess},
 };
 use frame_system::EnsureRoot;
 use pallet_xcm::XcmPassthrough;
-use parachains_common::{impls::ToStakingPot, xcm_config::AssetFeeAsExistentialDepositMultiplier};
+use parachains_common::{
+	impls::ToStakingPot,
+	xcm_config::{AssetFeeAsExistentialDepositMultiplier, SystemParachainBarrier},
+};
 use polkadot_parachain::primitives::Sibling;
 use sp_runtime::traits::ConvertInto;
 use xcm::latest::prelude::*;
 use xcm_builder::{
-	AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses,
-	AllowSubscriptionsFrom, AllowTopLevelPaidExecutionFrom, CurrencyAdapter,
-	DenyReserveTransferToRelayChain, DenyThenTry, DescribeAllTerminal, DescribeFamily,
-	EnsureXcmOrigin, FungiblesAdapter, HashedDescription, IsConcrete, LocalMint, NativeAsset,
-	NoChecking, ParentAsSuperuser, ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative,
+	AccountId32Aliases, CurrencyAdapter, DescribeAllTerminal, DescribeFamily, EnsureXcmOrigin,
+	FungiblesAdapter, HashedDescription, IsConcrete, LocalMint, NativeAsset, NoChecking,
+	ParentAsSuperuser, ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative,
 	SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
-	SovereignSignedViaLocation, TakeWeightCredit, TrailingSetTopicAsId, UsingComponents,
-	WeightInfoBounds, WithComputedOrigin, WithUniqueTopic,
+	SovereignSignedViaLocation, UsingComponents, WeightInfoBounds, WithUniqueTopic,
 };
 use xcm_executor::{traits::WithOriginFilter, XcmExecutor};
 
@@ -271,6 +271,7 @@ impl Contains<RuntimeCall> for SafeCallFilter {
 				) | RuntimeCall::Session(pallet_session::Call::purge_keys { .. }) |
 				RuntimeCall::XcmpQueue(..) |
 				RuntimeCall::DmpQueue(..) |
+				RuntimeCall::HrmpChannelManager(..) |
 				RuntimeCall::Assets(
 					pallet_assets::Call::create { .. } |
 						pallet_assets::Call::force_create { .. } |
@@ -433,34 +434,21 @@ impl Contains<RuntimeCall> for SafeCallFilter {
 					pallet_uniques::Call::set_collection_max_supply { .. } |
 					pallet_uniques::Call::set_price { .. } |
 					pallet_uniques::Call::buy_item { .. }
+			) | RuntimeCall::ToPolkadotXcmRouter(
+				pallet_xcm_bridge_hub_router::Call::report_bridge_status { .. }
 			)
 		)
 	}
 }
 
-pub type Barrier = TrailingSetTopicAsId<
-	DenyThenTry<
-		DenyReserveTransferToRelayChain,
-		(
-			TakeWeightCredit,
-			// Expected responses are OK.
-			AllowKnownQueryResponses<PolkadotXcm>,
-			// Allow XCMs with some computed origins to pass through.
-			WithComputedOrigin<
-				(
-					// If the message is one that immediately attemps to pay for execution, then
-					// allow it.
-					AllowTopLevelPaidExecutionFrom<Everything>,
-					// Parent and its pluralities (i.e. governance bodies) get free execution.
-					AllowExplicitUnpaidExecutionFrom<ParentOrParentsPlurality>,
-					// Subscriptions for version tracking are OK.
-					AllowSubscriptionsFrom<ParentOrSiblings>,
-				),
-				UniversalLocation,
-				ConstU32<8>,
-			>,
-		),
-	>,
+pub type Barrier = SystemParachainBarrier<
+	PolkadotXcm,
+	UniversalLocation,
+	// Parent and its pluralities (i.e. governance bodies) and the sibling bridge hub (to report
+	// the bridge status) get free execution.
+	(ParentOrParentsPlurality, Equals<bridging::SiblingBridgeHub>),
+	// Subscriptions for version tracking are OK.
+	ParentOrSiblings,
 >;
 
 pub type AssetFeeAsExistentialDepositMultiplierFeeCharger = AssetFeeAsExistentialDepositMultiplier<
@@ -470,6 +458,21 @@ pub type AssetFeeAsExistentialDepositMultiplierFeeCharger = AssetFeeAsExistentia
 	TrustBackedAssetsInstance,
 >;
 
+/// The fee charger of the sufficient `ForeignAssets`, see
+/// [`AssetFeeAsExistentialDepositMultiplierFeeCharger`].
+pub type ForeignAssetFeeAsExistentialDepositMultiplierFeeCharger =
+	AssetFeeAsExistentialDepositMultiplier<
+		Runtime,
+		WeightToFee,
+		pallet_assets::BalanceToAssetBalance<
+			Balances,
+			Runtime,
+			ConvertInto,
+			crate::ForeignAssetsInstance,
+		>,
+		crate::ForeignAssetsInstance,
+	>;
+
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
 	type RuntimeCall = RuntimeCall;
@@ -507,6 +510,24 @@ impl xcm_executor::Config for XcmConfig {
 				XcmAssetFeesReceiver,
 			>,
 		>,
+		// The sufficient foreign assets.
+		cumulus_primitives_utility::TakeFirstAssetTrader<
+			AccountId,
+			ForeignAssetFeeAsExistentialDepositMultiplierFeeCharger,
+			ForeignAssetsConvertedConcreteId,
+			ForeignAssets,
+			cumulus_primitives_utility::XcmFeesTo32ByteAccount<
+				ForeignFungiblesTransactor,
+				AccountId,
+				XcmAssetFeesReceiver,
+			>,
+		>,
+		// Any other asset with a pool against the native asset.
+		assets_common::swap_trader::SwapFirstAssetTrader<
+			Runtime,
+			WeightToFee,
+			XcmAssetFeesReceiver,
+		>,
 	);
 	type ResponseHandler = PolkadotXcm;
 	type AssetTrap = PolkadotXcm;
@@ -520,7 +541,7 @@ impl xcm_executor::Config for XcmConfig {
 	type MessageExporter = ();
 	type UniversalAliases = Nothing;
 	type CallDispatcher = WithOriginFilter<SafeCallFilter>;
-	type SafeCallFilter = SafeCallFilter;
+	type SafeCallFilter = parachains_common::session_keys::NoSetKeys<Runtime, SafeCallFilter>;
 	type Aliasers = Nothing;
 }
 
@@ -531,10 +552,12 @@ pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, R
 /// The means for routing XCM messages which are not for local execution into the right message
 /// queues.
 pub type XcmRouter = WithUniqueTopic<(
-	// Two routers - use UMP to communicate with the relay chain:
+	// Three routers - use UMP to communicate with the relay chain:
 	cumulus_primitives_utility::ParentAsUmp<ParachainSystem, PolkadotXcm, ()>,
 	// ..and XCMP to communicate with the sibling chains.
 	XcmpQueue,
+	// ..and the bridge hub router to send messages to the Polkadot network.
+	crate::ToPolkadotXcmRouter,
 )>;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -624,3 +647,45 @@ where
 		sp_std::boxed::Box::new(Self::asset_id(asset_id))
 	}
 }
+
+/// All configuration related to bridging.
+pub mod bridging {
+	use super::*;
+	use bp_messages::LaneId;
+	use cumulus_primitives_core::ParaId;
+	use sp_runtime::FixedU128;
+	use sp_std::vec::Vec;
+
+	parameter_types! {
+		pub SiblingBridgeHubParaId: ParaId = ParaId::from(1002);
+		pub SiblingBridgeHub: MultiLocation =
+			MultiLocation::new(1, X1(Parachain(SiblingBridgeHubParaId::get().into())));
+		pub const PolkadotNetwork: NetworkId = NetworkId::Polkadot;
+		pub BridgedNetworkId: Option<NetworkId> = Some(PolkadotNetwork::get());
+		/// Router expects payment with this `AssetId`.
+		pub XcmBridgeHubRouterFeeAssetId: AssetId = KsmLocation::get().into();
+		/// Price of every byte of the outbound message.
+		pub XcmBridgeHubRouterByteFee: u128 = crate::TransactionByteFee::get();
+		/// Lane that is used by the sibling bridge hub to deliver our messages.
+		pub const XcmBridgeLane: LaneId = LaneId([0, 0, 0, 1]);
+
+		/// All messages to the Polkadot network are exported by the sibling bridge hub.
+		pub BridgeTable: Vec<(NetworkId, MultiLocation, Option<MultiAsset>)> = sp_std::vec![
+			(
+				PolkadotNetwork::get(),
+				SiblingBridgeHub::get(),
+				Some((
+					XcmBridgeHubRouterFeeAssetId::get(),
+					bp_asset_hub_kusama::BridgeHubKusamaBaseFeeInDots::get(),
+				).into()),
+			),
+		];
+
+		/// Delivery fee factor is increased by 5% in every block, while the bridge is congested.
+		pub const FeeFactorIncreasePerBlock: FixedU128 = FixedU128::from_rational(105, 100);
+		/// Delivery fee factor is decreased by 5% in every block, while the bridge is not congested.
+		pub const FeeFactorDecreasePerBlock: FixedU128 = FixedU128::from_rational(105, 100);
+		/// Delivery fee never grows above the 100x base fee.
+		pub const MaxDeliveryFeeFactor: FixedU128 = FixedU128::from_u32(100);
+	}
+}
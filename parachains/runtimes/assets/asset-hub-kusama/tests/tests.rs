@@ -18,16 +18,18 @@
 //! Tests for the Statemine (Kusama Assets Hub) chain.
 
 use asset_hub_kusama_runtime::xcm_config::{
-	AssetFeeAsExistentialDepositMultiplierFeeCharger, KsmLocation, TrustBackedAssetsPalletLocation,
+	bridging::SiblingBridgeHub, AssetFeeAsExistentialDepositMultiplierFeeCharger, KsmLocation,
+	TrustBackedAssetsPalletLocation,
 };
 pub use asset_hub_kusama_runtime::{
 	constants::fee::WeightToFee,
 	xcm_config::{CheckingAccount, ForeignCreatorsSovereignAccountOf, XcmConfig},
 	AllPalletsWithoutSystem, AssetDeposit, Assets, Balances, ExistentialDeposit, ForeignAssets,
-	ForeignAssetsInstance, MetadataDepositBase, MetadataDepositPerByte, ParachainSystem, Runtime,
-	RuntimeCall, RuntimeEvent, SessionKeys, System, TrustBackedAssetsInstance,
+	ForeignAssetsInstance, HrmpChannelManager, MetadataDepositBase, MetadataDepositPerByte,
+	ParachainSystem, Runtime, RuntimeCall, RuntimeEvent, SessionKeys, System, ToPolkadotXcmRouter,
+	TrustBackedAssetsInstance,
 };
-use asset_test_utils::{CollatorSessionKeys, ExtBuilder};
+use asset_test_utils::{CollatorSessionKeys, ExtBuilder, XcmReceivedFrom};
 use codec::{Decode, Encode};
 use cumulus_primitives_utility::ChargeWeightInFungibles;
 use frame_support::{
@@ -38,7 +40,10 @@ use frame_support::{
 use parachains_common::{AccountId, AssetIdForTrustBackedAssets, AuraId, Balance};
 use sp_runtime::traits::MaybeEquivalence;
 use xcm::latest::prelude::*;
-use xcm_executor::traits::{Identity, JustTry, WeightTrader};
+use xcm_executor::{
+	traits::{Identity, JustTry, WeightTrader},
+	XcmExecutor,
+};
 
 const ALICE: [u8; 32] = [1u8; 32];
 const SOME_ASSET_ADMIN: [u8; 32] = [5u8; 32];
@@ -631,3 +636,158 @@ asset_test_utils::include_create_and_manage_foreign_assets_for_local_consensus_p
 		assert_eq!(ForeignAssets::asset_ids().collect::<Vec<_>>().len(), 1);
 	})
 );
+
+#[test]
+fn report_bridge_status_from_sibling_bridge_hub_works() {
+	ExtBuilder::<Runtime>::default()
+		.with_collators(collator_session_keys().collators())
+		.with_session_keys(collator_session_keys().session_keys())
+		.build()
+		.execute_with(|| {
+			let report_bridge_status = |is_congested| {
+				Xcm::<RuntimeCall>(vec![
+					UnpaidExecution { weight_limit: Unlimited, check_origin: None },
+					Transact {
+						origin_kind: OriginKind::Xcm,
+						require_weight_at_most:
+							bp_asset_hub_kusama::XcmBridgeHubRouterTransactCallMaxWeight::get(),
+						call: bp_asset_hub_kusama::Call::ToPolkadotXcmRouter(
+							bp_asset_hub_kusama::XcmBridgeHubRouterCall::report_bridge_status {
+								bridge_id: Default::default(),
+								is_congested,
+							},
+						)
+						.encode()
+						.into(),
+					},
+				])
+			};
+			let execute = |origin: MultiLocation, xcm: Xcm<RuntimeCall>| {
+				let hash = xcm.using_encoded(sp_core::hashing::blake2_256);
+				XcmExecutor::<XcmConfig>::execute_xcm(
+					origin,
+					xcm,
+					hash,
+					RuntimeHelper::xcm_max_weight(XcmReceivedFrom::Sibling),
+				)
+			};
+
+			// other siblings are not allowed to report the bridge status
+			let other_sibling = MultiLocation::new(1, X1(Parachain(1003)));
+			assert_eq!(
+				execute(other_sibling, report_bridge_status(true)),
+				Outcome::Error(XcmError::Barrier),
+			);
+			assert!(!ToPolkadotXcmRouter::bridge().is_congested);
+
+			// but the sibling bridge hub is
+			assert_eq!(
+				execute(SiblingBridgeHub::get(), report_bridge_status(true)).ensure_complete(),
+				Ok(()),
+			);
+			assert!(ToPolkadotXcmRouter::bridge().is_congested);
+
+			assert_eq!(
+				execute(SiblingBridgeHub::get(), report_bridge_status(false)).ensure_complete(),
+				Ok(()),
+			);
+			assert!(!ToPolkadotXcmRouter::bridge().is_congested);
+		})
+}
+
+#[test]
+fn request_open_hrmp_channel_sends_transact_to_relay_chain() {
+	use cumulus_primitives_core::ParaId;
+	use pallet_hrmp_channel_manager::ChannelState;
+	use xcm::VersionedXcm;
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(collator_session_keys().collators())
+		.with_session_keys(collator_session_keys().session_keys())
+		.with_safe_xcm_version(XCM_VERSION)
+		.with_para_id(1000.into())
+		.build()
+		.execute_with(|| {
+			let recipient = ParaId::from(2000);
+			assert_ok!(HrmpChannelManager::request_open_channel(
+				RuntimeHelper::root_origin(),
+				recipient,
+				8,
+				8192,
+			));
+			assert_eq!(
+				HrmpChannelManager::channel_state(1000.into(), recipient),
+				Some(ChannelState::OpenRequested),
+			);
+
+			// the request is sent to the relay chain as a `Hrmp::hrmp_init_open_channel` call,
+			// dispatched with the origin of the parachain
+			let messages = ParachainSystem::take_pending_upward_messages();
+			assert_eq!(messages.len(), 1);
+			let xcm: Xcm<()> = VersionedXcm::<()>::decode(&mut &messages[0][..])
+				.expect("upward message is a versioned XCM")
+				.try_into()
+				.expect("upward message is a latest XCM");
+			let expected_call = (60u8, 0u8, recipient, 8u32, 8192u32).encode();
+			assert!(xcm.0.iter().any(|instruction| matches!(
+				instruction,
+				Transact { origin_kind: OriginKind::Native, call, .. }
+					if call.clone().into_encoded() == expected_call
+			)));
+		})
+}
+
+#[test]
+fn set_keys_cannot_bypass_the_session_keys_proof_in_other_calls_or_xcm() {
+	use frame_support::traits::Contains;
+	use sp_runtime::traits::Dispatchable;
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(collator_session_keys().collators())
+		.with_session_keys(collator_session_keys().session_keys())
+		.build()
+		.execute_with(|| {
+			let bob = AccountId::from([2u8; 32]);
+			// the aura key of alice, without any proof that bob controls it.
+			let set_keys = RuntimeCall::Session(pallet_session::Call::set_keys {
+				keys: SessionKeys { aura: AuraId::from(sp_core::sr25519::Public::from_raw(ALICE)) },
+				proof: Vec::new(),
+			});
+			let batch = RuntimeCall::Utility(pallet_utility::Call::batch {
+				calls: vec![
+					RuntimeCall::System(frame_system::Call::remark { remark: vec![] }),
+					set_keys.clone(),
+				],
+			});
+
+			// dispatched directly, the call is checked by the `CheckSessionKeysProof` extension.
+			assert!(<Runtime as frame_system::Config>::BaseCallFilter::contains(&set_keys));
+
+			let result = batch.clone().dispatch(RuntimeHelper::origin_of(bob.clone()));
+			assert_eq!(
+				result.map_err(|e| e.error),
+				Err(frame_system::Error::<Runtime>::CallFiltered.into())
+			);
+			assert!(pallet_session::NextKeys::<Runtime>::get(&bob).is_none());
+
+			// nor can it be nested any deeper.
+			let proxy = RuntimeCall::Proxy(pallet_proxy::Call::proxy {
+				real: bob.clone().into(),
+				force_proxy_type: None,
+				call: Box::new(batch),
+			});
+			assert!(!<Runtime as frame_system::Config>::BaseCallFilter::contains(&proxy));
+			let multisig = RuntimeCall::Multisig(pallet_multisig::Call::as_multi_threshold_1 {
+				other_signatories: vec![AccountId::from(ALICE)],
+				call: Box::new(set_keys.clone()),
+			});
+			assert!(!<Runtime as frame_system::Config>::BaseCallFilter::contains(&multisig));
+
+			// nor dispatched by XCM `Transact`, e.g. by the sovereign account of a sibling.
+			type SafeCallFilter = <XcmConfig as xcm_executor::Config>::SafeCallFilter;
+			assert!(!SafeCallFilter::contains(&set_keys));
+			assert!(SafeCallFilter::contains(&RuntimeCall::Session(
+				pallet_session::Call::purge_keys {}
+			)));
+		})
+}
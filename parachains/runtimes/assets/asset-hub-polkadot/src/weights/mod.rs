@@ -2,11 +2,14 @@ pub mod block_weights;
 pub mod cumulus_pallet_xcmp_queue;
 pub mod extrinsic_weights;
 pub mod frame_system;
+pub mod pallet_asset_conversion;
 pub mod pallet_assets_foreign;
 pub mod pallet_assets_local;
+pub mod pallet_assets_pool;
 pub mod pallet_balances;
 pub mod pallet_collator_selection;
 pub mod pallet_multisig;
+pub mod pallet_nft_fractionalization;
 pub mod pallet_nfts;
 pub mod pallet_proxy;
 pub mod pallet_session;
@@ -21,6 +21,11 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `runner-ynta1nyy-project-238-concurrent-0`, CPU: `Intel(R) Xeon(R) CPU @ 2.60GHz`
 //! EXECUTION: ``, WASM-EXECUTION: `Compiled`, CHAIN: `Some("asset-hub-polkadot-dev")`, DB CACHE: 1024
+//!
+//! The weights of `update_bond`, `take_candidate_slot`, `set_liveness_exemption`,
+//! `remove_offline_candidate`, `delegate`, `undelegate`, `withdraw_unbonded` and
+//! `reward_delegators` are not benchmarked yet. They are placeholders that must be replaced by the
+//! output of the command below.
 
 // Executed Command:
 // ./target/production/polkadot-parachain
@@ -176,6 +181,68 @@ impl<T: frame_system::Config> pallet_collator_selection::WeightInfo for WeightIn
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
+	fn update_bond(c: u32, ) -> Weight {
+		Weight::from_parts(56_085_000, 0)
+			.saturating_add(Weight::from_parts(0, 49487))
+			.saturating_add(Weight::from_parts(160_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	fn take_candidate_slot(c: u32, ) -> Weight {
+		Weight::from_parts(82_460_000, 0)
+			.saturating_add(Weight::from_parts(0, 49487))
+			.saturating_add(Weight::from_parts(215_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes(6))
+			.saturating_add(Weight::from_parts(0, 54).saturating_mul(c.into()))
+	}
+	fn set_liveness_exemption() -> Weight {
+		Weight::from_parts(15_912_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	fn remove_offline_candidate(c: u32, ) -> Weight {
+		Weight::from_parts(45_230_000, 0)
+			.saturating_add(Weight::from_parts(0, 54090))
+			.saturating_add(Weight::from_parts(180_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	fn delegate(c: u32, ) -> Weight {
+		Weight::from_parts(52_340_000, 0)
+			.saturating_add(Weight::from_parts(0, 6287))
+			.saturating_add(Weight::from_parts(1_163_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(Weight::from_parts(0, 2543).saturating_mul(c.into()))
+	}
+	fn undelegate(c: u32, ) -> Weight {
+		Weight::from_parts(55_021_000, 0)
+			.saturating_add(Weight::from_parts(0, 6287))
+			.saturating_add(Weight::from_parts(1_170_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(Weight::from_parts(0, 2543).saturating_mul(c.into()))
+	}
+	fn withdraw_unbonded() -> Weight {
+		Weight::from_parts(38_725_000, 0)
+			.saturating_add(Weight::from_parts(0, 3894))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	fn reward_delegators(d: u32, ) -> Weight {
+		Weight::from_parts(9_512_000, 0)
+			.saturating_add(Weight::from_parts(0, 6287))
+			.saturating_add(Weight::from_parts(33_840_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(d.into()))
+	}
 	/// Storage: `System::Account` (r:2 w:2)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	/// Storage: `System::BlockWeight` (r:1 w:1)
@@ -18,20 +18,21 @@
 //! Tests for the Statemint (Polkadot Assets Hub) chain.
 
 use asset_hub_polkadot_runtime::xcm_config::{
-	AssetFeeAsExistentialDepositMultiplierFeeCharger, CheckingAccount, DotLocation,
-	ForeignCreatorsSovereignAccountOf, TrustBackedAssetsPalletLocation, XcmConfig,
+	bridging::SiblingBridgeHub, AssetFeeAsExistentialDepositMultiplierFeeCharger, CheckingAccount,
+	DotLocation, ForeignCreatorsSovereignAccountOf, TrustBackedAssetsPalletLocation, XcmConfig,
 };
 pub use asset_hub_polkadot_runtime::{
-	constants::fee::WeightToFee, AllPalletsWithoutSystem, AssetDeposit, Assets, Balances,
-	ExistentialDeposit, ForeignAssets, ForeignAssetsInstance, MetadataDepositBase,
-	MetadataDepositPerByte, ParachainSystem, Runtime, RuntimeCall, RuntimeEvent, SessionKeys,
-	System, TrustBackedAssetsInstance,
+	constants::fee::WeightToFee, AllPalletsWithoutSystem, AssetConversion, AssetDeposit, Assets,
+	Balances, ExistentialDeposit, ForeignAssets, ForeignAssetsInstance, HrmpChannelManager,
+	MetadataDepositBase, MetadataDepositPerByte, ParachainSystem, Runtime, RuntimeCall,
+	RuntimeEvent, SessionKeys, System, ToKusamaXcmRouter, TrustBackedAssetsInstance,
 };
-use asset_test_utils::{CollatorSessionKeys, ExtBuilder};
+use asset_test_utils::{CollatorSessionKeys, ExtBuilder, XcmReceivedFrom};
 use codec::{Decode, Encode};
 use cumulus_primitives_utility::ChargeWeightInFungibles;
 use frame_support::{
 	assert_noop, assert_ok,
+	dispatch::DispatchClass,
 	traits::fungibles::InspectEnumerable,
 	weights::{Weight, WeightToFee as WeightToFeeT},
 };
@@ -40,7 +41,10 @@ use parachains_common::{
 };
 use sp_runtime::traits::MaybeEquivalence;
 use xcm::latest::prelude::*;
-use xcm_executor::traits::{Identity, JustTry, WeightTrader};
+use xcm_executor::{
+	traits::{Identity, JustTry, WeightTrader},
+	XcmExecutor,
+};
 
 const ALICE: [u8; 32] = [1u8; 32];
 const SOME_ASSET_ADMIN: [u8; 32] = [5u8; 32];
@@ -406,6 +410,102 @@ fn test_asset_xcm_trader_not_possible_for_non_sufficient_assets() {
 		});
 }
 
+#[test]
+fn test_asset_xcm_trader_swaps_pooled_non_sufficient_assets() {
+	ExtBuilder::<Runtime>::default()
+		.with_collators(vec![AccountId::from(ALICE)])
+		.with_session_keys(vec![(
+			AccountId::from(ALICE),
+			AccountId::from(ALICE),
+			SessionKeys { aura: AuraId::from(sp_core::ed25519::Public::from_raw(ALICE)) },
+		)])
+		.with_balances(vec![(
+			AccountId::from(ALICE),
+			100 * ExistentialDeposit::get() + 1_000_000_000_000,
+		)])
+		.build()
+		.execute_with(|| {
+			// Create a non-sufficient asset
+			let minimum_asset_balance = 1_000_000_u128;
+			assert_ok!(Assets::force_create(
+				RuntimeHelper::root_origin(),
+				1.into(),
+				AccountId::from(ALICE).into(),
+				false,
+				minimum_asset_balance
+			));
+			assert_ok!(Assets::mint(
+				RuntimeHelper::origin_of(AccountId::from(ALICE)),
+				1.into(),
+				AccountId::from(ALICE).into(),
+				3_000_000_000_000
+			));
+
+			let asset_multilocation = AssetIdForTrustBackedAssetsConvert::convert_back(&1).unwrap();
+
+			// Create a pool of the asset against the native asset
+			assert_ok!(AssetConversion::create_pool(
+				RuntimeHelper::origin_of(AccountId::from(ALICE)),
+				Box::new(DotLocation::get()),
+				Box::new(asset_multilocation),
+			));
+			assert_ok!(AssetConversion::add_liquidity(
+				RuntimeHelper::origin_of(AccountId::from(ALICE)),
+				Box::new(DotLocation::get()),
+				Box::new(asset_multilocation),
+				1_000_000_000_000,
+				2_000_000_000_000,
+				0,
+				0,
+				AccountId::from(ALICE).into()
+			));
+
+			let mut trader = <XcmConfig as xcm_executor::Config>::Trader::new();
+			let ctx = XcmContext { origin: None, message_id: XcmHash::default(), topic: None };
+
+			// Set Alice as block author, who will receive fees
+			RuntimeHelper::run_to_block(2, AccountId::from(ALICE));
+
+			// We are going to buy 400e9 weight
+			let bought = Weight::from_parts(400_000_000_000u64, 0);
+			let native_fee = WeightToFee::weight_to_fee(&bought);
+
+			// The asset is charged for the native fee of the weight and of the swap
+			let asset_charged = AssetConversion::quote_price_tokens_for_exact_tokens(
+				asset_multilocation,
+				DotLocation::get(),
+				native_fee,
+				true,
+			)
+			.unwrap();
+			let payment: MultiAsset = (asset_multilocation, 2 * asset_charged).into();
+
+			let unused = trader.buy_weight(bought, payment.into(), &ctx).unwrap();
+			let unused = unused.fungible.get(&Concrete(asset_multilocation)).copied().unwrap();
+			assert!(unused > 0 && unused < asset_charged);
+
+			let native_balance_before = Balances::free_balance(AccountId::from(ALICE));
+			let asset_balance_before = Assets::balance(1, AccountId::from(ALICE));
+			let mandatory_weight_before =
+				*frame_system::Pallet::<Runtime>::block_weight().get(DispatchClass::Mandatory);
+
+			// Drop trader
+			drop(trader);
+
+			// Make sure author(Alice) has received the fee in the native asset
+			assert!(
+				Balances::free_balance(AccountId::from(ALICE)) >=
+					native_balance_before + native_fee
+			);
+			assert_eq!(Assets::balance(1, AccountId::from(ALICE)), asset_balance_before);
+
+			// The weight of the swap has been accounted for
+			assert!(frame_system::Pallet::<Runtime>::block_weight()
+				.get(DispatchClass::Mandatory)
+				.all_gt(mandatory_weight_before));
+		});
+}
+
 #[test]
 fn test_assets_balances_api_works() {
 	use assets_common::runtime_api::runtime_decl_for_fungibles_api::FungiblesApi;
@@ -657,3 +757,103 @@ asset_test_utils::include_create_and_manage_foreign_assets_for_local_consensus_p
 		assert_eq!(ForeignAssets::asset_ids().collect::<Vec<_>>().len(), 1);
 	})
 );
+
+#[test]
+fn report_bridge_status_from_sibling_bridge_hub_works() {
+	ExtBuilder::<Runtime>::default()
+		.with_collators(collator_session_keys().collators())
+		.with_session_keys(collator_session_keys().session_keys())
+		.build()
+		.execute_with(|| {
+			let report_bridge_status = |is_congested| {
+				Xcm::<RuntimeCall>(vec![
+					UnpaidExecution { weight_limit: Unlimited, check_origin: None },
+					Transact {
+						origin_kind: OriginKind::Xcm,
+						require_weight_at_most:
+							bp_asset_hub_polkadot::XcmBridgeHubRouterTransactCallMaxWeight::get(),
+						call: bp_asset_hub_polkadot::Call::ToKusamaXcmRouter(
+							bp_asset_hub_polkadot::XcmBridgeHubRouterCall::report_bridge_status {
+								bridge_id: Default::default(),
+								is_congested,
+							},
+						)
+						.encode()
+						.into(),
+					},
+				])
+			};
+			let execute = |origin: MultiLocation, xcm: Xcm<RuntimeCall>| {
+				let hash = xcm.using_encoded(sp_core::hashing::blake2_256);
+				XcmExecutor::<XcmConfig>::execute_xcm(
+					origin,
+					xcm,
+					hash,
+					RuntimeHelper::xcm_max_weight(XcmReceivedFrom::Sibling),
+				)
+			};
+
+			// other siblings are not allowed to report the bridge status
+			let other_sibling = MultiLocation::new(1, X1(Parachain(1003)));
+			assert_eq!(
+				execute(other_sibling, report_bridge_status(true)),
+				Outcome::Error(XcmError::Barrier),
+			);
+			assert!(!ToKusamaXcmRouter::bridge().is_congested);
+
+			// but the sibling bridge hub is
+			assert_eq!(
+				execute(SiblingBridgeHub::get(), report_bridge_status(true)).ensure_complete(),
+				Ok(()),
+			);
+			assert!(ToKusamaXcmRouter::bridge().is_congested);
+
+			assert_eq!(
+				execute(SiblingBridgeHub::get(), report_bridge_status(false)).ensure_complete(),
+				Ok(()),
+			);
+			assert!(!ToKusamaXcmRouter::bridge().is_congested);
+		})
+}
+
+#[test]
+fn request_open_hrmp_channel_sends_transact_to_relay_chain() {
+	use cumulus_primitives_core::ParaId;
+	use pallet_hrmp_channel_manager::ChannelState;
+	use xcm::VersionedXcm;
+
+	ExtBuilder::<Runtime>::default()
+		.with_collators(collator_session_keys().collators())
+		.with_session_keys(collator_session_keys().session_keys())
+		.with_safe_xcm_version(XCM_VERSION)
+		.with_para_id(1000.into())
+		.build()
+		.execute_with(|| {
+			let recipient = ParaId::from(2000);
+			assert_ok!(HrmpChannelManager::request_open_channel(
+				RuntimeHelper::root_origin(),
+				recipient,
+				8,
+				8192,
+			));
+			assert_eq!(
+				HrmpChannelManager::channel_state(1000.into(), recipient),
+				Some(ChannelState::OpenRequested),
+			);
+
+			// the request is sent to the relay chain as a `Hrmp::hrmp_init_open_channel` call,
+			// dispatched with the origin of the parachain
+			let messages = ParachainSystem::take_pending_upward_messages();
+			assert_eq!(messages.len(), 1);
+			let xcm: Xcm<()> = VersionedXcm::<()>::decode(&mut &messages[0][..])
+				.expect("upward message is a versioned XCM")
+				.try_into()
+				.expect("upward message is a latest XCM");
+			let expected_call = (60u8, 0u8, recipient, 8u32, 8192u32).encode();
+			assert!(xcm.0.iter().any(|instruction| matches!(
+				instruction,
+				Transact { origin_kind: OriginKind::Native, call, .. }
+					if call.clone().into_encoded() == expected_call
+			)));
+		})
+}
@@ -67,7 +67,7 @@ use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, Verify},
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, Permill, RuntimeDebug,
+	ApplyExtrinsicResult, Perbill, Permill, RuntimeDebug,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -98,6 +98,38 @@ impl_opaque_keys! {
 	}
 }
 
+impl parachains_common::session_keys::ProveOwnership<AccountId> for SessionKeys {
+	fn verify_ownership(&self, owner: &AccountId, proof: &[u8]) -> bool {
+		parachains_common::session_keys::verify_key(&self.aura, owner, &mut &proof[..])
+	}
+}
+
+impl parachains_common::session_keys::NestedCalls for RuntimeCall {
+	fn nested_calls(&self) -> Vec<&Self> {
+		match self {
+			RuntimeCall::Utility(
+				pallet_utility::Call::batch { calls } |
+				pallet_utility::Call::batch_all { calls } |
+				pallet_utility::Call::force_batch { calls },
+			) => calls.iter().collect(),
+			RuntimeCall::Utility(
+				pallet_utility::Call::as_derivative { call, .. } |
+				pallet_utility::Call::dispatch_as { call, .. } |
+				pallet_utility::Call::with_weight { call, .. },
+			) => sp_std::vec![&**call],
+			RuntimeCall::Multisig(
+				pallet_multisig::Call::as_multi_threshold_1 { call, .. } |
+				pallet_multisig::Call::as_multi { call, .. },
+			) => sp_std::vec![&**call],
+			RuntimeCall::Proxy(
+				pallet_proxy::Call::proxy { call, .. } |
+				pallet_proxy::Call::proxy_announced { call, .. },
+			) => sp_std::vec![&**call],
+			_ => Vec::new(),
+		}
+	}
+}
+
 #[sp_version::runtime_version]
 pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// Note: "westmint" is the legacy name for this chain. It has been renamed to
@@ -109,7 +141,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_version: 10000,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 13,
+	transaction_version: 14,
 	state_version: 0,
 };
 
@@ -146,7 +178,7 @@ parameter_types! {
 
 // Configure FRAME pallets to include in runtime.
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = parachains_common::session_keys::NoNestedSetKeys<Runtime>;
 	type BlockWeights = RuntimeBlockWeights;
 	type BlockLength = RuntimeBlockLength;
 	type AccountId = AccountId;
@@ -472,7 +504,8 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 						RuntimeCall::Utility { .. } |
 						RuntimeCall::Multisig { .. } |
 						RuntimeCall::NftFractionalization { .. } |
-						RuntimeCall::Nfts { .. } | RuntimeCall::Uniques { .. }
+						RuntimeCall::Nfts { .. } |
+						RuntimeCall::Uniques { .. }
 				)
 			},
 			ProxyType::AssetOwner => matches!(
@@ -579,18 +612,24 @@ impl pallet_proxy::Config for Runtime {
 parameter_types! {
 	pub const ReservedXcmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
 	pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
+	pub const PovSizeWatermark: u64 = MAXIMUM_BLOCK_WEIGHT.proof_size() / 2;
 }
 
 impl cumulus_pallet_parachain_system::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type OnSystemEvent = ();
+	type RelayChainGenesisHashStore = ParachainInfo;
 	type SelfParaId = parachain_info::Pallet<Runtime>;
-	type DmpMessageHandler = DmpQueue;
+	type DmpMessageHandler = cumulus_pallet_dmp_queue::EnqueueDmpMessages<Runtime, MessageQueue>;
 	type ReservedDmpWeight = ReservedDmpWeight;
 	type OutboundXcmpMessageSource = XcmpQueue;
-	type XcmpMessageHandler = XcmpQueue;
+	type PriorityUpwardMessageSource = ();
+	type XcmpMessageHandler = cumulus_pallet_xcmp_queue::EnqueueXcmpMessages<Runtime, MessageQueue>;
 	type ReservedXcmpWeight = ReservedXcmpWeight;
+	type PovSizeWatermark = PovSizeWatermark;
 	type CheckAssociatedRelayNumber = RelayNumberStrictlyIncreases;
+	type CheckRelayTimestamp = ();
+	type AuthorshipProofVerifier = ();
 	type ConsensusHook = cumulus_pallet_aura_ext::FixedVelocityConsensusHook<
 		Runtime,
 		RELAY_CHAIN_SLOT_DURATION_MILLIS,
@@ -599,6 +638,30 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
 	>;
 }
 
+parameter_types! {
+	pub MessageQueueServiceWeight: Weight =
+		Perbill::from_percent(35) * RuntimeBlockWeights::get().max_block;
+}
+
+impl pallet_message_queue::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_message_queue::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type MessageProcessor = pallet_message_queue::mock_helpers::NoopMessageProcessor<
+		parachains_common::message_queue::AggregateMessageOrigin,
+	>;
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	type MessageProcessor =
+		parachains_common::message_queue::XcmMessageProcessor<Runtime, xcm_config::XcmConfig>;
+	type Size = u32;
+	// Resumes the XCMP channels, that have been suspended because of the queue size.
+	type QueueChangeHandler = XcmpQueue;
+	type QueuePausedQuery = ();
+	type HeapSize = sp_core::ConstU32<{ 64 * 1024 }>;
+	type MaxStale = sp_core::ConstU32<8>;
+	type ServiceWeight = MessageQueueServiceWeight;
+}
+
 impl parachain_info::Config for Runtime {}
 
 impl cumulus_pallet_aura_ext::Config for Runtime {}
@@ -613,6 +676,12 @@ impl cumulus_pallet_xcmp_queue::Config for Runtime {
 	type ControllerOriginConverter = XcmOriginToTransactDispatchOrigin;
 	type WeightInfo = weights::cumulus_pallet_xcmp_queue::WeightInfo<Runtime>;
 	type PriceForSiblingDelivery = ();
+	type XcmDeferFilter = ();
+	type OutboundChannelPriority = ();
+	type XcmRateLimiter = ();
+	type RelayChainBlockNumberProvider =
+		cumulus_pallet_parachain_system::RelaychainDataProvider<Runtime>;
+	type MaxInboundPageSize = ConstU32<{ 64 * 1024 }>;
 }
 
 impl cumulus_pallet_dmp_queue::Config for Runtime {
@@ -651,6 +720,7 @@ impl pallet_aura::Config for Runtime {
 
 parameter_types! {
 	pub const PotId: PalletId = PalletId(*b"PotStake");
+	pub const CollatorOfflineSlash: Perbill = Perbill::from_percent(10);
 	pub const SessionLength: BlockNumber = 6 * HOURS;
 }
 
@@ -666,6 +736,14 @@ impl pallet_collator_selection::Config for Runtime {
 	type MaxInvulnerables = ConstU32<20>;
 	// should be a multiple of session or things will get inconsistent
 	type KickThreshold = Period;
+	// remove candidates that authored no block for four sessions in a row
+	type MaxOfflineSessions = ConstU32<4>;
+	type OfflineSlash = CollatorOfflineSlash;
+	// delegations are disabled
+	type MaxDelegatorsPerCandidate = ConstU32<0>;
+	type MinDelegation = ();
+	type DelegationUnbondingPeriod = ();
+	type MaxUnbondingChunks = ();
 	type ValidatorId = <Self as frame_system::Config>::AccountId;
 	type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
 	type ValidatorRegistration = Session;
@@ -808,6 +886,7 @@ construct_runtime!(
 		PolkadotXcm: pallet_xcm::{Pallet, Call, Storage, Event<T>, Origin, Config<T>} = 31,
 		CumulusXcm: cumulus_pallet_xcm::{Pallet, Event<T>, Origin} = 32,
 		DmpQueue: cumulus_pallet_dmp_queue::{Pallet, Call, Storage, Event<T>} = 33,
+		MessageQueue: pallet_message_queue::{Pallet, Call, Storage, Event<T>} = 34,
 
 		// Handy utilities.
 		Utility: pallet_utility::{Pallet, Call, Event} = 40,
@@ -843,6 +922,7 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_asset_conversion_tx_payment::ChargeAssetTxPayment<Runtime>,
+	parachains_common::session_keys::CheckSessionKeysProof<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -854,8 +934,11 @@ pub type Migrations = (
 	pallet_nfts::migration::v1::MigrateToV1<Runtime>,
 	// unreleased
 	pallet_collator_selection::migration::v1::MigrateToV1<Runtime>,
+	pallet_collator_selection::migration::v2::MigrateToV2<Runtime>,
 	// unreleased
 	migrations::NativeAssetParents0ToParents1Migration<Runtime>,
+	cumulus_pallet_dmp_queue::migration::MigrateToMessageQueue<Runtime, MessageQueue>,
+	cumulus_pallet_xcmp_queue::migration::MigrateInboundToMessageQueue<Runtime, MessageQueue>,
 );
 
 /// Executive: handles dispatch to the various modules.
@@ -881,6 +964,7 @@ mod benches {
 		[pallet_assets, Pool]
 		[pallet_asset_conversion, AssetConversion]
 		[pallet_balances, Balances]
+		[pallet_message_queue, MessageQueue]
 		[pallet_multisig, Multisig]
 		[pallet_nft_fractionalization, NftFractionalization]
 		[pallet_nfts, Nfts]
@@ -1141,6 +1225,74 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl parachains_common::xcm_payment_api::XcmPaymentApi<Block> for Runtime {
+		fn query_acceptable_payment_assets(
+			xcm_version: u32,
+		) -> Result<Vec<xcm::VersionedMultiLocation>, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			use frame_support::traits::fungibles::InspectEnumerable;
+			use sp_runtime::traits::MaybeEquivalence;
+
+			parachains_common::xcm_payment_api::acceptable_payment_assets::<xcm_config::XcmConfig>(
+				// The native asset, the trust backed and the foreign assets.
+				[xcm_config::WestendLocation::get()]
+					.into_iter()
+					.chain(
+						<Assets as InspectEnumerable<AccountId>>::asset_ids().filter_map(|id| {
+							assets_common::AssetIdForTrustBackedAssetsConvert::<
+								xcm_config::TrustBackedAssetsPalletLocation,
+							>::convert_back(&id)
+						}),
+					)
+					.chain(<ForeignAssets as InspectEnumerable<AccountId>>::asset_ids()),
+				xcm_version,
+			)
+		}
+
+		fn query_xcm_weight(
+			message: xcm::VersionedXcm<()>,
+		) -> Result<Weight, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			parachains_common::xcm_payment_api::query_xcm_weight::<xcm_config::XcmConfig>(message)
+		}
+
+		fn query_weight_to_asset_fee(
+			weight: Weight,
+			asset: xcm::VersionedMultiLocation,
+		) -> Result<u128, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			parachains_common::xcm_payment_api::query_weight_to_asset_fee::<xcm_config::XcmConfig>(weight, asset)
+		}
+
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			message: xcm::VersionedXcm<()>,
+		) -> Result<xcm::latest::Outcome, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			parachains_common::xcm_payment_api::dry_run_xcm::<xcm_config::XcmConfig>(origin, message)
+		}
+	}
+
+	impl parachains_common::dry_run_api::DryRunApi<Block, RuntimeCall, RuntimeEvent, OriginCaller> for Runtime {
+		fn dry_run_call(
+			origin: OriginCaller,
+			call: RuntimeCall,
+		) -> Result<parachains_common::dry_run_api::CallDryRunEffects<RuntimeEvent>, parachains_common::dry_run_api::DryRunError> {
+			parachains_common::dry_run_api::dry_run_call::<Runtime, _, _>(origin, call)
+		}
+
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			xcm: xcm::VersionedXcm<RuntimeCall>,
+		) -> Result<parachains_common::dry_run_api::XcmDryRunEffects<RuntimeEvent>, parachains_common::dry_run_api::DryRunError> {
+			parachains_common::dry_run_api::dry_run_xcm::<Runtime, xcm_config::XcmConfig>(origin, xcm)
+		}
+	}
+
+	impl cumulus_pallet_dmp_queue::DmpQueueApi<Block> for Runtime {
+		fn overweight_message_summary(
+			index: cumulus_pallet_dmp_queue::OverweightIndex,
+		) -> Option<cumulus_pallet_dmp_queue::OverweightSummary> {
+			DmpQueue::overweight_message_summary(index)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -26,7 +26,8 @@ pub use asset_hub_westend_runtime::{
 };
 use asset_hub_westend_runtime::{
 	xcm_config::{
-		AssetFeeAsExistentialDepositMultiplierFeeCharger, ForeignCreatorsSovereignAccountOf,
+		AssetFeeAsExistentialDepositMultiplierFeeCharger,
+		ForeignAssetFeeAsExistentialDepositMultiplierFeeCharger, ForeignCreatorsSovereignAccountOf,
 		WestendLocation,
 	},
 	AllPalletsWithoutSystem, MetadataDepositBase, MetadataDepositPerByte, RuntimeCall,
@@ -146,6 +147,82 @@ fn test_asset_xcm_trader() {
 		});
 }
 
+#[test]
+fn test_foreign_asset_xcm_trader() {
+	ExtBuilder::<Runtime>::default()
+		.with_collators(vec![AccountId::from(ALICE)])
+		.with_session_keys(vec![(
+			AccountId::from(ALICE),
+			AccountId::from(ALICE),
+			SessionKeys { aura: AuraId::from(sp_core::sr25519::Public::from_raw(ALICE)) },
+		)])
+		.build()
+		.execute_with(|| {
+			// We need root origin to create a sufficient asset
+			let minimum_asset_balance = 3333333_u128;
+			let foreign_asset_id_multilocation =
+				MultiLocation { parents: 1, interior: X2(Parachain(1234), GeneralIndex(12345)) };
+			assert_ok!(ForeignAssets::force_create(
+				RuntimeHelper::root_origin(),
+				foreign_asset_id_multilocation,
+				AccountId::from(ALICE).into(),
+				true,
+				minimum_asset_balance
+			));
+
+			// We first mint enough asset for the account to exist for assets
+			assert_ok!(ForeignAssets::mint(
+				RuntimeHelper::origin_of(AccountId::from(ALICE)),
+				foreign_asset_id_multilocation,
+				AccountId::from(ALICE).into(),
+				minimum_asset_balance
+			));
+
+			// Set Alice as block author, who will receive fees
+			RuntimeHelper::run_to_block(2, AccountId::from(ALICE));
+
+			// We are going to buy 4e9 weight
+			let bought = Weight::from_parts(4_000_000_000u64, 0);
+
+			// Lets calculate amount needed
+			let asset_amount_needed =
+				ForeignAssetFeeAsExistentialDepositMultiplierFeeCharger::charge_weight_in_fungibles(
+					foreign_asset_id_multilocation,
+					bought,
+				)
+				.expect("failed to compute");
+
+			// Lets pay with: asset_amount_needed + asset_amount_extra
+			let asset_amount_extra = 100_u128;
+			let asset: MultiAsset =
+				(foreign_asset_id_multilocation, asset_amount_needed + asset_amount_extra).into();
+
+			let mut trader = <XcmConfig as xcm_executor::Config>::Trader::new();
+			let ctx = XcmContext { origin: None, message_id: XcmHash::default(), topic: None };
+
+			// Lets buy_weight and make sure buy_weight does not return an error
+			let unused_assets = trader.buy_weight(bought, asset.into(), &ctx).expect("Expected Ok");
+			// Check whether a correct amount of unused assets is returned
+			assert_ok!(unused_assets
+				.ensure_contains(&(foreign_asset_id_multilocation, asset_amount_extra).into()));
+
+			// Drop trader
+			drop(trader);
+
+			// Make sure author(Alice) has received the amount
+			assert_eq!(
+				ForeignAssets::balance(foreign_asset_id_multilocation, AccountId::from(ALICE)),
+				minimum_asset_balance + asset_amount_needed
+			);
+
+			// We also need to ensure the total supply increased
+			assert_eq!(
+				ForeignAssets::total_supply(foreign_asset_id_multilocation),
+				minimum_asset_balance + asset_amount_needed
+			);
+		});
+}
+
 #[test]
 fn test_asset_xcm_trader_with_refund() {
 	ExtBuilder::<Runtime>::default()
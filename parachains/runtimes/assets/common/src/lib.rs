@@ -20,6 +20,7 @@ pub mod fungible_conversion;
 pub mod local_and_foreign_assets;
 pub mod matching;
 pub mod runtime_api;
+pub mod swap_trader;
 
 use crate::matching::{Equals, LocalMultiLocationPattern, ParentLocation, StartsWith};
 use frame_support::traits::EverythingBut;
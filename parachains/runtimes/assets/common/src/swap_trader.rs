@@ -0,0 +1,201 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! XCM weight trader, that accepts the fees in any asset with a `pallet-asset-conversion` pool
+//! against the native asset.
+
+use frame_support::{
+	dispatch::DispatchClass,
+	traits::{fungibles::Mutate, Get},
+	weights::{Weight, WeightToFee as WeightToFeeT},
+};
+use pallet_asset_conversion::{MultiAssetIdConverter, Swap, WeightInfo};
+use sp_runtime::traits::Zero;
+use sp_std::{marker::PhantomData, vec};
+use xcm::latest::prelude::*;
+use xcm_executor::{traits::WeightTrader, Assets};
+
+/// The fee, that has been charged in an asset by the [`SwapFirstAssetTrader`].
+#[derive(Clone, Debug)]
+struct ChargedFee {
+	/// The location of the asset, that the fee is paid in.
+	asset: MultiLocation,
+	/// The weight, that has been bought and not refunded yet.
+	weight: Weight,
+	/// The amount of the asset, that has been charged for the `weight`.
+	amount: u128,
+}
+
+/// A [`WeightTrader`], that takes the fee from the first asset of the payment.
+///
+/// The amount of the asset is quoted from its pool against the native asset, so that it can be
+/// swapped for the native fee, that [`WeightToFee`](WeightToFeeT) charges for the weight. When the
+/// trader is dropped, the charged amount is deposited to the `FeeReceiver` and swapped for the
+/// native asset.
+///
+/// The deposit and the swap happen after the message has been weighed, so their weight is charged
+/// on top of the bought weight and registered with `frame_system`, when the trader is dropped.
+///
+/// Is meant to follow the trader of the native asset, e.g. the `UsingComponents`.
+pub struct SwapFirstAssetTrader<T, WeightToFee, FeeReceiver>(
+	Option<ChargedFee>,
+	PhantomData<(T, WeightToFee, FeeReceiver)>,
+)
+where
+	T: pallet_asset_conversion::Config<AssetId = MultiLocation, AssetBalance = u128>,
+	T::MultiAssetId: From<MultiLocation>,
+	WeightToFee: WeightToFeeT<Balance = u128>,
+	FeeReceiver: Get<Option<T::AccountId>>;
+
+impl<T, WeightToFee, FeeReceiver> SwapFirstAssetTrader<T, WeightToFee, FeeReceiver>
+where
+	T: pallet_asset_conversion::Config<AssetId = MultiLocation, AssetBalance = u128>,
+	T::MultiAssetId: From<MultiLocation>,
+	WeightToFee: WeightToFeeT<Balance = u128>,
+	FeeReceiver: Get<Option<T::AccountId>>,
+{
+	/// Returns the weight of depositing the fee to the `FeeReceiver` and swapping it for the native
+	/// asset.
+	pub fn swap_weight() -> Weight {
+		<T as pallet_asset_conversion::Config>::WeightInfo::swap_exact_tokens_for_tokens()
+			.saturating_add(<T as frame_system::Config>::DbWeight::get().reads_writes(2, 2))
+	}
+
+	/// Returns the amount of the `asset`, that is swapped for the native fee of the `weight` and
+	/// the [`swap_weight`](Self::swap_weight).
+	///
+	/// Returns `None` if the `asset` is native or has no pool against the native asset.
+	pub fn quote_fee(asset: MultiLocation, weight: &Weight) -> Option<u128> {
+		let native_fee = WeightToFee::weight_to_fee(&weight.saturating_add(Self::swap_weight()));
+		if native_fee.is_zero() {
+			return Some(0)
+		}
+
+		let asset_id = T::MultiAssetId::from(asset);
+		if T::MultiAssetIdConverter::is_native(&asset_id) {
+			return None
+		}
+
+		pallet_asset_conversion::Pallet::<T>::quote_price_tokens_for_exact_tokens(
+			asset_id,
+			T::MultiAssetIdConverter::get_native(),
+			native_fee,
+			true,
+		)
+	}
+}
+
+impl<T, WeightToFee, FeeReceiver> WeightTrader for SwapFirstAssetTrader<T, WeightToFee, FeeReceiver>
+where
+	T: pallet_asset_conversion::Config<AssetId = MultiLocation, AssetBalance = u128>,
+	T::MultiAssetId: From<MultiLocation>,
+	WeightToFee: WeightToFeeT<Balance = u128>,
+	FeeReceiver: Get<Option<T::AccountId>>,
+{
+	fn new() -> Self {
+		Self(None, PhantomData)
+	}
+
+	fn buy_weight(
+		&mut self,
+		weight: Weight,
+		payment: Assets,
+		context: &XcmContext,
+	) -> Result<Assets, XcmError> {
+		log::trace!(target: "xcm::weight", "SwapFirstAssetTrader::buy_weight weight: {:?}, payment: {:?}, context: {:?}", weight, payment, context);
+
+		// Make sure we dont enter twice
+		if self.0.is_some() {
+			return Err(XcmError::NotWithdrawable)
+		}
+
+		let multiassets: MultiAssets = payment.clone().into();
+		let asset = match multiassets.get(0) {
+			Some(MultiAsset { id: Concrete(location), fun: Fungible(_) }) => *location,
+			_ => return Err(XcmError::AssetNotFound),
+		};
+
+		let amount = Self::quote_fee(asset, &weight).ok_or(XcmError::TooExpensive)?;
+		let required: MultiAsset = (Concrete(asset), amount).into();
+		let unused = payment.checked_sub(required).map_err(|_| XcmError::TooExpensive)?;
+
+		self.0 = Some(ChargedFee { asset, weight, amount });
+		Ok(unused)
+	}
+
+	fn refund_weight(&mut self, weight: Weight, context: &XcmContext) -> Option<MultiAsset> {
+		log::trace!(target: "xcm::weight", "SwapFirstAssetTrader::refund_weight weight: {:?}, context: {:?}", weight, context);
+
+		let charged = self.0.as_mut()?;
+		let weight_outstanding = charged.weight.saturating_sub(weight);
+		// The pool prices are not linear, so the outstanding weight is quoted again.
+		let amount_outstanding = Self::quote_fee(charged.asset, &weight_outstanding)?;
+		let refund = charged.amount.saturating_sub(amount_outstanding);
+
+		charged.weight = weight_outstanding;
+		charged.amount = charged.amount.saturating_sub(refund);
+
+		if refund > 0 {
+			Some((Concrete(charged.asset), refund).into())
+		} else {
+			None
+		}
+	}
+}
+
+impl<T, WeightToFee, FeeReceiver> Drop for SwapFirstAssetTrader<T, WeightToFee, FeeReceiver>
+where
+	T: pallet_asset_conversion::Config<AssetId = MultiLocation, AssetBalance = u128>,
+	T::MultiAssetId: From<MultiLocation>,
+	WeightToFee: WeightToFeeT<Balance = u128>,
+	FeeReceiver: Get<Option<T::AccountId>>,
+{
+	fn drop(&mut self) {
+		let ChargedFee { asset, amount, .. } = match self.0.take() {
+			Some(charged) if charged.amount > 0 => charged,
+			_ => return,
+		};
+		let receiver = match FeeReceiver::get() {
+			Some(receiver) => receiver,
+			None => return,
+		};
+
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			Self::swap_weight(),
+			DispatchClass::Mandatory,
+		);
+
+		if let Err(error) = T::Assets::mint_into(asset, &receiver, amount) {
+			log::error!(target: "xcm::weight", "SwapFirstAssetTrader failed to deposit the fee {:?} of {:?}: {:?}", amount, asset, error);
+			return
+		}
+
+		// If the swap fails, the receiver keeps the fee in the asset.
+		if let Err(error) = <pallet_asset_conversion::Pallet<T> as Swap<
+			T::AccountId,
+			T::HigherPrecisionBalance,
+			T::MultiAssetId,
+		>>::swap_exact_tokens_for_tokens(
+			receiver.clone(),
+			vec![T::MultiAssetId::from(asset), T::MultiAssetIdConverter::get_native()],
+			T::HigherPrecisionBalance::from(amount),
+			None,
+			receiver,
+			false,
+		) {
+			log::debug!(target: "xcm::weight", "SwapFirstAssetTrader failed to swap the fee {:?} of {:?}: {:?}", amount, asset, error);
+		}
+	}
+}
@@ -22,6 +22,7 @@ pub mod cumulus_pallet_xcmp_queue;
 pub mod extrinsic_weights;
 pub mod frame_system;
 pub mod pallet_balances;
+pub mod pallet_bridge_fee_oracle;
 pub mod pallet_collator_selection;
 pub mod pallet_multisig;
 pub mod pallet_session;
@@ -46,7 +46,7 @@ use frame_support::{
 	construct_runtime,
 	dispatch::DispatchClass,
 	parameter_types,
-	traits::{ConstBool, ConstU32, ConstU64, ConstU8, EitherOfDiverse, Everything},
+	traits::{ConstBool, ConstU32, ConstU64, ConstU8, EitherOfDiverse},
 	weights::{ConstantMultiplier, Weight},
 	PalletId,
 };
@@ -99,6 +99,7 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	parachains_common::session_keys::CheckSessionKeysProof<Runtime>,
 );
 
 /// Unchecked extrinsic type as expected by this runtime.
@@ -106,7 +107,12 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 
 /// Migrations to apply on runtime upgrade.
-pub type Migrations = (pallet_collator_selection::migration::v1::MigrateToV1<Runtime>,);
+pub type Migrations = (
+	pallet_collator_selection::migration::v1::MigrateToV1<Runtime>,
+	pallet_collator_selection::migration::v2::MigrateToV2<Runtime>,
+	cumulus_pallet_dmp_queue::migration::MigrateToMessageQueue<Runtime, MessageQueue>,
+	cumulus_pallet_xcmp_queue::migration::MigrateInboundToMessageQueue<Runtime, MessageQueue>,
+);
 
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
@@ -124,6 +130,34 @@ impl_opaque_keys! {
 	}
 }
 
+impl parachains_common::session_keys::ProveOwnership<AccountId> for SessionKeys {
+	fn verify_ownership(&self, owner: &AccountId, proof: &[u8]) -> bool {
+		parachains_common::session_keys::verify_key(&self.aura, owner, &mut &proof[..])
+	}
+}
+
+impl parachains_common::session_keys::NestedCalls for RuntimeCall {
+	fn nested_calls(&self) -> Vec<&Self> {
+		match self {
+			RuntimeCall::Utility(
+				pallet_utility::Call::batch { calls } |
+				pallet_utility::Call::batch_all { calls } |
+				pallet_utility::Call::force_batch { calls },
+			) => calls.iter().collect(),
+			RuntimeCall::Utility(
+				pallet_utility::Call::as_derivative { call, .. } |
+				pallet_utility::Call::dispatch_as { call, .. } |
+				pallet_utility::Call::with_weight { call, .. },
+			) => sp_std::vec![&**call],
+			RuntimeCall::Multisig(
+				pallet_multisig::Call::as_multi_threshold_1 { call, .. } |
+				pallet_multisig::Call::as_multi { call, .. },
+			) => sp_std::vec![&**call],
+			_ => Vec::new(),
+		}
+	}
+}
+
 #[sp_version::runtime_version]
 pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("bridge-hub-polkadot"),
@@ -132,7 +166,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_version: 10000,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 2,
+	transaction_version: 3,
 	state_version: 1,
 };
 
@@ -203,7 +237,7 @@ impl frame_system::Config for Runtime {
 	/// The weight of database operations that the runtime can invoke.
 	type DbWeight = RocksDbWeight;
 	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = Everything;
+	type BaseCallFilter = parachains_common::session_keys::NoNestedSetKeys<Runtime>;
 	/// Weight information for the extrinsics of this pallet.
 	type SystemWeightInfo = weights::frame_system::WeightInfo<Runtime>;
 	/// Block & extrinsics weights: base values and limits.
@@ -262,25 +296,32 @@ impl pallet_transaction_payment::Config for Runtime {
 		pallet_transaction_payment::CurrencyAdapter<Balances, DealWithFees<Runtime>>;
 	type OperationalFeeMultiplier = ConstU8<5>;
 	type WeightToFee = WeightToFee;
-	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
+	type LengthToFee =
+		ConstantMultiplier<Balance, pallet_bridge_fee_oracle::CurrentByteFee<Runtime>>;
 	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
 }
 
 parameter_types! {
 	pub const ReservedXcmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
 	pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
+	pub const PovSizeWatermark: u64 = MAXIMUM_BLOCK_WEIGHT.proof_size() / 2;
 }
 
 impl cumulus_pallet_parachain_system::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type OnSystemEvent = ();
+	type RelayChainGenesisHashStore = ParachainInfo;
 	type SelfParaId = parachain_info::Pallet<Runtime>;
 	type OutboundXcmpMessageSource = XcmpQueue;
-	type DmpMessageHandler = DmpQueue;
+	type PriorityUpwardMessageSource = ();
+	type DmpMessageHandler = cumulus_pallet_dmp_queue::EnqueueDmpMessages<Runtime, MessageQueue>;
 	type ReservedDmpWeight = ReservedDmpWeight;
-	type XcmpMessageHandler = XcmpQueue;
+	type XcmpMessageHandler = cumulus_pallet_xcmp_queue::EnqueueXcmpMessages<Runtime, MessageQueue>;
 	type ReservedXcmpWeight = ReservedXcmpWeight;
+	type PovSizeWatermark = PovSizeWatermark;
 	type CheckAssociatedRelayNumber = RelayNumberStrictlyIncreases;
+	type CheckRelayTimestamp = ();
+	type AuthorshipProofVerifier = ();
 	type ConsensusHook = cumulus_pallet_aura_ext::FixedVelocityConsensusHook<
 		Runtime,
 		RELAY_CHAIN_SLOT_DURATION_MILLIS,
@@ -289,6 +330,30 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
 	>;
 }
 
+parameter_types! {
+	pub MessageQueueServiceWeight: Weight =
+		Perbill::from_percent(35) * RuntimeBlockWeights::get().max_block;
+}
+
+impl pallet_message_queue::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_message_queue::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type MessageProcessor = pallet_message_queue::mock_helpers::NoopMessageProcessor<
+		parachains_common::message_queue::AggregateMessageOrigin,
+	>;
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	type MessageProcessor =
+		parachains_common::message_queue::XcmMessageProcessor<Runtime, xcm_config::XcmConfig>;
+	type Size = u32;
+	// Resumes the XCMP channels, that have been suspended because of the queue size.
+	type QueueChangeHandler = XcmpQueue;
+	type QueuePausedQuery = ();
+	type HeapSize = sp_core::ConstU32<{ 64 * 1024 }>;
+	type MaxStale = sp_core::ConstU32<8>;
+	type ServiceWeight = MessageQueueServiceWeight;
+}
+
 impl parachain_info::Config for Runtime {}
 
 impl cumulus_pallet_aura_ext::Config for Runtime {}
@@ -314,6 +379,12 @@ impl cumulus_pallet_xcmp_queue::Config for Runtime {
 	type ControllerOriginConverter = XcmOriginToTransactDispatchOrigin;
 	type WeightInfo = weights::cumulus_pallet_xcmp_queue::WeightInfo<Runtime>;
 	type PriceForSiblingDelivery = ();
+	type XcmDeferFilter = ();
+	type OutboundChannelPriority = ();
+	type XcmRateLimiter = ();
+	type RelayChainBlockNumberProvider =
+		cumulus_pallet_parachain_system::RelaychainDataProvider<Runtime>;
+	type MaxInboundPageSize = ConstU32<{ 64 * 1024 }>;
 }
 
 impl cumulus_pallet_dmp_queue::Config for Runtime {
@@ -350,6 +421,7 @@ impl pallet_aura::Config for Runtime {
 
 parameter_types! {
 	pub const PotId: PalletId = PalletId(*b"PotStake");
+	pub const CollatorOfflineSlash: Perbill = Perbill::from_percent(10);
 	pub const SessionLength: BlockNumber = 6 * HOURS;
 	// StakingAdmin pluralistic body.
 	pub const StakingAdminBodyId: BodyId = BodyId::Defense;
@@ -371,6 +443,14 @@ impl pallet_collator_selection::Config for Runtime {
 	type MaxInvulnerables = ConstU32<20>;
 	// should be a multiple of session or things will get inconsistent
 	type KickThreshold = ConstU32<PERIOD>;
+	// liveness tracking is disabled until `remove_offline_candidate` is benchmarked
+	type MaxOfflineSessions = ConstU32<0>;
+	type OfflineSlash = CollatorOfflineSlash;
+	// delegations are disabled until they are benchmarked
+	type MaxDelegatorsPerCandidate = ConstU32<0>;
+	type MinDelegation = ();
+	type DelegationUnbondingPeriod = ();
+	type MaxUnbondingChunks = ();
 	type ValidatorId = <Self as frame_system::Config>::AccountId;
 	type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
 	type ValidatorRegistration = Session;
@@ -401,6 +481,26 @@ impl pallet_utility::Config for Runtime {
 	type WeightInfo = weights::pallet_utility::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	/// Byte fee never goes below `TransactionByteFee` / 10.
+	pub const MinTransactionByteFee: Balance = TransactionByteFee::get() / 10;
+	/// Byte fee never goes above `TransactionByteFee` * 10.
+	pub const MaxTransactionByteFee: Balance = TransactionByteFee::get() * 10;
+	pub const TransactionByteFeeSmoothingFactor: Perbill = Perbill::from_percent(10);
+}
+
+/// Tracks the byte fee of the chain transactions, which is used by the `LengthToFee`.
+impl pallet_bridge_fee_oracle::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = weights::pallet_bridge_fee_oracle::WeightInfo<Runtime>;
+	type Balance = Balance;
+	type ReportOrigin = RootOrFellows;
+	type InitialByteFee = TransactionByteFee;
+	type MinByteFee = MinTransactionByteFee;
+	type MaxByteFee = MaxTransactionByteFee;
+	type SmoothingFactor = TransactionByteFeeSmoothingFactor;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime
@@ -429,10 +529,14 @@ construct_runtime!(
 		PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin, Config<T>} = 31,
 		CumulusXcm: cumulus_pallet_xcm::{Pallet, Event<T>, Origin} = 32,
 		DmpQueue: cumulus_pallet_dmp_queue::{Pallet, Call, Storage, Event<T>} = 33,
+		MessageQueue: pallet_message_queue::{Pallet, Call, Storage, Event<T>} = 34,
 
 		// Handy utilities.
 		Utility: pallet_utility::{Pallet, Call, Event} = 40,
 		Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>} = 41,
+
+		// Bridge utilities.
+		BridgeFeeOracle: pallet_bridge_fee_oracle::{Pallet, Call, Storage, Event<T>} = 50,
 	}
 );
 
@@ -445,12 +549,14 @@ mod benches {
 	define_benchmarks!(
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
+		[pallet_message_queue, MessageQueue]
 		[pallet_multisig, Multisig]
 		[pallet_session, SessionBench::<Runtime>]
 		[pallet_utility, Utility]
 		[pallet_timestamp, Timestamp]
 		[pallet_collator_selection, CollatorSelection]
 		[cumulus_pallet_xcmp_queue, XcmpQueue]
+		[pallet_bridge_fee_oracle, BridgeFeeOracle]
 		// XCM
 		[pallet_xcm, PolkadotXcm]
 		// NOTE: Make sure you point to the individual modules below.
@@ -597,12 +703,74 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl parachains_common::xcm_payment_api::XcmPaymentApi<Block> for Runtime {
+		fn query_acceptable_payment_assets(
+			xcm_version: u32,
+		) -> Result<Vec<xcm::VersionedMultiLocation>, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			parachains_common::xcm_payment_api::acceptable_payment_assets::<xcm_config::XcmConfig>(
+				[xcm_config::DotRelayLocation::get()],
+				xcm_version,
+			)
+		}
+
+		fn query_xcm_weight(
+			message: xcm::VersionedXcm<()>,
+		) -> Result<Weight, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			parachains_common::xcm_payment_api::query_xcm_weight::<xcm_config::XcmConfig>(message)
+		}
+
+		fn query_weight_to_asset_fee(
+			weight: Weight,
+			asset: xcm::VersionedMultiLocation,
+		) -> Result<u128, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			parachains_common::xcm_payment_api::query_weight_to_asset_fee::<xcm_config::XcmConfig>(weight, asset)
+		}
+
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			message: xcm::VersionedXcm<()>,
+		) -> Result<xcm::latest::Outcome, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			parachains_common::xcm_payment_api::dry_run_xcm::<xcm_config::XcmConfig>(origin, message)
+		}
+	}
+
+	impl parachains_common::dry_run_api::DryRunApi<Block, RuntimeCall, RuntimeEvent, OriginCaller> for Runtime {
+		fn dry_run_call(
+			origin: OriginCaller,
+			call: RuntimeCall,
+		) -> Result<parachains_common::dry_run_api::CallDryRunEffects<RuntimeEvent>, parachains_common::dry_run_api::DryRunError> {
+			parachains_common::dry_run_api::dry_run_call::<Runtime, _, _>(origin, call)
+		}
+
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			xcm: xcm::VersionedXcm<RuntimeCall>,
+		) -> Result<parachains_common::dry_run_api::XcmDryRunEffects<RuntimeEvent>, parachains_common::dry_run_api::DryRunError> {
+			parachains_common::dry_run_api::dry_run_xcm::<Runtime, xcm_config::XcmConfig>(origin, xcm)
+		}
+	}
+
+	impl cumulus_pallet_dmp_queue::DmpQueueApi<Block> for Runtime {
+		fn overweight_message_summary(
+			index: cumulus_pallet_dmp_queue::OverweightIndex,
+		) -> Option<cumulus_pallet_dmp_queue::OverweightSummary> {
+			DmpQueue::overweight_message_summary(index)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
 		}
 	}
 
+	impl bp_bridge_hub_polkadot::BridgeInfoApi<Block> for Runtime {
+		fn bridges() -> Vec<bp_bridge_hub_polkadot::BridgeInfo> {
+			// No bridges are configured at this bridge hub yet.
+			Vec::new()
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
@@ -16,11 +16,14 @@
 
 pub use bridge_hub_polkadot_runtime::{
 	constants::fee::WeightToFee, xcm_config::XcmConfig, AllPalletsWithoutSystem, Balances,
-	ExistentialDeposit, ParachainSystem, PolkadotXcm, Runtime, RuntimeEvent, SessionKeys,
+	BridgeFeeOracle, ExistentialDeposit, MaxTransactionByteFee, ParachainSystem, PolkadotXcm,
+	Runtime, RuntimeEvent, RuntimeGenesisConfig, RuntimeOrigin, SessionKeys, TransactionByteFee,
+	TransactionPayment,
 };
 use codec::Decode;
-use frame_support::parameter_types;
+use frame_support::{assert_noop, assert_ok, parameter_types};
 use parachains_common::{AccountId, AuraId};
+use sp_runtime::{BuildStorage, DispatchError};
 
 const ALICE: [u8; 32] = [1u8; 32];
 
@@ -55,3 +58,29 @@ bridge_hub_test_utils::test_cases::include_teleports_for_native_asset_works!(
 	}),
 	1002
 );
+
+#[test]
+fn length_fee_follows_reported_byte_fee() {
+	let storage = RuntimeGenesisConfig::default().build_storage().unwrap();
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		let initial_length_fee = TransactionPayment::length_to_fee(1_000);
+		assert_eq!(initial_length_fee, TransactionByteFee::get() * 1_000);
+
+		assert_noop!(
+			BridgeFeeOracle::report_delivery_cost(
+				RuntimeOrigin::signed(AccountId::from(ALICE)),
+				1_000,
+				MaxTransactionByteFee::get() * 1_000,
+			),
+			DispatchError::BadOrigin,
+		);
+
+		assert_ok!(BridgeFeeOracle::report_delivery_cost(
+			RuntimeOrigin::root(),
+			1_000,
+			MaxTransactionByteFee::get() * 1_000,
+		));
+		assert!(TransactionPayment::length_to_fee(1_000) > initial_length_fee);
+		assert_eq!(TransactionPayment::length_to_fee(1_000), BridgeFeeOracle::byte_fee() * 1_000);
+	});
+}
@@ -20,7 +20,7 @@ use crate::{
 	BridgeParachainWococoInstance, BridgeWococoMessages, ParachainInfo, Runtime,
 	WithBridgeHubWococoMessagesInstance, XcmRouter,
 };
-use bp_messages::LaneId;
+use bp_messages::{target_chain::PriorityInboundLanes, LaneId, LaneQosLimits};
 use bridge_runtime_common::{
 	messages,
 	messages::{
@@ -29,12 +29,17 @@ use bridge_runtime_common::{
 	},
 	messages_xcm_extension::{SenderAndLane, XcmBlobHauler, XcmBlobHaulerAdapter},
 	refund_relayer_extension::{
-		ActualFeeRefund, RefundBridgedParachainMessages, RefundableMessagesLane,
-		RefundableParachain,
+		ActualFeeRefund, FeeOracleReportingRefund, RefundBridgedParachainMessages,
+		RefundableMessagesLane, RefundableParachain,
 	},
 };
-use frame_support::{parameter_types, traits::PalletInfoAccess};
+use codec::Encode;
+use frame_support::{
+	parameter_types,
+	traits::{Get, PalletInfoAccess},
+};
 use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
 use xcm::{
 	latest::prelude::*,
 	prelude::{InteriorMultiLocation, NetworkId},
@@ -51,12 +56,51 @@ parameter_types! {
 	pub BridgeHubRococoUniversalLocation: InteriorMultiLocation = X2(GlobalConsensus(Rococo), Parachain(ParachainInfo::parachain_id().into()));
 	pub WococoGlobalConsensusNetwork: NetworkId = NetworkId::Wococo;
 	pub ActiveOutboundLanesToBridgeHubWococo: &'static [bp_messages::LaneId] = &[DEFAULT_XCM_LANE_TO_BRIDGE_HUB_WOCOCO];
+	pub PriorityInboundLanesFromBridgeHubWococo: &'static [bp_messages::LaneId] = &[PRIORITY_XCM_LANE_FROM_BRIDGE_HUB_WOCOCO];
+	// regular (e.g. asset transfer) messages may spend at most half of the maximal extrinsic
+	// weight on dispatch in every block
+	pub NormalInboundLanesQosLimits: LaneQosLimits = LaneQosLimits {
+		max_unrewarded_relayer_entries: MaxUnrewardedRelayerEntriesAtInboundLane::get(),
+		max_unconfirmed_messages: MaxUnconfirmedMessagesAtInboundLane::get(),
+		max_dispatch_weight: <bp_bridge_hub_rococo::BridgeHubRococo as bp_runtime::Chain>::max_extrinsic_weight() / 2,
+	};
+	// a quarter of the maximal extrinsic weight is reserved for priority (e.g. governance)
+	// messages in every block
+	pub PriorityInboundLanesQosLimits: LaneQosLimits = LaneQosLimits {
+		max_unrewarded_relayer_entries: MaxUnrewardedRelayerEntriesAtInboundLane::get(),
+		max_unconfirmed_messages: MaxUnconfirmedMessagesAtInboundLane::get(),
+		max_dispatch_weight: <bp_bridge_hub_rococo::BridgeHubRococo as bp_runtime::Chain>::max_extrinsic_weight() / 4,
+	};
 	pub PriorityBoostPerMessage: u64 = 921_900_294;
 
 	pub FromAssetHubRococoToAssetHubWococoRoute: SenderAndLane = SenderAndLane::new(
 		ParentThen(X1(Parachain(1000))).into(),
 		DEFAULT_XCM_LANE_TO_BRIDGE_HUB_WOCOCO,
 	);
+
+	pub CongestedMessage: Xcm<()> = build_congestion_message(true).into();
+	pub UncongestedMessage: Xcm<()> = build_congestion_message(false).into();
+}
+
+/// Builds the XCM message that reports the state of the bridge queue to the
+/// `pallet-xcm-bridge-hub-router` at AssetHubRococo, so it can adjust its delivery fees.
+fn build_congestion_message<Call>(is_congested: bool) -> Vec<Instruction<Call>> {
+	sp_std::vec![
+		UnpaidExecution { weight_limit: Unlimited, check_origin: None },
+		Transact {
+			origin_kind: OriginKind::Xcm,
+			require_weight_at_most:
+				bp_asset_hub_rococo::XcmBridgeHubRouterTransactCallMaxWeight::get(),
+			call: bp_asset_hub_rococo::Call::ToWococoXcmRouter(
+				bp_asset_hub_rococo::XcmBridgeHubRouterCall::report_bridge_status {
+					bridge_id: Default::default(),
+					is_congested,
+				}
+			)
+			.encode()
+			.into(),
+		},
+	]
 }
 
 /// Proof of messages, coming from Wococo.
@@ -86,10 +130,20 @@ impl XcmBlobHauler for ToBridgeHubWococoXcmBlobHauler {
 	type SenderAndLane = FromAssetHubRococoToAssetHubWococoRoute;
 
 	type ToSourceChainSender = crate::XcmRouter;
-	type CongestedMessage = ();
-	type UncongestedMessage = ();
+	type CongestedMessage = CongestedMessage;
+	type UncongestedMessage = UncongestedMessage;
 }
 pub const DEFAULT_XCM_LANE_TO_BRIDGE_HUB_WOCOCO: LaneId = LaneId([0, 0, 0, 1]);
+/// Inbound lane for priority (e.g. governance) messages from the BridgeHubWococo. The bridged
+/// chain needs to open it with the `open_lane` call before using it.
+pub const PRIORITY_XCM_LANE_FROM_BRIDGE_HUB_WOCOCO: LaneId = LaneId([0, 0, 0, 2]);
+
+/// Quality of service classes of inbound lanes from the BridgeHubWococo.
+pub type FromBridgeHubWococoInboundLanesQos = PriorityInboundLanes<
+	PriorityInboundLanesFromBridgeHubWococo,
+	NormalInboundLanesQosLimits,
+	PriorityInboundLanesQosLimits,
+>;
 
 /// Messaging Bridge configuration for BridgeHubRococo -> BridgeHubWococo
 pub struct WithBridgeHubWococoMessageBridge;
@@ -140,7 +194,7 @@ pub type BridgeRefundBridgeHubWococoMessages = RefundBridgedParachainMessages<
 	Runtime,
 	RefundableParachain<BridgeParachainWococoInstance, bp_bridge_hub_wococo::BridgeHubWococo>,
 	RefundableMessagesLane<WithBridgeHubWococoMessagesInstance, BridgeHubWococoMessagesLane>,
-	ActualFeeRefund<Runtime>,
+	FeeOracleReportingRefund<ActualFeeRefund<Runtime>, Runtime>,
 	PriorityBoostPerMessage,
 	StrBridgeRefundBridgeHubWococoMessages,
 >;
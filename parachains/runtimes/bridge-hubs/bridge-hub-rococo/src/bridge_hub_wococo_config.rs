@@ -20,7 +20,7 @@ use crate::{
 	BridgeParachainRococoInstance, BridgeRococoMessages, ParachainInfo, Runtime,
 	WithBridgeHubRococoMessagesInstance, XcmRouter,
 };
-use bp_messages::LaneId;
+use bp_messages::{target_chain::PriorityInboundLanes, LaneId, LaneQosLimits};
 use bridge_runtime_common::{
 	messages,
 	messages::{
@@ -29,12 +29,17 @@ use bridge_runtime_common::{
 	},
 	messages_xcm_extension::{SenderAndLane, XcmBlobHauler, XcmBlobHaulerAdapter},
 	refund_relayer_extension::{
-		ActualFeeRefund, RefundBridgedParachainMessages, RefundableMessagesLane,
-		RefundableParachain,
+		ActualFeeRefund, FeeOracleReportingRefund, RefundBridgedParachainMessages,
+		RefundableMessagesLane, RefundableParachain,
 	},
 };
-use frame_support::{parameter_types, traits::PalletInfoAccess};
+use codec::Encode;
+use frame_support::{
+	parameter_types,
+	traits::{Get, PalletInfoAccess},
+};
 use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
 use xcm::{
 	latest::prelude::*,
 	prelude::{InteriorMultiLocation, NetworkId},
@@ -51,12 +56,51 @@ parameter_types! {
 	pub BridgeRococoMessagesPalletInstance: InteriorMultiLocation = X1(PalletInstance(<BridgeRococoMessages as PalletInfoAccess>::index() as u8));
 	pub RococoGlobalConsensusNetwork: NetworkId = NetworkId::Rococo;
 	pub ActiveOutboundLanesToBridgeHubRococo: &'static [bp_messages::LaneId] = &[DEFAULT_XCM_LANE_TO_BRIDGE_HUB_ROCOCO];
+	pub PriorityInboundLanesFromBridgeHubRococo: &'static [bp_messages::LaneId] = &[PRIORITY_XCM_LANE_FROM_BRIDGE_HUB_ROCOCO];
+	// regular (e.g. asset transfer) messages may spend at most half of the maximal extrinsic
+	// weight on dispatch in every block
+	pub NormalInboundLanesQosLimits: LaneQosLimits = LaneQosLimits {
+		max_unrewarded_relayer_entries: MaxUnrewardedRelayerEntriesAtInboundLane::get(),
+		max_unconfirmed_messages: MaxUnconfirmedMessagesAtInboundLane::get(),
+		max_dispatch_weight: <bp_bridge_hub_wococo::BridgeHubWococo as bp_runtime::Chain>::max_extrinsic_weight() / 2,
+	};
+	// a quarter of the maximal extrinsic weight is reserved for priority (e.g. governance)
+	// messages in every block
+	pub PriorityInboundLanesQosLimits: LaneQosLimits = LaneQosLimits {
+		max_unrewarded_relayer_entries: MaxUnrewardedRelayerEntriesAtInboundLane::get(),
+		max_unconfirmed_messages: MaxUnconfirmedMessagesAtInboundLane::get(),
+		max_dispatch_weight: <bp_bridge_hub_wococo::BridgeHubWococo as bp_runtime::Chain>::max_extrinsic_weight() / 4,
+	};
 	pub PriorityBoostPerMessage: u64 = 921_900_294;
 
 	pub FromAssetHubWococoToAssetHubRococoRoute: SenderAndLane = SenderAndLane::new(
 		ParentThen(X1(Parachain(1000))).into(),
 		DEFAULT_XCM_LANE_TO_BRIDGE_HUB_ROCOCO,
 	);
+
+	pub CongestedMessage: Xcm<()> = build_congestion_message(true).into();
+	pub UncongestedMessage: Xcm<()> = build_congestion_message(false).into();
+}
+
+/// Builds the XCM message that reports the state of the bridge queue to the
+/// `pallet-xcm-bridge-hub-router` at AssetHubWococo, so it can adjust its delivery fees.
+fn build_congestion_message<Call>(is_congested: bool) -> Vec<Instruction<Call>> {
+	sp_std::vec![
+		UnpaidExecution { weight_limit: Unlimited, check_origin: None },
+		Transact {
+			origin_kind: OriginKind::Xcm,
+			require_weight_at_most:
+				bp_asset_hub_wococo::XcmBridgeHubRouterTransactCallMaxWeight::get(),
+			call: bp_asset_hub_wococo::Call::ToRococoXcmRouter(
+				bp_asset_hub_wococo::XcmBridgeHubRouterCall::report_bridge_status {
+					bridge_id: Default::default(),
+					is_congested,
+				}
+			)
+			.encode()
+			.into(),
+		},
+	]
 }
 
 /// Proof of messages, coming from Rococo.
@@ -86,10 +130,20 @@ impl XcmBlobHauler for ToBridgeHubRococoXcmBlobHauler {
 	type SenderAndLane = FromAssetHubWococoToAssetHubRococoRoute;
 
 	type ToSourceChainSender = crate::XcmRouter;
-	type CongestedMessage = ();
-	type UncongestedMessage = ();
+	type CongestedMessage = CongestedMessage;
+	type UncongestedMessage = UncongestedMessage;
 }
 pub const DEFAULT_XCM_LANE_TO_BRIDGE_HUB_ROCOCO: LaneId = LaneId([0, 0, 0, 1]);
+/// Inbound lane for priority (e.g. governance) messages from the BridgeHubRococo. The bridged
+/// chain needs to open it with the `open_lane` call before using it.
+pub const PRIORITY_XCM_LANE_FROM_BRIDGE_HUB_ROCOCO: LaneId = LaneId([0, 0, 0, 2]);
+
+/// Quality of service classes of inbound lanes from the BridgeHubRococo.
+pub type FromBridgeHubRococoInboundLanesQos = PriorityInboundLanes<
+	PriorityInboundLanesFromBridgeHubRococo,
+	NormalInboundLanesQosLimits,
+	PriorityInboundLanesQosLimits,
+>;
 
 /// Messaging Bridge configuration for BridgeHubWococo -> BridgeHubRococo
 pub struct WithBridgeHubRococoMessageBridge;
@@ -140,7 +194,7 @@ pub type BridgeRefundBridgeHubRococoMessages = RefundBridgedParachainMessages<
 	Runtime,
 	RefundableParachain<BridgeParachainRococoInstance, bp_bridge_hub_rococo::BridgeHubRococo>,
 	RefundableMessagesLane<WithBridgeHubRococoMessagesInstance, BridgeHubRococoMessagesLane>,
-	ActualFeeRefund<Runtime>,
+	FeeOracleReportingRefund<ActualFeeRefund<Runtime>, Runtime>,
 	PriorityBoostPerMessage,
 	StrBridgeRefundBridgeHubRococoMessages,
 >;
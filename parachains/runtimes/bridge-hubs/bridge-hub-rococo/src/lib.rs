@@ -34,7 +34,7 @@ use sp_api::impl_runtime_apis;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT},
+	traits::{AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult,
 };
@@ -48,7 +48,7 @@ use frame_support::{
 	construct_runtime,
 	dispatch::DispatchClass,
 	parameter_types,
-	traits::{ConstBool, ConstU32, ConstU64, ConstU8, Everything},
+	traits::{ConstBool, ConstU32, ConstU64, ConstU8, Get},
 	weights::{ConstantMultiplier, Weight},
 	PalletId,
 };
@@ -84,7 +84,7 @@ use crate::{
 };
 use bridge_runtime_common::{
 	messages::{source::TargetHeaderChainAdapter, target::SourceHeaderChainAdapter},
-	messages_xcm_extension::{XcmAsPlainPayload, XcmBlobMessageDispatch},
+	messages_xcm_extension::{XcmAsPlainPayload, XcmBlobHaulerAdapter, XcmBlobMessageDispatch},
 };
 use parachains_common::{
 	impls::DealWithFees, AccountId, Balance, BlockNumber, Hash, Header, Nonce, Signature,
@@ -116,6 +116,7 @@ pub type SignedExtra = (
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
 	BridgeRejectObsoleteHeadersAndMessages,
 	(BridgeRefundBridgeHubRococoMessages, BridgeRefundBridgeHubWococoMessages),
+	parachains_common::session_keys::CheckSessionKeysProof<Runtime>,
 );
 
 /// Unchecked extrinsic type as expected by this runtime.
@@ -123,7 +124,13 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 
 /// Migrations to apply on runtime upgrade.
-pub type Migrations = (pallet_collator_selection::migration::v1::MigrateToV1<Runtime>,);
+pub type Migrations = (
+	pallet_collator_selection::migration::v1::MigrateToV1<Runtime>,
+	pallet_collator_selection::migration::v2::MigrateToV2<Runtime>,
+	cumulus_pallet_dmp_queue::migration::MigrateToMessageQueue<Runtime, MessageQueue>,
+	cumulus_pallet_xcmp_queue::migration::MigrateInboundToMessageQueue<Runtime, MessageQueue>,
+	pallet_bridge_relayers::migration::v1::MigrateToV1<Runtime>,
+);
 
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
@@ -141,6 +148,34 @@ impl_opaque_keys! {
 	}
 }
 
+impl parachains_common::session_keys::ProveOwnership<AccountId> for SessionKeys {
+	fn verify_ownership(&self, owner: &AccountId, proof: &[u8]) -> bool {
+		parachains_common::session_keys::verify_key(&self.aura, owner, &mut &proof[..])
+	}
+}
+
+impl parachains_common::session_keys::NestedCalls for RuntimeCall {
+	fn nested_calls(&self) -> Vec<&Self> {
+		match self {
+			RuntimeCall::Utility(
+				pallet_utility::Call::batch { calls } |
+				pallet_utility::Call::batch_all { calls } |
+				pallet_utility::Call::force_batch { calls },
+			) => calls.iter().collect(),
+			RuntimeCall::Utility(
+				pallet_utility::Call::as_derivative { call, .. } |
+				pallet_utility::Call::dispatch_as { call, .. } |
+				pallet_utility::Call::with_weight { call, .. },
+			) => sp_std::vec![&**call],
+			RuntimeCall::Multisig(
+				pallet_multisig::Call::as_multi_threshold_1 { call, .. } |
+				pallet_multisig::Call::as_multi { call, .. },
+			) => sp_std::vec![&**call],
+			_ => Vec::new(),
+		}
+	}
+}
+
 #[sp_version::runtime_version]
 pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("bridge-hub-rococo"),
@@ -149,7 +184,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_version: 10000,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 3,
+	transaction_version: 4,
 	state_version: 1,
 };
 
@@ -220,7 +255,7 @@ impl frame_system::Config for Runtime {
 	/// The weight of database operations that the runtime can invoke.
 	type DbWeight = RocksDbWeight;
 	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = Everything;
+	type BaseCallFilter = parachains_common::session_keys::NoNestedSetKeys<Runtime>;
 	/// Weight information for the extrinsics of this pallet.
 	type SystemWeightInfo = weights::frame_system::WeightInfo<Runtime>;
 	/// Block & extrinsics weights: base values and limits.
@@ -287,18 +322,24 @@ impl pallet_transaction_payment::Config for Runtime {
 parameter_types! {
 	pub const ReservedXcmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
 	pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
+	pub const PovSizeWatermark: u64 = MAXIMUM_BLOCK_WEIGHT.proof_size() / 2;
 }
 
 impl cumulus_pallet_parachain_system::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type OnSystemEvent = ();
+	type RelayChainGenesisHashStore = ParachainInfo;
 	type SelfParaId = parachain_info::Pallet<Runtime>;
 	type OutboundXcmpMessageSource = XcmpQueue;
-	type DmpMessageHandler = DmpQueue;
+	type PriorityUpwardMessageSource = ();
+	type DmpMessageHandler = cumulus_pallet_dmp_queue::EnqueueDmpMessages<Runtime, MessageQueue>;
 	type ReservedDmpWeight = ReservedDmpWeight;
-	type XcmpMessageHandler = XcmpQueue;
+	type XcmpMessageHandler = cumulus_pallet_xcmp_queue::EnqueueXcmpMessages<Runtime, MessageQueue>;
 	type ReservedXcmpWeight = ReservedXcmpWeight;
+	type PovSizeWatermark = PovSizeWatermark;
 	type CheckAssociatedRelayNumber = RelayNumberStrictlyIncreases;
+	type CheckRelayTimestamp = ();
+	type AuthorshipProofVerifier = ();
 	type ConsensusHook = cumulus_pallet_aura_ext::FixedVelocityConsensusHook<
 		Runtime,
 		RELAY_CHAIN_SLOT_DURATION_MILLIS,
@@ -307,6 +348,30 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
 	>;
 }
 
+parameter_types! {
+	pub MessageQueueServiceWeight: Weight =
+		Perbill::from_percent(35) * RuntimeBlockWeights::get().max_block;
+}
+
+impl pallet_message_queue::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_message_queue::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type MessageProcessor = pallet_message_queue::mock_helpers::NoopMessageProcessor<
+		parachains_common::message_queue::AggregateMessageOrigin,
+	>;
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	type MessageProcessor =
+		parachains_common::message_queue::XcmMessageProcessor<Runtime, xcm_config::XcmConfig>;
+	type Size = u32;
+	// Resumes the XCMP channels, that have been suspended because of the queue size.
+	type QueueChangeHandler = XcmpQueue;
+	type QueuePausedQuery = ();
+	type HeapSize = sp_core::ConstU32<{ 64 * 1024 }>;
+	type MaxStale = sp_core::ConstU32<8>;
+	type ServiceWeight = MessageQueueServiceWeight;
+}
+
 impl parachain_info::Config for Runtime {}
 
 impl cumulus_pallet_aura_ext::Config for Runtime {}
@@ -321,6 +386,12 @@ impl cumulus_pallet_xcmp_queue::Config for Runtime {
 	type ControllerOriginConverter = XcmOriginToTransactDispatchOrigin;
 	type WeightInfo = weights::cumulus_pallet_xcmp_queue::WeightInfo<Runtime>;
 	type PriceForSiblingDelivery = ();
+	type XcmDeferFilter = ();
+	type OutboundChannelPriority = ();
+	type XcmRateLimiter = ();
+	type RelayChainBlockNumberProvider =
+		cumulus_pallet_parachain_system::RelaychainDataProvider<Runtime>;
+	type MaxInboundPageSize = ConstU32<{ 64 * 1024 }>;
 }
 
 impl cumulus_pallet_dmp_queue::Config for Runtime {
@@ -357,6 +428,7 @@ impl pallet_aura::Config for Runtime {
 
 parameter_types! {
 	pub const PotId: PalletId = PalletId(*b"PotStake");
+	pub const CollatorOfflineSlash: Perbill = Perbill::from_percent(10);
 	pub const SessionLength: BlockNumber = 6 * HOURS;
 }
 
@@ -372,6 +444,14 @@ impl pallet_collator_selection::Config for Runtime {
 	type MaxInvulnerables = ConstU32<20>;
 	// should be a multiple of session or things will get inconsistent
 	type KickThreshold = ConstU32<PERIOD>;
+	// remove candidates that authored no block for four sessions in a row
+	type MaxOfflineSessions = ConstU32<4>;
+	type OfflineSlash = CollatorOfflineSlash;
+	// delegations are disabled
+	type MaxDelegatorsPerCandidate = ConstU32<0>;
+	type MinDelegation = ();
+	type DelegationUnbondingPeriod = ();
+	type MaxUnbondingChunks = ();
 	type ValidatorId = <Self as frame_system::Config>::AccountId;
 	type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
 	type ValidatorRegistration = Session;
@@ -410,6 +490,7 @@ impl pallet_bridge_grandpa::Config<BridgeGrandpaWococoInstance> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type BridgedChain = bp_wococo::Wococo;
 	type MaxFreeMandatoryHeadersPerBlock = ConstU32<4>;
+	type FreeHeadersInterval = ();
 	type HeadersToKeep = RelayChainHeadersToKeep;
 	type WeightInfo = weights::pallet_bridge_grandpa_bridge_wococo_grandpa::WeightInfo<Runtime>;
 }
@@ -420,6 +501,7 @@ impl pallet_bridge_grandpa::Config<BridgeGrandpaRococoInstance> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type BridgedChain = bp_rococo::Rococo;
 	type MaxFreeMandatoryHeadersPerBlock = ConstU32<4>;
+	type FreeHeadersInterval = ();
 	type HeadersToKeep = RelayChainHeadersToKeep;
 	type WeightInfo = weights::pallet_bridge_grandpa_bridge_rococo_grandpa::WeightInfo<Runtime>;
 }
@@ -428,6 +510,14 @@ parameter_types! {
 	pub const RelayChainHeadersToKeep: u32 = 1024;
 	pub const ParachainHeadsToKeep: u32 = 64;
 	pub const RelayerStakeLease: u32 = 8;
+	/// Registered relayer reputation is tracked per session.
+	pub const RelayerReputationPeriod: BlockNumber = SessionLength::get();
+	pub const RelayerMaxInactivePeriods: u32 = 4;
+	pub const StaleRelayerSlash: Perbill = Perbill::from_percent(10);
+	/// Stake, slashed from inactive relayers, goes to the treasury account.
+	pub RelayerSlashDestination: AccountId = PalletId(*b"py/trsry").into_account_truncating();
+	/// Delivered outbound messages are kept in the storage for one hour.
+	pub const BridgeMessageRetentionBlocks: BlockNumber = HOURS;
 
 	pub const RococoBridgeParachainPalletName: &'static str = "Paras";
 	pub const WococoBridgeParachainPalletName: &'static str = "Paras";
@@ -497,7 +587,10 @@ impl pallet_bridge_messages::Config<WithBridgeHubWococoMessagesInstance> for Run
 	type SourceHeaderChain = SourceHeaderChainAdapter<WithBridgeHubWococoMessageBridge>;
 	type MessageDispatch =
 		XcmBlobMessageDispatch<OnBridgeHubRococoBlobDispatcher, Self::WeightInfo, ()>;
-	type OnMessagesDelivered = ();
+	type InboundLanesQos = bridge_hub_rococo_config::FromBridgeHubWococoInboundLanesQos;
+	type OnMessagesDelivered =
+		XcmBlobHaulerAdapter<bridge_hub_rococo_config::ToBridgeHubWococoXcmBlobHauler>;
+	type MessageRetentionBlocks = BridgeMessageRetentionBlocks;
 }
 
 /// Add XCM messages support for BridgeHubWococo to support Wococo->Rococo XCM messages
@@ -531,7 +624,10 @@ impl pallet_bridge_messages::Config<WithBridgeHubRococoMessagesInstance> for Run
 	type SourceHeaderChain = SourceHeaderChainAdapter<WithBridgeHubRococoMessageBridge>;
 	type MessageDispatch =
 		XcmBlobMessageDispatch<OnBridgeHubWococoBlobDispatcher, Self::WeightInfo, ()>;
-	type OnMessagesDelivered = ();
+	type InboundLanesQos = bridge_hub_wococo_config::FromBridgeHubRococoInboundLanesQos;
+	type OnMessagesDelivered =
+		XcmBlobHaulerAdapter<bridge_hub_wococo_config::ToBridgeHubRococoXcmBlobHauler>;
+	type MessageRetentionBlocks = BridgeMessageRetentionBlocks;
 }
 
 /// Allows collect and claim rewards for relayers
@@ -548,9 +644,33 @@ impl pallet_bridge_relayers::Config for Runtime {
 		RequiredStakeForStakeAndSlash,
 		RelayerStakeLease,
 	>;
+	type ReputationPeriod = RelayerReputationPeriod;
+	type MaxInactivePeriods = RelayerMaxInactivePeriods;
+	type StaleRelayerSlash = StaleRelayerSlash;
+	type SlashDestination = RelayerSlashDestination;
 	type WeightInfo = weights::pallet_bridge_relayers::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	/// Byte fee of bridge transactions never goes below `TransactionByteFee` / 10.
+	pub const MinBridgeTransactionByteFee: Balance = TransactionByteFee::get() / 10;
+	/// Byte fee of bridge transactions never goes above `TransactionByteFee` * 1000.
+	pub const MaxBridgeTransactionByteFee: Balance = TransactionByteFee::get() * 1000;
+	pub const BridgeTransactionByteFeeSmoothingFactor: Perbill = Perbill::from_percent(10);
+}
+
+/// Tracks costs of bridge transactions, refunded to relayers
+impl pallet_bridge_fee_oracle::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = weights::pallet_bridge_fee_oracle::WeightInfo<Runtime>;
+	type Balance = Balance;
+	type ReportOrigin = EnsureRoot<AccountId>;
+	type InitialByteFee = TransactionByteFee;
+	type MinByteFee = MinBridgeTransactionByteFee;
+	type MaxByteFee = MaxBridgeTransactionByteFee;
+	type SmoothingFactor = BridgeTransactionByteFeeSmoothingFactor;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime
@@ -579,6 +699,7 @@ construct_runtime!(
 		PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin, Config<T>} = 31,
 		CumulusXcm: cumulus_pallet_xcm::{Pallet, Event<T>, Origin} = 32,
 		DmpQueue: cumulus_pallet_dmp_queue::{Pallet, Call, Storage, Event<T>} = 33,
+		MessageQueue: pallet_message_queue::{Pallet, Call, Storage, Event<T>} = 34,
 
 		// Handy utilities.
 		Utility: pallet_utility::{Pallet, Call, Event} = 40,
@@ -599,6 +720,7 @@ construct_runtime!(
 		BridgeRococoMessages: pallet_bridge_messages::<Instance2>::{Pallet, Call, Storage, Event<T>, Config<T>} = 45,
 
 		BridgeRelayers: pallet_bridge_relayers::{Pallet, Call, Storage, Event<T>} = 47,
+		BridgeFeeOracle: pallet_bridge_fee_oracle::{Pallet, Call, Storage, Event<T>} = 48,
 	}
 );
 
@@ -612,6 +734,75 @@ bridge_runtime_common::generate_bridge_reject_obsolete_headers_and_messages! {
 	BridgeRococoMessages, BridgeWococoMessages
 }
 
+/// Returns the state of all bridges, reported by the `BridgeInfoApi`.
+pub fn bridges() -> Vec<bp_bridge_hub_rococo::BridgeInfo> {
+	// Both sets of bridge pallets are deployed, but only one of them is used at the particular
+	// chain, so report both.
+	vec![
+		bridge_info::<
+			BridgeGrandpaWococoInstance,
+			BridgeParachainWococoInstance,
+			WithBridgeHubWococoMessagesInstance,
+			bp_bridge_hub_wococo::BridgeHubWococo,
+		>(bp_runtime::BRIDGE_HUB_WOCOCO_CHAIN_ID),
+		bridge_info::<
+			BridgeGrandpaRococoInstance,
+			BridgeParachainRococoInstance,
+			WithBridgeHubRococoMessagesInstance,
+			bp_bridge_hub_rococo::BridgeHubRococo,
+		>(bp_runtime::BRIDGE_HUB_ROCOCO_CHAIN_ID),
+	]
+}
+
+/// Collects the state of the bridge, built from given bridge pallet instances.
+fn bridge_info<GrandpaInstance, ParachainsInstance, MessagesInstance, BridgedBridgeHub>(
+	bridged_chain_id: bp_runtime::ChainId,
+) -> bp_bridge_hub_rococo::BridgeInfo
+where
+	Runtime: pallet_bridge_grandpa::Config<GrandpaInstance>
+		+ pallet_bridge_parachains::Config<ParachainsInstance>
+		+ pallet_bridge_messages::Config<MessagesInstance>,
+	<Runtime as pallet_bridge_grandpa::Config<GrandpaInstance>>::BridgedChain:
+		bp_runtime::Chain<Hash = Hash, BlockNumber = BlockNumber>,
+	GrandpaInstance: 'static,
+	ParachainsInstance: 'static,
+	MessagesInstance: 'static,
+	BridgedBridgeHub: bp_runtime::Parachain<Hash = Hash, BlockNumber = BlockNumber>,
+{
+	type Messages<I> = pallet_bridge_messages::Pallet<Runtime, I>;
+
+	let mut lanes =
+		<Runtime as pallet_bridge_messages::Config<MessagesInstance>>::ActiveOutboundLanes::get()
+			.to_vec();
+	lanes.extend(pallet_bridge_messages::LaneStates::<Runtime, MessagesInstance>::iter_keys());
+	lanes.sort();
+	lanes.dedup();
+
+	bp_bridge_hub_rococo::BridgeInfo {
+		bridged_chain_id,
+		grandpa_operating_mode:
+			pallet_bridge_grandpa::PalletOperatingMode::<Runtime, GrandpaInstance>::get(),
+		best_finalized_relay_header:
+			pallet_bridge_grandpa::BestFinalized::<Runtime, GrandpaInstance>::get(),
+		best_parachain_head: pallet_bridge_parachains::Pallet::<Runtime, ParachainsInstance>::best_parachain_head_id::<
+			BridgedBridgeHub,
+		>()
+		.unwrap_or(None),
+		messages_operating_mode:
+			pallet_bridge_messages::PalletOperatingMode::<Runtime, MessagesInstance>::get(),
+		lanes: lanes
+			.into_iter()
+			.filter(|lane| Messages::<MessagesInstance>::is_outbound_lane_opened(*lane))
+			.map(|lane| bp_bridge_hub_rococo::BridgeLaneInfo {
+				lane,
+				outbound: Messages::<MessagesInstance>::outbound_lane_data(lane),
+				last_delivered_inbound_nonce: Messages::<MessagesInstance>::inbound_lane_data(lane)
+					.last_delivered_nonce(),
+			})
+			.collect(),
+	}
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
 extern crate frame_benchmarking;
@@ -621,6 +812,7 @@ mod benches {
 	define_benchmarks!(
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
+		[pallet_message_queue, MessageQueue]
 		[pallet_multisig, Multisig]
 		[pallet_session, SessionBench::<Runtime>]
 		[pallet_utility, Utility]
@@ -642,6 +834,7 @@ mod benches {
 		[pallet_bridge_messages, BridgeMessagesBench::<Runtime, WithBridgeHubRococoMessagesInstance>]
 		// Bridge relayer pallets
 		[pallet_bridge_relayers, BridgeRelayersBench::<Runtime>]
+		[pallet_bridge_fee_oracle, BridgeFeeOracle]
 	);
 }
 
@@ -783,6 +976,61 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl parachains_common::xcm_payment_api::XcmPaymentApi<Block> for Runtime {
+		fn query_acceptable_payment_assets(
+			xcm_version: u32,
+		) -> Result<Vec<xcm::VersionedMultiLocation>, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			parachains_common::xcm_payment_api::acceptable_payment_assets::<xcm_config::XcmConfig>(
+				[xcm_config::RelayLocation::get()],
+				xcm_version,
+			)
+		}
+
+		fn query_xcm_weight(
+			message: xcm::VersionedXcm<()>,
+		) -> Result<Weight, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			parachains_common::xcm_payment_api::query_xcm_weight::<xcm_config::XcmConfig>(message)
+		}
+
+		fn query_weight_to_asset_fee(
+			weight: Weight,
+			asset: xcm::VersionedMultiLocation,
+		) -> Result<u128, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			parachains_common::xcm_payment_api::query_weight_to_asset_fee::<xcm_config::XcmConfig>(weight, asset)
+		}
+
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			message: xcm::VersionedXcm<()>,
+		) -> Result<xcm::latest::Outcome, parachains_common::xcm_payment_api::XcmPaymentApiError> {
+			parachains_common::xcm_payment_api::dry_run_xcm::<xcm_config::XcmConfig>(origin, message)
+		}
+	}
+
+	impl parachains_common::dry_run_api::DryRunApi<Block, RuntimeCall, RuntimeEvent, OriginCaller> for Runtime {
+		fn dry_run_call(
+			origin: OriginCaller,
+			call: RuntimeCall,
+		) -> Result<parachains_common::dry_run_api::CallDryRunEffects<RuntimeEvent>, parachains_common::dry_run_api::DryRunError> {
+			parachains_common::dry_run_api::dry_run_call::<Runtime, _, _>(origin, call)
+		}
+
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			xcm: xcm::VersionedXcm<RuntimeCall>,
+		) -> Result<parachains_common::dry_run_api::XcmDryRunEffects<RuntimeEvent>, parachains_common::dry_run_api::DryRunError> {
+			parachains_common::dry_run_api::dry_run_xcm::<Runtime, xcm_config::XcmConfig>(origin, xcm)
+		}
+	}
+
+	impl cumulus_pallet_dmp_queue::DmpQueueApi<Block> for Runtime {
+		fn overweight_message_summary(
+			index: cumulus_pallet_dmp_queue::OverweightIndex,
+		) -> Option<cumulus_pallet_dmp_queue::OverweightSummary> {
+			DmpQueue::overweight_message_summary(index)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -853,6 +1101,21 @@ impl_runtime_apis! {
 		}
 	}
 
+	// This exposed by BridgeHubRococo
+	impl bp_bridge_hub_wococo::FromBridgeHubWococoMessageDeliveryProofSizeApi<Block> for Runtime {
+		fn delivery_proof_limits(
+			lane: bp_messages::LaneId,
+			begin: bp_messages::MessageNonce,
+			end: bp_messages::MessageNonce,
+		) -> Option<bp_messages::MessageDeliveryProofLimits> {
+			bridge_runtime_common::messages_api::message_delivery_proof_limits::<
+				Runtime,
+				WithBridgeHubWococoMessagesInstance,
+				bp_bridge_hub_rococo::BridgeHubRococo,
+			>(lane, begin, end)
+		}
+	}
+
 	// This is exposed by BridgeHubWococo
 	impl bp_bridge_hub_rococo::FromBridgeHubRococoInboundLaneApi<Block> for Runtime {
 		fn message_details(
@@ -880,6 +1143,27 @@ impl_runtime_apis! {
 		}
 	}
 
+	// This is exposed by BridgeHubWococo
+	impl bp_bridge_hub_rococo::FromBridgeHubRococoMessageDeliveryProofSizeApi<Block> for Runtime {
+		fn delivery_proof_limits(
+			lane: bp_messages::LaneId,
+			begin: bp_messages::MessageNonce,
+			end: bp_messages::MessageNonce,
+		) -> Option<bp_messages::MessageDeliveryProofLimits> {
+			bridge_runtime_common::messages_api::message_delivery_proof_limits::<
+				Runtime,
+				WithBridgeHubRococoMessagesInstance,
+				bp_bridge_hub_wococo::BridgeHubWococo,
+			>(lane, begin, end)
+		}
+	}
+
+	impl bp_bridge_hub_rococo::BridgeInfoApi<Block> for Runtime {
+		fn bridges() -> Vec<bp_bridge_hub_rococo::BridgeInfo> {
+			bridges()
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
@@ -1263,6 +1547,7 @@ mod tests {
 				BridgeRefundBridgeHubRococoMessages::default(),
 				BridgeRefundBridgeHubWococoMessages::default(),
 			),
+			parachains_common::session_keys::CheckSessionKeysProof::new(),
 		);
 
 		{
@@ -22,6 +22,7 @@ pub mod cumulus_pallet_xcmp_queue;
 pub mod extrinsic_weights;
 pub mod frame_system;
 pub mod pallet_balances;
+pub mod pallet_bridge_fee_oracle;
 pub mod pallet_bridge_grandpa_bridge_rococo_grandpa;
 pub mod pallet_bridge_grandpa_bridge_wococo_grandpa;
 pub mod pallet_bridge_messages_bridge_messages_bench_runtime_with_bridge_hub_rococo_messages_instance;
@@ -48,6 +49,7 @@ use crate::Runtime;
 use frame_support::weights::Weight;
 
 // import trait from dependency module
+use ::pallet_bridge_fee_oracle::WeightInfo as _;
 use ::pallet_bridge_relayers::WeightInfoExt as _;
 
 impl pallet_bridge_messages::WeightInfoExt for pallet_bridge_messages_bridge_messages_bench_runtime_with_bridge_hub_rococo_messages_instance::WeightInfo<crate::Runtime> {
@@ -57,10 +59,14 @@ impl pallet_bridge_messages::WeightInfoExt for pallet_bridge_messages_bridge_mes
 
 	fn receive_messages_proof_overhead_from_runtime() -> Weight {
 		pallet_bridge_relayers::WeightInfo::<Runtime>::receive_messages_proof_overhead_from_runtime()
+			.saturating_add(::pallet_bridge_relayers::Pallet::<Runtime>::relayer_reputation_weight())
+			.saturating_add(pallet_bridge_fee_oracle::WeightInfo::<Runtime>::report_delivery_cost())
 	}
 
 	fn receive_messages_delivery_proof_overhead_from_runtime() -> Weight {
 		pallet_bridge_relayers::WeightInfo::<Runtime>::receive_messages_delivery_proof_overhead_from_runtime()
+			.saturating_add(::pallet_bridge_relayers::Pallet::<Runtime>::relayer_reputation_weight())
+			.saturating_add(pallet_bridge_fee_oracle::WeightInfo::<Runtime>::report_delivery_cost())
 	}
 }
 
@@ -71,10 +77,14 @@ impl pallet_bridge_messages::WeightInfoExt for pallet_bridge_messages_bridge_mes
 
 	fn receive_messages_proof_overhead_from_runtime() -> Weight {
 		pallet_bridge_relayers::WeightInfo::<Runtime>::receive_messages_proof_overhead_from_runtime()
+			.saturating_add(::pallet_bridge_relayers::Pallet::<Runtime>::relayer_reputation_weight())
+			.saturating_add(pallet_bridge_fee_oracle::WeightInfo::<Runtime>::report_delivery_cost())
 	}
 
 	fn receive_messages_delivery_proof_overhead_from_runtime() -> Weight {
 		pallet_bridge_relayers::WeightInfo::<Runtime>::receive_messages_delivery_proof_overhead_from_runtime()
+			.saturating_add(::pallet_bridge_relayers::Pallet::<Runtime>::relayer_reputation_weight())
+			.saturating_add(pallet_bridge_fee_oracle::WeightInfo::<Runtime>::report_delivery_cost())
 	}
 }
 
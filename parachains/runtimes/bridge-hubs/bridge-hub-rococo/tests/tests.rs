@@ -23,7 +23,8 @@ use bridge_hub_rococo_runtime::{
 	xcm_config::{RelayNetwork, XcmConfig},
 	AllPalletsWithoutSystem, BridgeRejectObsoleteHeadersAndMessages, DeliveryRewardInBalance,
 	Executive, ExistentialDeposit, ParachainSystem, PolkadotXcm, RequiredStakeForStakeAndSlash,
-	Runtime, RuntimeCall, RuntimeEvent, SessionKeys, SignedExtra, UncheckedExtrinsic,
+	Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, SessionKeys, SignedExtra,
+	UncheckedExtrinsic,
 };
 use codec::{Decode, Encode};
 use frame_support::parameter_types;
@@ -61,6 +62,7 @@ fn construct_extrinsic(
 			bridge_hub_wococo_config::BridgeRefundBridgeHubRococoMessages::default(),
 			bridge_hub_rococo_config::BridgeRefundBridgeHubWococoMessages::default(),
 		),
+		parachains_common::session_keys::CheckSessionKeysProof::<Runtime>::new(),
 	);
 	let payload = SignedPayload::new(call.clone(), extra.clone()).unwrap();
 	let signature = payload.using_encoded(|e| sender.sign(e));
@@ -271,6 +273,151 @@ mod bridge_hub_rococo_tests {
 			construct_and_apply_extrinsic,
 		);
 	}
+
+	#[test]
+	fn priority_inbound_lanes_have_reserved_dispatch_weight() {
+		use bp_messages::{target_chain::InboundLanesQos, LaneQosClass};
+		use bp_runtime::Chain;
+		use bridge_hub_rococo_config::{
+			FromBridgeHubWococoInboundLanesQos as InboundLanesQosConfig,
+			PRIORITY_XCM_LANE_FROM_BRIDGE_HUB_WOCOCO,
+		};
+
+		assert_eq!(
+			InboundLanesQosConfig::lane_class(&DEFAULT_XCM_LANE_TO_BRIDGE_HUB_WOCOCO),
+			LaneQosClass::Normal,
+		);
+		assert_eq!(
+			InboundLanesQosConfig::lane_class(&PRIORITY_XCM_LANE_FROM_BRIDGE_HUB_WOCOCO),
+			LaneQosClass::Priority,
+		);
+
+		// regular messages can't spend the dispatch weight, reserved for priority messages
+		let normal_limits = InboundLanesQosConfig::class_limits(LaneQosClass::Normal).unwrap();
+		let priority_limits = InboundLanesQosConfig::class_limits(LaneQosClass::Priority).unwrap();
+		assert!(!priority_limits.max_dispatch_weight.is_zero());
+		assert!(normal_limits
+			.max_dispatch_weight
+			.saturating_add(priority_limits.max_dispatch_weight)
+			.all_lte(bp_bridge_hub_rococo::BridgeHubRococo::max_extrinsic_weight()));
+	}
+
+	#[test]
+	fn submit_aggregated_finality_proof_works() {
+		use bp_header_chain::{justification::aggregate_justification, InitializationData};
+		use bp_test_utils::{authority_list, make_default_justification, TEST_GRANDPA_SET_ID};
+		use frame_support::assert_ok;
+		use sp_runtime::traits::Header as _;
+
+		type BridgeWococoGrandpa =
+			pallet_bridge_grandpa::Pallet<Runtime, BridgeGrandpaWococoInstance>;
+
+		bridge_hub_test_utils::ExtBuilder::<Runtime>::default()
+			.with_collators(collator_session_keys().collators())
+			.with_session_keys(collator_session_keys().session_keys())
+			.with_para_id(bp_bridge_hub_rococo::BRIDGE_HUB_ROCOCO_PARACHAIN_ID.into())
+			.build()
+			.execute_with(|| {
+				assert_ok!(BridgeWococoGrandpa::initialize(
+					RuntimeOrigin::root(),
+					InitializationData {
+						header: Box::new(bridge_hub_test_utils::test_header::<bp_wococo::Header>(
+							0
+						)),
+						authority_list: authority_list(),
+						set_id: TEST_GRANDPA_SET_ID,
+						operating_mode: bp_runtime::BasicOperatingMode::Normal,
+					},
+				));
+
+				let header = bridge_hub_test_utils::test_header::<bp_wococo::Header>(1);
+				let justification = aggregate_justification(
+					&make_default_justification(&header),
+					TEST_GRANDPA_SET_ID,
+				)
+				.unwrap();
+				assert_ok!(BridgeWococoGrandpa::submit_aggregated_finality_proof(
+					RuntimeOrigin::signed(AccountId::from(Alice)),
+					Box::new(header.clone()),
+					justification,
+				));
+				assert_eq!(
+					BridgeWococoGrandpa::best_finalized().map(|id| id.hash()),
+					Some(header.hash()),
+				);
+			})
+	}
+
+	#[test]
+	fn bridge_info_reports_the_state_of_all_bridges() {
+		use bp_bridge_hub_rococo::BridgeLaneInfo;
+		use bp_header_chain::InitializationData;
+		use bp_messages::MessagesOperatingMode;
+		use bp_runtime::BasicOperatingMode;
+		use bp_test_utils::{authority_list, TEST_GRANDPA_SET_ID};
+		use bridge_hub_rococo_runtime::bridges;
+		use frame_support::assert_ok;
+		use sp_runtime::traits::Header as _;
+
+		type BridgeWococoGrandpa =
+			pallet_bridge_grandpa::Pallet<Runtime, BridgeGrandpaWococoInstance>;
+		type BridgeWococoMessages =
+			pallet_bridge_messages::Pallet<Runtime, WithBridgeHubWococoMessagesInstance>;
+
+		bridge_hub_test_utils::ExtBuilder::<Runtime>::default()
+			.with_collators(collator_session_keys().collators())
+			.with_session_keys(collator_session_keys().session_keys())
+			.with_para_id(bp_bridge_hub_rococo::BRIDGE_HUB_ROCOCO_PARACHAIN_ID.into())
+			.build()
+			.execute_with(|| {
+				let header = bridge_hub_test_utils::test_header::<bp_wococo::Header>(0);
+				assert_ok!(BridgeWococoGrandpa::initialize(
+					RuntimeOrigin::root(),
+					InitializationData {
+						header: Box::new(header.clone()),
+						authority_list: authority_list(),
+						set_id: TEST_GRANDPA_SET_ID,
+						operating_mode: BasicOperatingMode::Normal,
+					},
+				));
+				assert_ok!(BridgeWococoMessages::set_operating_mode(
+					RuntimeOrigin::root(),
+					MessagesOperatingMode::RejectingOutboundMessages,
+				));
+
+				let bridges = bridges();
+				assert_eq!(
+					bridges.iter().map(|bridge| bridge.bridged_chain_id).collect::<Vec<_>>(),
+					vec![
+						bp_runtime::BRIDGE_HUB_WOCOCO_CHAIN_ID,
+						bp_runtime::BRIDGE_HUB_ROCOCO_CHAIN_ID
+					],
+				);
+
+				let wococo = &bridges[0];
+				assert_eq!(wococo.grandpa_operating_mode, BasicOperatingMode::Normal);
+				assert_eq!(
+					wococo.best_finalized_relay_header.map(|id| id.hash()),
+					Some(header.hash())
+				);
+				assert_eq!(wococo.best_parachain_head, None);
+				assert_eq!(
+					wococo.messages_operating_mode,
+					MessagesOperatingMode::RejectingOutboundMessages
+				);
+				assert_eq!(
+					wococo.lanes,
+					vec![BridgeLaneInfo {
+						lane: DEFAULT_XCM_LANE_TO_BRIDGE_HUB_WOCOCO,
+						outbound: Default::default(),
+						last_delivered_inbound_nonce: 0,
+					}],
+				);
+
+				// the other bridge is not initialized
+				assert_eq!(bridges[1].best_finalized_relay_header, None);
+			})
+	}
 }
 
 mod bridge_hub_wococo_tests {
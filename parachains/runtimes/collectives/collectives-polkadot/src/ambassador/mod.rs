@@ -0,0 +1,147 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The Polkadot Ambassador Program.
+//!
+//! The ambassadors are inducted as candidates and promoted through the `AmbassadorCore` pallet
+//! instance by the votes of the ranks above them. The Fellows administer the program.
+
+mod origins;
+mod tracks;
+use crate::{
+	fellowship::{Fellows, ReferendaPalletAccount},
+	impls::ToParentTreasury,
+	weights, AccountId, AmbassadorReferenda, Balance, Balances, PolkadotTreasuryAccount, Preimage,
+	Runtime, RuntimeCall, RuntimeEvent, Scheduler, DAYS,
+};
+use frame_support::traits::{EitherOf, EitherOfDiverse, MapSuccess, TryMapSuccess};
+use frame_system::EnsureRootWithSuccess;
+pub use origins::{
+	pallet_origins as pallet_ambassador_origins, Ambassadors, EnsureAmbassadorsVoice,
+	HeadAmbassadors, SeniorAmbassadors,
+};
+use sp_core::{ConstU128, ConstU32};
+use sp_runtime::traits::{CheckedReduceBy, ConstU16, Replace};
+
+/// The Ambassador Program members' ranks.
+pub mod ranks {
+	use pallet_ranked_collective::Rank;
+
+	pub const CANDIDATE: Rank = 0;
+	pub const AMBASSADOR: Rank = 1;
+	pub const SENIOR_AMBASSADOR: Rank = 2;
+	pub const HEAD_AMBASSADOR: Rank = 3;
+}
+
+/// The root or a vote among all the Fellows; `Success` is the rank of the Head Ambassadors.
+pub type AmbassadorAdmin = EitherOf<
+	EnsureRootWithSuccess<AccountId, ConstU16<{ ranks::HEAD_AMBASSADOR }>>,
+	MapSuccess<Fellows, Replace<ConstU16<{ ranks::HEAD_AMBASSADOR }>>>,
+>;
+
+/// A vote among the members of at least the rank of the voice; `Success` is the rank below, i.e.
+/// the ambassadors may only approve and promote to the ranks below their own.
+pub type PromoteByVoiceAbove = TryMapSuccess<EnsureAmbassadorsVoice, CheckedReduceBy<ConstU16<1>>>;
+
+impl pallet_ambassador_origins::Config for Runtime {}
+
+pub type AmbassadorReferendaInstance = pallet_referenda::Instance2;
+
+impl pallet_referenda::Config<AmbassadorReferendaInstance> for Runtime {
+	type WeightInfo = weights::pallet_referenda::WeightInfo<Runtime>;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type Scheduler = Scheduler;
+	type Currency = Balances;
+	// Ambassadors can submit proposals.
+	type SubmitOrigin = pallet_ranked_collective::EnsureMember<
+		Runtime,
+		AmbassadorCollectiveInstance,
+		{ ranks::AMBASSADOR },
+	>;
+	type CancelOrigin = SeniorAmbassadors;
+	type KillOrigin = HeadAmbassadors;
+	type Slash = ToParentTreasury<PolkadotTreasuryAccount, ReferendaPalletAccount, Runtime>;
+	type Votes = pallet_ranked_collective::Votes;
+	type Tally = pallet_ranked_collective::TallyOf<Runtime, AmbassadorCollectiveInstance>;
+	type SubmissionDeposit = ConstU128<0>;
+	type MaxQueued = ConstU32<20>;
+	type UndecidingTimeout = ConstU32<{ 7 * DAYS }>;
+	type AlarmInterval = ConstU32<1>;
+	type Tracks = tracks::TracksInfo;
+	type Preimages = Preimage;
+}
+
+pub type AmbassadorCollectiveInstance = pallet_ranked_collective::Instance2;
+
+impl pallet_ranked_collective::Config<AmbassadorCollectiveInstance> for Runtime {
+	type WeightInfo = weights::pallet_ranked_collective::WeightInfo<Runtime>;
+	type RuntimeEvent = RuntimeEvent;
+
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	// Promotions and the induction of new members are serviced by `AmbassadorCore` pallet
+	// instance.
+	type PromoteOrigin = frame_system::EnsureNever<pallet_ranked_collective::Rank>;
+	#[cfg(feature = "runtime-benchmarks")]
+	// The maximum value of `u16` set as a success value for the root to ensure the benchmarks will
+	// pass.
+	type PromoteOrigin = EnsureRootWithSuccess<Self::AccountId, ConstU16<65535>>;
+
+	// Demotion is by any of:
+	// - Root can demote arbitrarily.
+	// - a vote among all the Fellows.
+	//
+	// The maximum value of `u16` set as a success value for the root to ensure the benchmarks will
+	// pass.
+	type DemoteOrigin = EitherOf<
+		EnsureRootWithSuccess<Self::AccountId, ConstU16<65535>>,
+		MapSuccess<Fellows, Replace<ConstU16<{ ranks::HEAD_AMBASSADOR }>>>,
+	>;
+	type Polls = AmbassadorReferenda;
+	// The track ID is the minimum rank required to vote on the track.
+	type MinRankOfClass = sp_runtime::traits::Identity;
+	type VoteWeight = pallet_ranked_collective::Linear;
+}
+
+pub type AmbassadorCoreInstance = pallet_core_fellowship::Instance2;
+
+impl pallet_core_fellowship::Config<AmbassadorCoreInstance> for Runtime {
+	type WeightInfo = weights::pallet_core_fellowship::WeightInfo<Runtime>;
+	type RuntimeEvent = RuntimeEvent;
+	type Members = pallet_ranked_collective::Pallet<Runtime, AmbassadorCollectiveInstance>;
+	type Balance = Balance;
+	// Parameters are set by the root or a vote among all the Fellows.
+	type ParamsOrigin = AmbassadorAdmin;
+	// Induction (creating a candidate) is by any of:
+	// - Root;
+	// - a vote among all the Fellows;
+	// - a single Head Ambassador.
+	type InductOrigin = EitherOfDiverse<
+		AmbassadorAdmin,
+		pallet_ranked_collective::EnsureMember<
+			Runtime,
+			AmbassadorCollectiveInstance,
+			{ ranks::HEAD_AMBASSADOR },
+		>,
+	>;
+	// Approval (rank-retention) and promotion is by any of:
+	// - Root;
+	// - a vote among all the Fellows;
+	// - a vote by the ranks above the rank to approve or to promote to.
+	type ApproveOrigin = EitherOf<AmbassadorAdmin, PromoteByVoiceAbove>;
+	type PromoteOrigin = EitherOf<AmbassadorAdmin, PromoteByVoiceAbove>;
+	type EvidenceSize = ConstU32<65536>;
+}
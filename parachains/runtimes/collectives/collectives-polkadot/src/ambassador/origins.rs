@@ -0,0 +1,95 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Ambassador Program custom origins.
+
+use super::ranks;
+pub use pallet_origins::*;
+
+#[frame_support::pallet]
+pub mod pallet_origins {
+	use super::ranks;
+	use frame_support::pallet_prelude::*;
+	use pallet_ranked_collective::Rank;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[derive(PartialEq, Eq, Clone, MaxEncodedLen, Encode, Decode, TypeInfo, RuntimeDebug)]
+	#[pallet::origin]
+	pub enum Origin {
+		/// Origin aggregated through weighted votes of those with rank 1 or above; `Success` is 1.
+		/// Aka the "voice" of all Ambassadors.
+		Ambassadors,
+		/// Origin aggregated through weighted votes of those with rank 2 or above; `Success` is 2.
+		/// Aka the "voice" of all Senior Ambassadors.
+		SeniorAmbassadors,
+		/// Origin aggregated through weighted votes of those with rank 3 or above; `Success` is 3.
+		/// Aka the "voice" of all Head Ambassadors.
+		HeadAmbassadors,
+	}
+
+	macro_rules! decl_unit_ensures {
+		( $name:ident: $success_type:ty = $success:expr ) => {
+			pub struct $name;
+			impl<O: Into<Result<Origin, O>> + From<Origin>>
+				EnsureOrigin<O> for $name
+			{
+				type Success = $success_type;
+				fn try_origin(o: O) -> Result<Self::Success, O> {
+					o.into().and_then(|o| match o {
+						Origin::$name => Ok($success),
+						r => Err(O::from(r)),
+					})
+				}
+				#[cfg(feature = "runtime-benchmarks")]
+				fn try_successful_origin() -> Result<O, ()> {
+					Ok(O::from(Origin::$name))
+				}
+			}
+		};
+		( $name:ident: $success_type:ty = $success:expr, $( $rest:tt )* ) => {
+			decl_unit_ensures! { $name: $success_type = $success }
+			decl_unit_ensures! { $( $rest )* }
+		};
+		() => {}
+	}
+	decl_unit_ensures!(
+		Ambassadors: Rank = ranks::AMBASSADOR,
+		SeniorAmbassadors: Rank = ranks::SENIOR_AMBASSADOR,
+		HeadAmbassadors: Rank = ranks::HEAD_AMBASSADOR,
+	);
+
+	/// Ambassador Program origin indicating weighted voting from at least the rank of `Success`.
+	pub struct EnsureAmbassadorsVoice;
+	impl<O: Into<Result<Origin, O>> + From<Origin>> EnsureOrigin<O> for EnsureAmbassadorsVoice {
+		type Success = Rank;
+		fn try_origin(o: O) -> Result<Self::Success, O> {
+			o.into().and_then(|o| match o {
+				Origin::Ambassadors => Ok(ranks::AMBASSADOR),
+				Origin::SeniorAmbassadors => Ok(ranks::SENIOR_AMBASSADOR),
+				Origin::HeadAmbassadors => Ok(ranks::HEAD_AMBASSADOR),
+			})
+		}
+		#[cfg(feature = "runtime-benchmarks")]
+		fn try_successful_origin() -> Result<O, ()> {
+			Ok(O::from(Origin::HeadAmbassadors))
+		}
+	}
+}
@@ -0,0 +1,125 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Track configurations for the Ambassador Program.
+
+use crate::{Balance, BlockNumber, RuntimeOrigin, DAYS, DOLLARS, MINUTES};
+use sp_runtime::Perbill;
+
+/// Referendum `TrackId` type.
+pub type TrackId = u16;
+
+/// Referendum track IDs. The required rank for voting is the same as the track ID.
+pub mod constants {
+	use super::TrackId;
+
+	pub const AMBASSADORS: TrackId = 1;
+	pub const SENIOR_AMBASSADORS: TrackId = 2;
+	pub const HEAD_AMBASSADORS: TrackId = 3;
+}
+
+const MAX_DECIDING: u32 = 10;
+const DECISION_DEPOSIT: Balance = 5 * DOLLARS;
+const PREPARE_PERIOD: BlockNumber = 30 * MINUTES;
+const DECISION_PERIOD: BlockNumber = 7 * DAYS;
+const CONFIRM_PERIOD: BlockNumber = 30 * MINUTES;
+const MIN_ENACTMENT_PERIOD: BlockNumber = 5 * MINUTES;
+const MIN_APPROVAL: pallet_referenda::Curve = pallet_referenda::Curve::LinearDecreasing {
+	length: Perbill::from_percent(100),
+	floor: Perbill::from_percent(50),
+	ceil: Perbill::from_percent(100),
+};
+const MIN_SUPPORT: pallet_referenda::Curve = pallet_referenda::Curve::LinearDecreasing {
+	length: Perbill::from_percent(100),
+	floor: Perbill::from_percent(0),
+	ceil: Perbill::from_percent(100),
+};
+
+pub struct TracksInfo;
+impl pallet_referenda::TracksInfo<Balance, BlockNumber> for TracksInfo {
+	type Id = TrackId;
+	type RuntimeOrigin = <RuntimeOrigin as frame_support::traits::OriginTrait>::PalletsOrigin;
+	fn tracks() -> &'static [(Self::Id, pallet_referenda::TrackInfo<Balance, BlockNumber>)] {
+		use constants as tracks;
+		static DATA: [(TrackId, pallet_referenda::TrackInfo<Balance, BlockNumber>); 3] = [
+			(
+				tracks::AMBASSADORS,
+				pallet_referenda::TrackInfo {
+					name: "ambassadors",
+					max_deciding: MAX_DECIDING,
+					decision_deposit: DECISION_DEPOSIT,
+					prepare_period: PREPARE_PERIOD,
+					decision_period: DECISION_PERIOD,
+					confirm_period: CONFIRM_PERIOD,
+					min_enactment_period: MIN_ENACTMENT_PERIOD,
+					min_approval: MIN_APPROVAL,
+					min_support: MIN_SUPPORT,
+				},
+			),
+			(
+				tracks::SENIOR_AMBASSADORS,
+				pallet_referenda::TrackInfo {
+					name: "senior ambassadors",
+					max_deciding: MAX_DECIDING,
+					decision_deposit: DECISION_DEPOSIT,
+					prepare_period: PREPARE_PERIOD,
+					decision_period: DECISION_PERIOD,
+					confirm_period: CONFIRM_PERIOD,
+					min_enactment_period: MIN_ENACTMENT_PERIOD,
+					min_approval: MIN_APPROVAL,
+					min_support: MIN_SUPPORT,
+				},
+			),
+			(
+				tracks::HEAD_AMBASSADORS,
+				pallet_referenda::TrackInfo {
+					name: "head ambassadors",
+					max_deciding: MAX_DECIDING,
+					decision_deposit: DECISION_DEPOSIT,
+					prepare_period: PREPARE_PERIOD,
+					decision_period: DECISION_PERIOD,
+					confirm_period: CONFIRM_PERIOD,
+					min_enactment_period: MIN_ENACTMENT_PERIOD,
+					min_approval: MIN_APPROVAL,
+					min_support: MIN_SUPPORT,
+				},
+			),
+		];
+		&DATA[..]
+	}
+	fn track_for(id: &Self::RuntimeOrigin) -> Result<Self::Id, ()> {
+		use super::origins::Origin;
+		use constants as tracks;
+
+		#[cfg(feature = "runtime-benchmarks")]
+		{
+			// For benchmarks, we enable a root origin.
+			// It is important that this is not available in production!
+			let root: Self::RuntimeOrigin = frame_system::RawOrigin::Root.into();
+			if &root == id {
+				return Ok(tracks::HEAD_AMBASSADORS)
+			}
+		}
+
+		match Origin::try_from(id.clone()) {
+			Ok(Origin::Ambassadors) => Ok(tracks::AMBASSADORS),
+			Ok(Origin::SeniorAmbassadors) => Ok(tracks::SENIOR_AMBASSADORS),
+			Ok(Origin::HeadAmbassadors) => Ok(tracks::HEAD_AMBASSADORS),
+			_ => Err(()),
+		}
+	}
+}
+pallet_referenda::impl_tracksinfo_get!(TracksInfo, Balance, BlockNumber);
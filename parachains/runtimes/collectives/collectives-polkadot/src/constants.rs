@@ -28,6 +28,17 @@ pub mod account {
 	pub const REFERENDA_PALLET_ID: PalletId = PalletId(*b"py/refer");
 }
 
+/// XCM related constants.
+pub mod xcm {
+	/// The indices of the pluralistic bodies of this chain, i.e. `BodyId::Index`.
+	pub mod body {
+		/// The Head Ambassadors of the Ambassador Program.
+		pub const HEAD_AMBASSADORS_INDEX: u32 = 1;
+		/// The Secretary collective.
+		pub const SECRETARIES_INDEX: u32 = 2;
+	}
+}
+
 pub mod currency {
 	use polkadot_core_primitives::Balance;
 	use polkadot_runtime_constants as constants;
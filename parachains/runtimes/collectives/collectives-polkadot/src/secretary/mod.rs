@@ -0,0 +1,96 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The Secretary collective.
+//!
+//! The secretaries assist the Fellowship with its administrative tasks. They are added and removed
+//! by the Fellows and have a single referenda track.
+
+mod origins;
+mod tracks;
+use crate::{
+	fellowship::{Fellows, ReferendaPalletAccount},
+	impls::ToParentTreasury,
+	weights, Balances, PolkadotTreasuryAccount, Preimage, Runtime, RuntimeCall, RuntimeEvent,
+	Scheduler, SecretaryReferenda, DAYS,
+};
+use frame_support::traits::{EitherOf, MapSuccess};
+use frame_system::EnsureRootWithSuccess;
+pub use origins::{pallet_origins as pallet_secretary_origins, Secretaries};
+use sp_core::{ConstU128, ConstU32};
+use sp_runtime::traits::{ConstU16, Replace};
+
+/// The Secretary collective members' ranks.
+pub mod ranks {
+	use pallet_ranked_collective::Rank;
+
+	pub const SECRETARY: Rank = 1;
+}
+
+impl pallet_secretary_origins::Config for Runtime {}
+
+pub type SecretaryReferendaInstance = pallet_referenda::Instance3;
+
+impl pallet_referenda::Config<SecretaryReferendaInstance> for Runtime {
+	type WeightInfo = weights::pallet_referenda::WeightInfo<Runtime>;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type Scheduler = Scheduler;
+	type Currency = Balances;
+	// Secretaries can submit proposals.
+	type SubmitOrigin = pallet_ranked_collective::EnsureMember<
+		Runtime,
+		SecretaryCollectiveInstance,
+		{ ranks::SECRETARY },
+	>;
+	type CancelOrigin = Secretaries;
+	type KillOrigin = Fellows;
+	type Slash = ToParentTreasury<PolkadotTreasuryAccount, ReferendaPalletAccount, Runtime>;
+	type Votes = pallet_ranked_collective::Votes;
+	type Tally = pallet_ranked_collective::TallyOf<Runtime, SecretaryCollectiveInstance>;
+	type SubmissionDeposit = ConstU128<0>;
+	type MaxQueued = ConstU32<20>;
+	type UndecidingTimeout = ConstU32<{ 7 * DAYS }>;
+	type AlarmInterval = ConstU32<1>;
+	type Tracks = tracks::TracksInfo;
+	type Preimages = Preimage;
+}
+
+pub type SecretaryCollectiveInstance = pallet_ranked_collective::Instance3;
+
+impl pallet_ranked_collective::Config<SecretaryCollectiveInstance> for Runtime {
+	type WeightInfo = weights::pallet_ranked_collective::WeightInfo<Runtime>;
+	type RuntimeEvent = RuntimeEvent;
+
+	// Adding, promotion and demotion are by any of:
+	// - Root;
+	// - a vote among all the Fellows.
+	//
+	// The maximum value of `u16` set as a success value for the root to ensure the benchmarks will
+	// pass.
+	type PromoteOrigin = EitherOf<
+		EnsureRootWithSuccess<Self::AccountId, ConstU16<65535>>,
+		MapSuccess<Fellows, Replace<ConstU16<{ ranks::SECRETARY }>>>,
+	>;
+	type DemoteOrigin = EitherOf<
+		EnsureRootWithSuccess<Self::AccountId, ConstU16<65535>>,
+		MapSuccess<Fellows, Replace<ConstU16<{ ranks::SECRETARY }>>>,
+	>;
+	type Polls = SecretaryReferenda;
+	// The track ID is the minimum rank required to vote on the track.
+	type MinRankOfClass = sp_runtime::traits::Identity;
+	type VoteWeight = pallet_ranked_collective::Linear;
+}
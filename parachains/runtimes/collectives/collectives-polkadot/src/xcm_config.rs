@@ -17,6 +17,11 @@ use super::{
 	AccountId, AllPalletsWithSystem, Balances, Fellows, ParachainInfo, ParachainSystem,
 	PolkadotXcm, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, WeightToFee, XcmpQueue,
 };
+use crate::{
+	ambassador::HeadAmbassadors,
+	constants::xcm::body::{HEAD_AMBASSADORS_INDEX, SECRETARIES_INDEX},
+	secretary::Secretaries,
+};
 use frame_support::{
 	match_types, parameter_types,
 	traits::{ConstU32, Contains, Everything, Nothing},
@@ -24,17 +29,17 @@ use frame_support::{
 };
 use frame_system::EnsureRoot;
 use pallet_xcm::XcmPassthrough;
-use parachains_common::{impls::ToStakingPot, xcm_config::ConcreteNativeAssetFrom};
+use parachains_common::{
+	impls::ToStakingPot,
+	xcm_config::{ConcreteNativeAssetFrom, SystemParachainBarrier},
+};
 use polkadot_parachain::primitives::Sibling;
 use xcm::latest::prelude::*;
 use xcm_builder::{
-	AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses,
-	AllowSubscriptionsFrom, AllowTopLevelPaidExecutionFrom, CurrencyAdapter,
-	DenyReserveTransferToRelayChain, DenyThenTry, EnsureXcmOrigin, FixedWeightBounds, IsConcrete,
+	AccountId32Aliases, CurrencyAdapter, EnsureXcmOrigin, FixedWeightBounds, IsConcrete,
 	OriginToPluralityVoice, ParentAsSuperuser, ParentIsPreset, RelayChainAsNative,
 	SiblingParachainAsNative, SiblingParachainConvertsVia, SignedAccountId32AsNative,
-	SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit, TrailingSetTopicAsId,
-	UsingComponents, WithComputedOrigin, WithUniqueTopic,
+	SignedToAccountId32, SovereignSignedViaLocation, UsingComponents, WithUniqueTopic,
 };
 use xcm_executor::{traits::WithOriginFilter, XcmExecutor};
 
@@ -108,6 +113,10 @@ parameter_types! {
 	pub const MaxAssetsIntoHolding: u32 = 64;
 	// Fellows pluralistic body.
 	pub const FellowsBodyId: BodyId = BodyId::Technical;
+	// Head Ambassadors pluralistic body.
+	pub const HeadAmbassadorsBodyId: BodyId = BodyId::Index(HEAD_AMBASSADORS_INDEX);
+	// Secretaries pluralistic body.
+	pub const SecretariesBodyId: BodyId = BodyId::Index(SECRETARIES_INDEX);
 }
 
 match_types! {
@@ -119,6 +128,13 @@ match_types! {
 		MultiLocation { parents: 1, interior: Here } |
 		MultiLocation { parents: 1, interior: X1(_) }
 	};
+	// The voice of the Fellows, as the origin of the messages sent with `pallet-xcm`.
+	pub type FellowsPlurality: impl Contains<MultiLocation> = {
+		MultiLocation {
+			parents: 0,
+			interior: X1(Plurality { id: BodyId::Technical, part: BodyPart::Voice })
+		}
+	};
 }
 
 /// A call filter for the XCM Transact instruction. This is a temporary measure until we properly
@@ -196,35 +212,38 @@ impl Contains<RuntimeCall> for SafeCallFilter {
 					pallet_core_fellowship::Call::offboard { .. } |
 					pallet_core_fellowship::Call::submit_evidence { .. } |
 					pallet_core_fellowship::Call::import { .. },
+			) | RuntimeCall::AmbassadorCollective(
+				pallet_ranked_collective::Call::add_member { .. } |
+					pallet_ranked_collective::Call::promote_member { .. } |
+					pallet_ranked_collective::Call::demote_member { .. } |
+					pallet_ranked_collective::Call::remove_member { .. },
+			) | RuntimeCall::AmbassadorCore(
+				pallet_core_fellowship::Call::bump { .. } |
+					pallet_core_fellowship::Call::set_params { .. } |
+					pallet_core_fellowship::Call::set_active { .. } |
+					pallet_core_fellowship::Call::approve { .. } |
+					pallet_core_fellowship::Call::induct { .. } |
+					pallet_core_fellowship::Call::promote { .. } |
+					pallet_core_fellowship::Call::offboard { .. } |
+					pallet_core_fellowship::Call::submit_evidence { .. } |
+					pallet_core_fellowship::Call::import { .. },
+			) | RuntimeCall::SecretaryCollective(
+				pallet_ranked_collective::Call::add_member { .. } |
+					pallet_ranked_collective::Call::promote_member { .. } |
+					pallet_ranked_collective::Call::demote_member { .. } |
+					pallet_ranked_collective::Call::remove_member { .. },
 			)
 		)
 	}
 }
 
-pub type Barrier = TrailingSetTopicAsId<
-	DenyThenTry<
-		DenyReserveTransferToRelayChain,
-		(
-			// Allow local users to buy weight credit.
-			TakeWeightCredit,
-			// Expected responses are OK.
-			AllowKnownQueryResponses<PolkadotXcm>,
-			// Allow XCMs with some computed origins to pass through.
-			WithComputedOrigin<
-				(
-					// If the message is one that immediately attemps to pay for execution, then
-					// allow it.
-					AllowTopLevelPaidExecutionFrom<Everything>,
-					// Parent and its pluralities (i.e. governance bodies) get free execution.
-					AllowExplicitUnpaidExecutionFrom<ParentOrParentsPlurality>,
-					// Subscriptions for version tracking are OK.
-					AllowSubscriptionsFrom<ParentOrSiblings>,
-				),
-				UniversalLocation,
-				ConstU32<8>,
-			>,
-		),
-	>,
+pub type Barrier = SystemParachainBarrier<
+	PolkadotXcm,
+	UniversalLocation,
+	// Parent and its pluralities (i.e. governance bodies) get free execution.
+	ParentOrParentsPlurality,
+	// Subscriptions for version tracking are OK.
+	ParentOrSiblings,
 >;
 
 pub struct XcmConfig;
@@ -255,7 +274,7 @@ impl xcm_executor::Config for XcmConfig {
 	type MessageExporter = ();
 	type UniversalAliases = Nothing;
 	type CallDispatcher = WithOriginFilter<SafeCallFilter>;
-	type SafeCallFilter = SafeCallFilter;
+	type SafeCallFilter = parachains_common::session_keys::NoSetKeys<Runtime, SafeCallFilter>;
 	type Aliasers = Nothing;
 }
 
@@ -280,10 +299,21 @@ parameter_types! {
 /// Type to convert the Fellows origin to a Plurality `MultiLocation` value.
 pub type FellowsToPlurality = OriginToPluralityVoice<RuntimeOrigin, Fellows, FellowsBodyId>;
 
+/// Type to convert the Head Ambassadors origin to a Plurality `MultiLocation` value.
+pub type HeadAmbassadorsToPlurality =
+	OriginToPluralityVoice<RuntimeOrigin, HeadAmbassadors, HeadAmbassadorsBodyId>;
+
+/// Type to convert the Secretaries origin to a Plurality `MultiLocation` value.
+pub type SecretariesToPlurality =
+	OriginToPluralityVoice<RuntimeOrigin, Secretaries, SecretariesBodyId>;
+
 impl pallet_xcm::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	// We only allow the Fellows to send messages.
-	type SendXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, FellowsToPlurality>;
+	// We only allow the Fellows, the Head Ambassadors and the Secretaries to send messages.
+	type SendXcmOrigin = EnsureXcmOrigin<
+		RuntimeOrigin,
+		(FellowsToPlurality, HeadAmbassadorsToPlurality, SecretariesToPlurality),
+	>;
 	type XcmRouter = XcmRouter;
 	// We support local origins dispatching XCM executions in principle...
 	type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
@@ -1,6 +1,6 @@
 use crate::{
-	constants::currency::deposit, Balance, Balances, RandomnessCollectiveFlip, Runtime,
-	RuntimeCall, RuntimeEvent, RuntimeHoldReason, Timestamp,
+	constants::currency::deposit, xcm_extension::XcmExtension, Balance, Balances,
+	RandomnessCollectiveFlip, Runtime, RuntimeCall, RuntimeEvent, RuntimeHoldReason, Timestamp,
 };
 use frame_support::{
 	parameter_types,
@@ -45,7 +45,8 @@ impl Config for Runtime {
 	type DefaultDepositLimit = DefaultDepositLimit;
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type WeightInfo = SubstrateWeight<Self>;
-	type ChainExtension = ();
+	/// Lets contracts execute and send XCM messages from their own account.
+	type ChainExtension = XcmExtension;
 	type Schedule = MySchedule;
 	type CallStack = [Frame<Self>; 5];
 	type AddressGenerator = DefaultAddressGenerator;
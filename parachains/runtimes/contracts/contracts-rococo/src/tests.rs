@@ -0,0 +1,112 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use codec::Encode;
+use xcm::latest::prelude::*;
+use xcm_config::UnitWeightCost;
+use xcm_extension::{contract_location, execute, send, weigh};
+
+const CONTRACT: [u8; 32] = [1; 32];
+const BOB: [u8; 32] = [2; 32];
+const INITIAL_BALANCE: Balance = 100 * UNITS;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![(CONTRACT.into(), INITIAL_BALANCE)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	storage.into()
+}
+
+/// Transfers `amount` of the native asset from the origin to the `beneficiary`.
+fn transfer(amount: Balance, beneficiary: [u8; 32]) -> Xcm<RuntimeCall> {
+	Xcm(vec![
+		WithdrawAsset((Parent, amount).into()),
+		DepositAsset {
+			assets: AllCounted(1).into(),
+			beneficiary: AccountId32 { network: None, id: beneficiary }.into(),
+		},
+	])
+}
+
+#[test]
+fn contract_messages_originate_from_the_contract_account() {
+	new_test_ext().execute_with(|| {
+		let origin = contract_location(CONTRACT.into()).unwrap();
+		assert_eq!(origin, AccountId32 { network: None, id: CONTRACT }.into());
+
+		let mut message = transfer(UNITS, BOB);
+		let weight = weigh(&mut message).unwrap();
+		// the message doesn't buy its execution, the contract has paid for it
+		assert_eq!(execute(origin, message, weight), Outcome::Complete(weight));
+
+		assert_eq!(Balances::free_balance(AccountId::from(CONTRACT)), INITIAL_BALANCE - UNITS);
+		assert_eq!(Balances::free_balance(AccountId::from(BOB)), UNITS);
+	});
+}
+
+#[test]
+fn contract_messages_cannot_transact() {
+	new_test_ext().execute_with(|| {
+		let origin = contract_location(CONTRACT.into()).unwrap();
+		let call = RuntimeCall::System(frame_system::Call::remark_with_event { remark: vec![1] });
+		let mut message = Xcm(vec![Transact {
+			origin_kind: OriginKind::SovereignAccount,
+			require_weight_at_most: Weight::from_parts(1_000_000_000, 64 * 1024),
+			call: call.encode().into(),
+		}]);
+		let weight = weigh(&mut message).unwrap();
+
+		let outcome = execute(origin, message, weight);
+		assert!(matches!(outcome, Outcome::Incomplete(_, XcmError::NoPermission)), "{:?}", outcome);
+	});
+}
+
+#[test]
+fn unused_weight_of_contract_messages_is_refunded() {
+	new_test_ext().execute_with(|| {
+		let origin = contract_location(CONTRACT.into()).unwrap();
+		let mut message = transfer(2 * INITIAL_BALANCE, BOB);
+		message.0.push(ClearOrigin);
+		let weight = weigh(&mut message).unwrap();
+		assert_eq!(weight, UnitWeightCost::get() * 3);
+
+		// the withdrawal fails, so only the weight of the first instruction is used and the
+		// extension refunds the rest to the contract
+		let outcome = execute(origin, message, weight);
+		assert!(matches!(outcome, Outcome::Incomplete(..)), "{:?}", outcome);
+		assert_eq!(outcome.weight_used(), UnitWeightCost::get());
+		assert_eq!(Balances::free_balance(AccountId::from(CONTRACT)), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn contract_messages_to_unroutable_destinations_are_not_sent() {
+	new_test_ext().execute_with(|| {
+		let origin = contract_location(CONTRACT.into()).unwrap();
+		let dest = MultiLocation::new(2, X1(GlobalConsensus(NetworkId::Polkadot)));
+
+		let result = send(origin, dest, Xcm(vec![ClearOrigin]));
+		assert!(
+			matches!(result, Err(SendError::NotApplicable | SendError::Unroutable)),
+			"{:?}",
+			result
+		);
+	});
+}
@@ -24,16 +24,14 @@ use frame_support::{
 };
 use frame_system::EnsureRoot;
 use pallet_xcm::{EnsureXcm, IsMajorityOfBody, XcmPassthrough};
+use parachains_common::xcm_config::SystemParachainBarrier;
 use polkadot_parachain::primitives::Sibling;
 use xcm::latest::prelude::*;
 use xcm_builder::{
-	AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses,
-	AllowSubscriptionsFrom, AllowTopLevelPaidExecutionFrom, CurrencyAdapter,
-	DenyReserveTransferToRelayChain, DenyThenTry, EnsureXcmOrigin, FixedWeightBounds, IsConcrete,
+	AccountId32Aliases, CurrencyAdapter, EnsureXcmOrigin, FixedWeightBounds, IsConcrete,
 	NativeAsset, ParentAsSuperuser, ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative,
 	SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
-	SovereignSignedViaLocation, TakeWeightCredit, TrailingSetTopicAsId, UsingComponents,
-	WithComputedOrigin, WithUniqueTopic,
+	SovereignSignedViaLocation, UsingComponents, WithUniqueTopic,
 };
 use xcm_executor::XcmExecutor;
 
@@ -119,29 +117,13 @@ match_types! {
 	};
 }
 
-pub type Barrier = TrailingSetTopicAsId<
-	DenyThenTry<
-		DenyReserveTransferToRelayChain,
-		(
-			TakeWeightCredit,
-			// Expected responses are OK.
-			AllowKnownQueryResponses<PolkadotXcm>,
-			// Allow XCMs with some computed origins to pass through.
-			WithComputedOrigin<
-				(
-					// If the message is one that immediately attemps to pay for execution, then
-					// allow it.
-					AllowTopLevelPaidExecutionFrom<Everything>,
-					// Parent and its pluralities (i.e. governance bodies) get free execution.
-					AllowExplicitUnpaidExecutionFrom<ParentOrParentsPlurality>,
-					// Subscriptions for version tracking are OK.
-					AllowSubscriptionsFrom<ParentOrSiblings>,
-				),
-				UniversalLocation,
-				ConstU32<8>,
-			>,
-		),
-	>,
+pub type Barrier = SystemParachainBarrier<
+	PolkadotXcm,
+	UniversalLocation,
+	// Parent and its pluralities (i.e. governance bodies) get free execution.
+	ParentOrParentsPlurality,
+	// Subscriptions for version tracking are OK.
+	ParentOrSiblings,
 >;
 
 pub struct XcmConfig;
@@ -168,10 +150,44 @@ impl xcm_executor::Config for XcmConfig {
 	type MessageExporter = ();
 	type UniversalAliases = Nothing;
 	type CallDispatcher = RuntimeCall;
-	type SafeCallFilter = Everything;
+	type SafeCallFilter = parachains_common::session_keys::NoSetKeys<Runtime, Everything>;
 	type Aliasers = Nothing;
 }
 
+/// The configuration, that contracts execute their XCM messages with.
+///
+/// Matches the [`XcmConfig`], except that `Transact` is not allowed to dispatch any call. The
+/// contracts are not permitted to dispatch any call themselves, see
+/// [`CallFilter`](pallet_contracts::Config::CallFilter), which must not be circumvented through
+/// XCM.
+pub struct ContractsXcmConfig;
+impl xcm_executor::Config for ContractsXcmConfig {
+	type RuntimeCall = RuntimeCall;
+	type XcmSender = <XcmConfig as xcm_executor::Config>::XcmSender;
+	type AssetTransactor = <XcmConfig as xcm_executor::Config>::AssetTransactor;
+	type OriginConverter = <XcmConfig as xcm_executor::Config>::OriginConverter;
+	type IsReserve = <XcmConfig as xcm_executor::Config>::IsReserve;
+	type IsTeleporter = <XcmConfig as xcm_executor::Config>::IsTeleporter;
+	type UniversalLocation = <XcmConfig as xcm_executor::Config>::UniversalLocation;
+	type Barrier = <XcmConfig as xcm_executor::Config>::Barrier;
+	type Weigher = <XcmConfig as xcm_executor::Config>::Weigher;
+	type Trader = <XcmConfig as xcm_executor::Config>::Trader;
+	type ResponseHandler = <XcmConfig as xcm_executor::Config>::ResponseHandler;
+	type AssetTrap = <XcmConfig as xcm_executor::Config>::AssetTrap;
+	type AssetClaims = <XcmConfig as xcm_executor::Config>::AssetClaims;
+	type SubscriptionService = <XcmConfig as xcm_executor::Config>::SubscriptionService;
+	type PalletInstancesInfo = <XcmConfig as xcm_executor::Config>::PalletInstancesInfo;
+	type MaxAssetsIntoHolding = <XcmConfig as xcm_executor::Config>::MaxAssetsIntoHolding;
+	type AssetLocker = <XcmConfig as xcm_executor::Config>::AssetLocker;
+	type AssetExchanger = <XcmConfig as xcm_executor::Config>::AssetExchanger;
+	type FeeManager = <XcmConfig as xcm_executor::Config>::FeeManager;
+	type MessageExporter = <XcmConfig as xcm_executor::Config>::MessageExporter;
+	type UniversalAliases = <XcmConfig as xcm_executor::Config>::UniversalAliases;
+	type CallDispatcher = RuntimeCall;
+	type SafeCallFilter = Nothing;
+	type Aliasers = <XcmConfig as xcm_executor::Config>::Aliasers;
+}
+
 /// Converts a local signed origin into an XCM multilocation.
 /// Forms the basis for local origins sending/executing XCMs.
 pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, RelayNetwork>;
@@ -241,6 +257,12 @@ impl cumulus_pallet_xcmp_queue::Config for Runtime {
 	type ControllerOriginConverter = XcmOriginToTransactDispatchOrigin;
 	type WeightInfo = cumulus_pallet_xcmp_queue::weights::SubstrateWeight<Runtime>;
 	type PriceForSiblingDelivery = ();
+	type XcmDeferFilter = ();
+	type OutboundChannelPriority = ();
+	type XcmRateLimiter = ();
+	type RelayChainBlockNumberProvider =
+		cumulus_pallet_parachain_system::RelaychainDataProvider<Runtime>;
+	type MaxInboundPageSize = ConstU32<{ 64 * 1024 }>;
 }
 
 impl cumulus_pallet_dmp_queue::Config for Runtime {
@@ -0,0 +1,169 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Chain extension allowing contracts to execute and send XCM messages.
+//!
+//! The messages always run with the calling contract's own account as their origin, so a contract
+//! can only ever spend or move what it owns itself, never anything belonging to its caller.
+//! Executed messages run under [`ContractsXcmConfig`], so `Transact` cannot dispatch any call.
+
+use crate::{
+	xcm_config::{ContractsXcmConfig, LocalOriginToLocation},
+	AccountId, Runtime, RuntimeCall, RuntimeOrigin,
+};
+use codec::{DecodeLimit, Encode};
+use pallet_contracts::chain_extension::{ChainExtension, Environment, Ext, InitState, RetVal};
+use pallet_xcm::WeightInfo as _;
+use sp_core::hashing::blake2_256;
+use sp_runtime::{traits::TryConvert, DispatchError};
+use xcm::{prelude::*, VersionedMultiLocation, VersionedXcm, MAX_XCM_DECODE_DEPTH};
+use xcm_executor::{traits::WeightBounds, XcmExecutor};
+
+const LOG_TARGET: &str = "runtime::contracts::xcm";
+
+/// Executes a `VersionedXcm<RuntimeCall>` locally. The encoded `Outcome` is written to the
+/// output buffer.
+pub const XCM_EXECUTE: u16 = 0;
+/// Sends a `(VersionedMultiLocation, VersionedXcm<()>)` pair to its destination. The `XcmHash`
+/// of the sent message is written to the output buffer.
+pub const XCM_SEND: u16 = 1;
+
+/// Status codes returned to the contract by [`XcmExtension`].
+#[repr(u32)]
+pub enum ReturnCode {
+	/// The message was executed completely or sent successfully.
+	Success = 0,
+	/// The message was executed, but did not complete. The outcome holds the details.
+	ExecutionIncomplete = 1,
+	/// The message could not be delivered to its destination.
+	SendFailed = 2,
+}
+
+impl From<ReturnCode> for RetVal {
+	fn from(code: ReturnCode) -> Self {
+		RetVal::Converging(code as u32)
+	}
+}
+
+/// Exposes [`XCM_EXECUTE`] and [`XCM_SEND`] to contracts.
+///
+/// Execution is metered with the XCM executor's weigher: the full weight of the message is charged
+/// upfront and any unused weight is refunded afterwards. As the contract pays for the execution,
+/// the charged weight is credited to the message, like `pallet_xcm::execute` does.
+#[derive(Default)]
+pub struct XcmExtension;
+
+impl ChainExtension<Runtime> for XcmExtension {
+	fn call<E: Ext<T = Runtime>>(
+		&mut self,
+		env: Environment<E, InitState>,
+	) -> Result<RetVal, DispatchError> {
+		let mut env = env.buf_in_buf_out();
+		let origin = contract_location(env.ext().address().clone())?;
+
+		match env.func_id() {
+			XCM_EXECUTE => {
+				let input = env.read(env.in_len())?;
+				let mut message: Xcm<RuntimeCall> =
+					VersionedXcm::<RuntimeCall>::decode_with_depth_limit(
+						MAX_XCM_DECODE_DEPTH,
+						&mut &input[..],
+					)
+					.map_err(|_| DispatchError::Other("Invalid XCM message"))?
+					.try_into()
+					.map_err(|()| DispatchError::Other("Unsupported XCM version"))?;
+
+				let weight = weigh(&mut message)?;
+				let charged = env.charge_weight(weight)?;
+
+				let outcome = execute(origin, message, weight);
+				env.adjust_weight(charged, outcome.weight_used());
+				log::debug!(target: LOG_TARGET, "Contract XCM execution outcome: {:?}", outcome);
+
+				env.write(&outcome.encode(), false, None)?;
+				Ok(match outcome {
+					Outcome::Complete(_) => ReturnCode::Success,
+					_ => ReturnCode::ExecutionIncomplete,
+				}
+				.into())
+			},
+			XCM_SEND => {
+				let input = env.read(env.in_len())?;
+				let (dest, message) =
+					<(VersionedMultiLocation, VersionedXcm<()>)>::decode_with_depth_limit(
+						MAX_XCM_DECODE_DEPTH,
+						&mut &input[..],
+					)
+					.map_err(|_| DispatchError::Other("Invalid XCM message"))?;
+				let dest: MultiLocation = dest
+					.try_into()
+					.map_err(|()| DispatchError::Other("Unsupported XCM version"))?;
+				let message: Xcm<()> = message
+					.try_into()
+					.map_err(|()| DispatchError::Other("Unsupported XCM version"))?;
+
+				env.charge_weight(<Runtime as pallet_xcm::Config>::WeightInfo::send())?;
+
+				match send(origin, dest, message) {
+					Ok(hash) => {
+						env.write(&hash.encode(), false, None)?;
+						Ok(ReturnCode::Success.into())
+					},
+					Err(error) => {
+						log::debug!(target: LOG_TARGET, "Contract XCM send failed: {:?}", error);
+						Ok(ReturnCode::SendFailed.into())
+					},
+				}
+			},
+			id => {
+				log::error!(target: LOG_TARGET, "Called an unregistered `func_id`: {:?}", id);
+				Err(DispatchError::Other("Unimplemented func_id"))
+			},
+		}
+	}
+}
+
+/// Returns the weight of the `message`, that is charged upfront.
+pub(crate) fn weigh(message: &mut Xcm<RuntimeCall>) -> Result<Weight, DispatchError> {
+	<ContractsXcmConfig as xcm_executor::Config>::Weigher::weight(message)
+		.map_err(|()| DispatchError::Other("Unweighable XCM message"))
+}
+
+/// Executes the `message` from the `origin` of a contract.
+///
+/// The `weight` has been charged from the contract already, so it is credited to the message.
+/// The weight used by the message is returned in the outcome.
+pub(crate) fn execute(origin: MultiLocation, message: Xcm<RuntimeCall>, weight: Weight) -> Outcome {
+	let mut hash = message.using_encoded(blake2_256);
+	XcmExecutor::<ContractsXcmConfig>::prepare_and_execute(
+		origin, message, &mut hash, weight, weight,
+	)
+}
+
+/// Sends the `message` from the `origin` of a contract to the `dest`.
+pub(crate) fn send(
+	origin: MultiLocation,
+	dest: MultiLocation,
+	message: Xcm<()>,
+) -> Result<XcmHash, SendError> {
+	pallet_xcm::Pallet::<Runtime>::send_xcm(origin.interior, dest, message)
+}
+
+/// The location a contract's messages originate from: its own account on this chain.
+pub(crate) fn contract_location(account: AccountId) -> Result<MultiLocation, DispatchError> {
+	LocalOriginToLocation::try_convert(RuntimeOrigin::signed(account))
+		.map_err(|_| DispatchError::BadOrigin)
+}
@@ -0,0 +1,370 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scheduled consumption curves for the Glutton pallet.
+//!
+//! Instead of a single static ratio, the compute and storage consumption of the Glutton pallet can
+//! follow a [`Curve`] configured by the `ScheduleOrigin`. At the start of every block the value of
+//! each active curve is computed and handed over to the Glutton pallet, which then wastes the
+//! resources accordingly in its `on_idle` hook.
+
+pub use pallet_glutton_schedule::*;
+
+#[frame_support::pallet]
+pub mod pallet_glutton_schedule {
+	use frame_support::pallet_prelude::*;
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use pallet_glutton::WeightInfo as _;
+	use sp_runtime::{
+		traits::{One, SaturatedConversion, Saturating, Zero},
+		FixedPointNumber, FixedU64,
+	};
+
+	const LOG_TARGET: &str = "runtime::glutton-schedule";
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_glutton::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Origin allowed to schedule the curves.
+		type ScheduleOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The resource that a curve controls.
+	#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+	pub enum Resource {
+		/// The compute ratio, as set by `pallet_glutton::set_compute`.
+		Compute,
+		/// The storage ratio, as set by `pallet_glutton::set_storage`.
+		Storage,
+	}
+
+	/// The shape of the consumption over time.
+	#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub enum Curve<BlockNumber> {
+		/// Goes linearly from `from` to `to` within `length` blocks and stays at `to` afterwards.
+		Ramp { from: FixedU64, to: FixedU64, length: BlockNumber },
+		/// Goes linearly from `min` to `max` within `period` blocks and then starts over at `min`.
+		Sawtooth { min: FixedU64, max: FixedU64, period: BlockNumber },
+		/// Starts at `min` and moves up or down by `step` every block, staying within `min..=max`.
+		///
+		/// The direction is derived from the parent block hash.
+		RandomWalk { min: FixedU64, max: FixedU64, step: FixedU64 },
+	}
+
+	/// A curve that is being followed.
+	#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct ScheduledCurve<BlockNumber> {
+		/// The followed curve.
+		pub curve: Curve<BlockNumber>,
+		/// The block at which the curve starts.
+		pub start: BlockNumber,
+		/// The value set for the latest block.
+		pub last: FixedU64,
+	}
+
+	/// The curves followed by the resources.
+	#[pallet::storage]
+	pub type Schedules<T: Config> =
+		StorageMap<_, Twox64Concat, Resource, ScheduledCurve<BlockNumberFor<T>>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The resource follows the curve, starting at block `start`.
+		CurveScheduled {
+			resource: Resource,
+			curve: Curve<BlockNumberFor<T>>,
+			start: BlockNumberFor<T>,
+		},
+		/// The resource no longer follows a curve and stays at its latest value.
+		CurveCleared { resource: Resource },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The curve has a zero length or period.
+		ZeroLength,
+		/// The minimum of the curve is above its maximum.
+		InvalidBounds,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let mut weight = T::DbWeight::get().reads(2);
+			for resource in [Resource::Compute, Resource::Storage] {
+				let Some(mut schedule) = Schedules::<T>::get(resource) else { continue };
+				if now < schedule.start {
+					continue
+				}
+
+				schedule.last = Self::value_at(&schedule, now);
+				let origin = RawOrigin::Root.into();
+				let (result, call_weight) = match resource {
+					Resource::Compute => (
+						pallet_glutton::Pallet::<T>::set_compute(origin, schedule.last),
+						<T as pallet_glutton::Config>::WeightInfo::set_compute(),
+					),
+					Resource::Storage => (
+						pallet_glutton::Pallet::<T>::set_storage(origin, schedule.last),
+						<T as pallet_glutton::Config>::WeightInfo::set_storage(),
+					),
+				};
+				if let Err(error) = result {
+					log::warn!(
+						target: LOG_TARGET,
+						"Failed to set {:?} to {:?}: {:?}",
+						resource,
+						schedule.last,
+						error,
+					);
+				}
+
+				Schedules::<T>::insert(resource, schedule);
+				weight.saturating_accrue(call_weight.saturating_add(T::DbWeight::get().writes(1)));
+			}
+			weight
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Make `resource` follow `curve` from the next block on, or stop following any curve if
+		/// `curve` is `None`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_curve(
+			origin: OriginFor<T>,
+			resource: Resource,
+			curve: Option<Curve<BlockNumberFor<T>>>,
+		) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin)?;
+
+			let Some(curve) = curve else {
+				Schedules::<T>::remove(resource);
+				Self::deposit_event(Event::CurveCleared { resource });
+				return Ok(())
+			};
+
+			let (min, max, length) = match curve {
+				Curve::Ramp { length, .. } => (FixedU64::zero(), FixedU64::zero(), length),
+				Curve::Sawtooth { min, max, period } => (min, max, period),
+				Curve::RandomWalk { min, max, .. } => (min, max, One::one()),
+			};
+			ensure!(!length.is_zero(), Error::<T>::ZeroLength);
+			ensure!(min <= max, Error::<T>::InvalidBounds);
+
+			let start = frame_system::Pallet::<T>::block_number().saturating_add(One::one());
+			let last = match curve {
+				Curve::Ramp { from, .. } => from,
+				Curve::Sawtooth { min, .. } | Curve::RandomWalk { min, .. } => min,
+			};
+			Schedules::<T>::insert(resource, ScheduledCurve { curve: curve.clone(), start, last });
+			Self::deposit_event(Event::CurveScheduled { resource, curve, start });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The value of the scheduled curve at block `now`.
+		fn value_at(
+			schedule: &ScheduledCurve<BlockNumberFor<T>>,
+			now: BlockNumberFor<T>,
+		) -> FixedU64 {
+			let elapsed: u128 = now.saturating_sub(schedule.start).saturated_into();
+			match schedule.curve {
+				Curve::Ramp { from, to, length } => {
+					let length: u128 = length.saturated_into();
+					interpolate(from, to, FixedU64::from_rational(elapsed.min(length), length))
+				},
+				Curve::Sawtooth { min, max, period } => {
+					let period: u128 = period.saturated_into();
+					interpolate(min, max, FixedU64::from_rational(elapsed % period, period))
+				},
+				Curve::RandomWalk { min, max, step } => {
+					if elapsed == 0 {
+						return min
+					}
+					let parent_hash = frame_system::Pallet::<T>::parent_hash();
+					if parent_hash.as_ref()[0] % 2 == 0 {
+						schedule.last.saturating_add(step).min(max)
+					} else {
+						schedule.last.saturating_sub(step).max(min)
+					}
+				},
+			}
+		}
+	}
+
+	/// The value at `ratio` of the way from `from` to `to`.
+	fn interpolate(from: FixedU64, to: FixedU64, ratio: FixedU64) -> FixedU64 {
+		if to >= from {
+			from.saturating_add(to.saturating_sub(from).saturating_mul(ratio))
+		} else {
+			from.saturating_sub(from.saturating_sub(to).saturating_mul(ratio))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{BuildStorage, GluttonSchedule, Runtime, RuntimeEvent, RuntimeOrigin, System};
+	use frame_support::{assert_noop, assert_ok, traits::Hooks};
+	use sp_runtime::{DispatchError, FixedU64};
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::<Runtime>::default()
+			.build_storage()
+			.unwrap()
+			.into();
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn ratio(percent: u128) -> FixedU64 {
+		FixedU64::from_rational(percent, 100)
+	}
+
+	/// Initializes the block `n` and returns the compute ratio set for it.
+	fn compute_at(n: u32) -> FixedU64 {
+		System::set_block_number(n);
+		GluttonSchedule::on_initialize(n);
+		System::assert_last_event(RuntimeEvent::Glutton(
+			pallet_glutton::Event::ComputationLimitSet {
+				compute: Schedules::<Runtime>::get(Resource::Compute).unwrap().last,
+			},
+		));
+		Schedules::<Runtime>::get(Resource::Compute).unwrap().last
+	}
+
+	#[test]
+	fn set_curve_checks_the_curve() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				GluttonSchedule::set_curve(
+					RuntimeOrigin::signed([1; 32].into()),
+					Resource::Compute,
+					None
+				),
+				DispatchError::BadOrigin,
+			);
+			assert_noop!(
+				GluttonSchedule::set_curve(
+					RuntimeOrigin::root(),
+					Resource::Compute,
+					Some(Curve::Ramp { from: ratio(0), to: ratio(100), length: 0 }),
+				),
+				Error::<Runtime>::ZeroLength,
+			);
+			assert_noop!(
+				GluttonSchedule::set_curve(
+					RuntimeOrigin::root(),
+					Resource::Storage,
+					Some(Curve::Sawtooth { min: ratio(60), max: ratio(20), period: 4 }),
+				),
+				Error::<Runtime>::InvalidBounds,
+			);
+		});
+	}
+
+	#[test]
+	fn ramp_is_followed_from_the_next_block() {
+		new_test_ext().execute_with(|| {
+			let curve = Curve::Ramp { from: ratio(0), to: ratio(100), length: 4 };
+			assert_ok!(GluttonSchedule::set_curve(
+				RuntimeOrigin::root(),
+				Resource::Compute,
+				Some(curve.clone())
+			));
+			System::assert_last_event(RuntimeEvent::GluttonSchedule(Event::CurveScheduled {
+				resource: Resource::Compute,
+				curve,
+				start: 2,
+			}));
+
+			assert_eq!(compute_at(2), ratio(0));
+			assert_eq!(compute_at(4), ratio(50));
+			assert_eq!(compute_at(6), ratio(100));
+			// the ramp holds at its end
+			assert_eq!(compute_at(10), ratio(100));
+		});
+	}
+
+	#[test]
+	fn sawtooth_starts_over_every_period() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(GluttonSchedule::set_curve(
+				RuntimeOrigin::root(),
+				Resource::Compute,
+				Some(Curve::Sawtooth { min: ratio(20), max: ratio(60), period: 4 }),
+			));
+
+			assert_eq!(compute_at(2), ratio(20));
+			assert_eq!(compute_at(3), ratio(30));
+			assert_eq!(compute_at(5), ratio(50));
+			assert_eq!(compute_at(6), ratio(20));
+		});
+	}
+
+	#[test]
+	fn random_walk_stays_within_bounds() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(GluttonSchedule::set_curve(
+				RuntimeOrigin::root(),
+				Resource::Compute,
+				Some(Curve::RandomWalk { min: ratio(20), max: ratio(40), step: ratio(15) }),
+			));
+			assert_eq!(compute_at(2), ratio(20));
+
+			// even parent hashes move the value up
+			frame_system::ParentHash::<Runtime>::put(sp_core::H256::repeat_byte(2));
+			assert_eq!(compute_at(3), ratio(35));
+			assert_eq!(compute_at(4), ratio(40));
+
+			// odd parent hashes move the value down
+			frame_system::ParentHash::<Runtime>::put(sp_core::H256::repeat_byte(1));
+			assert_eq!(compute_at(5), ratio(25));
+			assert_eq!(compute_at(6), ratio(20));
+		});
+	}
+
+	#[test]
+	fn cleared_curve_is_no_longer_followed() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(GluttonSchedule::set_curve(
+				RuntimeOrigin::root(),
+				Resource::Storage,
+				Some(Curve::Ramp { from: ratio(10), to: ratio(50), length: 2 }),
+			));
+			assert_ok!(GluttonSchedule::set_curve(RuntimeOrigin::root(), Resource::Storage, None));
+			System::assert_last_event(RuntimeEvent::GluttonSchedule(Event::CurveCleared {
+				resource: Resource::Storage,
+			}));
+
+			System::reset_events();
+			System::set_block_number(2);
+			GluttonSchedule::on_initialize(2);
+
+			assert_eq!(Schedules::<Runtime>::get(Resource::Storage), None);
+			assert!(System::events().is_empty());
+		});
+	}
+}
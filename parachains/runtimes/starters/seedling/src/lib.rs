@@ -18,6 +18,9 @@
 //!
 //! Seedling is a parachain meant to help parachain auction winners migrate a blockchain from
 //! another consensus system into the consensus system of a given Relay Chain.
+//!
+//! Downward messages from the Relay Chain are executed with the Root origin, so a relay-origin
+//! `Transact` can upgrade the runtime, as can the sudo key set at genesis.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 // `construct_runtime!` does a lot of recursion and requires us to increase the limit to 256.
@@ -27,6 +30,11 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+pub mod xcm_config;
+
+#[cfg(test)]
+mod tests;
+
 use cumulus_pallet_parachain_system::RelayNumberStrictlyIncreases;
 use sp_api::impl_runtime_apis;
 use sp_core::OpaqueMetadata;
@@ -67,7 +75,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("seedling"),
 	impl_name: create_runtime_str!("seedling"),
 	authoring_version: 1,
-	spec_version: 10000,
+	spec_version: 10001,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 2,
@@ -163,18 +171,29 @@ impl pallet_sudo::Config for Runtime {
 
 impl cumulus_pallet_solo_to_para::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = cumulus_pallet_solo_to_para::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	// We do anything the parent chain tells us in this runtime.
+	pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(2);
 }
 
 impl cumulus_pallet_parachain_system::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type OnSystemEvent = cumulus_pallet_solo_to_para::Pallet<Runtime>;
+	type RelayChainGenesisHashStore = ParachainInfo;
 	type SelfParaId = parachain_info::Pallet<Runtime>;
 	type OutboundXcmpMessageSource = ();
-	type DmpMessageHandler = ();
-	type ReservedDmpWeight = ();
+	type PriorityUpwardMessageSource = ();
+	type DmpMessageHandler = cumulus_pallet_xcm::UnlimitedDmpExecution<Runtime>;
+	type ReservedDmpWeight = ReservedDmpWeight;
 	type XcmpMessageHandler = ();
 	type ReservedXcmpWeight = ();
+	type PovSizeWatermark = frame_support::traits::ConstU64<{ u64::MAX }>;
 	type CheckAssociatedRelayNumber = RelayNumberStrictlyIncreases;
+	type CheckRelayTimestamp = ();
+	type AuthorshipProofVerifier = ();
 	type ConsensusHook = cumulus_pallet_parachain_system::consensus_hook::ExpectParentIncluded;
 }
 
@@ -191,6 +210,9 @@ construct_runtime! {
 		},
 		ParachainInfo: parachain_info::{Pallet, Storage, Config<T>},
 		SoloToPara: cumulus_pallet_solo_to_para::{Pallet, Call, Storage, Event},
+
+		// DMP handler.
+		CumulusXcm: cumulus_pallet_xcm::{Pallet, Call, Storage, Event<T>, Origin},
 	}
 }
 
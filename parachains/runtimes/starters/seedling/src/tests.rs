@@ -0,0 +1,74 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use codec::Encode;
+use xcm::latest::prelude::*;
+use xcm_executor::XcmExecutor;
+
+const KEY: &[u8] = b"seedling-test-key";
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	frame_system::GenesisConfig::<Runtime>::default()
+		.build_storage()
+		.unwrap()
+		.into()
+}
+
+/// A `Transact` with the Root origin, that sets the [`KEY`].
+fn root_transact() -> Xcm<RuntimeCall> {
+	let call = RuntimeCall::System(frame_system::Call::set_storage {
+		items: vec![(KEY.to_vec(), b"value".to_vec())],
+	});
+	Xcm(vec![
+		UnpaidExecution { weight_limit: Unlimited, check_origin: None },
+		Transact {
+			origin_kind: OriginKind::Superuser,
+			require_weight_at_most: Weight::from_parts(1_000_000_000, 64 * 1024),
+			call: call.encode().into(),
+		},
+	])
+}
+
+#[test]
+fn relay_chain_transacts_with_the_root_origin() {
+	new_test_ext().execute_with(|| {
+		let outcome = XcmExecutor::<xcm_config::XcmConfig>::execute_xcm(
+			Parent,
+			root_transact(),
+			[0; 32],
+			Weight::MAX,
+		);
+
+		assert!(matches!(outcome, Outcome::Complete(_)), "{:?}", outcome);
+		assert_eq!(sp_io::storage::get(KEY).as_deref(), Some(&b"value"[..]));
+	});
+}
+
+#[test]
+fn only_the_relay_chain_may_transact() {
+	new_test_ext().execute_with(|| {
+		let outcome = XcmExecutor::<xcm_config::XcmConfig>::execute_xcm(
+			(Parent, Parachain(1000)),
+			root_transact(),
+			[0; 32],
+			Weight::MAX,
+		);
+
+		assert_eq!(outcome, Outcome::Error(XcmError::Barrier));
+		assert_eq!(sp_io::storage::get(KEY), None);
+	});
+}
@@ -0,0 +1,74 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{assert_ok, dispatch::GetDispatchInfo};
+use sp_runtime::{
+	traits::SignedExtension,
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	AccountId32,
+};
+
+const SUDO: AccountId32 = AccountId32::new([1; 32]);
+const OTHER: AccountId32 = AccountId32::new([2; 32]);
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	RuntimeGenesisConfig { sudo: SudoConfig { key: Some(SUDO) }, ..Default::default() }
+		.build_storage()
+		.unwrap()
+		.into()
+}
+
+fn set_code_call() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::set_code_without_checks { code: vec![] })
+}
+
+#[test]
+fn only_the_sudo_key_may_sign() {
+	new_test_ext().execute_with(|| {
+		let call = RuntimeCall::Sudo(pallet_sudo::Call::sudo { call: Box::new(set_code_call()) });
+		let info = call.get_dispatch_info();
+
+		assert_ok!(
+			pallet_sudo::CheckOnlySudoAccount::<Runtime>::new().validate(&SUDO, &call, &info, 0)
+		);
+		assert_eq!(
+			pallet_sudo::CheckOnlySudoAccount::<Runtime>::new().validate(&OTHER, &call, &info, 0),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::BadSigner)),
+		);
+	});
+}
+
+#[test]
+fn sudo_key_may_authorize_an_upgrade() {
+	new_test_ext().execute_with(|| {
+		let code_hash = sp_core::H256::repeat_byte(3);
+		let call = RuntimeCall::ParachainSystem(
+			cumulus_pallet_parachain_system::Call::authorize_upgrade {
+				code_hash,
+				check_version: true,
+			},
+		);
+
+		System::set_block_number(1);
+
+		assert!(Sudo::sudo(RuntimeOrigin::signed(OTHER), Box::new(call.clone())).is_err());
+		assert_ok!(Sudo::sudo(RuntimeOrigin::signed(SUDO), Box::new(call)));
+		System::assert_has_event(RuntimeEvent::ParachainSystem(
+			cumulus_pallet_parachain_system::Event::UpgradeAuthorized { code_hash },
+		));
+	});
+}
@@ -360,10 +360,11 @@ impl<ParachainSystem: cumulus_pallet_parachain_system::Config, AllPalletsWithout
 	RuntimeHelper<ParachainSystem, AllPalletsWithoutSystem>
 {
 	pub fn xcm_max_weight(from: XcmReceivedFrom) -> Weight {
-		use frame_support::traits::Get;
 		match from {
-			XcmReceivedFrom::Parent => ParachainSystem::ReservedDmpWeight::get(),
-			XcmReceivedFrom::Sibling => ParachainSystem::ReservedXcmpWeight::get(),
+			XcmReceivedFrom::Parent =>
+				cumulus_pallet_parachain_system::Pallet::<ParachainSystem>::reserved_dmp_weight(),
+			XcmReceivedFrom::Sibling =>
+				cumulus_pallet_parachain_system::Pallet::<ParachainSystem>::reserved_xcmp_weight(),
 		}
 	}
 }
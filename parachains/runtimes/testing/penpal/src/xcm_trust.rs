@@ -0,0 +1,174 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage-backed XCM trust configuration of the Penpal chain.
+//!
+//! The locations that are trusted as reserves and as teleporters are kept in storage and can be
+//! changed by the `AdminOrigin`, so integration tests and testnets can exercise different trust
+//! topologies without rebuilding the runtime. A location is only ever trusted for the assets it is
+//! the reserve of.
+
+use crate::xcm_config::Reserve;
+use core::marker::PhantomData;
+use frame_support::traits::ContainsPair;
+pub use pallet_xcm_trust::*;
+use xcm::latest::prelude::*;
+
+#[frame_support::pallet]
+pub mod pallet_xcm_trust {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+	use xcm::{latest::prelude::*, VersionedMultiLocation};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Origin allowed to change the trust configuration.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Locations trusted as reserves until the configuration is changed for the first time.
+		type InitialReserveLocations: Get<Vec<MultiLocation>>;
+
+		/// Locations trusted as teleporters until the configuration is changed for the first time.
+		type InitialTeleporters: Get<Vec<MultiLocation>>;
+
+		/// Maximal number of locations in each of the trusted sets.
+		#[pallet::constant]
+		type MaxLocations: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::type_value]
+	pub fn DefaultReserveLocations<T: Config>() -> BoundedVec<MultiLocation, T::MaxLocations> {
+		BoundedVec::truncate_from(T::InitialReserveLocations::get())
+	}
+
+	#[pallet::type_value]
+	pub fn DefaultTeleporters<T: Config>() -> BoundedVec<MultiLocation, T::MaxLocations> {
+		BoundedVec::truncate_from(T::InitialTeleporters::get())
+	}
+
+	/// Locations trusted as reserves of their own assets.
+	#[pallet::storage]
+	pub type ReserveLocations<T: Config> = StorageValue<
+		_,
+		BoundedVec<MultiLocation, T::MaxLocations>,
+		ValueQuery,
+		DefaultReserveLocations<T>,
+	>;
+
+	/// Locations allowed to teleport their own assets to this chain.
+	#[pallet::storage]
+	pub type Teleporters<T: Config> = StorageValue<
+		_,
+		BoundedVec<MultiLocation, T::MaxLocations>,
+		ValueQuery,
+		DefaultTeleporters<T>,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The location is now trusted, or no longer trusted, as a reserve.
+		ReserveLocationSet { location: MultiLocation, trusted: bool },
+		/// The location is now allowed, or no longer allowed, to teleport assets.
+		TeleporterSet { location: MultiLocation, allowed: bool },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The location can't be converted to the latest XCM version.
+		BadVersion,
+		/// The trusted set is already at its maximal size.
+		TooManyLocations,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Trust or distrust `location` as the reserve of its own assets.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn set_reserve_location(
+			origin: OriginFor<T>,
+			location: Box<VersionedMultiLocation>,
+			trusted: bool,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let location: MultiLocation =
+				(*location).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			ReserveLocations::<T>::try_mutate(|locations| update(locations, location, trusted))?;
+			Self::deposit_event(Event::ReserveLocationSet { location, trusted });
+			Ok(())
+		}
+
+		/// Allow or disallow `location` to teleport its own assets to this chain.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn allow_teleporter(
+			origin: OriginFor<T>,
+			location: Box<VersionedMultiLocation>,
+			allowed: bool,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let location: MultiLocation =
+				(*location).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			Teleporters::<T>::try_mutate(|locations| update(locations, location, allowed))?;
+			Self::deposit_event(Event::TeleporterSet { location, allowed });
+			Ok(())
+		}
+	}
+
+	/// Adds `location` to, or removes it from, the `locations` set.
+	fn update<T: Config>(
+		locations: &mut BoundedVec<MultiLocation, T::MaxLocations>,
+		location: MultiLocation,
+		insert: bool,
+	) -> DispatchResult {
+		let position = locations.iter().position(|l| *l == location);
+		match (position, insert) {
+			(None, true) =>
+				locations.try_push(location).map_err(|_| Error::<T>::TooManyLocations)?,
+			(Some(index), false) => {
+				locations.remove(index);
+			},
+			_ => (),
+		}
+		Ok(())
+	}
+}
+
+/// Accepts assets from the origin if it is their reserve and is trusted as a reserve.
+pub struct TrustedReserves<T>(PhantomData<T>);
+impl<T: Config> ContainsPair<MultiAsset, MultiLocation> for TrustedReserves<T> {
+	fn contains(asset: &MultiAsset, origin: &MultiLocation) -> bool {
+		asset.reserve().as_ref() == Some(origin) && ReserveLocations::<T>::get().contains(origin)
+	}
+}
+
+/// Accepts teleported assets from the origin if it is their reserve and is an allowed teleporter.
+pub struct TrustedTeleporters<T>(PhantomData<T>);
+impl<T: Config> ContainsPair<MultiAsset, MultiLocation> for TrustedTeleporters<T> {
+	fn contains(asset: &MultiAsset, origin: &MultiLocation) -> bool {
+		asset.reserve().as_ref() == Some(origin) && Teleporters::<T>::get().contains(origin)
+	}
+}
@@ -0,0 +1,235 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Setup of the `benchmark overhead` command for the included runtimes.
+//!
+//! The benchmarked blocks contain the timestamp and the mocked validation data inherents, the
+//! base extrinsic is a `System::remark` signed by `Alice`.
+
+use codec::Encode;
+use cumulus_primitives_core::ParaId;
+use cumulus_primitives_parachain_inherent::{
+	MockValidationDataInherentDataProvider, MockXcmConfig,
+};
+use frame_benchmarking_cli::ExtrinsicBuilder;
+use parachains_common::{AccountId, Hash};
+use sp_inherents::{InherentData, InherentDataProvider};
+use sp_keyring::Sr25519Keyring;
+use sp_runtime::{
+	generic::{Era, SignedPayload, UncheckedExtrinsic},
+	traits::SignedExtension,
+	MultiAddress, MultiSignature, OpaqueExtrinsic,
+};
+
+/// Builds the signed extrinsic of a runtime from the nonce of `Alice` and the genesis hash.
+pub type BuildRemark = fn(nonce: u32, genesis_hash: Hash) -> OpaqueExtrinsic;
+
+/// Builds `System::remark` extrinsics of a runtime, signed by `Alice`.
+pub struct RemarkBuilder {
+	build: BuildRemark,
+	genesis_hash: Hash,
+}
+
+impl RemarkBuilder {
+	/// Creates a new [`Self`] for the chain with the given genesis hash.
+	pub fn new(build: BuildRemark, genesis_hash: Hash) -> Self {
+		Self { build, genesis_hash }
+	}
+}
+
+impl ExtrinsicBuilder for RemarkBuilder {
+	fn pallet(&self) -> &str {
+		"system"
+	}
+
+	fn extrinsic(&self) -> &str {
+		"remark"
+	}
+
+	fn build(&self, nonce: u32) -> std::result::Result<OpaqueExtrinsic, &'static str> {
+		Ok((self.build)(nonce, self.genesis_hash))
+	}
+}
+
+/// The inherent data of the benchmarked blocks of the parachain `para_id`.
+pub fn inherent_benchmark_data(para_id: ParaId) -> sc_cli::Result<InherentData> {
+	let mut inherent_data = InherentData::new();
+
+	// The timestamp falls into slot 0, the slot of the benchmarked blocks, which have no Aura
+	// pre-digest.
+	let timestamp = sp_timestamp::InherentDataProvider::new(0.into());
+	futures::executor::block_on(timestamp.provide_inherent_data(&mut inherent_data))
+		.map_err(|e| format!("creating timestamp inherent data failed: {:?}", e))?;
+
+	let validation_data = MockValidationDataInherentDataProvider {
+		current_para_block: 1,
+		relay_offset: 1000,
+		relay_blocks_per_para_block: 2,
+		para_blocks_per_relay_epoch: 10,
+		relay_randomness_config: (),
+		xcm_config: MockXcmConfig { para_id, ..Default::default() },
+		raw_downward_messages: Vec::new(),
+		raw_horizontal_messages: Vec::new(),
+	};
+	futures::executor::block_on(validation_data.provide_inherent_data(&mut inherent_data))
+		.map_err(|e| format!("creating validation data inherent data failed: {:?}", e))?;
+
+	Ok(inherent_data)
+}
+
+/// Signs `call` with `extra` by `Alice`.
+fn signed_extrinsic<Call, Extra>(
+	call: Call,
+	extra: Extra,
+	additional_signed: Extra::AdditionalSigned,
+) -> OpaqueExtrinsic
+where
+	Call: Encode + Clone,
+	Extra: SignedExtension,
+{
+	let signer = Sr25519Keyring::Alice;
+	let payload = SignedPayload::from_raw(call.clone(), extra.clone(), additional_signed);
+	let signature = payload.using_encoded(|payload| signer.sign(payload));
+	let extrinsic =
+		UncheckedExtrinsic::<MultiAddress<AccountId, ()>, _, MultiSignature, _>::new_signed(
+			call,
+			signer.to_account_id().into(),
+			signature.into(),
+			extra,
+		);
+	OpaqueExtrinsic::from_bytes(&extrinsic.encode()).expect("Encoded extrinsic is valid; qed")
+}
+
+/// Defines a [`BuildRemark`] function for a runtime.
+///
+/// The signed extensions following the common `frame_system` ones differ between the runtimes and
+/// are given together with their additional signed data.
+macro_rules! remark_builder {
+	(
+		$name:ident, $runtime:ident,
+		extra: ($($extra:expr),*), additional: ($($additional:expr),*) $(,)?
+	) => {
+		pub fn $name(nonce: u32, genesis_hash: Hash) -> OpaqueExtrinsic {
+			use $runtime as runtime;
+			signed_extrinsic(
+				runtime::RuntimeCall::System(frame_system::Call::remark { remark: Vec::new() }),
+				(
+					frame_system::CheckNonZeroSender::<runtime::Runtime>::new(),
+					frame_system::CheckSpecVersion::<runtime::Runtime>::new(),
+					frame_system::CheckTxVersion::<runtime::Runtime>::new(),
+					frame_system::CheckGenesis::<runtime::Runtime>::new(),
+					frame_system::CheckEra::<runtime::Runtime>::from(Era::Immortal),
+					frame_system::CheckNonce::<runtime::Runtime>::from(nonce),
+					frame_system::CheckWeight::<runtime::Runtime>::new(),
+					$($extra,)*
+				),
+				(
+					(),
+					runtime::VERSION.spec_version,
+					runtime::VERSION.transaction_version,
+					genesis_hash,
+					genesis_hash,
+					(),
+					(),
+					$($additional,)*
+				),
+			)
+		}
+	};
+}
+
+remark_builder!(
+	asset_hub_polkadot_remark, asset_hub_polkadot_runtime,
+	extra: (
+		pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::<runtime::Runtime>::from(0, None),
+		parachains_common::session_keys::CheckSessionKeysProof::<runtime::Runtime>::new()
+	),
+	additional: ((), ()),
+);
+
+remark_builder!(
+	asset_hub_kusama_remark, asset_hub_kusama_runtime,
+	extra: (
+		pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::<runtime::Runtime>::from(0, None),
+		parachains_common::session_keys::CheckSessionKeysProof::<runtime::Runtime>::new()
+	),
+	additional: ((), ()),
+);
+
+remark_builder!(
+	asset_hub_westend_remark, asset_hub_westend_runtime,
+	extra: (
+		pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::<runtime::Runtime>::from(0, None),
+		parachains_common::session_keys::CheckSessionKeysProof::<runtime::Runtime>::new()
+	),
+	additional: ((), ()),
+);
+
+remark_builder!(
+	bridge_hub_rococo_remark, bridge_hub_rococo_runtime,
+	extra: (
+		pallet_transaction_payment::ChargeTransactionPayment::<runtime::Runtime>::from(0),
+		runtime::BridgeRejectObsoleteHeadersAndMessages {},
+		(
+			runtime::bridge_hub_wococo_config::BridgeRefundBridgeHubRococoMessages::default(),
+			runtime::bridge_hub_rococo_config::BridgeRefundBridgeHubWococoMessages::default()
+		),
+		parachains_common::session_keys::CheckSessionKeysProof::<runtime::Runtime>::new()
+	),
+	additional: ((), (), ((), ()), ()),
+);
+
+remark_builder!(
+	bridge_hub_kusama_remark, bridge_hub_kusama_runtime,
+	extra: (
+		pallet_transaction_payment::ChargeTransactionPayment::<runtime::Runtime>::from(0),
+		parachains_common::session_keys::CheckSessionKeysProof::<runtime::Runtime>::new()
+	),
+	additional: ((), ()),
+);
+
+remark_builder!(
+	bridge_hub_polkadot_remark, bridge_hub_polkadot_runtime,
+	extra: (
+		pallet_transaction_payment::ChargeTransactionPayment::<runtime::Runtime>::from(0),
+		parachains_common::session_keys::CheckSessionKeysProof::<runtime::Runtime>::new()
+	),
+	additional: ((), ()),
+);
+
+remark_builder!(
+	collectives_polkadot_remark, collectives_polkadot_runtime,
+	extra: (parachains_common::session_keys::CheckSessionKeysProof::<runtime::Runtime>::new()),
+	additional: (()),
+);
+
+remark_builder!(
+	contracts_rococo_remark, contracts_rococo_runtime,
+	extra: (
+		pallet_transaction_payment::ChargeTransactionPayment::<runtime::Runtime>::from(0),
+		parachains_common::session_keys::CheckSessionKeysProof::<runtime::Runtime>::new()
+	),
+	additional: ((), ()),
+);
+
+remark_builder!(
+	penpal_remark, penpal_runtime,
+	extra: (
+		pallet_asset_tx_payment::ChargeAssetTxPayment::<runtime::Runtime>::from(0, None),
+		parachains_common::session_keys::CheckSessionKeysProof::<runtime::Runtime>::new()
+	),
+	additional: ((), ()),
+);
@@ -209,7 +209,7 @@ pub fn asset_hub_polkadot_config() -> AssetHubPolkadotChainSpec {
 	)
 }
 
-fn asset_hub_polkadot_genesis(
+pub(crate) fn asset_hub_polkadot_genesis(
 	invulnerables: Vec<(AccountId, AssetHubPolkadotAuraId)>,
 	endowed_accounts: Vec<AccountId>,
 	id: ParaId,
@@ -403,7 +403,7 @@ pub fn asset_hub_kusama_config() -> AssetHubKusamaChainSpec {
 	)
 }
 
-fn asset_hub_kusama_genesis(
+pub(crate) fn asset_hub_kusama_genesis(
 	invulnerables: Vec<(AccountId, AuraId)>,
 	endowed_accounts: Vec<AccountId>,
 	id: ParaId,
@@ -592,7 +592,7 @@ pub fn asset_hub_westend_config() -> AssetHubWestendChainSpec {
 	)
 }
 
-fn asset_hub_westend_genesis(
+pub(crate) fn asset_hub_westend_genesis(
 	invulnerables: Vec<(AccountId, AuraId)>,
 	endowed_accounts: Vec<AccountId>,
 	id: ParaId,
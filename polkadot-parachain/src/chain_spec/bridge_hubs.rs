@@ -263,7 +263,7 @@ pub mod rococo {
 		)
 	}
 
-	fn genesis(
+	pub(crate) fn genesis(
 		invulnerables: Vec<(AccountId, AuraId)>,
 		endowed_accounts: Vec<AccountId>,
 		id: ParaId,
@@ -435,7 +435,7 @@ pub mod kusama {
 		)
 	}
 
-	fn genesis(
+	pub(crate) fn genesis(
 		invulnerables: Vec<(AccountId, AuraId)>,
 		endowed_accounts: Vec<AccountId>,
 		id: ParaId,
@@ -572,7 +572,7 @@ pub mod polkadot {
 		)
 	}
 
-	fn genesis(
+	pub(crate) fn genesis(
 		invulnerables: Vec<(AccountId, AuraId)>,
 		endowed_accounts: Vec<AccountId>,
 		id: ParaId,
@@ -0,0 +1,454 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Chain specs of the included runtimes, built from named genesis presets.
+//!
+//! A preset fixes the collators, the endowed accounts, the para id and the relay chain of a
+//! development or a local network. Each of them can be overridden on the command line, so a
+//! one-off network doesn't need a chain spec function of its own.
+
+use crate::chain_spec::{
+	asset_hubs, bridge_hubs, get_account_id_from_seed, get_collator_keys_from_seed, glutton,
+	penpal, Extensions,
+};
+use cumulus_primitives_core::ParaId;
+use parachains_common::{AccountId, AssetHubPolkadotAuraId, AuraId};
+use sc_service::{ChainSpec, ChainType, GenericChainSpec};
+use sp_core::{crypto::Ss58Codec, sr25519, Pair, Public};
+use std::{io::Write, path::PathBuf, str::FromStr};
+
+/// A runtime that chain specs can be built for from a preset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PresetRuntime {
+	AssetHubPolkadot,
+	AssetHubKusama,
+	AssetHubWestend,
+	BridgeHubRococo,
+	BridgeHubKusama,
+	BridgeHubPolkadot,
+	Penpal,
+	Glutton,
+}
+
+impl PresetRuntime {
+	/// Human readable name of the chain, without the preset.
+	fn name(&self) -> &'static str {
+		match self {
+			Self::AssetHubPolkadot => "Polkadot Asset Hub",
+			Self::AssetHubKusama => "Kusama Asset Hub",
+			Self::AssetHubWestend => "Westend Asset Hub",
+			Self::BridgeHubRococo => "Rococo BridgeHub",
+			Self::BridgeHubKusama => "Kusama BridgeHub",
+			Self::BridgeHubPolkadot => "Polkadot BridgeHub",
+			Self::Penpal => "Penpal Parachain",
+			Self::Glutton => "Glutton",
+		}
+	}
+
+	/// Id of the chain spec.
+	///
+	/// The id must be recognized by the runtime resolver of the node, so the built chain spec can
+	/// be loaded from its file again.
+	fn id(&self, preset: Preset, para_id: ParaId) -> String {
+		match (self, preset) {
+			(Self::AssetHubPolkadot, _) => format!("asset-hub-polkadot-{}", preset.suffix()),
+			(Self::AssetHubKusama, _) => format!("asset-hub-kusama-{}", preset.suffix()),
+			(Self::AssetHubWestend, _) => format!("asset-hub-westend-{}", preset.suffix()),
+			(Self::BridgeHubRococo, Preset::Development) =>
+				bridge_hubs::rococo::BRIDGE_HUB_ROCOCO_DEVELOPMENT.into(),
+			(Self::BridgeHubRococo, Preset::Local) =>
+				bridge_hubs::rococo::BRIDGE_HUB_ROCOCO_LOCAL.into(),
+			(Self::BridgeHubKusama, Preset::Development) =>
+				bridge_hubs::kusama::BRIDGE_HUB_KUSAMA_DEVELOPMENT.into(),
+			(Self::BridgeHubKusama, Preset::Local) =>
+				bridge_hubs::kusama::BRIDGE_HUB_KUSAMA_LOCAL.into(),
+			(Self::BridgeHubPolkadot, Preset::Development) =>
+				bridge_hubs::polkadot::BRIDGE_HUB_POLKADOT_DEVELOPMENT.into(),
+			(Self::BridgeHubPolkadot, Preset::Local) =>
+				bridge_hubs::polkadot::BRIDGE_HUB_POLKADOT_LOCAL.into(),
+			(Self::Penpal, _) => format!("penpal-{}", preset.suffix()),
+			(Self::Glutton, _) => format!("glutton-kusama-{}-{}", preset.suffix(), para_id),
+		}
+	}
+
+	/// The para id used if none is given.
+	fn default_para_id(&self) -> u32 {
+		match self {
+			Self::AssetHubPolkadot | Self::AssetHubKusama | Self::AssetHubWestend => 1000,
+			Self::BridgeHubPolkadot => 1002,
+			Self::BridgeHubKusama => 1003,
+			Self::BridgeHubRococo => 1013,
+			Self::Penpal => 2000,
+			Self::Glutton => 1300,
+		}
+	}
+
+	/// The relay chain used if none is given, without the preset suffix.
+	fn default_relay_chain(&self) -> &'static str {
+		match self {
+			Self::AssetHubPolkadot | Self::BridgeHubPolkadot => "polkadot",
+			Self::AssetHubKusama | Self::BridgeHubKusama | Self::Glutton => "kusama",
+			Self::AssetHubWestend => "westend",
+			Self::BridgeHubRococo | Self::Penpal => "rococo",
+		}
+	}
+
+	fn properties(&self) -> Option<sc_chain_spec::Properties> {
+		let (ss58_format, token_symbol, token_decimals) = match self {
+			Self::AssetHubPolkadot | Self::BridgeHubPolkadot => (Some(0), "DOT", 10),
+			Self::AssetHubKusama | Self::BridgeHubKusama => (Some(2), "KSM", 12),
+			Self::AssetHubWestend => (None, "WND", 12),
+			Self::BridgeHubRococo => (Some(42), "ROC", 12),
+			Self::Penpal => (Some(42), "UNIT", 12),
+			Self::Glutton => return None,
+		};
+
+		let mut properties = sc_chain_spec::Properties::new();
+		if let Some(ss58_format) = ss58_format {
+			properties.insert("ss58Format".into(), ss58_format.into());
+		}
+		properties.insert("tokenSymbol".into(), token_symbol.into());
+		properties.insert("tokenDecimals".into(), token_decimals.into());
+		Some(properties)
+	}
+}
+
+/// A named genesis preset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Preset {
+	/// `Alice` as the only collator, `Alice` and `Bob` and their stashes endowed.
+	Development,
+	/// `Alice` and `Bob` as collators, all the well-known development accounts endowed.
+	Local,
+}
+
+impl Preset {
+	/// Suffix of the chain spec id and of the default relay chain.
+	fn suffix(&self) -> &'static str {
+		match self {
+			Self::Development => "dev",
+			Self::Local => "local",
+		}
+	}
+
+	fn name(&self) -> &'static str {
+		match self {
+			Self::Development => "Development",
+			Self::Local => "Local",
+		}
+	}
+
+	fn chain_type(&self) -> ChainType {
+		match self {
+			Self::Development => ChainType::Development,
+			Self::Local => ChainType::Local,
+		}
+	}
+
+	fn collators(&self) -> Vec<Collator> {
+		let seeds: &[&str] = match self {
+			Self::Development => &["Alice"],
+			Self::Local => &["Alice", "Bob"],
+		};
+		seeds.iter().map(|seed| Collator::Seed(seed.to_string())).collect()
+	}
+
+	fn endowed_accounts(&self) -> Vec<AccountId> {
+		let seeds: &[&str] = match self {
+			Self::Development => &["Alice", "Bob", "Alice//stash", "Bob//stash"],
+			Self::Local => &[
+				"Alice",
+				"Bob",
+				"Charlie",
+				"Dave",
+				"Eve",
+				"Ferdie",
+				"Alice//stash",
+				"Bob//stash",
+				"Charlie//stash",
+				"Dave//stash",
+				"Eve//stash",
+				"Ferdie//stash",
+			],
+		};
+		seeds
+			.iter()
+			.map(|seed| get_account_id_from_seed::<sr25519::Public>(seed))
+			.collect()
+	}
+}
+
+/// A collator of the genesis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Collator {
+	/// The account and the Aura key are derived from the seed, e.g. `Alice`.
+	Seed(String),
+	/// The SS58 encoded account and Aura key, given as `<account>:<aura key>`.
+	Keys(String, String),
+}
+
+impl FromStr for Collator {
+	type Err = String;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		match value.split_once(':') {
+			Some((account, aura)) => Ok(Collator::Keys(account.into(), aura.into())),
+			None => {
+				check_seed::<sr25519::Public>(value)?;
+				Ok(Collator::Seed(value.into()))
+			},
+		}
+	}
+}
+
+impl Collator {
+	/// The account and the Aura key of the collator.
+	fn keys<AuraKey>(&self) -> Result<(AccountId, AuraKey), String>
+	where
+		AuraKey: Public + Ss58Codec,
+		AuraKey::Pair: Pair<Public = AuraKey>,
+	{
+		match self {
+			Collator::Seed(seed) => {
+				check_seed::<AuraKey>(seed)?;
+				Ok((
+					get_account_id_from_seed::<sr25519::Public>(seed),
+					get_collator_keys_from_seed::<AuraKey>(seed),
+				))
+			},
+			Collator::Keys(account, aura) => Ok((
+				AccountId::from_ss58check(account)
+					.map_err(|e| format!("Invalid collator account `{}`: {:?}", account, e))?,
+				AuraKey::from_ss58check(aura)
+					.map_err(|e| format!("Invalid collator Aura key `{}`: {:?}", aura, e))?,
+			)),
+		}
+	}
+}
+
+/// Make sure that keys can be derived from `seed`, as the `get_*_from_seed` helpers panic
+/// otherwise.
+fn check_seed<TPublic: Public>(seed: &str) -> Result<(), String> {
+	TPublic::Pair::from_string(&format!("//{}", seed), None)
+		.map(drop)
+		.map_err(|e| format!("Invalid seed `{}`: {:?}", seed, e))
+}
+
+/// Account given either as SS58 address or as the seed of its sr25519 key.
+fn parse_account(account: &str) -> Result<AccountId, String> {
+	if let Ok(account) = AccountId::from_ss58check(account) {
+		return Ok(account)
+	}
+	check_seed::<sr25519::Public>(account)?;
+	Ok(get_account_id_from_seed::<sr25519::Public>(account))
+}
+
+/// The `build-spec-from-preset` command used to build the chain spec of an included runtime from
+/// one of its genesis presets.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct BuildSpecFromPresetCmd {
+	/// The runtime to build the chain spec for.
+	#[arg(long, value_enum)]
+	pub runtime: PresetRuntime,
+
+	/// The genesis preset.
+	#[arg(long, value_enum, default_value_t = Preset::Local)]
+	pub preset: Preset,
+
+	/// Para id of the chain, instead of the default one of the runtime.
+	#[arg(long)]
+	pub para_id: Option<u32>,
+
+	/// Relay chain of the chain, instead of the `-dev` or `-local` relay chain of the runtime.
+	#[arg(long)]
+	pub relay_chain: Option<String>,
+
+	/// Account endowed at genesis, instead of the accounts of the preset.
+	///
+	/// Either an SS58 address or the seed of an sr25519 key, e.g. `Alice`. May be given multiple
+	/// times.
+	#[arg(long = "endowed-account", value_name = "ACCOUNT")]
+	pub endowed_accounts: Vec<String>,
+
+	/// Collator at genesis, instead of the collators of the preset.
+	///
+	/// Either the seed the account and the Aura key are derived from, e.g. `Alice`, or the SS58
+	/// encoded account and Aura key as `<account>:<aura key>`. May be given multiple times.
+	#[arg(long = "collator", value_name = "COLLATOR")]
+	pub collators: Vec<Collator>,
+
+	/// Print the chain spec as raw genesis storage.
+	#[arg(long)]
+	pub raw: bool,
+
+	/// Output file name or stdout if unspecified.
+	#[arg(long, short)]
+	pub output: Option<PathBuf>,
+}
+
+impl BuildSpecFromPresetCmd {
+	/// Run the build-spec-from-preset command.
+	pub fn run(&self) -> Result<(), String> {
+		let json = self.chain_spec()?.as_json(self.raw)?;
+		match &self.output {
+			Some(path) => std::fs::write(path, json)
+				.map_err(|e| format!("Failed to write `{}`: {}", path.display(), e)),
+			None => std::io::stdout()
+				.write_all(json.as_bytes())
+				.map_err(|e| format!("Failed to write to stdout: {}", e)),
+		}
+	}
+
+	/// Build the chain spec described by the command.
+	pub fn chain_spec(&self) -> Result<Box<dyn ChainSpec>, String> {
+		let runtime = self.runtime;
+		let preset = self.preset;
+		let para_id = ParaId::from(self.para_id.unwrap_or_else(|| runtime.default_para_id()));
+		let relay_chain = self
+			.relay_chain
+			.clone()
+			.unwrap_or_else(|| format!("{}-{}", runtime.default_relay_chain(), preset.suffix()));
+		let spec = Spec {
+			name: format!("{} {}", runtime.name(), preset.name()),
+			id: runtime.id(preset, para_id),
+			chain_type: preset.chain_type(),
+			properties: runtime.properties(),
+			extensions: Extensions { relay_chain, para_id: para_id.into() },
+		};
+
+		if runtime == PresetRuntime::Glutton {
+			if !self.endowed_accounts.is_empty() || !self.collators.is_empty() {
+				return Err("The Glutton runtime has neither endowed accounts nor collators".into())
+			}
+			return Ok(Box::new(spec.build(move || glutton::glutton_genesis(para_id))))
+		}
+
+		let endowed = if self.endowed_accounts.is_empty() {
+			preset.endowed_accounts()
+		} else {
+			self.endowed_accounts
+				.iter()
+				.map(|account| parse_account(account))
+				.collect::<Result<Vec<_>, _>>()?
+		};
+
+		Ok(match runtime {
+			PresetRuntime::AssetHubPolkadot => {
+				let collators = self.collators::<AssetHubPolkadotAuraId>()?;
+				Box::new(spec.build(move || {
+					asset_hubs::asset_hub_polkadot_genesis(
+						collators.clone(),
+						endowed.clone(),
+						para_id,
+					)
+				}))
+			},
+			PresetRuntime::AssetHubKusama => {
+				let collators = self.collators::<AuraId>()?;
+				Box::new(spec.build(move || {
+					asset_hubs::asset_hub_kusama_genesis(
+						collators.clone(),
+						endowed.clone(),
+						para_id,
+					)
+				}))
+			},
+			PresetRuntime::AssetHubWestend => {
+				let collators = self.collators::<AuraId>()?;
+				Box::new(spec.build(move || {
+					asset_hubs::asset_hub_westend_genesis(
+						collators.clone(),
+						endowed.clone(),
+						para_id,
+					)
+				}))
+			},
+			PresetRuntime::BridgeHubRococo => {
+				let collators = self.collators::<AuraId>()?;
+				Box::new(spec.build(move || {
+					bridge_hubs::rococo::genesis(
+						collators.clone(),
+						endowed.clone(),
+						para_id,
+						Some(get_account_id_from_seed::<sr25519::Public>("Bob")),
+					)
+				}))
+			},
+			PresetRuntime::BridgeHubKusama => {
+				let collators = self.collators::<AuraId>()?;
+				Box::new(spec.build(move || {
+					bridge_hubs::kusama::genesis(collators.clone(), endowed.clone(), para_id)
+				}))
+			},
+			PresetRuntime::BridgeHubPolkadot => {
+				let collators = self.collators::<AuraId>()?;
+				Box::new(spec.build(move || {
+					bridge_hubs::polkadot::genesis(collators.clone(), endowed.clone(), para_id)
+				}))
+			},
+			PresetRuntime::Penpal => {
+				let collators = self.collators::<AuraId>()?;
+				Box::new(spec.build(move || {
+					penpal::penpal_testnet_genesis(collators.clone(), endowed.clone(), para_id)
+				}))
+			},
+			PresetRuntime::Glutton => unreachable!("handled above; qed"),
+		})
+	}
+
+	/// The collators given on the command line, or the ones of the preset.
+	fn collators<AuraKey>(&self) -> Result<Vec<(AccountId, AuraKey)>, String>
+	where
+		AuraKey: Public + Ss58Codec,
+		AuraKey::Pair: Pair<Public = AuraKey>,
+	{
+		let collators = if self.collators.is_empty() {
+			self.preset.collators()
+		} else {
+			self.collators.clone()
+		};
+		collators.iter().map(Collator::keys).collect()
+	}
+}
+
+/// Everything of a chain spec except its genesis.
+struct Spec {
+	name: String,
+	id: String,
+	chain_type: ChainType,
+	properties: Option<sc_chain_spec::Properties>,
+	extensions: Extensions,
+}
+
+impl Spec {
+	fn build<G>(
+		self,
+		genesis: impl Fn() -> G + 'static + Send + Sync,
+	) -> GenericChainSpec<G, Extensions> {
+		GenericChainSpec::from_genesis(
+			&self.name,
+			&self.id,
+			self.chain_type,
+			genesis,
+			Vec::new(),
+			None,
+			None,
+			None,
+			self.properties,
+			self.extensions,
+		)
+	}
+}
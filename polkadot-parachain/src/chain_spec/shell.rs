@@ -14,9 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::chain_spec::Extensions;
+use crate::chain_spec::{get_account_id_from_seed, Extensions};
 use cumulus_primitives_core::ParaId;
+use parachains_common::AccountId;
 use sc_service::ChainType;
+use sp_core::sr25519;
 
 /// Specialized `ChainSpec` for the shell parachain runtime.
 pub type ShellChainSpec =
@@ -27,7 +29,9 @@ pub fn get_shell_chain_spec() -> ShellChainSpec {
 		"Shell Local Testnet",
 		"shell_local_testnet",
 		ChainType::Local,
-		move || shell_testnet_genesis(1000.into()),
+		move || {
+			shell_testnet_genesis(get_account_id_from_seed::<sr25519::Public>("Alice"), 1000.into())
+		},
 		Vec::new(),
 		None,
 		None,
@@ -37,7 +41,10 @@ pub fn get_shell_chain_spec() -> ShellChainSpec {
 	)
 }
 
-fn shell_testnet_genesis(parachain_id: ParaId) -> shell_runtime::RuntimeGenesisConfig {
+fn shell_testnet_genesis(
+	root_key: AccountId,
+	parachain_id: ParaId,
+) -> shell_runtime::RuntimeGenesisConfig {
 	shell_runtime::RuntimeGenesisConfig {
 		system: shell_runtime::SystemConfig {
 			code: shell_runtime::WASM_BINARY
@@ -47,5 +54,6 @@ fn shell_testnet_genesis(parachain_id: ParaId) -> shell_runtime::RuntimeGenesisC
 		},
 		parachain_info: shell_runtime::ParachainInfoConfig { parachain_id, ..Default::default() },
 		parachain_system: Default::default(),
+		sudo: shell_runtime::SudoConfig { key: Some(root_key) },
 	}
 }
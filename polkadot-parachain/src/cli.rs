@@ -14,6 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
 
+use cumulus_primitives_core::relay_chain::{BlockNumber as RelayNumber, Hash as RelayHash};
+use cumulus_relay_chain_rpc_interface::{
+	probe_rpc_nodes, RpcNodeExpectations, RpcNodeReport, Url, DEFAULT_STATE_DEPTH,
+};
 use std::path::PathBuf;
 
 /// Sub-commands supported by the collator.
@@ -26,6 +30,9 @@ pub enum Subcommand {
 	/// Build a chain specification.
 	BuildSpec(sc_cli::BuildSpecCmd),
 
+	/// Build a chain specification of an included runtime from a genesis preset.
+	BuildSpecFromPreset(crate::chain_spec::presets::BuildSpecFromPresetCmd),
+
 	/// Validate blocks.
 	CheckBlock(sc_cli::CheckBlockCmd),
 
@@ -45,22 +52,84 @@ pub enum Subcommand {
 	PurgeChain(cumulus_client_cli::PurgeChainCmd),
 
 	/// Export the genesis state of the parachain.
+	#[command(alias = "export-genesis-head")]
 	ExportGenesisState(cumulus_client_cli::ExportGenesisStateCommand),
 
 	/// Export the genesis wasm of the parachain.
 	ExportGenesisWasm(cumulus_client_cli::ExportGenesisWasmCommand),
 
+	/// Export the genesis head and the genesis wasm of the parachain into a directory.
+	ExportGenesisArtifacts(cumulus_client_cli::ExportGenesisArtifactsCommand),
+
+	/// Probe relay chain RPC nodes, as done on startup with `--relay-chain-rpc-urls`.
+	CheckRelayRpc(CheckRelayRpcCmd),
+
 	/// Sub-commands concerned with benchmarking.
 	/// The pallet benchmarking moved to the `pallet` sub-command.
 	#[command(subcommand)]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
 
-	/// Try-runtime has migrated to a standalone
-	/// [CLI](<https://github.com/paritytech/try-runtime-cli>). The subcommand exists as a stub and
-	/// deprecation notice. It will be removed entirely some time after January 2024.
+	/// Try some testing command against a specified runtime state.
+	#[cfg(feature = "try-runtime")]
+	TryRuntime(try_runtime_cli::TryRuntimeCmd),
+
+	/// Errors since the binary was not build with `--features try-runtime`.
+	#[cfg(not(feature = "try-runtime"))]
 	TryRuntime,
 }
 
+/// The `check-relay-rpc` command used to probe relay chain RPC nodes.
+#[derive(Debug, clap::Parser)]
+pub struct CheckRelayRpcCmd {
+	/// The URLs of the relay chain RPC nodes.
+	#[arg(required = true)]
+	pub urls: Vec<Url>,
+
+	/// The expected genesis hash of the relay chain.
+	///
+	/// Without it, all nodes only need to serve the same chain.
+	#[arg(long)]
+	pub genesis_hash: Option<RelayHash>,
+
+	/// The expected name of the relay chain, e.g. `Polkadot`.
+	#[arg(long)]
+	pub chain_name: Option<String>,
+
+	/// The number of blocks below the finalized block, whose state must still be available.
+	#[arg(long, default_value_t = DEFAULT_STATE_DEPTH)]
+	pub state_depth: RelayNumber,
+}
+
+impl CheckRelayRpcCmd {
+	/// Run the check-relay-rpc command.
+	pub async fn run(&self) -> sc_cli::Result<()> {
+		let expectations = RpcNodeExpectations {
+			genesis_hash: self.genesis_hash,
+			chain_name: self.chain_name.clone(),
+			chain_type: None,
+			state_depth: self.state_depth,
+		};
+
+		let reports = probe_rpc_nodes(&self.urls, &expectations).await;
+		for report in &reports {
+			match report.genesis_hash {
+				Some(genesis_hash) if report.issues.is_empty() =>
+					println!("{}: ok, genesis hash {:?}", report.url, genesis_hash),
+				_ => println!("{}:", report.url),
+			}
+			for issue in &report.issues {
+				println!("  {}: {}", if issue.is_fatal() { "error" } else { "warning" }, issue);
+			}
+		}
+
+		if reports.iter().all(RpcNodeReport::is_usable) {
+			Ok(())
+		} else {
+			Err("Not all relay chain RPC nodes can be used".into())
+		}
+	}
+}
+
 const AFTER_HELP_EXAMPLE: &str = color_print::cstr!(
 	r#"<bold><underline>Examples:</></>
    <bold>polkadot-parachain --chain asset-hub-polkadot --sync warp -- --chain polkadot --sync warp</>
@@ -94,6 +163,20 @@ pub struct Cli {
 	#[arg(long)]
 	pub no_hardware_benchmarks: bool,
 
+	/// The time in milliseconds, that the collators of the chains with the asynchronous backing
+	/// spend on authoring a block.
+	///
+	/// It must leave enough time to propagate the block to the next author before their turn.
+	#[arg(long, default_value_t = 1500)]
+	pub authoring_duration: u64,
+
+	/// Use the experimental slot based collator, that builds a block for every availability core
+	/// claimed by the parachain in the claim queue of the relay chain.
+	///
+	/// It requires the relay chain runtime to provide the claim queue runtime API.
+	#[arg(long)]
+	pub experimental_use_slot_based: bool,
+
 	/// Relay chain arguments
 	#[arg(raw = true)]
 	pub relaychain_args: Vec<String>,
@@ -15,6 +15,7 @@
 // along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
+	benchmarking::{inherent_benchmark_data, BuildRemark, RemarkBuilder},
 	chain_spec,
 	cli::{Cli, RelayChainCli, Subcommand},
 	service::{new_partial, Block},
@@ -29,7 +30,7 @@ use sc_cli::{
 };
 use sc_service::config::{BasePath, PrometheusConfig};
 use sp_runtime::traits::AccountIdConversion;
-use std::{net::SocketAddr, path::PathBuf};
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
 
 /// Helper enum that is used for better distinction of different parachain/runtime configuration
 /// (it is based/calculated on ChainSpec's ID attribute)
@@ -380,6 +381,91 @@ impl SubstrateCli for RelayChainCli {
 	}
 }
 
+/// The builder of the signed extrinsic that `benchmark overhead` uses as base extrinsic.
+fn remark_builder(runtime: &Runtime) -> Result<BuildRemark> {
+	use crate::benchmarking::*;
+
+	Ok(match runtime {
+		Runtime::AssetHubPolkadot => asset_hub_polkadot_remark,
+		Runtime::AssetHubKusama => asset_hub_kusama_remark,
+		Runtime::AssetHubWestend => asset_hub_westend_remark,
+		Runtime::BridgeHub(bridge_hub_runtime_type) => match bridge_hub_runtime_type {
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::Polkadot |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::PolkadotLocal |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::PolkadotDevelopment => bridge_hub_polkadot_remark,
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::Kusama |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::KusamaLocal |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::KusamaDevelopment |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::Westend => bridge_hub_kusama_remark,
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::Rococo |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::RococoLocal |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::RococoDevelopment |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::Wococo |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::WococoLocal => bridge_hub_rococo_remark,
+		},
+		Runtime::CollectivesPolkadot | Runtime::CollectivesWestend => collectives_polkadot_remark,
+		Runtime::ContractsRococo => contracts_rococo_remark,
+		Runtime::Penpal(_) => penpal_remark,
+		_ => return Err("The chain is not supported by the overhead benchmark".into()),
+	})
+}
+
+/// The directory of the `*_weights.rs` files of the runtime, relative to the repository root.
+fn weights_dir(runtime: &Runtime) -> Option<&'static str> {
+	Some(match runtime {
+		Runtime::AssetHubPolkadot => "parachains/runtimes/assets/asset-hub-polkadot/src/weights",
+		Runtime::AssetHubKusama => "parachains/runtimes/assets/asset-hub-kusama/src/weights",
+		Runtime::AssetHubWestend => "parachains/runtimes/assets/asset-hub-westend/src/weights",
+		Runtime::BridgeHub(bridge_hub_runtime_type) => match bridge_hub_runtime_type {
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::Polkadot |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::PolkadotLocal |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::PolkadotDevelopment =>
+				"parachains/runtimes/bridge-hubs/bridge-hub-polkadot/src/weights",
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::Kusama |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::KusamaLocal |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::KusamaDevelopment |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::Westend =>
+				"parachains/runtimes/bridge-hubs/bridge-hub-kusama/src/weights",
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::Rococo |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::RococoLocal |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::RococoDevelopment |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::Wococo |
+			chain_spec::bridge_hubs::BridgeHubRuntimeType::WococoLocal =>
+				"parachains/runtimes/bridge-hubs/bridge-hub-rococo/src/weights",
+		},
+		Runtime::CollectivesPolkadot | Runtime::CollectivesWestend =>
+			"parachains/runtimes/collectives/collectives-polkadot/src/weights",
+		Runtime::ContractsRococo => "parachains/runtimes/contracts/contracts-rococo/src/weights",
+		Runtime::Penpal(_) => "parachains/runtimes/testing/penpal/src/weights",
+		_ => return None,
+	})
+}
+
+/// Makes `benchmark overhead` and `benchmark storage` write their `*_weights.rs` files into the
+/// weights directory of the benchmarked runtime, unless `--weight-path` is given.
+///
+/// This only applies when running from the root of this repository.
+fn set_default_weight_path(cmd: &mut BenchmarkCmd) {
+	let chain = cmd.shared_params().chain.clone().unwrap_or_default();
+	let weight_params = match cmd {
+		BenchmarkCmd::Overhead(cmd) => &mut cmd.params.weight,
+		BenchmarkCmd::Storage(cmd) => &mut cmd.params.weight_params,
+		_ => return,
+	};
+	if weight_params.weight_path.is_some() {
+		return
+	}
+
+	let Some(dir) = load_spec(&chain).ok().and_then(|spec| weights_dir(&spec.runtime())) else {
+		return
+	};
+	let dir = PathBuf::from(dir);
+	if dir.is_dir() {
+		info!("Writing the weights to {}", dir.display());
+		weight_params.weight_path = Some(dir);
+	}
+}
+
 /// Creates partial components for the runtimes that are supported by the benchmarks.
 macro_rules! construct_benchmark_partials {
 	($config:expr, |$partials:ident| $code:expr) => {
@@ -456,6 +542,20 @@ macro_rules! construct_benchmark_partials {
 				)?;
 				$code
 			},
+			Runtime::ContractsRococo => {
+				let $partials = new_partial::<contracts_rococo_runtime::RuntimeApi, _>(
+					&$config,
+					crate::service::contracts_rococo_build_import_queue,
+				)?;
+				$code
+			},
+			Runtime::Penpal(_) => {
+				let $partials = new_partial::<rococo_parachain_runtime::RuntimeApi, _>(
+					&$config,
+					crate::service::rococo_parachain_build_import_queue,
+				)?;
+				$code
+			},
 			_ => Err("The chain is not supported".into()),
 		}
 	};
@@ -630,13 +730,17 @@ macro_rules! construct_async_run {
 
 /// Parse command line arguments into service configuration.
 pub fn run() -> Result<()> {
-	let cli = Cli::from_args();
+	let mut cli = Cli::from_args();
+	if let Some(Subcommand::Benchmark(cmd)) = &mut cli.subcommand {
+		set_default_weight_path(cmd);
+	}
 
 	match &cli.subcommand {
 		Some(Subcommand::BuildSpec(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run(config.chain_spec, config.network))
 		},
+		Some(Subcommand::BuildSpecFromPreset(cmd)) => Ok(cmd.run()?),
 		Some(Subcommand::CheckBlock(cmd)) => {
 			construct_async_run!(|components, cli, cmd, config| {
 				Ok(cmd.run(components.client, components.import_queue))
@@ -683,6 +787,10 @@ pub fn run() -> Result<()> {
 			construct_async_run!(|components, cli, cmd, config| {
 				Ok(async move { cmd.run(&*config.chain_spec, &*components.client) })
 			}),
+		Some(Subcommand::ExportGenesisArtifacts(cmd)) =>
+			construct_async_run!(|components, cli, cmd, config| {
+				Ok(async move { cmd.run(&*config.chain_spec, &*components.client) })
+			}),
 		Some(Subcommand::ExportGenesisWasm(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|_config| {
@@ -690,6 +798,7 @@ pub fn run() -> Result<()> {
 				cmd.run(&*spec)
 			})
 		},
+		Some(Subcommand::CheckRelayRpc(cmd)) => sc_cli::build_runtime()?.block_on(cmd.run()),
 		Some(Subcommand::Benchmark(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 
@@ -723,6 +832,21 @@ pub fn run() -> Result<()> {
 						cmd.run(config, partials.client.clone(), db, storage)
 					})
 				}),
+				BenchmarkCmd::Overhead(cmd) => runner.sync_run(|config| {
+					let build = remark_builder(&config.chain_spec.runtime())?;
+					let para_id = chain_spec::Extensions::try_get(&*config.chain_spec)
+						.map(|e| e.para_id)
+						.ok_or("Could not find parachain extension in chain-spec.")?;
+					let inherent_data = inherent_benchmark_data(ParaId::from(para_id))?;
+
+					construct_benchmark_partials!(config, |partials| {
+						use sp_blockchain::HeaderBackend as _;
+
+						let builder =
+							RemarkBuilder::new(build, partials.client.info().genesis_hash);
+						cmd.run(config, partials.client, inherent_data, Vec::new(), &builder)
+					})
+				}),
 				BenchmarkCmd::Machine(cmd) =>
 					runner.sync_run(|config| cmd.run(&config, SUBSTRATE_REFERENCE_HARDWARE.clone())),
 				// NOTE: this allows the Client to leniently implement
@@ -731,7 +855,76 @@ pub fn run() -> Result<()> {
 				_ => Err("Benchmarking sub-command unsupported".into()),
 			}
 		},
-		Some(Subcommand::TryRuntime) => Err("The `try-runtime` subcommand has been migrated to a standalone CLI (https://github.com/paritytech/try-runtime-cli). It is no longer being maintained here and will be removed entirely some time after January 2024. Please remove this subcommand from your runtime and use the standalone CLI.".into()),
+		#[cfg(feature = "try-runtime")]
+		Some(Subcommand::TryRuntime(cmd)) => {
+			use crate::service::{
+				AssetHubKusamaExecutor, AssetHubPolkadotRuntimeExecutor, AssetHubWestendExecutor,
+				BridgeHubKusamaRuntimeExecutor, BridgeHubPolkadotRuntimeExecutor,
+				BridgeHubRococoRuntimeExecutor, CollectivesPolkadotRuntimeExecutor,
+				ContractsRococoRuntimeExecutor, PenpalRuntimeExecutor,
+			};
+			use sc_executor::{sp_wasm_interface::ExtendedHostFunctions, NativeExecutionDispatch};
+			use try_runtime_cli::block_building_info::timestamp_with_aura_info;
+
+			// grab the task manager.
+			let runner = cli.create_runner(cmd)?;
+			let registry = &runner.config().prometheus_config.as_ref().map(|cfg| &cfg.registry);
+			let task_manager =
+				sc_service::TaskManager::new(runner.config().tokio_handle.clone(), *registry)
+					.map_err(|e| format!("Error: {:?}", e))?;
+			type HostFunctionsOf<E> = ExtendedHostFunctions<
+				(
+					sp_io::SubstrateHostFunctions,
+					cumulus_primitives_storage_weight_reclaim::storage_proof_size::HostFunctions,
+				),
+				<E as NativeExecutionDispatch>::ExtendHostFunctions,
+			>;
+
+			let info_provider = timestamp_with_aura_info(6000);
+
+			macro_rules! run_with_executor {
+				($executor:ty) => {
+					runner.async_run(|_| {
+						Ok((
+							cmd.run::<Block, HostFunctionsOf<$executor>, _>(Some(info_provider)),
+							task_manager,
+						))
+					})
+				};
+			}
+
+			match runner.config().chain_spec.runtime() {
+				Runtime::AssetHubPolkadot => run_with_executor!(AssetHubPolkadotRuntimeExecutor),
+				Runtime::AssetHubKusama => run_with_executor!(AssetHubKusamaExecutor),
+				Runtime::AssetHubWestend => run_with_executor!(AssetHubWestendExecutor),
+				Runtime::CollectivesPolkadot | Runtime::CollectivesWestend =>
+					run_with_executor!(CollectivesPolkadotRuntimeExecutor),
+				Runtime::ContractsRococo => run_with_executor!(ContractsRococoRuntimeExecutor),
+				Runtime::Penpal(_) => run_with_executor!(PenpalRuntimeExecutor),
+				Runtime::BridgeHub(bridge_hub_runtime_type) => match bridge_hub_runtime_type {
+					chain_spec::bridge_hubs::BridgeHubRuntimeType::Polkadot |
+					chain_spec::bridge_hubs::BridgeHubRuntimeType::PolkadotLocal |
+					chain_spec::bridge_hubs::BridgeHubRuntimeType::PolkadotDevelopment =>
+						run_with_executor!(BridgeHubPolkadotRuntimeExecutor),
+					chain_spec::bridge_hubs::BridgeHubRuntimeType::Kusama |
+					chain_spec::bridge_hubs::BridgeHubRuntimeType::KusamaLocal |
+					chain_spec::bridge_hubs::BridgeHubRuntimeType::KusamaDevelopment |
+					chain_spec::bridge_hubs::BridgeHubRuntimeType::Westend =>
+						run_with_executor!(BridgeHubKusamaRuntimeExecutor),
+					chain_spec::bridge_hubs::BridgeHubRuntimeType::Rococo |
+					chain_spec::bridge_hubs::BridgeHubRuntimeType::RococoLocal |
+					chain_spec::bridge_hubs::BridgeHubRuntimeType::RococoDevelopment |
+					chain_spec::bridge_hubs::BridgeHubRuntimeType::Wococo |
+					chain_spec::bridge_hubs::BridgeHubRuntimeType::WococoLocal =>
+						run_with_executor!(BridgeHubRococoRuntimeExecutor),
+				},
+				_ => Err("Chain doesn't support try-runtime".into()),
+			}
+		},
+		#[cfg(not(feature = "try-runtime"))]
+		Some(Subcommand::TryRuntime) => Err("Try-runtime was not enabled when building the node. \
+				You can enable it with `--features try-runtime`."
+			.into()),
 		Some(Subcommand::Key(cmd)) => Ok(cmd.run(&cli)?),
 		None => {
 			let runner = cli.create_runner(&cli.run.normalize())?;
@@ -908,6 +1101,8 @@ pub fn run() -> Result<()> {
 							collator_options,
 							id,
 							hwbench,
+							Duration::from_millis(cli.authoring_duration),
+							cli.experimental_use_slot_based,
 						)
 						.await
 						.map(|r| r.0)
@@ -0,0 +1,226 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC for estimating the fees of XCM programs, see [`XcmPaymentApi`](RuntimeXcmPaymentApi).
+//!
+//! XCM messages and locations are passed SCALE encoded, as their versioned types.
+
+use std::sync::Arc;
+
+use codec::{Decode, Encode};
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+};
+use parachains_common::xcm_payment_api::{
+	XcmPaymentApi as RuntimeXcmPaymentApi, XcmPaymentApiError,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+use sp_weights::Weight;
+use xcm::{latest::Outcome, VersionedMultiLocation, VersionedXcm};
+
+/// The fee of executing an XCM program.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XcmFeeEstimate {
+	/// The weight of the program.
+	pub weight: Weight,
+	/// The fee for the weight, in the requested asset.
+	pub fee: NumberOrHex,
+}
+
+/// The outcome of the dry run of an XCM program.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XcmDryRun {
+	/// The weight used by the execution.
+	pub weight_used: Weight,
+	/// The error that stopped the execution, if it didn't complete.
+	pub error: Option<String>,
+}
+
+impl From<Outcome> for XcmDryRun {
+	fn from(outcome: Outcome) -> Self {
+		match outcome {
+			Outcome::Complete(weight_used) => Self { weight_used, error: None },
+			Outcome::Incomplete(weight_used, error) =>
+				Self { weight_used, error: Some(format!("{:?}", error)) },
+			Outcome::Error(error) =>
+				Self { weight_used: Weight::zero(), error: Some(format!("{:?}", error)) },
+		}
+	}
+}
+
+/// The RPC API for estimating the fees of XCM programs.
+#[rpc(client, server)]
+pub trait XcmPaymentApi<BlockHash> {
+	/// Returns the SCALE encoded `VersionedMultiLocation`s of the assets, in which fees can be
+	/// paid, in `xcm_version`.
+	#[method(name = "xcmPayment_acceptablePaymentAssets")]
+	fn acceptable_payment_assets(
+		&self,
+		xcm_version: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Bytes>>;
+
+	/// Returns the weight of the SCALE encoded `VersionedXcm<()>` `message`.
+	#[method(name = "xcmPayment_xcmWeight")]
+	fn xcm_weight(&self, message: Bytes, at: Option<BlockHash>) -> RpcResult<Weight>;
+
+	/// Returns the fee for `weight` in the asset with the SCALE encoded `VersionedMultiLocation`
+	/// `asset`.
+	#[method(name = "xcmPayment_weightToAssetFee")]
+	fn weight_to_asset_fee(
+		&self,
+		weight: Weight,
+		asset: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<NumberOrHex>;
+
+	/// Returns the weight of `message` and its fee in `asset`, see [`Self::xcm_weight`] and
+	/// [`Self::weight_to_asset_fee`].
+	#[method(name = "xcmPayment_estimateFee")]
+	fn estimate_fee(
+		&self,
+		message: Bytes,
+		asset: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<XcmFeeEstimate>;
+
+	/// Executes `message` as if it was received from the SCALE encoded `VersionedMultiLocation`
+	/// `origin`, without persisting anything.
+	#[method(name = "xcmPayment_dryRunXcm")]
+	fn dry_run_xcm(
+		&self,
+		origin: Bytes,
+		message: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<XcmDryRun>;
+}
+
+/// Implements the [`XcmPaymentApiServer`].
+pub struct XcmPayment<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> XcmPayment<C, Block> {
+	/// Create a new [`XcmPayment`].
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> XcmPayment<C, Block>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block>,
+{
+	fn at(&self, at: Option<Block::Hash>) -> Block::Hash {
+		at.unwrap_or_else(|| self.client.info().best_hash)
+	}
+}
+
+impl<C, Block> XcmPaymentApiServer<Block::Hash> for XcmPayment<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: RuntimeXcmPaymentApi<Block>,
+{
+	fn acceptable_payment_assets(
+		&self,
+		xcm_version: u32,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<Bytes>> {
+		let assets = self
+			.client
+			.runtime_api()
+			.query_acceptable_payment_assets(self.at(at), xcm_version)
+			.map_err(api_error)?
+			.map_err(xcm_payment_error)?;
+		Ok(assets.into_iter().map(|asset| asset.encode().into()).collect())
+	}
+
+	fn xcm_weight(&self, message: Bytes, at: Option<Block::Hash>) -> RpcResult<Weight> {
+		let message = decode::<VersionedXcm<()>>(&message, "message")?;
+		self.client
+			.runtime_api()
+			.query_xcm_weight(self.at(at), message)
+			.map_err(api_error)?
+			.map_err(xcm_payment_error)
+	}
+
+	fn weight_to_asset_fee(
+		&self,
+		weight: Weight,
+		asset: Bytes,
+		at: Option<Block::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let asset = decode::<VersionedMultiLocation>(&asset, "asset")?;
+		self.client
+			.runtime_api()
+			.query_weight_to_asset_fee(self.at(at), weight, asset)
+			.map_err(api_error)?
+			.map(Into::into)
+			.map_err(xcm_payment_error)
+	}
+
+	fn estimate_fee(
+		&self,
+		message: Bytes,
+		asset: Bytes,
+		at: Option<Block::Hash>,
+	) -> RpcResult<XcmFeeEstimate> {
+		let at = Some(self.at(at));
+		let weight = self.xcm_weight(message, at)?;
+		let fee = self.weight_to_asset_fee(weight, asset, at)?;
+		Ok(XcmFeeEstimate { weight, fee })
+	}
+
+	fn dry_run_xcm(
+		&self,
+		origin: Bytes,
+		message: Bytes,
+		at: Option<Block::Hash>,
+	) -> RpcResult<XcmDryRun> {
+		let origin = decode::<VersionedMultiLocation>(&origin, "origin")?;
+		let message = decode::<VersionedXcm<()>>(&message, "message")?;
+		self.client
+			.runtime_api()
+			.dry_run_xcm(self.at(at), origin, message)
+			.map_err(api_error)?
+			.map(Into::into)
+			.map_err(xcm_payment_error)
+	}
+}
+
+fn decode<T: Decode>(bytes: &Bytes, what: &str) -> RpcResult<T> {
+	T::decode(&mut &bytes[..])
+		.map_err(|e| JsonRpseeError::Custom(format!("Unable to decode the {}: {}", what, e)))
+}
+
+fn api_error(error: sp_api::ApiError) -> JsonRpseeError {
+	JsonRpseeError::Custom(format!("Runtime API call failed: {}", error))
+}
+
+fn xcm_payment_error(error: XcmPaymentApiError) -> JsonRpseeError {
+	JsonRpseeError::Custom(format!("XCM payment API error: {:?}", error))
+}
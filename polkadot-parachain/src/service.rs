@@ -17,8 +17,13 @@
 use codec::Codec;
 use cumulus_client_cli::CollatorOptions;
 use cumulus_client_collator::service::CollatorService;
-use cumulus_client_consensus_aura::collators::basic::{
-	self as basic_aura, Params as BasicAuraParams,
+use cumulus_client_consensus_aura::{
+	collators::{
+		basic::{self as basic_aura, Params as BasicAuraParams},
+		lookahead::{self as lookahead_aura, Params as LookaheadAuraParams},
+		slot_based as slot_based_aura, RelayFinalityBackoff,
+	},
+	key_rotation::{self, AuraKeyRotation, AuraKeyRotationApiServer, AuthoringKeys},
 };
 use cumulus_client_consensus_common::{
 	ParachainBlockImport as TParachainBlockImport, ParachainCandidate, ParachainConsensus,
@@ -29,6 +34,7 @@ use cumulus_client_service::old_consensus;
 use cumulus_client_service::{
 	build_network, build_relay_chain_interface, prepare_node_config, start_relay_chain_tasks,
 	BuildNetworkParams, CollatorSybilResistance, DARecoveryProfile, StartRelayChainTasksParams,
+	SyncStateApiServer, SyncStateProvider,
 };
 use cumulus_primitives_core::{
 	relay_chain::{Hash as PHash, PersistedValidationData},
@@ -60,14 +66,20 @@ use sp_runtime::{app_crypto::AppCrypto, traits::Header as HeaderT};
 use std::{marker::PhantomData, sync::Arc, time::Duration};
 use substrate_prometheus_endpoint::Registry;
 
-use polkadot_primitives::CollatorPair;
+use polkadot_primitives::{CollatorPair, ValidationCode};
 
 #[cfg(not(feature = "runtime-benchmarks"))]
-type HostFunctions = sp_io::SubstrateHostFunctions;
+type HostFunctions = (
+	sp_io::SubstrateHostFunctions,
+	cumulus_primitives_storage_weight_reclaim::storage_proof_size::HostFunctions,
+);
 
 #[cfg(feature = "runtime-benchmarks")]
-type HostFunctions =
-	(sp_io::SubstrateHostFunctions, frame_benchmarking::benchmarking::HostFunctions);
+type HostFunctions = (
+	sp_io::SubstrateHostFunctions,
+	cumulus_primitives_storage_weight_reclaim::storage_proof_size::HostFunctions,
+	frame_benchmarking::benchmarking::HostFunctions,
+);
 
 type ParachainClient<RuntimeApi> = TFullClient<Block, RuntimeApi, WasmExecutor<HostFunctions>>;
 
@@ -226,6 +238,21 @@ impl sc_executor::NativeExecutionDispatch for GluttonRuntimeExecutor {
 	}
 }
 
+/// Native Penpal executor instance.
+pub struct PenpalRuntimeExecutor;
+
+impl sc_executor::NativeExecutionDispatch for PenpalRuntimeExecutor {
+	type ExtendHostFunctions = frame_benchmarking::benchmarking::HostFunctions;
+
+	fn dispatch(method: &str, data: &[u8]) -> Option<Vec<u8>> {
+		penpal_runtime::api::dispatch(method, data)
+	}
+
+	fn native_version() -> sc_executor::NativeVersion {
+		penpal_runtime::native_version()
+	}
+}
+
 /// Starts a `ServiceBuilder` for a full service.
 ///
 /// Use this macro if you don't actually need the full service, but just the builder in order to
@@ -420,8 +447,21 @@ where
 		})
 		.await?;
 
-	let rpc_client = client.clone();
-	let rpc_builder = Box::new(move |_, _| rpc_ext_builder(rpc_client.clone()));
+	let sync_state_provider =
+		SyncStateProvider::new(client.clone(), relay_chain_interface.clone(), sync_service.clone());
+
+	let rpc_builder = {
+		let rpc_client = client.clone();
+		let sync_state_provider = sync_state_provider.clone();
+
+		Box::new(move |_, _| {
+			let mut module = rpc_ext_builder(rpc_client.clone())?;
+			module
+				.merge(sync_state_provider.clone().into_rpc())
+				.map_err(|e| sc_service::Error::Application(Box::new(e)))?;
+			Ok(module)
+		})
+	};
 
 	sc_service::spawn_tasks(sc_service::SpawnTasksParams {
 		rpc_builder,
@@ -459,6 +499,12 @@ where
 		Arc::new(move |hash, data| sync_service.announce_block(hash, data))
 	};
 
+	task_manager.spawn_handle().spawn(
+		"cumulus-informant",
+		None,
+		sync_state_provider.clone().run_informant(),
+	);
+
 	let relay_chain_slot_duration = Duration::from_secs(6);
 
 	let overseer_handle = relay_chain_interface
@@ -471,6 +517,10 @@ where
 		para_id,
 		relay_chain_interface: relay_chain_interface.clone(),
 		task_manager: &mut task_manager,
+		pov_recovery_concurrency: collator_options.pov_recovery_concurrency,
+		network: Some(network.clone()),
+		prometheus_registry: prometheus_registry.as_ref(),
+		pov_recovery_backlog: Some(sync_state_provider.pov_recovery_backlog()),
 		da_recovery_profile: if validator {
 			DARecoveryProfile::Collator
 		} else {
@@ -531,7 +581,8 @@ where
 		+ sp_block_builder::BlockBuilder<Block>
 		+ cumulus_primitives_core::CollectCollationInfo<Block>
 		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
-		+ frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+		+ frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
+		+ parachains_common::xcm_payment_api::XcmPaymentApi<Block>,
 	RB: Fn(Arc<ParachainClient<RuntimeApi>>) -> Result<jsonrpsee::RpcModule<()>, sc_service::Error>,
 	BIQ: FnOnce(
 		Arc<ParachainClient<RuntimeApi>>,
@@ -542,6 +593,7 @@ where
 	) -> Result<sc_consensus::DefaultImportQueue<Block>, sc_service::Error>,
 	SC: FnOnce(
 		Arc<ParachainClient<RuntimeApi>>,
+		Arc<ParachainBackend>,
 		ParachainBlockImport<RuntimeApi>,
 		Option<&Registry>,
 		Option<TelemetryHandle>,
@@ -555,6 +607,7 @@ where
 		CollatorPair,
 		OverseerHandle,
 		Arc<dyn Fn(Hash, Option<Vec<u8>>) + Send + Sync>,
+		AuthoringKeys,
 	) -> Result<(), sc_service::Error>,
 {
 	let parachain_config = prepare_node_config(parachain_config);
@@ -597,9 +650,17 @@ where
 		})
 		.await?;
 
+	let sync_state_provider =
+		SyncStateProvider::new(client.clone(), relay_chain_interface.clone(), sync_service.clone());
+
+	let authoring_keys = AuthoringKeys::default();
+
 	let rpc_builder = {
 		let client = client.clone();
 		let transaction_pool = transaction_pool.clone();
+		let sync_state_provider = sync_state_provider.clone();
+		let authoring_keys = authoring_keys.clone();
+		let keystore = params.keystore_container.keystore();
 
 		let backend_for_rpc = backend.clone();
 		Box::new(move |deny_unsafe, _| {
@@ -609,7 +670,17 @@ where
 				deny_unsafe,
 			};
 
-			rpc::create_full(deps, backend_for_rpc.clone()).map_err(Into::into)
+			let mut module = rpc::create_full(deps, backend_for_rpc.clone())?;
+			module
+				.merge(sync_state_provider.clone().into_rpc())
+				.map_err(|e| sc_service::Error::Application(Box::new(e)))?;
+			module
+				.merge(
+					AuraKeyRotation::new(authoring_keys.clone(), keystore.clone(), deny_unsafe)
+						.into_rpc(),
+				)
+				.map_err(|e| sc_service::Error::Application(Box::new(e)))?;
+			Ok(module)
 		})
 	};
 
@@ -649,6 +720,12 @@ where
 		Arc::new(move |hash, data| sync_service.announce_block(hash, data))
 	};
 
+	task_manager.spawn_handle().spawn(
+		"cumulus-informant",
+		None,
+		sync_state_provider.clone().run_informant(),
+	);
+
 	let relay_chain_slot_duration = Duration::from_secs(6);
 
 	let overseer_handle = relay_chain_interface
@@ -661,6 +738,10 @@ where
 		para_id,
 		relay_chain_interface: relay_chain_interface.clone(),
 		task_manager: &mut task_manager,
+		pov_recovery_concurrency: collator_options.pov_recovery_concurrency,
+		network: Some(network.clone()),
+		prometheus_registry: prometheus_registry.as_ref(),
+		pov_recovery_backlog: Some(sync_state_provider.pov_recovery_backlog()),
 		da_recovery_profile: if validator {
 			DARecoveryProfile::Collator
 		} else {
@@ -675,6 +756,7 @@ where
 	if validator {
 		start_consensus(
 			client.clone(),
+			backend.clone(),
 			block_import,
 			prometheus_registry.as_ref(),
 			telemetry.as_ref().map(|t| t.handle()),
@@ -688,6 +770,7 @@ where
 			collator_key.expect("Command line arguments do not allow this. qed"),
 			overseer_handle,
 			announce_block,
+			authoring_keys,
 		)?;
 	}
 
@@ -741,10 +824,15 @@ pub async fn start_rococo_parachain_node(
 	collator_options: CollatorOptions,
 	para_id: ParaId,
 	hwbench: Option<sc_sysinfo::HwBench>,
+	authoring_duration: Duration,
+	use_slot_based_collator: bool,
 ) -> sc_service::error::Result<(
 	TaskManager,
 	Arc<ParachainClient<rococo_parachain_runtime::RuntimeApi>>,
 )> {
+	// `--force-authoring` keeps authoring, even if the relay-chain finality stalls.
+	let finality_backoff = (!parachain_config.force_authoring).then(RelayFinalityBackoff::default);
+
 	start_node_impl::<rococo_parachain_runtime::RuntimeApi, _, _, _>(
 		parachain_config,
 		polkadot_config,
@@ -754,6 +842,7 @@ pub async fn start_rococo_parachain_node(
 		|_| Ok(RpcModule::new(())),
 		rococo_parachain_build_import_queue,
 		|client,
+		 backend,
 		 block_import,
 		 prometheus_registry,
 		 telemetry,
@@ -766,7 +855,8 @@ pub async fn start_rococo_parachain_node(
 		 para_id,
 		 collator_key,
 		 overseer_handle,
-		 announce_block| {
+		 announce_block,
+		 authoring_keys| {
 			let slot_duration = cumulus_client_consensus_aura::slot_duration(&*client)?;
 
 			let proposer_factory = sc_basic_authorship::ProposerFactory::with_proof_recording(
@@ -785,11 +875,25 @@ pub async fn start_rococo_parachain_node(
 				client.clone(),
 			);
 
-			let params = BasicAuraParams {
+			// Activates the Aura keys, that are scheduled with `cumulus_rotateAuraKey`.
+			task_manager.spawn_handle().spawn(
+				"aura-key-rotation",
+				None,
+				key_rotation::track_aura_authorities::<_, _, sp_consensus_aura::sr25519::AuthorityId>(
+					client.clone(),
+					authoring_keys.clone(),
+				),
+			);
+
+			let params = LookaheadAuraParams {
 				create_inherent_data_providers: move |_, ()| async move { Ok(()) },
 				block_import,
-				para_client: client,
+				para_client: client.clone(),
+				para_backend: backend,
 				relay_client: relay_chain_interface,
+				code_hash_provider: move |block_hash| {
+					client.code_at(block_hash).ok().map(ValidationCode).map(|c| c.hash())
+				},
 				sync_oracle,
 				keystore,
 				collator_key,
@@ -799,22 +903,42 @@ pub async fn start_rococo_parachain_node(
 				relay_chain_slot_duration,
 				proposer,
 				collator_service,
-				// Very limited proposal time.
-				authoring_duration: Duration::from_millis(500),
+				authoring_duration,
+				authoring_keys: Some(authoring_keys),
+				finality_backoff,
 			};
 
-			let fut = basic_aura::run::<
-				Block,
-				sp_consensus_aura::sr25519::AuthorityPair,
-				_,
-				_,
-				_,
-				_,
-				_,
-				_,
-				_,
-			>(params);
-			task_manager.spawn_essential_handle().spawn("aura", None, fut);
+			if use_slot_based_collator {
+				let fut = slot_based_aura::run::<
+					Block,
+					sp_consensus_aura::sr25519::AuthorityPair,
+					_,
+					_,
+					_,
+					_,
+					_,
+					_,
+					_,
+					_,
+					_,
+				>(params);
+				task_manager.spawn_essential_handle().spawn("aura", None, fut);
+			} else {
+				let fut = lookahead_aura::run::<
+					Block,
+					sp_consensus_aura::sr25519::AuthorityPair,
+					_,
+					_,
+					_,
+					_,
+					_,
+					_,
+					_,
+					_,
+					_,
+				>(params);
+				task_manager.spawn_essential_handle().spawn("aura", None, fut);
+			}
 
 			Ok(())
 		},
@@ -1133,10 +1257,14 @@ where
 		+ cumulus_primitives_core::CollectCollationInfo<Block>
 		+ sp_consensus_aura::AuraApi<Block, <<AuraId as AppCrypto>::Pair as Pair>::Public>
 		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
-		+ frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+		+ frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
+		+ parachains_common::xcm_payment_api::XcmPaymentApi<Block>,
 	<<AuraId as AppCrypto>::Pair as Pair>::Signature:
 		TryFrom<Vec<u8>> + std::hash::Hash + sp_runtime::traits::Member + Codec,
 {
+	// `--force-authoring` keeps authoring, even if the relay-chain finality stalls.
+	let finality_backoff = (!parachain_config.force_authoring).then(RelayFinalityBackoff::default);
+
 	start_node_impl::<RuntimeApi, _, _, _>(
 		parachain_config,
 		polkadot_config,
@@ -1146,6 +1274,7 @@ where
 		|_| Ok(RpcModule::new(())),
 		aura_build_import_queue::<_, AuraId>,
 		|client,
+		 _backend,
 		 block_import,
 		 prometheus_registry,
 		 telemetry,
@@ -1158,7 +1287,8 @@ where
 		 para_id,
 		 collator_key,
 		 overseer_handle,
-		 announce_block| {
+		 announce_block,
+		 authoring_keys| {
 			let slot_duration = cumulus_client_consensus_aura::slot_duration(&*client)?;
 
 			let proposer_factory = sc_basic_authorship::ProposerFactory::with_proof_recording(
@@ -1177,6 +1307,17 @@ where
 				client.clone(),
 			);
 
+			// Activates the Aura keys, that are scheduled with `cumulus_rotateAuraKey`.
+			task_manager.spawn_handle().spawn(
+				"aura-key-rotation",
+				None,
+				key_rotation::track_aura_authorities::<
+					_,
+					_,
+					<<AuraId as AppCrypto>::Pair as Pair>::Public,
+				>(client.clone(), authoring_keys.clone()),
+			);
+
 			let params = BasicAuraParams {
 				create_inherent_data_providers: move |_, ()| async move { Ok(()) },
 				block_import,
@@ -1193,6 +1334,8 @@ where
 				collator_service,
 				// Very limited proposal time.
 				authoring_duration: Duration::from_millis(500),
+				authoring_keys: Some(authoring_keys),
+				finality_backoff,
 			};
 
 			let fut =
@@ -1252,6 +1395,7 @@ where
 		CollatorPair,
 		OverseerHandle,
 		Arc<dyn Fn(Hash, Option<Vec<u8>>) + Send + Sync>,
+		AuthoringKeys,
 	) -> Result<(), sc_service::Error>,
 {
 	let parachain_config = prepare_node_config(parachain_config);
@@ -1294,9 +1438,17 @@ where
 		})
 		.await?;
 
+	let sync_state_provider =
+		SyncStateProvider::new(client.clone(), relay_chain_interface.clone(), sync_service.clone());
+
+	let authoring_keys = AuthoringKeys::default();
+
 	let rpc_builder = {
 		let client = client.clone();
 		let transaction_pool = transaction_pool.clone();
+		let sync_state_provider = sync_state_provider.clone();
+		let authoring_keys = authoring_keys.clone();
+		let keystore = params.keystore_container.keystore();
 
 		Box::new(move |deny_unsafe, _| {
 			let deps = crate::rpc::FullDeps {
@@ -1305,7 +1457,17 @@ where
 				deny_unsafe,
 			};
 
-			crate::rpc::create_contracts_rococo(deps).map_err(Into::into)
+			let mut module = crate::rpc::create_contracts_rococo(deps)?;
+			module
+				.merge(sync_state_provider.clone().into_rpc())
+				.map_err(|e| sc_service::Error::Application(Box::new(e)))?;
+			module
+				.merge(
+					AuraKeyRotation::new(authoring_keys.clone(), keystore.clone(), deny_unsafe)
+						.into_rpc(),
+				)
+				.map_err(|e| sc_service::Error::Application(Box::new(e)))?;
+			Ok(module)
 		})
 	};
 
@@ -1345,6 +1507,12 @@ where
 		Arc::new(move |hash, data| sync_service.announce_block(hash, data))
 	};
 
+	task_manager.spawn_handle().spawn(
+		"cumulus-informant",
+		None,
+		sync_state_provider.clone().run_informant(),
+	);
+
 	let relay_chain_slot_duration = Duration::from_secs(6);
 
 	let overseer_handle = relay_chain_interface
@@ -1357,6 +1525,10 @@ where
 		para_id,
 		relay_chain_interface: relay_chain_interface.clone(),
 		task_manager: &mut task_manager,
+		pov_recovery_concurrency: collator_options.pov_recovery_concurrency,
+		network: Some(network.clone()),
+		prometheus_registry: prometheus_registry.as_ref(),
+		pov_recovery_backlog: Some(sync_state_provider.pov_recovery_backlog()),
 		da_recovery_profile: if validator {
 			DARecoveryProfile::Collator
 		} else {
@@ -1384,6 +1556,7 @@ where
 			collator_key.expect("Command line arguments do not allow this. qed"),
 			overseer_handle,
 			announce_block,
+			authoring_keys,
 		)?;
 	}
 
@@ -1441,6 +1614,9 @@ pub async fn start_contracts_rococo_node(
 	TaskManager,
 	Arc<ParachainClient<contracts_rococo_runtime::RuntimeApi>>,
 )> {
+	// `--force-authoring` keeps authoring, even if the relay-chain finality stalls.
+	let finality_backoff = (!parachain_config.force_authoring).then(RelayFinalityBackoff::default);
+
 	start_contracts_rococo_node_impl::<contracts_rococo_runtime::RuntimeApi, _, _, _>(
 		parachain_config,
 		polkadot_config,
@@ -1462,7 +1638,8 @@ pub async fn start_contracts_rococo_node(
 		 para_id,
 		 collator_key,
 		 overseer_handle,
-		 announce_block| {
+		 announce_block,
+		 authoring_keys| {
 			let slot_duration = cumulus_client_consensus_aura::slot_duration(&*client)?;
 
 			let proposer_factory = sc_basic_authorship::ProposerFactory::with_proof_recording(
@@ -1481,6 +1658,16 @@ pub async fn start_contracts_rococo_node(
 				client.clone(),
 			);
 
+			// Activates the Aura keys, that are scheduled with `cumulus_rotateAuraKey`.
+			task_manager.spawn_handle().spawn(
+				"aura-key-rotation",
+				None,
+				key_rotation::track_aura_authorities::<_, _, sp_consensus_aura::sr25519::AuthorityId>(
+					client.clone(),
+					authoring_keys.clone(),
+				),
+			);
+
 			let params = BasicAuraParams {
 				create_inherent_data_providers: move |_, ()| async move { Ok(()) },
 				block_import,
@@ -1497,6 +1684,8 @@ pub async fn start_contracts_rococo_node(
 				collator_service,
 				// Very limited proposal time.
 				authoring_duration: Duration::from_millis(500),
+				authoring_keys: Some(authoring_keys),
+				finality_backoff,
 			};
 
 			let fut = basic_aura::run::<
@@ -51,3 +51,42 @@ async fn purge_chain_works() {
 	assert!(base_dir.path().join("polkadot/chains/rococo_local_testnet").exists());
 	assert!(!base_dir.path().join("polkadot/chains/rococo_local_testnet/db/full").exists());
 }
+
+#[tokio::test]
+#[cfg(unix)]
+#[ignore]
+async fn purge_chain_keep_relay_works() {
+	// Check that a dry run deletes nothing and that `--keep-relay` only deletes the parachain
+	// database
+
+	let base_dir = tempdir().expect("could not create a temp dir");
+	let base_dir_path = format!("{}/polkadot", base_dir.path().display());
+
+	let args = &["--", "-d", &base_dir_path, "--chain=rococo-local"];
+
+	common::run_node_for_a_while(base_dir.path(), args, SIGINT).await;
+
+	let para_db = base_dir.path().join("chains/local_testnet/db/full");
+	let relay_db = base_dir.path().join("polkadot/chains/rococo_local_testnet/db/full");
+	assert!(para_db.exists());
+	assert!(relay_db.exists());
+
+	let status = Command::new(cargo_bin("polkadot-parachain"))
+		.args(["purge-chain", "--dry-run", "-d"])
+		.arg(base_dir.path())
+		.status()
+		.unwrap();
+	assert!(status.success());
+	assert!(para_db.exists());
+	assert!(relay_db.exists());
+
+	let status = Command::new(cargo_bin("polkadot-parachain"))
+		.args(["purge-chain", "--keep-relay", "-d"])
+		.arg(base_dir.path())
+		.arg("-y")
+		.status()
+		.unwrap();
+	assert!(status.success());
+	assert!(!para_db.exists());
+	assert!(relay_db.exists());
+}
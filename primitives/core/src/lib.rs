@@ -18,11 +18,11 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use polkadot_parachain::primitives::HeadData;
 use scale_info::TypeInfo;
 use sp_runtime::RuntimeDebug;
-use sp_std::prelude::*;
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 pub use polkadot_core_primitives::InboundDownwardMessage;
 pub use polkadot_parachain::primitives::{
@@ -41,6 +41,8 @@ pub use sp_runtime::{
 
 pub use xcm::latest::prelude::*;
 
+pub mod shuffling;
+
 /// A module that re-exports relevant relay chain definitions.
 pub mod relay_chain {
 	pub use polkadot_core_primitives::*;
@@ -112,6 +114,22 @@ impl UpwardMessageSender for () {
 	}
 }
 
+/// Something that stores the genesis hash of the relay chain, that the parachain is connected to.
+pub trait RelayChainGenesisHashStore {
+	/// Returns the stored genesis hash of the relay chain, if any.
+	fn relay_chain_genesis_hash() -> Option<relay_chain::Hash>;
+	/// Store the genesis hash of the relay chain.
+	fn set_relay_chain_genesis_hash(genesis_hash: relay_chain::Hash);
+}
+
+impl RelayChainGenesisHashStore for () {
+	fn relay_chain_genesis_hash() -> Option<relay_chain::Hash> {
+		None
+	}
+
+	fn set_relay_chain_genesis_hash(_genesis_hash: relay_chain::Hash) {}
+}
+
 /// The status of a channel.
 pub enum ChannelStatus {
 	/// Channel doesn't exist/has been closed.
@@ -149,6 +167,32 @@ pub enum ServiceQuality {
 	Fast,
 }
 
+/// The origin of the inbound message, that is queued by the `pallet-message-queue`.
+#[derive(Eq, PartialEq, Clone, Copy, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub enum AggregateMessageOrigin {
+	/// The message came from the parachain itself.
+	Here,
+	/// The message came from the relay chain.
+	///
+	/// This is used by the DMP queue.
+	Parent,
+	/// The message came from a sibling parachain.
+	///
+	/// This is used by the XCMP queue.
+	Sibling(ParaId),
+}
+
+impl From<AggregateMessageOrigin> for MultiLocation {
+	fn from(origin: AggregateMessageOrigin) -> Self {
+		match origin {
+			AggregateMessageOrigin::Here => MultiLocation::here(),
+			AggregateMessageOrigin::Parent => MultiLocation::parent(),
+			AggregateMessageOrigin::Sibling(id) =>
+				MultiLocation::new(1, Junction::Parachain(id.into())),
+		}
+	}
+}
+
 /// The parachain block that is created by a collator.
 ///
 /// This is send as PoV (proof of validity block) to the relay-chain validators. There it will be
@@ -213,6 +257,16 @@ pub enum CumulusDigestItem {
 	/// A digest item indicating the relay-parent a parachain block was built against.
 	#[codec(index = 0)]
 	RelayParent(relay_chain::Hash),
+	/// A digest item carrying the proof, that the block has been built by the collator, i.e.
+	/// its signature over the [`authorship_proof_payload`].
+	///
+	/// The encoding of the proof is defined by the verifier of the runtime.
+	#[codec(index = 1)]
+	AuthorshipProof(Vec<u8>),
+	/// A digest item committing to the seed, that has been used to shuffle the extrinsics of the
+	/// block.
+	#[codec(index = 2)]
+	ShufflingSeed([u8; 32]),
 }
 
 impl CumulusDigestItem {
@@ -237,6 +291,48 @@ pub fn extract_relay_parent(digest: &Digest) -> Option<relay_chain::Hash> {
 	})
 }
 
+/// Extract the block authorship proof from the provided header digest. Returns `None` if none were
+/// found.
+pub fn extract_authorship_proof(digest: &Digest) -> Option<Vec<u8>> {
+	digest.convert_first(|d| match d {
+		DigestItem::Consensus(id, val) if id == &CUMULUS_CONSENSUS_ID =>
+			match CumulusDigestItem::decode(&mut &val[..]) {
+				Ok(CumulusDigestItem::AuthorshipProof(proof)) => Some(proof),
+				_ => None,
+			},
+		_ => None,
+	})
+}
+
+/// Extract the extrinsics shuffling seed from the provided header digest. Returns `None` if none
+/// were found.
+pub fn extract_shuffling_seed(digest: &Digest) -> Option<[u8; 32]> {
+	digest.convert_first(|d| match d {
+		DigestItem::Consensus(id, val) if id == &CUMULUS_CONSENSUS_ID =>
+			match CumulusDigestItem::decode(&mut &val[..]) {
+				Ok(CumulusDigestItem::ShufflingSeed(seed)) => Some(seed),
+				_ => None,
+			},
+		_ => None,
+	})
+}
+
+/// The context of the block authorship proof signatures.
+const AUTHORSHIP_PROOF_CONTEXT: &[u8] = b"cumulus-authorship-proof";
+
+/// Returns the payload, that is signed by the collator to prove the authorship of the block with
+/// the given `number`, that is built on top of the `parent_hash` in the given parachain `slot`.
+///
+/// Only the parts of the header, that are known before the block is built, are signed. The slot
+/// binds the proof to the slot author, so the proof can't be reused in another slot.
+pub fn authorship_proof_payload<Hash: Encode, Number: Encode>(
+	parent_hash: &Hash,
+	number: &Number,
+	slot: u64,
+) -> Vec<u8> {
+	(AUTHORSHIP_PROOF_CONTEXT, parent_hash, number, slot).encode()
+}
+
 /// Utilities for handling the relay-parent storage root as a digest item.
 ///
 /// This is not intended to be part of the public API, as it is a workaround for
@@ -307,7 +403,7 @@ pub struct CollationInfoV1 {
 impl CollationInfoV1 {
 	/// Convert into the latest version of the [`CollationInfo`] struct.
 	pub fn into_latest(self, head_data: HeadData) -> CollationInfo {
-		CollationInfo {
+		CollationInfoV2 {
 			upward_messages: self.upward_messages,
 			horizontal_messages: self.horizontal_messages,
 			new_validation_code: self.new_validation_code,
@@ -315,9 +411,92 @@ impl CollationInfoV1 {
 			hrmp_watermark: self.hrmp_watermark,
 			head_data,
 		}
+		.into_latest()
+	}
+}
+
+/// Information about a collation.
+///
+/// This was used in version 2 of the [`CollectCollationInfo`] runtime api.
+#[derive(Clone, Debug, codec::Decode, codec::Encode, PartialEq, TypeInfo)]
+pub struct CollationInfoV2 {
+	/// Messages destined to be interpreted by the Relay chain itself.
+	pub upward_messages: Vec<UpwardMessage>,
+	/// The horizontal messages sent by the parachain.
+	pub horizontal_messages: Vec<OutboundHrmpMessage>,
+	/// New validation code.
+	pub new_validation_code: Option<relay_chain::ValidationCode>,
+	/// The number of messages processed from the DMQ.
+	pub processed_downward_messages: u32,
+	/// The mark which specifies the block number up to which all inbound HRMP messages are
+	/// processed.
+	pub hrmp_watermark: relay_chain::BlockNumber,
+	/// The head data, aka encoded header, of the block that corresponds to the collation.
+	pub head_data: HeadData,
+}
+
+impl CollationInfoV2 {
+	/// Convert into the latest version of the [`CollationInfo`] struct.
+	///
+	/// The footprint is derived from the messages and the validation code, the limits are unknown.
+	pub fn into_latest(self) -> CollationInfo {
+		let footprint = message_footprint(
+			&self.upward_messages,
+			&self.horizontal_messages,
+			self.new_validation_code.is_some(),
+		);
+		CollationInfo {
+			upward_messages: self.upward_messages,
+			horizontal_messages: self.horizontal_messages,
+			new_validation_code: self.new_validation_code,
+			processed_downward_messages: self.processed_downward_messages,
+			hrmp_watermark: self.hrmp_watermark,
+			head_data: self.head_data,
+			footprint,
+			limits: Default::default(),
+		}
 	}
 }
 
+/// A resource that is consumed by a collation.
+#[derive(
+	Clone, Copy, Debug, codec::Decode, codec::Encode, PartialEq, Eq, PartialOrd, Ord, TypeInfo,
+)]
+pub enum CollationResource {
+	/// The total size of the upward messages, in bytes.
+	UmpBytes,
+	/// The total size of the horizontal messages sent to the given recipient, in bytes.
+	HrmpBytes(ParaId),
+	/// Whether a validation code upgrade is signalled, `1` if it is.
+	CodeUpgrade,
+	/// The proof size used by the block.
+	ProofSize,
+}
+
+/// The amount of each resource, resources that are not in the map amount to zero.
+pub type ResourceFootprint = BTreeMap<CollationResource, u64>;
+
+/// The footprint of the given messages and of the validation code upgrade, if it is signalled.
+pub fn message_footprint(
+	upward_messages: &[UpwardMessage],
+	horizontal_messages: &[OutboundHrmpMessage],
+	code_upgrade: bool,
+) -> ResourceFootprint {
+	let mut footprint = ResourceFootprint::new();
+	let mut add = |resource, amount: usize| {
+		let total = footprint.entry(resource).or_default();
+		*total = total.saturating_add(amount as u64);
+	};
+	upward_messages.iter().for_each(|m| add(CollationResource::UmpBytes, m.len()));
+	horizontal_messages
+		.iter()
+		.for_each(|m| add(CollationResource::HrmpBytes(m.recipient), m.data.len()));
+	if code_upgrade {
+		add(CollationResource::CodeUpgrade, 1);
+	}
+	footprint
+}
+
 /// Information about a collation.
 #[derive(Clone, Debug, codec::Decode, codec::Encode, PartialEq, TypeInfo)]
 pub struct CollationInfo {
@@ -334,19 +513,53 @@ pub struct CollationInfo {
 	pub hrmp_watermark: relay_chain::BlockNumber,
 	/// The head data, aka encoded header, of the block that corresponds to the collation.
 	pub head_data: HeadData,
+	/// The resources consumed by the collation.
+	pub footprint: ResourceFootprint,
+	/// The limits of the resources, as known to the runtime when the block was built.
+	///
+	/// Resources without a limit are not limited.
+	pub limits: ResourceFootprint,
+}
+
+impl CollationInfo {
+	/// The first resource of which the collation consumes more than its limit, if any.
+	pub fn exceeded_resource(&self) -> Option<CollationResource> {
+		self.footprint.iter().find_map(|(resource, amount)| {
+			self.limits.get(resource).filter(|limit| amount > *limit).map(|_| *resource)
+		})
+	}
 }
 
 sp_api::decl_runtime_apis! {
 	/// Runtime api to collect information about a collation.
-	#[api_version(2)]
+	#[api_version(3)]
 	pub trait CollectCollationInfo {
 		/// Collect information about a collation.
 		#[changed_in(2)]
 		fn collect_collation_info() -> CollationInfoV1;
 		/// Collect information about a collation.
+		#[changed_in(3)]
+		fn collect_collation_info(header: &Block::Header) -> CollationInfoV2;
+		/// Collect information about a collation.
 		///
 		/// The given `header` is the header of the built block for that
 		/// we are collecting the collation info for.
 		fn collect_collation_info(header: &Block::Header) -> CollationInfo;
 	}
+
+	/// Runtime api to query the unincluded segment of the parachain.
+	///
+	/// The unincluded segment is the chain of blocks built on top of the latest included block,
+	/// which have not been included into the relay chain yet.
+	pub trait UnincludedSegmentApi {
+		/// Returns the maximum number of blocks that may be unincluded at once, including the
+		/// block being built.
+		fn unincluded_segment_capacity() -> u32;
+		/// Returns the length of the unincluded segment, assuming the given block is the most
+		/// recently included one.
+		///
+		/// If the segment doesn't contain the given block, the length of the entire segment is
+		/// returned.
+		fn unincluded_segment_size_after(included_hash: Block::Hash) -> u32;
+	}
 }
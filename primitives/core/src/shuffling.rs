@@ -0,0 +1,198 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Deterministic shuffling of the extrinsics of parachain blocks.
+//!
+//! To mitigate front-running, e.g. on DEX parachains, the collator doesn't choose the order of
+//! the transactions in the block. Instead they are shuffled with a seed, that is derived from the
+//! relay parent storage root (which depends on the randomness of the relay chain) and the parent
+//! hash of the block. The seed is committed in the [`CumulusDigestItem::ShufflingSeed`] digest.
+//!
+//! The leading unsigned extrinsics of the block (the inherents) keep their positions. The
+//! remaining extrinsics are sorted by their hashes and then shuffled.
+//!
+//! The order is checked by the nodes, that import the blocks, as well as in `validate_block`, so
+//! it is enforced by the relay chain validators.
+
+use crate::{extract_shuffling_seed, relay_chain, rpsr_digest::extract_relay_parent_storage_root};
+use codec::Encode;
+use sp_runtime::traits::{
+	BlakeTwo256, Block as BlockT, Extrinsic as ExtrinsicT, Hash, Header as HeaderT,
+};
+use sp_std::vec::Vec;
+
+#[cfg(doc)]
+use crate::CumulusDigestItem;
+
+/// The context of the shuffling seeds.
+const SHUFFLING_SEED_CONTEXT: &[u8] = b"cumulus-shuffling-seed";
+
+fn blake2_256(data: &[u8]) -> [u8; 32] {
+	BlakeTwo256::hash(data).into()
+}
+
+/// Returns the seed, that is used to shuffle the extrinsics of the block built on top of the
+/// `parent_hash` against the relay parent with the given storage root.
+pub fn shuffling_seed<H: Encode>(
+	relay_parent_storage_root: &relay_chain::Hash,
+	parent_hash: &H,
+) -> [u8; 32] {
+	(SHUFFLING_SEED_CONTEXT, relay_parent_storage_root, parent_hash).using_encoded(blake2_256)
+}
+
+/// Shuffles the `extrinsics` with the `seed`.
+///
+/// The result only depends on the set of the extrinsics and not on their order.
+pub fn shuffle_extrinsics<E: ExtrinsicT + Encode>(
+	mut extrinsics: Vec<E>,
+	seed: &[u8; 32],
+) -> Vec<E> {
+	let num_inherents = extrinsics.iter().take_while(|xt| xt.is_signed() == Some(false)).count();
+	let mut shuffled = extrinsics.split_off(num_inherents);
+	shuffled.sort_by_cached_key(|xt| xt.using_encoded(blake2_256));
+
+	let mut counter = 0u64;
+	let mut next_random = || {
+		counter += 1;
+		let random = (seed, counter).using_encoded(blake2_256);
+		u64::from_le_bytes(random[..8].try_into().expect("the hash has 32 bytes; qed"))
+	};
+	for i in (1..shuffled.len()).rev() {
+		let j = (next_random() % (i as u64 + 1)) as usize;
+		shuffled.swap(i, j);
+	}
+
+	extrinsics.append(&mut shuffled);
+	extrinsics
+}
+
+/// Verifies, that the `extrinsics` of the block with the given `header` are shuffled with the
+/// committed seed.
+///
+/// Blocks without the shuffling seed are only accepted, if the shuffling is not `required`.
+pub fn verify_shuffling<Block: BlockT>(
+	header: &Block::Header,
+	extrinsics: &[Block::Extrinsic],
+	required: bool,
+) -> Result<(), &'static str> {
+	let seed = match extract_shuffling_seed(header.digest()) {
+		Some(seed) => seed,
+		None if required => return Err("The block misses the shuffling seed"),
+		None => return Ok(()),
+	};
+
+	let (relay_parent_storage_root, _) = extract_relay_parent_storage_root(header.digest())
+		.ok_or("The block misses the relay parent storage root")?;
+	if seed != shuffling_seed(&relay_parent_storage_root, header.parent_hash()) {
+		return Err("Invalid shuffling seed")
+	}
+
+	if shuffle_extrinsics(extrinsics.to_vec(), &seed) != extrinsics {
+		return Err("The extrinsics are not shuffled with the committed seed")
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{rpsr_digest::relay_parent_storage_root_item, CumulusDigestItem};
+	use sp_runtime::{
+		testing::{Block, Header, TestXt, H256},
+		Digest,
+	};
+
+	type Extrinsic = TestXt<u64, ()>;
+
+	fn signed(call: u64) -> Extrinsic {
+		TestXt::new(call, Some((call, ())))
+	}
+
+	fn inherent(call: u64) -> Extrinsic {
+		TestXt::new(call, None)
+	}
+
+	fn header(parent_hash: H256, seed: Option<[u8; 32]>) -> Header {
+		let mut digest = Digest::default();
+		digest.push(relay_parent_storage_root_item(H256::repeat_byte(1), 7u32));
+		if let Some(seed) = seed {
+			digest.push(CumulusDigestItem::ShufflingSeed(seed).to_digest_item());
+		}
+		<Header as HeaderT>::new(1, Default::default(), Default::default(), parent_hash, digest)
+	}
+
+	#[test]
+	fn shuffling_only_depends_on_the_set_of_extrinsics() {
+		let seed = shuffling_seed(&H256::repeat_byte(1), &H256::repeat_byte(2));
+		let extrinsics = (0..20).map(signed).collect::<Vec<_>>();
+		let shuffled = shuffle_extrinsics(extrinsics.clone(), &seed);
+
+		assert_ne!(shuffled, extrinsics);
+		assert_eq!(shuffle_extrinsics(extrinsics.iter().rev().cloned().collect(), &seed), shuffled);
+		assert_eq!(shuffle_extrinsics(shuffled.clone(), &seed), shuffled);
+
+		let other_seed = shuffling_seed(&H256::repeat_byte(1), &H256::repeat_byte(3));
+		assert_ne!(shuffle_extrinsics(extrinsics, &other_seed), shuffled);
+	}
+
+	#[test]
+	fn inherents_keep_their_positions() {
+		let seed = shuffling_seed(&H256::repeat_byte(1), &H256::repeat_byte(2));
+		let extrinsics = vec![inherent(100), inherent(101)]
+			.into_iter()
+			.chain((0..10).map(signed))
+			.collect();
+
+		let shuffled = shuffle_extrinsics(extrinsics, &seed);
+
+		assert_eq!(shuffled[..2], [inherent(100), inherent(101)]);
+		assert!(shuffled[2..].iter().all(|xt| xt.is_signed() == Some(true)));
+	}
+
+	#[test]
+	fn verify_shuffling_works() {
+		let parent_hash = H256::repeat_byte(2);
+		let seed = shuffling_seed(&H256::repeat_byte(1), &parent_hash);
+		let extrinsics = (0..10).map(signed).collect::<Vec<_>>();
+		let shuffled = shuffle_extrinsics(extrinsics.clone(), &seed);
+
+		let header = header(parent_hash, Some(seed));
+		assert_eq!(verify_shuffling::<Block<Extrinsic>>(&header, &shuffled, true), Ok(()));
+		assert_eq!(
+			verify_shuffling::<Block<Extrinsic>>(&header, &extrinsics, true),
+			Err("The extrinsics are not shuffled with the committed seed"),
+		);
+
+		let header_with_other_seed = self::header(parent_hash, Some([0; 32]));
+		assert_eq!(
+			verify_shuffling::<Block<Extrinsic>>(&header_with_other_seed, &shuffled, true),
+			Err("Invalid shuffling seed"),
+		);
+	}
+
+	#[test]
+	fn seed_is_only_required_if_configured() {
+		let header = header(H256::repeat_byte(2), None);
+		let extrinsics = (0..10).map(signed).collect::<Vec<_>>();
+
+		assert_eq!(verify_shuffling::<Block<Extrinsic>>(&header, &extrinsics, false), Ok(()));
+		assert_eq!(
+			verify_shuffling::<Block<Extrinsic>>(&header, &extrinsics, true),
+			Err("The block misses the shuffling seed"),
+		);
+	}
+}
@@ -16,7 +16,7 @@
 
 //! Client side code for generating the parachain inherent.
 
-use crate::ParachainInherentData;
+use crate::{relay_storage_keys, ParachainInherentData};
 use codec::Decode;
 use cumulus_primitives_core::{
 	relay_chain::{self, Hash as PHash, HrmpChannelId},
@@ -28,10 +28,13 @@ const LOG_TARGET: &str = "parachain-inherent";
 
 /// Collect the relevant relay chain state in form of a proof for putting it into the validation
 /// data inherent.
+///
+/// The `additional_keys` are added to the proof on top of the keys that are always required.
 async fn collect_relay_storage_proof(
 	relay_chain_interface: &impl RelayChainInterface,
 	para_id: ParaId,
 	relay_parent: PHash,
+	additional_keys: Vec<Vec<u8>>,
 ) -> Option<sp_state_machine::StorageProof> {
 	use relay_chain::well_known_keys as relay_well_known_keys;
 
@@ -97,6 +100,9 @@ async fn collect_relay_storage_proof(
 		relay_well_known_keys::ONE_EPOCH_AGO_RANDOMNESS.to_vec(),
 		relay_well_known_keys::TWO_EPOCHS_AGO_RANDOMNESS.to_vec(),
 		relay_well_known_keys::CURRENT_SLOT.to_vec(),
+		relay_well_known_keys::EPOCH_INDEX.to_vec(),
+		relay_storage_keys::timestamp_now(),
+		relay_storage_keys::genesis_hash(),
 		relay_well_known_keys::ACTIVE_CONFIG.to_vec(),
 		relay_well_known_keys::dmq_mqc_head(para_id),
 		// TODO paritytech/polkadot#6283: Remove all usages of `relay_dispatch_queue_size`
@@ -117,6 +123,7 @@ async fn collect_relay_storage_proof(
 	relevant_keys.extend(egress_channels.into_iter().map(|recipient| {
 		relay_well_known_keys::hrmp_channels(HrmpChannelId { sender: para_id, recipient })
 	}));
+	relevant_keys.extend(additional_keys);
 
 	relay_chain_interface
 		.prove_read(relay_parent, &relevant_keys)
@@ -142,8 +149,38 @@ impl ParachainInherentData {
 		validation_data: &PersistedValidationData,
 		para_id: ParaId,
 	) -> Option<ParachainInherentData> {
-		let relay_chain_state =
-			collect_relay_storage_proof(relay_chain_interface, para_id, relay_parent).await?;
+		Self::create_at_with_additional_keys(
+			relay_parent,
+			relay_chain_interface,
+			validation_data,
+			para_id,
+			Vec::new(),
+		)
+		.await
+	}
+
+	/// Create the [`ParachainInherentData`] at the given `relay_parent`, with the
+	/// `additional_keys` of the relay chain storage included into the relay chain state proof.
+	///
+	/// This can be used to prove relay chain data, that is only interesting to some parachains,
+	/// e.g. the [`relay_storage_keys::account`] of the accounts which balances the runtime
+	/// reads.
+	///
+	/// Returns `None` if the creation failed.
+	pub async fn create_at_with_additional_keys(
+		relay_parent: PHash,
+		relay_chain_interface: &impl RelayChainInterface,
+		validation_data: &PersistedValidationData,
+		para_id: ParaId,
+		additional_keys: Vec<Vec<u8>>,
+	) -> Option<ParachainInherentData> {
+		let relay_chain_state = collect_relay_storage_proof(
+			relay_chain_interface,
+			para_id,
+			relay_parent,
+			additional_keys,
+		)
+		.await?;
 
 		let downward_messages = relay_chain_interface
 			.retrieve_dmq_contents(para_id, relay_parent)
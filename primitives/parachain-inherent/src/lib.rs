@@ -28,7 +28,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use cumulus_primitives_core::{
-	relay_chain::{BlakeTwo256, Hash as RelayHash, HashT as _},
+	relay_chain::{self, BlakeTwo256, Hash as RelayHash, HashT as _},
 	InboundDownwardMessage, InboundHrmpMessage, ParaId, PersistedValidationData,
 };
 
@@ -43,7 +43,10 @@ pub use client_side::*;
 #[cfg(feature = "std")]
 mod mock;
 #[cfg(feature = "std")]
-pub use mock::{MockValidationDataInherentDataProvider, MockXcmConfig};
+pub use mock::{
+	MockValidParams, MockValidationDataInherentDataProvider, MockXcmConfig, MockXcmInjector,
+	ParachainSystemName,
+};
 
 /// The identifier for the parachain inherent.
 pub const INHERENT_IDENTIFIER: InherentIdentifier = *b"sysi1337";
@@ -56,11 +59,13 @@ pub struct ParachainInherentData {
 	///
 	/// Specifically this witness contains the data for:
 	///
-	/// - the current slot number at the given relay parent
+	/// - the current slot number, epoch index and timestamp at the given relay parent
 	/// - active host configuration as per the relay parent,
 	/// - the relay dispatch queue sizes
 	/// - the list of egress HRMP channels (in the list of recipients form)
 	/// - the metadata for the egress HRMP channels
+	/// - any additional keys requested by the collator, e.g. the [`relay_storage_keys::account`]
+	///   of some relay chain accounts
 	pub relay_chain_state: sp_trie::StorageProof,
 	/// Downward messages in the order they were sent.
 	pub downward_messages: Vec<InboundDownwardMessage>,
@@ -70,6 +75,42 @@ pub struct ParachainInherentData {
 	pub horizontal_messages: BTreeMap<ParaId, Vec<InboundHrmpMessage>>,
 }
 
+/// Storage keys of the relay chain, that are not part of the
+/// [`well_known_keys`](relay_chain::well_known_keys), but are still useful to parachains.
+///
+/// The keys depend on the relay chain runtime pallets, so they may change with the relay chain
+/// runtime upgrades.
+pub mod relay_storage_keys {
+	use super::*;
+	use codec::Encode;
+	use sp_core::hashing::{blake2_128, twox_128, twox_64};
+
+	/// The storage key of the relay chain timestamp, set in the current block.
+	pub fn timestamp_now() -> Vec<u8> {
+		[twox_128(b"Timestamp"), twox_128(b"Now")].concat()
+	}
+
+	/// The storage key of the relay chain account information of the given `account`.
+	pub fn account(account: &relay_chain::AccountId) -> Vec<u8> {
+		let account: &[u8] = account.as_ref();
+		[&twox_128(b"System")[..], &twox_128(b"Account")[..], &blake2_128(account)[..], account]
+			.concat()
+	}
+
+	/// The storage key of the relay chain genesis hash, i.e. the hash of the block `0`, that is
+	/// kept in the block hashes of the relay chain forever.
+	pub fn genesis_hash() -> Vec<u8> {
+		let genesis: relay_chain::BlockNumber = 0;
+		[
+			&twox_128(b"System")[..],
+			&twox_128(b"BlockHash")[..],
+			&twox_64(&genesis.encode())[..],
+			&genesis.encode()[..],
+		]
+		.concat()
+	}
+}
+
 /// This struct provides ability to extend a message queue chain (MQC) and compute a new head.
 ///
 /// MQC is an instance of a [hash chain] applied to a message queue. Using a hash chain it's
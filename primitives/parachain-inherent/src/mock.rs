@@ -23,7 +23,10 @@ use sc_client_api::{Backend, StorageProvider};
 use sp_core::twox_128;
 use sp_inherents::{InherentData, InherentDataProvider};
 use sp_runtime::traits::Block;
-use std::collections::BTreeMap;
+use std::{
+	collections::BTreeMap,
+	sync::{Arc, Mutex},
+};
 
 use cumulus_test_relay_sproof_builder::RelayStateSproofBuilder;
 
@@ -97,6 +100,7 @@ pub struct MockXcmConfig {
 /// storage.
 ///
 /// The `Default` implementation sets the name to `ParachainSystem`.
+#[derive(Clone)]
 pub struct ParachainSystemName(pub Vec<u8>);
 
 impl Default for ParachainSystemName {
@@ -148,6 +152,112 @@ impl MockXcmConfig {
 	}
 }
 
+/// XCM messages to be injected into the next block built with [`MockValidParams`].
+///
+/// The injector is cheap to clone, so a handle can be kept by tests, or by an RPC, to inject
+/// messages while the node is running.
+#[derive(Clone, Default)]
+pub struct MockXcmInjector(Arc<Mutex<InjectedMessages>>);
+
+#[derive(Default)]
+struct InjectedMessages {
+	downward: Vec<Vec<u8>>,
+	horizontal: Vec<(ParaId, Vec<u8>)>,
+}
+
+impl MockXcmInjector {
+	/// Inject a downward message, as if it was sent by the relay chain.
+	pub fn inject_downward_message(&self, message: Vec<u8>) {
+		self.0.lock().expect("Injector lock is not poisoned").downward.push(message);
+	}
+
+	/// Inject a horizontal message, as if it was sent by the `sender` parachain.
+	pub fn inject_horizontal_message(&self, sender: ParaId, message: Vec<u8>) {
+		self.0
+			.lock()
+			.expect("Injector lock is not poisoned")
+			.horizontal
+			.push((sender, message));
+	}
+
+	/// Take the messages injected so far.
+	fn take(&self) -> (Vec<Vec<u8>>, Vec<(ParaId, Vec<u8>)>) {
+		let messages = std::mem::take(&mut *self.0.lock().expect("Injector lock is not poisoned"));
+		(messages.downward, messages.horizontal)
+	}
+}
+
+/// Parameters of a mocked relay chain, for nodes that run without any relay chain.
+///
+/// For example a `--dev` node that seals blocks instantly can create the parachain inherent data
+/// of every block with [`Self::provider`]. The relay parent number, the randomness and the epoch
+/// are derived from the parachain block number, as described on
+/// [`MockValidationDataInherentDataProvider`]. The message queues are empty, unless messages are
+/// injected with the [`MockXcmInjector`].
+#[derive(Clone)]
+pub struct MockValidParams<R = ()> {
+	/// The parachain id of the parachain being mocked.
+	pub para_id: ParaId,
+	/// The relay block in which the parachain appears to start.
+	pub relay_offset: u32,
+	/// The number of relay blocks that elapse between each parablock.
+	pub relay_blocks_per_para_block: u32,
+	/// Number of parachain blocks per relay chain epoch.
+	pub para_blocks_per_relay_epoch: u32,
+	/// Function to mock BABE one epoch ago randomness.
+	pub relay_randomness_config: R,
+	/// The name of the parachain system in the runtime.
+	pub parachain_system_name: ParachainSystemName,
+	/// The XCM messages to be injected into the next block.
+	pub xcm_injector: MockXcmInjector,
+}
+
+impl MockValidParams {
+	/// Mock a relay chain that starts at block 1000 and produces two blocks for every parachain
+	/// block, with ten parachain blocks per epoch.
+	pub fn new(para_id: ParaId) -> Self {
+		Self {
+			para_id,
+			relay_offset: 1000,
+			relay_blocks_per_para_block: 2,
+			para_blocks_per_relay_epoch: 10,
+			relay_randomness_config: (),
+			parachain_system_name: Default::default(),
+			xcm_injector: Default::default(),
+		}
+	}
+}
+
+impl<R: Clone> MockValidParams<R> {
+	/// Create the inherent data provider of the parachain block `current_para_block`, built on
+	/// top of `parent_block`.
+	///
+	/// The messages injected so far are taken and included in the block.
+	pub fn provider<B: Block, BE: Backend<B>, C: StorageProvider<B, BE>>(
+		&self,
+		client: &C,
+		parent_block: B::Hash,
+		current_para_block: u32,
+	) -> MockValidationDataInherentDataProvider<R> {
+		let (raw_downward_messages, raw_horizontal_messages) = self.xcm_injector.take();
+		MockValidationDataInherentDataProvider {
+			current_para_block,
+			relay_offset: self.relay_offset,
+			relay_blocks_per_para_block: self.relay_blocks_per_para_block,
+			para_blocks_per_relay_epoch: self.para_blocks_per_relay_epoch,
+			relay_randomness_config: self.relay_randomness_config.clone(),
+			xcm_config: MockXcmConfig::new(
+				client,
+				parent_block,
+				self.para_id,
+				self.parachain_system_name.clone(),
+			),
+			raw_downward_messages,
+			raw_horizontal_messages,
+		}
+	}
+}
+
 #[async_trait::async_trait]
 impl<R: Send + Sync + GenerateRandomness<u64>> InherentDataProvider
 	for MockValidationDataInherentDataProvider<R>
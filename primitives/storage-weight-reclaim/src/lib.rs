@@ -0,0 +1,204 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Cumulus storage weight reclaim primitives.
+//!
+//! Benchmarked proof sizes of the extrinsics are pessimistic, because they assume that every
+//! storage item is read from the trie. The [`StorageWeightReclaim`] signed extension measures the
+//! size of the storage proof that has actually been recorded while dispatching the extrinsic and
+//! corrects the proof size part of the block weight accordingly.
+//!
+//! The proof size is read with the [`storage_proof_size`] host function. It requires the
+//! [`ProofSizeExt`] to be registered by the node, which records the storage proof. If the
+//! extension is not registered, the host function returns [`PROOF_RECORDING_DISABLED`] and no
+//! weight is reclaimed.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::{DispatchInfo, PostDispatchInfo},
+	weights::Weight,
+};
+use frame_system::Config;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension},
+	transaction_validity::TransactionValidityError,
+	DispatchResult,
+};
+use sp_runtime_interface::runtime_interface;
+use sp_std::marker::PhantomData;
+
+#[cfg(feature = "std")]
+use sp_externalities::ExternalitiesExt;
+
+#[cfg(test)]
+mod tests;
+
+const LOG_TARGET: &str = "runtime::storage_reclaim";
+
+/// The value, returned by the [`storage_proof_size`] host function, if the proof recording is
+/// disabled.
+pub const PROOF_RECORDING_DISABLED: u64 = u64::MAX;
+
+/// Something that knows the size of the storage proof, recorded so far.
+#[cfg(feature = "std")]
+pub trait ProofSizeProvider {
+	/// Returns the estimated encoded size of the recorded storage proof.
+	fn estimate_encoded_size(&self) -> usize;
+}
+
+#[cfg(feature = "std")]
+impl<H: sp_core::Hasher> ProofSizeProvider for sp_trie::recorder::Recorder<H> {
+	fn estimate_encoded_size(&self) -> usize {
+		sp_trie::recorder::Recorder::estimate_encoded_size(self)
+	}
+}
+
+#[cfg(feature = "std")]
+sp_externalities::decl_extension! {
+	/// The extension that provides the size of the recorded storage proof to the runtime.
+	pub struct ProofSizeExt(Box<dyn ProofSizeProvider + Send + Sync>);
+}
+
+#[cfg(feature = "std")]
+impl ProofSizeExt {
+	/// Creates the extension, that takes the proof size from the given `recorder`.
+	pub fn new<T: ProofSizeProvider + Send + Sync + 'static>(recorder: T) -> Self {
+		ProofSizeExt(Box::new(recorder))
+	}
+
+	/// Returns the size of the storage proof, recorded so far.
+	pub fn storage_proof_size(&self) -> u64 {
+		self.0.estimate_encoded_size() as u64
+	}
+}
+
+/// Interface that provides access to the size of the recorded storage proof.
+#[runtime_interface]
+pub trait StorageProofSize {
+	/// Returns the size of the storage proof, recorded so far, or [`PROOF_RECORDING_DISABLED`]
+	/// if the storage proof is not recorded.
+	fn storage_proof_size(&mut self) -> u64 {
+		self.extension::<ProofSizeExt>()
+			.map_or(PROOF_RECORDING_DISABLED, |ext| ext.storage_proof_size())
+	}
+}
+
+/// Returns the size of the storage proof, recorded so far, or `None` if the storage proof is not
+/// recorded.
+pub fn get_proof_size() -> Option<u64> {
+	let proof_size = storage_proof_size::storage_proof_size();
+	(proof_size != PROOF_RECORDING_DISABLED).then_some(proof_size)
+}
+
+/// Signed extension, that corrects the proof size part of the block weight with the size of the
+/// storage proof, that has actually been recorded while dispatching the extrinsic.
+///
+/// It should be the last signed extension of the runtime, so that the storage accesses of other
+/// extensions are not attributed to the extrinsic.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct StorageWeightReclaim<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> StorageWeightReclaim<T> {
+	/// Creates new `SignedExtension` to reclaim the storage weight.
+	pub fn new() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for StorageWeightReclaim<T> {
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "StorageWeightReclaim")
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for StorageWeightReclaim<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+{
+	const IDENTIFIER: &'static str = "StorageWeightReclaim";
+
+	type AccountId = T::AccountId;
+	type Call = T::RuntimeCall;
+	type AdditionalSigned = ();
+	/// The size of the storage proof before the extrinsic has been dispatched.
+	type Pre = Option<u64>;
+
+	fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+		Ok(())
+	}
+
+	fn pre_dispatch(
+		self,
+		_who: &Self::AccountId,
+		_call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		Ok(get_proof_size())
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		_len: usize,
+		_result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		let pre_dispatch_proof_size = match pre {
+			Some(Some(pre_dispatch_proof_size)) => pre_dispatch_proof_size,
+			_ => return Ok(()),
+		};
+		let post_dispatch_proof_size = match get_proof_size() {
+			Some(post_dispatch_proof_size) => post_dispatch_proof_size,
+			None => {
+				log::debug!(
+					target: LOG_TARGET,
+					"Proof recording enabled during pre-dispatch, now disabled. This should not happen."
+				);
+				return Ok(())
+			},
+		};
+
+		let benchmarked_proof_size = post_info.calc_actual_weight(info).proof_size();
+		let consumed_proof_size = post_dispatch_proof_size.saturating_sub(pre_dispatch_proof_size);
+		log::trace!(
+			target: LOG_TARGET,
+			"Benchmarked proof size: {}, consumed proof size: {}",
+			benchmarked_proof_size,
+			consumed_proof_size,
+		);
+
+		frame_system::BlockWeight::<T>::mutate(|current| {
+			if consumed_proof_size < benchmarked_proof_size {
+				current.reduce(
+					Weight::from_parts(0, benchmarked_proof_size - consumed_proof_size),
+					info.class,
+				);
+			} else {
+				current.accrue(
+					Weight::from_parts(0, consumed_proof_size - benchmarked_proof_size),
+					info.class,
+				);
+			}
+		});
+
+		Ok(())
+	}
+}
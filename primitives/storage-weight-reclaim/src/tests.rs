@@ -0,0 +1,201 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{
+	assert_ok,
+	dispatch::DispatchClass,
+	traits::{ConstU32, ConstU64},
+};
+use sp_core::H256;
+use sp_externalities::Extensions;
+use sp_io::TestExternalities;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, StateVersion,
+};
+use sp_state_machine::{Ext, InMemoryBackend, OverlayedChanges, TrieBackendBuilder};
+use sp_trie::recorder::Recorder;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+/// Recorder for tests, that returns the given proof sizes one by one.
+struct TestRecorder {
+	proof_sizes: Vec<usize>,
+	counter: AtomicUsize,
+}
+
+impl ProofSizeProvider for TestRecorder {
+	fn estimate_encoded_size(&self) -> usize {
+		self.proof_sizes[self.counter.fetch_add(1, Ordering::Relaxed)]
+	}
+}
+
+fn new_test_ext(proof_sizes: Option<Vec<usize>>) -> TestExternalities {
+	let mut ext: TestExternalities =
+		frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into();
+	if let Some(proof_sizes) = proof_sizes {
+		ext.register_extension(ProofSizeExt::new(TestRecorder {
+			proof_sizes,
+			counter: AtomicUsize::new(0),
+		}));
+	}
+	ext
+}
+
+fn set_block_proof_size(proof_size: u64) {
+	frame_system::BlockWeight::<Test>::mutate(|current| {
+		current.set(Weight::from_parts(0, proof_size), DispatchClass::Normal)
+	});
+}
+
+fn block_proof_size() -> u64 {
+	frame_system::BlockWeight::<Test>::get().get(DispatchClass::Normal).proof_size()
+}
+
+fn dispatch(info: &DispatchInfo, post_info: &PostDispatchInfo) {
+	let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+	let pre = StorageWeightReclaim::<Test>::new().pre_dispatch(&1, &call, info, 0).unwrap();
+	assert_ok!(StorageWeightReclaim::<Test>::post_dispatch(Some(pre), info, post_info, 0, &Ok(())));
+}
+
+fn info_with_proof_size(proof_size: u64) -> DispatchInfo {
+	DispatchInfo { weight: Weight::from_parts(0, proof_size), ..Default::default() }
+}
+
+#[test]
+fn weight_is_reclaimed() {
+	new_test_ext(Some(vec![100, 300])).execute_with(|| {
+		set_block_proof_size(1000);
+
+		// 200 bytes are consumed instead of benchmarked 500
+		dispatch(&info_with_proof_size(500), &PostDispatchInfo::default());
+
+		assert_eq!(block_proof_size(), 700);
+	});
+}
+
+#[test]
+fn actual_weight_is_used_as_benchmarked_weight() {
+	new_test_ext(Some(vec![100, 300])).execute_with(|| {
+		set_block_proof_size(1000);
+
+		let post_info = PostDispatchInfo {
+			actual_weight: Some(Weight::from_parts(0, 300)),
+			..Default::default()
+		};
+		dispatch(&info_with_proof_size(500), &post_info);
+
+		assert_eq!(block_proof_size(), 900);
+	});
+}
+
+#[test]
+fn underestimated_weight_is_accrued() {
+	new_test_ext(Some(vec![100, 800])).execute_with(|| {
+		set_block_proof_size(1000);
+
+		// 700 bytes are consumed instead of benchmarked 500
+		dispatch(&info_with_proof_size(500), &PostDispatchInfo::default());
+
+		assert_eq!(block_proof_size(), 1200);
+	});
+}
+
+#[test]
+fn nothing_is_reclaimed_without_proof_recording() {
+	new_test_ext(None).execute_with(|| {
+		set_block_proof_size(1000);
+		assert_eq!(get_proof_size(), None);
+
+		dispatch(&info_with_proof_size(500), &PostDispatchInfo::default());
+
+		assert_eq!(block_proof_size(), 1000);
+	});
+}
+
+#[test]
+fn weight_is_reclaimed_with_the_recorded_storage_proof() {
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	storage.top.insert(b"key".to_vec(), vec![1; 100]);
+	let backend = InMemoryBackend::<BlakeTwo256>::from((storage, StateVersion::V1));
+	let root = *backend.root();
+
+	let recorder = Recorder::<BlakeTwo256>::default();
+	let backend = TrieBackendBuilder::new(backend.into_storage(), root)
+		.with_recorder(recorder.clone())
+		.build();
+	let mut overlay = OverlayedChanges::default();
+	let mut extensions = Extensions::default();
+	extensions.register(ProofSizeExt::new(recorder.clone()));
+	let mut ext = Ext::new(&mut overlay, &backend, Some(&mut extensions));
+
+	sp_externalities::set_and_run_with_externalities(&mut ext, || {
+		set_block_proof_size(20_000);
+
+		let info = info_with_proof_size(10_000);
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let pre = StorageWeightReclaim::<Test>::new().pre_dispatch(&1, &call, &info, 0).unwrap();
+		let pre_dispatch_proof_size = recorder.estimate_encoded_size() as u64;
+		assert_eq!(sp_io::storage::get(b"key").as_deref(), Some(&[1; 100][..]));
+		let consumed_proof_size = recorder.estimate_encoded_size() as u64 - pre_dispatch_proof_size;
+		assert_ok!(StorageWeightReclaim::<Test>::post_dispatch(
+			Some(pre),
+			&info,
+			&PostDispatchInfo::default(),
+			0,
+			&Ok(())
+		));
+
+		// the value is part of the proof, but it is much smaller than the benchmarked proof size
+		assert!(consumed_proof_size >= 100);
+		assert_eq!(block_proof_size(), 20_000 - 10_000 + consumed_proof_size);
+	});
+}
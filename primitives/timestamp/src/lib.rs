@@ -24,6 +24,12 @@
 //! the relay chain slot is derived from a timestamp, we can easily convert it back to a timestamp
 //! by muliplying it with the slot duration. By comparing the relay chain slot derived timestamp
 //! with the timestamp we can ensure that the parachain timestamp is reasonable.
+//!
+//! The timestamp is `relay_chain_slot * relay_chain_slot_duration + offset`, where the offset is
+//! one relay chain slot by default and can be configured per chain. Collators can use their local
+//! clock instead, corrected to stay within a bounded drift of that timestamp. The runtime can check
+//! the timestamp with `cumulus_pallet_parachain_system::TimestampMatchesRelaySlot`, configured with
+//! the same offset and a tolerance of at least the drift bound.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -40,15 +46,58 @@ pub use sp_timestamp::{InherentType, INHERENT_IDENTIFIER};
 pub struct InherentDataProvider {
 	relay_chain_slot: Slot,
 	relay_chain_slot_duration: Duration,
+	offset: Duration,
+	drift_correction: Option<(Duration, Duration)>,
 }
 
 impl InherentDataProvider {
 	/// Create `Self` from the given relay chain slot and slot duration.
+	///
+	/// As the parachain starts building at around `relay_chain_slot + 1`, the offset is one relay
+	/// chain slot.
 	pub fn from_relay_chain_slot_and_duration(
 		relay_chain_slot: Slot,
 		relay_chain_slot_duration: Duration,
 	) -> Self {
-		Self { relay_chain_slot, relay_chain_slot_duration }
+		Self {
+			relay_chain_slot,
+			relay_chain_slot_duration,
+			offset: relay_chain_slot_duration,
+			drift_correction: None,
+		}
+	}
+
+	/// Use `offset` as the time between the start of the relay chain slot and the timestamp.
+	pub fn with_offset(mut self, offset: Duration) -> Self {
+		self.offset = offset;
+		self
+	}
+
+	/// Use the local time `now` as the timestamp, corrected to be at most `max_drift` away from
+	/// the timestamp derived from the relay chain slot.
+	pub fn with_drift_correction(mut self, now: Duration, max_drift: Duration) -> Self {
+		self.drift_correction = Some((now, max_drift));
+		self
+	}
+
+	/// The timestamp that is provided as inherent data.
+	pub fn timestamp(&self) -> InherentType {
+		let slot_timestamp = (*self.relay_chain_slot)
+			.saturating_mul(self.relay_chain_slot_duration.as_millis() as u64)
+			.saturating_add(self.offset.as_millis() as u64);
+
+		let timestamp = match self.drift_correction {
+			Some((now, max_drift)) => {
+				let max_drift = max_drift.as_millis() as u64;
+				(now.as_millis() as u64).clamp(
+					slot_timestamp.saturating_sub(max_drift),
+					slot_timestamp.saturating_add(max_drift),
+				)
+			},
+			None => slot_timestamp,
+		};
+
+		timestamp.into()
 	}
 
 	/// Create the inherent data.
@@ -59,12 +108,6 @@ impl InherentDataProvider {
 
 	/// Provide the inherent data into the given `inherent_data`.
 	pub fn provide_inherent_data(&self, inherent_data: &mut InherentData) -> Result<(), Error> {
-		// As the parachain starts building at around `relay_chain_slot + 1` we use that slot to
-		// calculate the timestamp.
-		let data: InherentType = ((*self.relay_chain_slot + 1) *
-			self.relay_chain_slot_duration.as_millis() as u64)
-			.into();
-
-		inherent_data.put_data(INHERENT_IDENTIFIER, &data)
+		inherent_data.put_data(INHERENT_IDENTIFIER, &self.timestamp())
 	}
 }
@@ -115,8 +115,8 @@ struct AssetTraderRefunder {
 /// Then charges for this assetId as described by FeeCharger
 /// Weight, paid balance, local asset Id and the multilocation is stored for
 /// later refund purposes
-/// Important: Errors if the Trader is being called twice by 2 BuyExecution instructions
-/// Alternatively we could just return payment in the aforementioned case
+/// Weight bought by later BuyExecution instructions is paid in the same asset and added to the
+/// stored weight and balance, so that any unused weight can be refunded in that asset
 pub struct TakeFirstAssetTrader<
 	AccountId,
 	FeeCharger: ChargeWeightInFungibles<AccountId, ConcreteAssets>,
@@ -150,21 +150,27 @@ impl<
 	) -> Result<xcm_executor::Assets, XcmError> {
 		log::trace!(target: "xcm::weight", "TakeFirstAssetTrader::buy_weight weight: {:?}, payment: {:?}, context: {:?}", weight, payment, context);
 
-		// Make sure we dont enter twice
-		if self.0.is_some() {
-			return Err(XcmError::NotWithdrawable)
-		}
-
-		// We take the very first multiasset from payment
-		// (assets are sorted by fungibility/amount after this conversion)
-		let multiassets: MultiAssets = payment.clone().into();
-
-		// Take the first multiasset from the selected MultiAssets
-		let first = multiassets.get(0).ok_or(XcmError::AssetNotFound)?;
+		let first: MultiAsset = match &self.0 {
+			// Weight was already bought, keep paying in the same asset so that all of it can be
+			// refunded in that asset
+			Some(AssetTraderRefunder { outstanding_concrete_asset, .. }) => {
+				let id = outstanding_concrete_asset.id;
+				let amount = payment.fungible.get(&id).copied().ok_or(XcmError::TooExpensive)?;
+				(id, amount).into()
+			},
+			None => {
+				// We take the very first multiasset from payment
+				// (assets are sorted by fungibility/amount after this conversion)
+				let multiassets: MultiAssets = payment.clone().into();
+
+				// Take the first multiasset from the selected MultiAssets
+				multiassets.get(0).ok_or(XcmError::AssetNotFound)?.clone()
+			},
+		};
 
 		// Get the local asset id in which we can pay for fees
 		let (local_asset_id, _) =
-			Matcher::matches_fungibles(first).map_err(|_| XcmError::AssetNotFound)?;
+			Matcher::matches_fungibles(&first).map_err(|_| XcmError::AssetNotFound)?;
 
 		// Calculate how much we should charge in the asset_id for such amount of weight
 		// Require at least a payment of minimum_balance
@@ -188,10 +194,17 @@ impl<
 		// Substract payment
 		let unused = payment.checked_sub(required.clone()).map_err(|_| XcmError::TooExpensive)?;
 
-		// record weight and multiasset
+		// record weight and multiasset, on top of what was already bought
+		let (weight_outstanding, balance_outstanding) = match self.0.take() {
+			Some(AssetTraderRefunder {
+				weight_outstanding,
+				outstanding_concrete_asset: MultiAsset { fun: Fungible(amount), .. },
+			}) => (weight_outstanding.saturating_add(weight), amount.saturating_add(asset_balance)),
+			_ => (weight, asset_balance),
+		};
 		self.0 = Some(AssetTraderRefunder {
-			weight_outstanding: weight,
-			outstanding_concrete_asset: required,
+			weight_outstanding,
+			outstanding_concrete_asset: (required.id, balance_outstanding).into(),
 		});
 
 		Ok(unused)
@@ -204,6 +217,12 @@ impl<
 			outstanding_concrete_asset: MultiAsset { id, fun },
 		}) = self.0.clone()
 		{
+			// Only the weight that was bought and not refunded yet can be refunded
+			let weight = weight.min(weight_outstanding);
+			if weight.is_zero() {
+				return None
+			}
+
 			// Get the local asset id in which we can refund fees
 			let (local_asset_id, outstanding_balance) =
 				Matcher::matches_fungibles(&(id, fun).into()).ok()?;
@@ -266,7 +285,10 @@ impl<
 {
 	fn drop(&mut self) {
 		if let Some(asset_trader) = self.0.clone() {
-			HandleRefund::take_revenue(asset_trader.outstanding_concrete_asset);
+			// Nothing to take if everything was refunded
+			if asset_trader.outstanding_concrete_asset.fun != Fungible(0) {
+				HandleRefund::take_revenue(asset_trader.outstanding_concrete_asset);
+			}
 		}
 	}
 }
@@ -417,128 +439,210 @@ mod tests {
 		);
 	}
 
-	#[test]
-	fn take_first_asset_trader_buy_weight_called_twice_throws_error() {
-		const AMOUNT: u128 = 100;
-
-		// prepare prerequisites to instantiate `TakeFirstAssetTrader`
-		type TestAccountId = u32;
-		type TestAssetId = u32;
-		type TestBalance = u128;
-		struct TestAssets;
-		impl MatchesFungibles<TestAssetId, TestBalance> for TestAssets {
-			fn matches_fungibles(a: &MultiAsset) -> Result<(TestAssetId, TestBalance), Error> {
-				match a {
-					MultiAsset { fun: Fungible(amount), id: Concrete(_id) } => Ok((1, *amount)),
-					_ => Err(Error::AssetNotHandled),
-				}
+	// prerequisites to instantiate `TakeFirstAssetTrader`
+	type TestAccountId = u32;
+	type TestAssetId = u32;
+	type TestBalance = u128;
+
+	/// Knows the local asset `1` at `Here` and the local asset `2` at `Parent`
+	struct TestAssets;
+	impl MatchesFungibles<TestAssetId, TestBalance> for TestAssets {
+		fn matches_fungibles(a: &MultiAsset) -> Result<(TestAssetId, TestBalance), Error> {
+			match a {
+				MultiAsset { fun: Fungible(amount), id: Concrete(id) } if *id == Here.into() =>
+					Ok((1, *amount)),
+				MultiAsset { fun: Fungible(amount), id: Concrete(id) } if *id == Parent.into() =>
+					Ok((2, *amount)),
+				_ => Err(Error::AssetNotHandled),
 			}
 		}
-		impl fungibles::Inspect<TestAccountId> for TestAssets {
-			type AssetId = TestAssetId;
-			type Balance = TestBalance;
+	}
+	impl fungibles::Inspect<TestAccountId> for TestAssets {
+		type AssetId = TestAssetId;
+		type Balance = TestBalance;
 
-			fn total_issuance(_: Self::AssetId) -> Self::Balance {
-				todo!()
-			}
+		fn total_issuance(_: Self::AssetId) -> Self::Balance {
+			todo!()
+		}
 
-			fn minimum_balance(_: Self::AssetId) -> Self::Balance {
-				0
-			}
+		fn minimum_balance(_: Self::AssetId) -> Self::Balance {
+			0
+		}
 
-			fn balance(_: Self::AssetId, _: &TestAccountId) -> Self::Balance {
-				todo!()
-			}
+		fn balance(_: Self::AssetId, _: &TestAccountId) -> Self::Balance {
+			todo!()
+		}
 
-			fn total_balance(_: Self::AssetId, _: &TestAccountId) -> Self::Balance {
-				todo!()
-			}
+		fn total_balance(_: Self::AssetId, _: &TestAccountId) -> Self::Balance {
+			todo!()
+		}
 
-			fn reducible_balance(
-				_: Self::AssetId,
-				_: &TestAccountId,
-				_: Preservation,
-				_: Fortitude,
-			) -> Self::Balance {
-				todo!()
-			}
+		fn reducible_balance(
+			_: Self::AssetId,
+			_: &TestAccountId,
+			_: Preservation,
+			_: Fortitude,
+		) -> Self::Balance {
+			todo!()
+		}
 
-			fn can_deposit(
-				_: Self::AssetId,
-				_: &TestAccountId,
-				_: Self::Balance,
-				_: Provenance,
-			) -> DepositConsequence {
-				todo!()
-			}
+		fn can_deposit(
+			_: Self::AssetId,
+			_: &TestAccountId,
+			_: Self::Balance,
+			_: Provenance,
+		) -> DepositConsequence {
+			todo!()
+		}
 
-			fn can_withdraw(
-				_: Self::AssetId,
-				_: &TestAccountId,
-				_: Self::Balance,
-			) -> WithdrawConsequence<Self::Balance> {
-				todo!()
-			}
+		fn can_withdraw(
+			_: Self::AssetId,
+			_: &TestAccountId,
+			_: Self::Balance,
+		) -> WithdrawConsequence<Self::Balance> {
+			todo!()
+		}
 
-			fn asset_exists(_: Self::AssetId) -> bool {
-				todo!()
-			}
+		fn asset_exists(_: Self::AssetId) -> bool {
+			todo!()
 		}
-		impl fungibles::Mutate<TestAccountId> for TestAssets {}
-		impl fungibles::Balanced<TestAccountId> for TestAssets {
-			type OnDropCredit = fungibles::DecreaseIssuance<TestAccountId, Self>;
-			type OnDropDebt = fungibles::IncreaseIssuance<TestAccountId, Self>;
+	}
+	impl fungibles::Mutate<TestAccountId> for TestAssets {}
+	impl fungibles::Balanced<TestAccountId> for TestAssets {
+		type OnDropCredit = fungibles::DecreaseIssuance<TestAccountId, Self>;
+		type OnDropDebt = fungibles::IncreaseIssuance<TestAccountId, Self>;
+	}
+	impl fungibles::Unbalanced<TestAccountId> for TestAssets {
+		fn handle_dust(_: fungibles::Dust<TestAccountId, Self>) {
+			todo!()
+		}
+		fn write_balance(
+			_: Self::AssetId,
+			_: &TestAccountId,
+			_: Self::Balance,
+		) -> Result<Option<Self::Balance>, DispatchError> {
+			todo!()
 		}
-		impl fungibles::Unbalanced<TestAccountId> for TestAssets {
-			fn handle_dust(_: fungibles::Dust<TestAccountId, Self>) {
-				todo!()
-			}
-			fn write_balance(
-				_: Self::AssetId,
-				_: &TestAccountId,
-				_: Self::Balance,
-			) -> Result<Option<Self::Balance>, DispatchError> {
-				todo!()
-			}
 
-			fn set_total_issuance(_: Self::AssetId, _: Self::Balance) {
-				todo!()
-			}
+		fn set_total_issuance(_: Self::AssetId, _: Self::Balance) {
+			todo!()
 		}
+	}
 
-		struct FeeChargerAssetsHandleRefund;
-		impl ChargeWeightInFungibles<TestAccountId, TestAssets> for FeeChargerAssetsHandleRefund {
-			fn charge_weight_in_fungibles(
-				_: <TestAssets as Inspect<TestAccountId>>::AssetId,
-				_: Weight,
-			) -> Result<<TestAssets as Inspect<TestAccountId>>::Balance, XcmError> {
-				Ok(AMOUNT)
-			}
+	std::thread_local! {
+		static REVENUE: std::cell::RefCell<Vec<MultiAsset>> = Default::default();
+	}
+
+	/// Charges one unit per unit of `ref_time` and records the taken revenue
+	struct FeeChargerAssetsHandleRefund;
+	impl ChargeWeightInFungibles<TestAccountId, TestAssets> for FeeChargerAssetsHandleRefund {
+		fn charge_weight_in_fungibles(
+			_: <TestAssets as Inspect<TestAccountId>>::AssetId,
+			weight: Weight,
+		) -> Result<<TestAssets as Inspect<TestAccountId>>::Balance, XcmError> {
+			Ok(weight.ref_time().into())
 		}
-		impl TakeRevenue for FeeChargerAssetsHandleRefund {
-			fn take_revenue(_: MultiAsset) {}
+	}
+	impl TakeRevenue for FeeChargerAssetsHandleRefund {
+		fn take_revenue(revenue: MultiAsset) {
+			REVENUE.with(|r| r.borrow_mut().push(revenue));
 		}
+	}
 
-		// create new instance
-		type Trader = TakeFirstAssetTrader<
-			TestAccountId,
-			FeeChargerAssetsHandleRefund,
-			TestAssets,
-			TestAssets,
-			FeeChargerAssetsHandleRefund,
-		>;
-		let mut trader = <Trader as WeightTrader>::new();
-		let ctx = XcmContext { origin: None, message_id: XcmHash::default(), topic: None };
+	type Trader = TakeFirstAssetTrader<
+		TestAccountId,
+		FeeChargerAssetsHandleRefund,
+		TestAssets,
+		TestAssets,
+		FeeChargerAssetsHandleRefund,
+	>;
 
-		// prepare test data
-		let asset: MultiAsset = (Here, AMOUNT).into();
-		let payment = Assets::from(asset);
+	fn ctx() -> XcmContext {
+		XcmContext { origin: None, message_id: XcmHash::default(), topic: None }
+	}
+
+	fn take_revenue() -> Vec<MultiAsset> {
+		REVENUE.with(|r| r.take())
+	}
+
+	#[test]
+	fn take_first_asset_trader_buy_weight_in_another_asset_throws_error() {
+		let mut trader = <Trader as WeightTrader>::new();
 		let weight_to_buy = Weight::from_parts(1_000, 1_000);
 
 		// lets do first call (success)
-		assert_ok!(trader.buy_weight(weight_to_buy, payment.clone(), &ctx));
+		let payment = Assets::from(MultiAsset::from((Here, 1_000u128)));
+		assert_ok!(trader.buy_weight(weight_to_buy, payment, &ctx()));
+
+		// the second call has to pay in the same asset (error)
+		let payment = Assets::from(MultiAsset::from((Parent, 1_000u128)));
+		assert_eq!(trader.buy_weight(weight_to_buy, payment, &ctx()), Err(XcmError::TooExpensive));
+	}
+
+	#[test]
+	fn take_first_asset_trader_buy_weight_called_twice_accumulates() {
+		let mut trader = <Trader as WeightTrader>::new();
+
+		let payment = Assets::from(MultiAsset::from((Parent, 3_000u128)));
+		let unused = trader.buy_weight(Weight::from_parts(1_000, 1_000), payment, &ctx());
+		assert_eq!(unused, Ok(MultiAsset::from((Parent, 2_000u128)).into()));
+
+		// the second call pays in the same asset, even if it is not the first one of the payment
+		let payment: MultiAssets = vec![(Here, 1_000u128).into(), (Parent, 500u128).into()].into();
+		let unused = trader.buy_weight(Weight::from_parts(500, 500), payment.into(), &ctx());
+		assert_eq!(unused, Ok(MultiAsset::from((Here, 1_000u128)).into()));
+
+		// everything bought is taken as revenue
+		drop(trader);
+		assert_eq!(take_revenue(), vec![(Parent, 1_500u128).into()]);
+	}
+
+	#[test]
+	fn take_first_asset_trader_refunds_weight_bought_by_multiple_calls() {
+		let mut trader = <Trader as WeightTrader>::new();
+		for amount in [1_000u128, 500] {
+			let payment = Assets::from(MultiAsset::from((Here, amount)));
+			let weight = Weight::from_parts(amount as u64, amount as u64);
+			assert_ok!(trader.buy_weight(weight, payment, &ctx()));
+		}
+
+		// partial refunds are taken from all of the bought weight
+		assert_eq!(
+			trader.refund_weight(Weight::from_parts(300, 300), &ctx()),
+			Some((Here, 300u128).into())
+		);
+		assert_eq!(
+			trader.refund_weight(Weight::from_parts(900, 900), &ctx()),
+			Some((Here, 900u128).into())
+		);
+
+		// the rest is taken as revenue
+		drop(trader);
+		assert_eq!(take_revenue(), vec![(Here, 300u128).into()]);
+	}
+
+	#[test]
+	fn take_first_asset_trader_refunds_at_most_the_bought_weight() {
+		let mut trader = <Trader as WeightTrader>::new();
+		for amount in [1_000u128, 500] {
+			let payment = Assets::from(MultiAsset::from((Here, amount)));
+			let weight = Weight::from_parts(amount as u64, amount as u64);
+			assert_ok!(trader.buy_weight(weight, payment, &ctx()));
+		}
+
+		assert_eq!(
+			trader.refund_weight(Weight::from_parts(1_000, 1_000), &ctx()),
+			Some((Here, 1_000u128).into())
+		);
+		// only 500 is left to be refunded
+		assert_eq!(
+			trader.refund_weight(Weight::from_parts(1_000, 1_000), &ctx()),
+			Some((Here, 500u128).into())
+		);
+		assert_eq!(trader.refund_weight(Weight::from_parts(1_000, 1_000), &ctx()), None);
 
-		// lets do second call (error)
-		assert_eq!(trader.buy_weight(weight_to_buy, payment, &ctx), Err(XcmError::NotWithdrawable));
+		// nothing is left to be taken as revenue
+		drop(trader);
+		assert!(take_revenue().is_empty());
 	}
 }
@@ -30,6 +30,45 @@ fn main() {
 		.import_memory()
 		.set_file_name("wasm_binary_spec_version_incremented.rs")
 		.build();
+
+	// The upgrade torture runtimes are only needed by the `upgrade-torture` chain of the test
+	// service, so they are only built when asked for with the `upgrade-torture-wasm` feature.
+	if std::env::var_os("CARGO_FEATURE_UPGRADE_TORTURE_WASM").is_none() {
+		for file_name in [
+			"wasm_binary_upgrade_torture.rs",
+			"wasm_binary_upgrade_torture_spec_version_incremented.rs",
+		] {
+			write_missing_wasm_binary(file_name);
+		}
+		return
+	}
+
+	WasmBuilder::new()
+		.with_current_project()
+		.enable_feature("upgrade-torture")
+		.import_memory()
+		.set_file_name("wasm_binary_upgrade_torture.rs")
+		.build();
+
+	WasmBuilder::new()
+		.with_current_project()
+		.enable_feature("upgrade-torture")
+		.enable_feature("increment-spec-version")
+		.import_memory()
+		.set_file_name("wasm_binary_upgrade_torture_spec_version_incremented.rs")
+		.build();
+}
+
+/// Writes `file_name` to `OUT_DIR` with the constants of a wasm binary that was not built.
+#[cfg(feature = "std")]
+fn write_missing_wasm_binary(file_name: &str) {
+	let out_dir = std::env::var("OUT_DIR").expect("`OUT_DIR` is set by cargo; qed");
+	std::fs::write(
+		std::path::Path::new(&out_dir).join(file_name),
+		"pub const WASM_BINARY: Option<&[u8]> = None;\n\
+		 pub const WASM_BINARY_BLOATY: Option<&[u8]> = None;\n",
+	)
+	.expect("`OUT_DIR` is writable; qed");
 }
 
 #[cfg(not(feature = "std"))]
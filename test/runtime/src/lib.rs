@@ -27,6 +27,22 @@ pub mod wasm_spec_version_incremented {
 	include!(concat!(env!("OUT_DIR"), "/wasm_binary_spec_version_incremented.rs"));
 }
 
+/// The runtime built with the `upgrade-torture` feature.
+///
+/// Only built with the `upgrade-torture-wasm` feature, `WASM_BINARY` is `None` otherwise.
+pub mod wasm_upgrade_torture {
+	#[cfg(feature = "std")]
+	include!(concat!(env!("OUT_DIR"), "/wasm_binary_upgrade_torture.rs"));
+}
+
+/// The runtime built with the `upgrade-torture` and the `increment-spec-version` features.
+///
+/// Only built with the `upgrade-torture-wasm` feature, `WASM_BINARY` is `None` otherwise.
+pub mod wasm_upgrade_torture_spec_version_incremented {
+	#[cfg(feature = "std")]
+	include!(concat!(env!("OUT_DIR"), "/wasm_binary_upgrade_torture_spec_version_incremented.rs"));
+}
+
 mod test_pallet;
 
 use frame_support::traits::OnRuntimeUpgrade;
@@ -68,7 +84,7 @@ pub use pallet_timestamp::Call as TimestampCall;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{Perbill, Permill};
-pub use test_pallet::Call as TestPalletCall;
+pub use test_pallet::{Call as TestPalletCall, NextUpgradeTortureCode, UpgradeTortureCodes};
 
 pub type SessionHandlers = ();
 
@@ -83,9 +99,10 @@ pub const TEST_RUNTIME_UPGRADE_KEY: &[u8] = b"+test_runtime_upgrade_key+";
 /// The para-id used in this runtime.
 pub const PARACHAIN_ID: u32 = 100;
 
-// The only difference between the two declarations below is the `spec_version`. With the
+// The only difference between the declarations below is the `spec_version`. With the
 // `increment-spec-version` feature enabled `spec_version` should be greater than the one of without
-// the `increment-spec-version` feature.
+// the `increment-spec-version` feature. With the `upgrade-torture` feature enabled `spec_version`
+// should differ from the native runtime, so that the upgrades are always executed in wasm.
 //
 // The duplication here is unfortunate necessity.
 //
@@ -94,7 +111,7 @@ pub const PARACHAIN_ID: u32 = 100;
 // details. Since macro kicks in early, it operates on AST. Thus you cannot use constants.
 // Macros are expanded top to bottom, meaning we also cannot use `cfg` here.
 
-#[cfg(not(feature = "increment-spec-version"))]
+#[cfg(all(not(feature = "increment-spec-version"), not(feature = "upgrade-torture")))]
 #[sp_version::runtime_version]
 pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("cumulus-test-parachain"),
@@ -108,7 +125,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	state_version: 1,
 };
 
-#[cfg(feature = "increment-spec-version")]
+#[cfg(all(feature = "increment-spec-version", not(feature = "upgrade-torture")))]
 #[sp_version::runtime_version]
 pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("cumulus-test-parachain"),
@@ -122,6 +139,34 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	state_version: 1,
 };
 
+#[cfg(all(not(feature = "increment-spec-version"), feature = "upgrade-torture"))]
+#[sp_version::runtime_version]
+pub const VERSION: RuntimeVersion = RuntimeVersion {
+	spec_name: create_runtime_str!("cumulus-test-parachain"),
+	impl_name: create_runtime_str!("cumulus-test-parachain"),
+	authoring_version: 1,
+	// Read the note above.
+	spec_version: 1001,
+	impl_version: 1,
+	apis: RUNTIME_API_VERSIONS,
+	transaction_version: 1,
+	state_version: 1,
+};
+
+#[cfg(all(feature = "increment-spec-version", feature = "upgrade-torture"))]
+#[sp_version::runtime_version]
+pub const VERSION: RuntimeVersion = RuntimeVersion {
+	spec_name: create_runtime_str!("cumulus-test-parachain"),
+	impl_name: create_runtime_str!("cumulus-test-parachain"),
+	authoring_version: 1,
+	// Read the note above.
+	spec_version: 1002,
+	impl_version: 1,
+	apis: RUNTIME_API_VERSIONS,
+	transaction_version: 1,
+	state_version: 1,
+};
+
 pub const MILLISECS_PER_BLOCK: u64 = 12000;
 
 pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
@@ -279,13 +324,18 @@ impl pallet_glutton::Config for Runtime {
 impl cumulus_pallet_parachain_system::Config for Runtime {
 	type SelfParaId = ParachainId;
 	type RuntimeEvent = RuntimeEvent;
-	type OnSystemEvent = ();
+	type OnSystemEvent = TestPallet;
+	type RelayChainGenesisHashStore = ();
 	type OutboundXcmpMessageSource = ();
+	type PriorityUpwardMessageSource = ();
 	type DmpMessageHandler = ();
 	type ReservedDmpWeight = ();
 	type XcmpMessageHandler = ();
 	type ReservedXcmpWeight = ();
+	type PovSizeWatermark = frame_support::traits::ConstU64<{ u64::MAX }>;
 	type CheckAssociatedRelayNumber = cumulus_pallet_parachain_system::AnyRelayNumber;
+	type CheckRelayTimestamp = ();
+	type AuthorshipProofVerifier = ();
 	type ConsensusHook = cumulus_pallet_parachain_system::consensus_hook::RequireParentIncluded;
 }
 
@@ -470,6 +520,16 @@ impl_runtime_apis! {
 			ParachainSystem::collect_collation_info(header)
 		}
 	}
+
+	impl cumulus_primitives_core::UnincludedSegmentApi<Block> for Runtime {
+		fn unincluded_segment_capacity() -> u32 {
+			ParachainSystem::unincluded_segment_capacity()
+		}
+
+		fn unincluded_segment_size_after(included_hash: <Block as BlockT>::Hash) -> u32 {
+			ParachainSystem::unincluded_segment_size_after(included_hash)
+		}
+	}
 }
 
 cumulus_pallet_parachain_system::register_validate_block! {
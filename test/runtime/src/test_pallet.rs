@@ -19,8 +19,26 @@ pub use pallet::*;
 
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
+	use cumulus_primitives_core::relay_chain::BlockNumber as RelayChainBlockNumber;
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
+	use sp_std::vec::Vec;
+
+	/// The number of relay chain blocks to wait after an upgrade was applied, before the next
+	/// upgrade is scheduled with the `upgrade-torture` feature.
+	pub const UPGRADE_TORTURE_INTERVAL: RelayChainBlockNumber = 10;
+
+	/// The progress of the upgrades that are scheduled with the `upgrade-torture` feature.
+	#[derive(Clone, Copy, Default, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+	pub enum UpgradeTortureState {
+		/// The last upgrade was applied in the current block, or the chain was just launched.
+		#[default]
+		Applied,
+		/// No upgrade is pending since the given relay chain block.
+		Idle { since: RelayChainBlockNumber },
+		/// An upgrade is scheduled and waits for the go-ahead of the relay chain.
+		Pending,
+	}
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
@@ -28,9 +46,71 @@ pub mod pallet {
 	#[pallet::config]
 	pub trait Config: frame_system::Config + cumulus_pallet_parachain_system::Config {}
 
+	/// The codes that are alternately scheduled as upgrades with the `upgrade-torture` feature.
+	///
+	/// Expected to be set at genesis, under consecutive indices starting at `0`.
+	#[pallet::storage]
+	pub type UpgradeTortureCodes<T: Config> = StorageMap<_, Twox64Concat, u32, Vec<u8>>;
+
+	/// The index in [`UpgradeTortureCodes`] of the code to schedule next.
+	#[pallet::storage]
+	pub type NextUpgradeTortureCode<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The progress of the upgrades that are scheduled with the `upgrade-torture` feature.
+	#[pallet::storage]
+	pub type UpgradeTorture<T: Config> = StorageValue<_, UpgradeTortureState, ValueQuery>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
 
+	impl<T: Config> cumulus_pallet_parachain_system::OnSystemEvent for Pallet<T> {
+		fn on_validation_data(_data: &cumulus_primitives_core::PersistedValidationData) {
+			#[cfg(feature = "upgrade-torture")]
+			Self::torture_upgrades(_data.relay_parent_number);
+		}
+
+		fn on_validation_code_applied() {
+			if UpgradeTorture::<T>::get() == UpgradeTortureState::Pending {
+				UpgradeTorture::<T>::put(UpgradeTortureState::Applied);
+			}
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Schedules the next of the [`UpgradeTortureCodes`], once the last upgrade was applied
+		/// [`UPGRADE_TORTURE_INTERVAL`] relay chain blocks ago.
+		///
+		/// Called with the validation data, as the upgrade restriction signal of the relay chain
+		/// is only known from there until the end of the block.
+		#[cfg(feature = "upgrade-torture")]
+		fn torture_upgrades(relay_parent_number: RelayChainBlockNumber) {
+			match UpgradeTorture::<T>::get() {
+				UpgradeTortureState::Applied =>
+					UpgradeTorture::<T>::put(UpgradeTortureState::Idle {
+						since: relay_parent_number,
+					}),
+				UpgradeTortureState::Idle { since }
+					if relay_parent_number >= since.saturating_add(UPGRADE_TORTURE_INTERVAL) =>
+				{
+					let index = NextUpgradeTortureCode::<T>::get();
+					let Some(code) = UpgradeTortureCodes::<T>::get(index) else { return };
+
+					// Retried in the next block, e.g. if the relay chain restricts upgrades.
+					if cumulus_pallet_parachain_system::Pallet::<T>::schedule_code_upgrade(code)
+						.is_ok()
+					{
+						let next = index + 1;
+						NextUpgradeTortureCode::<T>::put(
+							if UpgradeTortureCodes::<T>::contains_key(next) { next } else { 0 },
+						);
+						UpgradeTorture::<T>::put(UpgradeTortureState::Pending);
+					}
+				},
+				UpgradeTortureState::Idle { .. } | UpgradeTortureState::Pending => {},
+			}
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// A test dispatchable for setting a custom head data in `validate_block`.
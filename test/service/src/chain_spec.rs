@@ -34,6 +34,10 @@ pub struct GenesisExt {
 	runtime_genesis_config: cumulus_test_runtime::RuntimeGenesisConfig,
 	/// The parachain id.
 	para_id: ParaId,
+	/// Whether the runtime upgrades are continuously scheduled, see
+	/// [`get_upgrade_torture_chain_spec`].
+	#[serde(default)]
+	upgrade_torture: bool,
 }
 
 impl sp_runtime::BuildStorage for GenesisExt {
@@ -41,6 +45,18 @@ impl sp_runtime::BuildStorage for GenesisExt {
 		sp_state_machine::BasicExternalities::execute_with_storage(storage, || {
 			sp_io::storage::set(cumulus_test_runtime::TEST_RUNTIME_UPGRADE_KEY, &[1, 2, 3, 4]);
 			cumulus_test_runtime::ParachainId::set(&self.para_id);
+
+			if self.upgrade_torture {
+				// The genesis code is the one at index `1`.
+				cumulus_test_runtime::UpgradeTortureCodes::<cumulus_test_runtime::Runtime>::insert(
+					0,
+					upgrade_torture_code(true),
+				);
+				cumulus_test_runtime::UpgradeTortureCodes::<cumulus_test_runtime::Runtime>::insert(
+					1,
+					upgrade_torture_code(false),
+				);
+			}
 		});
 
 		self.runtime_genesis_config.assimilate_storage(storage)
@@ -95,6 +111,7 @@ pub fn get_chain_spec_with_extra_endowed(
 				extra_endowed_accounts.clone(),
 			),
 			para_id: id,
+			upgrade_torture: false,
 		},
 		Vec::new(),
 		None,
@@ -110,6 +127,45 @@ pub fn get_chain_spec(id: ParaId) -> ChainSpec {
 	get_chain_spec_with_extra_endowed(id, Default::default())
 }
 
+/// Get the chain spec for a specific parachain ID, whose runtime continuously schedules upgrades.
+///
+/// The runtime is built with the `upgrade-torture` feature and alternates between two codes,
+/// that only differ in their `spec_version`.
+pub fn get_upgrade_torture_chain_spec(
+	id: ParaId,
+	extra_endowed_accounts: Vec<AccountId>,
+) -> ChainSpec {
+	ChainSpec::from_genesis(
+		"Upgrade Torture Testnet",
+		"upgrade_torture_testnet",
+		ChainType::Local,
+		move || {
+			let mut runtime_genesis_config =
+				testnet_genesis_with_default_endowed(extra_endowed_accounts.clone());
+			runtime_genesis_config.system.code = upgrade_torture_code(false);
+
+			GenesisExt { runtime_genesis_config, para_id: id, upgrade_torture: true }
+		},
+		Vec::new(),
+		None,
+		None,
+		None,
+		None,
+		Extensions { para_id: id.into() },
+	)
+}
+
+/// The code of the runtime built with the `upgrade-torture` feature.
+fn upgrade_torture_code(spec_version_incremented: bool) -> Vec<u8> {
+	if spec_version_incremented {
+		cumulus_test_runtime::wasm_upgrade_torture_spec_version_incremented::WASM_BINARY
+	} else {
+		cumulus_test_runtime::wasm_upgrade_torture::WASM_BINARY
+	}
+	.expect("upgrade torture WASM binary was not built, enable the `upgrade-torture` feature!")
+	.to_vec()
+}
+
 /// Local testnet genesis for testing.
 pub fn testnet_genesis_with_default_endowed(
 	mut extra_endowed_accounts: Vec<AccountId>,
@@ -16,6 +16,7 @@
 
 use std::{net::SocketAddr, path::PathBuf};
 
+use cumulus_test_service::Misbehavior;
 use polkadot_service::{ChainSpec, ParaId, PrometheusConfig};
 use sc_cli::{
 	CliConfiguration, DefaultConfigurationValues, ImportParams, KeystoreParams, NetworkParams,
@@ -52,6 +53,10 @@ pub struct TestCollatorCli {
 
 	#[arg(long)]
 	pub fail_pov_recovery: bool,
+
+	/// Makes the collator misbehave in the given way.
+	#[arg(long, value_enum)]
+	pub misbehavior: Option<Misbehavior>,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -289,6 +294,10 @@ impl SubstrateCli for TestCollatorCli {
 		Ok(match id {
 			"" => Box::new(cumulus_test_service::get_chain_spec(ParaId::from(self.parachain_id)))
 				as Box<_>,
+			"upgrade-torture" => Box::new(cumulus_test_service::get_upgrade_torture_chain_spec(
+				ParaId::from(self.parachain_id),
+				Default::default(),
+			)),
 			path => {
 				let chain_spec =
 					cumulus_test_service::chain_spec::ChainSpec::from_json_file(path.into())?;
@@ -23,6 +23,7 @@ pub mod bench_utils;
 
 pub mod chain_spec;
 mod genesis;
+mod misbehavior;
 
 use runtime::AccountId;
 use sc_executor::{HeapAllocStrategy, WasmExecutor, DEFAULT_HEAP_ALLOC_STRATEGY};
@@ -38,7 +39,7 @@ use cumulus_client_cli::{CollatorOptions, RelayChainMode};
 use cumulus_client_consensus_common::{
 	ParachainBlockImport as TParachainBlockImport, ParachainCandidate, ParachainConsensus,
 };
-use cumulus_client_pov_recovery::RecoveryHandle;
+use cumulus_client_pov_recovery::{RecoveryHandle, DEFAULT_MAX_CONCURRENT_RECOVERIES};
 #[allow(deprecated)]
 use cumulus_client_service::old_consensus;
 use cumulus_client_service::{
@@ -86,6 +87,7 @@ use substrate_test_client::{
 pub use chain_spec::*;
 pub use cumulus_test_runtime as runtime;
 pub use genesis::*;
+pub use misbehavior::Misbehavior;
 pub use sp_keyring::Sr25519Keyring as Keyring;
 
 const LOG_TARGET: &str = "cumulus-test-service";
@@ -299,6 +301,7 @@ pub async fn start_node_impl<RB>(
 	para_id: ParaId,
 	wrap_announce_block: Option<Box<dyn FnOnce(AnnounceBlockFn) -> AnnounceBlockFn>>,
 	fail_pov_recovery: bool,
+	misbehavior: Option<Misbehavior>,
 	rpc_ext_builder: RB,
 	consensus: Consensus,
 	collator_options: CollatorOptions,
@@ -381,6 +384,13 @@ where
 		.map(|w| (w)(announce_block.clone()))
 		.unwrap_or_else(|| announce_block);
 
+	// The blocks of a misbehaving collator that aren't part of its candidates are announced
+	// as usual.
+	let extra_announce_block = announce_block.clone();
+	let announce_block = misbehavior
+		.map(|m| m.wrap_announce_block(announce_block.clone()))
+		.unwrap_or_else(|| announce_block);
+
 	let relay_chain_interface_for_closure = relay_chain_interface.clone();
 
 	let overseer_handle = relay_chain_interface
@@ -401,6 +411,10 @@ where
 		para_id,
 		relay_chain_interface: relay_chain_interface.clone(),
 		task_manager: &mut task_manager,
+		pov_recovery_concurrency: collator_options.pov_recovery_concurrency,
+		network: Some(network.clone()),
+		prometheus_registry: prometheus_registry.as_ref(),
+		pov_recovery_backlog: None,
 		da_recovery_profile: if is_collator {
 			DARecoveryProfile::Collator
 		} else {
@@ -454,6 +468,14 @@ where
 			Consensus::Null => Box::new(NullConsensus),
 		};
 
+		let parachain_consensus = match misbehavior {
+			Some(misbehavior) => {
+				tracing::info!(target: LOG_TARGET, ?misbehavior, "Collator is misbehaving.");
+				misbehavior.wrap_consensus(parachain_consensus, extra_announce_block)
+			},
+			None => parachain_consensus,
+		};
+
 		#[allow(deprecated)]
 		old_consensus::start_collator(old_consensus::StartCollatorParams {
 			block_status: client.clone(),
@@ -513,6 +535,8 @@ pub struct TestNodeBuilder {
 	consensus: Consensus,
 	relay_chain_mode: RelayChainMode,
 	endowed_accounts: Vec<AccountId>,
+	misbehavior: Option<Misbehavior>,
+	upgrade_torture: bool,
 }
 
 impl TestNodeBuilder {
@@ -537,6 +561,8 @@ impl TestNodeBuilder {
 			consensus: Consensus::RelayChain,
 			endowed_accounts: Default::default(),
 			relay_chain_mode: RelayChainMode::Embedded,
+			misbehavior: None,
+			upgrade_torture: false,
 		}
 	}
 
@@ -649,6 +675,23 @@ impl TestNodeBuilder {
 		self
 	}
 
+	/// Make the collator of this node misbehave.
+	///
+	/// Only has an effect if the collator is enabled with [`Self::enable_collator`].
+	pub fn misbehave(mut self, misbehavior: Misbehavior) -> Self {
+		self.misbehavior = Some(misbehavior);
+		self
+	}
+
+	/// Continuously schedule runtime upgrades, see [`get_upgrade_torture_chain_spec`].
+	///
+	/// As the `spec_version` changes with every upgrade, the extrinsics sent with
+	/// [`TestNode::send_extrinsic`] are only valid while the genesis code is in use.
+	pub fn torture_runtime_upgrades(mut self) -> Self {
+		self.upgrade_torture = true;
+		self
+	}
+
 	/// Build the [`TestNode`].
 	pub async fn build(self) -> TestNode {
 		let parachain_config = node_config(
@@ -660,6 +703,7 @@ impl TestNodeBuilder {
 			self.para_id,
 			self.collator_key.is_some(),
 			self.endowed_accounts,
+			self.upgrade_torture,
 		)
 		.expect("could not generate Configuration");
 
@@ -671,7 +715,12 @@ impl TestNodeBuilder {
 			false,
 		);
 
-		let collator_options = CollatorOptions { relay_chain_mode: self.relay_chain_mode };
+		let collator_options = CollatorOptions {
+			relay_chain_mode: self.relay_chain_mode,
+			pov_recovery_concurrency: DEFAULT_MAX_CONCURRENT_RECOVERIES,
+			relay_chain_websocket_listen_addresses: Vec::new(),
+			relay_chain_websocket_public_addresses: Vec::new(),
+		};
 
 		relay_chain_config.network.node_name =
 			format!("{} (relay chain)", relay_chain_config.network.node_name);
@@ -684,6 +733,7 @@ impl TestNodeBuilder {
 			self.para_id,
 			self.wrap_announce_block,
 			false,
+			self.misbehavior,
 			|_| Ok(jsonrpsee::RpcModule::new(())),
 			self.consensus,
 			collator_options,
@@ -703,7 +753,8 @@ impl TestNodeBuilder {
 /// By default an in-memory socket will be used, therefore you need to provide nodes if you want the
 /// node to be connected to other nodes. If `nodes_exclusive` is `true`, the node will only connect
 /// to the given `nodes` and not to any other node. The `storage_update_func` can be used to make
-/// adjustments to the runtime genesis.
+/// adjustments to the runtime genesis. If `upgrade_torture` is `true`, the runtime continuously
+/// schedules upgrades.
 pub fn node_config(
 	storage_update_func: impl Fn(),
 	tokio_handle: tokio::runtime::Handle,
@@ -713,13 +764,17 @@ pub fn node_config(
 	para_id: ParaId,
 	is_collator: bool,
 	endowed_accounts: Vec<AccountId>,
+	upgrade_torture: bool,
 ) -> Result<Configuration, ServiceError> {
 	let base_path = BasePath::new_temp_dir()?;
 	let root = base_path.path().join(format!("cumulus_test_service_{}", key));
 	let role = if is_collator { Role::Authority } else { Role::Full };
 	let key_seed = key.to_seed();
-	let mut spec =
-		Box::new(chain_spec::get_chain_spec_with_extra_endowed(para_id, endowed_accounts));
+	let mut spec = Box::new(if upgrade_torture {
+		chain_spec::get_upgrade_torture_chain_spec(para_id, endowed_accounts)
+	} else {
+		chain_spec::get_chain_spec_with_extra_endowed(para_id, endowed_accounts)
+	});
 
 	let mut storage = spec.as_storage_builder().build_storage().expect("could not build storage");
 
@@ -73,7 +73,14 @@ fn main() -> Result<(), sc_cli::Error> {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|_config| {
 				let parachain_id = ParaId::from(cmd.parachain_id);
-				let spec = cumulus_test_service::get_chain_spec(parachain_id);
+				let spec = match cmd.base.shared_params.chain.as_deref() {
+					Some("upgrade-torture") =>
+						cumulus_test_service::get_upgrade_torture_chain_spec(
+							parachain_id,
+							Default::default(),
+						),
+					_ => cumulus_test_service::get_chain_spec(parachain_id),
+				};
 				cmd.base.run(&spec)
 			})
 		},
@@ -117,6 +124,9 @@ fn main() -> Result<(), sc_cli::Error> {
 			if cli.fail_pov_recovery {
 				tracing::info!("PoV recovery failure enabled");
 			}
+			if let Some(misbehavior) = cli.misbehavior {
+				tracing::info!("Misbehavior enabled: {:?}", misbehavior);
+			}
 
 			let collator_key = config.role.is_authority().then(|| CollatorPair::generate().0);
 
@@ -136,6 +146,7 @@ fn main() -> Result<(), sc_cli::Error> {
 					parachain_id,
 					cli.disable_block_announcements.then(wrap_announce_block),
 					cli.fail_pov_recovery,
+					cli.misbehavior,
 					|_| Ok(jsonrpsee::RpcModule::new(())),
 					consensus,
 					collator_options,
@@ -0,0 +1,140 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Misbehaviors that can be injected into a test collator.
+//!
+//! They are used to check that honest nodes of the network cope with malicious collators, by
+//! exercising the block announce validation, the PoV recovery and the rejection of invalid
+//! candidates by the relay chain validators.
+
+use crate::{AnnounceBlockFn, LOG_TARGET};
+use cumulus_client_consensus_common::{ParachainCandidate, ParachainConsensus};
+use cumulus_test_runtime::{Header, NodeBlock as Block};
+use polkadot_primitives::{Hash as PHash, PersistedValidationData};
+use rand::RngCore;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use sp_trie::StorageProof;
+use std::sync::Arc;
+
+/// The misbehavior of a malicious collator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Misbehavior {
+	/// Never announce the produced blocks to the parachain network.
+	///
+	/// The other nodes only learn about the blocks once they are included at the relay chain and
+	/// have to recover their PoV from the availability store.
+	WithholdPov,
+	/// Announce the produced blocks with data that doesn't prove they are backed.
+	InvalidAnnounce,
+	/// Produce and announce a second block on the same parent every time a candidate is produced.
+	Equivocate,
+	/// Produce candidates whose PoV exceeds the maximum PoV size set by the relay chain.
+	OversizedPov,
+}
+
+impl Misbehavior {
+	/// Wrap the announce block function of the collator according to `self`.
+	pub fn wrap_announce_block(self, announce_block: AnnounceBlockFn) -> AnnounceBlockFn {
+		match self {
+			Self::WithholdPov => Arc::new(|hash, _| {
+				tracing::info!(target: LOG_TARGET, ?hash, "Withholding block announcement.");
+			}),
+			Self::InvalidAnnounce => Arc::new(move |hash, _| {
+				tracing::info!(target: LOG_TARGET, ?hash, "Announcing block with invalid data.");
+				announce_block(hash, Some(b"invalid block announce data".to_vec()))
+			}),
+			Self::Equivocate | Self::OversizedPov => announce_block,
+		}
+	}
+
+	/// Wrap the consensus of the collator according to `self`.
+	///
+	/// `announce_block` is used to announce the blocks that are produced besides the candidates.
+	pub fn wrap_consensus(
+		self,
+		consensus: Box<dyn ParachainConsensus<Block>>,
+		announce_block: AnnounceBlockFn,
+	) -> Box<dyn ParachainConsensus<Block>> {
+		match self {
+			Self::WithholdPov | Self::InvalidAnnounce => consensus,
+			Self::Equivocate | Self::OversizedPov => Box::new(MisbehavingConsensus {
+				inner: consensus,
+				announce_block,
+				misbehavior: self,
+			}),
+		}
+	}
+}
+
+/// A consensus that misbehaves on top of the candidates produced by `inner`.
+#[derive(Clone)]
+struct MisbehavingConsensus {
+	inner: Box<dyn ParachainConsensus<Block>>,
+	announce_block: AnnounceBlockFn,
+	misbehavior: Misbehavior,
+}
+
+#[async_trait::async_trait]
+impl ParachainConsensus<Block> for MisbehavingConsensus {
+	async fn produce_candidate(
+		&mut self,
+		parent: &Header,
+		relay_parent: PHash,
+		validation_data: &PersistedValidationData,
+	) -> Option<ParachainCandidate<Block>> {
+		let candidate = self.inner.produce_candidate(parent, relay_parent, validation_data).await?;
+
+		match self.misbehavior {
+			Misbehavior::Equivocate => {
+				if let Some(equivocation) =
+					self.inner.produce_candidate(parent, relay_parent, validation_data).await
+				{
+					let hash = equivocation.block.header().hash();
+					if hash != candidate.block.header().hash() {
+						tracing::info!(
+							target: LOG_TARGET,
+							?hash,
+							parent = ?parent.hash(),
+							"Announcing equivocating block.",
+						);
+						(self.announce_block)(hash, None);
+					}
+				}
+
+				Some(candidate)
+			},
+			Misbehavior::OversizedPov => {
+				// Random bytes, as they can't be compressed away when building the collation.
+				let mut padding = vec![0u8; validation_data.max_pov_size as usize + 1];
+				rand::thread_rng().fill_bytes(&mut padding);
+
+				tracing::info!(
+					target: LOG_TARGET,
+					hash = ?candidate.block.header().hash(),
+					padding = padding.len(),
+					"Padding the PoV of the candidate over the maximum PoV size.",
+				);
+
+				let proof = StorageProof::new(
+					candidate.proof.into_iter_nodes().chain(std::iter::once(padding)),
+				);
+
+				Some(ParachainCandidate { block: candidate.block, proof })
+			},
+			Misbehavior::WithholdPov | Misbehavior::InvalidAnnounce => Some(candidate),
+		}
+	}
+}
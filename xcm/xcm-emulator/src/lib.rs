@@ -239,6 +239,9 @@ pub trait Chain: TestExt + NetworkComponent {
 	fn account_data_of(account: AccountId) -> AccountData<Balance>;
 
 	fn events() -> Vec<<Self as Chain>::RuntimeEvent>;
+
+	/// The location of the chain as seen from the relay chain of its network.
+	fn location_in_network() -> MultiLocation;
 }
 
 pub trait RelayChain: Chain {
@@ -264,6 +267,11 @@ pub trait Parachain: Chain {
 	type LocationToAccountId: ConvertLocation<AccountId>;
 	type ParachainInfo: Get<ParaId>;
 	type ParachainSystem;
+	/// The `pallet-message-queue`, into which the handlers enqueue the inbound DMP and HRMP
+	/// messages.
+	type MessageQueue: ServiceQueues;
+	/// The weight the `MessageQueue` may use for servicing messages in a block.
+	type MessageQueueServiceWeight: Get<Weight>;
 
 	fn para_id() -> ParaId {
 		Self::ext_wrapper(|| Self::ParachainInfo::get())
@@ -281,6 +289,14 @@ pub trait Parachain: Chain {
 		Self::LocationToAccountId::convert_location(&location).unwrap()
 	}
 
+	/// Services the queued inbound messages, as the `MessageQueue` does in a block.
+	///
+	/// Messages that don't fit into the service weight stay queued for the next block, overweight
+	/// messages are left for manual execution.
+	fn service_message_queue() -> Weight {
+		Self::MessageQueue::service_queues(Self::MessageQueueServiceWeight::get())
+	}
+
 	fn init();
 }
 
@@ -314,7 +330,8 @@ pub trait BridgeMessageHandler {
 		message: BridgeMessage,
 	) -> Result<(), BridgeMessageDispatchError>;
 
-	fn notify_source_message_delivery(lane_id: u32);
+	/// Confirms the delivery of the message `nonce` of the lane `lane_id` at the source.
+	fn notify_source_message_delivery(lane_id: u32, nonce: u64);
 }
 
 impl BridgeMessageHandler for () {
@@ -328,7 +345,7 @@ impl BridgeMessageHandler for () {
 		Err(BridgeMessageDispatchError(Box::new("Not a bridge")))
 	}
 
-	fn notify_source_message_delivery(_lane_id: u32) {}
+	fn notify_source_message_delivery(_lane_id: u32, _nonce: u64) {}
 }
 
 #[derive(Debug)]
@@ -385,6 +402,10 @@ macro_rules! decl_test_relay_chains {
 						.map(|record| record.event.clone())
 						.collect()
 				}
+
+				fn location_in_network() -> $crate::MultiLocation {
+					$crate::MultiLocation::here()
+				}
 			}
 
 			impl RelayChain for $name {
@@ -576,10 +597,9 @@ macro_rules! decl_test_parachains {
 				on_init = $on_init:expr,
 				runtime = $runtime:ident,
 				core = {
-					XcmpMessageHandler: $xcmp_message_handler:path,
-					DmpMessageHandler: $dmp_message_handler:path,
 					LocationToAccountId: $location_to_account:path,
 					ParachainInfo: $parachain_info:path,
+					MessageQueue: $message_queue:path,
 				},
 				pallets = {
 					$($pallet_name:ident: $pallet_path:path,)*
@@ -609,14 +629,25 @@ macro_rules! decl_test_parachains {
 						.map(|record| record.event.clone())
 						.collect()
 				}
+
+				fn location_in_network() -> $crate::MultiLocation {
+					(
+						$crate::Ancestor(0),
+						$crate::ParachainJunction(<Self as Parachain>::para_id().into()),
+					).into()
+				}
 			}
 
 			impl Parachain for $name {
-				type XcmpMessageHandler = $xcmp_message_handler;
-				type DmpMessageHandler = $dmp_message_handler;
+				// The handlers of the runtime, so that the messages are enqueued and serviced as
+				// on a live chain.
+				type XcmpMessageHandler = <<Self as Chain>::Runtime as $crate::cumulus_pallet_parachain_system::Config>::XcmpMessageHandler;
+				type DmpMessageHandler = <<Self as Chain>::Runtime as $crate::cumulus_pallet_parachain_system::Config>::DmpMessageHandler;
 				type LocationToAccountId = $location_to_account;
 				type ParachainSystem = $crate::ParachainSystemPallet<<Self as Chain>::Runtime>;
 				type ParachainInfo = $parachain_info;
+				type MessageQueue = $message_queue;
+				type MessageQueueServiceWeight = <<Self as Chain>::Runtime as $crate::MessageQueueConfig>::ServiceWeight;
 
 				fn init() {
 					use $crate::{Network, NetworkComponent, Hooks};
@@ -788,6 +819,9 @@ macro_rules! __impl_test_ext_for_parachain {
 							<Self as Chain>::RuntimeOrigin::none(),
 							<$name as NetworkComponent>::Network::hrmp_channel_parachain_inherent_data(para_id, relay_block_number, parent_head_data),
 						);
+
+						// Service the messages left over from previous blocks.
+						<Self as Parachain>::service_message_queue();
 					})
 				});
 
@@ -973,7 +1007,7 @@ macro_rules! decl_test_networks {
 				}
 
 				fn process_downward_messages() {
-					use $crate::{DmpMessageHandler, Bounded};
+					use $crate::DmpMessageHandler;
 					use polkadot_parachain::primitives::RelayChainBlockNumber;
 
 					while let Some((to_para_id, messages))
@@ -993,7 +1027,9 @@ macro_rules! decl_test_networks {
 								}).collect::<Vec<(RelayChainBlockNumber, Vec<u8>)>>();
 								if msgs.len() != 0 {
 									<$parachain>::ext_wrapper(|| {
-										<$parachain as Parachain>::DmpMessageHandler::handle_dmp_messages(msgs.clone().into_iter(), $crate::Weight::max_value());
+										let max_weight = <$parachain as Parachain>::ParachainSystem::reserved_dmp_weight();
+										<$parachain as Parachain>::DmpMessageHandler::handle_dmp_messages(msgs.clone().into_iter(), max_weight);
+										<$parachain as Parachain>::service_message_queue();
 									});
 									$crate::log::debug!(target: concat!("dmp::", stringify!($name)) , "DMP messages processed {:?} to para_id {:?}", msgs.clone(), &to_para_id);
 									for m in msgs {
@@ -1006,7 +1042,7 @@ macro_rules! decl_test_networks {
 				}
 
 				fn process_horizontal_messages() {
-					use $crate::{XcmpMessageHandler, Bounded};
+					use $crate::XcmpMessageHandler;
 
 					while let Some((to_para_id, messages))
 						= $crate::HORIZONTAL_MESSAGES.with(|b| b.borrow_mut().get_mut(Self::name()).unwrap().pop_front()) {
@@ -1016,7 +1052,9 @@ macro_rules! decl_test_networks {
 
 							if $crate::PARA_IDS.with(|b| b.borrow_mut().get_mut(Self::name()).unwrap().contains(&to_para_id)) && para_id == to_para_id {
 								<$parachain>::ext_wrapper(|| {
-									<$parachain as Parachain>::XcmpMessageHandler::handle_xcmp_messages(iter.clone(), $crate::Weight::max_value());
+									let max_weight = <$parachain as Parachain>::ParachainSystem::reserved_xcmp_weight();
+									<$parachain as Parachain>::XcmpMessageHandler::handle_xcmp_messages(iter.clone(), max_weight);
+									<$parachain as Parachain>::service_message_queue();
 								});
 								$crate::log::debug!(target: concat!("hrmp::", stringify!($name)) , "HRMP messages processed {:?} to para_id {:?}", &messages, &to_para_id);
 							}
@@ -1055,7 +1093,7 @@ macro_rules! decl_test_networks {
 							Err(e) => panic!("Error {:?} processing bridged message: {:?}", e, msg.clone()),
 							Ok(()) => {
 								<<Self::Bridge as $crate::Bridge>::Source as TestExt>::ext_wrapper(|| {
-									<<Self::Bridge as Bridge>::Handler as BridgeMessageHandler>::notify_source_message_delivery(msg.id);
+									<<Self::Bridge as Bridge>::Handler as BridgeMessageHandler>::notify_source_message_delivery(msg.id, msg.nonce);
 								});
 								$crate::log::debug!(target: concat!("bridge::", stringify!($name)) , "Bridged message processed {:?}", msg.clone());
 							}
@@ -1307,7 +1345,9 @@ where
 			msg.try_into().expect("Message too long"),
 			AggregateMessageOrigin::Ump(UmpQueueId::Para(para)),
 		);
-		MessageQueuePallet::<T::Runtime>::service_queues(Weight::MAX);
+		MessageQueuePallet::<T::Runtime>::service_queues(
+			<T::Runtime as MessageQueueConfig>::ServiceWeight::get(),
+		);
 
 		Ok(true)
 	}
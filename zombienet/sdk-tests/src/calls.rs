@@ -0,0 +1,113 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Calls dispatched at the chains of a network.
+
+use anyhow::{anyhow, ensure};
+use codec::Encode;
+use subxt::{tx::TxPayload, Metadata, OnlineClient, PolkadotConfig};
+use xcm::{VersionedMultiLocation, VersionedXcm};
+use zombienet_sdk::NetworkNode;
+
+/// A call, whose indices are looked up in the metadata of the chain it is dispatched at.
+///
+/// Used instead of the `subxt` codegen, so that the tests don't depend on the metadata of the
+/// runtimes at build time.
+pub struct RawCall {
+	pallet: &'static str,
+	call: &'static str,
+	args: Vec<u8>,
+}
+
+impl RawCall {
+	/// The call `pallet::call` with the SCALE encoded `args`.
+	pub fn new(pallet: &'static str, call: &'static str, args: impl Encode) -> Self {
+		Self { pallet, call, args: args.encode() }
+	}
+
+	/// Wraps `self` into `Sudo::sudo`.
+	pub fn sudo(self, metadata: &Metadata) -> Result<Self, subxt::Error> {
+		let mut call = Vec::new();
+		self.encode_call_data_to(metadata, &mut call)?;
+		Ok(Self { pallet: "Sudo", call: "sudo", args: call })
+	}
+}
+
+impl TxPayload for RawCall {
+	fn encode_call_data_to(
+		&self,
+		metadata: &Metadata,
+		out: &mut Vec<u8>,
+	) -> Result<(), subxt::Error> {
+		let pallet = metadata.pallet_by_name(self.pallet).ok_or_else(|| {
+			subxt::Error::Other(format!("Pallet `{}` not found in the metadata", self.pallet))
+		})?;
+		let call = pallet.call_variant_by_name(self.call).ok_or_else(|| {
+			subxt::Error::Other(format!("Call `{}::{}` not found", self.pallet, self.call))
+		})?;
+
+		out.push(pallet.index());
+		out.push(call.index);
+		out.extend_from_slice(&self.args);
+		Ok(())
+	}
+}
+
+/// Dispatches `call` with the root origin at `node`, signed by Alice as the sudo key.
+///
+/// Returns once the call is finalized and fails if it wasn't dispatched successfully.
+pub async fn submit_sudo_call(node: &NetworkNode, call: RawCall) -> anyhow::Result<()> {
+	let client = OnlineClient::<PolkadotConfig>::from_url(node.ws_uri()).await?;
+	let call = call.sudo(&client.metadata())?;
+
+	let events = client
+		.tx()
+		.sign_and_submit_then_watch_default(&call, &subxt_signer::sr25519::dev::alice())
+		.await?
+		.wait_for_finalized_success()
+		.await?;
+
+	let sudid = events
+		.iter()
+		.filter_map(Result::ok)
+		.find(|event| event.pallet_name() == "Sudo" && event.variant_name() == "Sudid")
+		.ok_or_else(|| anyhow!("{}: the sudo call wasn't dispatched", node.name()))?;
+	// `Sudid { sudo_result: DispatchResult }`, where `0` encodes `Ok`.
+	ensure!(
+		sudid.field_bytes().first() == Some(&0),
+		"{}: the sudo call failed: {:?}",
+		node.name(),
+		sudid.field_values()?,
+	);
+	Ok(())
+}
+
+/// Sends `message` to `dest` with the root origin of the chain of `node`.
+pub async fn send_xcm_as_root(
+	node: &NetworkNode,
+	dest: VersionedMultiLocation,
+	message: VersionedXcm<()>,
+) -> anyhow::Result<()> {
+	let client = OnlineClient::<PolkadotConfig>::from_url(node.ws_uri()).await?;
+	// The relay chains and the parachains name `pallet-xcm` differently.
+	let pallet = if client.metadata().pallet_by_name("XcmPallet").is_some() {
+		"XcmPallet"
+	} else {
+		"PolkadotXcm"
+	};
+
+	submit_sudo_call(node, RawCall::new(pallet, "send", (dest, message))).await
+}
@@ -0,0 +1,60 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! End-to-end tests of the parachains of Cumulus.
+//!
+//! The networks are launched with `zombienet-sdk` from native binaries, which are looked up in the
+//! `PATH`, unless set otherwise by the environment variables below. The parachains are registered
+//! at the genesis of the relay chain.
+//!
+//! The crate and its tests are only built with the `zombienet` feature, which pulls in their
+//! dependencies:
+//!
+//! ```sh
+//! cargo test -p cumulus-zombienet-tests --features zombienet
+//! ```
+
+#![cfg(feature = "zombienet")]
+
+mod calls;
+mod networks;
+mod relayer;
+mod wait;
+
+pub use calls::{send_xcm_as_root, submit_sudo_call, RawCall};
+pub use networks::{
+	bridge_hub_network, spawn, test_parachain_network, xcmp_network, BRIDGE_HUB_PARA_ID,
+	XCMP_PARA_IDS,
+};
+pub use relayer::Relayer;
+pub use wait::{wait_for_block_height, wait_for_event};
+
+/// The environment variable that overrides the `polkadot` binary.
+pub const POLKADOT_BIN: &str = "POLKADOT_BIN";
+
+/// The environment variable that overrides the `polkadot-parachain` binary.
+pub const POLKADOT_PARACHAIN_BIN: &str = "POLKADOT_PARACHAIN_BIN";
+
+/// The environment variable that overrides the `test-parachain` binary.
+pub const TEST_PARACHAIN_BIN: &str = "TEST_PARACHAIN_BIN";
+
+/// The environment variable that overrides the `substrate-relay` binary.
+pub const SUBSTRATE_RELAY_BIN: &str = "SUBSTRATE_RELAY_BIN";
+
+/// Returns the binary set by the environment variable `var`, or `default`.
+pub fn binary(var: &str, default: &str) -> String {
+	std::env::var(var).unwrap_or_else(|_| default.into())
+}
@@ -0,0 +1,160 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The networks the tests are run on.
+
+use crate::{binary, POLKADOT_BIN, POLKADOT_PARACHAIN_BIN, TEST_PARACHAIN_BIN};
+use anyhow::anyhow;
+use zombienet_sdk::{
+	LocalFileSystem, Network, NetworkConfig, NetworkConfigBuilder, NetworkConfigExt,
+};
+
+/// The ids of the parachains of the [`xcmp_network`].
+pub const XCMP_PARA_IDS: (u32, u32) = (2000, 2001);
+
+/// The id of the bridge hub of the [`bridge_hub_network`].
+pub const BRIDGE_HUB_PARA_ID: u32 = 1013;
+
+/// Spawns the network of `config` from the native binaries.
+pub async fn spawn(config: NetworkConfig) -> anyhow::Result<Network<LocalFileSystem>> {
+	config
+		.spawn_native()
+		.await
+		.map_err(|e| anyhow!("Failed to spawn the network: {e}"))
+}
+
+/// A `rococo-local` relay chain with the parachain `2000` of `test-parachain`, run by a collator
+/// and a full node.
+pub fn test_parachain_network() -> anyhow::Result<NetworkConfig> {
+	let test_parachain = binary(TEST_PARACHAIN_BIN, "test-parachain");
+
+	NetworkConfigBuilder::new()
+		.with_relaychain(|relaychain| {
+			relaychain
+				.with_chain("rococo-local")
+				.with_default_command(binary(POLKADOT_BIN, "polkadot").as_str())
+				.with_default_args(vec!["-lparachain=debug".into()])
+				.with_node(|node| node.with_name("alice"))
+				.with_node(|node| node.with_name("bob"))
+		})
+		.with_parachain(|parachain| {
+			parachain
+				.with_id(2000)
+				.cumulus_based(true)
+				.with_collator(|node| {
+					node.with_name("charlie")
+						.with_command(test_parachain.as_str())
+						.with_args(vec!["-lparachain=debug".into()])
+				})
+				.with_collator(|node| {
+					node.with_name("dave")
+						.validator(false)
+						.with_command(test_parachain.as_str())
+						.with_args(vec!["-lparachain=debug".into()])
+				})
+		})
+		.build()
+		.map_err(|errors| anyhow!("Invalid network configuration: {errors:?}"))
+}
+
+/// A `rococo-local` relay chain with the two parachains [`XCMP_PARA_IDS`] of the default
+/// `polkadot-parachain` runtime, connected by HRMP channels in both directions.
+pub fn xcmp_network() -> anyhow::Result<NetworkConfig> {
+	let polkadot_parachain = binary(POLKADOT_PARACHAIN_BIN, "polkadot-parachain");
+	let (a, b) = XCMP_PARA_IDS;
+
+	NetworkConfigBuilder::new()
+		.with_relaychain(|relaychain| {
+			relaychain
+				.with_chain("rococo-local")
+				.with_default_command(binary(POLKADOT_BIN, "polkadot").as_str())
+				.with_default_args(vec!["-lparachain=debug".into()])
+				.with_node(|node| node.with_name("alice"))
+				.with_node(|node| node.with_name("bob"))
+				.with_node(|node| node.with_name("charlie"))
+		})
+		.with_parachain(|parachain| {
+			parachain.with_id(a).cumulus_based(true).with_collator(|node| {
+				node.with_name("collator-a")
+					.with_command(polkadot_parachain.as_str())
+					.with_args(vec!["-lparachain=debug,xcm=trace".into()])
+			})
+		})
+		.with_parachain(|parachain| {
+			parachain.with_id(b).cumulus_based(true).with_collator(|node| {
+				node.with_name("collator-b")
+					.with_command(polkadot_parachain.as_str())
+					.with_args(vec!["-lparachain=debug,xcm=trace".into()])
+			})
+		})
+		.with_hrmp_channel(|channel| {
+			channel
+				.with_sender(a)
+				.with_recipient(b)
+				.with_max_capacity(8)
+				.with_max_message_size(512)
+		})
+		.with_hrmp_channel(|channel| {
+			channel
+				.with_sender(b)
+				.with_recipient(a)
+				.with_max_capacity(8)
+				.with_max_message_size(512)
+		})
+		.build()
+		.map_err(|errors| anyhow!("Invalid network configuration: {errors:?}"))
+}
+
+/// The relay chain `relay_chain`, i.e. `rococo-local` or `wococo-local`, with its bridge hub
+/// `bridge_hub`, i.e. `bridge-hub-rococo-local` or `bridge-hub-wococo-local`.
+///
+/// The names of the nodes are suffixed with `suffix`, to tell apart the nodes of both sides of a
+/// bridge.
+pub fn bridge_hub_network(
+	relay_chain: &str,
+	bridge_hub: &str,
+	suffix: &str,
+) -> anyhow::Result<NetworkConfig> {
+	let polkadot_parachain = binary(POLKADOT_PARACHAIN_BIN, "polkadot-parachain");
+
+	NetworkConfigBuilder::new()
+		.with_relaychain(|relaychain| {
+			relaychain
+				.with_chain(relay_chain)
+				.with_default_command(binary(POLKADOT_BIN, "polkadot").as_str())
+				.with_default_args(vec!["-lparachain=debug,xcm=trace".into()])
+				.with_node(|node| node.with_name(format!("alice-{suffix}").as_str()))
+				.with_node(|node| node.with_name(format!("bob-{suffix}").as_str()))
+				.with_node(|node| node.with_name(format!("charlie-{suffix}").as_str()))
+		})
+		.with_parachain(|parachain| {
+			parachain
+				.with_id(BRIDGE_HUB_PARA_ID)
+				.with_chain(bridge_hub)
+				.cumulus_based(true)
+				.with_collator(|node| {
+					node.with_name(format!("bridge-hub-{suffix}").as_str())
+						.with_command(polkadot_parachain.as_str())
+						.with_args(vec![
+							"-lparachain=debug,runtime::bridge-hub=trace,runtime::bridge=trace,bridge=trace,xcm=trace"
+								.into(),
+							"--force-authoring".into(),
+						])
+				})
+		})
+		.build()
+		.map_err(|errors| anyhow!("Invalid network configuration: {errors:?}"))
+}
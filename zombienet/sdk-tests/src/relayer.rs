@@ -0,0 +1,116 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The `substrate-relay` between the bridge hubs of Rococo and Wococo.
+//!
+//! Mirrors the `init-bridge` and `run-relay` commands of `scripts/bridges_rococo_wococo.sh`.
+
+use crate::{binary, SUBSTRATE_RELAY_BIN};
+use anyhow::{anyhow, ensure};
+use tokio::process::{Child, Command};
+use zombienet_sdk::NetworkNode;
+
+/// The lane of the messages between the bridge hubs.
+const LANE: &str = "00000001";
+
+/// A running `substrate-relay` that relays the headers and the messages between the bridge hubs,
+/// killed when dropped.
+pub struct Relayer {
+	_process: Child,
+}
+
+impl Relayer {
+	/// Initializes the bridges in both directions and starts relaying between them.
+	pub async fn rococo_wococo(
+		rococo: &NetworkNode,
+		bridge_hub_rococo: &NetworkNode,
+		wococo: &NetworkNode,
+		bridge_hub_wococo: &NetworkNode,
+	) -> anyhow::Result<Self> {
+		init_bridge("rococo-to-bridge-hub-wococo", rococo, bridge_hub_wococo).await?;
+		init_bridge("wococo-to-bridge-hub-rococo", wococo, bridge_hub_rococo).await?;
+
+		let (rococo_host, rococo_port) = host_and_port(rococo)?;
+		let (bridge_hub_rococo_host, bridge_hub_rococo_port) = host_and_port(bridge_hub_rococo)?;
+		let (wococo_host, wococo_port) = host_and_port(wococo)?;
+		let (bridge_hub_wococo_host, bridge_hub_wococo_port) = host_and_port(bridge_hub_wococo)?;
+
+		let process = Command::new(binary(SUBSTRATE_RELAY_BIN, "substrate-relay"))
+			.env("RUST_LOG", "runtime=trace,rpc=trace,bridge=trace")
+			.args(["relay-headers-and-messages", "bridge-hub-rococo-bridge-hub-wococo"])
+			.args(["--rococo-host", &rococo_host, "--rococo-port", &rococo_port])
+			.args(["--rococo-version-mode", "Auto"])
+			.args(["--bridge-hub-rococo-host", &bridge_hub_rococo_host])
+			.args(["--bridge-hub-rococo-port", &bridge_hub_rococo_port])
+			.args(["--bridge-hub-rococo-version-mode", "Auto"])
+			.args(["--bridge-hub-rococo-signer", "//Charlie"])
+			.args(["--wococo-headers-to-bridge-hub-rococo-signer", "//Bob"])
+			.args(["--wococo-parachains-to-bridge-hub-rococo-signer", "//Bob"])
+			.args(["--bridge-hub-rococo-transactions-mortality", "4"])
+			.args(["--wococo-host", &wococo_host, "--wococo-port", &wococo_port])
+			.args(["--wococo-version-mode", "Auto"])
+			.args(["--bridge-hub-wococo-host", &bridge_hub_wococo_host])
+			.args(["--bridge-hub-wococo-port", &bridge_hub_wococo_port])
+			.args(["--bridge-hub-wococo-version-mode", "Auto"])
+			.args(["--bridge-hub-wococo-signer", "//Charlie"])
+			.args(["--rococo-headers-to-bridge-hub-wococo-signer", "//Bob"])
+			.args(["--rococo-parachains-to-bridge-hub-wococo-signer", "//Bob"])
+			.args(["--bridge-hub-wococo-transactions-mortality", "4"])
+			.args(["--lane", LANE])
+			.kill_on_drop(true)
+			.spawn()
+			.map_err(|e| anyhow!("Failed to start `substrate-relay`: {e}"))?;
+
+		Ok(Self { _process: process })
+	}
+}
+
+/// Initializes the bridge `bridge`, i.e. the light client of `source` at `target`.
+async fn init_bridge(
+	bridge: &str,
+	source: &NetworkNode,
+	target: &NetworkNode,
+) -> anyhow::Result<()> {
+	let (source_host, source_port) = host_and_port(source)?;
+	let (target_host, target_port) = host_and_port(target)?;
+
+	let status = Command::new(binary(SUBSTRATE_RELAY_BIN, "substrate-relay"))
+		.env("RUST_LOG", "runtime=trace,rpc=trace,bridge=trace")
+		.args(["init-bridge", bridge])
+		.args(["--source-host", &source_host, "--source-port", &source_port])
+		.args(["--source-version-mode", "Auto"])
+		.args(["--target-host", &target_host, "--target-port", &target_port])
+		.args(["--target-version-mode", "Auto"])
+		.args(["--target-signer", "//Bob"])
+		.status()
+		.await
+		.map_err(|e| anyhow!("Failed to run `substrate-relay`: {e}"))?;
+
+	ensure!(status.success(), "Failed to initialize the bridge {bridge}: {status}");
+	Ok(())
+}
+
+/// The host and the port of the websocket RPC of `node`.
+fn host_and_port(node: &NetworkNode) -> anyhow::Result<(String, String)> {
+	let uri = node.ws_uri();
+	let address = uri.split_once("://").map_or(uri, |(_, address)| address);
+	let (host, port) = address
+		.trim_end_matches('/')
+		.rsplit_once(':')
+		.ok_or_else(|| anyhow!("{}: no port in the websocket uri {uri}", node.name()))?;
+
+	Ok((host.to_string(), port.to_string()))
+}
@@ -0,0 +1,89 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Waiting for the chains of a network to reach some state.
+
+use anyhow::anyhow;
+use futures::StreamExt;
+use std::time::Duration;
+use subxt::{OnlineClient, PolkadotConfig};
+use zombienet_sdk::NetworkNode;
+
+/// The metric of the best block of a node.
+const BEST_BLOCK_METRIC: &str = "block_height{status=\"best\"}";
+
+/// How often the metrics of a node are polled.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Waits for the best block of `node` to be at least at `height`, for at most `timeout`.
+pub async fn wait_for_block_height(
+	node: &NetworkNode,
+	height: u64,
+	timeout: Duration,
+) -> anyhow::Result<()> {
+	let wait = async {
+		loop {
+			// The metrics are only available once the node is up.
+			if let Ok(best) = node.reports(BEST_BLOCK_METRIC).await {
+				if best >= height as f64 {
+					return
+				}
+			}
+			tokio::time::sleep(POLL_INTERVAL).await;
+		}
+	};
+
+	tokio::time::timeout(timeout, wait).await.map_err(|_| {
+		anyhow!("{}: block height {height} not reached within {timeout:?}", node.name())
+	})
+}
+
+/// Waits for an event `pallet::variant` of one of `variants` to be emitted in a best block of
+/// `node`, for at most `timeout`.
+///
+/// Returns the variant of the event.
+pub async fn wait_for_event(
+	node: &NetworkNode,
+	pallet: &str,
+	variants: &[&str],
+	timeout: Duration,
+) -> anyhow::Result<String> {
+	let client = OnlineClient::<PolkadotConfig>::from_url(node.ws_uri()).await?;
+	let mut blocks = client.blocks().subscribe_best().await?;
+
+	let wait = async {
+		while let Some(block) = blocks.next().await {
+			let events = block?.events().await?;
+			for event in events.iter() {
+				let event = event?;
+				if event.pallet_name() == pallet && variants.contains(&event.variant_name()) {
+					tracing::info!(
+						node = node.name(),
+						pallet,
+						variant = event.variant_name(),
+						"Found event"
+					);
+					return Ok(event.variant_name().to_string())
+				}
+			}
+		}
+		Err(anyhow!("{}: the subscription to the best blocks ended", node.name()))
+	};
+
+	tokio::time::timeout(timeout, wait).await.map_err(|_| {
+		anyhow!("{}: no event {pallet}::{variants:?} within {timeout:?}", node.name())
+	})?
+}
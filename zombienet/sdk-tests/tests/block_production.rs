@@ -0,0 +1,35 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The parachains of a network produce blocks.
+
+#![cfg(feature = "zombienet")]
+
+use cumulus_zombienet_tests::{spawn, test_parachain_network, wait_for_block_height};
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_parachain_produces_blocks() -> anyhow::Result<()> {
+	let network = spawn(test_parachain_network()?).await?;
+
+	wait_for_block_height(network.get_node("alice")?, 5, Duration::from_secs(250)).await?;
+
+	// The collator and the full node follow the parachain.
+	wait_for_block_height(network.get_node("charlie")?, 10, Duration::from_secs(300)).await?;
+	wait_for_block_height(network.get_node("dave")?, 10, Duration::from_secs(300)).await?;
+
+	Ok(())
+}
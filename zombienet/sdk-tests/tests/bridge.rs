@@ -0,0 +1,87 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Messages are relayed between the bridge hubs of Rococo and Wococo.
+//!
+//! Requires `substrate-relay` besides the binaries of the other tests.
+
+#![cfg(feature = "zombienet")]
+
+use cumulus_zombienet_tests::{
+	bridge_hub_network, send_xcm_as_root, spawn, wait_for_block_height, wait_for_event, Relayer,
+	BRIDGE_HUB_PARA_ID,
+};
+use std::time::Duration;
+use xcm::{v3::prelude::*, VersionedMultiLocation, VersionedXcm};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn message_is_relayed_from_rococo_to_wococo() -> anyhow::Result<()> {
+	let (rococo, wococo) = futures::try_join!(
+		spawn(bridge_hub_network("rococo-local", "bridge-hub-rococo-local", "rococo")?),
+		spawn(bridge_hub_network("wococo-local", "bridge-hub-wococo-local", "wococo")?),
+	)?;
+	let relay_rococo = rococo.get_node("alice-rococo")?;
+	let bridge_hub_rococo = rococo.get_node("bridge-hub-rococo")?;
+	let relay_wococo = wococo.get_node("alice-wococo")?;
+	let bridge_hub_wococo = wococo.get_node("bridge-hub-wococo")?;
+
+	wait_for_block_height(bridge_hub_rococo, 3, Duration::from_secs(300)).await?;
+	wait_for_block_height(bridge_hub_wococo, 3, Duration::from_secs(300)).await?;
+
+	let _relayer =
+		Relayer::rococo_wococo(relay_rococo, bridge_hub_rococo, relay_wococo, bridge_hub_wococo)
+			.await?;
+
+	// Rococo asks its bridge hub to export a message to the bridge hub of Wococo.
+	send_xcm_as_root(
+		relay_rococo,
+		VersionedMultiLocation::V3(MultiLocation::new(0, X1(Parachain(BRIDGE_HUB_PARA_ID)))),
+		VersionedXcm::V3(Xcm(vec![
+			UnpaidExecution { weight_limit: Unlimited, check_origin: None },
+			ExportMessage {
+				network: Wococo,
+				destination: X1(Parachain(BRIDGE_HUB_PARA_ID)),
+				xcm: Xcm(vec![ClearOrigin]),
+			},
+		])),
+	)
+	.await?;
+
+	wait_for_event(
+		bridge_hub_rococo,
+		"BridgeWococoMessages",
+		&["MessageAccepted"],
+		Duration::from_secs(120),
+	)
+	.await?;
+	wait_for_event(
+		bridge_hub_wococo,
+		"BridgeRococoMessages",
+		&["MessagesReceived"],
+		Duration::from_secs(600),
+	)
+	.await?;
+	// The delivery is confirmed back at the source.
+	wait_for_event(
+		bridge_hub_rococo,
+		"BridgeWococoMessages",
+		&["MessagesDelivered"],
+		Duration::from_secs(600),
+	)
+	.await?;
+
+	Ok(())
+}
@@ -0,0 +1,53 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! XCM is delivered between sibling parachains over HRMP channels.
+
+#![cfg(feature = "zombienet")]
+
+use cumulus_zombienet_tests::{
+	send_xcm_as_root, spawn, wait_for_block_height, wait_for_event, xcmp_network, XCMP_PARA_IDS,
+};
+use std::time::Duration;
+use xcm::{v3::prelude::*, VersionedMultiLocation, VersionedXcm};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn xcm_is_delivered_to_sibling() -> anyhow::Result<()> {
+	let network = spawn(xcmp_network()?).await?;
+	let (_, b) = XCMP_PARA_IDS;
+	let collator_a = network.get_node("collator-a")?;
+	let collator_b = network.get_node("collator-b")?;
+
+	wait_for_block_height(collator_a, 3, Duration::from_secs(300)).await?;
+	wait_for_block_height(collator_b, 3, Duration::from_secs(300)).await?;
+
+	send_xcm_as_root(
+		collator_a,
+		VersionedMultiLocation::V3(MultiLocation::new(1, X1(Parachain(b)))),
+		VersionedXcm::V3(Xcm(vec![
+			UnpaidExecution { weight_limit: Unlimited, check_origin: None },
+			ClearOrigin,
+		])),
+	)
+	.await?;
+
+	wait_for_event(collator_a, "XcmpQueue", &["XcmpMessageSent"], Duration::from_secs(60)).await?;
+	// Whether the XCM is executed depends on the barrier of the recipient, both mean that the
+	// message was delivered.
+	wait_for_event(collator_b, "XcmpQueue", &["Success", "Fail"], Duration::from_secs(120)).await?;
+
+	Ok(())
+}
@@ -0,0 +1,32 @@
+const assert = require("assert");
+
+// Waits until the runtime of the parachain changed `args[0]` times, alternating between the two
+// spec versions of the `upgrade-torture` runtime.
+async function run(nodeName, networkInfo, args) {
+    const {wsUri, userDefinedTypes} = networkInfo.nodesByName[nodeName];
+    const api = await zombie.connect(wsUri, userDefinedTypes);
+    const expectedUpgrades = parseInt(args[0], 10);
+
+    const versions = [];
+    await new Promise(async (resolve) => {
+        const unsubscribe = await api.rpc.state.subscribeRuntimeVersion((version) => {
+            const specVersion = parseInt(version.specVersion.toHuman().replace(/,/g, ""), 10);
+            console.log("spec version", specVersion);
+            if (versions.length === 0 || versions[versions.length - 1] !== specVersion) {
+                versions.push(specVersion);
+            }
+            // The initial version is not an upgrade.
+            if (versions.length > expectedUpgrades) {
+                unsubscribe();
+                resolve();
+            }
+        });
+    });
+
+    for (let i = 2; i < versions.length; i++) {
+        assert.equal(versions[i], versions[i - 2], "Upgrades should alternate between two codes");
+    }
+    assert.notEqual(versions[0], versions[1], "Upgrades should change the spec version");
+}
+
+module.exports = { run }